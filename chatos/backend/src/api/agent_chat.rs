@@ -1,6 +1,10 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
+#[path = "agent_chat/edit_sessions.rs"]
+mod edit_sessions;
+#[path = "agent_chat/mcp_prompts.rs"]
+mod mcp_prompts;
 mod task_runner_callback;
 #[path = "agent_chat/tools_panel.rs"]
 mod tools_panel;
@@ -15,6 +19,8 @@ use serde::Deserialize;
 use serde_json::{json, Value};
 use uuid::Uuid;
 
+use self::edit_sessions::{list_edit_sessions, revert_edit_session};
+use self::mcp_prompts::{list_mcp_prompts, render_mcp_prompt};
 use self::task_runner_callback::task_runner_callback;
 use self::tools_panel::{agent_status, agent_tools};
 use crate::api::chat_stream_common::{validate_chat_stream_request, ChatStreamRequest};
@@ -55,6 +61,22 @@ pub fn router() -> Router {
             "/api/agent/conversation/{conversation_id}/reset",
             post(reset_conversation),
         )
+        .route(
+            "/api/agent/conversation/{conversation_id}/mcp-prompts",
+            get(list_mcp_prompts),
+        )
+        .route(
+            "/api/agent/conversation/{conversation_id}/mcp-prompts/render",
+            post(render_mcp_prompt),
+        )
+        .route(
+            "/api/agent/conversation/{conversation_id}/edit-sessions",
+            get(list_edit_sessions),
+        )
+        .route(
+            "/api/agent/conversation/{conversation_id}/edit-sessions/{session_id}/revert",
+            post(revert_edit_session),
+        )
 }
 
 pub fn internal_router() -> Router {
@@ -0,0 +1,71 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use axum::http::StatusCode;
+use axum::{
+    extract::{Path, Query},
+    Json,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::core::auth::AuthUser;
+use crate::core::session_access::{ensure_owned_session, map_session_access_error};
+use crate::modules::conversation_runtime::edit_sessions::{
+    list_conversation_edit_sessions, revert_conversation_edit_session,
+};
+
+#[derive(Debug, Deserialize)]
+pub(super) struct ListEditSessionsQuery {
+    limit: Option<usize>,
+}
+
+pub(super) async fn list_edit_sessions(
+    auth: AuthUser,
+    Path(conversation_id): Path<String>,
+    Query(query): Query<ListEditSessionsQuery>,
+) -> (StatusCode, Json<Value>) {
+    let session = match ensure_owned_session(&conversation_id, &auth).await {
+        Ok(session) => session,
+        Err(err) => return map_session_access_error(err),
+    };
+    match list_conversation_edit_sessions(
+        &session,
+        auth.user_id.as_str(),
+        auth.role.as_str(),
+        query.limit,
+    )
+    .await
+    {
+        Ok(payload) => (StatusCode::OK, Json(payload)),
+        Err(err) => (StatusCode::BAD_GATEWAY, Json(json!({ "error": err }))),
+    }
+}
+
+pub(super) async fn revert_edit_session(
+    auth: AuthUser,
+    Path((conversation_id, edit_session_id)): Path<(String, String)>,
+) -> (StatusCode, Json<Value>) {
+    let edit_session_id = edit_session_id.trim();
+    if edit_session_id.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "session_id 不能为空" })),
+        );
+    }
+    let session = match ensure_owned_session(&conversation_id, &auth).await {
+        Ok(session) => session,
+        Err(err) => return map_session_access_error(err),
+    };
+    match revert_conversation_edit_session(
+        &session,
+        auth.user_id.as_str(),
+        auth.role.as_str(),
+        edit_session_id,
+    )
+    .await
+    {
+        Ok(payload) => (StatusCode::OK, Json(payload)),
+        Err(err) => (StatusCode::CONFLICT, Json(json!({ "error": err }))),
+    }
+}
@@ -0,0 +1,67 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use axum::http::StatusCode;
+use axum::{extract::Path, Json};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::core::auth::AuthUser;
+use crate::core::session_access::{ensure_owned_session, map_session_access_error};
+use crate::modules::conversation_runtime::mcp_prompts::{
+    list_conversation_mcp_prompts, render_conversation_mcp_prompt,
+};
+
+#[derive(Debug, Deserialize)]
+pub(super) struct RenderMcpPromptRequest {
+    server: Option<String>,
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Map<String, Value>,
+}
+
+pub(super) async fn list_mcp_prompts(
+    auth: AuthUser,
+    Path(conversation_id): Path<String>,
+) -> (StatusCode, Json<Value>) {
+    let session = match ensure_owned_session(&conversation_id, &auth).await {
+        Ok(session) => session,
+        Err(err) => return map_session_access_error(err),
+    };
+    match list_conversation_mcp_prompts(&session, auth.user_id.as_str(), auth.role.as_str()).await {
+        Ok(prompts) => (StatusCode::OK, Json(json!({ "prompts": prompts }))),
+        Err(err) => (StatusCode::BAD_GATEWAY, Json(json!({ "error": err }))),
+    }
+}
+
+pub(super) async fn render_mcp_prompt(
+    auth: AuthUser,
+    Path(conversation_id): Path<String>,
+    Json(req): Json<RenderMcpPromptRequest>,
+) -> (StatusCode, Json<Value>) {
+    let server = req.server.as_deref().map(str::trim).unwrap_or_default();
+    let name = req.name.as_deref().map(str::trim).unwrap_or_default();
+    if server.is_empty() || name.is_empty() {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({ "error": "server 和 name 不能为空" })),
+        );
+    }
+    let session = match ensure_owned_session(&conversation_id, &auth).await {
+        Ok(session) => session,
+        Err(err) => return map_session_access_error(err),
+    };
+    match render_conversation_mcp_prompt(
+        &session,
+        auth.user_id.as_str(),
+        auth.role.as_str(),
+        server,
+        name,
+        req.arguments,
+    )
+    .await
+    {
+        Ok(rendered) => (StatusCode::OK, Json(json!(rendered))),
+        Err(err) => (StatusCode::BAD_GATEWAY, Json(json!({ "error": err }))),
+    }
+}
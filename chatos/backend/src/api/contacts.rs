@@ -14,6 +14,7 @@ use crate::core::auth::AuthUser;
 use crate::core::user_scope::resolve_user_id;
 use crate::models::memory_mapping_types::{
     CreateMemoryContactRequestDto, UpdateContactTaskRunnerConfigRequestDto,
+    UpdateMemoryAgentRecallRequestDto,
 };
 use crate::services::chatos_memory_mappings;
 use crate::services::realtime::publish_contacts_updated;
@@ -65,6 +66,18 @@ pub fn router() -> Router {
             "/api/contacts/{contact_id}/agent-recalls",
             get(list_contact_agent_recalls),
         )
+        .route(
+            "/api/contacts/{contact_id}/agent-recalls/{recall_id}",
+            patch(update_contact_agent_recall),
+        )
+        .route(
+            "/api/contacts/{contact_id}/agent-recalls/{recall_id}/versions",
+            get(list_contact_agent_recall_versions),
+        )
+        .route(
+            "/api/contacts/{contact_id}/agent-recalls/{recall_id}/provenance",
+            get(get_contact_agent_recall_provenance),
+        )
 }
 
 async fn list_contacts(
@@ -316,3 +329,86 @@ async fn list_contact_agent_recalls(
         ),
     }
 }
+
+async fn update_contact_agent_recall(
+    auth: AuthUser,
+    Path((contact_id, recall_id)): Path<(String, String)>,
+    Json(req): Json<UpdateMemoryAgentRecallRequestDto>,
+) -> (StatusCode, Json<Value>) {
+    if let Err(err) = ensure_contact_owner(&auth, contact_id.as_str()).await {
+        return err;
+    }
+    match chatos_memory_mappings::update_contact_agent_recall(
+        contact_id.as_str(),
+        recall_id.as_str(),
+        &req,
+    )
+    .await
+    {
+        Ok(item) => (StatusCode::OK, Json(json!(item))),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": "update contact agent recall failed", "detail": err})),
+        ),
+    }
+}
+
+async fn list_contact_agent_recall_versions(
+    auth: AuthUser,
+    Path((contact_id, recall_id)): Path<(String, String)>,
+) -> (StatusCode, Json<Value>) {
+    if let Err(err) = ensure_contact_owner(&auth, contact_id.as_str()).await {
+        return err;
+    }
+    match chatos_memory_mappings::list_contact_agent_recall_versions(
+        contact_id.as_str(),
+        recall_id.as_str(),
+    )
+    .await
+    {
+        Ok(items) => (StatusCode::OK, Json(json!(items))),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": "list contact agent recall versions failed", "detail": err})),
+        ),
+    }
+}
+
+async fn get_contact_agent_recall_provenance(
+    auth: AuthUser,
+    Path((contact_id, recall_id)): Path<(String, String)>,
+) -> (StatusCode, Json<Value>) {
+    if let Err(err) = ensure_contact_owner(&auth, contact_id.as_str()).await {
+        return err;
+    }
+    match chatos_memory_mappings::get_contact_agent_recall_provenance(
+        contact_id.as_str(),
+        recall_id.as_str(),
+    )
+    .await
+    {
+        Ok(item) => (StatusCode::OK, Json(json!(item))),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": "get contact agent recall provenance failed", "detail": err})),
+        ),
+    }
+}
+
+async fn ensure_contact_owner(
+    auth: &AuthUser,
+    contact_id: &str,
+) -> Result<(), (StatusCode, Json<Value>)> {
+    match chatos_memory_mappings::get_memory_contact(contact_id).await {
+        Ok(Some(contact)) if contact.user_id == auth.user_id => Ok(()),
+        Ok(Some(_)) => Err((StatusCode::FORBIDDEN, Json(json!({"error": "forbidden"})))),
+        Ok(None) => Err((
+            StatusCode::NOT_FOUND,
+            Json(json!({"error": "contact not found"})),
+        )),
+        Err(err) => Err((
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": "get contact failed", "detail": err})),
+        )),
+    }
+}
@@ -20,9 +20,11 @@ use crate::models::project::{
 };
 use crate::services::git;
 use crate::services::git::{
-    GitActionResult, GitCheckoutRequest, GitCommitRequest, GitCompareQuery, GitCreateBranchRequest,
-    GitDiffQuery, GitFetchRequest, GitMergeRequest, GitPathRequest, GitPullRequest, GitPushRequest,
-    GitRepositoryCandidate, GitRootQuery, GitSummary,
+    GitActionResult, GitBlameQuery, GitCheckoutRequest, GitCherryPickRequest, GitCommitRequest,
+    GitCompareQuery, GitConflictsQuery, GitCreateBranchRequest, GitDiffQuery, GitFetchRequest,
+    GitLogQuery, GitMergeRequest, GitOperationRequest, GitPathRequest, GitPullRequest,
+    GitPushRequest, GitRebaseRequest, GitRepositoryCandidate, GitResolveConflictRequest,
+    GitRootQuery, GitShowQuery, GitStashRequest, GitStashSaveRequest, GitSummary,
 };
 use crate::services::{access_token_scope, project_management_api_client};
 
@@ -34,6 +36,11 @@ pub fn router() -> Router {
         .route("/api/git/status", get(status))
         .route("/api/git/compare", get(compare))
         .route("/api/git/diff", get(diff))
+        .route("/api/git/log", get(log))
+        .route("/api/git/show", get(show_commit))
+        .route("/api/git/blame", get(blame))
+        .route("/api/git/stashes", get(stash_list))
+        .route("/api/git/conflicts", get(conflicts))
         .route("/api/git/fetch", post(fetch))
         .route("/api/git/pull", post(pull))
         .route("/api/git/push", post(push))
@@ -44,6 +51,14 @@ pub fn router() -> Router {
         .route("/api/git/unstage", post(unstage))
         .route("/api/git/discard", post(discard))
         .route("/api/git/commit", post(commit))
+        .route("/api/git/stash", post(stash_save))
+        .route("/api/git/stash/apply", post(stash_apply))
+        .route("/api/git/stash/drop", post(stash_drop))
+        .route("/api/git/conflicts/resolve", post(resolve_conflict))
+        .route("/api/git/operation/continue", post(continue_operation))
+        .route("/api/git/operation/abort", post(abort_operation))
+        .route("/api/git/rebase", post(rebase))
+        .route("/api/git/cherry-pick", post(cherry_pick))
 }
 
 async fn client() -> (StatusCode, Json<Value>) {
@@ -165,6 +180,75 @@ async fn diff(auth: AuthUser, Query(mut query): Query<GitDiffQuery>) -> (StatusC
     }
 }
 
+async fn log(auth: AuthUser, Query(mut query): Query<GitLogQuery>) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    query.root = match authorize_git_root(&policy, query.root.as_str(), false) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::log(query).await {
+        Ok(response) => (StatusCode::OK, Json(json!(response))),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn show_commit(
+    auth: AuthUser,
+    Query(mut query): Query<GitShowQuery>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    query.root = match authorize_git_root(&policy, query.root.as_str(), false) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::show_commit(query).await {
+        Ok(response) => (StatusCode::OK, Json(json!(response))),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn blame(
+    auth: AuthUser,
+    Query(mut query): Query<GitBlameQuery>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    query.root = match authorize_git_root(&policy, query.root.as_str(), false) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::blame(query).await {
+        Ok(response) => (StatusCode::OK, Json(json!(response))),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn stash_list(
+    auth: AuthUser,
+    Query(query): Query<GitRootQuery>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    let root = match authorize_git_root(&policy, query.root.as_str(), false) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::stash_list(root.as_str()).await {
+        Ok(response) => (StatusCode::OK, Json(json!(response))),
+        Err(message) => error_response(message),
+    }
+}
+
 async fn fetch(
     auth: AuthUser,
     Json(mut request): Json<GitFetchRequest>,
@@ -375,6 +459,192 @@ async fn discard(
     }
 }
 
+async fn stash_save(
+    auth: AuthUser,
+    Json(mut request): Json<GitStashSaveRequest>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    request.root = match authorize_git_root(&policy, request.root.as_str(), true) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::stash_save(request).await {
+        Ok(response) => (
+            StatusCode::OK,
+            Json(json!(visible_git_action_result(&policy, response))),
+        ),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn stash_apply(
+    auth: AuthUser,
+    Json(mut request): Json<GitStashRequest>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    request.root = match authorize_git_root(&policy, request.root.as_str(), true) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::stash_apply(request).await {
+        Ok(response) => (
+            StatusCode::OK,
+            Json(json!(visible_git_action_result(&policy, response))),
+        ),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn stash_drop(
+    auth: AuthUser,
+    Json(mut request): Json<GitStashRequest>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    request.root = match authorize_git_root(&policy, request.root.as_str(), true) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::stash_drop(request).await {
+        Ok(response) => (
+            StatusCode::OK,
+            Json(json!(visible_git_action_result(&policy, response))),
+        ),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn conflicts(
+    auth: AuthUser,
+    Query(mut query): Query<GitConflictsQuery>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    query.root = match authorize_git_root(&policy, query.root.as_str(), false) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::conflicts(query).await {
+        Ok(response) => (StatusCode::OK, Json(json!(response))),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn resolve_conflict(
+    auth: AuthUser,
+    Json(mut request): Json<GitResolveConflictRequest>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    request.root = match authorize_git_root(&policy, request.root.as_str(), true) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::resolve_conflict(request).await {
+        Ok(response) => (
+            StatusCode::OK,
+            Json(json!(visible_git_action_result(&policy, response))),
+        ),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn continue_operation(
+    auth: AuthUser,
+    Json(mut request): Json<GitOperationRequest>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    request.root = match authorize_git_root(&policy, request.root.as_str(), true) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::continue_operation(request).await {
+        Ok(response) => (
+            StatusCode::OK,
+            Json(json!(visible_git_action_result(&policy, response))),
+        ),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn abort_operation(
+    auth: AuthUser,
+    Json(mut request): Json<GitOperationRequest>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    request.root = match authorize_git_root(&policy, request.root.as_str(), true) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::abort_operation(request).await {
+        Ok(response) => (
+            StatusCode::OK,
+            Json(json!(visible_git_action_result(&policy, response))),
+        ),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn rebase(
+    auth: AuthUser,
+    Json(mut request): Json<GitRebaseRequest>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    request.root = match authorize_git_root(&policy, request.root.as_str(), true) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::rebase(request).await {
+        Ok(response) => (
+            StatusCode::OK,
+            Json(json!(visible_git_action_result(&policy, response))),
+        ),
+        Err(message) => error_response(message),
+    }
+}
+
+async fn cherry_pick(
+    auth: AuthUser,
+    Json(mut request): Json<GitCherryPickRequest>,
+) -> (StatusCode, Json<Value>) {
+    let policy = match git_path_policy(&auth).await {
+        Ok(policy) => policy,
+        Err(err) => return err,
+    };
+    request.root = match authorize_git_root(&policy, request.root.as_str(), true) {
+        Ok(root) => root,
+        Err(err) => return err,
+    };
+    match git::cherry_pick(request).await {
+        Ok(response) => (
+            StatusCode::OK,
+            Json(json!(visible_git_action_result(&policy, response))),
+        ),
+        Err(message) => error_response(message),
+    }
+}
+
 async fn resolve_harness_project_id_for_git_root(
     auth: &AuthUser,
     root: &str,
@@ -114,6 +114,32 @@ pub struct MemoryAgentRecallDto {
     pub confidence: Option<f64>,
     pub last_seen_at: Option<String>,
     pub updated_at: String,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    #[serde(default)]
+    pub version: i64,
+    #[serde(default)]
+    pub edited_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UpdateMemoryAgentRecallRequestDto {
+    #[serde(default)]
+    pub recall_text: Option<String>,
+    #[serde(default)]
+    pub pinned: Option<bool>,
+    #[serde(default)]
+    pub expires_at: Option<String>,
+    #[serde(default)]
+    pub ttl_seconds: Option<i64>,
+    #[serde(default)]
+    pub clear_expiry: bool,
+    #[serde(default)]
+    pub expected_version: Option<i64>,
+    #[serde(default)]
+    pub reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
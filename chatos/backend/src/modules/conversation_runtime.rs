@@ -13,8 +13,12 @@ pub mod chat_usecase;
 pub mod cloud_agent;
 #[path = "conversation_runtime/context_history.rs"]
 pub mod context_history;
+#[path = "conversation_runtime/edit_sessions.rs"]
+pub mod edit_sessions;
 #[path = "conversation_runtime/guidance.rs"]
 pub mod guidance;
+#[path = "conversation_runtime/mcp_prompts.rs"]
+pub mod mcp_prompts;
 #[path = "conversation_runtime/memory_compat.rs"]
 pub mod memory_compat;
 #[path = "conversation_runtime/messages.rs"]
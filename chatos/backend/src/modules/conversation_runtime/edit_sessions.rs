@@ -0,0 +1,128 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::HashMap;
+
+use chatos_mcp_runtime::{ToolCallContext, ToolInfo};
+use serde_json::{json, Value};
+
+use crate::models::session::Session;
+use crate::modules::conversation_runtime::mcp_prompts::close_runtime_session;
+use crate::modules::conversation_runtime::runtime_context::open_conversation_mcp_gateway;
+use crate::services::shared_mcp_runtime::build_shared_mcp_executor;
+
+const LIST_EDIT_SESSIONS_TOOL: &str = "list_edit_sessions";
+const REVERT_EDIT_SESSION_TOOL: &str = "revert_edit_session";
+
+/// Committed code-maintainer edit sessions of the conversation, newest first.
+pub async fn list_conversation_edit_sessions(
+    session: &Session,
+    owner_user_id: &str,
+    owner_role: &str,
+    limit: Option<usize>,
+) -> Result<Value, String> {
+    let mut args = json!({ "scope": "conversation" });
+    if let Some(limit) = limit {
+        args["limit"] = json!(limit);
+    }
+    call_code_maintainer_tool(
+        session,
+        owner_user_id,
+        owner_role,
+        LIST_EDIT_SESSIONS_TOOL,
+        args,
+    )
+    .await
+}
+
+/// Reverts one committed edit session through the conversation's
+/// code-maintainer write tools, so the user gets the same checks the agent
+/// would: the revert is refused when a path changed after the commit.
+pub async fn revert_conversation_edit_session(
+    session: &Session,
+    owner_user_id: &str,
+    owner_role: &str,
+    edit_session_id: &str,
+) -> Result<Value, String> {
+    call_code_maintainer_tool(
+        session,
+        owner_user_id,
+        owner_role,
+        REVERT_EDIT_SESSION_TOOL,
+        json!({ "session_id": edit_session_id }),
+    )
+    .await
+}
+
+async fn call_code_maintainer_tool(
+    session: &Session,
+    owner_user_id: &str,
+    owner_role: &str,
+    tool: &str,
+    args: Value,
+) -> Result<Value, String> {
+    let gateway = open_conversation_mcp_gateway(session, owner_user_id, owner_role).await?;
+    let mut executor = build_shared_mcp_executor(vec![gateway.server], Vec::new(), Vec::new());
+    let result = match executor.init().await {
+        Ok(()) => match exposed_tool_name(executor.tool_metadata(), tool) {
+            Some(name) => {
+                let call = json!({
+                    "id": format!("user-{tool}-{}", uuid::Uuid::new_v4()),
+                    "type": "function",
+                    "function": { "name": name, "arguments": args.to_string() }
+                });
+                executor
+                    .execute_tools_stream(
+                        &[call],
+                        ToolCallContext::new(Some(session.id.clone()), None, None),
+                        None,
+                    )
+                    .await
+                    .into_iter()
+                    .find(|result| !result.is_stream)
+                    .ok_or_else(|| format!("{tool} returned no result"))
+                    .and_then(|result| {
+                        if result.success {
+                            Ok(tool_payload(result.content.as_str()))
+                        } else {
+                            Err(result.content)
+                        }
+                    })
+            }
+            None => Err(format!("{tool} is not available in this conversation")),
+        },
+        Err(err) => Err(err),
+    };
+    close_runtime_session(session, gateway.runtime_session).await;
+    result
+}
+
+fn exposed_tool_name(metadata: &HashMap<String, ToolInfo>, original_name: &str) -> Option<String> {
+    let mut names = metadata
+        .iter()
+        .filter(|(_, info)| info.original_name == original_name)
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    names.sort();
+    names.into_iter().next()
+}
+
+fn tool_payload(content: &str) -> Value {
+    serde_json::from_str(content).unwrap_or_else(|_| json!({ "message": content }))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::tool_payload;
+
+    #[test]
+    fn tool_payload_keeps_structured_results_and_wraps_plain_text() {
+        assert_eq!(
+            tool_payload(r#"{"result":{"sessions":[]}}"#)["result"]["sessions"],
+            json!([])
+        );
+        assert_eq!(tool_payload("done")["message"], "done");
+    }
+}
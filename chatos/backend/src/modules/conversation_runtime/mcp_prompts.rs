@@ -0,0 +1,95 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use chatos_mcp_management_sdk::McpManagementRuntimeSessionHandle;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use tracing::warn;
+
+use crate::models::session::Session;
+use crate::modules::conversation_runtime::runtime_context::open_conversation_mcp_gateway;
+use crate::services::shared_mcp_runtime::build_shared_mcp_executor;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RenderedMcpPrompt {
+    pub items: Vec<Value>,
+    /// Message contents joined for insertion into the chat composer.
+    pub text: String,
+}
+
+/// Prompts the conversation's MCP servers offer, each tagged with the
+/// `server` to pass back to [`render_conversation_mcp_prompt`].
+pub async fn list_conversation_mcp_prompts(
+    session: &Session,
+    owner_user_id: &str,
+    owner_role: &str,
+) -> Result<Vec<Value>, String> {
+    let gateway = open_conversation_mcp_gateway(session, owner_user_id, owner_role).await?;
+    let executor = build_shared_mcp_executor(vec![gateway.server], Vec::new(), Vec::new());
+    let prompts = executor.list_prompts().await;
+    close_runtime_session(session, gateway.runtime_session).await;
+    Ok(prompts)
+}
+
+pub async fn render_conversation_mcp_prompt(
+    session: &Session,
+    owner_user_id: &str,
+    owner_role: &str,
+    server_name: &str,
+    prompt_name: &str,
+    arguments: Map<String, Value>,
+) -> Result<RenderedMcpPrompt, String> {
+    let gateway = open_conversation_mcp_gateway(session, owner_user_id, owner_role).await?;
+    let executor = build_shared_mcp_executor(vec![gateway.server], Vec::new(), Vec::new());
+    let prompt = executor
+        .get_prompt(server_name, prompt_name, arguments)
+        .await;
+    close_runtime_session(session, gateway.runtime_session).await;
+    Ok(rendered_prompt(&prompt?))
+}
+
+fn rendered_prompt(prompt: &Value) -> RenderedMcpPrompt {
+    let items = chatos_mcp_runtime::mcp_prompt_input_items(prompt);
+    let text = items
+        .iter()
+        .filter_map(|item| item.get("content").and_then(Value::as_str))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    RenderedMcpPrompt { items, text }
+}
+
+pub(super) async fn close_runtime_session(
+    session: &Session,
+    runtime_session: McpManagementRuntimeSessionHandle,
+) {
+    let mcp_session_id = runtime_session.session_id().to_string();
+    if let Err(error) = runtime_session.close().await {
+        warn!(
+            source_session_id = session.id.as_str(),
+            mcp_session_id,
+            error = %error,
+            "close ChatOS MCP gateway runtime session failed"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::rendered_prompt;
+
+    #[test]
+    fn rendered_prompt_joins_message_text_in_order() {
+        let rendered = rendered_prompt(&json!({"messages": [
+            {"role": "user", "content": {"type": "text", "text": "Summarize releases"}},
+            {"role": "user", "content": {"type": "resource", "resource": {
+                "uri": "docs://readme", "text": "# Docs"
+            }}}
+        ]}));
+        assert_eq!(rendered.items.len(), 2);
+        assert!(rendered
+            .text
+            .starts_with("Summarize releases\n\n<resource uri=\"docs://readme\""));
+    }
+}
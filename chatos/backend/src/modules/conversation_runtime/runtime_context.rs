@@ -42,6 +42,7 @@ use crate::models::memory_runtime_types::{
     TurnRuntimeSnapshotPluginCommandInvocationDto, TurnRuntimeSnapshotSelectedCommandDto,
 };
 use crate::models::project::PUBLIC_PROJECT_ID;
+use crate::models::session::Session;
 use crate::services::{
     chatos_agents, chatos_memory_engine, chatos_memory_mappings, chatos_sessions,
     plugin_management_prompts,
@@ -118,6 +119,56 @@ pub async fn resume_mcp_management_gateway(
     })
 }
 
+pub struct ConversationMcpGateway {
+    pub server: crate::services::mcp_loader::McpHttpServer,
+    pub runtime_session: McpManagementRuntimeSessionHandle,
+}
+
+/// Opens an MCP Management runtime session for a conversation outside a chat
+/// turn, scoped like the default agent's next turn, so user-invoked MCP
+/// prompts resolve under the same policy. The caller closes the session.
+pub async fn open_conversation_mcp_gateway(
+    session: &Session,
+    owner_user_id: &str,
+    owner_role: &str,
+) -> Result<ConversationMcpGateway, String> {
+    let runtime_metadata = ChatRuntimeMetadata::from_metadata(session.metadata.as_ref());
+    let requested_project_id =
+        normalize_id(session.project_id.clone()).or_else(|| runtime_metadata.project_id.clone());
+    let project_id =
+        resolve_project_runtime_context(Some(owner_user_id), requested_project_id, None)
+            .await
+            .project_id
+            .unwrap_or_else(|| PUBLIC_PROJECT_ID.to_string());
+    let request_id = format!("mcp-prompts-{}", uuid::Uuid::new_v4());
+    let contact_agent_id = runtime_metadata
+        .contact_agent_id
+        .clone()
+        .or_else(|| normalize_id(session.selected_agent_id.clone()));
+    let gateway = resolve_mcp_management_gateway(McpManagementGatewayRequest {
+        tenant_id: Some(owner_user_id),
+        owner_user_id: Some(owner_user_id),
+        owner_role: Some(owner_role),
+        agent_profile: ChatosAgentProfile::from_flags(false, false),
+        project_id: Some(project_id.as_str()),
+        source_session_id: Some(session.id.as_str()),
+        turn_id: Some(request_id.as_str()),
+        source_user_message_id: Some(request_id.as_str()),
+        contact_agent_id: contact_agent_id.as_deref(),
+        default_model_config_id: None,
+        expected_project_task_ids: &[],
+        selected_plugins: Vec::new(),
+        plugin_command_invocations: Vec::new(),
+        locale: None,
+    })
+    .await?;
+    let (server, _, _, _, _, runtime_session) = gateway.into_parts();
+    Ok(ConversationMcpGateway {
+        server,
+        runtime_session,
+    })
+}
+
 pub async fn resolve_runtime_context(
     session_id: &str,
     _content: &str,
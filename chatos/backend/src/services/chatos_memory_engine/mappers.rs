@@ -175,6 +175,10 @@ pub(super) fn engine_subject_memory_to_agent_recall(
         confidence: item.confidence,
         last_seen_at: item.last_seen_at,
         updated_at: item.updated_at,
+        pinned: item.pinned,
+        expires_at: item.expires_at,
+        version: item.version,
+        edited_by: item.edited_by,
     }
 }
 
@@ -2,12 +2,15 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 use memory_engine_sdk::{
-    EngineSubjectMemory, QuerySubjectMemoriesRequest, UpsertSubjectMemoryScopeRequest,
+    EngineSubjectMemory, EngineSubjectMemoryVersion, QuerySubjectMemoriesRequest,
+    SubjectMemoryProvenanceResponse, UpdateSubjectMemoryRequest, UpsertSubjectMemoryScopeRequest,
 };
 use serde_json::json;
 
 use crate::core::chat_runtime::{contact_agent_id_from_metadata, contact_id_from_metadata};
-use crate::models::memory_mapping_types::{MemoryAgentRecallDto, MemoryProjectMemoryDto};
+use crate::models::memory_mapping_types::{
+    MemoryAgentRecallDto, MemoryProjectMemoryDto, UpdateMemoryAgentRecallRequestDto,
+};
 use crate::models::project::PUBLIC_PROJECT_ID;
 use crate::models::session::Session;
 
@@ -105,6 +108,51 @@ pub async fn list_contact_agent_recalls(
         .collect())
 }
 
+pub async fn update_contact_agent_recall(
+    user_id: &str,
+    agent_id: &str,
+    recall_id: &str,
+    payload: &UpdateMemoryAgentRecallRequestDto,
+) -> Result<MemoryAgentRecallDto, String> {
+    let client = build_client()?;
+    let item = client
+        .update_subject_memory(
+            recall_id,
+            &UpdateSubjectMemoryRequest {
+                tenant_id: user_id.to_string(),
+                source_id: CHATOS_COMPAT_SOURCE_ID.to_string(),
+                text: payload.recall_text.clone(),
+                pinned: payload.pinned,
+                expires_at: payload.expires_at.clone(),
+                ttl_seconds: payload.ttl_seconds,
+                clear_expiry: payload.clear_expiry,
+                expected_version: payload.expected_version,
+                edited_by: Some(user_id.to_string()),
+                reason: payload.reason.clone(),
+            },
+        )
+        .await?;
+    Ok(engine_subject_memory_to_agent_recall(item, agent_id))
+}
+
+pub async fn list_contact_agent_recall_versions(
+    user_id: &str,
+    recall_id: &str,
+) -> Result<Vec<EngineSubjectMemoryVersion>, String> {
+    build_client()?
+        .list_subject_memory_versions(recall_id, user_id)
+        .await
+}
+
+pub async fn get_contact_agent_recall_provenance(
+    user_id: &str,
+    recall_id: &str,
+) -> Result<SubjectMemoryProvenanceResponse, String> {
+    build_client()?
+        .get_subject_memory_provenance(recall_id, user_id)
+        .await
+}
+
 pub(super) async fn register_subject_memory_scopes(
     client: &memory_engine_sdk::MemoryEngineClient,
     session: &Session,
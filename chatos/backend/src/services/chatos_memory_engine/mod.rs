@@ -14,8 +14,9 @@ pub(crate) use self::client::apply_data_auth;
 pub(crate) use self::mappers::engine_record_to_message;
 pub use self::mapping::CHATOS_COMPAT_SOURCE_ID;
 pub use self::memories::{
+    get_contact_agent_recall_provenance, list_contact_agent_recall_versions,
     list_contact_agent_recalls, list_contact_project_memories,
-    list_contact_project_memories_by_contact,
+    list_contact_project_memories_by_contact, update_contact_agent_recall,
 };
 pub use self::review_repair::{
     get_chatos_review_repair_job_run, get_chatos_review_repair_status, run_chatos_review_repair,
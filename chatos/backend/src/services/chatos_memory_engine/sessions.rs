@@ -95,6 +95,7 @@ pub async fn compose_chatos_context(
                 include_subject_memory: Some(true),
                 recent_record_limit: None,
                 summary_limit: Some(2),
+                ..Default::default()
             }),
         })
         .await?;
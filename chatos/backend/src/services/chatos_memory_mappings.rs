@@ -12,8 +12,9 @@ pub use contacts::{
     get_memory_contact, list_memory_contacts, update_contact_task_runner_config,
 };
 pub use memories::{
+    get_contact_agent_recall_provenance, list_contact_agent_recall_versions,
     list_contact_agent_recalls, list_contact_project_memories,
-    list_contact_project_memories_by_contact, list_contact_projects,
+    list_contact_project_memories_by_contact, list_contact_projects, update_contact_agent_recall,
 };
 pub use project_links::{
     delete_project_contact_link, list_project_contacts, list_project_contacts_for_owner,
@@ -1,9 +1,12 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
+use memory_engine_sdk::{EngineSubjectMemoryVersion, SubjectMemoryProvenanceResponse};
 use serde_json::Value;
 
-use crate::models::memory_mapping_types::{MemoryAgentRecallDto, MemoryProjectMemoryDto};
+use crate::models::memory_mapping_types::{
+    MemoryAgentRecallDto, MemoryProjectMemoryDto, UpdateMemoryAgentRecallRequestDto,
+};
 use crate::models::project::{normalize_project_id, PUBLIC_PROJECT_ID};
 use crate::repositories::chatos_memory_mappings as mappings_repo;
 use crate::services::chatos_memory_engine;
@@ -157,3 +160,42 @@ pub async fn list_contact_agent_recalls(
     )
     .await
 }
+
+pub async fn update_contact_agent_recall(
+    contact_id: &str,
+    recall_id: &str,
+    payload: &UpdateMemoryAgentRecallRequestDto,
+) -> Result<MemoryAgentRecallDto, String> {
+    let contact = mappings_repo::get_contact_by_id(contact_id)
+        .await?
+        .ok_or_else(|| "contact not found".to_string())?;
+    chatos_memory_engine::update_contact_agent_recall(
+        contact.user_id.as_str(),
+        contact.agent_id.as_str(),
+        recall_id,
+        payload,
+    )
+    .await
+}
+
+pub async fn list_contact_agent_recall_versions(
+    contact_id: &str,
+    recall_id: &str,
+) -> Result<Vec<EngineSubjectMemoryVersion>, String> {
+    let contact = mappings_repo::get_contact_by_id(contact_id)
+        .await?
+        .ok_or_else(|| "contact not found".to_string())?;
+    chatos_memory_engine::list_contact_agent_recall_versions(contact.user_id.as_str(), recall_id)
+        .await
+}
+
+pub async fn get_contact_agent_recall_provenance(
+    contact_id: &str,
+    recall_id: &str,
+) -> Result<SubjectMemoryProvenanceResponse, String> {
+    let contact = mappings_repo::get_contact_by_id(contact_id)
+        .await?
+        .ok_or_else(|| "contact not found".to_string())?;
+    chatos_memory_engine::get_contact_agent_recall_provenance(contact.user_id.as_str(), recall_id)
+        .await
+}
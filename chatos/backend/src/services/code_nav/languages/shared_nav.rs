@@ -473,6 +473,7 @@ pub(crate) fn heuristic_nav_capabilities() -> NavCapabilities {
         supports_definition: true,
         supports_references: true,
         supports_document_symbols: true,
+        ..NavCapabilities::default()
     }
 }
 
@@ -67,6 +67,7 @@ pub fn semantic_capabilities() -> NavCapabilities {
         supports_definition: available,
         supports_references: available,
         supports_document_symbols: available,
+        ..NavCapabilities::default()
     }
 }
 
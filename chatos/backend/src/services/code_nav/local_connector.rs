@@ -38,6 +38,9 @@ pub async fn capabilities(
         supports_definition: true,
         supports_references: true,
         supports_document_symbols: true,
+        supports_hover: false,
+        supports_call_hierarchy: false,
+        supports_workspace_symbols: false,
         fallback_available: true,
     })
 }
@@ -0,0 +1,710 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use serde_json::{json, Value};
+use url::Url;
+
+use crate::services::code_nav::file_limits::read_code_nav_file_to_string;
+use crate::services::code_nav::languages::shared_nav::nav_location_from_coordinates;
+use crate::services::code_nav::types::{
+    DocumentSymbolItem, DocumentSymbolsRequest, DocumentSymbolsResponse, NavCapabilities,
+    NavLocation, NavPositionRequest, ProjectContext,
+};
+use crate::services::code_nav::workspace::detect_language;
+use crate::services::code_nav::CodeNavProvider;
+
+pub mod client;
+pub mod servers;
+
+use self::client::LspClient;
+use self::servers::{LspServerSpec, DEFAULT_SERVERS};
+
+const LSP_INITIALIZE_TIMEOUT: Duration = Duration::from_secs(30);
+const LSP_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+const LSP_IDLE_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+const LSP_RETRY_AFTER_FAILURE: Duration = Duration::from_secs(5 * 60);
+const MAX_LSP_LOCATIONS: usize = 200;
+const MAX_LSP_SYMBOLS: usize = 500;
+
+type SessionKey = (&'static str, PathBuf);
+type SessionSlot = Arc<tokio::sync::Mutex<Option<Arc<LspSession>>>>;
+
+static SESSIONS: Lazy<Mutex<HashMap<SessionKey, SessionSlot>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static FAILED_STARTS: Lazy<Mutex<HashMap<&'static str, Instant>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+pub fn default_lsp_providers() -> Vec<Arc<dyn CodeNavProvider>> {
+    DEFAULT_SERVERS
+        .iter()
+        .map(|spec| Arc::new(LspCodeNavProvider::new(spec)) as Arc<dyn CodeNavProvider>)
+        .collect()
+}
+
+/// Code navigation through a language server launched over stdio. One server
+/// is kept warm per project root; when it is not installed or fails to start,
+/// the provider stops matching files and the regex providers take over.
+pub struct LspCodeNavProvider {
+    spec: &'static LspServerSpec,
+}
+
+impl LspCodeNavProvider {
+    pub fn new(spec: &'static LspServerSpec) -> Self {
+        Self { spec }
+    }
+
+    fn server_available(&self) -> bool {
+        let recently_failed = FAILED_STARTS
+            .lock()
+            .ok()
+            .and_then(|failed| failed.get(self.spec.id).copied())
+            .is_some_and(|at| at.elapsed() < LSP_RETRY_AFTER_FAILURE);
+        !recently_failed && self.spec.resolve_command().is_some()
+    }
+}
+
+#[async_trait::async_trait]
+impl CodeNavProvider for LspCodeNavProvider {
+    fn provider_id(&self) -> &'static str {
+        self.spec.id
+    }
+
+    fn language_id(&self) -> &'static str {
+        self.spec.languages.first().copied().unwrap_or("plain")
+    }
+
+    fn definition_mode(&self) -> &'static str {
+        "lsp"
+    }
+
+    fn references_mode(&self) -> &'static str {
+        "lsp"
+    }
+
+    fn document_symbols_mode(&self) -> &'static str {
+        "lsp"
+    }
+
+    fn supports_file(&self, file_path: &Path) -> bool {
+        self.spec
+            .handles_language(detect_language(file_path).as_str())
+            && self.server_available()
+    }
+
+    fn detect_project(&self, ctx: &ProjectContext) -> bool {
+        self.spec.detect_project(ctx)
+    }
+
+    fn capabilities(&self, ctx: &ProjectContext) -> NavCapabilities {
+        if !self.detect_project(ctx) || !self.server_available() {
+            return NavCapabilities::default();
+        }
+        match warm_server_capabilities(self.spec, ctx.root.as_path()) {
+            Some(server) => capabilities_from_server(&server),
+            None => {
+                // Start the server in the background so the next request
+                // finds it warm.
+                if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+                    let spec = self.spec;
+                    let root = ctx.root.clone();
+                    runtime.spawn(async move {
+                        let _ = session_for(spec, root.as_path()).await;
+                    });
+                }
+                NavCapabilities {
+                    supports_definition: true,
+                    supports_references: true,
+                    supports_document_symbols: true,
+                    ..NavCapabilities::default()
+                }
+            }
+        }
+    }
+
+    async fn definition(
+        &self,
+        ctx: &ProjectContext,
+        req: &NavPositionRequest,
+    ) -> Result<Vec<NavLocation>, String> {
+        let session = session_for(self.spec, ctx.root.as_path()).await?;
+        let (uri, text) = session.sync_document(ctx).await?;
+        let result = session
+            .client
+            .request(
+                "textDocument/definition",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "position": lsp_position(text.as_str(), req.line, req.column),
+                }),
+                LSP_REQUEST_TIMEOUT,
+            )
+            .await?;
+        Ok(locations_from_response(ctx.root.as_path(), &result))
+    }
+
+    async fn references(
+        &self,
+        ctx: &ProjectContext,
+        req: &NavPositionRequest,
+    ) -> Result<Vec<NavLocation>, String> {
+        let session = session_for(self.spec, ctx.root.as_path()).await?;
+        let (uri, text) = session.sync_document(ctx).await?;
+        let result = session
+            .client
+            .request(
+                "textDocument/references",
+                json!({
+                    "textDocument": { "uri": uri },
+                    "position": lsp_position(text.as_str(), req.line, req.column),
+                    "context": { "includeDeclaration": true },
+                }),
+                LSP_REQUEST_TIMEOUT,
+            )
+            .await?;
+        Ok(locations_from_response(ctx.root.as_path(), &result))
+    }
+
+    async fn document_symbols(
+        &self,
+        ctx: &ProjectContext,
+        _req: &DocumentSymbolsRequest,
+    ) -> Result<DocumentSymbolsResponse, String> {
+        let session = session_for(self.spec, ctx.root.as_path()).await?;
+        let (uri, text) = session.sync_document(ctx).await?;
+        let result = session
+            .client
+            .request(
+                "textDocument/documentSymbol",
+                json!({ "textDocument": { "uri": uri } }),
+                LSP_REQUEST_TIMEOUT,
+            )
+            .await?;
+        Ok(DocumentSymbolsResponse {
+            provider: self.provider_id().to_string(),
+            language: ctx.language.clone(),
+            mode: self.document_symbols_mode().to_string(),
+            symbols: symbols_from_response(text.as_str(), &result),
+        })
+    }
+}
+
+struct LspSession {
+    client: LspClient,
+    server_capabilities: Value,
+    documents: tokio::sync::Mutex<HashMap<String, OpenDocument>>,
+    last_used: Mutex<Instant>,
+}
+
+struct OpenDocument {
+    version: i64,
+    text: String,
+}
+
+impl LspSession {
+    fn touch(&self) {
+        if let Ok(mut last_used) = self.last_used.lock() {
+            *last_used = Instant::now();
+        }
+    }
+
+    fn idle_for(&self) -> Duration {
+        self.last_used
+            .lock()
+            .map(|last_used| last_used.elapsed())
+            .unwrap_or_default()
+    }
+
+    /// Opens the file in the server, or sends its current content when it
+    /// changed since the last request. Returns the URI and the text.
+    async fn sync_document(&self, ctx: &ProjectContext) -> Result<(String, String), String> {
+        let file_path = ctx.file_path.clone();
+        let text = tokio::task::spawn_blocking(move || read_code_nav_file_to_string(&file_path))
+            .await
+            .map_err(|err| format!("code-nav lsp read task failed: {err}"))??;
+        let uri = path_to_uri(ctx.file_path.as_path())?;
+        let mut documents = self.documents.lock().await;
+        match documents.get_mut(uri.as_str()) {
+            None => {
+                self.client
+                    .notify(
+                        "textDocument/didOpen",
+                        json!({
+                            "textDocument": {
+                                "uri": uri,
+                                "languageId": ctx.language,
+                                "version": 1,
+                                "text": text,
+                            }
+                        }),
+                    )
+                    .await?;
+                documents.insert(
+                    uri.clone(),
+                    OpenDocument {
+                        version: 1,
+                        text: text.clone(),
+                    },
+                );
+            }
+            Some(document) if document.text != text => {
+                document.version += 1;
+                document.text = text.clone();
+                self.client
+                    .notify(
+                        "textDocument/didChange",
+                        json!({
+                            "textDocument": { "uri": uri, "version": document.version },
+                            "contentChanges": [{ "text": text }],
+                        }),
+                    )
+                    .await?;
+            }
+            Some(_) => {}
+        }
+        Ok((uri, text))
+    }
+}
+
+async fn session_for(spec: &'static LspServerSpec, root: &Path) -> Result<Arc<LspSession>, String> {
+    let slot = {
+        let mut sessions = SESSIONS
+            .lock()
+            .map_err(|_| "language server registry unavailable".to_string())?;
+        evict_idle_sessions(&mut sessions);
+        sessions
+            .entry((spec.id, root.to_path_buf()))
+            .or_default()
+            .clone()
+    };
+    let mut slot = slot.lock().await;
+    if let Some(session) = slot.as_ref().filter(|session| session.client.is_alive()) {
+        session.touch();
+        return Ok(session.clone());
+    }
+    match start_session(spec, root).await {
+        Ok(session) => {
+            *slot = Some(session.clone());
+            Ok(session)
+        }
+        Err(err) => {
+            *slot = None;
+            if let Ok(mut failed) = FAILED_STARTS.lock() {
+                failed.insert(spec.id, Instant::now());
+            }
+            Err(err)
+        }
+    }
+}
+
+/// Drops sessions that died or sat idle too long. Slots that are locked are
+/// starting or serving a request and are kept.
+fn evict_idle_sessions(sessions: &mut HashMap<SessionKey, SessionSlot>) {
+    sessions.retain(|_, slot| match slot.try_lock() {
+        Ok(session) => session.as_ref().is_some_and(|session| {
+            session.client.is_alive() && session.idle_for() < LSP_IDLE_TIMEOUT
+        }),
+        Err(_) => true,
+    });
+}
+
+fn warm_server_capabilities(spec: &'static LspServerSpec, root: &Path) -> Option<Value> {
+    let slot = SESSIONS
+        .lock()
+        .ok()?
+        .get(&(spec.id, root.to_path_buf()))?
+        .clone();
+    let session = slot.try_lock().ok()?;
+    session
+        .as_ref()
+        .filter(|session| session.client.is_alive())
+        .map(|session| session.server_capabilities.clone())
+}
+
+async fn start_session(
+    spec: &'static LspServerSpec,
+    root: &Path,
+) -> Result<Arc<LspSession>, String> {
+    let program = spec
+        .resolve_command()
+        .ok_or_else(|| format!("未找到语言服务器: {}", spec.command))?;
+    let client = LspClient::spawn(program.as_path(), spec.args, root)?;
+    let root_uri = path_to_uri(root)?;
+    let root_name = root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "workspace".to_string());
+    let initialized = client
+        .request(
+            "initialize",
+            json!({
+                "processId": std::process::id(),
+                "clientInfo": { "name": "chatos-code-nav" },
+                "rootUri": root_uri,
+                "workspaceFolders": [{ "uri": root_uri, "name": root_name }],
+                "capabilities": {
+                    "textDocument": {
+                        "synchronization": { "didSave": false },
+                        "definition": { "linkSupport": true },
+                        "references": {},
+                        "documentSymbol": { "hierarchicalDocumentSymbolSupport": true },
+                        "hover": { "contentFormat": ["markdown", "plaintext"] },
+                        "callHierarchy": {},
+                    },
+                    "workspace": { "symbol": {}, "workspaceFolders": true, "configuration": true },
+                    "general": { "positionEncodings": ["utf-16"] },
+                },
+            }),
+            LSP_INITIALIZE_TIMEOUT,
+        )
+        .await?;
+    client.notify("initialized", json!({})).await?;
+    Ok(Arc::new(LspSession {
+        client,
+        server_capabilities: initialized
+            .get("capabilities")
+            .cloned()
+            .unwrap_or_else(|| json!({})),
+        documents: tokio::sync::Mutex::new(HashMap::new()),
+        last_used: Mutex::new(Instant::now()),
+    }))
+}
+
+fn capabilities_from_server(server: &Value) -> NavCapabilities {
+    let offers = |name: &str| match server.get(name) {
+        None | Some(Value::Null) | Some(Value::Bool(false)) => false,
+        Some(_) => true,
+    };
+    NavCapabilities {
+        supports_definition: offers("definitionProvider"),
+        supports_references: offers("referencesProvider"),
+        supports_document_symbols: offers("documentSymbolProvider"),
+        supports_hover: offers("hoverProvider"),
+        supports_call_hierarchy: offers("callHierarchyProvider"),
+        supports_workspace_symbols: offers("workspaceSymbolProvider"),
+    }
+}
+
+fn path_to_uri(path: &Path) -> Result<String, String> {
+    Url::from_file_path(path)
+        .map(|url| url.to_string())
+        .map_err(|_| format!("无法转换为文件 URI: {}", path.display()))
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+/// Request coordinates are 1-based with columns in characters; LSP wants
+/// 0-based lines and UTF-16 offsets.
+fn lsp_position(text: &str, line: usize, column: usize) -> Value {
+    let line_index = line.saturating_sub(1);
+    let line_text = text.lines().nth(line_index).unwrap_or_default();
+    let character = line_text
+        .chars()
+        .take(column.saturating_sub(1))
+        .map(char::len_utf16)
+        .sum::<usize>();
+    json!({ "line": line_index, "character": character })
+}
+
+fn column_from_utf16(line_text: &str, character: usize) -> usize {
+    let mut units = 0;
+    let mut column = 1;
+    for ch in line_text.chars() {
+        if units >= character {
+            break;
+        }
+        units += ch.len_utf16();
+        column += 1;
+    }
+    column
+}
+
+fn range_start_end(range: &Value) -> Option<(usize, usize, usize, usize)> {
+    let read = |point: &str, field: &str| {
+        range
+            .get(point)?
+            .get(field)?
+            .as_u64()
+            .map(|value| value as usize)
+    };
+    Some((
+        read("start", "line")?,
+        read("start", "character")?,
+        read("end", "line")?,
+        read("end", "character")?,
+    ))
+}
+
+/// Handles `Location`, `Location[]` and `LocationLink[]` results.
+fn locations_from_response(root: &Path, result: &Value) -> Vec<NavLocation> {
+    let items = match result {
+        Value::Array(items) => items.clone(),
+        Value::Null => Vec::new(),
+        single => vec![single.clone()],
+    };
+    let mut lines = LineCache::default();
+    items
+        .iter()
+        .filter_map(|item| {
+            let uri = item
+                .get("uri")
+                .or_else(|| item.get("targetUri"))?
+                .as_str()?;
+            let range = item
+                .get("range")
+                .or_else(|| item.get("targetSelectionRange"))
+                .or_else(|| item.get("targetRange"))?;
+            let path = uri_to_path(uri)?;
+            let (start_line, start_character, end_line, end_character) = range_start_end(range)?;
+            let column = column_from_utf16(lines.line(&path, start_line), start_character);
+            let end_column = column_from_utf16(lines.line(&path, end_line), end_character);
+            nav_location_from_coordinates(
+                root,
+                path.as_path(),
+                start_line + 1,
+                column,
+                end_line + 1,
+                end_column,
+                1.0,
+            )
+            .ok()
+            .flatten()
+        })
+        .take(MAX_LSP_LOCATIONS)
+        .collect()
+}
+
+/// Flattens hierarchical `DocumentSymbol[]` and reads flat
+/// `SymbolInformation[]`.
+fn symbols_from_response(text: &str, result: &Value) -> Vec<DocumentSymbolItem> {
+    let lines = text.lines().collect::<Vec<_>>();
+    let mut symbols = Vec::new();
+    let mut stack = result
+        .as_array()
+        .map(|items| items.iter().rev().collect::<Vec<_>>())
+        .unwrap_or_default();
+    while let Some(item) = stack.pop() {
+        if symbols.len() >= MAX_LSP_SYMBOLS {
+            break;
+        }
+        let range = item.get("range").or_else(|| {
+            item.get("location")
+                .and_then(|location| location.get("range"))
+        });
+        let start_range = item.get("selectionRange").or(range);
+        if let (Some(name), Some((_, _, end_line, end_character)), Some(start)) = (
+            item.get("name").and_then(Value::as_str),
+            range.and_then(range_start_end),
+            start_range.and_then(range_start_end),
+        ) {
+            let line_text = |line: usize| lines.get(line).copied().unwrap_or_default();
+            symbols.push(DocumentSymbolItem {
+                name: name.to_string(),
+                kind: symbol_kind_name(item.get("kind").and_then(Value::as_u64).unwrap_or(0))
+                    .to_string(),
+                line: start.0 + 1,
+                column: column_from_utf16(line_text(start.0), start.1),
+                end_line: end_line + 1,
+                end_column: column_from_utf16(line_text(end_line), end_character),
+            });
+        }
+        if let Some(children) = item.get("children").and_then(Value::as_array) {
+            stack.extend(children.iter().rev());
+        }
+    }
+    symbols
+}
+
+fn symbol_kind_name(kind: u64) -> &'static str {
+    match kind {
+        1 => "file",
+        2 => "module",
+        3 => "namespace",
+        4 => "package",
+        5 => "class",
+        6 => "method",
+        7 => "property",
+        8 => "field",
+        9 => "constructor",
+        10 => "enum",
+        11 => "interface",
+        12 => "function",
+        13 => "variable",
+        14 => "constant",
+        22 => "enum_member",
+        23 => "struct",
+        24 => "event",
+        25 => "operator",
+        26 => "type_parameter",
+        15..=21 => "value",
+        _ => "symbol",
+    }
+}
+
+/// Lines of the files a result points into, read once per file.
+#[derive(Default)]
+struct LineCache {
+    files: HashMap<PathBuf, Vec<String>>,
+}
+
+impl LineCache {
+    fn line(&mut self, path: &Path, line: usize) -> &str {
+        self.files
+            .entry(path.to_path_buf())
+            .or_insert_with(|| {
+                read_code_nav_file_to_string(path)
+                    .map(|text| text.lines().map(ToOwned::to_owned).collect())
+                    .unwrap_or_default()
+            })
+            .get(line)
+            .map(String::as_str)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use std::fs;
+    use std::sync::Arc;
+
+    use super::servers::LspServerSpec;
+    use super::{
+        capabilities_from_server, column_from_utf16, locations_from_response, lsp_position,
+        path_to_uri, symbols_from_response, LspCodeNavProvider,
+    };
+    use crate::services::code_nav::languages::rust::RustCodeNavProvider;
+    use crate::services::code_nav::manager::CodeNavManager;
+    use crate::services::code_nav::types::NavPositionRequest;
+
+    /// Exits right away, so the initialize handshake fails.
+    static BROKEN_SERVER: LspServerSpec = LspServerSpec {
+        id: "broken-test-server",
+        languages: &["rust"],
+        command: "true",
+        args: &[],
+        project_markers: &["Cargo.toml"],
+    };
+
+    #[test]
+    fn positions_convert_between_characters_and_utf16() {
+        let text = "fn a() {}\nlet s = \"😀\"; call();\n";
+        assert_eq!(
+            lsp_position(text, 2, 15),
+            json!({ "line": 1, "character": 15 })
+        );
+        assert_eq!(column_from_utf16("let s = \"😀\"; call();", 15), 15);
+        assert_eq!(column_from_utf16("abc", 0), 1);
+    }
+
+    #[test]
+    fn definition_results_accept_locations_and_links() {
+        let root = std::env::temp_dir().join(format!("code_nav_lsp_test_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(root.join("src")).expect("create project");
+        let root = root.canonicalize().expect("canonical root");
+        let file = root.join("src/lib.rs");
+        fs::write(&file, "pub fn target() {}\n").expect("write lib");
+        let uri = path_to_uri(&file).expect("file uri");
+        let range = json!({
+            "start": { "line": 0, "character": 7 },
+            "end": { "line": 0, "character": 13 }
+        });
+
+        let single = locations_from_response(&root, &json!({ "uri": uri, "range": range }));
+        let links = locations_from_response(
+            &root,
+            &json!([{ "targetUri": uri, "targetRange": range, "targetSelectionRange": range }]),
+        );
+
+        for locations in [single, links] {
+            assert_eq!(locations.len(), 1);
+            assert_eq!(locations[0].relative_path, "src/lib.rs");
+            assert_eq!((locations[0].line, locations[0].column), (1, 8));
+            assert_eq!(locations[0].end_column, 14);
+            assert_eq!(locations[0].preview, "pub fn target() {}");
+        }
+        assert!(locations_from_response(&root, &json!(null)).is_empty());
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn document_symbols_are_flattened_in_source_order() {
+        let text = "struct A {\n    x: u8,\n}\nfn b() {}\n";
+        let range = |start: u64, end: u64| json!({ "start": { "line": start, "character": 0 }, "end": { "line": end, "character": 1 } });
+        let result = json!([
+            {
+                "name": "A", "kind": 23, "range": range(0, 2), "selectionRange": range(0, 0),
+                "children": [{ "name": "x", "kind": 8, "range": range(1, 1), "selectionRange": range(1, 1) }]
+            },
+            { "name": "b", "kind": 12, "location": { "uri": "file:///x.rs", "range": range(3, 3) } }
+        ]);
+
+        let symbols = symbols_from_response(text, &result);
+
+        let names = symbols
+            .iter()
+            .map(|symbol| format!("{}:{}:{}", symbol.name, symbol.kind, symbol.line))
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["A:struct:1", "x:field:2", "b:function:4"]);
+        assert_eq!(symbols[0].end_line, 3);
+    }
+
+    #[test]
+    fn server_capabilities_enable_extra_features_only_when_offered() {
+        let capabilities = capabilities_from_server(&json!({
+            "definitionProvider": true,
+            "referencesProvider": { "workDoneProgress": true },
+            "documentSymbolProvider": true,
+            "hoverProvider": true,
+            "callHierarchyProvider": false,
+        }));
+        assert!(capabilities.supports_references);
+        assert!(capabilities.supports_hover);
+        assert!(!capabilities.supports_call_hierarchy);
+        assert!(!capabilities.supports_workspace_symbols);
+    }
+
+    #[tokio::test]
+    async fn failed_server_start_falls_back_to_the_regex_provider() {
+        let root =
+            std::env::temp_dir().join(format!("code_nav_lsp_fallback_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(root.join("src")).expect("create project");
+        fs::write(root.join("Cargo.toml"), "[package]\nname = \"demo\"\n").expect("write manifest");
+        let file = root.join("src/lib.rs");
+        fs::write(
+            &file,
+            "pub fn target() {}\n\npub fn caller() {\n    target();\n}\n",
+        )
+        .expect("write lib");
+        let manager = CodeNavManager::new(vec![
+            Arc::new(LspCodeNavProvider::new(&BROKEN_SERVER)),
+            Arc::new(RustCodeNavProvider),
+        ]);
+        let request = NavPositionRequest {
+            project_root: root.to_string_lossy().to_string(),
+            file_path: file.to_string_lossy().to_string(),
+            line: 4,
+            column: 6,
+        };
+
+        let response = manager.definition(&request).await.expect("definition");
+
+        assert_eq!(response.provider, "rust");
+        assert_eq!(response.mode, "provider-heuristic");
+        assert_eq!(response.locations[0].line, 1);
+        let capabilities = manager
+            .capabilities(request.project_root.as_str(), request.file_path.as_str())
+            .await
+            .expect("capabilities");
+        assert_eq!(capabilities.provider, "rust");
+        assert!(!capabilities.supports_hover);
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+};
+use tokio::process::{Child, ChildStdin, ChildStdout, Command};
+use tokio::sync::oneshot;
+use tokio::task::JoinHandle;
+
+const LSP_MESSAGE_LIMIT_BYTES: usize = 32 * 1024 * 1024;
+
+type PendingRequests = Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>;
+type SharedStdin = Arc<tokio::sync::Mutex<ChildStdin>>;
+
+/// JSON-RPC over the stdio of one language server process. The process is
+/// killed when the client is dropped.
+pub struct LspClient {
+    _child: Child,
+    stdin: SharedStdin,
+    pending: PendingRequests,
+    next_id: AtomicI64,
+    alive: Arc<AtomicBool>,
+    reader: JoinHandle<()>,
+}
+
+impl LspClient {
+    pub fn spawn(program: &Path, args: &[&str], cwd: &Path) -> Result<Self, String> {
+        let mut child = Command::new(program)
+            .args(args)
+            .current_dir(cwd)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| format!("启动语言服务器失败 {}: {err}", program.display()))?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| "missing language server stdin".to_string())?;
+        let stdout = child
+            .stdout
+            .take()
+            .ok_or_else(|| "missing language server stdout".to_string())?;
+        let stdin = Arc::new(tokio::sync::Mutex::new(stdin));
+        let pending: PendingRequests = Arc::new(Mutex::new(HashMap::new()));
+        let alive = Arc::new(AtomicBool::new(true));
+        let reader = tokio::spawn(read_loop(
+            stdout,
+            stdin.clone(),
+            pending.clone(),
+            alive.clone(),
+        ));
+        Ok(Self {
+            _child: child,
+            stdin,
+            pending,
+            next_id: AtomicI64::new(1),
+            alive,
+            reader,
+        })
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::SeqCst)
+    }
+
+    pub async fn request(
+        &self,
+        method: &str,
+        params: Value,
+        timeout: Duration,
+    ) -> Result<Value, String> {
+        if !self.is_alive() {
+            return Err("语言服务器已退出".to_string());
+        }
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = oneshot::channel();
+        self.pending
+            .lock()
+            .map_err(|_| "language server request table unavailable".to_string())?
+            .insert(id, sender);
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if let Err(err) = self.write(&message).await {
+            self.forget(id);
+            return Err(err);
+        }
+        match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(result)) => result,
+            Ok(Err(_)) => Err("语言服务器已退出".to_string()),
+            Err(_) => {
+                self.forget(id);
+                let _ = self.notify("$/cancelRequest", json!({ "id": id })).await;
+                Err(format!(
+                    "语言服务器请求超时: {method} ({}s)",
+                    timeout.as_secs()
+                ))
+            }
+        }
+    }
+
+    pub async fn notify(&self, method: &str, params: Value) -> Result<(), String> {
+        self.write(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await
+    }
+
+    async fn write(&self, message: &Value) -> Result<(), String> {
+        let mut stdin = self.stdin.lock().await;
+        write_message(&mut *stdin, message).await
+    }
+
+    fn forget(&self, id: i64) {
+        if let Ok(mut pending) = self.pending.lock() {
+            pending.remove(&id);
+        }
+    }
+}
+
+impl Drop for LspClient {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+async fn read_loop(
+    stdout: ChildStdout,
+    stdin: SharedStdin,
+    pending: PendingRequests,
+    alive: Arc<AtomicBool>,
+) {
+    let mut reader = BufReader::new(stdout);
+    while let Ok(Some(message)) = read_message(&mut reader).await {
+        let method = message.get("method").and_then(Value::as_str);
+        let id = message.get("id").cloned();
+        match (method, id) {
+            (None, Some(id)) => {
+                let Some(sender) = id.as_i64().and_then(|id| pending.lock().ok()?.remove(&id))
+                else {
+                    continue;
+                };
+                let result = match message.get("error") {
+                    Some(error) => Err(format!(
+                        "语言服务器返回错误: {}",
+                        error
+                            .get("message")
+                            .and_then(Value::as_str)
+                            .unwrap_or("unknown error")
+                    )),
+                    None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+                };
+                let _ = sender.send(result);
+            }
+            (Some(method), Some(id)) => {
+                let result = server_request_result(method, message.get("params"));
+                let reply = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+                let mut stdin = stdin.lock().await;
+                if write_message(&mut *stdin, &reply).await.is_err() {
+                    break;
+                }
+            }
+            _ => {}
+        }
+    }
+    alive.store(false, Ordering::SeqCst);
+    if let Ok(mut pending) = pending.lock() {
+        for (_, sender) in pending.drain() {
+            let _ = sender.send(Err("语言服务器已退出".to_string()));
+        }
+    }
+}
+
+/// Answers for the requests servers send to clients during normal operation.
+/// Configuration is left to the server's defaults.
+fn server_request_result(method: &str, params: Option<&Value>) -> Value {
+    match method {
+        "workspace/configuration" => {
+            let count = params
+                .and_then(|params| params.get("items"))
+                .and_then(Value::as_array)
+                .map(Vec::len)
+                .unwrap_or(0);
+            Value::Array(vec![Value::Null; count])
+        }
+        "workspace/workspaceFolders" => Value::Array(Vec::new()),
+        _ => Value::Null,
+    }
+}
+
+pub(super) async fn write_message<W>(writer: &mut W, message: &Value) -> Result<(), String>
+where
+    W: AsyncWrite + Unpin,
+{
+    let body = serde_json::to_vec(message).map_err(|err| err.to_string())?;
+    let header = format!("Content-Length: {}\r\n\r\n", body.len());
+    writer
+        .write_all(header.as_bytes())
+        .await
+        .map_err(|err| format!("写入语言服务器失败: {err}"))?;
+    writer
+        .write_all(body.as_slice())
+        .await
+        .map_err(|err| format!("写入语言服务器失败: {err}"))?;
+    writer
+        .flush()
+        .await
+        .map_err(|err| format!("写入语言服务器失败: {err}"))
+}
+
+/// Reads one `Content-Length` framed message. `Ok(None)` means the stream
+/// ended cleanly between messages.
+pub(super) async fn read_message<R>(reader: &mut R) -> Result<Option<Value>, String>
+where
+    R: AsyncBufRead + Unpin,
+{
+    let mut content_length: Option<usize> = None;
+    let mut saw_header = false;
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .read_line(&mut line)
+            .await
+            .map_err(|err| format!("读取语言服务器输出失败: {err}"))?;
+        if read == 0 {
+            return if saw_header {
+                Err("语言服务器输出在消息头中断".to_string())
+            } else {
+                Ok(None)
+            };
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            if saw_header {
+                break;
+            }
+            continue;
+        }
+        saw_header = true;
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = content_length.ok_or_else(|| "语言服务器消息缺少 Content-Length".to_string())?;
+    if length > LSP_MESSAGE_LIMIT_BYTES {
+        return Err(format!(
+            "language server message exceeded limit: {length} bytes > {LSP_MESSAGE_LIMIT_BYTES} bytes"
+        ));
+    }
+    let mut body = vec![0_u8; length];
+    reader
+        .read_exact(body.as_mut_slice())
+        .await
+        .map_err(|err| format!("读取语言服务器输出失败: {err}"))?;
+    serde_json::from_slice(body.as_slice())
+        .map(Some)
+        .map_err(|err| format!("解析语言服务器消息失败: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tokio::io::BufReader;
+
+    use super::{read_message, server_request_result, write_message};
+
+    #[tokio::test]
+    async fn framed_messages_round_trip_and_end_cleanly() {
+        let mut buffer = Vec::new();
+        write_message(&mut buffer, &json!({"id": 1, "result": "é"}))
+            .await
+            .expect("write first");
+        write_message(&mut buffer, &json!({"method": "initialized"}))
+            .await
+            .expect("write second");
+
+        let mut reader = BufReader::new(buffer.as_slice());
+        let first = read_message(&mut reader).await.expect("read first");
+        assert_eq!(first, Some(json!({"id": 1, "result": "é"})));
+        let second = read_message(&mut reader).await.expect("read second");
+        assert_eq!(second, Some(json!({"method": "initialized"})));
+        assert_eq!(read_message(&mut reader).await.expect("eof"), None);
+
+        let mut truncated = BufReader::new(&b"Content-Length: 10\r\n"[..]);
+        assert!(read_message(&mut truncated).await.is_err());
+    }
+
+    #[test]
+    fn configuration_requests_get_one_default_per_item() {
+        let params = json!({"items": [{"section": "a"}, {"section": "b"}]});
+        assert_eq!(
+            server_request_result("workspace/configuration", Some(&params)),
+            json!([null, null])
+        );
+        assert_eq!(
+            server_request_result("window/workDoneProgress/create", None),
+            json!(null)
+        );
+    }
+}
@@ -0,0 +1,91 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::path::{Path, PathBuf};
+
+use crate::services::code_nav::types::ProjectContext;
+
+/// A language server the LSP provider knows how to launch over stdio.
+#[derive(Debug)]
+pub struct LspServerSpec {
+    pub id: &'static str,
+    /// Languages from `detect_language`; also sent as the LSP `languageId`.
+    pub languages: &'static [&'static str],
+    pub command: &'static str,
+    pub args: &'static [&'static str],
+    /// Files one of which must exist in the project root. Empty means the
+    /// server works on any directory.
+    pub project_markers: &'static [&'static str],
+}
+
+pub const RUST_ANALYZER: LspServerSpec = LspServerSpec {
+    id: "rust-analyzer",
+    languages: &["rust"],
+    command: "rust-analyzer",
+    args: &[],
+    project_markers: &["Cargo.toml"],
+};
+
+pub const GOPLS: LspServerSpec = LspServerSpec {
+    id: "gopls",
+    languages: &["go"],
+    command: "gopls",
+    args: &["serve"],
+    project_markers: &["go.mod", "go.work"],
+};
+
+pub const PYRIGHT: LspServerSpec = LspServerSpec {
+    id: "pyright",
+    languages: &["python"],
+    command: "pyright-langserver",
+    args: &["--stdio"],
+    project_markers: &[],
+};
+
+pub const CLANGD: LspServerSpec = LspServerSpec {
+    id: "clangd",
+    languages: &["c", "cpp"],
+    command: "clangd",
+    args: &["--background-index"],
+    project_markers: &[],
+};
+
+pub const DEFAULT_SERVERS: &[&LspServerSpec] = &[&RUST_ANALYZER, &GOPLS, &PYRIGHT, &CLANGD];
+
+impl LspServerSpec {
+    pub fn handles_language(&self, language: &str) -> bool {
+        self.languages.contains(&language)
+    }
+
+    pub fn detect_project(&self, ctx: &ProjectContext) -> bool {
+        self.project_markers.is_empty()
+            || self
+                .project_markers
+                .iter()
+                .any(|marker| ctx.root.join(marker).exists())
+    }
+
+    pub fn resolve_command(&self) -> Option<PathBuf> {
+        find_on_path(self.command)
+    }
+}
+
+fn find_on_path(command: &str) -> Option<PathBuf> {
+    let path_var = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_var).find_map(|segment| {
+        executable_candidates(segment.as_path(), command)
+            .into_iter()
+            .find(|candidate| candidate.is_file())
+    })
+}
+
+fn executable_candidates(dir: &Path, command: &str) -> Vec<PathBuf> {
+    if cfg!(windows) {
+        vec![
+            dir.join(format!("{command}.exe")),
+            dir.join(format!("{command}.cmd")),
+        ]
+    } else {
+        vec![dir.join(command)]
+    }
+}
@@ -7,8 +7,8 @@ use super::fallback::{fallback_definition, fallback_document_symbols, fallback_r
 use super::local_connector;
 use super::registry::default_providers;
 use super::types::{
-    DocumentSymbolsRequest, DocumentSymbolsResponse, NavCapabilitiesResponse, NavLocationsResponse,
-    NavPositionRequest, ProjectContext,
+    DocumentSymbolsRequest, DocumentSymbolsResponse, NavCapabilities, NavCapabilitiesResponse,
+    NavLocationsResponse, NavPositionRequest, ProjectContext,
 };
 use super::workspace::build_project_context;
 use super::CodeNavProvider;
@@ -39,25 +39,33 @@ impl CodeNavManager {
         }
 
         let ctx = build_project_context(project_root, file_path)?;
-        let provider = self.resolve_provider(&ctx);
-        let capabilities = provider
-            .as_ref()
-            .map(|provider| provider.capabilities(&ctx))
-            .unwrap_or_else(|| super::types::NavCapabilities {
-                supports_definition: false,
-                supports_references: false,
-                supports_document_symbols: false,
-            });
+        let mut provider_id = None;
+        let mut capabilities = NavCapabilities::default();
+        for provider in self.resolve_providers(&ctx) {
+            let offered = provider.capabilities(&ctx);
+            let serves_any = offered.supports_definition
+                || offered.supports_references
+                || offered.supports_document_symbols;
+            if serves_any && provider_id.is_none() {
+                provider_id = Some(provider.provider_id());
+            }
+            capabilities.supports_definition |= offered.supports_definition;
+            capabilities.supports_references |= offered.supports_references;
+            capabilities.supports_document_symbols |= offered.supports_document_symbols;
+            capabilities.supports_hover |= offered.supports_hover;
+            capabilities.supports_call_hierarchy |= offered.supports_call_hierarchy;
+            capabilities.supports_workspace_symbols |= offered.supports_workspace_symbols;
+        }
 
         Ok(NavCapabilitiesResponse {
             language: ctx.language.clone(),
-            provider: provider
-                .as_ref()
-                .map(|provider| provider.provider_id().to_string())
-                .unwrap_or_else(|| "fallback".to_string()),
+            provider: provider_id.unwrap_or("fallback").to_string(),
             supports_definition: capabilities.supports_definition,
             supports_references: capabilities.supports_references,
             supports_document_symbols: capabilities.supports_document_symbols,
+            supports_hover: capabilities.supports_hover,
+            supports_call_hierarchy: capabilities.supports_call_hierarchy,
+            supports_workspace_symbols: capabilities.supports_workspace_symbols,
             fallback_available: true,
         })
     }
@@ -71,30 +79,26 @@ impl CodeNavManager {
         }
 
         let ctx = build_project_context(&request.project_root, &request.file_path)?;
-        let provider = self.resolve_provider(&ctx);
-
-        if let Some(provider) = provider {
-            let capabilities = provider.capabilities(&ctx);
-            if capabilities.supports_definition {
-                if let Ok(locations) = provider.definition(&ctx, request).await {
-                    if !locations.is_empty() {
-                        return Ok(NavLocationsResponse {
-                            provider: provider.provider_id().to_string(),
-                            language: ctx.language.clone(),
-                            mode: provider.definition_mode().to_string(),
-                            token: None,
-                            locations,
-                        });
-                    }
+        let providers = self.resolve_providers(&ctx);
+
+        for provider in &providers {
+            if !provider.capabilities(&ctx).supports_definition {
+                continue;
+            }
+            if let Ok(locations) = provider.definition(&ctx, request).await {
+                if !locations.is_empty() {
+                    return Ok(NavLocationsResponse {
+                        provider: provider.provider_id().to_string(),
+                        language: ctx.language.clone(),
+                        mode: provider.definition_mode().to_string(),
+                        token: None,
+                        locations,
+                    });
                 }
-                return fallback_definition_blocking(ctx, request.clone(), provider.provider_id())
-                    .await;
             }
-            return fallback_definition_blocking(ctx, request.clone(), provider.provider_id())
-                .await;
         }
 
-        fallback_definition_blocking(ctx, request.clone(), "fallback").await
+        fallback_definition_blocking(ctx, request.clone(), fallback_provider_id(&providers)).await
     }
 
     pub async fn references(
@@ -106,30 +110,26 @@ impl CodeNavManager {
         }
 
         let ctx = build_project_context(&request.project_root, &request.file_path)?;
-        let provider = self.resolve_provider(&ctx);
-
-        if let Some(provider) = provider {
-            let capabilities = provider.capabilities(&ctx);
-            if capabilities.supports_references {
-                if let Ok(locations) = provider.references(&ctx, request).await {
-                    if !locations.is_empty() {
-                        return Ok(NavLocationsResponse {
-                            provider: provider.provider_id().to_string(),
-                            language: ctx.language.clone(),
-                            mode: provider.references_mode().to_string(),
-                            token: None,
-                            locations,
-                        });
-                    }
+        let providers = self.resolve_providers(&ctx);
+
+        for provider in &providers {
+            if !provider.capabilities(&ctx).supports_references {
+                continue;
+            }
+            if let Ok(locations) = provider.references(&ctx, request).await {
+                if !locations.is_empty() {
+                    return Ok(NavLocationsResponse {
+                        provider: provider.provider_id().to_string(),
+                        language: ctx.language.clone(),
+                        mode: provider.references_mode().to_string(),
+                        token: None,
+                        locations,
+                    });
                 }
-                return fallback_references_blocking(ctx, request.clone(), provider.provider_id())
-                    .await;
             }
-            return fallback_references_blocking(ctx, request.clone(), provider.provider_id())
-                .await;
         }
 
-        fallback_references_blocking(ctx, request.clone(), "fallback").await
+        fallback_references_blocking(ctx, request.clone(), fallback_provider_id(&providers)).await
     }
 
     pub async fn document_symbols(
@@ -141,48 +141,46 @@ impl CodeNavManager {
         }
 
         let ctx = build_project_context(&request.project_root, &request.file_path)?;
-        let provider = self.resolve_provider(&ctx);
-
-        if let Some(provider) = provider {
-            let capabilities = provider.capabilities(&ctx);
-            if capabilities.supports_document_symbols {
-                if let Ok(response) = provider.document_symbols(&ctx, request).await {
-                    if !response.symbols.is_empty() {
-                        return Ok(response);
-                    }
+        let providers = self.resolve_providers(&ctx);
+
+        for provider in &providers {
+            if !provider.capabilities(&ctx).supports_document_symbols {
+                continue;
+            }
+            if let Ok(response) = provider.document_symbols(&ctx, request).await {
+                if !response.symbols.is_empty() {
+                    return Ok(response);
                 }
-                return fallback_document_symbols_blocking(
-                    ctx,
-                    request.clone(),
-                    provider.provider_id(),
-                )
-                .await;
             }
-            return fallback_document_symbols_blocking(
-                ctx,
-                request.clone(),
-                provider.provider_id(),
-            )
-            .await;
         }
 
-        fallback_document_symbols_blocking(ctx, request.clone(), "fallback").await
+        fallback_document_symbols_blocking(ctx, request.clone(), fallback_provider_id(&providers))
+            .await
     }
 
-    fn resolve_provider(&self, ctx: &ProjectContext) -> Option<Arc<dyn CodeNavProvider>> {
-        self.providers
+    /// Providers that handle the file, in the order they are tried: those that
+    /// recognize the project first, registry order otherwise.
+    fn resolve_providers(&self, ctx: &ProjectContext) -> Vec<Arc<dyn CodeNavProvider>> {
+        let (mut detected, rest): (Vec<_>, Vec<_>) = self
+            .providers
             .iter()
-            .find(|provider| provider.supports_file(&ctx.file_path) && provider.detect_project(ctx))
+            .filter(|provider| provider.supports_file(&ctx.file_path))
             .cloned()
-            .or_else(|| {
-                self.providers
-                    .iter()
-                    .find(|provider| provider.supports_file(&ctx.file_path))
-                    .cloned()
-            })
+            .partition(|provider| provider.detect_project(ctx));
+        detected.extend(rest);
+        detected
     }
 }
 
+/// The generic fallback reports the language provider it stood in for, which
+/// is the last one tried.
+fn fallback_provider_id(providers: &[Arc<dyn CodeNavProvider>]) -> &'static str {
+    providers
+        .last()
+        .map(|provider| provider.provider_id())
+        .unwrap_or("fallback")
+}
+
 async fn fallback_definition_blocking(
     ctx: ProjectContext,
     request: NavPositionRequest,
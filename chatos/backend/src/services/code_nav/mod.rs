@@ -5,6 +5,7 @@ pub mod fallback;
 pub(crate) mod file_limits;
 pub mod languages;
 pub mod local_connector;
+pub mod lsp;
 pub mod manager;
 pub mod registry;
 pub mod symbol_index;
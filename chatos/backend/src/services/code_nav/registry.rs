@@ -13,10 +13,14 @@ use super::languages::kotlin::KotlinCodeNavProvider;
 use super::languages::python::PythonCodeNavProvider;
 use super::languages::rust::RustCodeNavProvider;
 use super::languages::typescript::TypeScriptCodeNavProvider;
+use super::lsp::default_lsp_providers;
 use super::CodeNavProvider;
 
+/// Language servers come first; a file they cannot serve falls through to the
+/// regex providers of the same language.
 pub fn default_providers() -> Vec<Arc<dyn CodeNavProvider>> {
-    vec![
+    let mut providers = default_lsp_providers();
+    providers.extend::<[Arc<dyn CodeNavProvider>; 10]>([
         Arc::new(JavaCodeNavProvider),
         Arc::new(KotlinCodeNavProvider),
         Arc::new(TypeScriptCodeNavProvider),
@@ -27,5 +31,6 @@ pub fn default_providers() -> Vec<Arc<dyn CodeNavProvider>> {
         Arc::new(RustCodeNavProvider),
         Arc::new(GoCodeNavProvider),
         Arc::new(PythonCodeNavProvider),
-    ]
+    ]);
+    providers
 }
@@ -26,11 +26,17 @@ pub struct DocumentSymbolsRequest {
     pub file_path: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct NavCapabilities {
     pub supports_definition: bool,
     pub supports_references: bool,
     pub supports_document_symbols: bool,
+    #[serde(default)]
+    pub supports_hover: bool,
+    #[serde(default)]
+    pub supports_call_hierarchy: bool,
+    #[serde(default)]
+    pub supports_workspace_symbols: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -40,6 +46,9 @@ pub struct NavCapabilitiesResponse {
     pub supports_definition: bool,
     pub supports_references: bool,
     pub supports_document_symbols: bool,
+    pub supports_hover: bool,
+    pub supports_call_hierarchy: bool,
+    pub supports_workspace_symbols: bool,
     pub fallback_available: bool,
 }
 
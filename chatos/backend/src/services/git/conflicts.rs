@@ -0,0 +1,302 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::HashMap;
+
+use super::contracts::{
+    GitConflictFile, GitConflictHunk, GitHunkResolution, GitResolveConflictRequest,
+};
+use super::validation::validate_relative_paths;
+
+pub(super) const CONFLICTED_PATHS_ARGS: [&str; 4] =
+    ["diff", "--name-only", "--diff-filter=U", "-z"];
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR_MARKER: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+enum HunkSection {
+    Ours,
+    Base,
+    Theirs,
+}
+
+struct ParsedHunk {
+    hunk: GitConflictHunk,
+    start: usize,
+    end: usize,
+}
+
+pub(super) enum ConflictResolution<'a> {
+    /// `--ours` or `--theirs` for `git checkout`.
+    Side(&'static str),
+    Content(&'a str),
+    Hunks(&'a [GitHunkResolution]),
+}
+
+pub(super) fn conflict_resolution(
+    request: &GitResolveConflictRequest,
+) -> Result<(String, ConflictResolution<'_>), String> {
+    let path = validate_relative_paths(std::slice::from_ref(&request.path))?.remove(0);
+    let choice = request
+        .choice
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    let hunks = request.hunks.as_deref().filter(|hunks| !hunks.is_empty());
+    let resolution = match (choice, request.content.as_deref(), hunks) {
+        (Some("ours"), None, None) => ConflictResolution::Side("--ours"),
+        (Some("theirs"), None, None) => ConflictResolution::Side("--theirs"),
+        (Some(_), None, None) => return Err("不支持的冲突解决方式".to_string()),
+        (None, Some(content), None) => ConflictResolution::Content(content),
+        (None, None, Some(hunks)) => ConflictResolution::Hunks(hunks),
+        (None, None, None) => return Err("choice、content 或 hunks 不能为空".to_string()),
+        _ => return Err("choice、content 和 hunks 只能选择一种，参数不合法".to_string()),
+    };
+    Ok((path, resolution))
+}
+
+/// Paths git still reports as unmerged, optionally narrowed to one file.
+pub(super) fn conflicted_paths(raw: &str, only: Option<&str>) -> Result<Vec<String>, String> {
+    let only = match only.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(path) => Some(validate_relative_paths(&[path.to_string()])?.remove(0)),
+        None => None,
+    };
+    Ok(raw
+        .split('\0')
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+        .filter(|path| only.as_deref().is_none_or(|only| only == *path))
+        .map(ToOwned::to_owned)
+        .collect())
+}
+
+pub(super) fn conflict_file(path: String, content: Result<String, String>) -> GitConflictFile {
+    match content {
+        Ok(content) => GitConflictFile {
+            path,
+            hunks: parse_conflict_hunks(content.as_str()),
+            error: None,
+        },
+        Err(err) => GitConflictFile {
+            path,
+            hunks: Vec::new(),
+            error: Some(err),
+        },
+    }
+}
+
+pub(super) fn unresolved_note(remaining: usize) -> String {
+    format!("仍有 {} 个冲突块未解决，文件未暂存", remaining)
+}
+
+/// Finds `<<<<<<<` / `|||||||` / `=======` / `>>>>>>>` blocks in a worktree
+/// file. The base section only exists when git wrote diff3-style markers.
+pub(super) fn parse_conflict_hunks(content: &str) -> Vec<GitConflictHunk> {
+    parse_hunks(content)
+        .into_iter()
+        .map(|parsed| parsed.hunk)
+        .collect()
+}
+
+/// Replaces the chosen hunks and keeps the markers of the others, returning
+/// the new content and how many hunks are still unresolved.
+pub(super) fn apply_hunk_resolutions(
+    content: &str,
+    resolutions: &[GitHunkResolution],
+) -> Result<(String, usize), String> {
+    let parsed = parse_hunks(content);
+    let mut chosen = HashMap::new();
+    for resolution in resolutions {
+        let hunk = parsed
+            .get(resolution.index)
+            .map(|parsed| &parsed.hunk)
+            .ok_or_else(|| format!("冲突块不存在: {}", resolution.index))?;
+        let text = resolved_hunk_text(hunk, resolution)?;
+        if chosen.insert(resolution.index, text).is_some() {
+            return Err(format!("冲突块 {} 重复解决，参数不合法", resolution.index));
+        }
+    }
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let mut out = String::with_capacity(content.len());
+    let mut cursor = 0;
+    for (index, parsed) in parsed.iter().enumerate() {
+        let Some(text) = chosen.get(&index) else {
+            continue;
+        };
+        out.extend(lines[cursor..parsed.start].iter().copied());
+        out.push_str(text);
+        cursor = parsed.end + 1;
+    }
+    out.extend(lines[cursor..].iter().copied());
+    Ok((out, parsed.len() - chosen.len()))
+}
+
+fn resolved_hunk_text(
+    hunk: &GitConflictHunk,
+    resolution: &GitHunkResolution,
+) -> Result<String, String> {
+    match resolution.choice.trim() {
+        "ours" => Ok(hunk.ours.clone()),
+        "theirs" => Ok(hunk.theirs.clone()),
+        "both" => Ok(format!("{}{}", hunk.ours, hunk.theirs)),
+        "base" => hunk
+            .base
+            .clone()
+            .ok_or_else(|| format!("冲突块 {} 不是 diff3 格式，没有 base 内容", hunk.index)),
+        "custom" => resolution
+            .content
+            .clone()
+            .ok_or_else(|| format!("冲突块 {} 的 content 不能为空", hunk.index)),
+        _ => Err("不支持的冲突解决方式".to_string()),
+    }
+}
+
+fn parse_hunks(content: &str) -> Vec<ParsedHunk> {
+    let mut hunks = Vec::new();
+    let mut current: Option<(ParsedHunk, HunkSection)> = None;
+    for (line_index, line) in content.split_inclusive('\n').enumerate() {
+        let marker = line.trim_end_matches(['\n', '\r']);
+        if let Some(label) = marker_label(marker, OURS_MARKER) {
+            current = Some((
+                ParsedHunk {
+                    hunk: GitConflictHunk {
+                        index: hunks.len(),
+                        start_line: line_index + 1,
+                        end_line: line_index + 1,
+                        ours_label: label,
+                        theirs_label: None,
+                        ours: String::new(),
+                        base: None,
+                        theirs: String::new(),
+                    },
+                    start: line_index,
+                    end: line_index,
+                },
+                HunkSection::Ours,
+            ));
+            continue;
+        }
+        let Some((parsed, section)) = current.as_mut() else {
+            continue;
+        };
+        if marker_label(marker, BASE_MARKER).is_some() && matches!(section, HunkSection::Ours) {
+            parsed.hunk.base = Some(String::new());
+            *section = HunkSection::Base;
+        } else if marker == SEPARATOR_MARKER && !matches!(section, HunkSection::Theirs) {
+            *section = HunkSection::Theirs;
+        } else if let Some(label) =
+            marker_label(marker, THEIRS_MARKER).filter(|_| matches!(section, HunkSection::Theirs))
+        {
+            let (mut parsed, _) = current.take().expect("conflict hunk in progress");
+            parsed.hunk.theirs_label = label;
+            parsed.hunk.end_line = line_index + 1;
+            parsed.end = line_index;
+            hunks.push(parsed);
+        } else {
+            match section {
+                HunkSection::Ours => parsed.hunk.ours.push_str(line),
+                HunkSection::Base => parsed
+                    .hunk
+                    .base
+                    .get_or_insert_with(String::new)
+                    .push_str(line),
+                HunkSection::Theirs => parsed.hunk.theirs.push_str(line),
+            }
+        }
+    }
+    hunks
+}
+
+/// Markers are exactly seven characters, optionally followed by a space and
+/// a label such as `HEAD` or a commit subject.
+fn marker_label(line: &str, marker: &str) -> Option<Option<String>> {
+    let rest = line.strip_prefix(marker)?;
+    if rest.is_empty() {
+        return Some(None);
+    }
+    let label = rest.strip_prefix(' ')?.trim();
+    Some((!label.is_empty()).then(|| label.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_hunk_resolutions, parse_conflict_hunks};
+    use crate::services::git::contracts::GitHunkResolution;
+
+    const DIFF3: &str = "\
+fn main() {
+<<<<<<< HEAD
+    let a = 1;
+||||||| base
+    let a = 0;
+=======
+    let a = 2;
+>>>>>>> feature
+    run();
+<<<<<<< HEAD
+    ours();
+=======
+    theirs();
+>>>>>>> feature
+}
+";
+
+    fn resolution(index: usize, choice: &str, content: Option<&str>) -> GitHunkResolution {
+        GitHunkResolution {
+            index,
+            choice: choice.to_string(),
+            content: content.map(ToOwned::to_owned),
+        }
+    }
+
+    #[test]
+    fn parses_diff3_and_merge_style_hunks() {
+        let hunks = parse_conflict_hunks(DIFF3);
+        assert_eq!(hunks.len(), 2);
+        assert_eq!(hunks[0].start_line, 2);
+        assert_eq!(hunks[0].end_line, 8);
+        assert_eq!(hunks[0].ours_label.as_deref(), Some("HEAD"));
+        assert_eq!(hunks[0].theirs_label.as_deref(), Some("feature"));
+        assert_eq!(hunks[0].ours, "    let a = 1;\n");
+        assert_eq!(hunks[0].base.as_deref(), Some("    let a = 0;\n"));
+        assert_eq!(hunks[0].theirs, "    let a = 2;\n");
+        assert_eq!(hunks[1].index, 1);
+        assert_eq!(hunks[1].base, None);
+        assert_eq!(hunks[1].theirs, "    theirs();\n");
+
+        assert!(parse_conflict_hunks("<<<<<<< HEAD\nunterminated\n").is_empty());
+    }
+
+    #[test]
+    fn applies_per_hunk_resolutions_and_keeps_the_rest() {
+        let (partial, remaining) =
+            apply_hunk_resolutions(DIFF3, &[resolution(1, "both", None)]).expect("resolve one");
+        assert_eq!(remaining, 1);
+        assert!(partial.contains("    ours();\n    theirs();\n}\n"));
+        assert_eq!(parse_conflict_hunks(partial.as_str()).len(), 1);
+
+        let (resolved, remaining) = apply_hunk_resolutions(
+            DIFF3,
+            &[
+                resolution(0, "base", None),
+                resolution(1, "custom", Some("    merged();\n")),
+            ],
+        )
+        .expect("resolve all");
+        assert_eq!(remaining, 0);
+        assert_eq!(
+            resolved,
+            "fn main() {\n    let a = 0;\n    run();\n    merged();\n}\n"
+        );
+
+        assert!(apply_hunk_resolutions(DIFF3, &[resolution(2, "ours", None)]).is_err());
+        assert!(apply_hunk_resolutions(DIFF3, &[resolution(1, "base", None)]).is_err());
+        assert!(apply_hunk_resolutions(
+            DIFF3,
+            &[resolution(0, "ours", None), resolution(0, "theirs", None)]
+        )
+        .is_err());
+    }
+}
@@ -87,6 +87,105 @@ pub struct GitDiffQuery {
     pub staged: Option<bool>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitLogQuery {
+    pub root: String,
+    pub revision: Option<String>,
+    pub path: Option<String>,
+    pub skip: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitShowQuery {
+    pub root: String,
+    pub revision: String,
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitBlameQuery {
+    pub root: String,
+    pub path: String,
+    pub revision: Option<String>,
+    pub start_line: Option<usize>,
+    pub end_line: Option<usize>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitStashSaveRequest {
+    pub root: String,
+    pub message: Option<String>,
+    pub include_untracked: Option<bool>,
+    pub paths: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitStashRequest {
+    pub root: String,
+    pub index: Option<usize>,
+    pub pop: Option<bool>,
+    pub restore_index: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitConflictsQuery {
+    pub root: String,
+    pub path: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitHunkResolution {
+    pub index: usize,
+    /// `ours`, `theirs`, `base`, `both` or `custom`.
+    pub choice: String,
+    pub content: Option<String>,
+}
+
+/// Exactly one of `choice` (whole file, `ours` or `theirs`), `content`
+/// (whole file) or `hunks` is used. The file is staged once no conflict
+/// markers remain unless `stage` is false.
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitResolveConflictRequest {
+    pub root: String,
+    pub path: String,
+    pub choice: Option<String>,
+    pub content: Option<String>,
+    pub hunks: Option<Vec<GitHunkResolution>>,
+    pub stage: Option<bool>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitOperationRequest {
+    pub root: String,
+    /// `merge`, `rebase` or `cherry-pick`; detected from the repository
+    /// when omitted.
+    pub operation: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitRebaseStep {
+    /// `pick`, `squash`, `fixup` or `drop`.
+    pub action: String,
+    pub commit: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitRebaseRequest {
+    pub root: String,
+    pub upstream: String,
+    pub onto: Option<String>,
+    /// Replaces the rebase todo list, oldest commit first.
+    pub steps: Option<Vec<GitRebaseStep>>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GitCherryPickRequest {
+    pub root: String,
+    pub commits: Vec<String>,
+    pub no_commit: Option<bool>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitChangeCounts {
     pub staged: usize,
@@ -191,6 +290,98 @@ pub struct GitFileDiff {
     pub patch: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitLogCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub parents: Vec<String>,
+    pub author_name: String,
+    pub author_email: String,
+    pub authored_at: String,
+    pub committer_name: String,
+    pub committed_at: String,
+    pub subject: String,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitLogResult {
+    pub revision: Option<String>,
+    pub path: Option<String>,
+    pub skip: usize,
+    pub limit: usize,
+    pub has_more: bool,
+    pub commits: Vec<GitLogCommit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitCommitDetail {
+    pub commit: GitLogCommit,
+    pub files: Vec<GitDiffFile>,
+    pub patch: String,
+    pub patch_truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitBlameLine {
+    pub line: usize,
+    pub original_line: usize,
+    pub hash: String,
+    pub author_name: String,
+    pub author_email: String,
+    pub authored_at: Option<String>,
+    pub summary: String,
+    pub original_path: Option<String>,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitBlameResult {
+    pub path: String,
+    pub revision: Option<String>,
+    pub lines: Vec<GitBlameLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitStashEntry {
+    pub index: usize,
+    pub name: String,
+    pub hash: String,
+    pub branch: Option<String>,
+    pub message: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitStashList {
+    pub entries: Vec<GitStashEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitConflictHunk {
+    pub index: usize,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub ours_label: Option<String>,
+    pub theirs_label: Option<String>,
+    pub ours: String,
+    pub base: Option<String>,
+    pub theirs: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitConflictFile {
+    pub path: String,
+    pub hunks: Vec<GitConflictHunk>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitConflicts {
+    pub operation_state: Option<String>,
+    pub files: Vec<GitConflictFile>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GitActionResult {
     pub success: bool,
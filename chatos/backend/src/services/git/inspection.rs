@@ -1,13 +1,14 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use tokio::fs;
 
 use super::process::{git_output, DEFAULT_GIT_TIMEOUT};
 
 const MAX_UNTRACKED_DIFF_BYTES: u64 = 256 * 1024;
+pub(super) const MAX_CONFLICT_FILE_BYTES: u64 = 2 * 1024 * 1024;
 
 pub(super) async fn ahead_behind(
     repo_root: &Path,
@@ -98,3 +99,48 @@ pub(super) async fn untracked_file_patch(repo_root: &Path, path: &str) -> Result
     }
     Ok(patch)
 }
+
+/// Reads a conflicted worktree file after checking it resolves inside the
+/// repository.
+pub(super) async fn read_worktree_file(repo_root: &Path, path: &str) -> Result<String, String> {
+    let canonical_path = worktree_file_path(repo_root, path).await?;
+    let bytes = fs::read(canonical_path.as_path())
+        .await
+        .map_err(|err| format!("读取冲突文件失败: {}", err))?;
+    String::from_utf8(bytes).map_err(|_| "冲突文件不是文本文件".to_string())
+}
+
+pub(super) async fn write_worktree_file(
+    repo_root: &Path,
+    path: &str,
+    content: &str,
+) -> Result<(), String> {
+    let canonical_path = worktree_file_path(repo_root, path).await?;
+    fs::write(canonical_path.as_path(), content)
+        .await
+        .map_err(|err| format!("写入冲突文件失败: {}", err))
+}
+
+async fn worktree_file_path(repo_root: &Path, path: &str) -> Result<PathBuf, String> {
+    let absolute_path = repo_root.join(path);
+    let metadata = fs::symlink_metadata(absolute_path.as_path())
+        .await
+        .map_err(|err| format!("读取冲突文件失败: {}", err))?;
+    if !metadata.is_file() {
+        return Err("冲突路径不是普通文件".to_string());
+    }
+    if metadata.len() > MAX_CONFLICT_FILE_BYTES {
+        return Err(format!(
+            "冲突文件过大（{} bytes），请在本地编辑器中解决",
+            metadata.len()
+        ));
+    }
+    let canonical_path = std::fs::canonicalize(absolute_path.as_path())
+        .map_err(|err| format!("解析冲突文件路径失败: {}", err))?;
+    let canonical_repo_root = std::fs::canonicalize(repo_root)
+        .map_err(|err| format!("解析 Git 仓库路径失败: {}", err))?;
+    if !canonical_path.starts_with(canonical_repo_root.as_path()) {
+        return Err("冲突文件不在 Git 仓库内，已拒绝访问".to_string());
+    }
+    Ok(canonical_path)
+}
@@ -5,17 +5,30 @@ use std::path::{Path, PathBuf};
 
 use serde_json::{json, Value};
 
+use super::conflicts::{
+    apply_hunk_resolutions, conflict_file, conflict_resolution, conflicted_paths,
+    parse_conflict_hunks, unresolved_note, ConflictResolution, CONFLICTED_PATHS_ARGS,
+};
 use super::contracts::*;
+use super::inspection::MAX_CONFLICT_FILE_BYTES;
 use super::parsing::{
-    non_repo_summary, parse_compare_commits, parse_name_status_z, parse_status_files,
-    split_remote_branch, summary_from_status,
+    log_result, non_repo_summary, parse_blame_porcelain, parse_compare_commits, parse_log_commits,
+    parse_name_status_z, parse_stash_entries, parse_status_files, split_remote_branch,
+    summary_from_status, truncate_patch, STASH_LIST_FORMAT,
 };
 use super::process::{DEFAULT_GIT_TIMEOUT, REMOTE_GIT_TIMEOUT};
-use super::validation::{ensure_safe_ref, merge_args, validate_relative_paths};
+use super::validation::{
+    blame_args, cherry_pick_args, commit_diff_args, ensure_safe_ref, log_args, log_page,
+    merge_args, operation_step_args, rebase_args, resolve_operation, show_commit_args,
+    stash_apply_args, stash_push_args, stash_ref, validate_relative_paths, with_conflict_style,
+    OPERATION_HEADS,
+};
 use crate::api::local_connectors::{
     call_local_mcp_tool, parse_local_connector_root_path, LOCAL_CONNECTOR_BUILTIN_TERMINAL,
 };
 
+const CONNECTOR_WRITE_LIMIT_BYTES: usize = 96 * 1024;
+
 #[derive(Debug, Clone)]
 struct LocalGitOutput {
     stdout: String,
@@ -407,10 +420,7 @@ pub async fn merge(request: GitMergeRequest) -> Result<GitActionResult, String>
         return Err("branch 不能为空".to_string());
     }
     ensure_safe_ref(branch, "branch")?;
-    let args = merge_args(request.mode.as_deref(), branch)?
-        .into_iter()
-        .map(ToOwned::to_owned)
-        .collect::<Vec<_>>();
+    let args = with_conflict_style(&merge_args(request.mode.as_deref(), branch)?);
     action_result(
         request.root.as_str(),
         git_exec(
@@ -503,6 +513,303 @@ pub async fn discard(request: GitPathRequest) -> Result<GitActionResult, String>
     .await
 }
 
+pub async fn log(query: GitLogQuery) -> Result<GitLogResult, String> {
+    let output = git_exec(
+        query.root.as_str(),
+        log_args(&query)?,
+        DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+    )
+    .await?;
+    let page = log_page(&query);
+    Ok(log_result(query, page, output.stdout.as_str()))
+}
+
+pub async fn show_commit(query: GitShowQuery) -> Result<GitCommitDetail, String> {
+    let output = git_exec(
+        query.root.as_str(),
+        show_commit_args(query.revision.as_str())?,
+        DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+    )
+    .await?;
+    let commit = parse_log_commits(output.stdout.as_str())
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("提交不存在: {}", query.revision.trim()))?;
+    let files_output = git_exec(
+        query.root.as_str(),
+        commit_diff_args(&commit, true, query.path.as_deref())?,
+        DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+    )
+    .await?;
+    let patch_output = git_exec(
+        query.root.as_str(),
+        commit_diff_args(&commit, false, query.path.as_deref())?,
+        DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+    )
+    .await?;
+    let (patch, patch_truncated) = truncate_patch(patch_output.stdout);
+    Ok(GitCommitDetail {
+        commit,
+        files: parse_name_status_z(files_output.stdout.as_str()),
+        patch,
+        patch_truncated,
+    })
+}
+
+pub async fn blame(query: GitBlameQuery) -> Result<GitBlameResult, String> {
+    let (path, args) = blame_args(&query)?;
+    let output = git_exec(
+        query.root.as_str(),
+        args,
+        DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+    )
+    .await?;
+    Ok(GitBlameResult {
+        path,
+        revision: query.revision.as_deref().and_then(non_empty),
+        lines: parse_blame_porcelain(output.stdout.as_str()),
+    })
+}
+
+pub async fn stash_list(root: &str) -> Result<GitStashList, String> {
+    let output = git_exec(
+        root,
+        vec![
+            "stash".to_string(),
+            "list".to_string(),
+            STASH_LIST_FORMAT.to_string(),
+        ],
+        DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+    )
+    .await?;
+    Ok(GitStashList {
+        entries: parse_stash_entries(output.stdout.as_str()),
+    })
+}
+
+pub async fn stash_save(request: GitStashSaveRequest) -> Result<GitActionResult, String> {
+    action_result(
+        request.root.as_str(),
+        git_exec(
+            request.root.as_str(),
+            stash_push_args(&request)?,
+            DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+        )
+        .await?,
+    )
+    .await
+}
+
+pub async fn stash_apply(request: GitStashRequest) -> Result<GitActionResult, String> {
+    let args = stash_apply_args(
+        request.index,
+        request.pop.unwrap_or(false),
+        request.restore_index.unwrap_or(false),
+    );
+    action_result(
+        request.root.as_str(),
+        git_exec_allow_failure(
+            request.root.as_str(),
+            args,
+            DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+        )
+        .await?,
+    )
+    .await
+}
+
+pub async fn stash_drop(request: GitStashRequest) -> Result<GitActionResult, String> {
+    action_result(
+        request.root.as_str(),
+        git_exec(
+            request.root.as_str(),
+            vec![
+                "stash".to_string(),
+                "drop".to_string(),
+                stash_ref(request.index),
+            ],
+            DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+        )
+        .await?,
+    )
+    .await
+}
+
+pub async fn conflicts(query: GitConflictsQuery) -> Result<GitConflicts, String> {
+    let root = query.root.as_str();
+    let output = git_exec(
+        root,
+        CONFLICTED_PATHS_ARGS.map(ToOwned::to_owned).to_vec(),
+        DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+    )
+    .await?;
+    let mut files = Vec::new();
+    for path in conflicted_paths(output.stdout.as_str(), query.path.as_deref())? {
+        let content = read_worktree_file(root, path.as_str()).await;
+        files.push(conflict_file(path, content));
+    }
+    Ok(GitConflicts {
+        operation_state: in_progress_operation(root).await?.map(ToOwned::to_owned),
+        files,
+    })
+}
+
+pub async fn resolve_conflict(
+    request: GitResolveConflictRequest,
+) -> Result<GitActionResult, String> {
+    let root = request.root.as_str();
+    let (path, resolution) = conflict_resolution(&request)?;
+    let remaining = match resolution {
+        ConflictResolution::Side(side) => {
+            git_exec(
+                root,
+                vec![
+                    "checkout".to_string(),
+                    side.to_string(),
+                    "--".to_string(),
+                    path.clone(),
+                ],
+                DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+            )
+            .await?;
+            0
+        }
+        ConflictResolution::Content(content) => {
+            write_worktree_file(root, path.as_str(), content).await?;
+            parse_conflict_hunks(content).len()
+        }
+        ConflictResolution::Hunks(hunks) => {
+            let current = read_worktree_file(root, path.as_str()).await?;
+            let (content, remaining) = apply_hunk_resolutions(current.as_str(), hunks)?;
+            write_worktree_file(root, path.as_str(), content.as_str()).await?;
+            remaining
+        }
+    };
+    let output = if remaining > 0 {
+        LocalGitOutput {
+            stdout: unresolved_note(remaining),
+            stderr: String::new(),
+            success: true,
+        }
+    } else if request.stage.unwrap_or(true) {
+        git_exec(
+            root,
+            vec!["add".to_string(), "--".to_string(), path],
+            DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+        )
+        .await?
+    } else {
+        LocalGitOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+            success: true,
+        }
+    };
+    action_result(root, output).await
+}
+
+pub async fn operation_step(
+    request: GitOperationRequest,
+    abort: bool,
+) -> Result<GitActionResult, String> {
+    let root = request.root.as_str();
+    let operation = resolve_operation(
+        request.operation.as_deref(),
+        in_progress_operation(root).await?,
+    )?;
+    action_result(
+        root,
+        git_exec_allow_failure(
+            root,
+            operation_step_args(operation, abort),
+            REMOTE_GIT_TIMEOUT.as_millis() as u64,
+        )
+        .await?,
+    )
+    .await
+}
+
+pub async fn rebase(request: GitRebaseRequest) -> Result<GitActionResult, String> {
+    let args = rebase_args(&request)?;
+    run_history_operation(request.root.as_str(), args).await
+}
+
+pub async fn cherry_pick(request: GitCherryPickRequest) -> Result<GitActionResult, String> {
+    let args = cherry_pick_args(&request)?;
+    run_history_operation(request.root.as_str(), args).await
+}
+
+async fn run_history_operation(root: &str, args: Vec<String>) -> Result<GitActionResult, String> {
+    if let Some(operation) = in_progress_operation(root).await? {
+        return Err(format!("当前已有 Git 操作未完成: {}", operation));
+    }
+    action_result(
+        root,
+        git_exec_allow_failure(root, args, REMOTE_GIT_TIMEOUT.as_millis() as u64).await?,
+    )
+    .await
+}
+
+async fn in_progress_operation(root: &str) -> Result<Option<&'static str>, String> {
+    for (head, operation) in OPERATION_HEADS {
+        let output = git_exec_allow_failure(
+            root,
+            vec![
+                "rev-parse".to_string(),
+                "-q".to_string(),
+                "--verify".to_string(),
+                head.to_string(),
+            ],
+            DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+        )
+        .await?;
+        if output.success {
+            return Ok(Some(operation));
+        }
+    }
+    Ok(None)
+}
+
+async fn read_worktree_file(root: &str, path: &str) -> Result<String, String> {
+    let output = connector_exec(
+        root,
+        format!("cat -- {}", shell_quote(path)),
+        DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+    )
+    .await
+    .map_err(|err| format!("读取冲突文件失败: {}", err))?;
+    if output.stdout.len() as u64 > MAX_CONFLICT_FILE_BYTES {
+        return Err(format!(
+            "冲突文件过大（{} bytes），请在本地编辑器中解决",
+            output.stdout.len()
+        ));
+    }
+    Ok(output.stdout)
+}
+
+/// The content travels inside the shell command, so it is capped well below
+/// the platform's argument size limit.
+async fn write_worktree_file(root: &str, path: &str, content: &str) -> Result<(), String> {
+    if content.len() > CONNECTOR_WRITE_LIMIT_BYTES {
+        return Err(format!(
+            "冲突文件过大（{} bytes），无法通过 Local Connector 写入",
+            content.len()
+        ));
+    }
+    connector_exec(
+        root,
+        format!(
+            "printf '%s' {} > {}",
+            shell_quote(content),
+            shell_quote(path)
+        ),
+        DEFAULT_GIT_TIMEOUT.as_millis() as u64,
+    )
+    .await
+    .map(|_| ())
+    .map_err(|err| format!("写入冲突文件失败: {}", err))
+}
+
 async fn current_branch(root: &str) -> Result<String, String> {
     let output = git_exec(
         root,
@@ -527,7 +834,23 @@ async fn git_exec(
     args: Vec<String>,
     timeout_ms: u64,
 ) -> Result<LocalGitOutput, String> {
-    let output = git_exec_allow_failure(root, args, timeout_ms).await?;
+    connector_exec(root, git_command(args.as_slice()), timeout_ms).await
+}
+
+async fn git_exec_allow_failure(
+    root: &str,
+    args: Vec<String>,
+    timeout_ms: u64,
+) -> Result<LocalGitOutput, String> {
+    connector_exec_allow_failure(root, git_command(args.as_slice()), timeout_ms).await
+}
+
+async fn connector_exec(
+    root: &str,
+    command: String,
+    timeout_ms: u64,
+) -> Result<LocalGitOutput, String> {
+    let output = connector_exec_allow_failure(root, command, timeout_ms).await?;
     if output.success {
         return Ok(output);
     }
@@ -536,14 +859,13 @@ async fn git_exec(
         .unwrap_or_else(|| "Local Connector Git 命令执行失败".to_string()))
 }
 
-async fn git_exec_allow_failure(
+async fn connector_exec_allow_failure(
     root: &str,
-    args: Vec<String>,
+    command: String,
     timeout_ms: u64,
 ) -> Result<LocalGitOutput, String> {
     let root_ref = parse_local_connector_root_path(root)
         .ok_or_else(|| "Local Connector root 格式错误".to_string())?;
-    let command = git_command(args.as_slice());
     let value = call_local_mcp_tool(
         root_ref.device_id.as_str(),
         root_ref.workspace_id.as_str(),
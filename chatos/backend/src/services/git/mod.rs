@@ -3,6 +3,7 @@
 
 pub mod contracts;
 
+mod conflicts;
 mod inspection;
 mod local_connector;
 mod parsing;
@@ -13,8 +14,13 @@ mod validation;
 mod write_ops;
 
 pub use contracts::*;
-pub use query_ops::{branches, client_info, compare, file_diff, status, summary};
+pub use query_ops::{
+    blame, branches, client_info, compare, conflicts, file_diff, log, show_commit, stash_list,
+    status, summary,
+};
 pub use validation::discover_repo_root;
 pub use write_ops::{
-    checkout, commit, create_branch, discard, fetch, merge, pull, push, stage, unstage,
+    abort_operation, checkout, cherry_pick, commit, continue_operation, create_branch, discard,
+    fetch, merge, pull, push, rebase, resolve_conflict, stage, stash_apply, stash_drop, stash_save,
+    unstage,
 };
@@ -1,11 +1,22 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
-use super::contracts::{GitChangeCounts, GitCompareCommit, GitDiffFile, GitStatusFile, GitSummary};
+use chrono::{DateTime, FixedOffset};
+
+use super::contracts::{
+    GitBlameLine, GitChangeCounts, GitCompareCommit, GitDiffFile, GitLogCommit, GitLogQuery,
+    GitLogResult, GitStashEntry, GitStatusFile, GitSummary,
+};
 use crate::services::project_local_cache::is_project_local_cache_relative_path;
 
+pub(super) const LOG_FORMAT: &str =
+    "--format=%H%x1f%h%x1f%P%x1f%an%x1f%ae%x1f%aI%x1f%cn%x1f%cI%x1f%s%x1f%b%x1e";
+pub(super) const STASH_LIST_FORMAT: &str = "--format=%gd%x1f%H%x1f%gs%x1f%cI";
+const COMMIT_PATCH_LIMIT_BYTES: usize = 512 * 1024;
+
 pub(super) fn summary_from_status(repo_root: PathBuf, status: &str) -> GitSummary {
     let mut head = None;
     let mut current_branch = None;
@@ -237,6 +248,190 @@ pub(super) fn parse_compare_commits(raw: &str) -> Vec<GitCompareCommit> {
         .collect()
 }
 
+pub(super) fn log_result(
+    query: GitLogQuery,
+    (skip, limit): (usize, usize),
+    raw: &str,
+) -> GitLogResult {
+    let mut commits = parse_log_commits(raw);
+    let has_more = commits.len() > limit;
+    commits.truncate(limit);
+    GitLogResult {
+        revision: query.revision.and_then(|value| non_empty(value.as_str())),
+        path: query.path.and_then(|value| non_empty(value.as_str())),
+        skip,
+        limit,
+        has_more,
+        commits,
+    }
+}
+
+pub(super) fn parse_log_commits(raw: &str) -> Vec<GitLogCommit> {
+    raw.split('\x1e')
+        .filter_map(|record| {
+            let record = record.trim_start_matches(['\n', '\r']);
+            let fields: Vec<&str> = record.splitn(10, '\x1f').collect();
+            let hash = fields.first()?.trim();
+            if hash.is_empty() || fields.len() < 9 {
+                return None;
+            }
+            let field = |index: usize| fields.get(index).copied().unwrap_or("").trim().to_string();
+            Some(GitLogCommit {
+                hash: hash.to_string(),
+                short_hash: field(1),
+                parents: fields[2]
+                    .split_whitespace()
+                    .map(ToOwned::to_owned)
+                    .collect(),
+                author_name: field(3),
+                author_email: field(4),
+                authored_at: field(5),
+                committer_name: field(6),
+                committed_at: field(7),
+                subject: field(8),
+                body: field(9),
+            })
+        })
+        .collect()
+}
+
+/// Parses `git blame --porcelain`. Commit details are only printed the first
+/// time a commit appears, so later lines reuse what was seen before.
+pub(super) fn parse_blame_porcelain(raw: &str) -> Vec<GitBlameLine> {
+    let mut commits: HashMap<String, GitBlameLine> = HashMap::new();
+    let mut lines = Vec::new();
+    let mut current: Option<(String, usize, usize)> = None;
+    let mut author_time: Option<String> = None;
+    for line in raw.lines() {
+        if let Some(content) = line.strip_prefix('\t') {
+            let Some((hash, original_line, final_line)) = current.take() else {
+                continue;
+            };
+            let Some(info) = commits.get(hash.as_str()) else {
+                continue;
+            };
+            lines.push(GitBlameLine {
+                line: final_line,
+                original_line,
+                content: content.to_string(),
+                ..info.clone()
+            });
+            continue;
+        }
+        if let Some((hash, original_line, final_line)) = parse_blame_header(line) {
+            commits.entry(hash.clone()).or_insert_with(|| GitBlameLine {
+                line: 0,
+                original_line: 0,
+                hash: hash.clone(),
+                author_name: String::new(),
+                author_email: String::new(),
+                authored_at: None,
+                summary: String::new(),
+                original_path: None,
+                content: String::new(),
+            });
+            current = Some((hash, original_line, final_line));
+            continue;
+        }
+        let Some((hash, _, _)) = current.as_ref() else {
+            continue;
+        };
+        let Some(info) = commits.get_mut(hash.as_str()) else {
+            continue;
+        };
+        let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+        match key {
+            "author" => info.author_name = value.to_string(),
+            "author-mail" => {
+                info.author_email = value
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            }
+            "author-time" => author_time = Some(value.to_string()),
+            "author-tz" => {
+                info.authored_at = author_time
+                    .take()
+                    .and_then(|time| blame_timestamp(time.as_str(), value));
+            }
+            "summary" => info.summary = value.to_string(),
+            "filename" => info.original_path = non_empty(value),
+            _ => {}
+        }
+    }
+    lines
+}
+
+fn parse_blame_header(line: &str) -> Option<(String, usize, usize)> {
+    let mut parts = line.split(' ');
+    let hash = parts.next()?;
+    if hash.len() < 40 || !hash.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return None;
+    }
+    let original_line = parts.next()?.parse().ok()?;
+    let final_line = parts.next()?.parse().ok()?;
+    Some((hash.to_string(), original_line, final_line))
+}
+
+fn blame_timestamp(time: &str, tz: &str) -> Option<String> {
+    let seconds = time.trim().parse::<i64>().ok()?;
+    let tz = tz.trim();
+    let sign = if tz.starts_with('-') { -1 } else { 1 };
+    let digits = tz.trim_start_matches(['+', '-']);
+    let hours = digits.get(0..2)?.parse::<i32>().ok()?;
+    let minutes = digits.get(2..4)?.parse::<i32>().ok()?;
+    let offset = FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))?;
+    DateTime::from_timestamp(seconds, 0).map(|time| time.with_timezone(&offset).to_rfc3339())
+}
+
+pub(super) fn parse_stash_entries(raw: &str) -> Vec<GitStashEntry> {
+    raw.lines()
+        .filter_map(|line| {
+            let mut parts = line.splitn(4, '\x1f');
+            let name = parts.next()?.trim();
+            let index = name
+                .strip_prefix("stash@{")?
+                .strip_suffix('}')?
+                .parse::<usize>()
+                .ok()?;
+            let hash = parts.next()?.trim();
+            let subject = parts.next()?.trim();
+            let created_at = parts.next().unwrap_or("").trim();
+            let (branch, message) = split_stash_subject(subject);
+            Some(GitStashEntry {
+                index,
+                name: name.to_string(),
+                hash: hash.to_string(),
+                branch,
+                message,
+                created_at: created_at.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Stash subjects read `On <branch>: <message>` or `WIP on <branch>: <head>`.
+fn split_stash_subject(subject: &str) -> (Option<String>, String) {
+    let rest = subject
+        .strip_prefix("WIP on ")
+        .or_else(|| subject.strip_prefix("On "));
+    match rest.and_then(|rest| rest.split_once(": ")) {
+        Some((branch, message)) => (non_empty(branch), message.trim().to_string()),
+        None => (None, subject.to_string()),
+    }
+}
+
+pub(super) fn truncate_patch(patch: String) -> (String, bool) {
+    if patch.len() <= COMMIT_PATCH_LIMIT_BYTES {
+        return (patch, false);
+    }
+    let mut end = COMMIT_PATCH_LIMIT_BYTES;
+    while !patch.is_char_boundary(end) {
+        end -= 1;
+    }
+    (patch[..end].to_string(), true)
+}
+
 pub(super) fn split_remote_branch(name: &str) -> (Option<String>, Option<String>) {
     let mut parts = name.splitn(2, '/');
     let remote = parts
@@ -379,7 +574,8 @@ mod tests {
     use std::path::PathBuf;
 
     use super::{
-        parse_compare_commits, parse_name_status_z, parse_status_files, summary_from_status,
+        parse_blame_porcelain, parse_compare_commits, parse_log_commits, parse_name_status_z,
+        parse_stash_entries, parse_status_files, summary_from_status, truncate_patch,
     };
 
     #[test]
@@ -455,4 +651,69 @@ mod tests {
         assert_eq!(commits[0].hash, "abc123");
         assert_eq!(commits[1].side, "target");
     }
+
+    #[test]
+    fn parses_log_records_with_multiline_bodies() {
+        let raw = "aaa\u{1f}a\u{1f}p1 p2\u{1f}Ann\u{1f}ann@x\u{1f}2025-01-02T03:04:05+08:00\u{1f}Bob\u{1f}2025-01-03T00:00:00+00:00\u{1f}Merge it\u{1f}line one\nline two\n\u{1e}\n\
+bbb\u{1f}b\u{1f}\u{1f}Ann\u{1f}ann@x\u{1f}2025-01-01T00:00:00+00:00\u{1f}Ann\u{1f}2025-01-01T00:00:00+00:00\u{1f}Initial\u{1f}\u{1e}\n";
+        let commits = parse_log_commits(raw);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].parents, vec!["p1", "p2"]);
+        assert_eq!(commits[0].committer_name, "Bob");
+        assert_eq!(commits[0].body, "line one\nline two");
+        assert_eq!(commits[1].hash, "bbb");
+        assert!(commits[1].parents.is_empty());
+        assert_eq!(commits[1].subject, "Initial");
+    }
+
+    #[test]
+    fn parses_blame_porcelain_reusing_commit_details() {
+        let first = "eb94b9faf6129863b70d8ae3a91555019fbcf9cd";
+        let second = "3073d887c91dea376c04e634b123b7c2bd21b59c";
+        let raw = format!(
+            "{first} 1 1 1\nauthor A B\nauthor-mail <a@b>\nauthor-time 1700000000\nauthor-tz +0800\n\
+summary one\nboundary\nfilename old.txt\n\ta\n\
+{second} 2 2 2\nauthor C\nauthor-mail <c@d>\nauthor-time 1700000000\nauthor-tz -0130\n\
+summary two\nprevious {first} f.txt\nfilename f.txt\n\tc\n\
+{second} 3 3\n\td\n"
+        );
+        let lines = parse_blame_porcelain(raw.as_str());
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].author_email, "a@b");
+        assert_eq!(lines[0].original_path.as_deref(), Some("old.txt"));
+        assert_eq!(
+            lines[0].authored_at.as_deref(),
+            Some("2023-11-15T06:13:20+08:00")
+        );
+        assert_eq!(lines[2].line, 3);
+        assert_eq!(lines[2].hash, second);
+        assert_eq!(lines[2].summary, "two");
+        assert_eq!(lines[2].content, "d");
+        assert_eq!(
+            lines[2].authored_at.as_deref(),
+            Some("2023-11-14T20:43:20-01:30")
+        );
+    }
+
+    #[test]
+    fn parses_stash_entries_and_truncates_large_patches() {
+        let raw =
+            "stash@{0}\u{1f}f62\u{1f}WIP on main: 3073d88 two\u{1f}2026-01-01T00:00:00+00:00\n\
+stash@{1}\u{1f}aec\u{1f}On feature/x: my msg\u{1f}2026-01-01T00:00:00+00:00\n";
+        let entries = parse_stash_entries(raw);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].branch.as_deref(), Some("main"));
+        assert_eq!(entries[0].message, "3073d88 two");
+        assert_eq!(entries[1].index, 1);
+        assert_eq!(entries[1].branch.as_deref(), Some("feature/x"));
+        assert_eq!(entries[1].message, "my msg");
+
+        let (patch, truncated) = truncate_patch("é".repeat(300 * 1024));
+        assert!(truncated);
+        assert!(patch.len() <= 512 * 1024);
+        assert_eq!(
+            truncate_patch("small".to_string()),
+            ("small".to_string(), false)
+        );
+    }
 }
@@ -4,18 +4,21 @@
 use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
+use super::conflicts::{conflict_file, conflicted_paths, CONFLICTED_PATHS_ARGS};
 use super::contracts::*;
-use super::inspection::{ahead_behind, is_tracked_path, untracked_file_patch};
+use super::inspection::{ahead_behind, is_tracked_path, read_worktree_file, untracked_file_patch};
 use super::local_connector;
 use super::parsing::{
-    non_empty, non_repo_summary, parse_compare_commits, parse_name_status_z, parse_status_files,
-    split_remote_branch,
+    log_result, non_empty, non_repo_summary, parse_blame_porcelain, parse_compare_commits,
+    parse_log_commits, parse_name_status_z, parse_stash_entries, parse_status_files,
+    split_remote_branch, truncate_patch, STASH_LIST_FORMAT,
 };
 use super::process::{git_output, git_version, resolve_git_binary, DEFAULT_GIT_TIMEOUT};
-use super::shared::{comparison_range, read_repo_summary};
+use super::shared::{comparison_range, in_progress_operation, read_repo_summary};
 use super::validation::{
-    discover_child_repo_roots, discover_repo_root, parse_optional_root, parse_root,
-    require_repo_root, validate_relative_paths,
+    blame_args, commit_diff_args, discover_child_repo_roots, discover_repo_root, log_args,
+    log_page, parse_optional_root, parse_root, require_repo_root, show_commit_args,
+    validate_relative_paths,
 };
 use crate::services::project_local_cache::is_local_connector_project_root;
 use crate::services::project_local_cache::{cache_key, read_cache_json, write_cache_json};
@@ -479,3 +482,103 @@ pub async fn file_diff(query: GitDiffQuery) -> Result<GitFileDiff, String> {
         patch: output.stdout,
     })
 }
+
+pub async fn log(query: GitLogQuery) -> Result<GitLogResult, String> {
+    if is_local_connector_project_root(query.root.as_str()) {
+        return local_connector::log(query).await;
+    }
+    let repo_root = require_repo_root(&query.root).await?;
+    let output = git_output(repo_root.as_path(), log_args(&query)?, DEFAULT_GIT_TIMEOUT).await?;
+    let page = log_page(&query);
+    Ok(log_result(query, page, output.stdout.as_str()))
+}
+
+pub async fn show_commit(query: GitShowQuery) -> Result<GitCommitDetail, String> {
+    if is_local_connector_project_root(query.root.as_str()) {
+        return local_connector::show_commit(query).await;
+    }
+    let repo_root = require_repo_root(&query.root).await?;
+    let output = git_output(
+        repo_root.as_path(),
+        show_commit_args(query.revision.as_str())?,
+        DEFAULT_GIT_TIMEOUT,
+    )
+    .await?;
+    let commit = parse_log_commits(output.stdout.as_str())
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("提交不存在: {}", query.revision.trim()))?;
+    let files_output = git_output(
+        repo_root.as_path(),
+        commit_diff_args(&commit, true, query.path.as_deref())?,
+        DEFAULT_GIT_TIMEOUT,
+    )
+    .await?;
+    let patch_output = git_output(
+        repo_root.as_path(),
+        commit_diff_args(&commit, false, query.path.as_deref())?,
+        DEFAULT_GIT_TIMEOUT,
+    )
+    .await?;
+    let (patch, patch_truncated) = truncate_patch(patch_output.stdout);
+    Ok(GitCommitDetail {
+        commit,
+        files: parse_name_status_z(files_output.stdout.as_str()),
+        patch,
+        patch_truncated,
+    })
+}
+
+pub async fn blame(query: GitBlameQuery) -> Result<GitBlameResult, String> {
+    if is_local_connector_project_root(query.root.as_str()) {
+        return local_connector::blame(query).await;
+    }
+    let repo_root = require_repo_root(&query.root).await?;
+    let (path, args) = blame_args(&query)?;
+    let output = git_output(repo_root.as_path(), args, DEFAULT_GIT_TIMEOUT).await?;
+    Ok(GitBlameResult {
+        path,
+        revision: query.revision.and_then(|value| non_empty(value.as_str())),
+        lines: parse_blame_porcelain(output.stdout.as_str()),
+    })
+}
+
+pub async fn stash_list(root: &str) -> Result<GitStashList, String> {
+    if is_local_connector_project_root(root) {
+        return local_connector::stash_list(root).await;
+    }
+    let repo_root = require_repo_root(root).await?;
+    let output = git_output(
+        repo_root.as_path(),
+        ["stash", "list", STASH_LIST_FORMAT],
+        DEFAULT_GIT_TIMEOUT,
+    )
+    .await?;
+    Ok(GitStashList {
+        entries: parse_stash_entries(output.stdout.as_str()),
+    })
+}
+
+pub async fn conflicts(query: GitConflictsQuery) -> Result<GitConflicts, String> {
+    if is_local_connector_project_root(query.root.as_str()) {
+        return local_connector::conflicts(query).await;
+    }
+    let repo_root = require_repo_root(&query.root).await?;
+    let output = git_output(
+        repo_root.as_path(),
+        CONFLICTED_PATHS_ARGS,
+        DEFAULT_GIT_TIMEOUT,
+    )
+    .await?;
+    let mut files = Vec::new();
+    for path in conflicted_paths(output.stdout.as_str(), query.path.as_deref())? {
+        let content = read_worktree_file(repo_root.as_path(), path.as_str()).await;
+        files.push(conflict_file(path, content));
+    }
+    Ok(GitConflicts {
+        operation_state: in_progress_operation(repo_root.as_path())
+            .await?
+            .map(ToOwned::to_owned),
+        files,
+    })
+}
@@ -6,8 +6,11 @@ use std::path::Path;
 use super::contracts::{GitActionResult, GitSummary};
 use super::inspection::is_tracked_path;
 use super::parsing::{compact_output, summary_from_status};
-use super::process::{git_output, GitCommandOutput, GitCommandStatusOutput, DEFAULT_GIT_TIMEOUT};
-use super::validation::ensure_safe_ref;
+use super::process::{
+    git_output, git_output_with_status, GitCommandOutput, GitCommandStatusOutput,
+    DEFAULT_GIT_TIMEOUT,
+};
+use super::validation::{ensure_safe_ref, OPERATION_HEADS};
 
 pub(super) async fn read_repo_summary(repo_root: &Path) -> Result<GitSummary, String> {
     let status = git_output(
@@ -53,6 +56,23 @@ pub(super) async fn require_current_branch(
         .ok_or_else(|| format!("当前不是分支状态，无法 {}", action))
 }
 
+pub(super) async fn in_progress_operation(
+    repo_root: &Path,
+) -> Result<Option<&'static str>, String> {
+    for (head, operation) in OPERATION_HEADS {
+        let output = git_output_with_status(
+            repo_root,
+            ["rev-parse", "-q", "--verify", head],
+            DEFAULT_GIT_TIMEOUT,
+        )
+        .await?;
+        if output.success {
+            return Ok(Some(operation));
+        }
+    }
+    Ok(None)
+}
+
 pub(super) async fn stage_paths(
     repo_root: &Path,
     paths: &[String],
@@ -4,8 +4,16 @@
 use std::collections::BTreeSet;
 use std::path::{Component, Path, PathBuf};
 
+use super::contracts::{
+    GitBlameQuery, GitCherryPickRequest, GitLogCommit, GitLogQuery, GitRebaseRequest,
+    GitStashSaveRequest,
+};
+use super::parsing::LOG_FORMAT;
 use super::process::{git_output, DEFAULT_GIT_TIMEOUT};
 
+const DEFAULT_LOG_LIMIT: usize = 50;
+const MAX_LOG_LIMIT: usize = 500;
+
 pub(super) async fn require_repo_root(root: &str) -> Result<PathBuf, String> {
     let root = parse_root(root)?;
     discover_repo_root(root.as_path())
@@ -144,6 +152,268 @@ pub(super) fn merge_args<'a>(mode: Option<&str>, branch: &'a str) -> Result<Vec<
     }
 }
 
+pub(super) fn log_page(query: &GitLogQuery) -> (usize, usize) {
+    let limit = query
+        .limit
+        .filter(|limit| *limit > 0)
+        .unwrap_or(DEFAULT_LOG_LIMIT)
+        .min(MAX_LOG_LIMIT);
+    (query.skip.unwrap_or(0), limit)
+}
+
+/// One extra commit is requested so the caller can tell whether another page
+/// exists without a separate count.
+pub(super) fn log_args(query: &GitLogQuery) -> Result<Vec<String>, String> {
+    let (skip, limit) = log_page(query);
+    let mut args = vec![
+        "log".to_string(),
+        LOG_FORMAT.to_string(),
+        format!("--skip={skip}"),
+        format!("--max-count={}", limit + 1),
+    ];
+    if let Some(revision) = optional_text(query.revision.as_deref()) {
+        ensure_safe_ref(revision, "revision")?;
+        args.push(revision.to_string());
+    }
+    args.push("--".to_string());
+    if let Some(path) = optional_text(query.path.as_deref()) {
+        args.extend(validate_relative_paths(&[path.to_string()])?);
+    }
+    Ok(args)
+}
+
+pub(super) fn show_commit_args(revision: &str) -> Result<Vec<String>, String> {
+    let revision = revision.trim();
+    if revision.is_empty() {
+        return Err("revision 不能为空".to_string());
+    }
+    ensure_safe_ref(revision, "revision")?;
+    Ok(vec![
+        "log".to_string(),
+        "-1".to_string(),
+        LOG_FORMAT.to_string(),
+        revision.to_string(),
+        "--".to_string(),
+    ])
+}
+
+/// Diffs a commit against its first parent, so merges show what they brought
+/// into the branch and root commits show every file as added.
+pub(super) fn commit_diff_args(
+    commit: &GitLogCommit,
+    name_status: bool,
+    path: Option<&str>,
+) -> Result<Vec<String>, String> {
+    let mut args = vec![
+        "diff-tree".to_string(),
+        "-r".to_string(),
+        "-M".to_string(),
+        "--no-color".to_string(),
+        "--no-commit-id".to_string(),
+    ];
+    if name_status {
+        args.push("--name-status".to_string());
+        args.push("-z".to_string());
+    } else {
+        args.push("-p".to_string());
+    }
+    match commit.parents.first() {
+        Some(parent) => args.push(parent.clone()),
+        None => args.push("--root".to_string()),
+    }
+    args.push(commit.hash.clone());
+    if let Some(path) = optional_text(path) {
+        args.push("--".to_string());
+        args.extend(validate_relative_paths(&[path.to_string()])?);
+    }
+    Ok(args)
+}
+
+pub(super) fn blame_args(query: &GitBlameQuery) -> Result<(String, Vec<String>), String> {
+    let path = validate_relative_paths(std::slice::from_ref(&query.path))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "path 不能为空".to_string())?;
+    let mut args = vec!["blame".to_string(), "--porcelain".to_string()];
+    match (query.start_line, query.end_line) {
+        (None, None) => {}
+        (start, end) => {
+            let start = start.unwrap_or(1);
+            if start == 0 || end.is_some_and(|end| end < start) {
+                return Err("blame 行号范围不合法".to_string());
+            }
+            args.push("-L".to_string());
+            args.push(match end {
+                Some(end) => format!("{start},{end}"),
+                None => format!("{start},"),
+            });
+        }
+    }
+    if let Some(revision) = optional_text(query.revision.as_deref()) {
+        ensure_safe_ref(revision, "revision")?;
+        args.push(revision.to_string());
+    }
+    args.push("--".to_string());
+    args.push(path.clone());
+    Ok((path, args))
+}
+
+pub(super) fn stash_ref(index: Option<usize>) -> String {
+    format!("stash@{{{}}}", index.unwrap_or(0))
+}
+
+pub(super) fn stash_push_args(request: &GitStashSaveRequest) -> Result<Vec<String>, String> {
+    let mut args = vec!["stash".to_string(), "push".to_string()];
+    if request.include_untracked.unwrap_or(false) {
+        args.push("--include-untracked".to_string());
+    }
+    if let Some(message) = optional_text(request.message.as_deref()) {
+        args.push("-m".to_string());
+        args.push(message.to_string());
+    }
+    if let Some(paths) = request.paths.as_ref().filter(|paths| !paths.is_empty()) {
+        args.push("--".to_string());
+        args.extend(validate_relative_paths(paths)?);
+    }
+    Ok(args)
+}
+
+pub(super) fn stash_apply_args(
+    index: Option<usize>,
+    pop: bool,
+    restore_index: bool,
+) -> Vec<String> {
+    let mut args = vec![
+        "stash".to_string(),
+        if pop { "pop" } else { "apply" }.to_string(),
+    ];
+    if restore_index {
+        args.push("--index".to_string());
+    }
+    args.push(stash_ref(index));
+    args
+}
+
+/// Refs whose presence marks a stopped operation, checked through
+/// `rev-parse` so linked worktrees and Local Connector roots work too.
+pub(super) const OPERATION_HEADS: [(&str, &str); 3] = [
+    ("MERGE_HEAD", "merge"),
+    ("CHERRY_PICK_HEAD", "cherry-pick"),
+    ("REBASE_HEAD", "rebase"),
+];
+
+/// Conflicts are written with the merge base so hunks carry all three sides.
+pub(super) fn with_conflict_style<S: AsRef<str>>(args: &[S]) -> Vec<String> {
+    let mut out = vec!["-c".to_string(), "merge.conflictStyle=diff3".to_string()];
+    out.extend(args.iter().map(|arg| arg.as_ref().to_string()));
+    out
+}
+
+pub(super) fn operation_name(value: &str) -> Result<&'static str, String> {
+    match value.trim() {
+        "merge" => Ok("merge"),
+        "rebase" => Ok("rebase"),
+        "cherry-pick" | "cherry_pick" => Ok("cherry-pick"),
+        _ => Err("不支持的 Git 操作".to_string()),
+    }
+}
+
+/// Picks the operation to continue or abort, preferring what the repository
+/// reports over the caller's guess.
+pub(super) fn resolve_operation(
+    requested: Option<&str>,
+    detected: Option<&'static str>,
+) -> Result<&'static str, String> {
+    let requested = optional_text(requested).map(operation_name).transpose()?;
+    match (requested, detected) {
+        (Some(requested), Some(detected)) if requested != detected => Err(format!(
+            "当前未完成的是 {} 操作，不是 {}",
+            detected, requested
+        )),
+        (_, Some(operation)) | (Some(operation), None) => Ok(operation),
+        (None, None) => Err("没有未完成的 merge、rebase 或 cherry-pick 操作".to_string()),
+    }
+}
+
+/// `--continue` keeps the prepared commit message instead of opening an editor.
+pub(super) fn operation_step_args(operation: &str, abort: bool) -> Vec<String> {
+    let mut args = Vec::new();
+    if !abort {
+        args.push("-c".to_string());
+        args.push("core.editor=true".to_string());
+    }
+    args.push(operation.to_string());
+    args.push(if abort { "--abort" } else { "--continue" }.to_string());
+    args
+}
+
+pub(super) fn rebase_args(request: &GitRebaseRequest) -> Result<Vec<String>, String> {
+    let upstream = request.upstream.trim();
+    ensure_safe_ref(upstream, "upstream")?;
+    let steps = request.steps.as_deref().unwrap_or_default();
+    let mut args = Vec::new();
+    if !steps.is_empty() {
+        let mut todo = Vec::new();
+        for step in steps {
+            let action = step.action.trim();
+            if !matches!(action, "pick" | "squash" | "fixup" | "drop") {
+                return Err(format!("不支持的 rebase 步骤: {}", action));
+            }
+            let commit = step.commit.trim();
+            if !(4..=64).contains(&commit.len()) || !commit.chars().all(|ch| ch.is_ascii_hexdigit())
+            {
+                return Err(format!("rebase 步骤的 commit 不合法: {}", commit));
+            }
+            todo.push(format!("'{} {}'", action, commit));
+        }
+        // Git appends the todo path to the editor command, so printf
+        // overwrites the generated list with the requested one.
+        args.push("-c".to_string());
+        args.push(format!(
+            "sequence.editor=printf '%s\\n' {} >",
+            todo.join(" ")
+        ));
+        args.push("-c".to_string());
+        args.push("core.editor=true".to_string());
+    }
+    args.push("rebase".to_string());
+    if !steps.is_empty() {
+        args.push("-i".to_string());
+    }
+    if let Some(onto) = optional_text(request.onto.as_deref()) {
+        ensure_safe_ref(onto, "onto")?;
+        args.push("--onto".to_string());
+        args.push(onto.to_string());
+    }
+    args.push(upstream.to_string());
+    Ok(with_conflict_style(&args))
+}
+
+pub(super) fn cherry_pick_args(request: &GitCherryPickRequest) -> Result<Vec<String>, String> {
+    let commits = request
+        .commits
+        .iter()
+        .map(|commit| commit.trim())
+        .filter(|commit| !commit.is_empty())
+        .collect::<Vec<_>>();
+    if commits.is_empty() {
+        return Err("commits 不能为空".to_string());
+    }
+    let mut args = vec!["cherry-pick".to_string()];
+    if request.no_commit.unwrap_or(false) {
+        args.push("--no-commit".to_string());
+    }
+    for commit in commits {
+        ensure_safe_ref(commit, "commit")?;
+        args.push(commit.to_string());
+    }
+    Ok(with_conflict_style(&args))
+}
+
+fn optional_text(value: Option<&str>) -> Option<&str> {
+    value.map(str::trim).filter(|value| !value.is_empty())
+}
+
 pub(super) fn validate_relative_paths(paths: &[String]) -> Result<Vec<String>, String> {
     if paths.is_empty() {
         return Err("paths 不能为空".to_string());
@@ -183,7 +453,14 @@ pub(super) fn ensure_safe_ref(value: &str, label: &str) -> Result<(), String> {
 
 #[cfg(test)]
 mod tests {
-    use super::{merge_args, validate_relative_paths};
+    use super::{
+        blame_args, cherry_pick_args, log_args, merge_args, operation_step_args, rebase_args,
+        stash_apply_args, stash_push_args, validate_relative_paths,
+    };
+    use crate::services::git::contracts::{
+        GitBlameQuery, GitCherryPickRequest, GitLogQuery, GitRebaseRequest, GitRebaseStep,
+        GitStashSaveRequest,
+    };
 
     #[test]
     fn builds_merge_args_without_editor() {
@@ -208,4 +485,133 @@ mod tests {
         assert!(validate_relative_paths(&["/etc/passwd".to_string()]).is_err());
         assert!(validate_relative_paths(&["safe/file.rs".to_string()]).is_ok());
     }
+
+    #[test]
+    fn builds_paginated_log_args_with_path_filter() {
+        let query = GitLogQuery {
+            root: "/repo".to_string(),
+            revision: Some("main".to_string()),
+            path: Some("src/lib.rs".to_string()),
+            skip: Some(20),
+            limit: Some(10_000),
+        };
+        let args = log_args(&query).expect("log args");
+        assert_eq!(
+            args[2..],
+            ["--skip=20", "--max-count=501", "main", "--", "src/lib.rs"]
+        );
+
+        let query = GitLogQuery {
+            revision: Some("--output=/tmp/x".to_string()),
+            ..query
+        };
+        assert!(log_args(&query).is_err());
+    }
+
+    #[test]
+    fn builds_blame_and_stash_args() {
+        let mut query = GitBlameQuery {
+            root: "/repo".to_string(),
+            path: "src/lib.rs".to_string(),
+            revision: None,
+            start_line: Some(10),
+            end_line: None,
+        };
+        let (path, args) = blame_args(&query).expect("blame args");
+        assert_eq!(path, "src/lib.rs");
+        assert_eq!(
+            args,
+            ["blame", "--porcelain", "-L", "10,", "--", "src/lib.rs"]
+        );
+        query.end_line = Some(5);
+        assert!(blame_args(&query).is_err());
+
+        let request = GitStashSaveRequest {
+            root: "/repo".to_string(),
+            message: Some("wip".to_string()),
+            include_untracked: Some(true),
+            paths: Some(vec!["src".to_string()]),
+        };
+        assert_eq!(
+            stash_push_args(&request).expect("stash args"),
+            [
+                "stash",
+                "push",
+                "--include-untracked",
+                "-m",
+                "wip",
+                "--",
+                "src"
+            ]
+        );
+        assert_eq!(
+            stash_apply_args(Some(2), true, true),
+            ["stash", "pop", "--index", "stash@{2}"]
+        );
+    }
+
+    #[test]
+    fn builds_rebase_and_cherry_pick_args_with_diff3_conflicts() {
+        let mut request = GitRebaseRequest {
+            root: "/repo".to_string(),
+            upstream: "origin/main".to_string(),
+            onto: Some("release".to_string()),
+            steps: None,
+        };
+        assert_eq!(
+            rebase_args(&request).expect("rebase args"),
+            [
+                "-c",
+                "merge.conflictStyle=diff3",
+                "rebase",
+                "--onto",
+                "release",
+                "origin/main"
+            ]
+        );
+
+        request.onto = None;
+        request.steps = Some(vec![
+            GitRebaseStep {
+                action: "pick".to_string(),
+                commit: "abc123".to_string(),
+            },
+            GitRebaseStep {
+                action: "fixup".to_string(),
+                commit: "def456".to_string(),
+            },
+        ]);
+        let args = rebase_args(&request).expect("interactive rebase args");
+        assert_eq!(
+            args[3],
+            "sequence.editor=printf '%s\\n' 'pick abc123' 'fixup def456' >"
+        );
+        assert_eq!(args[6..], ["rebase", "-i", "origin/main"]);
+
+        request.steps = Some(vec![GitRebaseStep {
+            action: "exec".to_string(),
+            commit: "abc123".to_string(),
+        }]);
+        assert!(rebase_args(&request).is_err());
+        request.steps = Some(vec![GitRebaseStep {
+            action: "pick".to_string(),
+            commit: "abc'; rm -rf /".to_string(),
+        }]);
+        assert!(rebase_args(&request).is_err());
+
+        let request = GitCherryPickRequest {
+            root: "/repo".to_string(),
+            commits: vec!["abc123".to_string(), " ".to_string()],
+            no_commit: Some(true),
+        };
+        assert_eq!(
+            cherry_pick_args(&request).expect("cherry-pick args")[2..],
+            ["cherry-pick", "--no-commit", "abc123"]
+        );
+        assert_eq!(
+            operation_step_args("rebase", false),
+            ["-c", "core.editor=true", "rebase", "--continue"]
+        );
+        assert_eq!(operation_step_args("merge", true), ["merge", "--abort"]);
+    }
 }
@@ -1,15 +1,24 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
+use super::conflicts::{
+    apply_hunk_resolutions, conflict_resolution, parse_conflict_hunks, unresolved_note,
+    ConflictResolution,
+};
 use super::contracts::*;
+use super::inspection::{read_worktree_file, write_worktree_file};
 use super::local_connector;
-use super::process::{git_output, git_output_with_status, DEFAULT_GIT_TIMEOUT, REMOTE_GIT_TIMEOUT};
+use super::process::{
+    git_output, git_output_with_status, GitCommandOutput, DEFAULT_GIT_TIMEOUT, REMOTE_GIT_TIMEOUT,
+};
 use super::shared::{
-    action_result, action_result_with_status, discard_paths, read_repo_summary,
-    require_current_branch, stage_paths, unstage_paths,
+    action_result, action_result_with_status, discard_paths, in_progress_operation,
+    read_repo_summary, require_current_branch, stage_paths, unstage_paths,
 };
 use super::validation::{
-    ensure_safe_ref, merge_args, require_repo_root, validate_branch_name, validate_relative_paths,
+    cherry_pick_args, ensure_safe_ref, merge_args, operation_step_args, rebase_args,
+    require_repo_root, resolve_operation, stash_apply_args, stash_push_args, stash_ref,
+    validate_branch_name, validate_relative_paths, with_conflict_style,
 };
 use crate::services::project_local_cache::is_local_connector_project_root;
 
@@ -183,7 +192,7 @@ pub async fn merge(request: GitMergeRequest) -> Result<GitActionResult, String>
 
     let output = git_output_with_status(
         repo_root.as_path(),
-        merge_args(request.mode.as_deref(), branch)?,
+        with_conflict_style(&merge_args(request.mode.as_deref(), branch)?),
         REMOTE_GIT_TIMEOUT,
     )
     .await?;
@@ -241,3 +250,155 @@ pub async fn discard(request: GitPathRequest) -> Result<GitActionResult, String>
     let output = discard_paths(repo_root.as_path(), &paths).await?;
     action_result(repo_root.as_path(), output).await
 }
+
+pub async fn stash_save(request: GitStashSaveRequest) -> Result<GitActionResult, String> {
+    if is_local_connector_project_root(request.root.as_str()) {
+        return local_connector::stash_save(request).await;
+    }
+    let repo_root = require_repo_root(&request.root).await?;
+    let output = git_output(
+        repo_root.as_path(),
+        stash_push_args(&request)?,
+        DEFAULT_GIT_TIMEOUT,
+    )
+    .await?;
+    action_result(repo_root.as_path(), output).await
+}
+
+pub async fn stash_apply(request: GitStashRequest) -> Result<GitActionResult, String> {
+    if is_local_connector_project_root(request.root.as_str()) {
+        return local_connector::stash_apply(request).await;
+    }
+    let repo_root = require_repo_root(&request.root).await?;
+    let output = git_output_with_status(
+        repo_root.as_path(),
+        stash_apply_args(
+            request.index,
+            request.pop.unwrap_or(false),
+            request.restore_index.unwrap_or(false),
+        ),
+        DEFAULT_GIT_TIMEOUT,
+    )
+    .await?;
+    action_result_with_status(repo_root.as_path(), output).await
+}
+
+pub async fn stash_drop(request: GitStashRequest) -> Result<GitActionResult, String> {
+    if is_local_connector_project_root(request.root.as_str()) {
+        return local_connector::stash_drop(request).await;
+    }
+    let repo_root = require_repo_root(&request.root).await?;
+    let output = git_output(
+        repo_root.as_path(),
+        vec![
+            "stash".to_string(),
+            "drop".to_string(),
+            stash_ref(request.index),
+        ],
+        DEFAULT_GIT_TIMEOUT,
+    )
+    .await?;
+    action_result(repo_root.as_path(), output).await
+}
+
+/// `ours`/`theirs` follow git's meaning, so during a rebase `ours` is the
+/// branch being rebased onto.
+pub async fn resolve_conflict(
+    request: GitResolveConflictRequest,
+) -> Result<GitActionResult, String> {
+    if is_local_connector_project_root(request.root.as_str()) {
+        return local_connector::resolve_conflict(request).await;
+    }
+    let repo_root = require_repo_root(&request.root).await?;
+    let (path, resolution) = conflict_resolution(&request)?;
+    let remaining = match resolution {
+        ConflictResolution::Side(side) => {
+            git_output(
+                repo_root.as_path(),
+                ["checkout", side, "--", path.as_str()],
+                DEFAULT_GIT_TIMEOUT,
+            )
+            .await?;
+            0
+        }
+        ConflictResolution::Content(content) => {
+            write_worktree_file(repo_root.as_path(), path.as_str(), content).await?;
+            parse_conflict_hunks(content).len()
+        }
+        ConflictResolution::Hunks(hunks) => {
+            let current = read_worktree_file(repo_root.as_path(), path.as_str()).await?;
+            let (content, remaining) = apply_hunk_resolutions(current.as_str(), hunks)?;
+            write_worktree_file(repo_root.as_path(), path.as_str(), content.as_str()).await?;
+            remaining
+        }
+    };
+    let output = if remaining > 0 {
+        GitCommandOutput {
+            stdout: unresolved_note(remaining),
+            stderr: String::new(),
+        }
+    } else if request.stage.unwrap_or(true) {
+        stage_paths(repo_root.as_path(), &[path]).await?
+    } else {
+        GitCommandOutput {
+            stdout: String::new(),
+            stderr: String::new(),
+        }
+    };
+    action_result(repo_root.as_path(), output).await
+}
+
+pub async fn continue_operation(request: GitOperationRequest) -> Result<GitActionResult, String> {
+    operation_step(request, false).await
+}
+
+pub async fn abort_operation(request: GitOperationRequest) -> Result<GitActionResult, String> {
+    operation_step(request, true).await
+}
+
+async fn operation_step(
+    request: GitOperationRequest,
+    abort: bool,
+) -> Result<GitActionResult, String> {
+    if is_local_connector_project_root(request.root.as_str()) {
+        return local_connector::operation_step(request, abort).await;
+    }
+    let repo_root = require_repo_root(&request.root).await?;
+    let operation = resolve_operation(
+        request.operation.as_deref(),
+        in_progress_operation(repo_root.as_path()).await?,
+    )?;
+    let output = git_output_with_status(
+        repo_root.as_path(),
+        operation_step_args(operation, abort),
+        REMOTE_GIT_TIMEOUT,
+    )
+    .await?;
+    action_result_with_status(repo_root.as_path(), output).await
+}
+
+pub async fn rebase(request: GitRebaseRequest) -> Result<GitActionResult, String> {
+    if is_local_connector_project_root(request.root.as_str()) {
+        return local_connector::rebase(request).await;
+    }
+    let repo_root = require_repo_root(&request.root).await?;
+    let args = rebase_args(&request)?;
+    if let Some(operation) = in_progress_operation(repo_root.as_path()).await? {
+        return Err(format!("当前已有 Git 操作未完成: {}", operation));
+    }
+    let output = git_output_with_status(repo_root.as_path(), args, REMOTE_GIT_TIMEOUT).await?;
+    action_result_with_status(repo_root.as_path(), output).await
+}
+
+pub async fn cherry_pick(request: GitCherryPickRequest) -> Result<GitActionResult, String> {
+    if is_local_connector_project_root(request.root.as_str()) {
+        return local_connector::cherry_pick(request).await;
+    }
+    let repo_root = require_repo_root(&request.root).await?;
+    let args = cherry_pick_args(&request)?;
+    if let Some(operation) = in_progress_operation(repo_root.as_path()).await? {
+        return Err(format!("当前已有 Git 操作未完成: {}", operation));
+    }
+    let output = git_output_with_status(repo_root.as_path(), args, REMOTE_GIT_TIMEOUT).await?;
+    action_result_with_status(repo_root.as_path(), output).await
+}
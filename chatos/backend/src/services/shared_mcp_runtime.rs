@@ -1,11 +1,14 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
+use std::sync::Arc;
+
 use crate::core::mcp_tools::{build_builtin_tool_service, ToolInfo as ChatosToolInfo};
 use crate::services::mcp_loader::{
     BuiltinMcpKind as ChatosBuiltinMcpKind, McpBuiltinServer as ChatosBuiltinServer,
     McpHttpServer as ChatosHttpServer, McpStdioServer as ChatosStdioServer,
 };
+use crate::services::shared_builtin_ask_user::ChatosAskUserStore;
 
 pub(crate) fn build_shared_mcp_executor(
     http_servers: Vec<ChatosHttpServer>,
@@ -22,6 +25,11 @@ pub(crate) fn build_shared_mcp_executor(
             .collect(),
         registry,
     )
+    .with_resource_tools(true)
+    .with_sampling_handler(Arc::new(chatos_ai_runtime::AiMcpSamplingHandler::default()))
+    .with_elicitation_handler(Arc::new(chatos_mcp::AskUserElicitationHandler::new(
+        chatos_mcp::AskUserStoreRef::new(Arc::new(ChatosAskUserStore)),
+    )))
 }
 
 pub(crate) fn build_shared_builtin_registry(
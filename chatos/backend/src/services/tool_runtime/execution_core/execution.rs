@@ -30,6 +30,7 @@ const HEAVY_IO_TOOL_NAMES: &[&str] = &[
     "read_file",
     "read_file_range",
     "read_file_raw",
+    "revert_edit_session",
     "search_files",
     "search_text",
     "stage_edit_batch",
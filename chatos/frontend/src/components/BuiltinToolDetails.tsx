@@ -27,6 +27,8 @@ const CODE_MAINTAINER_TOOLS = new Set([
   'stage_edit_batch',
   'commit_edit_session',
   'abort_edit_session',
+  'revert_edit_session',
+  'list_edit_sessions',
 ]);
 
 const isBrowserToolName = (name: string): boolean => (
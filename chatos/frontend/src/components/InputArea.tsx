@@ -84,6 +84,7 @@ export const InputArea: React.FC<InputAreaProps> = ({
     projectFilePickerRef,
     pickerOpen,
     pluginPicker,
+    mcpPromptPicker,
     pluginCommandSuggestions,
     commandSuggestionsOpen,
     commandSuggestionIndex,
@@ -220,6 +221,7 @@ export const InputArea: React.FC<InputAreaProps> = ({
           showProjectSelector={showProjectSelector}
           showWorkspaceRootPicker={showWorkspaceRootPicker}
           pluginPicker={pluginPicker}
+          mcpPromptPicker={mcpPromptPicker}
           currentRemoteConnectionId={currentRemoteConnectionId}
           availableRemoteConnections={availableRemoteConnections}
           onRemoteConnectionChange={onRemoteConnectionChange}
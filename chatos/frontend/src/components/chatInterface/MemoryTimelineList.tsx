@@ -13,6 +13,8 @@ export interface MemoryTimelineItem {
   text: string;
   time: string;
   sourceLabel: string;
+  pinned?: boolean;
+  expiresAt?: string | null;
 }
 
 export const MemoryTimelineList: React.FC<{
@@ -29,7 +31,15 @@ export const MemoryTimelineList: React.FC<{
       {items.map((item) => (
         <div key={item.id} className="rounded border border-border p-2">
           <div className="flex items-center justify-between gap-2 text-[11px] text-muted-foreground">
-            <span>{item.sourceLabel}</span>
+            <span className="flex items-center gap-1">
+              <span>{item.sourceLabel}</span>
+              {item.pinned ? (
+                <span className="rounded bg-primary/10 px-1 text-primary">{t('memory.pinned')}</span>
+              ) : null}
+              {item.expiresAt ? (
+                <span>{t('memory.expiresAt', { time: formatTextDate(item.expiresAt) })}</span>
+              ) : null}
+            </span>
             <span>{formatTextDate(item.time)}</span>
           </div>
           <div className="mt-1 text-sm leading-6">
@@ -25,6 +25,8 @@ interface AgentRecallViewItem {
   lastSeenAt?: string | null;
   updatedAt: string;
   subjectType?: string | null;
+  pinned?: boolean;
+  expiresAt?: string | null;
 }
 
 interface SummaryPaneProps {
@@ -91,6 +93,8 @@ const SummaryPane: React.FC<SummaryPaneProps> = ({
       sourceLabel: recall.subjectType === 'project'
         ? t('memory.projectRecallSource', { level: recall.level })
         : t('memory.agentRecallSource', { level: recall.level }),
+      pinned: recall.pinned === true,
+      expiresAt: recall.expiresAt || null,
     })),
   ].sort((left, right) => right.timeTs - left.timeTs);
 
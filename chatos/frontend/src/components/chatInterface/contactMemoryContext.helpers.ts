@@ -44,6 +44,9 @@ export const normalizeAgentRecalls = (
         lastSeenAt: readString(record, 'last_seen_at') || null,
         updatedAt: String(record?.updated_at || ''),
         subjectType: readString(record, 'subject_type') || null,
+        pinned: record?.pinned === true,
+        expiresAt: readString(record, 'expires_at') || null,
+        version: Number.isFinite(Number(record?.version)) ? Number(record?.version) : 1,
       };
     })
     .filter((item) => item.id && item.recallKey);
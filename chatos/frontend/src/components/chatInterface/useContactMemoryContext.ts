@@ -39,6 +39,9 @@ export interface ContactAgentRecall {
   lastSeenAt?: string | null;
   updatedAt: string;
   subjectType?: string | null;
+  pinned?: boolean;
+  expiresAt?: string | null;
+  version?: number;
 }
 
 interface MemoryApiClient {
@@ -6,6 +6,7 @@ import { cn } from '../../lib/utils';
 import { InputAreaFloatingModelPicker } from './InlineWidgets';
 import type { InputAreaComposerProps } from './InputAreaComposerTypes';
 import {
+  InputAreaMcpPromptPicker,
   InputAreaProjectFilePicker,
   InputAreaPluginPicker,
   InputAreaProjectSelector,
@@ -30,6 +31,7 @@ export function InputAreaComposerControls({
   showProjectSelector,
   showWorkspaceRootPicker,
   pluginPicker,
+  mcpPromptPicker,
   currentRemoteConnectionId,
   availableRemoteConnections,
   onRemoteConnectionChange,
@@ -154,6 +156,11 @@ export function InputAreaComposerControls({
         disabled={disabled}
       />
 
+      <InputAreaMcpPromptPicker
+        mcpPromptPicker={mcpPromptPicker}
+        disabled={disabled}
+      />
+
       <InputAreaWorkspacePicker
         showWorkspaceRootPicker={showWorkspaceRootPicker}
         workspacePickerRef={workspacePickerRef}
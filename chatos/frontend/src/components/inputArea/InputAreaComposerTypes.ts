@@ -18,6 +18,7 @@ import type {
   Project,
   RemoteConnection,
 } from '../../types';
+import type { useMcpPromptPicker } from './useMcpPromptPicker';
 import type { useTaskPluginPicker } from './useTaskPluginPicker';
 
 export type InputAreaRefObject<T> = RefObject<T> | RefObject<T | null>;
@@ -46,6 +47,7 @@ export interface InputAreaComposerProps {
   showProjectSelector: boolean;
   showWorkspaceRootPicker: boolean;
   pluginPicker: ReturnType<typeof useTaskPluginPicker>;
+  mcpPromptPicker: ReturnType<typeof useMcpPromptPicker>;
   currentRemoteConnectionId: string | null;
   availableRemoteConnections: RemoteConnection[];
   onRemoteConnectionChange?: (connectionId: string | null) => void;
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
+export { InputAreaMcpPromptPicker } from './pickerWidgets/InputAreaMcpPromptPicker';
 export { InputAreaProjectFilePicker } from './pickerWidgets/InputAreaProjectFilePicker';
 export { InputAreaProjectSelector } from './pickerWidgets/InputAreaProjectSelector';
 export { InputAreaPluginPicker } from './pickerWidgets/InputAreaPluginPicker';
@@ -0,0 +1,148 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+import { useI18n } from '../../../i18n/I18nProvider';
+import { cn } from '../../../lib/utils';
+import { mcpPromptKey, type useMcpPromptPicker } from '../useMcpPromptPicker';
+
+type McpPromptPickerModel = ReturnType<typeof useMcpPromptPicker>;
+
+export const InputAreaMcpPromptPicker = ({
+  mcpPromptPicker,
+  disabled,
+}: {
+  mcpPromptPicker: McpPromptPickerModel;
+  disabled: boolean;
+}) => {
+  const { t } = useI18n();
+  if (!mcpPromptPicker.visible) {
+    return null;
+  }
+  const activePrompt = mcpPromptPicker.activePrompt;
+
+  return (
+    <div ref={mcpPromptPicker.pickerRef} className="relative flex-shrink-0">
+      <button
+        type="button"
+        onClick={mcpPromptPicker.toggleOpen}
+        disabled={disabled}
+        className={cn(
+          'flex items-center gap-1 rounded-md bg-muted px-2 py-1 text-xs text-muted-foreground transition-colors hover:text-foreground',
+          disabled && 'cursor-not-allowed opacity-50',
+        )}
+        title={t('inputArea.mcpPrompt.chooseTitle')}
+      >
+        <svg className="h-4 w-4" fill="none" stroke="currentColor" viewBox="0 0 24 24">
+          <path strokeLinecap="round" strokeLinejoin="round" strokeWidth={2} d="M8 10h8M8 14h5m-9 6l3-3h10a2 2 0 002-2V6a2 2 0 00-2-2H6a2 2 0 00-2 2v14z" />
+        </svg>
+        <span>{t('inputArea.mcpPrompt.button')}</span>
+      </button>
+
+      {mcpPromptPicker.open ? (
+        <div className="absolute bottom-full left-0 z-50 mb-2 w-[min(92vw,480px)] rounded-lg border bg-popover p-3 text-popover-foreground shadow-xl">
+          <div className="mb-3 flex items-start justify-between gap-3">
+            <div className="text-sm font-medium">
+              {activePrompt ? (activePrompt.title || activePrompt.name) : t('inputArea.mcpPrompt.title')}
+            </div>
+            <button
+              type="button"
+              onClick={mcpPromptPicker.close}
+              className="rounded p-1 text-muted-foreground hover:bg-muted hover:text-foreground"
+              aria-label={t('inputArea.plugin.done')}
+            >
+              ×
+            </button>
+          </div>
+
+          {activePrompt ? (
+            <div className="space-y-2">
+              {activePrompt.description ? (
+                <div className="text-xs text-muted-foreground">{activePrompt.description}</div>
+              ) : null}
+              {(activePrompt.arguments || []).map((argument) => (
+                <label key={argument.name} className="block text-xs">
+                  <span className="font-mono text-foreground">
+                    {argument.required
+                      ? t('inputArea.mcpPrompt.argumentRequired', { name: argument.name })
+                      : argument.name}
+                  </span>
+                  <input
+                    value={mcpPromptPicker.argumentValues[argument.name] || ''}
+                    onChange={(event) => mcpPromptPicker.setArgumentValue(
+                      argument.name,
+                      event.target.value,
+                    )}
+                    placeholder={argument.description || ''}
+                    className="mt-1 w-full rounded-md border bg-background px-2 py-1.5 text-xs text-foreground outline-none focus:ring-1 focus:ring-primary"
+                  />
+                </label>
+              ))}
+            </div>
+          ) : (
+            <>
+              <input
+                value={mcpPromptPicker.search}
+                onChange={(event) => mcpPromptPicker.setSearch(event.target.value)}
+                placeholder={t('inputArea.mcpPrompt.search')}
+                className="w-full rounded-md border bg-background px-3 py-2 text-sm text-foreground outline-none focus:ring-1 focus:ring-primary"
+              />
+              <div className="mt-3 max-h-64 space-y-1 overflow-y-auto pr-1">
+                {mcpPromptPicker.loading ? (
+                  <div className="py-6 text-center text-sm text-muted-foreground">
+                    {t('inputArea.mcpPrompt.loading')}
+                  </div>
+                ) : null}
+                {!mcpPromptPicker.loading && mcpPromptPicker.filteredPrompts.length === 0 ? (
+                  <div className="py-6 text-center text-sm text-muted-foreground">
+                    {t('inputArea.mcpPrompt.empty')}
+                  </div>
+                ) : null}
+                {!mcpPromptPicker.loading && mcpPromptPicker.filteredPrompts.map((prompt) => (
+                  <button
+                    key={mcpPromptKey(prompt)}
+                    type="button"
+                    onClick={() => mcpPromptPicker.choosePrompt(prompt)}
+                    className="block w-full rounded-md border px-3 py-2 text-left hover:bg-muted/60"
+                  >
+                    <span className="block font-mono text-xs text-primary">{prompt.name}</span>
+                    {prompt.title || prompt.description ? (
+                      <span className="mt-0.5 block text-[11px] text-muted-foreground">
+                        {prompt.title || prompt.description}
+                      </span>
+                    ) : null}
+                  </button>
+                ))}
+              </div>
+            </>
+          )}
+
+          {mcpPromptPicker.error ? (
+            <div className="mt-2 text-xs text-destructive">{mcpPromptPicker.error}</div>
+          ) : null}
+
+          {activePrompt ? (
+            <div className="mt-3 flex items-center justify-between border-t pt-3">
+              <button
+                type="button"
+                onClick={() => mcpPromptPicker.choosePrompt(null)}
+                className="text-xs text-muted-foreground hover:text-foreground"
+              >
+                {t('inputArea.mcpPrompt.back')}
+              </button>
+              <button
+                type="button"
+                onClick={() => { void mcpPromptPicker.insertPrompt(t('inputArea.mcpPrompt.tooLong')); }}
+                disabled={mcpPromptPicker.inserting || mcpPromptPicker.missingRequiredArgument}
+                className="rounded-md bg-primary px-3 py-1.5 text-xs text-primary-foreground hover:bg-primary/90 disabled:opacity-40"
+              >
+                {mcpPromptPicker.inserting
+                  ? t('inputArea.mcpPrompt.inserting')
+                  : t('inputArea.mcpPrompt.insert')}
+              </button>
+            </div>
+          ) : null}
+        </div>
+      ) : null}
+    </div>
+  );
+};
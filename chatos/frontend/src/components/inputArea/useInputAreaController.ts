@@ -31,6 +31,7 @@ import {
   findPluginMentionAtCursor,
   replacePluginMention,
 } from './pluginMentions';
+import { useMcpPromptPicker } from './useMcpPromptPicker';
 import { useTaskPluginPicker } from './useTaskPluginPicker';
 import type { TaskRunnerSelectablePluginResponse } from '../../lib/api/client/types';
 
@@ -521,6 +522,30 @@ export function useInputAreaController({
     window.requestAnimationFrame(() => textareaRef.current?.focus());
   }, [leadingPluginCommand?.arguments, message, pluginPicker, setMessageValue, textareaRef]);
 
+  const insertMcpPromptText = useCallback((text: string) => {
+    const trimmed = text.trim();
+    if (!trimmed) {
+      return true;
+    }
+    const nextMessage = message.trim() ? `${message.replace(/\s+$/, '')}\n\n${trimmed}` : trimmed;
+    if (nextMessage.length > maxLength) {
+      return false;
+    }
+    setMessageValue(nextMessage);
+    window.requestAnimationFrame(() => {
+      const textarea = textareaRef.current;
+      textarea?.focus();
+      textarea?.setSelectionRange(nextMessage.length, nextMessage.length);
+    });
+    return true;
+  }, [maxLength, message, setMessageValue, textareaRef]);
+  const mcpPromptPicker = useMcpPromptPicker({
+    client,
+    conversationId,
+    disabled,
+    onInsert: insertMcpPromptText,
+  });
+
   const selectPluginMentionSuggestion = useCallback((
     plugin: TaskRunnerSelectablePluginResponse,
   ) => {
@@ -643,6 +668,7 @@ export function useInputAreaController({
     projectFilePickerRef,
     pickerOpen,
     pluginPicker,
+    mcpPromptPicker,
     pluginCommandSuggestions,
     commandSuggestionsOpen,
     commandSuggestionIndex,
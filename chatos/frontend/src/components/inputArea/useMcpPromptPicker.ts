@@ -0,0 +1,166 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+import { useCallback, useEffect, useMemo, useState } from 'react';
+
+import type ApiClient from '../../lib/api/client';
+import type { McpPromptDescriptor } from '../../lib/api/client/types';
+import { useDismissiblePopover } from './useDismissiblePopover';
+
+const normalizeError = (error: unknown): string => (
+  error instanceof Error ? error.message : String(error || 'Unknown error')
+);
+
+export const mcpPromptKey = (prompt: Pick<McpPromptDescriptor, 'server' | 'name'>): string => (
+  `${prompt.server}\u0000${prompt.name}`
+);
+
+export const filterMcpPrompts = (
+  prompts: McpPromptDescriptor[],
+  search: string,
+): McpPromptDescriptor[] => {
+  const keyword = search.trim().toLowerCase();
+  if (!keyword) {
+    return prompts;
+  }
+  return prompts.filter((prompt) => [prompt.name, prompt.title, prompt.description]
+    .some((value) => typeof value === 'string' && value.toLowerCase().includes(keyword)));
+};
+
+export const useMcpPromptPicker = ({
+  client,
+  conversationId,
+  disabled,
+  onInsert,
+}: {
+  client: ApiClient;
+  conversationId?: string | null;
+  disabled: boolean;
+  onInsert: (text: string) => boolean;
+}) => {
+  const [open, setOpen] = useState(false);
+  const [prompts, setPrompts] = useState<McpPromptDescriptor[]>([]);
+  const [loading, setLoading] = useState(false);
+  const [inserting, setInserting] = useState(false);
+  const [error, setError] = useState<string | null>(null);
+  const [search, setSearch] = useState('');
+  const [activePrompt, setActivePrompt] = useState<McpPromptDescriptor | null>(null);
+  const [argumentValues, setArgumentValues] = useState<Record<string, string>>({});
+
+  const visible = Boolean(conversationId);
+  const close = useCallback(() => {
+    setOpen(false);
+    setActivePrompt(null);
+    setArgumentValues({});
+  }, []);
+  const pickerRef = useDismissiblePopover<HTMLDivElement>(open, close);
+
+  useEffect(() => {
+    setPrompts([]);
+    close();
+  }, [close, conversationId]);
+
+  const loadPrompts = useCallback(async () => {
+    if (!conversationId) {
+      return;
+    }
+    setLoading(true);
+    setError(null);
+    try {
+      const response = await client.listMcpPrompts(conversationId);
+      setPrompts(Array.isArray(response?.prompts) ? response.prompts : []);
+    } catch (err) {
+      setPrompts([]);
+      setError(normalizeError(err));
+    } finally {
+      setLoading(false);
+    }
+  }, [client, conversationId]);
+
+  const toggleOpen = useCallback(() => {
+    if (disabled || !conversationId) {
+      return;
+    }
+    if (open) {
+      close();
+      return;
+    }
+    setOpen(true);
+    setSearch('');
+    void loadPrompts();
+  }, [close, conversationId, disabled, loadPrompts, open]);
+
+  const choosePrompt = useCallback((prompt: McpPromptDescriptor | null) => {
+    setActivePrompt(prompt);
+    setArgumentValues({});
+    setError(null);
+  }, []);
+
+  const setArgumentValue = useCallback((name: string, value: string) => {
+    setArgumentValues((current) => ({ ...current, [name]: value }));
+  }, []);
+
+  const missingRequiredArgument = useMemo(() => (
+    (activePrompt?.arguments || []).some((argument) => (
+      argument.required && !(argumentValues[argument.name] || '').trim()
+    ))
+  ), [activePrompt, argumentValues]);
+
+  const insertPrompt = useCallback(async (tooLongMessage: string) => {
+    if (!conversationId || !activePrompt || missingRequiredArgument) {
+      return;
+    }
+    setInserting(true);
+    setError(null);
+    try {
+      const argumentsPayload = Object.fromEntries(
+        Object.entries(argumentValues).filter(([, value]) => value.trim().length > 0),
+      );
+      const response = await client.renderMcpPrompt(conversationId, {
+        server: activePrompt.server,
+        name: activePrompt.name,
+        arguments: argumentsPayload,
+      });
+      const text = typeof response?.text === 'string' ? response.text : '';
+      if (!onInsert(text)) {
+        setError(tooLongMessage);
+        return;
+      }
+      close();
+    } catch (err) {
+      setError(normalizeError(err));
+    } finally {
+      setInserting(false);
+    }
+  }, [
+    activePrompt,
+    argumentValues,
+    client,
+    close,
+    conversationId,
+    missingRequiredArgument,
+    onInsert,
+  ]);
+
+  const filteredPrompts = useMemo(() => filterMcpPrompts(prompts, search), [prompts, search]);
+
+  return {
+    visible,
+    open,
+    pickerRef,
+    toggleOpen,
+    close,
+    loading,
+    inserting,
+    error,
+    search,
+    setSearch,
+    filteredPrompts,
+    activePrompt,
+    choosePrompt,
+    argumentValues,
+    setArgumentValue,
+    missingRequiredArgument,
+    insertPrompt,
+  };
+};
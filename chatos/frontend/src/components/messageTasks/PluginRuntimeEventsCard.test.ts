@@ -43,6 +43,8 @@ describe('pluginRuntimeSummariesFromEvents', () => {
                 stdout_sha256: 'a'.repeat(64),
               },
             ],
+            decision: { decision: 'deny', user_approved: false },
+            input_rewritten: false,
           },
         },
       },
@@ -80,6 +82,9 @@ describe('pluginRuntimeSummariesFromEvents', () => {
         workspaceWriteRequested: 1,
         workspaceWriteApproved: 0,
         workspaceWriteDenied: 1,
+        decision: 'deny',
+        userApproved: false,
+        inputRewritten: false,
       },
     });
     expect(summaries[1]).toMatchObject({
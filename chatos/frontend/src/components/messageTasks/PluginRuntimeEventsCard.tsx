@@ -100,6 +100,17 @@ export const PluginRuntimeEventsCard: FC<{ events: MessageTaskRunnerRunEvent[] }
                       denied: activity.hook.workspaceWriteDenied,
                     })}</span>
                   ) : null}
+                  {activity.hook.decision ? (
+                    <span>{t(
+                      activity.hook.userApproved === null
+                        ? 'pluginRuntime.hookDecision'
+                        : 'pluginRuntime.hookDecisionByUser',
+                      { decision: activity.hook.decision },
+                    )}</span>
+                  ) : null}
+                  {activity.hook.inputRewritten ? (
+                    <span>{t('pluginRuntime.hookInputRewritten')}</span>
+                  ) : null}
                 </div>
               ) : null}
               {activity.error ? (
@@ -13,6 +13,9 @@ interface PluginHookSummary {
   workspaceWriteRequested: number;
   workspaceWriteApproved: number;
   workspaceWriteDenied: number;
+  decision: string | null;
+  userApproved: boolean | null;
+  inputRewritten: boolean;
 }
 
 export interface PluginRuntimeEventSummary {
@@ -62,6 +65,7 @@ const hookSummary = (value: unknown): PluginHookSummary | null => {
   const executions = Array.isArray(hook.executions)
     ? hook.executions.map(record).filter((item): item is Record<string, unknown> => Boolean(item))
     : [];
+  const decision = record(hook.decision);
   return {
     event: text(hook.event, 128),
     blockingFailure: hook.blocking_failure === true,
@@ -76,6 +80,9 @@ const hookSummary = (value: unknown): PluginHookSummary | null => {
     workspaceWriteDenied: executions.filter((item) => (
       item.workspace_write === true && item.workspace_write_approved === false
     )).length,
+    decision: text(decision?.decision, 16),
+    userApproved: typeof decision?.user_approved === 'boolean' ? decision.user_approved : null,
+    inputRewritten: hook.input_rewritten === true,
   };
 };
 
@@ -54,4 +54,22 @@ describe('buildRunEventTimelineEntries', () => {
     expect(entries[0]?.aggregatedText).toBe('A');
     expect(entries[1]?.aggregatedText).toBe('B');
   });
+
+  it('shows MCP tool notices as readable lines instead of raw JSON', () => {
+    const notice = JSON.stringify({
+      event: 'tool_notice',
+      data: {
+        tool_name: 'build',
+        server_name: 'docs',
+        notice: { kind: 'progress', progress: 1, total: 2, message: 'Rendering' },
+      },
+    });
+    const entries = buildRunEventTimelineEntries([
+      createEvent('1', 'tool_stream', { tool_call_id: 'tool-a', name: 'build', content: notice, is_stream: true }),
+      createEvent('2', 'tool_stream', { tool_call_id: 'tool-a', name: 'build', content: 'done' }),
+    ]);
+
+    expect(entries).toHaveLength(1);
+    expect(entries[0]?.aggregatedText).toBe('进度 50% · Rendering\n\ndone');
+  });
 });
@@ -2,6 +2,7 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 import type { MessageTaskRunnerRunEvent } from '../../lib/api/client/types';
+import { readableToolStreamText } from '../../lib/tools/toolNotice';
 
 type TimelineTone = 'danger' | 'info' | 'muted' | 'success' | 'warning';
 
@@ -232,7 +233,7 @@ function describeGroupedSummary(eventType: string, count: number, charCount: num
 
 function extractEventText(event: MessageTaskRunnerRunEvent): string | null {
   const payload = asRecord(event.payload);
-  return (
+  const text = (
     readString(payload?.text)
     || readString(payload?.chunk)
     || readString(payload?.content)
@@ -240,6 +241,9 @@ function extractEventText(event: MessageTaskRunnerRunEvent): string | null {
     || readString(event.message)
     || null
   );
+  return text && normalizeEventType(event.event_type) === 'tool_stream'
+    ? readableToolStreamText(text)
+    : text;
 }
 
 function asRecord(value: unknown): Record<string, unknown> | null {
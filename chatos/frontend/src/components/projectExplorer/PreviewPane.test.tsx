@@ -51,6 +51,9 @@ const defaultCapabilities: CodeNavCapabilities = {
   supportsDefinition: true,
   supportsReferences: true,
   supportsDocumentSymbols: true,
+  supportsHover: false,
+  supportsCallHierarchy: false,
+  supportsWorkspaceSymbols: false,
   fallbackAvailable: false,
 };
 
@@ -214,4 +214,40 @@ describe('requirement execution planner timeline selection', () => {
       type: 'tool_call',
     });
   });
+
+  it('adds MCP tool notices to the stream log without replacing the result', async () => {
+    const { result } = renderHook(() => useRequirementExecutionPlannerTimeline({
+      active: true,
+      conversationId: 'conversation-1',
+      turnId: 'turn-1',
+      userMessageId: 'user-1',
+    }), { wrapper: apiWrapper(emptyClient()) });
+
+    await waitFor(() => expect(realtimeMock.onEvent).not.toBeNull());
+    act(() => {
+      realtimeMock.onEvent?.(realtimePayload('tools_start', {
+        data: {
+          tool_calls: [{ id: 'call-1', function: { name: 'build_docs', arguments: '{}' } }],
+        },
+      }), 'chat.tool.started');
+      realtimeMock.onEvent?.(realtimePayload('tools_stream', {
+        data: {
+          tool_call_id: 'call-1',
+          name: 'build_docs',
+          is_stream: true,
+          content: JSON.stringify({
+            event: 'tool_notice',
+            data: { notice: { kind: 'log', level: 'info', data: 'indexing' } },
+          }),
+        },
+      }), 'chat.tool.delta');
+    });
+
+    expect(result.current.items[0]).toMatchObject({
+      hasResult: false,
+      status: 'pending',
+      toolCall: { streamLog: '[info] indexing' },
+      type: 'tool_call',
+    });
+  });
 });
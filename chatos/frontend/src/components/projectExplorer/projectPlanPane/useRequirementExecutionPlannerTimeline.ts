@@ -7,6 +7,7 @@ import { useApiClient } from '../../../lib/api/ApiClientContext';
 import type { RealtimeChatStreamPayloadWrapper } from '../../../lib/realtime/types';
 import { useConversationChatStreamRealtime } from '../../../lib/realtime/useConversationChatStreamRealtime';
 import { normalizePersistedMessage } from '../../../lib/store/actions/sendMessage/persistedTurnMessages';
+import { formatToolNotice, parseToolNoticeChunk } from '../../../lib/tools/toolNotice';
 import type { Message } from '../../../types';
 import type { MessageToolCallLike } from '../../messageItem/messageReaders';
 import {
@@ -168,10 +169,25 @@ const applyToolResult = (
   if (!callId) return items;
   const isError = record.is_error === true || record.success === false;
   const content = typeof record.content === 'string' ? record.content : '';
-  const result = record.result !== undefined ? record.result : content;
   const existingIndex = items.findIndex((item) => (
     item.type === 'tool_call' && item.toolCall.id === callId
   ));
+  const notice = terminal ? null : parseToolNoticeChunk(content);
+  if (notice) {
+    const item = items[existingIndex];
+    if (!item || item.type !== 'tool_call') return items;
+    const line = formatToolNotice(notice);
+    const next = [...items];
+    next[existingIndex] = {
+      ...item,
+      toolCall: {
+        ...item.toolCall,
+        streamLog: item.toolCall.streamLog ? `${item.toolCall.streamLog}\n${line}` : line,
+      },
+    };
+    return next;
+  }
+  const result = record.result !== undefined ? record.result : content;
   if (existingIndex < 0) {
     return [...items, {
       callId,
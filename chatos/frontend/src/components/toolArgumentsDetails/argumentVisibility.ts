@@ -46,6 +46,8 @@ const TOOL_ARGUMENT_ALLOWLIST: Record<string, Set<string>> = {
   'code:stage_edit_batch': new Set(['session_id', 'operations']),
   'code:commit_edit_session': new Set(['session_id']),
   'code:abort_edit_session': new Set(['session_id']),
+  'code:revert_edit_session': new Set(['session_id']),
+  'code:list_edit_sessions': new Set(['scope', 'limit']),
   'browser:browser_open': new Set(['url']),
   'browser:browser_tabs': new Set([]),
   'browser:browser_tab_new': new Set(['url']),
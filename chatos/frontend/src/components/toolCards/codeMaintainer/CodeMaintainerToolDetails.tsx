@@ -3,7 +3,12 @@
 
 import React from 'react';
 
+import { useI18n } from '../../../i18n/I18nProvider';
+import { translateToolTitle } from '../../../i18n/toolText';
+import { formatToolCardCount, renderCardHeader } from '../shared/primitives';
 import ChangeOperationDetails from './ChangeOperationDetails';
+import EditSessionRevertButton from './EditSessionRevertButton';
+import { committedEditSessionId, listedEditSessions } from './editSessionRevert';
 import ListDirDetails from './ListDirDetails';
 import ReadFileDetails from './ReadFileDetails';
 import SearchMatchesDetails from './SearchMatchesDetails';
@@ -13,6 +18,7 @@ const EDIT_SESSION_TOOLS = new Set([
   'stage_edit_batch',
   'commit_edit_session',
   'abort_edit_session',
+  'revert_edit_session',
 ]);
 
 interface CodeMaintainerToolDetailsProps {
@@ -49,9 +55,23 @@ export const CodeMaintainerToolDetails: React.FC<CodeMaintainerToolDetailsProps>
   }
 
   if (EDIT_SESSION_TOOLS.has(displayName)) {
+    const revertSessionId = committedEditSessionId(displayName, result);
     return (
       <div className="tool-detail-stack">
         <ChangeOperationDetails result={result} />
+        {revertSessionId ? (
+          <div className="tool-detail-card tool-detail-card--full">
+            <EditSessionRevertButton sessionId={revertSessionId} />
+          </div>
+        ) : null}
+      </div>
+    );
+  }
+
+  if (displayName === 'list_edit_sessions') {
+    return (
+      <div className="tool-detail-stack">
+        <EditSessionListDetails result={result} />
       </div>
     );
   }
@@ -59,4 +79,29 @@ export const CodeMaintainerToolDetails: React.FC<CodeMaintainerToolDetailsProps>
   return null;
 };
 
+const EditSessionListDetails: React.FC<{ result: unknown }> = ({ result }) => {
+  const { locale, t } = useI18n();
+  const sessions = listedEditSessions(result);
+  if (sessions.length === 0) return null;
+
+  return (
+    <div className="tool-detail-card tool-detail-card--full">
+      {renderCardHeader(
+        translateToolTitle('Edit sessions', locale),
+        formatToolCardCount(t, 'items', sessions.length),
+      )}
+      <div className="tool-detail-list">
+        {sessions.map((session) => (
+          <div key={session.sessionId} className="tool-detail-item">
+            <div className="tool-detail-item-body">
+              {[session.sessionId, session.committedAt, ...session.paths].filter(Boolean).join(' · ')}
+            </div>
+            <EditSessionRevertButton sessionId={session.sessionId} disabled={!session.revertible} />
+          </div>
+        ))}
+      </div>
+    </div>
+  );
+};
+
 export default CodeMaintainerToolDetails;
@@ -0,0 +1,68 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+import React from 'react';
+
+import { useI18n } from '../../../i18n/I18nProvider';
+import { useApiClient } from '../../../lib/api/ApiClientContext';
+import { useOptionalChatStoreContext } from '../../../lib/store/ChatStoreContext';
+
+type RevertState = 'idle' | 'pending' | 'done';
+
+interface EditSessionRevertButtonProps {
+  sessionId: string;
+  disabled?: boolean;
+}
+
+export const EditSessionRevertButton: React.FC<EditSessionRevertButtonProps> = ({
+  sessionId,
+  disabled = false,
+}) => {
+  const { t } = useI18n();
+  const client = useApiClient();
+  const store = useOptionalChatStoreContext();
+  const conversationId = store?.getState().currentSessionId ?? null;
+  const [state, setState] = React.useState<RevertState>('idle');
+  const [error, setError] = React.useState<string | null>(null);
+
+  const handleRevert = React.useCallback(async () => {
+    if (!conversationId) return;
+    setState('pending');
+    setError(null);
+    try {
+      await client.revertEditSession(conversationId, sessionId);
+      setState('done');
+    } catch (err) {
+      setError(err instanceof Error ? err.message : String(err));
+      setState('idle');
+    }
+  }, [client, conversationId, sessionId]);
+
+  if (!conversationId) return null;
+
+  const label = state === 'done'
+    ? t('toolCard.editSession.reverted')
+    : state === 'pending'
+      ? t('toolCard.editSession.reverting')
+      : t('toolCard.editSession.revert');
+
+  return (
+    <div className="flex flex-wrap items-center gap-2">
+      <button
+        type="button"
+        onClick={() => { void handleRevert(); }}
+        disabled={disabled || state !== 'idle'}
+        className="rounded-md bg-primary px-3 py-1.5 text-xs text-primary-foreground hover:bg-primary/90 disabled:opacity-60"
+      >
+        {label}
+      </button>
+      {error ? (
+        <span className="text-xs text-destructive">
+          {t('toolCard.editSession.revertFailed', { error })}
+        </span>
+      ) : null}
+    </div>
+  );
+};
+
+export default EditSessionRevertButton;
@@ -0,0 +1,45 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+import { describe, expect, it } from 'vitest';
+
+import { committedEditSessionId, listedEditSessions } from './editSessionRevert';
+
+describe('editSessionRevert', () => {
+  it('offers a revert only for commits that changed files', () => {
+    const committed = {
+      outcome: 'changed',
+      result: { session_id: 'edit_1', committed_paths: [{ path: 'src/lib.rs' }] },
+    };
+    expect(committedEditSessionId('commit_edit_session', committed)).toBe('edit_1');
+    expect(committedEditSessionId('revert_edit_session', committed)).toBe('edit_1');
+    expect(committedEditSessionId('stage_edit_batch', committed)).toBeNull();
+    expect(committedEditSessionId('commit_edit_session', {
+      outcome: 'already_applied',
+      result: { session_id: 'edit_1', committed_paths: [] },
+    })).toBeNull();
+  });
+
+  it('reads listed sessions with their paths and revertibility', () => {
+    expect(listedEditSessions({
+      result: {
+        sessions: [
+          {
+            session_id: 'edit_2',
+            committed_at: '2026-10-17T08:00:00Z',
+            revertible: false,
+            files: [{ path: 'a.txt', change_kind: 'edit' }],
+          },
+          { session_id: '', files: [] },
+        ],
+      },
+    })).toEqual([
+      {
+        sessionId: 'edit_2',
+        committedAt: '2026-10-17T08:00:00Z',
+        revertible: false,
+        paths: ['a.txt'],
+      },
+    ]);
+  });
+});
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+import { asArray, asBoolean, asRecord, asString } from '../shared/value';
+
+const COMMITTING_TOOLS = new Set(['commit_edit_session', 'revert_edit_session']);
+
+// A successful commit (a revert is one too) leaves a committed session the
+// user can undo; anything else has nothing to revert.
+export const committedEditSessionId = (displayName: string, result: unknown): string | null => {
+  if (!COMMITTING_TOOLS.has(displayName)) return null;
+  const record = asRecord(result);
+  if (!record || asString(record.outcome) !== 'changed') return null;
+  const operationResult = asRecord(record.result);
+  const sessionId = asString(operationResult?.session_id).trim();
+  return sessionId && asArray(operationResult?.committed_paths).length > 0 ? sessionId : null;
+};
+
+export interface ListedEditSession {
+  sessionId: string;
+  committedAt: string;
+  revertible: boolean;
+  paths: string[];
+}
+
+export const listedEditSessions = (result: unknown): ListedEditSession[] => (
+  asArray(asRecord(asRecord(result)?.result)?.sessions)
+    .map((item) => asRecord(item))
+    .filter((item): item is Record<string, unknown> => item !== null)
+    .map((item) => ({
+      sessionId: asString(item.session_id).trim(),
+      committedAt: asString(item.committed_at).trim(),
+      revertible: asBoolean(item.revertible) === true,
+      paths: asArray(item.files)
+        .map((file) => asString(asRecord(file)?.path).trim())
+        .filter(Boolean),
+    }))
+    .filter((item) => item.sessionId.length > 0)
+);
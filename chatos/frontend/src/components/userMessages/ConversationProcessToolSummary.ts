@@ -192,6 +192,12 @@ export const buildToolActionSummary = (
   if (displayName === 'abort_edit_session') {
     return action('modify', '已取消项目修改', '正在取消项目修改', '取消项目修改失败');
   }
+  if (displayName === 'revert_edit_session') {
+    return action('modify', '已撤销项目修改', '正在撤销项目修改', '撤销项目修改失败');
+  }
+  if (displayName === 'list_edit_sessions') {
+    return action('read', '已读取修改记录', '正在读取修改记录', '读取修改记录失败');
+  }
   if (displayName === 'write_file' && family === 'remote') {
     return action('modify', `已修改 ${path}`, `正在修改 ${path}`, `修改 ${path} 失败`);
   }
@@ -115,6 +115,8 @@ export const enUS: MessageDictionary = {
   'memory.sessionSummarySource': 'Conversation summary L{level}',
   'memory.emptyRecall': '(empty recall)',
   'memory.agentRecallSource': 'Agent memory L{level}',
+  'memory.pinned': 'Pinned',
+  'memory.expiresAt': 'Expires {time}',
   'memory.projectRecallSource': 'Project memory L{level}',
   'memory.sessionSummaryLoadFailed': 'Failed to load conversation summaries',
   'memory.unboundContact': 'This conversation is not bound to a contact, so memory cannot be loaded.',
@@ -682,6 +684,9 @@ export const enUS: MessageDictionary = {
   'pluginRuntime.hook': 'Hook',
   'pluginRuntime.hookCounts': 'matched {matched} · failed {failed} · timed out {timedOut}',
   'pluginRuntime.workspaceWriteCounts': 'workspace write approved {approved} · denied {denied}',
+  'pluginRuntime.hookDecision': 'decision {decision}',
+  'pluginRuntime.hookDecisionByUser': 'decision {decision} (answered by user)',
+  'pluginRuntime.hookInputRewritten': 'tool input rewritten',
   'toolCard.count.items': '{count} items',
   'toolCard.count.rows': '{count} items',
   'toolCard.count.notes': '{count} notes',
@@ -703,4 +708,8 @@ export const enUS: MessageDictionary = {
   'toolCard.task.blocker': 'Blocked: {value}',
   'toolCard.task.needs': 'Needs: {value}',
   'toolCard.task.hint': 'Hint: {value}',
+  'toolCard.editSession.revert': 'Revert these changes',
+  'toolCard.editSession.reverting': 'Reverting…',
+  'toolCard.editSession.reverted': 'Reverted',
+  'toolCard.editSession.revertFailed': 'Revert failed: {error}',
 };
@@ -52,6 +52,17 @@ export const enUSInputAreaMessages: MessageDictionary = {
   'inputArea.plugin.commandArgumentsLabel': 'Arguments for {command}',
   'inputArea.plugin.commandArgumentsInvalid': 'Command arguments cannot contain NUL and must be at most 16 KiB of UTF-8 text.',
   'inputArea.plugin.removeCommand': 'Remove command {command}',
+  'inputArea.mcpPrompt.button': 'Prompts',
+  'inputArea.mcpPrompt.chooseTitle': 'Insert a prompt offered by this conversation\'s MCP servers',
+  'inputArea.mcpPrompt.title': 'MCP prompts',
+  'inputArea.mcpPrompt.search': 'Search prompts...',
+  'inputArea.mcpPrompt.loading': 'Loading MCP prompts...',
+  'inputArea.mcpPrompt.empty': 'No MCP server in this conversation offers prompts.',
+  'inputArea.mcpPrompt.argumentRequired': '{name} (required)',
+  'inputArea.mcpPrompt.back': 'Back',
+  'inputArea.mcpPrompt.insert': 'Insert',
+  'inputArea.mcpPrompt.inserting': 'Inserting...',
+  'inputArea.mcpPrompt.tooLong': 'The rendered prompt does not fit in the message length limit.',
   'inputArea.attach.unsupportedType': '{name} type is not supported',
   'inputArea.attach.fileTooLarge': '{name} exceeds the per-file limit ({limit})',
   'inputArea.attach.tooManyFiles': '{name} exceeds the file count limit ({limit})',
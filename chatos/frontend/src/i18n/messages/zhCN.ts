@@ -115,6 +115,8 @@ export const zhCN: MessageDictionary = {
   'memory.sessionSummarySource': '会话总结 L{level}',
   'memory.emptyRecall': '(空回忆)',
   'memory.agentRecallSource': '智能体记忆 L{level}',
+  'memory.pinned': '已置顶',
+  'memory.expiresAt': '{time} 过期',
   'memory.projectRecallSource': '项目记忆 L{level}',
   'memory.sessionSummaryLoadFailed': '会话总结加载失败',
   'memory.unboundContact': '当前会话未绑定联系人，无法加载记忆。',
@@ -675,6 +677,9 @@ export const zhCN: MessageDictionary = {
   'pluginRuntime.hook': 'Hook',
   'pluginRuntime.hookCounts': '匹配 {matched} · 失败 {failed} · 超时 {timedOut}',
   'pluginRuntime.workspaceWriteCounts': '工作区写入批准 {approved} · 拒绝 {denied}',
+  'pluginRuntime.hookDecision': '决定 {decision}',
+  'pluginRuntime.hookDecisionByUser': '决定 {decision}（用户确认）',
+  'pluginRuntime.hookInputRewritten': '工具参数已改写',
   'toolCard.count.items': '{count} 项',
   'toolCard.count.rows': '{count} 项',
   'toolCard.count.notes': '{count} 条',
@@ -696,6 +701,10 @@ export const zhCN: MessageDictionary = {
   'toolCard.task.blocker': '阻塞: {value}',
   'toolCard.task.needs': '需满足: {value}',
   'toolCard.task.hint': '提示: {value}',
+  'toolCard.editSession.revert': '撤销这些修改',
+  'toolCard.editSession.reverting': '正在撤销…',
+  'toolCard.editSession.reverted': '已撤销',
+  'toolCard.editSession.revertFailed': '撤销失败: {error}',
   'taskRunnerConfig.agentAccount': 'Agent 账号',
   'taskRunnerConfig.agentAccountPlaceholder': '请选择 Agent 账号',
   'taskRunnerConfig.agentAccountSelected': '已选择 Agent 账号',
@@ -52,6 +52,17 @@ export const zhCNInputAreaMessages: MessageDictionary = {
   'inputArea.plugin.commandArgumentsLabel': '{command} 的参数',
   'inputArea.plugin.commandArgumentsInvalid': '命令参数不能包含 NUL，且 UTF-8 文本最多为 16 KiB。',
   'inputArea.plugin.removeCommand': '移除命令 {command}',
+  'inputArea.mcpPrompt.button': '提示词',
+  'inputArea.mcpPrompt.chooseTitle': '插入当前对话 MCP 服务器提供的提示词',
+  'inputArea.mcpPrompt.title': 'MCP 提示词',
+  'inputArea.mcpPrompt.search': '搜索提示词...',
+  'inputArea.mcpPrompt.loading': '正在加载 MCP 提示词...',
+  'inputArea.mcpPrompt.empty': '当前对话的 MCP 服务器没有提供提示词。',
+  'inputArea.mcpPrompt.argumentRequired': '{name}(必填)',
+  'inputArea.mcpPrompt.back': '返回',
+  'inputArea.mcpPrompt.insert': '插入',
+  'inputArea.mcpPrompt.inserting': '正在插入...',
+  'inputArea.mcpPrompt.tooLong': '渲染后的提示词超出消息长度上限。',
   'inputArea.attach.unsupportedType': '{name} 类型不支持',
   'inputArea.attach.fileTooLarge': '{name} 超过单文件上限({limit})',
   'inputArea.attach.tooManyFiles': '{name} 超过数量上限({limit} 个)',
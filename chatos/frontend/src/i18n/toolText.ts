@@ -19,6 +19,7 @@ const zhTitleMap: Record<string, string> = {
   'Input items': '输入项',
   'Input summary': '输入摘要',
   'Session summary': '编辑会话摘要',
+  'Edit sessions': '编辑会话',
   'Result payload': '结果内容',
   'Result items': '结果项',
   'Result summary': '结果摘要',
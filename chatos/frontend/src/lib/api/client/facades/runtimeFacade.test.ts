@@ -64,4 +64,41 @@ describe('runtimeFacade cloud orchestration', () => {
     expect(request.mock.calls[1][0]).toContain('/task-manager/tasks?conversation_id=conv-1');
     expect(request.mock.calls[2][0]).toBe('/conversations/conv-1/summaries?limit=20');
   });
+
+  it('lists and renders MCP prompts for a conversation', async () => {
+    const request = vi.fn().mockResolvedValue({ prompts: [] });
+    const context = { getRequestFn: () => request };
+
+    await runtimeFacade.listMcpPrompts.call(context as never, 'conv 1');
+    await runtimeFacade.renderMcpPrompt.call(context as never, 'conv 1', {
+      server: 'mcp_management',
+      name: 'docs/summarize',
+      arguments: { topic: 'releases' },
+    });
+
+    expect(request).toHaveBeenNthCalledWith(1, '/agent/conversation/conv%201/mcp-prompts');
+    expect(request).toHaveBeenNthCalledWith(2, '/agent/conversation/conv%201/mcp-prompts/render', {
+      method: 'POST',
+      body: JSON.stringify({
+        server: 'mcp_management',
+        name: 'docs/summarize',
+        arguments: { topic: 'releases' },
+      }),
+    });
+  });
+
+  it('lists and reverts edit sessions for a conversation', async () => {
+    const request = vi.fn().mockResolvedValue({});
+    const context = { getRequestFn: () => request };
+
+    await runtimeFacade.listEditSessions.call(context as never, 'conv 1', { limit: 10 });
+    await runtimeFacade.revertEditSession.call(context as never, 'conv 1', 'edit_session_1');
+
+    expect(request).toHaveBeenNthCalledWith(1, '/agent/conversation/conv%201/edit-sessions?limit=10');
+    expect(request).toHaveBeenNthCalledWith(
+      2,
+      '/agent/conversation/conv%201/edit-sessions/edit_session_1/revert',
+      { method: 'POST' },
+    );
+  });
 });
@@ -27,6 +27,11 @@ import type {
   SendRegisterCodePayload,
   SendRegisterCodeResponse,
   AgentToolsResponse,
+  McpPromptListResponse,
+  McpPromptRenderPayload,
+  McpPromptRenderResponse,
+  EditSessionListResponse,
+  EditSessionRevertResponse,
   ReviewRepairResponse,
   ReviewRepairStatusResponse,
   SessionSummariesListResponse,
@@ -77,6 +82,16 @@ export interface RuntimeFacade {
     skillsEnabled?: boolean;
     selectedSkillIds?: string[];
   }): Promise<AgentToolsResponse>;
+  listMcpPrompts(conversationId: string): Promise<McpPromptListResponse>;
+  renderMcpPrompt(
+    conversationId: string,
+    payload: McpPromptRenderPayload,
+  ): Promise<McpPromptRenderResponse>;
+  listEditSessions(
+    conversationId: string,
+    options?: { limit?: number },
+  ): Promise<EditSessionListResponse>;
+  revertEditSession(conversationId: string, sessionId: string): Promise<EditSessionRevertResponse>;
   getTaskManagerTasks(
     conversationId: string,
     options?: { conversationTurnId?: string; includeDone?: boolean; limit?: number },
@@ -189,6 +204,36 @@ export const runtimeFacade: RuntimeFacade & ThisType<ApiClient> = {
     });
     return this.getRequestFn()<AgentToolsResponse>(`/agent/tools${query}`);
   },
+  async listMcpPrompts(conversationId) {
+    return this.getRequestFn()<McpPromptListResponse>(
+      `/agent/conversation/${encodeURIComponent(conversationId)}/mcp-prompts`,
+    );
+  },
+  async renderMcpPrompt(conversationId, payload) {
+    return this.getRequestFn()<McpPromptRenderResponse>(
+      `/agent/conversation/${encodeURIComponent(conversationId)}/mcp-prompts/render`,
+      {
+        method: 'POST',
+        body: JSON.stringify({
+          server: payload.server,
+          name: payload.name,
+          arguments: payload.arguments || {},
+        }),
+      },
+    );
+  },
+  async listEditSessions(conversationId, options) {
+    const query = buildQuery({ limit: options?.limit });
+    return this.getRequestFn()<EditSessionListResponse>(
+      `/agent/conversation/${encodeURIComponent(conversationId)}/edit-sessions${query}`,
+    );
+  },
+  async revertEditSession(conversationId, sessionId) {
+    return this.getRequestFn()<EditSessionRevertResponse>(
+      `/agent/conversation/${encodeURIComponent(conversationId)}/edit-sessions/${encodeURIComponent(sessionId)}/revert`,
+      { method: 'POST' },
+    );
+  },
   async getTaskManagerTasks(conversationId, options) {
     return tasksApi.getTaskManagerTasks(this.getRequestFn(), conversationId, options);
   },
@@ -3,7 +3,10 @@
 
 import * as workspaceApi from '../../workspace';
 import type {
+  ContactAgentRecallProvenanceResponse,
   ContactAgentRecallResponse,
+  ContactAgentRecallUpdatePayload,
+  ContactAgentRecallVersionResponse,
   ContactCreateResponse,
   ContactProjectLinkResponse,
   ContactProjectMemoryResponse,
@@ -27,6 +30,13 @@ export interface WorkspaceContactFacade {
   ): Promise<ContactProjectMemoryResponse[]>;
   getContactProjects(contactId: string, paging?: PagingOptions): Promise<ContactProjectLinkResponse[]>;
   getContactAgentRecalls(contactId: string, paging?: PagingOptions): Promise<ContactAgentRecallResponse[]>;
+  updateContactAgentRecall(
+    contactId: string,
+    recallId: string,
+    data: ContactAgentRecallUpdatePayload,
+  ): Promise<ContactAgentRecallResponse>;
+  getContactAgentRecallVersions(contactId: string, recallId: string): Promise<ContactAgentRecallVersionResponse[]>;
+  getContactAgentRecallProvenance(contactId: string, recallId: string): Promise<ContactAgentRecallProvenanceResponse>;
 }
 
 export const workspaceContactFacade: WorkspaceContactFacade & ThisType<ApiClient> = {
@@ -54,4 +64,13 @@ export const workspaceContactFacade: WorkspaceContactFacade & ThisType<ApiClient
   async getContactAgentRecalls(contactId, paging) {
     return workspaceApi.getContactAgentRecalls(this.getRequestFn(), contactId, paging);
   },
+  async updateContactAgentRecall(contactId, recallId, data) {
+    return workspaceApi.updateContactAgentRecall(this.getRequestFn(), contactId, recallId, data);
+  },
+  async getContactAgentRecallVersions(contactId, recallId) {
+    return workspaceApi.getContactAgentRecallVersions(this.getRequestFn(), contactId, recallId);
+  },
+  async getContactAgentRecallProvenance(contactId, recallId) {
+    return workspaceApi.getContactAgentRecallProvenance(this.getRequestFn(), contactId, recallId);
+  },
 };
@@ -4,10 +4,18 @@
 import * as workspaceApi from '../../workspace';
 import type {
   GitActionResponse,
+  GitBlameResponse,
   GitBranchesResponse,
   GitClientInfoResponse,
+  GitCommitDetailResponse,
   GitCompareResponse,
+  GitConflictsResponse,
   GitFileDiffResponse,
+  GitHunkResolution,
+  GitLogResponse,
+  GitOperationName,
+  GitRebaseStep,
+  GitStashListResponse,
   GitStatusResponse,
   GitSummaryResponse,
 } from '../../types';
@@ -20,6 +28,10 @@ export interface WorkspaceGitFacade {
   getGitStatus(root: string, forceRefresh?: boolean): Promise<GitStatusResponse>;
   compareGitBranch(root: string, target: string): Promise<GitCompareResponse>;
   getGitDiff(data: { root: string; path: string; target?: string; staged?: boolean }): Promise<GitFileDiffResponse>;
+  getGitLog(data: { root: string; revision?: string; path?: string; skip?: number; limit?: number }): Promise<GitLogResponse>;
+  getGitCommit(data: { root: string; revision: string; path?: string }): Promise<GitCommitDetailResponse>;
+  getGitBlame(data: { root: string; path: string; revision?: string; startLine?: number; endLine?: number }): Promise<GitBlameResponse>;
+  getGitStashes(root: string): Promise<GitStashListResponse>;
   fetchGit(data: { root: string; remote?: string }): Promise<GitActionResponse>;
   pullGit(data: { root: string; mode?: 'ff-only' | 'rebase' | string }): Promise<GitActionResponse>;
   pushGit(data: { root: string; remote?: string; branch?: string; setUpstream?: boolean }): Promise<GitActionResponse>;
@@ -30,6 +42,22 @@ export interface WorkspaceGitFacade {
   unstageGitPaths(data: { root: string; paths: string[] }): Promise<GitActionResponse>;
   discardGitPaths(data: { root: string; paths: string[] }): Promise<GitActionResponse>;
   commitGit(data: { root: string; message: string; paths?: string[] }): Promise<GitActionResponse>;
+  stashGitChanges(data: { root: string; message?: string; includeUntracked?: boolean; paths?: string[] }): Promise<GitActionResponse>;
+  applyGitStash(data: { root: string; index?: number; pop?: boolean; restoreIndex?: boolean }): Promise<GitActionResponse>;
+  dropGitStash(data: { root: string; index?: number }): Promise<GitActionResponse>;
+  getGitConflicts(data: { root: string; path?: string }): Promise<GitConflictsResponse>;
+  resolveGitConflict(data: {
+    root: string;
+    path: string;
+    choice?: 'ours' | 'theirs';
+    content?: string;
+    hunks?: GitHunkResolution[];
+    stage?: boolean;
+  }): Promise<GitActionResponse>;
+  continueGitOperation(data: { root: string; operation?: GitOperationName }): Promise<GitActionResponse>;
+  abortGitOperation(data: { root: string; operation?: GitOperationName }): Promise<GitActionResponse>;
+  rebaseGit(data: { root: string; upstream: string; onto?: string; steps?: GitRebaseStep[] }): Promise<GitActionResponse>;
+  cherryPickGit(data: { root: string; commits: string[]; noCommit?: boolean }): Promise<GitActionResponse>;
 }
 
 export const workspaceGitFacade: WorkspaceGitFacade & ThisType<ApiClient> = {
@@ -51,6 +79,18 @@ export const workspaceGitFacade: WorkspaceGitFacade & ThisType<ApiClient> = {
   async getGitDiff(data) {
     return workspaceApi.getGitDiff(this.getRequestFn(), data);
   },
+  async getGitLog(data) {
+    return workspaceApi.getGitLog(this.getRequestFn(), data);
+  },
+  async getGitCommit(data) {
+    return workspaceApi.getGitCommit(this.getRequestFn(), data);
+  },
+  async getGitBlame(data) {
+    return workspaceApi.getGitBlame(this.getRequestFn(), data);
+  },
+  async getGitStashes(root) {
+    return workspaceApi.getGitStashes(this.getRequestFn(), root);
+  },
   async fetchGit(data) {
     return workspaceApi.fetchGit(this.getRequestFn(), data);
   },
@@ -81,4 +121,31 @@ export const workspaceGitFacade: WorkspaceGitFacade & ThisType<ApiClient> = {
   async commitGit(data) {
     return workspaceApi.commitGit(this.getRequestFn(), data);
   },
+  async stashGitChanges(data) {
+    return workspaceApi.stashGitChanges(this.getRequestFn(), data);
+  },
+  async applyGitStash(data) {
+    return workspaceApi.applyGitStash(this.getRequestFn(), data);
+  },
+  async dropGitStash(data) {
+    return workspaceApi.dropGitStash(this.getRequestFn(), data);
+  },
+  async getGitConflicts(data) {
+    return workspaceApi.getGitConflicts(this.getRequestFn(), data);
+  },
+  async resolveGitConflict(data) {
+    return workspaceApi.resolveGitConflict(this.getRequestFn(), data);
+  },
+  async continueGitOperation(data) {
+    return workspaceApi.continueGitOperation(this.getRequestFn(), data);
+  },
+  async abortGitOperation(data) {
+    return workspaceApi.abortGitOperation(this.getRequestFn(), data);
+  },
+  async rebaseGit(data) {
+    return workspaceApi.rebaseGit(this.getRequestFn(), data);
+  },
+  async cherryPickGit(data) {
+    return workspaceApi.cherryPickGit(this.getRequestFn(), data);
+  },
 };
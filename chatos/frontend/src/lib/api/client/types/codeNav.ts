@@ -10,6 +10,12 @@ export interface CodeNavCapabilitiesResponse {
   supportsReferences?: boolean;
   supports_document_symbols?: boolean;
   supportsDocumentSymbols?: boolean;
+  supports_hover?: boolean;
+  supportsHover?: boolean;
+  supports_call_hierarchy?: boolean;
+  supportsCallHierarchy?: boolean;
+  supports_workspace_symbols?: boolean;
+  supportsWorkspaceSymbols?: boolean;
   fallback_available?: boolean;
   fallbackAvailable?: boolean;
 }
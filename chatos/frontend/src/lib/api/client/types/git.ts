@@ -122,3 +122,100 @@ export interface GitActionResponse {
   stdout?: string | null;
   stderr?: string | null;
 }
+
+export interface GitLogCommitResponse {
+  hash?: string;
+  short_hash?: string;
+  parents?: string[];
+  author_name?: string;
+  author_email?: string;
+  authored_at?: string;
+  committer_name?: string;
+  committed_at?: string;
+  subject?: string;
+  body?: string;
+}
+
+export interface GitLogResponse {
+  revision?: string | null;
+  path?: string | null;
+  skip?: number;
+  limit?: number;
+  has_more?: boolean;
+  commits?: GitLogCommitResponse[];
+}
+
+export interface GitCommitDetailResponse {
+  commit?: GitLogCommitResponse;
+  files?: GitDiffFileResponse[];
+  patch?: string;
+  patch_truncated?: boolean;
+}
+
+export interface GitBlameLineResponse {
+  line?: number;
+  original_line?: number;
+  hash?: string;
+  author_name?: string;
+  author_email?: string;
+  authored_at?: string | null;
+  summary?: string;
+  original_path?: string | null;
+  content?: string;
+}
+
+export interface GitBlameResponse {
+  path?: string;
+  revision?: string | null;
+  lines?: GitBlameLineResponse[];
+}
+
+export interface GitStashEntryResponse {
+  index?: number;
+  name?: string;
+  hash?: string;
+  branch?: string | null;
+  message?: string;
+  created_at?: string;
+}
+
+export interface GitStashListResponse {
+  entries?: GitStashEntryResponse[];
+}
+
+export interface GitConflictHunkResponse {
+  index?: number;
+  start_line?: number;
+  end_line?: number;
+  ours_label?: string | null;
+  theirs_label?: string | null;
+  ours?: string;
+  base?: string | null;
+  theirs?: string;
+}
+
+export interface GitConflictFileResponse {
+  path?: string;
+  hunks?: GitConflictHunkResponse[];
+  error?: string | null;
+}
+
+export interface GitConflictsResponse {
+  operation_state?: string | null;
+  files?: GitConflictFileResponse[];
+}
+
+export type GitHunkResolutionChoice = 'ours' | 'theirs' | 'base' | 'both' | 'custom';
+
+export interface GitHunkResolution {
+  index: number;
+  choice: GitHunkResolutionChoice;
+  content?: string;
+}
+
+export type GitOperationName = 'merge' | 'rebase' | 'cherry-pick';
+
+export interface GitRebaseStep {
+  action: 'pick' | 'squash' | 'fixup' | 'drop';
+  commit: string;
+}
@@ -280,6 +280,67 @@ export interface AgentToolsResponse {
   service?: string | null;
 }
 
+export interface McpPromptArgumentDescriptor {
+  name: string;
+  description?: string | null;
+  required?: boolean | null;
+}
+
+export interface McpPromptDescriptor {
+  server: string;
+  name: string;
+  title?: string | null;
+  description?: string | null;
+  arguments?: McpPromptArgumentDescriptor[];
+}
+
+export interface McpPromptListResponse {
+  prompts?: McpPromptDescriptor[];
+}
+
+export interface McpPromptRenderPayload {
+  server: string;
+  name: string;
+  arguments?: Record<string, string>;
+}
+
+export interface McpPromptRenderResponse {
+  items?: Array<{ role: string; content: string }>;
+  text?: string;
+}
+
+export interface CommittedEditSessionFile {
+  path: string;
+  change_kind: 'create' | 'edit' | 'delete' | 'move';
+  moved_from?: string | null;
+}
+
+export interface CommittedEditSessionSummary {
+  session_id: string;
+  run_id?: string | null;
+  committed_at?: string | null;
+  reverted_at?: string | null;
+  reverted_by_session_id?: string | null;
+  revertible: boolean;
+  files: CommittedEditSessionFile[];
+}
+
+export interface EditSessionListResponse {
+  result?: {
+    scope?: string;
+    sessions?: CommittedEditSessionSummary[];
+  };
+}
+
+export interface EditSessionRevertResponse {
+  outcome?: string;
+  message?: string;
+  result?: {
+    session_id?: string;
+    reverted_session_id?: string;
+  };
+}
+
 export interface TurnRuntimeSnapshotSystemMessage {
   id: string;
   source: string;
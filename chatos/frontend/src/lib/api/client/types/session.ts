@@ -135,6 +135,39 @@ export interface ContactAgentRecallResponse {
   confidence?: number | null;
   last_seen_at?: string | null;
   updated_at?: string;
+  pinned?: boolean;
+  expires_at?: string | null;
+  version?: number;
+  edited_by?: string | null;
+}
+
+export interface ContactAgentRecallUpdatePayload {
+  recall_text?: string;
+  pinned?: boolean;
+  expires_at?: string;
+  ttl_seconds?: number;
+  clear_expiry?: boolean;
+  expected_version?: number;
+  reason?: string;
+}
+
+export interface ContactAgentRecallVersionResponse {
+  id: string;
+  memory_id: string;
+  version: number;
+  text: string;
+  pinned?: boolean;
+  expires_at?: string | null;
+  edited_by?: string | null;
+  reason?: string | null;
+  created_at: string;
+}
+
+export interface ContactAgentRecallProvenanceResponse {
+  memory: Record<string, unknown>;
+  source_summaries: Array<Record<string, unknown>>;
+  source_memories: Array<Record<string, unknown>>;
+  source_records: Array<Record<string, unknown>>;
 }
 
 export interface SessionMessageResponse {
@@ -3,7 +3,10 @@
 
 import { buildQuery } from '../shared';
 import type {
+  ContactAgentRecallProvenanceResponse,
   ContactAgentRecallResponse,
+  ContactAgentRecallUpdatePayload,
+  ContactAgentRecallVersionResponse,
   ContactCreateResponse,
   ContactProjectLinkResponse,
   ContactProjectMemoryResponse,
@@ -105,3 +108,38 @@ export const getContactAgentRecalls = (
     `/contacts/${encodeURIComponent(contactId)}/agent-recalls${query}`,
   );
 };
+
+export const updateContactAgentRecall = (
+  request: ApiRequestFn,
+  contactId: string,
+  recallId: string,
+  data: ContactAgentRecallUpdatePayload,
+): Promise<ContactAgentRecallResponse> => {
+  return request<ContactAgentRecallResponse>(
+    `/contacts/${encodeURIComponent(contactId)}/agent-recalls/${encodeURIComponent(recallId)}`,
+    {
+      method: 'PATCH',
+      body: JSON.stringify(data),
+    },
+  );
+};
+
+export const getContactAgentRecallVersions = (
+  request: ApiRequestFn,
+  contactId: string,
+  recallId: string,
+): Promise<ContactAgentRecallVersionResponse[]> => {
+  return request<ContactAgentRecallVersionResponse[]>(
+    `/contacts/${encodeURIComponent(contactId)}/agent-recalls/${encodeURIComponent(recallId)}/versions`,
+  );
+};
+
+export const getContactAgentRecallProvenance = (
+  request: ApiRequestFn,
+  contactId: string,
+  recallId: string,
+): Promise<ContactAgentRecallProvenanceResponse> => {
+  return request<ContactAgentRecallProvenanceResponse>(
+    `/contacts/${encodeURIComponent(contactId)}/agent-recalls/${encodeURIComponent(recallId)}/provenance`,
+  );
+};
@@ -4,10 +4,18 @@
 import { buildQuery } from '../shared';
 import type {
   GitActionResponse,
+  GitBlameResponse,
   GitBranchesResponse,
   GitClientInfoResponse,
+  GitCommitDetailResponse,
   GitCompareResponse,
+  GitConflictsResponse,
   GitFileDiffResponse,
+  GitHunkResolution,
+  GitLogResponse,
+  GitOperationName,
+  GitRebaseStep,
+  GitStashListResponse,
   GitStatusResponse,
   GitSummaryResponse,
 } from '../types';
@@ -68,6 +76,50 @@ export const getGitDiff = (
   })}`);
 };
 
+export const getGitLog = (
+  request: ApiRequestFn,
+  data: { root: string; revision?: string; path?: string; skip?: number; limit?: number },
+): Promise<GitLogResponse> => {
+  return request<GitLogResponse>(`/git/log${buildQuery({
+    root: data.root,
+    revision: data.revision,
+    path: data.path,
+    skip: data.skip,
+    limit: data.limit,
+  })}`);
+};
+
+export const getGitCommit = (
+  request: ApiRequestFn,
+  data: { root: string; revision: string; path?: string },
+): Promise<GitCommitDetailResponse> => {
+  return request<GitCommitDetailResponse>(`/git/show${buildQuery({
+    root: data.root,
+    revision: data.revision,
+    path: data.path,
+  })}`);
+};
+
+export const getGitBlame = (
+  request: ApiRequestFn,
+  data: { root: string; path: string; revision?: string; startLine?: number; endLine?: number },
+): Promise<GitBlameResponse> => {
+  return request<GitBlameResponse>(`/git/blame${buildQuery({
+    root: data.root,
+    path: data.path,
+    revision: data.revision,
+    start_line: data.startLine,
+    end_line: data.endLine,
+  })}`);
+};
+
+export const getGitStashes = (
+  request: ApiRequestFn,
+  root: string,
+): Promise<GitStashListResponse> => {
+  return request<GitStashListResponse>(`/git/stashes${buildQuery({ root })}`);
+};
+
 export const fetchGit = (
   request: ApiRequestFn,
   data: { root: string; remote?: string },
@@ -205,3 +257,135 @@ export const discardGitPaths = (
     }),
   });
 };
+
+export const stashGitChanges = (
+  request: ApiRequestFn,
+  data: { root: string; message?: string; includeUntracked?: boolean; paths?: string[] },
+): Promise<GitActionResponse> => {
+  return request<GitActionResponse>('/git/stash', {
+    method: 'POST',
+    body: JSON.stringify({
+      root: data.root,
+      message: data.message,
+      include_untracked: data.includeUntracked,
+      paths: data.paths,
+    }),
+  });
+};
+
+export const applyGitStash = (
+  request: ApiRequestFn,
+  data: { root: string; index?: number; pop?: boolean; restoreIndex?: boolean },
+): Promise<GitActionResponse> => {
+  return request<GitActionResponse>('/git/stash/apply', {
+    method: 'POST',
+    body: JSON.stringify({
+      root: data.root,
+      index: data.index,
+      pop: data.pop,
+      restore_index: data.restoreIndex,
+    }),
+  });
+};
+
+export const dropGitStash = (
+  request: ApiRequestFn,
+  data: { root: string; index?: number },
+): Promise<GitActionResponse> => {
+  return request<GitActionResponse>('/git/stash/drop', {
+    method: 'POST',
+    body: JSON.stringify({
+      root: data.root,
+      index: data.index,
+    }),
+  });
+};
+
+export const getGitConflicts = (
+  request: ApiRequestFn,
+  data: { root: string; path?: string },
+): Promise<GitConflictsResponse> => {
+  return request<GitConflictsResponse>(`/git/conflicts${buildQuery({
+    root: data.root,
+    path: data.path,
+  })}`);
+};
+
+export const resolveGitConflict = (
+  request: ApiRequestFn,
+  data: {
+    root: string;
+    path: string;
+    choice?: 'ours' | 'theirs';
+    content?: string;
+    hunks?: GitHunkResolution[];
+    stage?: boolean;
+  },
+): Promise<GitActionResponse> => {
+  return request<GitActionResponse>('/git/conflicts/resolve', {
+    method: 'POST',
+    body: JSON.stringify({
+      root: data.root,
+      path: data.path,
+      choice: data.choice,
+      content: data.content,
+      hunks: data.hunks,
+      stage: data.stage,
+    }),
+  });
+};
+
+export const continueGitOperation = (
+  request: ApiRequestFn,
+  data: { root: string; operation?: GitOperationName },
+): Promise<GitActionResponse> => {
+  return request<GitActionResponse>('/git/operation/continue', {
+    method: 'POST',
+    body: JSON.stringify({
+      root: data.root,
+      operation: data.operation,
+    }),
+  });
+};
+
+export const abortGitOperation = (
+  request: ApiRequestFn,
+  data: { root: string; operation?: GitOperationName },
+): Promise<GitActionResponse> => {
+  return request<GitActionResponse>('/git/operation/abort', {
+    method: 'POST',
+    body: JSON.stringify({
+      root: data.root,
+      operation: data.operation,
+    }),
+  });
+};
+
+export const rebaseGit = (
+  request: ApiRequestFn,
+  data: { root: string; upstream: string; onto?: string; steps?: GitRebaseStep[] },
+): Promise<GitActionResponse> => {
+  return request<GitActionResponse>('/git/rebase', {
+    method: 'POST',
+    body: JSON.stringify({
+      root: data.root,
+      upstream: data.upstream,
+      onto: data.onto,
+      steps: data.steps,
+    }),
+  });
+};
+
+export const cherryPickGit = (
+  request: ApiRequestFn,
+  data: { root: string; commits: string[]; noCommit?: boolean },
+): Promise<GitActionResponse> => {
+  return request<GitActionResponse>('/git/cherry-pick', {
+    method: 'POST',
+    body: JSON.stringify({
+      root: data.root,
+      commits: data.commits,
+      no_commit: data.noCommit,
+    }),
+  });
+};
@@ -32,6 +32,9 @@ export const normalizeCodeNavCapabilities = (raw: CodeNavCapabilitiesResponse |
     supportsDefinition: readBooleanFirst(record, ['supports_definition', 'supportsDefinition']),
     supportsReferences: readBooleanFirst(record, ['supports_references', 'supportsReferences']),
     supportsDocumentSymbols: readBooleanFirst(record, ['supports_document_symbols', 'supportsDocumentSymbols']),
+    supportsHover: readBooleanFirst(record, ['supports_hover', 'supportsHover']),
+    supportsCallHierarchy: readBooleanFirst(record, ['supports_call_hierarchy', 'supportsCallHierarchy']),
+    supportsWorkspaceSymbols: readBooleanFirst(record, ['supports_workspace_symbols', 'supportsWorkspaceSymbols']),
     fallbackAvailable: readBooleanFirst(record, ['fallback_available', 'fallbackAvailable'], true),
   };
 };
@@ -26,6 +26,8 @@ const CODE_TOOL_NAMES = new Set([
   'stage_edit_batch',
   'commit_edit_session',
   'abort_edit_session',
+  'revert_edit_session',
+  'list_edit_sessions',
 ]);
 
 const BROWSER_TOOL_NAMES = new Set([
@@ -0,0 +1,42 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+import { describe, expect, it } from 'vitest';
+
+import { parseToolNoticeChunk, readableToolStreamText } from './toolNotice';
+
+const chunk = (notice: Record<string, unknown>) => JSON.stringify({
+  event: 'tool_notice',
+  data: { tool_name: 'docs_build', server_name: 'docs', notice },
+});
+
+describe('tool notices', () => {
+  it('renders progress notices as a percentage with the server message', () => {
+    expect(readableToolStreamText(chunk({
+      kind: 'progress',
+      progress: 3,
+      total: 4,
+      message: 'Indexing pages',
+    }))).toBe('进度 75% · Indexing pages');
+    expect(readableToolStreamText(chunk({ kind: 'progress', progress: 12 }))).toBe('进度 12');
+  });
+
+  it('renders log notices with level, logger and data', () => {
+    expect(readableToolStreamText(chunk({
+      kind: 'log',
+      level: 'warning',
+      logger: 'indexer',
+      data: 'slow page',
+    }))).toBe('[warning · indexer] slow page');
+    expect(readableToolStreamText(chunk({
+      kind: 'log',
+      level: 'info',
+      data: { pages: 2 },
+    }))).toBe('[info] {"pages":2}');
+  });
+
+  it('leaves ordinary tool output untouched', () => {
+    expect(parseToolNoticeChunk('{"event":"ask_user_prompt_required","data":{}}')).toBeNull();
+    expect(readableToolStreamText('partial output')).toBe('partial output');
+  });
+});
@@ -0,0 +1,94 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+// Progress and log notices MCP servers send while a tool runs arrive as
+// `tool_notice` stream chunks: `{"event":"tool_notice","data":{...,"notice":{...}}}`.
+
+export type ToolServerNotice =
+  | { kind: 'progress'; progress: number; total?: number; message?: string }
+  | { kind: 'log'; level: string; logger?: string; data: unknown };
+
+const TOOL_NOTICE_EVENT = 'tool_notice';
+
+const readRecord = (value: unknown): Record<string, unknown> | null => (
+  value && typeof value === 'object' && !Array.isArray(value)
+    ? value as Record<string, unknown>
+    : null
+);
+
+const readNotice = (value: unknown): ToolServerNotice | null => {
+  const record = readRecord(value);
+  if (!record) {
+    return null;
+  }
+  if (record.kind === 'progress' && typeof record.progress === 'number') {
+    return {
+      kind: 'progress',
+      progress: record.progress,
+      total: typeof record.total === 'number' ? record.total : undefined,
+      message: typeof record.message === 'string' ? record.message : undefined,
+    };
+  }
+  if (record.kind === 'log') {
+    return {
+      kind: 'log',
+      level: typeof record.level === 'string' ? record.level : 'info',
+      logger: typeof record.logger === 'string' ? record.logger : undefined,
+      data: record.data,
+    };
+  }
+  return null;
+};
+
+export const parseToolNoticeChunk = (content: unknown): ToolServerNotice | null => {
+  let envelope: unknown = content;
+  if (typeof content === 'string') {
+    const trimmed = content.trim();
+    if (!trimmed.startsWith('{') || !trimmed.includes(TOOL_NOTICE_EVENT)) {
+      return null;
+    }
+    try {
+      envelope = JSON.parse(trimmed);
+    } catch {
+      return null;
+    }
+  }
+  const record = readRecord(envelope);
+  if (!record || record.event !== TOOL_NOTICE_EVENT) {
+    return null;
+  }
+  return readNotice(readRecord(record.data)?.notice);
+};
+
+const formatLogData = (data: unknown): string => {
+  if (typeof data === 'string') {
+    return data;
+  }
+  if (data === null || data === undefined) {
+    return '';
+  }
+  try {
+    return JSON.stringify(data);
+  } catch {
+    return String(data);
+  }
+};
+
+export const formatToolNotice = (notice: ToolServerNotice): string => {
+  if (notice.kind === 'progress') {
+    const amount = typeof notice.total === 'number' && notice.total > 0
+      ? `${Math.round(Math.min(notice.progress / notice.total, 1) * 100)}%`
+      : String(notice.progress);
+    return notice.message ? `进度 ${amount} · ${notice.message}` : `进度 ${amount}`;
+  }
+  const source = notice.logger ? `${notice.level} · ${notice.logger}` : notice.level;
+  const text = formatLogData(notice.data);
+  return text ? `[${source}] ${text}` : `[${source}]`;
+};
+
+// Tool stream text as shown to the user: notices become one readable line,
+// anything else is returned unchanged.
+export const readableToolStreamText = (content: string): string => {
+  const notice = parseToolNoticeChunk(content);
+  return notice ? formatToolNotice(notice) : content;
+};
@@ -231,6 +231,9 @@ export interface CodeNavCapabilities {
   supportsDefinition: boolean;
   supportsReferences: boolean;
   supportsDocumentSymbols: boolean;
+  supportsHover: boolean;
+  supportsCallHierarchy: boolean;
+  supportsWorkspaceSymbols: boolean;
   fallbackAvailable: boolean;
 }
 
@@ -280,5 +280,103 @@ pub(super) fn definitions(now: &str) -> Vec<ConfigDefinitionRecord> {
             3637,
             now,
         ),
+        definition(
+            MEMORY_ENGINE_EMBEDDING_PROVIDER_CONFIG_KEY,
+            "向量化 Provider",
+            "语义召回使用的向量化实现：disabled 关闭语义召回，openai_compatible 调用 /embeddings 接口，local_hashing 使用本地特征哈希",
+            "Memory Engine / AI",
+            "service",
+            Some("memory-engine"),
+            "string",
+            json!("local_hashing"),
+            None,
+            None,
+            &["disabled", "openai_compatible", "local_hashing"],
+            "restart_required",
+            &["MEMORY_ENGINE_EMBEDDING_PROVIDER"],
+            3671,
+            now,
+        ),
+        nullable_definition(
+            MEMORY_ENGINE_EMBEDDING_BASE_URL_CONFIG_KEY,
+            "向量化 Base URL",
+            "openai_compatible 向量化服务基础地址，留空时复用 OpenAI Base URL；本地模型可指向 Ollama 等兼容服务",
+            "Memory Engine / AI",
+            "service",
+            Some("memory-engine"),
+            "string",
+            Value::Null,
+            None,
+            None,
+            &[],
+            "restart_required",
+            &["MEMORY_ENGINE_EMBEDDING_BASE_URL"],
+            3672,
+            now,
+        ),
+        nullable_secret_definition(
+            MEMORY_ENGINE_EMBEDDING_API_KEY_CONFIG_KEY,
+            "向量化 API Key",
+            "openai_compatible 向量化服务使用的 API Key，留空时复用 OpenAI API Key",
+            "Memory Engine / AI",
+            "service",
+            Some("memory-engine"),
+            Value::Null,
+            "restart_required",
+            &["MEMORY_ENGINE_EMBEDDING_API_KEY"],
+            3673,
+            now,
+        ),
+        definition(
+            MEMORY_ENGINE_EMBEDDING_MODEL_CONFIG_KEY,
+            "向量化模型",
+            "openai_compatible 向量化服务使用的模型名称，切换模型后旧向量会按需重新生成",
+            "Memory Engine / AI",
+            "service",
+            Some("memory-engine"),
+            "string",
+            json!("text-embedding-3-small"),
+            None,
+            None,
+            &[],
+            "restart_required",
+            &["MEMORY_ENGINE_EMBEDDING_MODEL"],
+            3674,
+            now,
+        ),
+        definition(
+            MEMORY_ENGINE_EMBEDDING_DIMENSIONS_CONFIG_KEY,
+            "本地向量维度",
+            "local_hashing 生成的向量维度，远程 Provider 使用模型自身的输出维度",
+            "Memory Engine / AI",
+            "service",
+            Some("memory-engine"),
+            "integer",
+            json!(512),
+            Some(32),
+            Some(4_096),
+            &[],
+            "restart_required",
+            &["MEMORY_ENGINE_EMBEDDING_DIMENSIONS"],
+            3675,
+            now,
+        ),
+        definition(
+            MEMORY_ENGINE_EMBEDDING_BACKFILL_LIMIT_CONFIG_KEY,
+            "向量补齐批次",
+            "单次上下文组装最多为缺失向量的记录、摘要和主体记忆补齐的条目数",
+            "Memory Engine / AI",
+            "service",
+            Some("memory-engine"),
+            "integer",
+            json!(64),
+            Some(1),
+            Some(1_000),
+            &[],
+            "restart_required",
+            &["MEMORY_ENGINE_EMBEDDING_BACKFILL_LIMIT"],
+            3676,
+            now,
+        ),
     ]
 }
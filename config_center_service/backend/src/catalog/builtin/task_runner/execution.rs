@@ -87,6 +87,40 @@ pub(super) fn definitions(now: &str) -> Vec<ConfigDefinitionRecord> {
             210,
             now,
         ),
+        nullable_definition(
+            TASK_RUNNER_SPEND_RUN_BUDGET_USD_CONFIG_KEY,
+            "单次运行花费预算（USD）",
+            "单次 Task Run 累计模型花费上限；超出后运行在当前步骤结束时停止。为空表示不限制",
+            "Task Runner / Spend",
+            "service",
+            Some("task-runner"),
+            "number",
+            Value::Null,
+            Some(0),
+            None,
+            &[],
+            "next_run",
+            &[],
+            211,
+            now,
+        ),
+        nullable_definition(
+            TASK_RUNNER_SPEND_PROJECT_BUDGETS_USD_CONFIG_KEY,
+            "项目花费预算（USD）",
+            "按项目 ID 配置的累计模型花费上限，例如 {\"project-id\": 50}；未列出的项目不限制",
+            "Task Runner / Spend",
+            "service",
+            Some("task-runner"),
+            "json",
+            Value::Null,
+            None,
+            None,
+            &[],
+            "next_run",
+            &[],
+            212,
+            now,
+        ),
         definition(
             TASK_RUNNER_SUPPLY_CHAIN_BASELINE_REVISION_CONFIG_KEY,
             "Node.js 依赖基线修订号",
@@ -201,5 +235,73 @@ pub(super) fn definitions(now: &str) -> Vec<ConfigDefinitionRecord> {
             221,
             now,
         ),
+        definition(
+            TASK_RUNNER_SUPPLY_CHAIN_CARGO_BUILD_SCRIPT_ALLOWLIST_CONFIG_KEY,
+            "Cargo 构建脚本白名单",
+            "允许带 build.rs 或过程宏的 crate 名称列表；列表外的此类依赖记为供应链风险",
+            "Task Runner / Supply Chain",
+            "service",
+            Some("task-runner"),
+            "json",
+            json!([]),
+            None,
+            None,
+            &[],
+            "next_run",
+            &[],
+            231,
+            now,
+        ),
+        nullable_definition(
+            TASK_RUNNER_SUPPLY_CHAIN_PYTHON_INDEX_URL_CONFIG_KEY,
+            "Python 包索引地址",
+            "Python 依赖安装必须使用的包索引；为空表示不校验安装来源",
+            "Task Runner / Supply Chain",
+            "service",
+            Some("task-runner"),
+            "url",
+            Value::Null,
+            None,
+            None,
+            &[],
+            "next_run",
+            &[],
+            232,
+            now,
+        ),
+        definition(
+            TASK_RUNNER_SUPPLY_CHAIN_PYTHON_SOURCE_BUILD_ALLOWLIST_CONFIG_KEY,
+            "Python 源码构建白名单",
+            "允许从源码包构建安装的 Python 包名称列表；其余包必须使用 wheel",
+            "Task Runner / Supply Chain",
+            "service",
+            Some("task-runner"),
+            "json",
+            json!([]),
+            None,
+            None,
+            &[],
+            "next_run",
+            &[],
+            233,
+            now,
+        ),
+        definition(
+            TASK_RUNNER_SUPPLY_CHAIN_BLOCKING_ECOSYSTEMS_CONFIG_KEY,
+            "阻断完成的生态",
+            "审计未通过时阻止 Task Run 完成的生态列表，可选 node、cargo、python、go；未列出的生态只记录证据",
+            "Task Runner / Supply Chain",
+            "service",
+            Some("task-runner"),
+            "json",
+            json!([]),
+            None,
+            None,
+            &[],
+            "next_run",
+            &[],
+            234,
+            now,
+        ),
     ]
 }
@@ -87,6 +87,14 @@ pub const MEMORY_ENGINE_OPENAI_API_KEY_CONFIG_KEY: &str = "memory_engine.ai.open
 pub const MEMORY_ENGINE_OPENAI_BASE_URL_CONFIG_KEY: &str = "memory_engine.ai.openai_base_url";
 pub const MEMORY_ENGINE_OPENAI_MODEL_CONFIG_KEY: &str = "memory_engine.ai.openai_model";
 pub const MEMORY_ENGINE_OPENAI_TEMPERATURE_CONFIG_KEY: &str = "memory_engine.ai.openai_temperature";
+pub const MEMORY_ENGINE_EMBEDDING_PROVIDER_CONFIG_KEY: &str = "memory_engine.ai.embedding_provider";
+pub const MEMORY_ENGINE_EMBEDDING_BASE_URL_CONFIG_KEY: &str = "memory_engine.ai.embedding_base_url";
+pub const MEMORY_ENGINE_EMBEDDING_API_KEY_CONFIG_KEY: &str = "memory_engine.ai.embedding_api_key";
+pub const MEMORY_ENGINE_EMBEDDING_MODEL_CONFIG_KEY: &str = "memory_engine.ai.embedding_model";
+pub const MEMORY_ENGINE_EMBEDDING_DIMENSIONS_CONFIG_KEY: &str =
+    "memory_engine.ai.embedding_dimensions";
+pub const MEMORY_ENGINE_EMBEDDING_BACKFILL_LIMIT_CONFIG_KEY: &str =
+    "memory_engine.ai.embedding_backfill_limit";
 pub const MEMORY_ENGINE_WORKER_ENABLED_CONFIG_KEY: &str = "memory_engine.worker.enabled";
 pub const MEMORY_ENGINE_WORKER_INTERVAL_SECS_CONFIG_KEY: &str =
     "memory_engine.worker.interval_secs";
@@ -12,6 +12,17 @@ pub const TASK_RUNNER_SUPPLY_CHAIN_NODE_INSTALL_REGISTRY_CONFIG_KEY: &str =
     "task_runner.supply_chain.node_install_registry";
 pub const TASK_RUNNER_SUPPLY_CHAIN_NODE_AUDIT_REGISTRY_CONFIG_KEY: &str =
     "task_runner.supply_chain.node_audit_registry";
+pub const TASK_RUNNER_SUPPLY_CHAIN_CARGO_BUILD_SCRIPT_ALLOWLIST_CONFIG_KEY: &str =
+    "task_runner.supply_chain.cargo_build_script_allowlist";
+pub const TASK_RUNNER_SUPPLY_CHAIN_PYTHON_INDEX_URL_CONFIG_KEY: &str =
+    "task_runner.supply_chain.python_index_url";
+pub const TASK_RUNNER_SUPPLY_CHAIN_PYTHON_SOURCE_BUILD_ALLOWLIST_CONFIG_KEY: &str =
+    "task_runner.supply_chain.python_source_build_allowlist";
+pub const TASK_RUNNER_SUPPLY_CHAIN_BLOCKING_ECOSYSTEMS_CONFIG_KEY: &str =
+    "task_runner.supply_chain.blocking_ecosystems";
+pub const TASK_RUNNER_SPEND_RUN_BUDGET_USD_CONFIG_KEY: &str = "task_runner.spend.run_budget_usd";
+pub const TASK_RUNNER_SPEND_PROJECT_BUDGETS_USD_CONFIG_KEY: &str =
+    "task_runner.spend.project_budgets_usd";
 pub const TASK_RUNNER_QUEUE_CALLBACK_DELIVERY_MODE_CONFIG_KEY: &str =
     "task_runner.queue.callback_delivery_mode";
 pub const TASK_RUNNER_QUEUE_RABBITMQ_URL_CONFIG_KEY: &str = "task_runner.queue.rabbitmq_url";
@@ -97,6 +97,12 @@ fn catalog_exposes_task_runner_runtime_controls_without_env_overrides() {
         TASK_RUNNER_SUPPLY_CHAIN_BASELINE_REVISION_CONFIG_KEY,
         TASK_RUNNER_SUPPLY_CHAIN_NODE_AUDIT_LEVEL_CONFIG_KEY,
         TASK_RUNNER_SUPPLY_CHAIN_INSTALL_SCRIPT_ALLOWLIST_CONFIG_KEY,
+        TASK_RUNNER_SUPPLY_CHAIN_CARGO_BUILD_SCRIPT_ALLOWLIST_CONFIG_KEY,
+        TASK_RUNNER_SUPPLY_CHAIN_PYTHON_INDEX_URL_CONFIG_KEY,
+        TASK_RUNNER_SUPPLY_CHAIN_PYTHON_SOURCE_BUILD_ALLOWLIST_CONFIG_KEY,
+        TASK_RUNNER_SUPPLY_CHAIN_BLOCKING_ECOSYSTEMS_CONFIG_KEY,
+        TASK_RUNNER_SPEND_RUN_BUDGET_USD_CONFIG_KEY,
+        TASK_RUNNER_SPEND_PROJECT_BUDGETS_USD_CONFIG_KEY,
     ] {
         let definition = definitions
             .iter()
@@ -151,6 +157,41 @@ fn catalog_exposes_task_runner_runtime_controls_without_env_overrides() {
     assert_eq!(install_script_allowlist.value_type, "json");
     assert_eq!(install_script_allowlist.default_value, json!(["esbuild"]));
 
+    for key in [
+        TASK_RUNNER_SUPPLY_CHAIN_CARGO_BUILD_SCRIPT_ALLOWLIST_CONFIG_KEY,
+        TASK_RUNNER_SUPPLY_CHAIN_PYTHON_SOURCE_BUILD_ALLOWLIST_CONFIG_KEY,
+        TASK_RUNNER_SUPPLY_CHAIN_BLOCKING_ECOSYSTEMS_CONFIG_KEY,
+    ] {
+        let definition = definitions
+            .iter()
+            .find(|definition| definition.key == key)
+            .unwrap_or_else(|| panic!("missing definition for {key}"));
+        assert_eq!(definition.value_type, "json");
+        assert_eq!(definition.default_value, json!([]));
+        assert_eq!(definition.reload_mode, "next_run");
+    }
+
+    let python_index_url = definitions
+        .iter()
+        .find(|definition| definition.key == TASK_RUNNER_SUPPLY_CHAIN_PYTHON_INDEX_URL_CONFIG_KEY)
+        .expect("task runner Python index URL definition");
+    assert_eq!(python_index_url.value_type, "url");
+    assert!(python_index_url.nullable);
+    assert_eq!(python_index_url.default_value, Value::Null);
+
+    for (key, expected_value_type) in [
+        (TASK_RUNNER_SPEND_RUN_BUDGET_USD_CONFIG_KEY, "number"),
+        (TASK_RUNNER_SPEND_PROJECT_BUDGETS_USD_CONFIG_KEY, "json"),
+    ] {
+        let definition = definitions
+            .iter()
+            .find(|definition| definition.key == key)
+            .unwrap_or_else(|| panic!("missing definition for {key}"));
+        assert_eq!(definition.value_type, expected_value_type);
+        assert!(definition.nullable, "{key} must allow clearing the budget");
+        assert_eq!(definition.default_value, Value::Null);
+    }
+
     for (key, expected_default) in [
         (
             TASK_RUNNER_PROMPT_CACHE_ENABLED_CONFIG_KEY,
@@ -1756,6 +1797,42 @@ fn catalog_exposes_memory_engine_runtime_routes_via_env_projection() {
             "string",
             false,
         ),
+        (
+            MEMORY_ENGINE_EMBEDDING_PROVIDER_CONFIG_KEY,
+            "MEMORY_ENGINE_EMBEDDING_PROVIDER",
+            "string",
+            false,
+        ),
+        (
+            MEMORY_ENGINE_EMBEDDING_BASE_URL_CONFIG_KEY,
+            "MEMORY_ENGINE_EMBEDDING_BASE_URL",
+            "string",
+            true,
+        ),
+        (
+            MEMORY_ENGINE_EMBEDDING_API_KEY_CONFIG_KEY,
+            "MEMORY_ENGINE_EMBEDDING_API_KEY",
+            "string",
+            true,
+        ),
+        (
+            MEMORY_ENGINE_EMBEDDING_MODEL_CONFIG_KEY,
+            "MEMORY_ENGINE_EMBEDDING_MODEL",
+            "string",
+            false,
+        ),
+        (
+            MEMORY_ENGINE_EMBEDDING_DIMENSIONS_CONFIG_KEY,
+            "MEMORY_ENGINE_EMBEDDING_DIMENSIONS",
+            "integer",
+            false,
+        ),
+        (
+            MEMORY_ENGINE_EMBEDDING_BACKFILL_LIMIT_CONFIG_KEY,
+            "MEMORY_ENGINE_EMBEDDING_BACKFILL_LIMIT",
+            "integer",
+            false,
+        ),
         (
             MEMORY_ENGINE_WORKER_ENABLED_CONFIG_KEY,
             "MEMORY_ENGINE_WORKER_ENABLED",
@@ -93,6 +93,9 @@ use crate::catalog::{
     MEMORY_ENGINE_AI_REQUEST_TIMEOUT_SECS_CONFIG_KEY,
     MEMORY_ENGINE_CHATOS_INTERNAL_API_SECRET_CONFIG_KEY,
     MEMORY_ENGINE_CONFIGURATION_CENTER_INTERNAL_API_SECRET_CONFIG_KEY,
+    MEMORY_ENGINE_EMBEDDING_API_KEY_CONFIG_KEY, MEMORY_ENGINE_EMBEDDING_BACKFILL_LIMIT_CONFIG_KEY,
+    MEMORY_ENGINE_EMBEDDING_BASE_URL_CONFIG_KEY, MEMORY_ENGINE_EMBEDDING_DIMENSIONS_CONFIG_KEY,
+    MEMORY_ENGINE_EMBEDDING_MODEL_CONFIG_KEY, MEMORY_ENGINE_EMBEDDING_PROVIDER_CONFIG_KEY,
     MEMORY_ENGINE_INTERNAL_MTLS_PORT_CONFIG_KEY, MEMORY_ENGINE_OPENAI_API_KEY_CONFIG_KEY,
     MEMORY_ENGINE_OPENAI_BASE_URL_CONFIG_KEY, MEMORY_ENGINE_OPENAI_MODEL_CONFIG_KEY,
     MEMORY_ENGINE_OPENAI_TEMPERATURE_CONFIG_KEY,
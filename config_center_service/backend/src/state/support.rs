@@ -527,6 +527,12 @@ pub(super) const MEMORY_ENGINE_RUNTIME_CONFIG_KEYS: &[&str] = &[
     MEMORY_ENGINE_OPENAI_BASE_URL_CONFIG_KEY,
     MEMORY_ENGINE_OPENAI_MODEL_CONFIG_KEY,
     MEMORY_ENGINE_OPENAI_TEMPERATURE_CONFIG_KEY,
+    MEMORY_ENGINE_EMBEDDING_PROVIDER_CONFIG_KEY,
+    MEMORY_ENGINE_EMBEDDING_BASE_URL_CONFIG_KEY,
+    MEMORY_ENGINE_EMBEDDING_API_KEY_CONFIG_KEY,
+    MEMORY_ENGINE_EMBEDDING_MODEL_CONFIG_KEY,
+    MEMORY_ENGINE_EMBEDDING_DIMENSIONS_CONFIG_KEY,
+    MEMORY_ENGINE_EMBEDDING_BACKFILL_LIMIT_CONFIG_KEY,
     MEMORY_ENGINE_PLUGIN_MANAGEMENT_INTERNAL_API_SECRET_CONFIG_KEY,
     MEMORY_ENGINE_WORKER_ENABLED_CONFIG_KEY,
     MEMORY_ENGINE_WORKER_INTERVAL_SECS_CONFIG_KEY,
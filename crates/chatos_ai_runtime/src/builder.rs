@@ -6,6 +6,7 @@ use std::time::Duration;
 
 use crate::mcp_executor::McpRuntimeToolExecutor;
 use crate::memory_context::{MemoryContextComposer, MemoryEngineRecordWriter, MemoryRecordScope};
+use crate::request::AiCassette;
 use crate::runtime::{AiRuntime, MemoryContextOverflowRecovery};
 use crate::traits::{MemoryRecordWriter, ToolExecutor};
 use crate::turn::ContextualTurnRunner;
@@ -17,6 +18,7 @@ pub struct AiRuntimeBuilder {
     memory_composer: Option<MemoryContextComposer>,
     max_iterations: Option<usize>,
     request_read_timeout: Option<Duration>,
+    cassette: Option<Arc<AiCassette>>,
     context_overflow_recovery: Option<MemoryContextOverflowRecovery>,
 }
 
@@ -92,6 +94,11 @@ impl AiRuntimeBuilder {
         self
     }
 
+    pub fn with_cassette(mut self, cassette: Arc<AiCassette>) -> Self {
+        self.cassette = Some(cassette);
+        self
+    }
+
     pub fn with_context_overflow_recovery(
         mut self,
         context_overflow_recovery: Option<MemoryContextOverflowRecovery>,
@@ -108,6 +115,9 @@ impl AiRuntimeBuilder {
         if let Some(read_timeout) = self.request_read_timeout {
             runtime = runtime.with_request_read_timeout(read_timeout);
         }
+        if self.cassette.is_some() {
+            runtime = runtime.with_cassette(self.cassette);
+        }
         runtime
     }
 
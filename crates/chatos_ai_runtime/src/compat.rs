@@ -11,7 +11,13 @@ use crate::response_parse::text_value_or_json;
 pub struct UsageSnapshot {
     pub input_tokens: i64,
     pub cached_tokens: i64,
+    /// Part of `input_tokens` written to the prompt cache; `0` when the
+    /// provider does not break it out.
+    pub cache_write_tokens: i64,
     pub output_tokens: i64,
+    /// Part of `output_tokens` spent on hidden reasoning; `0` when the
+    /// provider does not break it out.
+    pub reasoning_tokens: i64,
 }
 
 pub fn cap_tool_output_for_input(raw: &str) -> String {
@@ -53,6 +59,24 @@ pub fn truncate_function_call_outputs_in_input(input: &Value) -> Option<Value> {
 }
 
 pub fn extract_usage_snapshot(usage: &Value) -> UsageSnapshot {
+    // Gemini `usageMetadata` counts thinking tokens separately from the
+    // visible candidates; both are billed as output.
+    if let Some(prompt_tokens) = usage_value_i64(usage, "promptTokenCount") {
+        let candidates_tokens = usage_value_i64(usage, "candidatesTokenCount");
+        let thoughts_tokens = usage_value_i64(usage, "thoughtsTokenCount");
+        return UsageSnapshot {
+            input_tokens: prompt_tokens,
+            cached_tokens: usage_value_i64(usage, "cachedContentTokenCount").unwrap_or(0),
+            cache_write_tokens: 0,
+            output_tokens: if candidates_tokens.is_some() || thoughts_tokens.is_some() {
+                candidates_tokens.unwrap_or(0) + thoughts_tokens.unwrap_or(0)
+            } else {
+                -1
+            },
+            reasoning_tokens: thoughts_tokens.unwrap_or(0),
+        };
+    }
+
     let input_tokens = usage_value_i64(usage, "input_tokens")
         .or_else(|| usage_value_i64(usage, "prompt_tokens"))
         .unwrap_or(-1);
@@ -62,11 +86,32 @@ pub fn extract_usage_snapshot(usage: &Value) -> UsageSnapshot {
     let cached_tokens = usage_nested_i64(usage, "input_tokens_details", "cached_tokens")
         .or_else(|| usage_nested_i64(usage, "prompt_tokens_details", "cached_tokens"))
         .unwrap_or(0);
+    let reasoning_tokens = usage_nested_i64(usage, "output_tokens_details", "reasoning_tokens")
+        .or_else(|| usage_nested_i64(usage, "completion_tokens_details", "reasoning_tokens"))
+        .unwrap_or(0);
+
+    // Anthropic reports cache reads and writes separately from `input_tokens`,
+    // which then only covers the uncached tail of the prompt.
+    let cache_read_tokens = usage_value_i64(usage, "cache_read_input_tokens");
+    let cache_creation_tokens = usage_value_i64(usage, "cache_creation_input_tokens");
+    if cache_read_tokens.is_some() || cache_creation_tokens.is_some() {
+        let cache_read_tokens = cache_read_tokens.unwrap_or(0);
+        let cache_creation_tokens = cache_creation_tokens.unwrap_or(0);
+        return UsageSnapshot {
+            input_tokens: input_tokens.max(0) + cache_read_tokens + cache_creation_tokens,
+            cached_tokens: cache_read_tokens,
+            cache_write_tokens: cache_creation_tokens,
+            output_tokens,
+            reasoning_tokens,
+        };
+    }
 
     UsageSnapshot {
         input_tokens,
         cached_tokens,
+        cache_write_tokens: 0,
         output_tokens,
+        reasoning_tokens,
     }
 }
 
@@ -77,8 +122,12 @@ pub fn log_usage_snapshot(purpose: &str, usage: Option<&Value>) {
     let snapshot = extract_usage_snapshot(usage);
 
     info!(
-        "[Agent Runtime] usage snapshot: purpose={}, input_tokens={}, cached_tokens={}, output_tokens={}",
-        purpose, snapshot.input_tokens, snapshot.cached_tokens, snapshot.output_tokens
+        "[Agent Runtime] usage snapshot: purpose={}, input_tokens={}, cached_tokens={}, output_tokens={}, reasoning_tokens={}",
+        purpose,
+        snapshot.input_tokens,
+        snapshot.cached_tokens,
+        snapshot.output_tokens,
+        snapshot.reasoning_tokens
     );
 }
 
@@ -298,7 +347,8 @@ mod tests {
         let responses_usage = json!({
             "input_tokens": 100,
             "output_tokens": 25,
-            "input_tokens_details": { "cached_tokens": 40 }
+            "input_tokens_details": { "cached_tokens": 40 },
+            "output_tokens_details": { "reasoning_tokens": 9 }
         });
         let chat_usage = json!({
             "prompt_tokens": 120,
@@ -311,7 +361,9 @@ mod tests {
             super::UsageSnapshot {
                 input_tokens: 100,
                 cached_tokens: 40,
-                output_tokens: 25
+                cache_write_tokens: 0,
+                output_tokens: 25,
+                reasoning_tokens: 9
             }
         );
         assert_eq!(
@@ -319,7 +371,52 @@ mod tests {
             super::UsageSnapshot {
                 input_tokens: 120,
                 cached_tokens: 12,
-                output_tokens: 30
+                cache_write_tokens: 0,
+                output_tokens: 30,
+                reasoning_tokens: 0
+            }
+        );
+    }
+
+    #[test]
+    fn extracts_usage_snapshot_from_anthropic_cache_counters() {
+        let anthropic_usage = json!({
+            "input_tokens": 20,
+            "cache_creation_input_tokens": 30,
+            "cache_read_input_tokens": 50,
+            "output_tokens": 7
+        });
+
+        assert_eq!(
+            extract_usage_snapshot(&anthropic_usage),
+            super::UsageSnapshot {
+                input_tokens: 100,
+                cached_tokens: 50,
+                cache_write_tokens: 30,
+                output_tokens: 7,
+                reasoning_tokens: 0
+            }
+        );
+    }
+
+    #[test]
+    fn extracts_usage_snapshot_from_gemini_usage_metadata() {
+        let gemini_usage = json!({
+            "promptTokenCount": 80,
+            "cachedContentTokenCount": 64,
+            "candidatesTokenCount": 12,
+            "thoughtsTokenCount": 30,
+            "totalTokenCount": 122
+        });
+
+        assert_eq!(
+            extract_usage_snapshot(&gemini_usage),
+            super::UsageSnapshot {
+                input_tokens: 80,
+                cached_tokens: 64,
+                cache_write_tokens: 0,
+                output_tokens: 42,
+                reasoning_tokens: 30
             }
         );
     }
@@ -149,10 +149,13 @@ pub fn is_retryable_failed_provider_response(err: &str) -> bool {
 pub fn is_retryable_provider_overload_error(err: &str) -> bool {
     let message = err.to_lowercase();
     message.contains("engine_overloaded_error")
+        || message.contains("overloaded_error")
+        || message.contains("status 529")
         || message.contains("server_is_overloaded")
         || message.contains("our servers are currently overloaded")
         || message.contains("server is currently overloaded")
         || message.contains("currently overloaded")
+        || message.contains("the model is overloaded")
         || message.contains("selected model is at capacity")
         || message.contains("model is at capacity")
         || (message.contains("at capacity") && message.contains("try a different model"))
@@ -167,6 +170,8 @@ pub fn is_rate_limited_provider_error(err: &str) -> bool {
     message.contains("rate limit exceeded")
         || message.contains("rate limit reached")
         || message.contains("rate_limit_exceeded")
+        || message.contains("rate_limit_error")
+        || (message.contains("status 429") && message.contains("resource_exhausted"))
         || message.contains("too many requests")
         || message.contains("requests rate limit")
         || (message.contains("status 429") && message.contains("try again later"))
@@ -176,6 +181,13 @@ pub fn is_retryable_provider_backpressure_error(err: &str) -> bool {
     is_rate_limited_provider_error(err) || is_retryable_provider_overload_error(err)
 }
 
+/// Errors that say the serving model, not the request, is unhealthy. The
+/// runtime moves to the next model in a fallback chain instead of retrying
+/// the same one.
+pub fn is_model_failover_error(err: &str) -> bool {
+    is_retryable_provider_backpressure_error(err) || is_upstream_auth_unavailable_error(err)
+}
+
 pub fn is_provider_authentication_error(err: &str) -> bool {
     let message = err.to_lowercase();
     message.contains("status 401")
@@ -419,8 +431,9 @@ mod tests {
     use super::{
         classify_transient_retry, classify_user_facing_ai_error, exhausted_transient_retry_message,
         handle_transient_retry, handle_transient_retry_with_abort,
-        is_context_length_exceeded_error, is_provider_authentication_error,
-        is_rate_limited_provider_error, is_request_body_too_large_error, is_response_parse_error,
+        is_context_length_exceeded_error, is_model_failover_error,
+        is_provider_authentication_error, is_rate_limited_provider_error,
+        is_request_body_too_large_error, is_response_parse_error,
         is_retryable_failed_provider_response, is_retryable_provider_backpressure_error,
         is_retryable_provider_overload_error, is_transient_network_error,
         is_transient_transport_or_parse_error, is_upstream_auth_unavailable_error,
@@ -562,6 +575,9 @@ mod tests {
         assert!(is_retryable_provider_overload_error(
             "Selected model is at capacity. Please try a different model."
         ));
+        assert!(is_retryable_provider_overload_error(
+            "ai response failed: finish_reason=failed; provider_error={\"type\":\"overloaded_error\",\"message\":\"Overloaded\"}"
+        ));
         assert!(!is_retryable_provider_overload_error(
             "status 400: invalid_request_error"
         ));
@@ -578,6 +594,12 @@ mod tests {
         assert!(!is_rate_limited_provider_error(
             "{\"error\":{\"message\":\"insufficient_quota\"}}"
         ));
+        assert!(is_rate_limited_provider_error(
+            "status 429 Too Many Requests: {\"error\":{\"code\":429,\"message\":\"Resource has been exhausted (e.g. check quota).\",\"status\":\"RESOURCE_EXHAUSTED\"}}"
+        ));
+        assert!(is_retryable_provider_overload_error(
+            "status 503 Service Unavailable: {\"error\":{\"code\":503,\"message\":\"The model is overloaded. Please try again later.\",\"status\":\"UNAVAILABLE\"}}"
+        ));
     }
 
     #[test]
@@ -601,6 +623,22 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn model_failover_covers_backpressure_and_upstream_auth_outages() {
+        assert!(is_model_failover_error(
+            "status 529: {\"type\":\"error\",\"error\":{\"type\":\"overloaded_error\"}}"
+        ));
+        assert!(is_model_failover_error(
+            "status 503 Service Unavailable: auth_unavailable: no auth available"
+        ));
+        assert!(!is_model_failover_error(
+            "status 400: invalid_request_error"
+        ));
+        assert!(!is_model_failover_error(
+            "connection reset before message completed"
+        ));
+    }
+
     #[test]
     fn detects_provider_authentication_errors() {
         assert!(is_provider_authentication_error(
@@ -8,6 +8,7 @@ pub mod file_write_recovery;
 pub mod input_transform;
 pub mod lifecycle;
 pub mod mcp_executor;
+pub mod mcp_sampling;
 pub mod memory_context;
 pub mod model_config;
 pub mod request;
@@ -24,6 +25,7 @@ pub mod tool_call;
 pub mod tool_runtime;
 pub mod traits;
 pub mod turn;
+pub mod usage_cost;
 
 pub use builder::AiRuntimeBuilder;
 pub use compat::{
@@ -33,8 +35,8 @@ pub use compat::{
 pub use error_policy::{
     classify_transient_retry, classify_user_facing_ai_error, exhausted_transient_retry_message,
     handle_transient_retry, handle_transient_retry_with_abort, is_context_length_exceeded_error,
-    is_invalid_input_text_error, is_missing_tool_call_error, is_rate_limited_provider_error,
-    is_request_body_too_large_error, is_response_parse_error,
+    is_invalid_input_text_error, is_missing_tool_call_error, is_model_failover_error,
+    is_rate_limited_provider_error, is_request_body_too_large_error, is_response_parse_error,
     is_retryable_provider_backpressure_error, is_retryable_provider_overload_error,
     is_transient_network_error, is_transient_transport_or_parse_error,
     is_upstream_auth_unavailable_error, is_upstream_connection_interrupted_error,
@@ -53,14 +55,20 @@ pub use lifecycle::{
     RuntimeIterationContext, RuntimeLifecycleHook, TaskFinalizationLifecycleHook,
 };
 pub use mcp_executor::McpRuntimeToolExecutor;
+pub use mcp_sampling::{AiMcpSamplingHandler, McpSamplingUsageRecorder};
 pub use memory_context::{
     compose_response_to_input_items, compose_response_to_input_items_with_budget,
     MemoryContextComposer, MemoryEngineRecordWriter, MemoryRecordScope, MemoryScope,
 };
-pub use request::{AiRequestHandler, AiRequestOptions, AiResponse, AiTransport, StreamCallbacks};
+pub use request::{
+    normalize_cassette_request, AiCassette, AiCassetteFile, AiCassetteInteraction, AiCassetteMode,
+    AiCassetteResponse, AiRequestHandler, AiRequestOptions, AiResponse, AiTransport,
+    StreamCallbacks,
+};
 pub use runtime::{
-    AiRuntime, AiRuntimeOptions, AiRuntimeResult, AiSingleStepOutcome, AiSingleStepRequest,
-    AiTurnReport, AiTurnStatus, IterativeContextRefresh, MemoryContextOverflowRecovery,
+    model_circuit_key, AiIterationModel, AiRuntime, AiRuntimeOptions, AiRuntimeResult,
+    AiSingleStepOutcome, AiSingleStepRequest, AiTurnReport, AiTurnStatus, IterativeContextRefresh,
+    MemoryContextOverflowRecovery, ModelCircuitBreaker, DEFAULT_MODEL_CIRCUIT_COOLDOWN,
 };
 pub use simple_prompt::{
     base_url_disallows_system_messages, base_url_requires_responses_input_list,
@@ -101,3 +109,4 @@ pub use turn::{
     build_contextual_input, input_value_to_items, message_item, user_text_item,
     ContextualTurnRequest, ContextualTurnRunner, RuntimeTurnSpec,
 };
+pub use usage_cost::{ModelPricing, UsageTotals};
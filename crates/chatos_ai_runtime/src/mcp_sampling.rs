@@ -0,0 +1,203 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chatos_mcp_runtime::{McpSamplingHandler, ToolCallerModelRuntime};
+use serde_json::{json, Value};
+
+use crate::simple_prompt::{
+    build_responses_text_input, run_compatible_prompt_with, select_preferred_response_text,
+    SimplePromptOptions,
+};
+use crate::{AiRequestHandler, ModelRuntimeConfig, StreamCallbacks};
+
+/// Answers MCP `sampling/createMessage` with the model of the turn that
+/// called the tool. Server model preferences are ignored: the server gets the
+/// caller's model or nothing, and never more output tokens than the caller's
+/// model config allows.
+#[derive(Debug, Default, Clone)]
+pub struct AiMcpSamplingHandler {
+    usage_recorder: Option<Arc<dyn McpSamplingUsageRecorder>>,
+}
+
+impl AiMcpSamplingHandler {
+    /// Reports the provider usage of every answered request, so callers can
+    /// bill sampling like the turn's own model requests.
+    pub fn with_usage_recorder(mut self, recorder: Arc<dyn McpSamplingUsageRecorder>) -> Self {
+        self.usage_recorder = Some(recorder);
+        self
+    }
+}
+
+/// Receives the usage of sampling requests answered by [`AiMcpSamplingHandler`].
+#[async_trait]
+pub trait McpSamplingUsageRecorder: Debug + Send + Sync {
+    /// `usage` is the provider's usage object for one sampling request made
+    /// with `caller_model_runtime`.
+    async fn record_sampling_usage(
+        &self,
+        caller_model_runtime: &ToolCallerModelRuntime,
+        usage: &Value,
+    );
+}
+
+#[async_trait]
+impl McpSamplingHandler for AiMcpSamplingHandler {
+    async fn create_message(
+        &self,
+        caller_model_runtime: &ToolCallerModelRuntime,
+        params: Value,
+    ) -> Result<Value, String> {
+        let prompt = sampling_prompt(&params)?;
+        let config = sampling_model_config(caller_model_runtime);
+        let handler = AiRequestHandler::new();
+        let response = run_compatible_prompt_with(
+            &handler,
+            &config,
+            prompt.as_str(),
+            SimplePromptOptions {
+                system_prompt: params
+                    .get("systemPrompt")
+                    .and_then(Value::as_str)
+                    .map(ToOwned::to_owned),
+                temperature: params.get("temperature").and_then(Value::as_f64),
+                max_output_tokens: sampling_max_tokens(
+                    params.get("maxTokens").and_then(Value::as_i64),
+                    caller_model_runtime.max_output_tokens,
+                ),
+                callbacks: StreamCallbacks::default(),
+                ..Default::default()
+            },
+            build_responses_text_input,
+        )
+        .await?;
+        if let (Some(recorder), Some(usage)) =
+            (self.usage_recorder.as_ref(), response.usage.as_ref())
+        {
+            recorder
+                .record_sampling_usage(caller_model_runtime, usage)
+                .await;
+        }
+        let text = select_preferred_response_text(
+            response.content.as_str(),
+            response.reasoning.as_deref(),
+        )
+        .unwrap_or_default();
+        Ok(json!({
+            "role": "assistant",
+            "content": {"type": "text", "text": text},
+            "model": config.model,
+            "stopReason": sampling_stop_reason(response.finish_reason.as_deref()),
+        }))
+    }
+}
+
+fn sampling_model_config(runtime: &ToolCallerModelRuntime) -> ModelRuntimeConfig {
+    ModelRuntimeConfig::openai_compatible(
+        runtime.base_url.clone(),
+        runtime.api_key.clone(),
+        runtime.model.clone(),
+        runtime.provider.clone(),
+    )
+    .with_responses_support(runtime.supports_responses)
+    .with_thinking_level(runtime.thinking_level.clone())
+    .with_temperature(runtime.temperature)
+    .with_max_output_tokens(runtime.max_output_tokens)
+    .with_request_body_limit_bytes(runtime.request_body_limit_bytes)
+    .with_max_transient_retries(runtime.max_transient_retries)
+}
+
+/// The server's `maxTokens`, capped at the caller's own output limit.
+fn sampling_max_tokens(requested: Option<i64>, limit: Option<i64>) -> Option<i64> {
+    let requested = requested.filter(|tokens| *tokens > 0);
+    match (requested, limit) {
+        (Some(requested), Some(limit)) => Some(requested.min(limit)),
+        (requested, limit) => requested.or(limit),
+    }
+}
+
+/// Flattens the sampling messages into one prompt. A single user message is
+/// sent as-is; longer exchanges are labelled by role.
+fn sampling_prompt(params: &Value) -> Result<String, String> {
+    let messages = params
+        .get("messages")
+        .and_then(Value::as_array)
+        .filter(|messages| !messages.is_empty())
+        .ok_or("sampling/createMessage requires messages")?;
+    let parts = messages
+        .iter()
+        .map(|message| {
+            let role = message
+                .get("role")
+                .and_then(Value::as_str)
+                .unwrap_or("user");
+            let content = message.get("content").unwrap_or(&Value::Null);
+            let text = match content.get("type").and_then(Value::as_str) {
+                Some("text") => content
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_string(),
+                Some(other) => format!("[{other} content omitted]"),
+                None => String::new(),
+            };
+            (role, text)
+        })
+        .collect::<Vec<_>>();
+    if let [("user", text)] = parts.as_slice() {
+        return Ok(text.clone());
+    }
+    Ok(parts
+        .iter()
+        .map(|(role, text)| format!("{role}: {text}"))
+        .collect::<Vec<_>>()
+        .join("\n\n"))
+}
+
+fn sampling_stop_reason(finish_reason: Option<&str>) -> &'static str {
+    match finish_reason {
+        Some("length" | "max_tokens" | "max_output_tokens") => "maxTokens",
+        Some("stop_sequence") => "stopSequence",
+        _ => "endTurn",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{sampling_max_tokens, sampling_prompt, sampling_stop_reason};
+
+    #[test]
+    fn sampling_prompt_keeps_single_messages_and_labels_exchanges() {
+        let single = json!({"messages": [
+            {"role": "user", "content": {"type": "text", "text": "Summarize the diff"}}
+        ]});
+        assert_eq!(sampling_prompt(&single).unwrap(), "Summarize the diff");
+
+        let exchange = json!({"messages": [
+            {"role": "user", "content": {"type": "text", "text": "Hi"}},
+            {"role": "assistant", "content": {"type": "image", "data": "..."}}
+        ]});
+        assert_eq!(
+            sampling_prompt(&exchange).unwrap(),
+            "user: Hi\n\nassistant: [image content omitted]"
+        );
+        assert!(sampling_prompt(&json!({"messages": []})).is_err());
+        assert_eq!(sampling_stop_reason(Some("length")), "maxTokens");
+        assert_eq!(sampling_stop_reason(None), "endTurn");
+    }
+
+    #[test]
+    fn sampling_max_tokens_never_exceed_the_caller_limit() {
+        assert_eq!(sampling_max_tokens(Some(100_000), Some(4_096)), Some(4_096));
+        assert_eq!(sampling_max_tokens(Some(512), Some(4_096)), Some(512));
+        assert_eq!(sampling_max_tokens(Some(512), None), Some(512));
+        assert_eq!(sampling_max_tokens(None, Some(4_096)), Some(4_096));
+        assert_eq!(sampling_max_tokens(Some(0), Some(4_096)), Some(4_096));
+        assert_eq!(sampling_max_tokens(None, None), None);
+    }
+}
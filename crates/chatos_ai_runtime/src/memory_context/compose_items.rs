@@ -59,6 +59,7 @@ pub(super) fn standard_compose_policy() -> Option<ComposeContextPolicy> {
         include_subject_memory: Some(true),
         recent_record_limit: None,
         summary_limit: Some(2),
+        ..Default::default()
     })
 }
 
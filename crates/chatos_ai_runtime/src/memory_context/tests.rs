@@ -23,6 +23,7 @@ fn memory_scope_builder_keeps_runtime_source_key() {
         include_subject_memory: Some(true),
         recent_record_limit: Some(12),
         summary_limit: Some(3),
+        ..Default::default()
     };
     let scope = MemoryScope::thread("tenant_1", "task_runner", "task_thread_1")
         .with_subject_id("contact_1")
@@ -138,6 +139,7 @@ fn compose_response_to_input_items_rebuilds_tool_exchange_in_responses_shape() {
         meta: ComposeContextMeta {
             summary_count: 1,
             recent_record_count: 2,
+            semantic_hit_count: 0,
         },
     };
 
@@ -185,6 +187,7 @@ fn compose_response_to_input_items_skips_orphan_tool_outputs() {
         meta: ComposeContextMeta {
             summary_count: 0,
             recent_record_count: 1,
+            semantic_hit_count: 0,
         },
     };
 
@@ -225,6 +228,7 @@ fn compose_response_to_input_items_skips_orphan_tool_calls() {
         meta: ComposeContextMeta {
             summary_count: 0,
             recent_record_count: 1,
+            semantic_hit_count: 0,
         },
     };
 
@@ -293,6 +297,7 @@ fn compose_response_to_input_items_omits_large_tool_outputs() {
         meta: ComposeContextMeta {
             summary_count: 0,
             recent_record_count: 2,
+            semantic_hit_count: 0,
         },
     };
 
@@ -377,6 +382,7 @@ fn compose_response_to_input_items_keeps_oldest_tool_output_prefix_stable() {
         meta: ComposeContextMeta {
             summary_count: 0,
             recent_record_count: 4,
+            semantic_hit_count: 0,
         },
     };
 
@@ -453,6 +459,7 @@ fn compose_response_to_input_items_reads_tool_calls_from_structured_payload() {
         meta: ComposeContextMeta {
             summary_count: 0,
             recent_record_count: 2,
+            semantic_hit_count: 0,
         },
     };
 
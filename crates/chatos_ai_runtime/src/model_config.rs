@@ -7,16 +7,26 @@ pub fn normalize_provider(provider: &str) -> String {
         "kimik2" | "kimi" | "moonshot" => "kimi".to_string(),
         "glm" | "zhipu" | "zhipuai" | "zai" | "chatglm" => "glm".to_string(),
         "openai-compatible" | "openai_compatible" | "compatible" => "openai_compatible".to_string(),
+        "anthropic" | "claude" => "anthropic".to_string(),
+        "gemini" | "google" | "google_ai" | "googleai" => "gemini".to_string(),
         other => other.to_string(),
     }
 }
 
+pub fn is_anthropic_provider(provider: &str) -> bool {
+    normalize_provider(provider) == "anthropic"
+}
+
+pub fn is_gemini_provider(provider: &str) -> bool {
+    normalize_provider(provider) == "gemini"
+}
+
 pub fn is_gpt_provider(provider: &str) -> bool {
     normalize_provider(provider) == "gpt"
 }
 
-pub fn effective_responses_support(_provider: &str, base_url: &str, configured: bool) -> bool {
-    if !configured {
+pub fn effective_responses_support(provider: &str, base_url: &str, configured: bool) -> bool {
+    if !configured || is_anthropic_provider(provider) || is_gemini_provider(provider) {
         return false;
     }
     let base_url = base_url.trim().to_ascii_lowercase();
@@ -66,6 +76,8 @@ pub fn default_base_url_for_provider(provider: &str, fallback_base_url: &str) ->
         "deepseek" => "https://api.deepseek.com".to_string(),
         "kimi" => "https://api.moonshot.ai/v1".to_string(),
         "glm" => "https://open.bigmodel.cn/api/paas/v4".to_string(),
+        "anthropic" => "https://api.anthropic.com/v1".to_string(),
+        "gemini" => "https://generativelanguage.googleapis.com/v1beta".to_string(),
         _ => {
             let fallback = fallback_base_url.trim();
             if fallback.is_empty() {
@@ -106,6 +118,7 @@ pub fn normalize_thinking_level(
         "gpt" => ["none", "minimal", "low", "medium", "high", "xhigh"].as_slice(),
         "deepseek" => ["none", "low", "medium", "high", "max"].as_slice(),
         "kimi" => ["none", "auto", "low", "medium", "high", "xhigh"].as_slice(),
+        "anthropic" | "gemini" => ["none", "minimal", "low", "medium", "high", "xhigh"].as_slice(),
         _ => ["none", "low", "medium", "high", "xhigh"].as_slice(),
     };
     if provider == "openai_compatible" && normalized == "minimal" {
@@ -133,11 +146,59 @@ pub fn reasoning_effort_for_provider(
             "low" | "medium" | "high" | "auto" | "minimal" => Some("high".to_string()),
             _ => None,
         },
-        "kimi" => None,
+        "kimi" | "anthropic" | "gemini" => None,
         _ => Some(normalized),
     }
 }
 
+/// Maps a normalized thinking level onto an Anthropic extended-thinking
+/// `budget_tokens` value. `None` means thinking stays disabled.
+pub fn anthropic_thinking_budget_tokens(level: Option<&str>) -> Option<i64> {
+    let normalized = normalize_thinking_level("anthropic", level)
+        .ok()
+        .flatten()?;
+    match normalized.as_str() {
+        "minimal" => Some(1_024),
+        "low" => Some(4_096),
+        "medium" => Some(12_000),
+        "high" => Some(24_000),
+        "xhigh" => Some(48_000),
+        _ => None,
+    }
+}
+
+/// Maps a normalized thinking level onto a Gemini `thinkingConfig`. Gemini 3
+/// models take a coarse `thinkingLevel`; 2.5 models take a token budget and
+/// Pro variants cannot turn thinking off, so `none` leaves their default.
+pub fn gemini_thinking_config(model: &str, level: Option<&str>) -> Option<serde_json::Value> {
+    let normalized = normalize_thinking_level("gemini", level).ok().flatten()?;
+    let model = model.trim().to_ascii_lowercase();
+    if model.contains("gemini-3") {
+        let thinking_level = match normalized.as_str() {
+            "none" | "minimal" | "low" => "low",
+            _ => "high",
+        };
+        return Some(serde_json::json!({
+            "thinkingLevel": thinking_level,
+            "includeThoughts": true,
+        }));
+    }
+    let budget = match normalized.as_str() {
+        "none" if model.contains("pro") => return None,
+        "none" => return Some(serde_json::json!({ "thinkingBudget": 0 })),
+        "minimal" => 512,
+        "low" => 2_048,
+        "medium" => 8_192,
+        "high" => 16_384,
+        "xhigh" => 24_576,
+        _ => return None,
+    };
+    Some(serde_json::json!({
+        "thinkingBudget": budget,
+        "includeThoughts": true,
+    }))
+}
+
 pub fn thinking_mode_for_provider(
     provider: Option<&str>,
     level: Option<&str>,
@@ -161,6 +222,13 @@ pub fn thinking_mode_for_provider(
                 None
             }
         }
+        "anthropic" => {
+            if normalized == "none" {
+                Some("disabled")
+            } else {
+                Some("enabled")
+            }
+        }
         _ => None,
     }
 }
@@ -168,7 +236,8 @@ pub fn thinking_mode_for_provider(
 #[cfg(test)]
 mod tests {
     use super::{
-        default_base_url_for_provider, effective_responses_support, normalize_provider,
+        anthropic_thinking_budget_tokens, default_base_url_for_provider,
+        effective_responses_support, gemini_thinking_config, normalize_provider,
         normalize_thinking_level, reasoning_effort_for_provider, supports_previous_response_id,
         supports_responses_input_token_count, thinking_mode_for_provider,
     };
@@ -180,6 +249,7 @@ mod tests {
         assert_eq!(normalize_provider("moonshot"), "kimi");
         assert_eq!(normalize_provider("zhipu"), "glm");
         assert_eq!(normalize_provider("openai-compatible"), "openai_compatible");
+        assert_eq!(normalize_provider("Claude"), "anthropic");
     }
 
     #[test]
@@ -242,6 +312,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn maps_anthropic_thinking_controls_to_budgets() {
+        assert_eq!(
+            default_base_url_for_provider("anthropic", "https://api.openai.com/v1"),
+            "https://api.anthropic.com/v1"
+        );
+        assert_eq!(
+            anthropic_thinking_budget_tokens(Some("minimal")),
+            Some(1_024)
+        );
+        assert_eq!(anthropic_thinking_budget_tokens(Some("max")), Some(48_000));
+        assert_eq!(anthropic_thinking_budget_tokens(Some("none")), None);
+        assert_eq!(anthropic_thinking_budget_tokens(None), None);
+        assert_eq!(
+            reasoning_effort_for_provider(Some("anthropic"), Some("high")),
+            None
+        );
+        assert_eq!(
+            thinking_mode_for_provider(Some("claude"), Some("high")),
+            Some("enabled")
+        );
+        assert!(!effective_responses_support(
+            "anthropic",
+            "https://api.anthropic.com/v1",
+            true
+        ));
+    }
+
+    #[test]
+    fn maps_gemini_thinking_controls_to_thinking_config() {
+        assert_eq!(normalize_provider("google"), "gemini");
+        assert_eq!(
+            default_base_url_for_provider("gemini", "https://api.openai.com/v1"),
+            "https://generativelanguage.googleapis.com/v1beta"
+        );
+        assert_eq!(
+            gemini_thinking_config("gemini-2.5-flash", Some("none")),
+            Some(serde_json::json!({"thinkingBudget": 0}))
+        );
+        assert_eq!(gemini_thinking_config("gemini-2.5-pro", Some("none")), None);
+        assert_eq!(
+            gemini_thinking_config("gemini-2.5-pro", Some("high")),
+            Some(serde_json::json!({"thinkingBudget": 16_384, "includeThoughts": true}))
+        );
+        assert_eq!(
+            gemini_thinking_config("gemini-3-pro-preview", Some("medium")),
+            Some(serde_json::json!({"thinkingLevel": "high", "includeThoughts": true}))
+        );
+        assert_eq!(gemini_thinking_config("gemini-2.5-pro", None), None);
+        assert_eq!(
+            reasoning_effort_for_provider(Some("gemini"), Some("high")),
+            None
+        );
+        assert!(!effective_responses_support(
+            "gemini",
+            "https://generativelanguage.googleapis.com/v1beta",
+            true
+        ));
+    }
+
     #[test]
     fn maps_openai_compatible_minimal_to_low() {
         assert_eq!(
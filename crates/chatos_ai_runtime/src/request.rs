@@ -2,7 +2,7 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use serde_json::Value;
@@ -11,19 +11,22 @@ use tokio_util::sync::CancellationToken;
 use tracing::{info, warn};
 
 use crate::model_config::{
-    effective_responses_support, normalize_provider, supports_previous_response_id,
+    effective_responses_support, is_anthropic_provider, is_gemini_provider, normalize_provider,
+    supports_previous_response_id,
 };
 #[cfg(test)]
 use crate::request_payload::response_items_to_chat_messages;
 use crate::request_payload::{
-    build_chat_completions_request_payload, build_responses_request_payload,
+    build_anthropic_messages_request_payload, build_chat_completions_request_payload,
+    build_gemini_generate_content_request_payload, build_responses_request_payload,
+    gemini_generate_content_url,
 };
 use crate::request_retry::should_retry_without_prompt_cache_options;
 use http::{
-    log_preview, read_error_response_text_limited, retry_after_delay_ms, send_json_request,
-    serialize_request_payload, validate_request_payload_size,
+    log_preview, provider_request_headers, read_error_response_text_limited, retry_after_delay_ms,
+    send_json_request, serialize_request_payload, validate_request_payload_size,
 };
-use streaming::parse_stream_response;
+use streaming::{parse_stream_chunks, parse_stream_response};
 
 const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 15;
 const DEFAULT_READ_TIMEOUT_SECS: u64 = 7_200;
@@ -31,12 +34,17 @@ const MAX_CONFIGURED_TIMEOUT_SECS: u64 = 7_200;
 const AI_CONNECT_TIMEOUT_SECS_ENV: &str = "CHATOS_AI_CONNECT_TIMEOUT_SECS";
 const AI_READ_TIMEOUT_SECS_ENV: &str = "CHATOS_AI_READ_TIMEOUT_SECS";
 
+mod cassette;
 mod http;
 mod streaming;
 #[cfg(test)]
 mod tests;
 mod types;
 
+pub use cassette::{
+    normalize_cassette_request, AiCassette, AiCassetteFile, AiCassetteInteraction, AiCassetteMode,
+    AiCassetteResponse,
+};
 pub use types::{AiRequestOptions, AiResponse, AiTransport, StreamCallbacks};
 
 #[cfg(test)]
@@ -47,6 +55,7 @@ pub struct AiRequestHandler {
     client: reqwest::Client,
     read_timeout: Option<Duration>,
     input_token_count_capabilities: Arc<RwLock<HashMap<String, bool>>>,
+    cassette: Option<Arc<AiCassette>>,
 }
 
 impl AiRequestHandler {
@@ -70,6 +79,7 @@ impl AiRequestHandler {
             client,
             read_timeout: Some(read_timeout),
             input_token_count_capabilities: Arc::new(RwLock::new(HashMap::new())),
+            cassette: AiCassette::from_env(),
         }
     }
 
@@ -78,9 +88,33 @@ impl AiRequestHandler {
             client,
             read_timeout: None,
             input_token_count_capabilities: Arc::new(RwLock::new(HashMap::new())),
+            cassette: None,
         }
     }
 
+    /// Routes provider traffic through `cassette`: a recording cassette
+    /// captures every round trip, a replay cassette answers without a network.
+    pub fn with_cassette(mut self, cassette: Option<Arc<AiCassette>>) -> Self {
+        self.cassette = cassette;
+        self
+    }
+
+    pub fn cassette(&self) -> Option<&Arc<AiCassette>> {
+        self.cassette.as_ref()
+    }
+
+    fn replay_cassette(&self) -> Option<&AiCassette> {
+        self.cassette
+            .as_deref()
+            .filter(|cassette| cassette.mode() == AiCassetteMode::Replay)
+    }
+
+    fn recording_cassette(&self) -> Option<&AiCassette> {
+        self.cassette
+            .as_deref()
+            .filter(|cassette| cassette.mode() == AiCassetteMode::Record)
+    }
+
     pub fn read_timeout_seconds(&self) -> Option<u64> {
         self.read_timeout.map(|value| value.as_secs())
     }
@@ -92,6 +126,11 @@ impl AiRequestHandler {
         payload: Value,
         abort_token: Option<CancellationToken>,
     ) -> Result<Option<usize>, String> {
+        // Token counting is only an estimate refinement; replay falls back to
+        // the local estimate instead of recording a second kind of request.
+        if self.replay_cassette().is_some() {
+            return Ok(None);
+        }
         let capability_key = base_url.trim().trim_end_matches('/').to_ascii_lowercase();
         if self
             .input_token_count_capabilities
@@ -107,7 +146,7 @@ impl AiRequestHandler {
         let response = send_json_request(
             &self.client,
             url.as_str(),
-            api_key,
+            provider_request_headers(AiTransport::Responses, api_key, &payload),
             payload_body,
             abort_token,
             false,
@@ -215,7 +254,11 @@ impl AiRequestHandler {
         if !supports_previous_response_id(provider.as_deref().unwrap_or("gpt"), base_url) {
             options.previous_response_id = None;
         }
-        let transport = if supports_responses {
+        let transport = if is_anthropic_provider(provider.as_deref().unwrap_or("gpt")) {
+            AiTransport::AnthropicMessages
+        } else if is_gemini_provider(provider.as_deref().unwrap_or("gpt")) {
+            AiTransport::GeminiGenerateContent
+        } else if supports_responses {
             AiTransport::Responses
         } else {
             AiTransport::ChatCompletions
@@ -238,6 +281,7 @@ impl AiRequestHandler {
                 base_url,
                 api_key,
                 transport,
+                model.as_str(),
                 first_payload.clone(),
                 callbacks.clone(),
                 provider.clone(),
@@ -259,7 +303,7 @@ impl AiRequestHandler {
             let retry_payload = build_request_payload(
                 transport,
                 input,
-                model,
+                model.clone(),
                 instructions,
                 tools,
                 temperature,
@@ -273,6 +317,7 @@ impl AiRequestHandler {
                     base_url,
                     api_key,
                     transport,
+                    model.as_str(),
                     retry_payload,
                     callbacks,
                     provider,
@@ -289,6 +334,9 @@ impl AiRequestHandler {
         first_attempt
     }
 
+    /// Sends a payload built by the caller. The model is read from the
+    /// payload's `model` field, so Gemini payloads (which carry the model in
+    /// the URL instead) must go through `handle_request_with_options`.
     #[allow(clippy::too_many_arguments)]
     pub async fn send_prebuilt_payload_with_options(
         &self,
@@ -302,10 +350,16 @@ impl AiRequestHandler {
         on_before_send_model_request: Option<Arc<dyn Fn(Value) + Send + Sync>>,
         options: AiRequestOptions,
     ) -> Result<AiResponse, String> {
+        let model = payload
+            .get("model")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
         self.send_payload(
             base_url,
             api_key,
             transport,
+            model.as_str(),
             payload,
             callbacks,
             provider,
@@ -325,6 +379,7 @@ impl AiRequestHandler {
         base_url: &str,
         api_key: &str,
         transport: AiTransport,
+        model: &str,
         payload: Value,
         callbacks: StreamCallbacks,
         provider: Option<String>,
@@ -345,6 +400,12 @@ impl AiRequestHandler {
             AiTransport::ChatCompletions => {
                 format!("{}/chat/completions", base_url.trim_end_matches('/'))
             }
+            AiTransport::AnthropicMessages => {
+                format!("{}/messages", base_url.trim_end_matches('/'))
+            }
+            AiTransport::GeminiGenerateContent => {
+                gemini_generate_content_url(base_url, model, stream)
+            }
         };
         info!(
             transport = transport_label(transport),
@@ -354,10 +415,85 @@ impl AiRequestHandler {
             "dispatching ai provider request"
         );
         let request_started_at = Instant::now();
+        let parsed = match self.replay_cassette() {
+            Some(cassette) => {
+                replay_cassette_response(
+                    cassette,
+                    transport,
+                    &payload,
+                    callbacks,
+                    provider.as_deref(),
+                    thinking_level.as_deref(),
+                    abort_token,
+                )
+                .await
+            }
+            None => {
+                self.send_over_network(
+                    url.as_str(),
+                    api_key,
+                    transport,
+                    &payload,
+                    payload_body,
+                    callbacks,
+                    provider.as_deref(),
+                    thinking_level.as_deref(),
+                    abort_token,
+                    force_identity_encoding,
+                    request_started_at,
+                )
+                .await
+            }
+        };
+        match &parsed {
+            Ok(ai_response) => {
+                info!(
+                    transport = transport_label(transport),
+                    url = url.as_str(),
+                    response_id = ai_response.response_id.as_deref().unwrap_or(""),
+                    finish_reason = ai_response.finish_reason.as_deref().unwrap_or(""),
+                    content_bytes = ai_response.content.len(),
+                    reasoning_bytes = ai_response.reasoning.as_deref().map(str::len).unwrap_or(0),
+                    tool_call_count = ai_response_tool_call_count(ai_response),
+                    has_provider_error = ai_response.provider_error.is_some(),
+                    has_usage = ai_response.usage.is_some(),
+                    ai_provider_request_ms = request_started_at.elapsed().as_millis(),
+                    stream,
+                    "received ai provider response"
+                );
+            }
+            Err(err) => {
+                warn!(
+                    transport = transport_label(transport),
+                    url = url.as_str(),
+                    error = err.as_str(),
+                    stream,
+                    "failed to parse ai provider response"
+                );
+            }
+        }
+        parsed
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn send_over_network(
+        &self,
+        url: &str,
+        api_key: &str,
+        transport: AiTransport,
+        payload: &Value,
+        payload_body: Vec<u8>,
+        callbacks: StreamCallbacks,
+        provider: Option<&str>,
+        thinking_level: Option<&str>,
+        abort_token: Option<CancellationToken>,
+        force_identity_encoding: bool,
+        request_started_at: Instant,
+    ) -> Result<AiResponse, String> {
         let response = send_json_request(
             &self.client,
-            url.as_str(),
-            api_key,
+            url,
+            provider_request_headers(transport, api_key, payload),
             payload_body,
             abort_token.clone(),
             force_identity_encoding,
@@ -366,9 +502,7 @@ impl AiRequestHandler {
         let response_headers_ms = request_started_at.elapsed().as_millis();
         info!(
             transport = transport_label(transport),
-            url = url.as_str(),
-            response_headers_ms,
-            "ai provider response headers received"
+            url, response_headers_ms, "ai provider response headers received"
         );
         if !response.status().is_success() {
             let status = response.status();
@@ -377,53 +511,58 @@ impl AiRequestHandler {
             let body_preview = log_preview(body.as_str());
             warn!(
                 transport = transport_label(transport),
-                url = url.as_str(),
+                url,
                 status = status.as_u16(),
                 retry_after_ms,
                 response_body = body_preview.as_str(),
                 "ai provider request failed"
             );
-            let retry_hint = retry_after_ms
-                .map(|value| format!(" [retry_after_ms={value}]"))
-                .unwrap_or_default();
-            return Err(format!("status {status}{retry_hint}: {body}"));
+            let error = provider_status_error(status, retry_after_ms, body.as_str());
+            if let Some(cassette) = self.recording_cassette() {
+                cassette.record(
+                    transport,
+                    payload,
+                    AiCassetteResponse {
+                        status: status.as_u16(),
+                        retry_after_ms,
+                        body,
+                    },
+                );
+            }
+            return Err(error);
         }
 
+        let body_tap = self
+            .recording_cassette()
+            .map(|_| Mutex::new(Vec::<u8>::new()));
         let parsed = parse_stream_response(
             response,
             transport,
             callbacks,
-            provider.as_deref(),
-            thinking_level.as_deref(),
-            abort_token,
+            provider,
+            thinking_level,
+            abort_token.clone(),
+            body_tap.as_ref(),
         )
         .await;
-        match &parsed {
-            Ok(ai_response) => {
-                info!(
-                    transport = transport_label(transport),
-                    url = url.as_str(),
-                    response_id = ai_response.response_id.as_deref().unwrap_or(""),
-                    finish_reason = ai_response.finish_reason.as_deref().unwrap_or(""),
-                    content_bytes = ai_response.content.len(),
-                    reasoning_bytes = ai_response.reasoning.as_deref().map(str::len).unwrap_or(0),
-                    tool_call_count = ai_response_tool_call_count(ai_response),
-                    has_provider_error = ai_response.provider_error.is_some(),
-                    has_usage = ai_response.usage.is_some(),
-                    ai_provider_request_ms = request_started_at.elapsed().as_millis(),
-                    stream,
-                    "received ai provider response"
-                );
-            }
-            Err(err) => {
-                warn!(
-                    transport = transport_label(transport),
-                    url = url.as_str(),
-                    error = err.as_str(),
-                    stream,
-                    "failed to parse ai provider response"
-                );
-            }
+        let aborted = abort_token
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled);
+        if let (Some(cassette), Some(body_tap), false) =
+            (self.recording_cassette(), body_tap, aborted)
+        {
+            let body = body_tap
+                .into_inner()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+            cassette.record(
+                transport,
+                payload,
+                AiCassetteResponse {
+                    status: 200,
+                    retry_after_ms: None,
+                    body: String::from_utf8_lossy(body.as_slice()).into_owned(),
+                },
+            );
         }
         parsed
     }
@@ -476,6 +615,29 @@ fn build_request_payload(
             options.stream,
             options.output_format.clone(),
         ),
+        AiTransport::AnthropicMessages => build_anthropic_messages_request_payload(
+            input,
+            model,
+            instructions,
+            tools,
+            temperature,
+            max_output_tokens,
+            thinking_level,
+            options.stream,
+            options.prompt_cache_key.clone(),
+            options.include_prompt_cache_retention,
+            options.output_format.clone(),
+        ),
+        AiTransport::GeminiGenerateContent => build_gemini_generate_content_request_payload(
+            input,
+            model.as_str(),
+            instructions,
+            tools,
+            temperature,
+            max_output_tokens,
+            thinking_level,
+            options.output_format.clone(),
+        ),
     }
 }
 
@@ -519,10 +681,54 @@ fn is_openai_api_base_url(base_url: &str) -> bool {
     value.is_empty() || value.contains("api.openai.com")
 }
 
+async fn replay_cassette_response(
+    cassette: &AiCassette,
+    transport: AiTransport,
+    payload: &Value,
+    callbacks: StreamCallbacks,
+    provider: Option<&str>,
+    thinking_level: Option<&str>,
+    abort_token: Option<CancellationToken>,
+) -> Result<AiResponse, String> {
+    let recorded = cassette.take_response(transport, payload)?;
+    let status = reqwest::StatusCode::from_u16(recorded.status)
+        .map_err(|err| format!("AI cassette has invalid status {}: {err}", recorded.status))?;
+    if !status.is_success() {
+        return Err(provider_status_error(
+            status,
+            recorded.retry_after_ms,
+            recorded.body.as_str(),
+        ));
+    }
+    let body = futures::stream::iter([Ok::<_, String>(bytes::Bytes::from(recorded.body))]);
+    parse_stream_chunks(
+        body,
+        transport,
+        callbacks,
+        provider,
+        thinking_level,
+        abort_token,
+    )
+    .await
+}
+
+fn provider_status_error(
+    status: reqwest::StatusCode,
+    retry_after_ms: Option<u64>,
+    body: &str,
+) -> String {
+    let retry_hint = retry_after_ms
+        .map(|value| format!(" [retry_after_ms={value}]"))
+        .unwrap_or_default();
+    format!("status {status}{retry_hint}: {body}")
+}
+
 fn transport_label(transport: AiTransport) -> &'static str {
     match transport {
         AiTransport::Responses => "responses",
         AiTransport::ChatCompletions => "chat_completions",
+        AiTransport::AnthropicMessages => "anthropic_messages",
+        AiTransport::GeminiGenerateContent => "gemini_generate_content",
     }
 }
 
@@ -0,0 +1,333 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use super::transport_label;
+use super::AiTransport;
+
+const AI_CASSETTE_RECORD_ENV: &str = "CHATOS_AI_CASSETTE_RECORD";
+const AI_CASSETTE_REPLAY_ENV: &str = "CHATOS_AI_CASSETTE_REPLAY";
+const CASSETTE_FORMAT_VERSION: u32 = 1;
+
+/// Request fields that differ between otherwise identical runs, such as
+/// cache keys derived from conversation ids. They are dropped before requests
+/// are stored or matched.
+const VOLATILE_REQUEST_KEYS: &[&str] = &[
+    "prompt_cache_key",
+    "prompt_cache_retention",
+    "metadata",
+    "user",
+];
+const SYNTHESIZED_CALL_ID_PREFIX: &str = "call_";
+const SYNTHESIZED_CALL_ID_PLACEHOLDER: &str = "call_<synthesized>";
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct AiCassetteFile {
+    #[serde(default = "cassette_format_version")]
+    pub version: u32,
+    #[serde(default)]
+    pub interactions: Vec<AiCassetteInteraction>,
+}
+
+/// One provider round trip. `request` is the normalized payload and
+/// `response.body` is the raw response body, so replay runs the same stream
+/// parser as a live request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AiCassetteInteraction {
+    pub transport: String,
+    pub request: Value,
+    pub response: AiCassetteResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AiCassetteResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after_ms: Option<u64>,
+    #[serde(default)]
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiCassetteMode {
+    Record,
+    Replay,
+}
+
+#[derive(Debug, Default)]
+struct CassetteState {
+    interactions: Vec<AiCassetteInteraction>,
+    used: Vec<bool>,
+}
+
+/// Records provider traffic or serves it back without a network. A handler
+/// with a replay cassette never opens a connection; a request with no
+/// matching recorded interaction fails instead of reaching the provider.
+#[derive(Debug)]
+pub struct AiCassette {
+    mode: AiCassetteMode,
+    path: Option<PathBuf>,
+    state: Mutex<CassetteState>,
+}
+
+impl AiCassette {
+    /// Records every interaction and rewrites `path` after each one, so a
+    /// run that dies half-way still leaves a usable fixture.
+    pub fn record_to(path: impl Into<PathBuf>) -> Self {
+        Self::new(AiCassetteMode::Record, Some(path.into()), Vec::new())
+    }
+
+    pub fn record_in_memory() -> Self {
+        Self::new(AiCassetteMode::Record, None, Vec::new())
+    }
+
+    pub fn replay(file: AiCassetteFile) -> Self {
+        Self::new(AiCassetteMode::Replay, None, file.interactions)
+    }
+
+    pub fn replay_from(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .map_err(|err| format!("failed to read AI cassette {}: {err}", path.display()))?;
+        let file = serde_json::from_str::<AiCassetteFile>(text.as_str())
+            .map_err(|err| format!("failed to parse AI cassette {}: {err}", path.display()))?;
+        if file.version != CASSETTE_FORMAT_VERSION {
+            return Err(format!(
+                "unsupported AI cassette version {} in {}",
+                file.version,
+                path.display()
+            ));
+        }
+        Ok(Self::replay(file))
+    }
+
+    /// Process-wide cassette configured through `CHATOS_AI_CASSETTE_RECORD`
+    /// or `CHATOS_AI_CASSETTE_REPLAY`, shared by every handler built with
+    /// `AiRequestHandler::new`.
+    pub fn from_env() -> Option<Arc<Self>> {
+        static ENV_CASSETTE: OnceLock<Option<Arc<AiCassette>>> = OnceLock::new();
+        ENV_CASSETTE
+            .get_or_init(|| {
+                if let Some(path) = non_empty_env(AI_CASSETTE_REPLAY_ENV) {
+                    let cassette = Self::replay_from(path.as_str()).unwrap_or_else(|err| {
+                        warn!(
+                            error = err.as_str(),
+                            "AI cassette replay disabled network access"
+                        );
+                        Self::replay(AiCassetteFile::default())
+                    });
+                    return Some(Arc::new(cassette));
+                }
+                non_empty_env(AI_CASSETTE_RECORD_ENV).map(|path| Arc::new(Self::record_to(path)))
+            })
+            .clone()
+    }
+
+    fn new(
+        mode: AiCassetteMode,
+        path: Option<PathBuf>,
+        interactions: Vec<AiCassetteInteraction>,
+    ) -> Self {
+        let used = vec![false; interactions.len()];
+        Self {
+            mode,
+            path,
+            state: Mutex::new(CassetteState { interactions, used }),
+        }
+    }
+
+    pub fn mode(&self) -> AiCassetteMode {
+        self.mode
+    }
+
+    pub fn file(&self) -> AiCassetteFile {
+        AiCassetteFile {
+            version: CASSETTE_FORMAT_VERSION,
+            interactions: self.lock().interactions.clone(),
+        }
+    }
+
+    /// Recorded interactions that replay has not served yet.
+    pub fn unused_interactions(&self) -> usize {
+        self.lock().used.iter().filter(|used| !**used).count()
+    }
+
+    pub(super) fn record(
+        &self,
+        transport: AiTransport,
+        payload: &Value,
+        response: AiCassetteResponse,
+    ) {
+        let file = {
+            let mut state = self.lock();
+            state.interactions.push(AiCassetteInteraction {
+                transport: transport_label(transport).to_string(),
+                request: normalize_cassette_request(payload),
+                response,
+            });
+            state.used.push(true);
+            AiCassetteFile {
+                version: CASSETTE_FORMAT_VERSION,
+                interactions: state.interactions.clone(),
+            }
+        };
+        let Some(path) = self.path.as_ref() else {
+            return;
+        };
+        let written = serde_json::to_string_pretty(&file)
+            .map_err(|err| err.to_string())
+            .and_then(|text| std::fs::write(path, text).map_err(|err| err.to_string()));
+        if let Err(error) = written {
+            warn!(
+                path = %path.display(),
+                error = error.as_str(),
+                "failed to write AI cassette"
+            );
+        }
+    }
+
+    /// Serves the first unused interaction whose transport and normalized
+    /// request match, so repeated identical requests replay in order.
+    pub(super) fn take_response(
+        &self,
+        transport: AiTransport,
+        payload: &Value,
+    ) -> Result<AiCassetteResponse, String> {
+        let transport = transport_label(transport);
+        let request = normalize_cassette_request(payload);
+        let mut state = self.lock();
+        let CassetteState { interactions, used } = &mut *state;
+        let index = interactions
+            .iter()
+            .zip(used.iter())
+            .position(|(interaction, used)| {
+                !used && interaction.transport == transport && interaction.request == request
+            })
+            .ok_or_else(|| {
+                format!(
+                    "AI cassette has no recorded {transport} response for request: {}",
+                    super::http::log_preview(request.to_string().as_str())
+                )
+            })?;
+        used[index] = true;
+        Ok(interactions[index].response.clone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CassetteState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Strips volatile fields and ids the runtime synthesizes per run so a
+/// replayed request matches the one that was recorded.
+pub fn normalize_cassette_request(payload: &Value) -> Value {
+    match payload {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(key, _)| !VOLATILE_REQUEST_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), normalize_cassette_request(value)))
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(normalize_cassette_request).collect()),
+        Value::String(text) if is_synthesized_call_id(text) => {
+            Value::String(SYNTHESIZED_CALL_ID_PLACEHOLDER.to_string())
+        }
+        other => other.clone(),
+    }
+}
+
+fn is_synthesized_call_id(text: &str) -> bool {
+    text.strip_prefix(SYNTHESIZED_CALL_ID_PREFIX)
+        .is_some_and(|id| id.len() == 32 && id.bytes().all(|byte| byte.is_ascii_hexdigit()))
+}
+
+fn non_empty_env(key: &str) -> Option<String> {
+    std::env::var(key)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn cassette_format_version() -> u32 {
+    CASSETTE_FORMAT_VERSION
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{normalize_cassette_request, AiCassette, AiCassetteResponse};
+    use crate::request::AiTransport;
+
+    fn ok(body: &str) -> AiCassetteResponse {
+        AiCassetteResponse {
+            status: 200,
+            retry_after_ms: None,
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn normalization_drops_cache_keys_and_synthesized_call_ids() {
+        let normalized = normalize_cassette_request(&json!({
+            "model": "gemini-test",
+            "prompt_cache_key": "conversation:abc",
+            "contents": [{"parts": [{"functionCall": {
+                "id": "call_0123456789abcdef0123456789abcdef",
+                "name": "lookup"
+            }}]}]
+        }));
+
+        assert!(normalized.get("prompt_cache_key").is_none());
+        assert_eq!(
+            normalized["contents"][0]["parts"][0]["functionCall"]["id"],
+            "call_<synthesized>"
+        );
+        assert_eq!(
+            normalize_cassette_request(&json!({"call_id": "call_1"}))["call_id"],
+            "call_1"
+        );
+    }
+
+    #[test]
+    fn replay_serves_identical_requests_in_recorded_order() {
+        let recorder = AiCassette::record_in_memory();
+        let request = json!({"model": "m", "input": "hi", "prompt_cache_key": "run-1"});
+        recorder.record(AiTransport::Responses, &request, ok("first"));
+        recorder.record(AiTransport::Responses, &request, ok("second"));
+
+        let replay = AiCassette::replay(recorder.file());
+        let request = json!({"model": "m", "input": "hi", "prompt_cache_key": "run-2"});
+        assert_eq!(replay.unused_interactions(), 2);
+        assert_eq!(
+            replay
+                .take_response(AiTransport::Responses, &request)
+                .unwrap()
+                .body,
+            "first"
+        );
+        assert_eq!(
+            replay
+                .take_response(AiTransport::Responses, &request)
+                .unwrap()
+                .body,
+            "second"
+        );
+        let error = replay
+            .take_response(AiTransport::Responses, &request)
+            .unwrap_err();
+        assert!(error.contains("no recorded responses response"));
+        assert!(replay
+            .take_response(AiTransport::ChatCompletions, &json!({"model": "m"}))
+            .is_err());
+        assert_eq!(replay.unused_interactions(), 0);
+    }
+}
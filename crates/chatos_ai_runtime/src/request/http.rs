@@ -6,21 +6,26 @@ use std::error::Error as _;
 use chatos_service_runtime::http_body::read_response_preview_text_limited_or_message;
 use tokio_util::sync::CancellationToken;
 
+use super::AiTransport;
+use crate::request_payload::anthropic_request_headers;
+
 const ERROR_RESPONSE_BODY_LIMIT_BYTES: usize = 16 * 1024;
 
 pub(super) async fn send_json_request(
     client: &reqwest::Client,
     url: &str,
-    api_key: &str,
+    auth_headers: Vec<(&'static str, String)>,
     payload_body: Vec<u8>,
     abort_token: Option<CancellationToken>,
     force_identity_encoding: bool,
 ) -> Result<reqwest::Response, String> {
     let mut request = client
         .post(url)
-        .bearer_auth(api_key)
         .header(reqwest::header::CONTENT_TYPE, "application/json")
         .body(payload_body);
+    for (name, value) in auth_headers {
+        request = request.header(name, value);
+    }
     if force_identity_encoding {
         request = request
             .header(reqwest::header::ACCEPT_ENCODING, "identity")
@@ -39,6 +44,20 @@ pub(super) async fn send_json_request(
     }
 }
 
+pub(super) fn provider_request_headers(
+    transport: AiTransport,
+    api_key: &str,
+    payload: &serde_json::Value,
+) -> Vec<(&'static str, String)> {
+    match transport {
+        AiTransport::Responses | AiTransport::ChatCompletions => {
+            vec![("authorization", format!("Bearer {api_key}"))]
+        }
+        AiTransport::AnthropicMessages => anthropic_request_headers(api_key, payload),
+        AiTransport::GeminiGenerateContent => vec![("x-goog-api-key", api_key.to_string())],
+    }
+}
+
 pub(super) fn format_reqwest_error(err: reqwest::Error) -> String {
     let kind = if err.is_timeout() {
         "timeout"
@@ -2,6 +2,7 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 use std::collections::BTreeMap;
+use std::sync::Mutex;
 
 use futures::{Stream, StreamExt};
 use serde_json::Value;
@@ -12,9 +13,11 @@ use super::{AiResponse, AiTransport, StreamCallbacks};
 use crate::model_config::{reasoning_effort_for_provider, thinking_mode_for_provider};
 use crate::stream::consume_sse_stream;
 use crate::stream_parse::{
-    apply_chat_completions_stream_event, apply_responses_stream_event,
-    finalize_chat_completions_stream_state, finalize_responses_stream_state, FinalizedStreamState,
-    StreamState,
+    apply_anthropic_messages_stream_event, apply_chat_completions_stream_event,
+    apply_gemini_generate_content_stream_event, apply_responses_stream_event,
+    finalize_anthropic_messages_stream_state, finalize_chat_completions_stream_state,
+    finalize_gemini_generate_content_stream_state, finalize_responses_stream_state,
+    FinalizedStreamState, StreamState,
 };
 use crate::tool_call::collect_ordered_tool_calls;
 
@@ -28,10 +31,16 @@ pub(super) async fn parse_stream_response(
     provider: Option<&str>,
     thinking_level: Option<&str>,
     abort_token: Option<CancellationToken>,
+    body_tap: Option<&Mutex<Vec<u8>>>,
 ) -> Result<AiResponse, String> {
-    let response_stream = response
-        .bytes_stream()
-        .map(|chunk| chunk.map_err(super::http::format_reqwest_error));
+    let response_stream = response.bytes_stream().map(|chunk| {
+        if let (Ok(bytes), Some(tap)) = (chunk.as_ref(), body_tap) {
+            tap.lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .extend_from_slice(bytes);
+        }
+        chunk.map_err(super::http::format_reqwest_error)
+    });
     parse_stream_chunks(
         response_stream,
         transport,
@@ -43,7 +52,7 @@ pub(super) async fn parse_stream_response(
     .await
 }
 
-async fn parse_stream_chunks<S, E>(
+pub(super) async fn parse_stream_chunks<S, E>(
     response_stream: S,
     transport: AiTransport,
     callbacks: StreamCallbacks,
@@ -66,6 +75,12 @@ where
             AiTransport::ChatCompletions => {
                 apply_chat_completions_stream_event(&mut state, &event, reasoning_enabled)
             }
+            AiTransport::AnthropicMessages => {
+                apply_anthropic_messages_stream_event(&mut state, &event)
+            }
+            AiTransport::GeminiGenerateContent => {
+                apply_gemini_generate_content_stream_event(&mut state, &event)
+            }
         };
         if let Some(chunk) = payload.chunk {
             if let Some(cb) = &callbacks.on_chunk {
@@ -128,6 +143,10 @@ where
     let finalized = match transport {
         AiTransport::Responses => finalize_responses_stream_state(&mut state),
         AiTransport::ChatCompletions => finalize_chat_completions_stream_state(&mut state),
+        AiTransport::AnthropicMessages => finalize_anthropic_messages_stream_state(&mut state),
+        AiTransport::GeminiGenerateContent => {
+            finalize_gemini_generate_content_stream_state(&mut state)
+        }
     };
 
     emit_finalized_stream_callbacks(
@@ -141,7 +160,9 @@ where
         content: finalized.content,
         reasoning: finalized.reasoning,
         tool_calls: match transport {
-            AiTransport::Responses => finalized.tool_calls,
+            AiTransport::Responses
+            | AiTransport::AnthropicMessages
+            | AiTransport::GeminiGenerateContent => finalized.tool_calls,
             AiTransport::ChatCompletions => {
                 collect_tool_calls(&state.tool_calls_map).or(finalized.tool_calls)
             }
@@ -170,7 +191,9 @@ fn stream_state_is_safely_completed(
                 .is_some_and(|status| status.eq_ignore_ascii_case("completed"))
                 && response_function_calls_are_complete(response)
         }),
-        AiTransport::ChatCompletions => {
+        AiTransport::ChatCompletions
+        | AiTransport::AnthropicMessages
+        | AiTransport::GeminiGenerateContent => {
             malformed_event_count == 0
                 && state.finish_reason.as_deref().is_some_and(|reason| {
                     let reason = reason.trim();
@@ -38,6 +38,10 @@ pub struct StreamCallbacks {
 pub enum AiTransport {
     Responses,
     ChatCompletions,
+    /// Native Anthropic Messages API (`/messages`) with content blocks.
+    AnthropicMessages,
+    /// Native Gemini `generateContent` / `streamGenerateContent` API.
+    GeminiGenerateContent,
 }
 
 #[derive(Clone, Debug)]
@@ -13,6 +13,21 @@ use crate::model_config::{
 use crate::response_parse::{chat_message_content_to_text, tool_arguments_to_string};
 use crate::JsonSchemaOutputFormat;
 
+#[path = "request_payload/anthropic.rs"]
+mod anthropic;
+#[path = "request_payload/gemini.rs"]
+mod gemini;
+
+pub use self::anthropic::{
+    anthropic_request_headers, build_anthropic_messages_request_payload,
+    input_to_anthropic_messages, ANTHROPIC_API_VERSION, ANTHROPIC_DEFAULT_MAX_TOKENS,
+    ANTHROPIC_STRUCTURED_OUTPUTS_BETA, ANTHROPIC_THINKING_BLOCKS_KEY,
+};
+pub use self::gemini::{
+    build_gemini_generate_content_request_payload, gemini_generate_content_url, gemini_schema,
+    input_to_gemini_contents, GEMINI_THOUGHT_SIGNATURE_KEY,
+};
+
 pub const CHAT_PROMPT_CACHE_RETENTION: &str = "24h";
 
 #[allow(clippy::too_many_arguments)]
@@ -0,0 +1,561 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::HashSet;
+
+use serde_json::{json, Map, Value};
+
+use crate::model_config::anthropic_thinking_budget_tokens;
+use crate::response_parse::chat_message_content_to_text;
+use crate::JsonSchemaOutputFormat;
+
+use super::normalized_option;
+
+pub const ANTHROPIC_API_VERSION: &str = "2023-06-01";
+pub const ANTHROPIC_STRUCTURED_OUTPUTS_BETA: &str = "structured-outputs-2025-11-13";
+pub const ANTHROPIC_DEFAULT_MAX_TOKENS: i64 = 8_192;
+/// Tool call values parsed from Anthropic responses carry the signed thinking
+/// blocks of that assistant turn under this key, so the next tool-loop request
+/// can echo them back as the API requires while extended thinking is enabled.
+pub const ANTHROPIC_THINKING_BLOCKS_KEY: &str = "anthropic_thinking_blocks";
+
+const ANTHROPIC_MIN_THINKING_BUDGET_TOKENS: i64 = 1_024;
+const ANTHROPIC_EXTENDED_CACHE_TTL: &str = "1h";
+
+#[allow(clippy::too_many_arguments)]
+pub fn build_anthropic_messages_request_payload(
+    input: Value,
+    model: String,
+    instructions: Option<String>,
+    tools: Option<Vec<Value>>,
+    temperature: Option<f64>,
+    max_output_tokens: Option<i64>,
+    thinking_level: Option<String>,
+    stream: bool,
+    prompt_cache_key: Option<String>,
+    include_prompt_cache_retention: bool,
+    output_format: Option<JsonSchemaOutputFormat>,
+) -> Value {
+    let (mut system, mut messages) = input_to_anthropic_messages(input);
+    if let Some(instructions) = normalized_option(instructions.as_deref()) {
+        system.insert(0, json!({ "type": "text", "text": instructions }));
+    }
+    let max_tokens = max_output_tokens
+        .filter(|value| *value > 0)
+        .unwrap_or(ANTHROPIC_DEFAULT_MAX_TOKENS);
+    let thinking_budget = anthropic_thinking_budget_tokens(thinking_level.as_deref())
+        .map(|budget| budget.min(max_tokens - ANTHROPIC_MIN_THINKING_BUDGET_TOKENS))
+        .filter(|budget| *budget >= ANTHROPIC_MIN_THINKING_BUDGET_TOKENS)
+        .filter(|_| last_tool_use_turn_keeps_thinking(messages.as_slice()));
+    let mut tools = tools
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(anthropic_tool_definition)
+        .collect::<Vec<_>>();
+
+    if normalized_option(prompt_cache_key.as_deref()).is_some() {
+        let cache_control = anthropic_cache_control(include_prompt_cache_retention);
+        // Breakpoints follow the Messages API prefix order: tools, then
+        // system, then the newest message. Three stay under the limit of four.
+        if let Some(tool) = tools.last_mut() {
+            tool["cache_control"] = cache_control.clone();
+        }
+        if let Some(block) = system.last_mut() {
+            block["cache_control"] = cache_control.clone();
+        }
+        if let Some(block) = messages
+            .last_mut()
+            .and_then(|message| message.get_mut("content"))
+            .and_then(Value::as_array_mut)
+            .and_then(|blocks| {
+                blocks
+                    .iter_mut()
+                    .rev()
+                    .find(|block| !is_thinking_block(block))
+            })
+        {
+            block["cache_control"] = cache_control;
+        }
+    }
+
+    let mut payload = json!({
+        "model": model,
+        "max_tokens": max_tokens,
+        "messages": messages,
+    });
+    if !system.is_empty() {
+        payload["system"] = Value::Array(system);
+    }
+    if !tools.is_empty() {
+        payload["tools"] = Value::Array(tools);
+        payload["tool_choice"] = json!({ "type": "auto" });
+    }
+    if let Some(budget) = thinking_budget {
+        payload["thinking"] = json!({ "type": "enabled", "budget_tokens": budget });
+    } else if let Some(value) = temperature {
+        // Extended thinking only accepts the default temperature.
+        payload["temperature"] = json!(value);
+    }
+    if let Some(output_format) = output_format {
+        payload["output_format"] = json!({
+            "type": "json_schema",
+            "schema": output_format.schema,
+        });
+    }
+    payload["stream"] = Value::Bool(stream);
+    payload
+}
+
+pub fn anthropic_request_headers(api_key: &str, payload: &Value) -> Vec<(&'static str, String)> {
+    let mut headers = vec![
+        ("x-api-key", api_key.to_string()),
+        ("anthropic-version", ANTHROPIC_API_VERSION.to_string()),
+    ];
+    if payload.get("output_format").is_some() {
+        headers.push((
+            "anthropic-beta",
+            ANTHROPIC_STRUCTURED_OUTPUTS_BETA.to_string(),
+        ));
+    }
+    headers
+}
+
+/// Converts runtime input (Responses items or Chat Completions messages) into
+/// Anthropic system blocks and strictly alternating `messages`.
+pub fn input_to_anthropic_messages(input: Value) -> (Vec<Value>, Vec<Value>) {
+    let items = match input {
+        Value::Array(items) => items,
+        Value::String(text) => vec![json!({ "role": "user", "content": text })],
+        Value::Null => Vec::new(),
+        other => vec![json!({ "role": "user", "content": other.to_string() })],
+    };
+
+    let mut system = Vec::new();
+    let mut turns: Vec<(String, Vec<Value>)> = Vec::new();
+    for item in items {
+        let item_type = item.get("type").and_then(Value::as_str).unwrap_or("");
+        let role = item.get("role").and_then(Value::as_str).unwrap_or("");
+        match (item_type, role) {
+            ("function_call", _) => {
+                push_turn_blocks(&mut turns, "assistant", vec![tool_use_block(&item)]);
+            }
+            ("function_call_output", _) => {
+                push_turn_blocks(&mut turns, "user", vec![tool_result_block(&item)]);
+            }
+            ("reasoning", _) => {}
+            ("message" | "", "system" | "developer") => {
+                let text =
+                    chat_message_content_to_text(item.get("content").unwrap_or(&Value::Null));
+                if !text.trim().is_empty() {
+                    system.push(json!({ "type": "text", "text": text }));
+                }
+            }
+            ("message" | "", "assistant") => {
+                push_turn_blocks(&mut turns, "assistant", assistant_blocks(&item));
+            }
+            ("message" | "", "tool") => {
+                push_turn_blocks(&mut turns, "user", vec![tool_result_block(&item)]);
+            }
+            ("message" | "", _) if item.get("content").is_some() => {
+                let blocks = item
+                    .get("content")
+                    .map(content_to_anthropic_blocks)
+                    .unwrap_or_default();
+                push_turn_blocks(&mut turns, "user", blocks);
+            }
+            _ => {}
+        }
+    }
+
+    (system, pair_tool_exchanges(turns))
+}
+
+fn push_turn_blocks(turns: &mut Vec<(String, Vec<Value>)>, role: &str, blocks: Vec<Value>) {
+    if blocks.is_empty() {
+        return;
+    }
+    match turns.last_mut() {
+        Some((last_role, last_blocks)) if last_role == role => last_blocks.extend(blocks),
+        _ => turns.push((role.to_string(), blocks)),
+    }
+}
+
+fn assistant_blocks(item: &Value) -> Vec<Value> {
+    let tool_calls = item
+        .get("tool_calls")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    let mut blocks = tool_calls
+        .iter()
+        .filter_map(|call| call.get(ANTHROPIC_THINKING_BLOCKS_KEY))
+        .filter_map(Value::as_array)
+        .flatten()
+        .cloned()
+        .collect::<Vec<_>>();
+    if let Some(content) = item.get("content") {
+        blocks.extend(content_to_anthropic_blocks(content));
+    }
+    blocks.extend(tool_calls.iter().map(tool_use_block));
+    blocks
+}
+
+fn content_to_anthropic_blocks(content: &Value) -> Vec<Value> {
+    match content {
+        Value::Null => Vec::new(),
+        Value::String(text) => text_block(text.as_str()).into_iter().collect(),
+        Value::Array(parts) => parts.iter().filter_map(content_part_to_block).collect(),
+        other => text_block(chat_message_content_to_text(other).as_str())
+            .into_iter()
+            .collect(),
+    }
+}
+
+fn content_part_to_block(part: &Value) -> Option<Value> {
+    match part.get("type").and_then(Value::as_str).unwrap_or("") {
+        "input_text" | "output_text" | "text" => text_block(
+            part.get("text")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned)
+                .unwrap_or_else(|| chat_message_content_to_text(part))
+                .as_str(),
+        ),
+        "input_image" | "image_url" | "image" => image_block(part),
+        "thinking" | "redacted_thinking" => Some(part.clone()),
+        "reasoning" | "reasoning_content" => None,
+        _ => text_block(chat_message_content_to_text(part).as_str()),
+    }
+}
+
+fn text_block(text: &str) -> Option<Value> {
+    if text.trim().is_empty() {
+        None
+    } else {
+        Some(json!({ "type": "text", "text": text }))
+    }
+}
+
+fn image_block(part: &Value) -> Option<Value> {
+    if part.get("source").is_some() {
+        return Some(part.clone());
+    }
+    let url = part.get("image_url").and_then(|value| {
+        value
+            .as_str()
+            .or_else(|| value.get("url").and_then(Value::as_str))
+    })?;
+    if let Some(data_url) = url.strip_prefix("data:") {
+        let (media_type, data) = data_url.split_once(";base64,")?;
+        return Some(json!({
+            "type": "image",
+            "source": { "type": "base64", "media_type": media_type, "data": data },
+        }));
+    }
+    Some(json!({
+        "type": "image",
+        "source": { "type": "url", "url": url },
+    }))
+}
+
+fn tool_use_block(call: &Value) -> Value {
+    let id = crate::tool_call::extract_tool_call_id(call).unwrap_or("");
+    let name = crate::tool_call::extract_tool_call_name(call).unwrap_or("");
+    let arguments = crate::tool_call::clone_tool_call_arguments(call);
+    let input = match arguments {
+        Value::String(text) => serde_json::from_str::<Value>(text.as_str())
+            .ok()
+            .filter(Value::is_object)
+            .unwrap_or_else(|| json!({})),
+        Value::Object(_) => arguments,
+        _ => json!({}),
+    };
+    json!({ "type": "tool_use", "id": id, "name": name, "input": input })
+}
+
+fn tool_result_block(item: &Value) -> Value {
+    let tool_use_id = ["call_id", "tool_call_id"]
+        .iter()
+        .find_map(|key| item.get(*key).and_then(Value::as_str))
+        .unwrap_or("");
+    let content = item
+        .get("output")
+        .or_else(|| item.get("content"))
+        .map(chat_message_content_to_text)
+        .unwrap_or_default();
+    json!({ "type": "tool_result", "tool_use_id": tool_use_id, "content": content })
+}
+
+/// Keeps only `tool_use` blocks answered by the following user turn and only
+/// `tool_result` blocks that answer the preceding assistant turn. The
+/// Messages API rejects either side of an unpaired exchange.
+fn pair_tool_exchanges(turns: Vec<(String, Vec<Value>)>) -> Vec<Value> {
+    let answered_ids = |blocks: &[Value]| -> HashSet<String> {
+        blocks
+            .iter()
+            .filter(|block| block.get("type").and_then(Value::as_str) == Some("tool_result"))
+            .filter_map(|block| block.get("tool_use_id").and_then(Value::as_str))
+            .map(ToOwned::to_owned)
+            .collect()
+    };
+
+    let mut paired: Vec<(String, Vec<Value>)> = Vec::with_capacity(turns.len());
+    let mut open_tool_use_ids = HashSet::new();
+    for (index, (role, blocks)) in turns.iter().enumerate() {
+        let blocks = if role == "assistant" {
+            let answered = turns
+                .get(index + 1)
+                .filter(|(next_role, _)| next_role == "user")
+                .map(|(_, next_blocks)| answered_ids(next_blocks.as_slice()))
+                .unwrap_or_default();
+            let kept = blocks
+                .iter()
+                .filter(|block| {
+                    block.get("type").and_then(Value::as_str) != Some("tool_use")
+                        || block
+                            .get("id")
+                            .and_then(Value::as_str)
+                            .is_some_and(|id| answered.contains(id))
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            open_tool_use_ids = kept
+                .iter()
+                .filter(|block| block.get("type").and_then(Value::as_str) == Some("tool_use"))
+                .filter_map(|block| block.get("id").and_then(Value::as_str))
+                .map(ToOwned::to_owned)
+                .collect();
+            if open_tool_use_ids.is_empty() {
+                kept.into_iter()
+                    .filter(|block| !is_thinking_block(block))
+                    .collect()
+            } else {
+                kept
+            }
+        } else {
+            let mut tool_results = Vec::new();
+            let mut others = Vec::new();
+            for block in blocks {
+                if block.get("type").and_then(Value::as_str) == Some("tool_result") {
+                    if block
+                        .get("tool_use_id")
+                        .and_then(Value::as_str)
+                        .is_some_and(|id| open_tool_use_ids.contains(id))
+                    {
+                        tool_results.push(block.clone());
+                    }
+                } else {
+                    others.push(block.clone());
+                }
+            }
+            open_tool_use_ids.clear();
+            tool_results.extend(others);
+            tool_results
+        };
+        if blocks.is_empty() {
+            continue;
+        }
+        match paired.last_mut() {
+            Some((last_role, last_blocks)) if last_role == role => last_blocks.extend(blocks),
+            _ => paired.push((role.clone(), blocks)),
+        }
+    }
+
+    paired
+        .into_iter()
+        .map(|(role, blocks)| json!({ "role": role, "content": blocks }))
+        .collect()
+}
+
+/// Extended thinking requires the assistant turn of an in-flight tool loop to
+/// start with its signed thinking block. History rebuilt from stored records
+/// cannot provide one, so thinking is disabled for that request instead.
+fn last_tool_use_turn_keeps_thinking(messages: &[Value]) -> bool {
+    let Some(blocks) = messages
+        .iter()
+        .rev()
+        .find(|message| message.get("role").and_then(Value::as_str) == Some("assistant"))
+        .and_then(|message| message.get("content"))
+        .and_then(Value::as_array)
+    else {
+        return true;
+    };
+    let has_tool_use = blocks
+        .iter()
+        .any(|block| block.get("type").and_then(Value::as_str) == Some("tool_use"));
+    !has_tool_use || blocks.first().is_some_and(is_thinking_block)
+}
+
+fn is_thinking_block(block: &Value) -> bool {
+    matches!(
+        block.get("type").and_then(Value::as_str),
+        Some("thinking") | Some("redacted_thinking")
+    )
+}
+
+fn anthropic_tool_definition(tool: Value) -> Option<Value> {
+    let definition = tool.get("function").cloned().unwrap_or(tool);
+    let name = definition
+        .get("name")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())?;
+    let mut converted = Map::new();
+    converted.insert("name".to_string(), Value::String(name.to_string()));
+    if let Some(description) = definition
+        .get("description")
+        .and_then(Value::as_str)
+        .filter(|value| !value.trim().is_empty())
+    {
+        converted.insert(
+            "description".to_string(),
+            Value::String(description.to_string()),
+        );
+    }
+    converted.insert(
+        "input_schema".to_string(),
+        definition
+            .get("parameters")
+            .or_else(|| definition.get("input_schema"))
+            .cloned()
+            .filter(Value::is_object)
+            .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+    );
+    Some(Value::Object(converted))
+}
+
+fn anthropic_cache_control(extended_ttl: bool) -> Value {
+    if extended_ttl {
+        json!({ "type": "ephemeral", "ttl": ANTHROPIC_EXTENDED_CACHE_TTL })
+    } else {
+        json!({ "type": "ephemeral" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::{build_anthropic_messages_request_payload, ANTHROPIC_THINKING_BLOCKS_KEY};
+
+    #[test]
+    fn converts_chat_tool_loop_into_paired_content_blocks() {
+        let payload = build_anthropic_messages_request_payload(
+            json!([
+                {"role": "system", "content": "dynamic context"},
+                {"role": "user", "content": "list pages"},
+                {
+                    "role": "assistant",
+                    "content": "",
+                    "tool_calls": [{
+                        "id": "toolu_1",
+                        "type": "function",
+                        "function": {"name": "list_page", "arguments": "{\"offset\":0}"}
+                    }]
+                },
+                {"role": "tool", "tool_call_id": "toolu_1", "content": "page-1"},
+                {"type": "function_call", "call_id": "toolu_orphan", "name": "x", "arguments": "{}"}
+            ]),
+            "claude-test".to_string(),
+            Some("be brief".to_string()),
+            Some(vec![json!({
+                "type": "function",
+                "name": "list_page",
+                "description": "List one page",
+                "parameters": {"type": "object", "properties": {"offset": {"type": "integer"}}}
+            })]),
+            Some(0.2),
+            None,
+            None,
+            true,
+            None,
+            false,
+            None,
+        );
+
+        assert_eq!(payload["max_tokens"], json!(8_192));
+        assert_eq!(payload["temperature"], json!(0.2));
+        assert_eq!(payload["system"][0]["text"], "be brief");
+        assert_eq!(payload["system"][1]["text"], "dynamic context");
+        assert_eq!(payload["tools"][0]["input_schema"]["type"], "object");
+        let messages = payload["messages"].as_array().expect("messages");
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[1]["content"][0]["type"], "tool_use");
+        assert_eq!(messages[1]["content"][0]["input"]["offset"], json!(0));
+        assert_eq!(messages[2]["content"][0]["type"], "tool_result");
+        assert_eq!(messages[2]["content"][0]["tool_use_id"], "toolu_1");
+        assert!(!payload.to_string().contains("toolu_orphan"));
+        assert!(payload.get("thinking").is_none());
+    }
+
+    #[test]
+    fn thinking_budget_and_cache_breakpoints_follow_request_options() {
+        let thinking = json!({"type": "thinking", "thinking": "plan", "signature": "sig"});
+        let payload = build_anthropic_messages_request_payload(
+            json!([
+                {"role": "user", "content": "hi"},
+                {
+                    "role": "assistant",
+                    "content": null,
+                    "tool_calls": [{
+                        "id": "toolu_1",
+                        "type": "function",
+                        "function": {"name": "lookup", "arguments": "{}"},
+                        ANTHROPIC_THINKING_BLOCKS_KEY: [thinking.clone()]
+                    }]
+                },
+                {"type": "function_call_output", "call_id": "toolu_1", "output": "found"}
+            ]),
+            "claude-test".to_string(),
+            Some("system".to_string()),
+            Some(vec![json!({"type": "function", "name": "lookup"})]),
+            Some(0.5),
+            Some(16_000),
+            Some("high".to_string()),
+            false,
+            Some("conversation:1".to_string()),
+            true,
+            None,
+        );
+
+        assert_eq!(payload["thinking"]["budget_tokens"], json!(14_976));
+        assert!(payload.get("temperature").is_none());
+        assert_eq!(payload["messages"][1]["content"][0], thinking);
+        assert_eq!(payload["tools"][0]["cache_control"]["ttl"], "1h");
+        assert_eq!(payload["system"][0]["cache_control"]["type"], "ephemeral");
+        assert_eq!(
+            payload["messages"][2]["content"][0]["cache_control"]["type"],
+            "ephemeral"
+        );
+    }
+
+    #[test]
+    fn tool_loop_without_signed_thinking_disables_thinking() {
+        let payload = build_anthropic_messages_request_payload(
+            json!([
+                {"role": "user", "content": "hi"},
+                {"type": "function_call", "call_id": "toolu_1", "name": "lookup", "arguments": "{}"},
+                {"type": "function_call_output", "call_id": "toolu_1", "output": "found"}
+            ]),
+            "claude-test".to_string(),
+            None,
+            None,
+            None,
+            None,
+            Some("medium".to_string()),
+            true,
+            None,
+            false,
+            None,
+        );
+
+        assert!(payload.get("thinking").is_none());
+        assert!(payload["messages"]
+            .as_array()
+            .is_some_and(|messages| messages.iter().all(|message| message
+                .get("content")
+                .and_then(Value::as_array)
+                .is_some_and(|blocks| blocks
+                    .iter()
+                    .all(|block| block.get("cache_control").is_none())))));
+    }
+}
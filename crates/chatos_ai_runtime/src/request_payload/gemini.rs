@@ -0,0 +1,578 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::{json, Map, Value};
+
+use crate::model_config::gemini_thinking_config;
+use crate::response_parse::chat_message_content_to_text;
+use crate::JsonSchemaOutputFormat;
+
+use super::normalized_option;
+
+/// Tool call values parsed from Gemini responses keep the part's
+/// `thoughtSignature` under this key; Gemini 3 rejects follow-up requests whose
+/// `functionCall` parts drop it.
+pub const GEMINI_THOUGHT_SIGNATURE_KEY: &str = "gemini_thought_signature";
+/// Documented placeholder for function calls that were not produced by Gemini
+/// in this session (for example history rebuilt from stored records).
+const GEMINI_SKIP_THOUGHT_SIGNATURE: &str = "skip_thought_signature_validator";
+
+const GEMINI_SCHEMA_KEYS: &[&str] = &[
+    "type",
+    "format",
+    "title",
+    "description",
+    "nullable",
+    "enum",
+    "maxItems",
+    "minItems",
+    "properties",
+    "required",
+    "minProperties",
+    "maxProperties",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "example",
+    "anyOf",
+    "propertyOrdering",
+    "default",
+    "items",
+    "minimum",
+    "maximum",
+];
+
+#[allow(clippy::too_many_arguments)]
+pub fn build_gemini_generate_content_request_payload(
+    input: Value,
+    model: &str,
+    instructions: Option<String>,
+    tools: Option<Vec<Value>>,
+    temperature: Option<f64>,
+    max_output_tokens: Option<i64>,
+    thinking_level: Option<String>,
+    output_format: Option<JsonSchemaOutputFormat>,
+) -> Value {
+    let (mut system, contents) = input_to_gemini_contents(input);
+    if let Some(instructions) = normalized_option(instructions.as_deref()) {
+        system.insert(0, instructions);
+    }
+
+    let mut payload = json!({ "contents": contents });
+    if !system.is_empty() {
+        payload["systemInstruction"] = json!({
+            "parts": system.into_iter().map(|text| json!({ "text": text })).collect::<Vec<_>>(),
+        });
+    }
+    let declarations = tools
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(gemini_function_declaration)
+        .collect::<Vec<_>>();
+    if !declarations.is_empty() {
+        payload["tools"] = json!([{ "functionDeclarations": declarations }]);
+        payload["toolConfig"] = json!({ "functionCallingConfig": { "mode": "AUTO" } });
+    }
+
+    let mut generation_config = Map::new();
+    if let Some(value) = temperature {
+        generation_config.insert("temperature".to_string(), json!(value));
+    }
+    if let Some(value) = max_output_tokens.filter(|value| *value > 0) {
+        generation_config.insert("maxOutputTokens".to_string(), json!(value));
+    }
+    if let Some(thinking_config) = gemini_thinking_config(model, thinking_level.as_deref()) {
+        generation_config.insert("thinkingConfig".to_string(), thinking_config);
+    }
+    if let Some(output_format) = output_format {
+        generation_config.insert(
+            "responseMimeType".to_string(),
+            Value::String("application/json".to_string()),
+        );
+        generation_config.insert(
+            "responseSchema".to_string(),
+            gemini_schema(&output_format.schema),
+        );
+    }
+    if !generation_config.is_empty() {
+        payload["generationConfig"] = Value::Object(generation_config);
+    }
+    payload
+}
+
+pub fn gemini_generate_content_url(base_url: &str, model: &str, stream: bool) -> String {
+    let model = model.trim();
+    let model = model.strip_prefix("models/").unwrap_or(model);
+    if stream {
+        format!(
+            "{}/models/{model}:streamGenerateContent?alt=sse",
+            base_url.trim_end_matches('/')
+        )
+    } else {
+        format!(
+            "{}/models/{model}:generateContent",
+            base_url.trim_end_matches('/')
+        )
+    }
+}
+
+/// Converts runtime input (Responses items or Chat Completions messages) into
+/// Gemini system instruction text and alternating `user`/`model` contents.
+pub fn input_to_gemini_contents(input: Value) -> (Vec<String>, Vec<Value>) {
+    let items = match input {
+        Value::Array(items) => items,
+        Value::String(text) => vec![json!({ "role": "user", "content": text })],
+        Value::Null => Vec::new(),
+        other => vec![json!({ "role": "user", "content": other.to_string() })],
+    };
+
+    let mut system = Vec::new();
+    let mut turns: Vec<(&'static str, Vec<Value>)> = Vec::new();
+    let mut call_names = HashMap::new();
+    for item in items {
+        let item_type = item.get("type").and_then(Value::as_str).unwrap_or("");
+        let role = item.get("role").and_then(Value::as_str).unwrap_or("");
+        match (item_type, role) {
+            ("function_call", _) => {
+                let part = function_call_part(&item, &mut call_names);
+                push_turn_parts(&mut turns, "model", vec![part]);
+            }
+            ("function_call_output", _) | ("message" | "", "tool") => {
+                if let Some(part) = function_response_part(&item, &call_names) {
+                    push_turn_parts(&mut turns, "user", vec![part]);
+                }
+            }
+            ("reasoning", _) => {}
+            ("message" | "", "system" | "developer") => {
+                let text =
+                    chat_message_content_to_text(item.get("content").unwrap_or(&Value::Null));
+                if !text.trim().is_empty() {
+                    system.push(text);
+                }
+            }
+            ("message" | "", "assistant") => {
+                let mut parts = item
+                    .get("content")
+                    .map(content_to_gemini_parts)
+                    .unwrap_or_default();
+                if let Some(calls) = item.get("tool_calls").and_then(Value::as_array) {
+                    parts.extend(
+                        calls
+                            .iter()
+                            .map(|call| function_call_part(call, &mut call_names)),
+                    );
+                }
+                push_turn_parts(&mut turns, "model", parts);
+            }
+            ("message" | "", _) if item.get("content").is_some() => {
+                let parts = item
+                    .get("content")
+                    .map(content_to_gemini_parts)
+                    .unwrap_or_default();
+                push_turn_parts(&mut turns, "user", parts);
+            }
+            _ => {}
+        }
+    }
+
+    (system, pair_function_exchanges(turns))
+}
+
+fn push_turn_parts(
+    turns: &mut Vec<(&'static str, Vec<Value>)>,
+    role: &'static str,
+    parts: Vec<Value>,
+) {
+    if parts.is_empty() {
+        return;
+    }
+    match turns.last_mut() {
+        Some((last_role, last_parts)) if *last_role == role => last_parts.extend(parts),
+        _ => turns.push((role, parts)),
+    }
+}
+
+fn content_to_gemini_parts(content: &Value) -> Vec<Value> {
+    match content {
+        Value::Null => Vec::new(),
+        Value::String(text) => text_part(text.as_str()).into_iter().collect(),
+        Value::Array(parts) => parts.iter().filter_map(content_part_to_gemini).collect(),
+        other => text_part(chat_message_content_to_text(other).as_str())
+            .into_iter()
+            .collect(),
+    }
+}
+
+fn content_part_to_gemini(part: &Value) -> Option<Value> {
+    match part.get("type").and_then(Value::as_str).unwrap_or("") {
+        "input_text" | "output_text" | "text" => text_part(
+            part.get("text")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned)
+                .unwrap_or_else(|| chat_message_content_to_text(part))
+                .as_str(),
+        ),
+        "input_image" | "image_url" => image_part(part),
+        "reasoning" | "reasoning_content" | "thinking" | "redacted_thinking" => None,
+        _ => text_part(chat_message_content_to_text(part).as_str()),
+    }
+}
+
+fn text_part(text: &str) -> Option<Value> {
+    if text.trim().is_empty() {
+        None
+    } else {
+        Some(json!({ "text": text }))
+    }
+}
+
+fn image_part(part: &Value) -> Option<Value> {
+    let url = part.get("image_url").and_then(|value| {
+        value
+            .as_str()
+            .or_else(|| value.get("url").and_then(Value::as_str))
+    })?;
+    if let Some(data_url) = url.strip_prefix("data:") {
+        let (mime_type, data) = data_url.split_once(";base64,")?;
+        return Some(json!({ "inlineData": { "mimeType": mime_type, "data": data } }));
+    }
+    Some(json!({ "fileData": { "fileUri": url } }))
+}
+
+fn function_call_part(call: &Value, call_names: &mut HashMap<String, String>) -> Value {
+    let id = crate::tool_call::extract_tool_call_id(call).unwrap_or("");
+    let name = crate::tool_call::extract_tool_call_name(call).unwrap_or("");
+    if !id.is_empty() {
+        call_names.insert(id.to_string(), name.to_string());
+    }
+    let args = match crate::tool_call::clone_tool_call_arguments(call) {
+        Value::String(text) => serde_json::from_str::<Value>(text.as_str())
+            .ok()
+            .filter(Value::is_object)
+            .unwrap_or_else(|| json!({})),
+        value @ Value::Object(_) => value,
+        _ => json!({}),
+    };
+    let mut function_call = json!({ "name": name, "args": args });
+    if !id.is_empty() {
+        function_call["id"] = Value::String(id.to_string());
+    }
+    let signature = call
+        .get(GEMINI_THOUGHT_SIGNATURE_KEY)
+        .and_then(Value::as_str)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(GEMINI_SKIP_THOUGHT_SIGNATURE);
+    json!({ "functionCall": function_call, "thoughtSignature": signature })
+}
+
+fn function_response_part(item: &Value, call_names: &HashMap<String, String>) -> Option<Value> {
+    let id = ["call_id", "tool_call_id"]
+        .iter()
+        .find_map(|key| item.get(*key).and_then(Value::as_str))
+        .unwrap_or("");
+    let name = call_names.get(id)?;
+    let output = item
+        .get("output")
+        .or_else(|| item.get("content"))
+        .map(chat_message_content_to_text)
+        .unwrap_or_default();
+    Some(json!({
+        "functionResponse": {
+            "id": id,
+            "name": name,
+            "response": { "content": output },
+        }
+    }))
+}
+
+/// Gemini requires every `functionCall` of a model turn to be answered by the
+/// immediately following user turn, so unanswered calls and orphan responses
+/// are dropped the same way the Chat Completions path drops them.
+fn pair_function_exchanges(turns: Vec<(&'static str, Vec<Value>)>) -> Vec<Value> {
+    let call_id = |part: &Value| -> Option<String> {
+        part.get("functionCall")
+            .and_then(|call| call.get("id"))
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned)
+    };
+    let response_id = |part: &Value| -> Option<String> {
+        part.get("functionResponse")
+            .and_then(|response| response.get("id"))
+            .and_then(Value::as_str)
+            .map(ToOwned::to_owned)
+    };
+
+    let mut paired: Vec<(&'static str, Vec<Value>)> = Vec::with_capacity(turns.len());
+    let mut open_call_ids = HashSet::new();
+    for (index, (role, parts)) in turns.iter().enumerate() {
+        let parts = if *role == "model" {
+            let answered: HashSet<String> = turns
+                .get(index + 1)
+                .filter(|(next_role, _)| *next_role == "user")
+                .map(|(_, next_parts)| next_parts.iter().filter_map(response_id).collect())
+                .unwrap_or_default();
+            let kept = parts
+                .iter()
+                .filter(|part| {
+                    part.get("functionCall").is_none()
+                        || call_id(part).is_some_and(|id| answered.contains(id.as_str()))
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            open_call_ids = kept.iter().filter_map(call_id).collect();
+            kept
+        } else {
+            let mut responses = Vec::new();
+            let mut others = Vec::new();
+            for part in parts {
+                match response_id(part) {
+                    Some(id) if open_call_ids.contains(id.as_str()) => responses.push(part.clone()),
+                    Some(_) => {}
+                    None => others.push(part.clone()),
+                }
+            }
+            open_call_ids.clear();
+            responses.extend(others);
+            responses
+        };
+        if parts.is_empty() {
+            continue;
+        }
+        match paired.last_mut() {
+            Some((last_role, last_parts)) if last_role == role => last_parts.extend(parts),
+            _ => paired.push((role, parts)),
+        }
+    }
+
+    paired
+        .into_iter()
+        .map(|(role, parts)| json!({ "role": role, "parts": parts }))
+        .collect()
+}
+
+fn gemini_function_declaration(tool: Value) -> Option<Value> {
+    let definition = tool.get("function").cloned().unwrap_or(tool);
+    let name = definition
+        .get("name")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())?;
+    let mut declaration = json!({ "name": name });
+    if let Some(description) = definition
+        .get("description")
+        .and_then(Value::as_str)
+        .filter(|value| !value.trim().is_empty())
+    {
+        declaration["description"] = Value::String(description.to_string());
+    }
+    if let Some(parameters) = definition.get("parameters").filter(|value| {
+        value
+            .get("properties")
+            .is_some_and(|properties| properties.as_object().is_some_and(|map| !map.is_empty()))
+    }) {
+        declaration["parameters"] = gemini_schema(parameters);
+    }
+    Some(declaration)
+}
+
+/// Reduces a JSON Schema to the OpenAPI subset accepted by Gemini's `Schema`
+/// object. Nullable union types collapse onto `nullable` and `const` becomes a
+/// single-value `enum`.
+pub fn gemini_schema(schema: &Value) -> Value {
+    let Some(object) = schema.as_object() else {
+        return schema.clone();
+    };
+    let mut converted = Map::new();
+    for (key, value) in object {
+        match key.as_str() {
+            "type" => match value {
+                Value::Array(types) => {
+                    let non_null = types
+                        .iter()
+                        .filter(|value| value.as_str() != Some("null"))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    if non_null.len() < types.len() {
+                        converted.insert("nullable".to_string(), Value::Bool(true));
+                    }
+                    if let Some(first) = non_null.into_iter().next() {
+                        converted.insert("type".to_string(), first);
+                    }
+                }
+                other => {
+                    converted.insert("type".to_string(), other.clone());
+                }
+            },
+            "const" => {
+                converted.insert("enum".to_string(), json!([value]));
+            }
+            "properties" => {
+                let properties = value
+                    .as_object()
+                    .map(|map| {
+                        map.iter()
+                            .map(|(name, property)| (name.clone(), gemini_schema(property)))
+                            .collect::<Map<_, _>>()
+                    })
+                    .unwrap_or_default();
+                converted.insert("properties".to_string(), Value::Object(properties));
+            }
+            "items" => {
+                converted.insert("items".to_string(), gemini_schema(value));
+            }
+            "anyOf" | "oneOf" => {
+                let variants = value
+                    .as_array()
+                    .map(|variants| variants.iter().map(gemini_schema).collect::<Vec<_>>())
+                    .unwrap_or_default();
+                converted.insert("anyOf".to_string(), Value::Array(variants));
+            }
+            key if GEMINI_SCHEMA_KEYS.contains(&key) => {
+                converted.insert(key.to_string(), value.clone());
+            }
+            _ => {}
+        }
+    }
+    Value::Object(converted)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        build_gemini_generate_content_request_payload, gemini_generate_content_url, gemini_schema,
+        GEMINI_THOUGHT_SIGNATURE_KEY,
+    };
+    use crate::JsonSchemaOutputFormat;
+
+    #[test]
+    fn converts_tool_loop_into_function_call_and_response_parts() {
+        let payload = build_gemini_generate_content_request_payload(
+            json!([
+                {"role": "system", "content": "context"},
+                {"role": "user", "content": "weather?"},
+                {
+                    "role": "assistant",
+                    "content": "",
+                    "tool_calls": [{
+                        "id": "call_1",
+                        "type": "function",
+                        "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"},
+                        GEMINI_THOUGHT_SIGNATURE_KEY: "sig-1"
+                    }]
+                },
+                {"role": "tool", "tool_call_id": "call_1", "content": "sunny"},
+                {"type": "function_call", "call_id": "call_2", "name": "unanswered", "arguments": "{}"}
+            ]),
+            "gemini-2.5-pro",
+            Some("be brief".to_string()),
+            Some(vec![json!({
+                "type": "function",
+                "name": "get_weather",
+                "description": "Weather lookup",
+                "parameters": {
+                    "type": "object",
+                    "additionalProperties": false,
+                    "properties": {"city": {"type": ["string", "null"]}},
+                    "required": ["city"]
+                }
+            })]),
+            Some(0.3),
+            Some(2_048),
+            Some("low".to_string()),
+            None,
+        );
+
+        assert_eq!(payload["systemInstruction"]["parts"][0]["text"], "be brief");
+        assert_eq!(payload["systemInstruction"]["parts"][1]["text"], "context");
+        let declaration = &payload["tools"][0]["functionDeclarations"][0];
+        assert!(declaration["parameters"]
+            .get("additionalProperties")
+            .is_none());
+        assert_eq!(
+            declaration["parameters"]["properties"]["city"],
+            json!({"nullable": true, "type": "string"})
+        );
+        let contents = payload["contents"].as_array().expect("contents");
+        assert_eq!(contents.len(), 3);
+        assert_eq!(contents[1]["role"], "model");
+        assert_eq!(
+            contents[1]["parts"][0]["functionCall"]["args"]["city"],
+            "Paris"
+        );
+        assert_eq!(contents[1]["parts"][0]["thoughtSignature"], "sig-1");
+        assert_eq!(
+            contents[2]["parts"][0]["functionResponse"]["name"],
+            "get_weather"
+        );
+        assert_eq!(
+            contents[2]["parts"][0]["functionResponse"]["response"]["content"],
+            "sunny"
+        );
+        assert!(!payload.to_string().contains("unanswered"));
+        assert_eq!(payload["generationConfig"]["temperature"], json!(0.3));
+        assert_eq!(payload["generationConfig"]["maxOutputTokens"], json!(2_048));
+        assert_eq!(
+            payload["generationConfig"]["thinkingConfig"]["thinkingBudget"],
+            json!(2_048)
+        );
+    }
+
+    #[test]
+    fn structured_output_maps_to_response_schema() {
+        let payload = build_gemini_generate_content_request_payload(
+            json!("summarize"),
+            "gemini-2.5-flash",
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(JsonSchemaOutputFormat::strict(
+                "summary",
+                json!({
+                    "type": "object",
+                    "$schema": "https://json-schema.org/draft/2020-12/schema",
+                    "properties": {"kind": {"const": "summary"}},
+                    "required": ["kind"]
+                }),
+            )),
+        );
+
+        assert_eq!(
+            payload["generationConfig"]["responseMimeType"],
+            "application/json"
+        );
+        assert_eq!(
+            payload["generationConfig"]["responseSchema"],
+            json!({
+                "type": "object",
+                "properties": {"kind": {"enum": ["summary"]}},
+                "required": ["kind"]
+            })
+        );
+        assert_eq!(
+            gemini_schema(&json!({"oneOf": [{"type": "string"}]})),
+            json!({"anyOf": [{"type": "string"}]})
+        );
+    }
+
+    #[test]
+    fn builds_streaming_and_blocking_urls() {
+        assert_eq!(
+            gemini_generate_content_url(
+                "https://generativelanguage.googleapis.com/v1beta/",
+                "models/gemini-2.5-pro",
+                true
+            ),
+            "https://generativelanguage.googleapis.com/v1beta/models/gemini-2.5-pro:streamGenerateContent?alt=sse"
+        );
+        assert_eq!(
+            gemini_generate_content_url("https://gateway.local/v1beta", "gemini-2.5-pro", false),
+            "https://gateway.local/v1beta/models/gemini-2.5-pro:generateContent"
+        );
+    }
+}
@@ -12,13 +12,13 @@ use tracing::info;
 use tracing::warn;
 
 #[cfg(feature = "local-agent-loop")]
-use crate::error_policy::is_missing_tool_call_error;
+use crate::error_policy::{is_missing_tool_call_error, is_model_failover_error};
 #[cfg(feature = "local-agent-loop")]
 use crate::file_write_recovery::automatic_file_write_recovery_calls;
 use crate::model_config::supports_responses_input_token_count;
 #[cfg(feature = "local-agent-loop")]
 use crate::model_config::{effective_responses_support, supports_previous_response_id};
-use crate::request::AiRequestHandler;
+use crate::request::{AiCassette, AiRequestHandler};
 use crate::request_payload::{
     build_chat_completions_request_payload, build_responses_request_payload,
     responses_input_token_count_payload,
@@ -32,12 +32,15 @@ use crate::traits::SaveToolRecordInput;
 use crate::traits::{
     MemoryRecordWriter, ModelRequest, SaveAssistantRecordInput, SaveRecordInput, ToolExecutor,
 };
+#[cfg(feature = "local-agent-loop")]
+use crate::usage_cost::UsageTotals;
 use crate::{RuntimeBeforeModelRequest, RuntimeIterationContext};
 #[cfg(feature = "local-agent-loop")]
 use crate::{RuntimeFinalResponseAction, RuntimeFinalResponseContext};
 
 mod final_response;
 mod input_items;
+mod model_fallback;
 mod model_request;
 mod options;
 mod persistence;
@@ -49,8 +52,11 @@ mod summaries;
 #[cfg(feature = "local-agent-loop")]
 mod tool_execution;
 
+pub use self::model_fallback::{
+    model_circuit_key, ModelCircuitBreaker, DEFAULT_MODEL_CIRCUIT_COOLDOWN,
+};
 pub use self::options::{AiRuntimeOptions, IterativeContextRefresh, MemoryContextOverflowRecovery};
-pub use self::report::{AiRuntimeResult, AiTurnReport, AiTurnStatus};
+pub use self::report::{AiIterationModel, AiRuntimeResult, AiTurnReport, AiTurnStatus};
 pub use self::single_step::{AiSingleStepOutcome, AiSingleStepRequest};
 
 use self::final_response::runtime_result_from_response;
@@ -65,6 +71,8 @@ use self::input_items::{
     merge_pending_tool_turn_into_input,
 };
 #[cfg(feature = "local-agent-loop")]
+use self::model_fallback::{log_model_switch, ModelFallbackChain};
+#[cfg(feature = "local-agent-loop")]
 use self::model_request::dispatch_model_request;
 use self::persistence::normalized_option;
 use self::persistence::should_persist_tool_result;
@@ -84,6 +92,7 @@ pub struct AiRuntime {
     tool_executor: Option<Arc<dyn ToolExecutor>>,
     record_writer: Option<Arc<dyn MemoryRecordWriter>>,
     max_iterations: usize,
+    model_circuit_breaker: Arc<ModelCircuitBreaker>,
 }
 
 const EMPTY_FINAL_RESPONSE_FOLLOWUP_PROMPT: &str = "上一轮响应没有返回任何可展示的最终结果。请先检查当前任务是否已经真实完成：如果已经满足目标且不需要更多验证，直接输出最终结果；如果仍有未完成工作、未处理的任务状态/门禁反馈、缺少关键事实或缺少验证，请继续使用必要工具完成工作或记录明确阻塞。不要把未完成工作包装成最终结果。";
@@ -110,6 +119,7 @@ impl AiRuntime {
             tool_executor,
             record_writer: None,
             max_iterations: 600,
+            model_circuit_breaker: ModelCircuitBreaker::shared(),
         }
     }
 
@@ -125,7 +135,26 @@ impl AiRuntime {
     }
 
     pub fn with_request_read_timeout(mut self, read_timeout: Duration) -> Self {
-        self.request_handler = AiRequestHandler::new_with_read_timeout(read_timeout);
+        let cassette = self.request_handler.cassette().cloned();
+        self.request_handler =
+            AiRequestHandler::new_with_read_timeout(read_timeout).with_cassette(cassette);
+        self
+    }
+
+    /// Records or replays every provider request this runtime makes, e.g. to
+    /// run a whole tool loop offline against a recorded cassette.
+    pub fn with_cassette(mut self, cassette: Option<Arc<AiCassette>>) -> Self {
+        self.request_handler = self.request_handler.with_cassette(cassette);
+        self
+    }
+
+    /// Replaces the process-wide model circuit breaker, e.g. to isolate tests
+    /// or to use a different cool-down window.
+    pub fn with_model_circuit_breaker(
+        mut self,
+        model_circuit_breaker: Arc<ModelCircuitBreaker>,
+    ) -> Self {
+        self.model_circuit_breaker = model_circuit_breaker;
         self
     }
 
@@ -200,6 +229,9 @@ impl AiRuntime {
             .as_ref()
             .map(|_| request.input.clone());
         let mut continuation_disabled = false;
+        let mut model_chain = ModelFallbackChain::from_request(&request);
+        let mut iteration_models = Vec::new();
+        let mut usage_totals = UsageTotals::default();
         'runtime_loop: loop {
             if options.is_aborted() {
                 return Err("aborted".to_string());
@@ -278,6 +310,18 @@ impl AiRuntime {
                 }
             }
 
+            let previous_model = request.model.clone();
+            if model_chain.select_available(&self.model_circuit_breaker, &mut request) {
+                log_model_switch(
+                    &options,
+                    iteration,
+                    previous_model.as_str(),
+                    request.model.as_str(),
+                    "circuit_open",
+                );
+                continuation_input = None;
+            }
+
             let (mut iteration_request, lifecycle_before) =
                 prepare_iteration_request(&request, &options, iteration, iteration_reason.as_str())
                     .await?;
@@ -440,6 +484,24 @@ impl AiRuntime {
                             continuation_disabled = true;
                             continue;
                         }
+                        if is_model_failover_error(err.as_str()) {
+                            let previous_model = request.model.clone();
+                            if model_chain.fail_over(&self.model_circuit_breaker, &mut request) {
+                                log_model_switch(
+                                    &options,
+                                    iteration,
+                                    previous_model.as_str(),
+                                    request.model.as_str(),
+                                    err.as_str(),
+                                );
+                                model_chain.apply_active(&mut iteration_request);
+                                iteration_request.input = standalone_iteration_input.clone();
+                                continuation_input = None;
+                                transient_retry_count = 0;
+                                recovery_request_handler = None;
+                                continue;
+                            }
+                        }
                         match handle_model_request_error(
                             err,
                             &iteration_request,
@@ -467,7 +529,10 @@ impl AiRuntime {
                                 // A retry must not inherit a potentially unhealthy pooled
                                 // connection. Build a new client for every retry attempt and
                                 // ask the provider to close that isolated connection afterward.
-                                recovery_request_handler = Some(AiRequestHandler::new());
+                                recovery_request_handler = Some(
+                                    AiRequestHandler::new()
+                                        .with_cassette(self.request_handler.cassette().cloned()),
+                                );
                                 continue;
                             }
                             ModelRequestErrorAction::Fail(err) => return Err(err),
@@ -476,6 +541,11 @@ impl AiRuntime {
                 }
             };
             missing_tool_turn_replay_attempted = false;
+            model_chain.record_success(&self.model_circuit_breaker);
+            iteration_models.push(model_chain.served_by(iteration));
+            if let Some(usage) = response.usage.as_ref() {
+                usage_totals.record(usage, iteration_request.pricing.as_ref());
+            }
 
             if options.is_aborted() {
                 return Err("aborted".to_string());
@@ -574,7 +644,10 @@ impl AiRuntime {
                             lifecycle_metadata,
                         )
                         .await?;
-                        return Ok(runtime_result_from_response(response));
+                        let mut result = runtime_result_from_response(response);
+                        result.iteration_models = iteration_models;
+                        result.usage_totals = usage_totals;
+                        return Ok(result);
                     }
                 }
             };
@@ -599,7 +672,10 @@ impl AiRuntime {
             .await?;
 
             let Some(executor) = &self.tool_executor else {
-                return Ok(runtime_result_from_response(response));
+                let mut result = runtime_result_from_response(response);
+                result.iteration_models = iteration_models;
+                result.usage_totals = usage_totals;
+                return Ok(result);
             };
 
             let mut tool_execution = execute_runtime_tools(
@@ -5,6 +5,7 @@
 use tracing::{info, warn};
 
 use crate::request::AiResponse;
+use crate::usage_cost::UsageTotals;
 
 #[cfg(feature = "local-agent-loop")]
 use super::options::AiRuntimeOptions;
@@ -71,5 +72,7 @@ pub(super) fn runtime_result_from_response(response: AiResponse) -> AiRuntimeRes
         response_id: response.response_id,
         response_output_items: response.response_output_items,
         request_input_items: Vec::new(),
+        iteration_models: Vec::new(),
+        usage_totals: UsageTotals::default(),
     }
 }
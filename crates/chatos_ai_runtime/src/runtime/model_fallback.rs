@@ -0,0 +1,310 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+use tracing::warn;
+
+use crate::model_config::{effective_responses_support, normalize_provider};
+use crate::traits::{ModelRequest, ModelRuntimeConfig};
+
+use super::options::AiRuntimeOptions;
+use super::report::AiIterationModel;
+
+pub const DEFAULT_MODEL_CIRCUIT_COOLDOWN: Duration = Duration::from_secs(60);
+
+/// Tracks models that recently failed with overload, rate limit or upstream
+/// auth errors. An open circuit makes the runtime skip that model until the
+/// cool-down expires. The shared instance lives for the whole process so a
+/// failure seen by one turn protects the next.
+#[derive(Debug)]
+pub struct ModelCircuitBreaker {
+    cooldown: Duration,
+    open_until: Mutex<HashMap<String, Instant>>,
+}
+
+impl ModelCircuitBreaker {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            open_until: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn shared() -> Arc<Self> {
+        static SHARED: OnceLock<Arc<ModelCircuitBreaker>> = OnceLock::new();
+        Arc::clone(
+            SHARED
+                .get_or_init(|| Arc::new(ModelCircuitBreaker::new(DEFAULT_MODEL_CIRCUIT_COOLDOWN))),
+        )
+    }
+
+    pub fn is_open(&self, key: &str) -> bool {
+        let mut open_until = self
+            .open_until
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        match open_until.get(key) {
+            Some(until) if *until > Instant::now() => true,
+            Some(_) => {
+                open_until.remove(key);
+                false
+            }
+            None => false,
+        }
+    }
+
+    pub fn trip(&self, key: &str) {
+        self.open_until
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(key.to_string(), Instant::now() + self.cooldown);
+    }
+
+    pub fn reset(&self, key: &str) {
+        self.open_until
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .remove(key);
+    }
+}
+
+impl Default for ModelCircuitBreaker {
+    fn default() -> Self {
+        Self::new(DEFAULT_MODEL_CIRCUIT_COOLDOWN)
+    }
+}
+
+pub fn model_circuit_key(provider: &str, base_url: &str, model: &str) -> String {
+    format!(
+        "{}|{}|{}",
+        normalize_provider(provider),
+        base_url.trim().trim_end_matches('/').to_ascii_lowercase(),
+        model.trim()
+    )
+}
+
+/// The requested model followed by its fallbacks. Switching keeps the turn's
+/// input, instructions and tools and only swaps the connection and sampling
+/// settings, so stateless history carries over unchanged.
+#[derive(Debug, Clone)]
+pub(super) struct ModelFallbackChain {
+    targets: Vec<ModelRuntimeConfig>,
+    active: usize,
+}
+
+impl ModelFallbackChain {
+    pub(super) fn from_request(request: &ModelRequest) -> Self {
+        let primary = ModelRuntimeConfig::openai_compatible(
+            request.base_url.clone(),
+            request.api_key.clone(),
+            request.model.clone(),
+            request.provider.clone(),
+        )
+        .with_responses_support(request.supports_responses)
+        .with_temperature(request.temperature)
+        .with_max_output_tokens(request.max_output_tokens)
+        .with_thinking_level(request.thinking_level.clone())
+        .with_request_body_limit_bytes(request.request_body_limit_bytes)
+        .with_max_transient_retries(request.max_transient_retries)
+        .with_pricing(request.pricing.clone());
+        let mut targets = vec![primary];
+        targets.extend(request.fallback_models.iter().cloned());
+        Self { targets, active: 0 }
+    }
+
+    fn key(&self, index: usize) -> String {
+        let target = &self.targets[index];
+        model_circuit_key(
+            target.provider.as_str(),
+            target.base_url.as_str(),
+            target.model.as_str(),
+        )
+    }
+
+    /// Moves off the active model when its circuit is open and a healthy
+    /// model exists. When every model is cooling down the active one is kept.
+    pub(super) fn select_available(
+        &mut self,
+        breaker: &ModelCircuitBreaker,
+        request: &mut ModelRequest,
+    ) -> bool {
+        if self.targets.len() < 2 || !breaker.is_open(self.key(self.active).as_str()) {
+            return false;
+        }
+        let Some(next) = (0..self.targets.len())
+            .find(|index| *index != self.active && !breaker.is_open(self.key(*index).as_str()))
+        else {
+            return false;
+        };
+        self.activate(next, request);
+        true
+    }
+
+    /// Opens the active model's circuit and switches to the next healthy
+    /// model, preferring later entries of the chain.
+    pub(super) fn fail_over(
+        &mut self,
+        breaker: &ModelCircuitBreaker,
+        request: &mut ModelRequest,
+    ) -> bool {
+        if self.targets.len() < 2 {
+            return false;
+        }
+        breaker.trip(self.key(self.active).as_str());
+        let count = self.targets.len();
+        let Some(next) = (1..count)
+            .map(|offset| (self.active + offset) % count)
+            .find(|index| !breaker.is_open(self.key(*index).as_str()))
+        else {
+            return false;
+        };
+        self.activate(next, request);
+        true
+    }
+
+    pub(super) fn record_success(&self, breaker: &ModelCircuitBreaker) {
+        if self.targets.len() > 1 {
+            breaker.reset(self.key(self.active).as_str());
+        }
+    }
+
+    pub(super) fn served_by(&self, iteration: usize) -> AiIterationModel {
+        let target = &self.targets[self.active];
+        AiIterationModel {
+            iteration,
+            model: target.model.clone(),
+            provider: target.provider.clone(),
+            fallback_index: self.active,
+        }
+    }
+
+    /// Applies the active model to a request derived from the turn request,
+    /// such as the per-iteration copy prepared by lifecycle hooks.
+    pub(super) fn apply_active(&self, request: &mut ModelRequest) {
+        apply_model_target(request, &self.targets[self.active]);
+    }
+
+    fn activate(&mut self, index: usize, request: &mut ModelRequest) {
+        self.active = index;
+        self.apply_active(request);
+    }
+}
+
+fn apply_model_target(request: &mut ModelRequest, target: &ModelRuntimeConfig) {
+    request.base_url = target.base_url.clone();
+    request.api_key = target.api_key.clone();
+    request.model = target.model.clone();
+    request.provider = target.provider.clone();
+    request.supports_responses = effective_responses_support(
+        target.provider.as_str(),
+        target.base_url.as_str(),
+        target.supports_responses,
+    );
+    request.temperature = target.temperature;
+    request.max_output_tokens = target.max_output_tokens;
+    request.thinking_level = target.thinking_level.clone();
+    request.request_body_limit_bytes = target.request_body_limit_bytes;
+    request.max_transient_retries = target.max_transient_retries;
+    request.pricing = target.pricing.clone();
+    // A response id from one provider means nothing to another.
+    request.previous_response_id = None;
+}
+
+pub(super) fn log_model_switch(
+    options: &AiRuntimeOptions,
+    iteration: usize,
+    from_model: &str,
+    to_model: &str,
+    reason: &str,
+) {
+    warn!(
+        conversation_id = options.conversation_id.as_deref().unwrap_or(""),
+        conversation_turn_id = options.conversation_turn_id.as_deref().unwrap_or(""),
+        iteration,
+        from_model,
+        to_model,
+        reason,
+        "ai runtime switched to fallback model"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use super::{model_circuit_key, ModelCircuitBreaker, ModelFallbackChain};
+    use crate::traits::{ModelRequest, ModelRuntimeConfig};
+
+    fn request_with_fallbacks() -> ModelRequest {
+        ModelRequest::openai_compatible(
+            "https://api.openai.com/v1",
+            "primary-key",
+            "gpt-primary",
+            "openai",
+            json!([{"role": "user", "content": "hi"}]),
+        )
+        .with_responses_support(true)
+        .with_previous_response_id(Some("resp-1".to_string()))
+        .with_fallback_models(vec![
+            ModelRuntimeConfig::openai_compatible(
+                "https://api.anthropic.com/v1",
+                "fallback-key",
+                "claude-fallback",
+                "anthropic",
+            )
+            .with_thinking_level(Some("low".to_string())),
+            ModelRuntimeConfig::openai_compatible(
+                "https://api.deepseek.com",
+                "last-key",
+                "deepseek-last",
+                "deepseek",
+            ),
+        ])
+    }
+
+    #[test]
+    fn fail_over_trips_the_active_model_and_keeps_turn_input() {
+        let breaker = ModelCircuitBreaker::new(Duration::from_secs(60));
+        let mut request = request_with_fallbacks();
+        let mut chain = ModelFallbackChain::from_request(&request);
+
+        assert!(chain.fail_over(&breaker, &mut request));
+        assert_eq!(chain.served_by(1).fallback_index, 1);
+        assert_eq!(request.model, "claude-fallback");
+        assert_eq!(request.api_key, "fallback-key");
+        assert!(!request.supports_responses);
+        assert_eq!(request.thinking_level.as_deref(), Some("low"));
+        assert!(request.previous_response_id.is_none());
+        assert_eq!(request.input, json!([{"role": "user", "content": "hi"}]));
+        assert!(breaker.is_open(
+            model_circuit_key("openai", "https://api.openai.com/v1/", "gpt-primary").as_str()
+        ));
+
+        assert!(chain.fail_over(&breaker, &mut request));
+        assert_eq!(request.model, "deepseek-last");
+        assert!(!chain.fail_over(&breaker, &mut request));
+        assert_eq!(chain.served_by(3).fallback_index, 2);
+    }
+
+    #[test]
+    fn select_available_skips_models_with_open_circuits() {
+        let breaker = ModelCircuitBreaker::new(Duration::from_secs(60));
+        breaker
+            .trip(model_circuit_key("openai", "https://api.openai.com/v1", "gpt-primary").as_str());
+        let mut request = request_with_fallbacks();
+        let mut chain = ModelFallbackChain::from_request(&request);
+
+        assert!(chain.select_available(&breaker, &mut request));
+        assert_eq!(request.model, "claude-fallback");
+
+        chain.record_success(&breaker);
+        let expired = ModelCircuitBreaker::new(Duration::ZERO);
+        expired.trip("gpt|https://api.openai.com/v1|gpt-primary");
+        assert!(!expired.is_open("gpt|https://api.openai.com/v1|gpt-primary"));
+    }
+}
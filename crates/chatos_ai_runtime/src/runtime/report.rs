@@ -4,6 +4,8 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::usage_cost::UsageTotals;
+
 #[derive(Debug, Clone)]
 pub struct AiRuntimeResult {
     pub content: String,
@@ -17,6 +19,21 @@ pub struct AiRuntimeResult {
     /// composition. Cloud orchestration persists this as the base of the next
     /// stateless Responses request.
     pub request_input_items: Vec<Value>,
+    /// Model that served each iteration, in order. Differs from the requested
+    /// model when the runtime failed over to a fallback.
+    pub iteration_models: Vec<AiIterationModel>,
+    /// Tokens and cost of every model request made for this result, priced
+    /// with the pricing of the model that served each request.
+    pub usage_totals: UsageTotals,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AiIterationModel {
+    pub iteration: usize,
+    pub model: String,
+    pub provider: String,
+    /// Position in the fallback chain; `0` is the requested model.
+    pub fallback_index: usize,
 }
 
 impl AiRuntimeResult {
@@ -43,6 +60,10 @@ pub struct AiTurnReport {
     pub finish_reason: Option<String>,
     pub usage: Option<Value>,
     pub response_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub iteration_models: Vec<AiIterationModel>,
+    #[serde(default, skip_serializing_if = "UsageTotals::is_empty")]
+    pub usage_totals: UsageTotals,
     pub completed_at: String,
 }
 
@@ -57,6 +78,8 @@ impl AiTurnReport {
             finish_reason: result.finish_reason,
             usage: result.usage,
             response_id: result.response_id,
+            iteration_models: result.iteration_models,
+            usage_totals: result.usage_totals,
             completed_at: chrono::Utc::now().to_rfc3339(),
         }
     }
@@ -77,6 +100,8 @@ impl AiTurnReport {
             finish_reason: None,
             usage: None,
             response_id: None,
+            iteration_models: Vec::new(),
+            usage_totals: UsageTotals::default(),
             completed_at: chrono::Utc::now().to_rfc3339(),
         }
     }
@@ -85,6 +110,13 @@ impl AiTurnReport {
         Self::failed("aborted")
     }
 
+    /// Attaches the models that served the turn's iterations when the report is
+    /// assembled outside the runtime, for example from durable step state.
+    pub fn with_iteration_models(mut self, iteration_models: Vec<AiIterationModel>) -> Self {
+        self.iteration_models = iteration_models;
+        self
+    }
+
     pub fn is_completed(&self) -> bool {
         self.status == AiTurnStatus::Completed
     }
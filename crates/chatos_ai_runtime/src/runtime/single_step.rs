@@ -3,13 +3,17 @@
 
 use serde_json::Value;
 
-use crate::error_policy::{classify_transient_retry, TransientRetryAction};
+use crate::error_policy::{
+    classify_transient_retry, is_model_failover_error, TransientRetryAction,
+};
 use crate::model_config::{effective_responses_support, supports_previous_response_id};
 use crate::tool_call::tool_calls_value_has_items;
 use crate::traits::{ModelRequest, DEFAULT_MODEL_REQUEST_MAX_RETRIES};
+use crate::usage_cost::UsageTotals;
 use crate::{RuntimeFinalResponseAction, RuntimeFinalResponseContext};
 
 use super::input_items::{append_runtime_input_items, input_item_count, json_value_size_bytes};
+use super::model_fallback::{log_model_switch, ModelFallbackChain};
 use super::model_request::dispatch_model_request;
 use super::{
     active_context_exceeds_hard_limit, count_iteration_input_tokens,
@@ -111,6 +115,19 @@ pub(super) async fn execute_once(
     ) {
         model_request.previous_response_id = None;
     }
+    // Cloud steps are independent deliveries, so failover happens through the
+    // shared circuit breaker: a tripped model is skipped by the next step.
+    let mut model_chain = ModelFallbackChain::from_request(&model_request);
+    let requested_model = model_request.model.clone();
+    if model_chain.select_available(&runtime.model_circuit_breaker, &mut model_request) {
+        log_model_switch(
+            &runtime_options,
+            iteration,
+            requested_model.as_str(),
+            model_request.model.as_str(),
+            "circuit_open",
+        );
+    }
     if let Some(refresh) = &runtime_options.iterative_context_refresh {
         match refresh
             .wait_for_inflight_summary(&runtime_options.callbacks)
@@ -249,8 +266,26 @@ pub(super) async fn execute_once(
     .await;
     let mut response = match response {
         Ok(response) => response,
-        Err(error) => return Ok(retry_or_fail(error, &iteration_request, model_attempt)),
+        Err(error) => {
+            if is_model_failover_error(error.as_str())
+                && model_chain.fail_over(&runtime.model_circuit_breaker, &mut model_request)
+            {
+                return Ok(AiSingleStepOutcome::Retry {
+                    error,
+                    retry_kind: "model_failover".to_string(),
+                    next_model_attempt: model_attempt,
+                    backoff_ms: 0,
+                });
+            }
+            return Ok(retry_or_fail(error, &iteration_request, model_attempt));
+        }
     };
+    model_chain.record_success(&runtime.model_circuit_breaker);
+    let iteration_models = vec![model_chain.served_by(iteration)];
+    let mut usage_totals = UsageTotals::default();
+    if let Some(usage) = response.usage.as_ref() {
+        usage_totals.record(usage, iteration_request.pricing.as_ref());
+    }
     if runtime_options.is_aborted() {
         return Ok(AiSingleStepOutcome::Cancelled);
     }
@@ -271,6 +306,8 @@ pub(super) async fn execute_once(
             .await?;
         let mut response = runtime_result_from_response(response);
         response.request_input_items = request_input_items;
+        response.iteration_models = iteration_models;
+        response.usage_totals = usage_totals;
         return Ok(AiSingleStepOutcome::ToolCommand {
             response,
             tool_calls,
@@ -280,6 +317,8 @@ pub(super) async fn execute_once(
     if response.content.trim().is_empty() {
         let mut response = runtime_result_from_response(response);
         response.request_input_items = request_input_items;
+        response.iteration_models = iteration_models;
+        response.usage_totals = usage_totals;
         return Ok(AiSingleStepOutcome::Continue {
             response,
             input_items: vec![super::input_items::empty_final_response_followup_item()],
@@ -306,6 +345,8 @@ pub(super) async fn execute_once(
             } => {
                 let mut response = runtime_result_from_response(response);
                 response.request_input_items = request_input_items;
+                response.iteration_models = iteration_models;
+                response.usage_totals = usage_totals;
                 return Ok(AiSingleStepOutcome::Continue {
                     response,
                     input_items,
@@ -337,6 +378,8 @@ pub(super) async fn execute_once(
         .await?;
     let mut result = runtime_result_from_response(response);
     result.request_input_items = request_input_items;
+    result.iteration_models = iteration_models;
+    result.usage_totals = usage_totals;
     Ok(AiSingleStepOutcome::Final(result))
 }
 
@@ -29,7 +29,7 @@ use crate::{
     AiResponse, AiRuntime, AiRuntimeOptions, AiRuntimeResult, AiSingleStepRequest, AiTurnReport,
     AiTurnStatus, MemoryRecordWriter, ModelRequest, RuntimeBeforeModelRequest, RuntimeCallbacks,
     RuntimeFinalResponseAction, RuntimeFinalResponseContext, RuntimeIterationContext,
-    RuntimeLifecycleHook, RuntimeRecordOptions, SaveRecordInput, ToolExecutor,
+    RuntimeLifecycleHook, RuntimeRecordOptions, SaveRecordInput, ToolExecutor, UsageTotals,
 };
 
 #[derive(Clone, Default)]
@@ -253,6 +253,8 @@ async fn start_lifecycle_mock_provider(
     let requests = Arc::clone(&state.requests);
     let app = Router::new()
         .route("/responses", post(mock_lifecycle_provider))
+        .route("/messages", post(mock_lifecycle_provider))
+        .route("/models/{*method}", post(mock_lifecycle_provider))
         .with_state(state);
     let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
         .await
@@ -536,6 +538,141 @@ async fn responses_tool_loop_uses_previous_response_id_and_delta_input() {
     assert!(!requests[1].to_string().contains("verify every page"));
 }
 
+#[tokio::test]
+async fn anthropic_messages_tool_loop_pairs_tool_use_with_tool_results() {
+    let (base_url, requests, _connection_headers, server) = start_lifecycle_mock_provider(vec![
+        json!({
+            "id": "msg_page_1",
+            "type": "message",
+            "role": "assistant",
+            "content": [
+                {"type": "text", "text": "Listing."},
+                {"type": "tool_use", "id": "toolu_1", "name": "list_page", "input": {"offset": 0}}
+            ],
+            "stop_reason": "tool_use",
+            "usage": {"input_tokens": 12, "output_tokens": 6}
+        }),
+        json!({
+            "id": "msg_final",
+            "type": "message",
+            "role": "assistant",
+            "content": [{"type": "text", "text": "all pages listed"}],
+            "stop_reason": "end_turn",
+            "usage": {"input_tokens": 30, "output_tokens": 4}
+        }),
+    ])
+    .await;
+    let request = ModelRequest::openai_compatible(
+        base_url,
+        "test-key",
+        "claude-test",
+        "anthropic",
+        json!([{"role": "user", "content": "list every page"}]),
+    )
+    .with_responses_support(true)
+    .with_pricing(Some(crate::ModelPricing {
+        input_per_million_usd: 3.0,
+        output_per_million_usd: 15.0,
+        ..crate::ModelPricing::default()
+    }));
+
+    let result = AiRuntime::new(Some(Arc::new(PagingToolExecutor)))
+        .with_max_iterations(3)
+        .run_turn(
+            request,
+            AiRuntimeOptions::for_conversation("session-anthropic"),
+        )
+        .await
+        .expect("anthropic tool turn");
+    server.abort();
+
+    assert_eq!(result.content, "all pages listed");
+    assert_eq!(result.usage_totals.requests, 2);
+    assert_eq!(result.usage_totals.input_tokens, 42);
+    assert_eq!(result.usage_totals.output_tokens, 10);
+    assert!((result.usage_totals.cost_usd - 0.000276).abs() < 1e-12);
+    let requests = requests.lock().await;
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].get("input").is_none());
+    let messages = requests[1]
+        .get("messages")
+        .and_then(Value::as_array)
+        .expect("anthropic messages");
+    assert_eq!(messages.len(), 3);
+    assert_eq!(messages[1]["content"][1]["type"], "tool_use");
+    assert_eq!(messages[2]["content"][0]["type"], "tool_result");
+    assert_eq!(messages[2]["content"][0]["tool_use_id"], "toolu_1");
+    assert_eq!(messages[2]["content"][0]["content"], "result-toolu_1");
+}
+
+#[tokio::test]
+async fn gemini_tool_loop_pairs_function_calls_with_function_responses() {
+    let (base_url, requests, _connection_headers, server) = start_lifecycle_mock_provider(vec![
+        json!({
+            "responseId": "gemini-page-1",
+            "candidates": [{
+                "content": {"role": "model", "parts": [
+                    {"text": "Listing."},
+                    {
+                        "functionCall": {"id": "fc_1", "name": "list_page", "args": {"offset": 0}},
+                        "thoughtSignature": "sig-page-1"
+                    }
+                ]},
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {"promptTokenCount": 12, "candidatesTokenCount": 6}
+        }),
+        json!({
+            "responseId": "gemini-final",
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"text": "all pages listed"}]},
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {"promptTokenCount": 30, "candidatesTokenCount": 4}
+        }),
+    ])
+    .await;
+    let request = ModelRequest::openai_compatible(
+        base_url,
+        "test-key",
+        "gemini-test",
+        "gemini",
+        json!([{"role": "user", "content": "list every page"}]),
+    )
+    .with_responses_support(true);
+
+    let result = AiRuntime::new(Some(Arc::new(PagingToolExecutor)))
+        .with_max_iterations(3)
+        .run_turn(
+            request,
+            AiRuntimeOptions::for_conversation("session-gemini"),
+        )
+        .await
+        .expect("gemini tool turn");
+    server.abort();
+
+    assert_eq!(result.content, "all pages listed");
+    let requests = requests.lock().await;
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].get("model").is_none());
+    let contents = requests[1]
+        .get("contents")
+        .and_then(Value::as_array)
+        .expect("gemini contents");
+    assert_eq!(contents.len(), 3);
+    assert_eq!(contents[1]["role"], "model");
+    assert_eq!(contents[1]["parts"][1]["functionCall"]["id"], "fc_1");
+    assert_eq!(contents[1]["parts"][1]["thoughtSignature"], "sig-page-1");
+    assert_eq!(
+        contents[2]["parts"][0]["functionResponse"]["name"],
+        "list_page"
+    );
+    assert_eq!(
+        contents[2]["parts"][0]["functionResponse"]["response"]["content"],
+        "result-fc_1"
+    );
+}
+
 #[derive(Clone, Default)]
 struct ContinuationFallbackProviderState {
     requests: Arc<AsyncMutex<Vec<Value>>>,
@@ -620,6 +757,111 @@ async fn unsupported_continuation_falls_back_to_full_input_once() {
     assert!(requests[2].to_string().contains("result-call-1"));
 }
 
+async fn mock_overloaded_provider(
+    State(requests): State<Arc<AsyncMutex<Vec<Value>>>>,
+    Json(payload): Json<Value>,
+) -> Response {
+    requests.lock().await.push(payload);
+    (
+        StatusCode::from_u16(529).expect("overloaded status"),
+        Json(json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}})),
+    )
+        .into_response()
+}
+
+#[tokio::test]
+async fn overloaded_model_fails_over_and_stays_skipped_across_turns() {
+    let overloaded_requests = Arc::new(AsyncMutex::new(Vec::new()));
+    let app = Router::new()
+        .route("/responses", post(mock_overloaded_provider))
+        .with_state(Arc::clone(&overloaded_requests));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind overloaded provider");
+    let address = listener.local_addr().expect("overloaded provider address");
+    let overloaded_server = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    let (fallback_url, fallback_requests, _headers, fallback_server) =
+        start_lifecycle_mock_provider(vec![
+            json!({
+                "id": "response-tool",
+                "status": "completed",
+                "output": [{
+                    "type": "function_call",
+                    "call_id": "call-1",
+                    "name": "list_page",
+                    "arguments": "{\"offset\":0}"
+                }]
+            }),
+            json!({"id": "response-final", "status": "completed", "output_text": "served by fallback"}),
+            json!({"id": "response-next-turn", "status": "completed", "output_text": "still fallback"}),
+        ])
+        .await;
+    let request = ModelRequest::openai_compatible(
+        format!("http://{address}"),
+        "primary-key",
+        "gpt-primary",
+        "openai",
+        json!([{"role": "user", "content": "keep this history"}]),
+    )
+    .with_responses_support(true)
+    .with_max_transient_retries(Some(0))
+    .with_fallback_models(vec![crate::ModelRuntimeConfig::openai_compatible(
+        fallback_url,
+        "fallback-key",
+        "gpt-fallback",
+        "openai",
+    )
+    .with_responses_support(true)]);
+    let runtime = AiRuntime::new(Some(Arc::new(PagingToolExecutor)))
+        .with_max_iterations(4)
+        .with_model_circuit_breaker(Arc::new(crate::ModelCircuitBreaker::new(
+            std::time::Duration::from_secs(60),
+        )));
+
+    let result = runtime
+        .run_turn(
+            request.clone(),
+            AiRuntimeOptions::for_conversation("failover-session"),
+        )
+        .await
+        .expect("failover turn");
+    let next_turn = runtime
+        .run_turn(
+            request,
+            AiRuntimeOptions::for_conversation("failover-session"),
+        )
+        .await
+        .expect("next turn");
+    overloaded_server.abort();
+    fallback_server.abort();
+
+    assert_eq!(result.content, "served by fallback");
+    assert_eq!(next_turn.content, "still fallback");
+    assert_eq!(overloaded_requests.lock().await.len(), 1);
+    let served = result
+        .iteration_models
+        .iter()
+        .map(|served| {
+            (
+                served.iteration,
+                served.model.as_str(),
+                served.fallback_index,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(served, vec![(1, "gpt-fallback", 1), (2, "gpt-fallback", 1)]);
+    let report = result.into_report();
+    assert_eq!(report.iteration_models.len(), 2);
+    let fallback_requests = fallback_requests.lock().await;
+    assert_eq!(fallback_requests.len(), 3);
+    assert!(fallback_requests[0]
+        .to_string()
+        .contains("keep this history"));
+    assert_eq!(fallback_requests[0]["model"], "gpt-fallback");
+}
+
 #[derive(Clone, Default)]
 struct ParseRecoveryProviderState {
     requests: Arc<AsyncMutex<Vec<Value>>>,
@@ -1051,6 +1293,8 @@ fn turn_report_wraps_success_and_failure() {
         response_id: Some("resp_1".to_string()),
         response_output_items: Vec::new(),
         request_input_items: Vec::new(),
+        iteration_models: Vec::new(),
+        usage_totals: UsageTotals::default(),
     }
     .into_report();
 
@@ -1320,3 +1564,117 @@ fn tool_result(
         transient_model_input: None,
     }
 }
+
+fn anthropic_paging_request(base_url: String) -> ModelRequest {
+    ModelRequest::openai_compatible(
+        base_url,
+        "test-key",
+        "claude-test",
+        "anthropic",
+        json!([{"role": "user", "content": "list every page"}]),
+    )
+    .with_responses_support(true)
+}
+
+#[tokio::test]
+async fn recorded_tool_loop_replays_without_a_provider() {
+    let (base_url, requests, _headers, server) = start_lifecycle_mock_provider(vec![
+        json!({
+            "id": "msg_page_1",
+            "type": "message",
+            "role": "assistant",
+            "content": [
+                {"type": "tool_use", "id": "toolu_1", "name": "list_page", "input": {"offset": 0}}
+            ],
+            "stop_reason": "tool_use",
+            "usage": {"input_tokens": 12, "output_tokens": 6}
+        }),
+        json!({
+            "id": "msg_final",
+            "type": "message",
+            "role": "assistant",
+            "content": [{"type": "text", "text": "all pages listed"}],
+            "stop_reason": "end_turn",
+            "usage": {"input_tokens": 30, "output_tokens": 4}
+        }),
+    ])
+    .await;
+    let cassette_path = std::env::temp_dir().join(format!(
+        "chatos-ai-cassette-{}-tool-loop.json",
+        std::process::id()
+    ));
+    let recorder = Arc::new(crate::AiCassette::record_to(&cassette_path));
+    let recorded = AiRuntime::new(Some(Arc::new(PagingToolExecutor)))
+        .with_max_iterations(3)
+        .with_cassette(Some(Arc::clone(&recorder)))
+        .run_turn(
+            anthropic_paging_request(base_url.clone()),
+            AiRuntimeOptions::for_conversation("session-record"),
+        )
+        .await
+        .expect("recorded tool turn");
+    server.abort();
+    assert_eq!(requests.lock().await.len(), 2);
+
+    let replay = Arc::new(crate::AiCassette::replay_from(&cassette_path).expect("load cassette"));
+    let cassette_text = std::fs::read_to_string(&cassette_path).expect("read cassette");
+    let _ = std::fs::remove_file(&cassette_path);
+    assert_eq!(replay.file(), recorder.file());
+    assert!(!cassette_text.contains("test-key"));
+    let replayed = AiRuntime::new(Some(Arc::new(PagingToolExecutor)))
+        .with_max_iterations(3)
+        .with_cassette(Some(Arc::clone(&replay)))
+        .run_turn(
+            anthropic_paging_request(base_url),
+            AiRuntimeOptions::for_conversation("session-replay"),
+        )
+        .await
+        .expect("replayed tool turn");
+
+    assert_eq!(replayed.content, "all pages listed");
+    assert_eq!(replayed.content, recorded.content);
+    assert_eq!(replayed.usage_totals, recorded.usage_totals);
+    assert_eq!(replay.unused_interactions(), 0);
+}
+
+#[tokio::test]
+async fn replayed_provider_error_fails_the_turn_like_the_recorded_one() {
+    let overloaded_requests = Arc::new(AsyncMutex::new(Vec::new()));
+    let app = Router::new()
+        .route("/messages", post(mock_overloaded_provider))
+        .with_state(Arc::clone(&overloaded_requests));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind overloaded provider");
+    let address = listener.local_addr().expect("overloaded provider address");
+    let server = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    let request =
+        anthropic_paging_request(format!("http://{address}")).with_max_transient_retries(Some(0));
+    let recorder = Arc::new(crate::AiCassette::record_in_memory());
+    let recorded_error = AiRuntime::new(None)
+        .with_cassette(Some(Arc::clone(&recorder)))
+        .run_turn(
+            request.clone(),
+            AiRuntimeOptions::for_conversation("session-overloaded"),
+        )
+        .await
+        .expect_err("overloaded provider fails the turn");
+    server.abort();
+
+    let replay = Arc::new(crate::AiCassette::replay(recorder.file()));
+    let replayed_error = AiRuntime::new(None)
+        .with_cassette(Some(Arc::clone(&replay)))
+        .run_turn(
+            request,
+            AiRuntimeOptions::for_conversation("session-overloaded"),
+        )
+        .await
+        .expect_err("replayed overload fails the turn");
+
+    assert!(recorded_error.contains("529"));
+    assert_eq!(replayed_error, recorded_error);
+    assert_eq!(overloaded_requests.lock().await.len(), 1);
+    assert_eq!(replay.unused_interactions(), 0);
+}
@@ -10,11 +10,21 @@ use crate::response_parse::{
     looks_like_response_id,
 };
 
+#[path = "stream_parse/anthropic.rs"]
+mod anthropic;
+#[path = "stream_parse/gemini.rs"]
+mod gemini;
 #[path = "stream_parse/text.rs"]
 mod text;
 #[path = "stream_parse/tool_calls.rs"]
 mod tool_calls;
 
+pub use self::anthropic::{
+    apply_anthropic_messages_stream_event, finalize_anthropic_messages_stream_state,
+};
+pub use self::gemini::{
+    apply_gemini_generate_content_stream_event, finalize_gemini_generate_content_stream_state,
+};
 use self::text::{
     extract_chat_delta_text, extract_chat_reasoning_text, extract_reasoning_event_text,
     extract_text_delta, extract_text_from_fields, non_empty_trimmed,
@@ -37,6 +47,9 @@ pub struct StreamState {
     pub provider_error: Option<Value>,
     pub response_obj: Option<Value>,
     pub sent_any_chunk: bool,
+    /// Provider content blocks keyed by stream index, for protocols such as
+    /// Anthropic Messages whose blocks must be echoed back verbatim.
+    pub content_blocks: BTreeMap<usize, Value>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -0,0 +1,359 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use serde_json::{json, Value};
+
+use crate::request_payload::ANTHROPIC_THINKING_BLOCKS_KEY;
+use crate::response_parse::append_stream_text;
+use crate::tool_call::{append_tool_call_arguments_delta, build_function_tool_call};
+
+use super::text::non_empty_trimmed;
+use super::tool_calls::collect_stream_tool_calls;
+use super::{FinalizedStreamState, StreamPayload, StreamState};
+
+pub fn apply_anthropic_messages_stream_event(
+    state: &mut StreamState,
+    event: &Value,
+) -> StreamPayload {
+    let mut payload = StreamPayload::default();
+    match event.get("type").and_then(Value::as_str).unwrap_or("") {
+        "message_start" => {
+            if let Some(message) = event.get("message") {
+                ingest_message_envelope(state, message);
+            }
+        }
+        "content_block_start" => {
+            if let (Some(index), Some(block)) = (event_index(event), event.get("content_block")) {
+                start_content_block(state, &mut payload, index, block);
+            }
+        }
+        "content_block_delta" => {
+            if let (Some(index), Some(delta)) = (event_index(event), event.get("delta")) {
+                apply_content_block_delta(state, &mut payload, index, delta);
+            }
+        }
+        "content_block_stop" => {
+            if let Some(index) = event_index(event) {
+                stop_content_block(state, index);
+            }
+        }
+        "message_delta" => {
+            if let Some(stop_reason) = event
+                .get("delta")
+                .and_then(|delta| delta.get("stop_reason"))
+                .and_then(Value::as_str)
+            {
+                state.finish_reason = Some(stop_reason.to_string());
+            }
+            if let Some(usage) = event.get("usage") {
+                merge_usage(state, usage);
+            }
+        }
+        "error" => {
+            state.finish_reason = Some("failed".to_string());
+            state.provider_error = event.get("error").cloned().filter(|value| !value.is_null());
+        }
+        // Non-streaming requests return the whole message as one JSON body.
+        "message" => {
+            ingest_message_envelope(state, event);
+            if let Some(blocks) = event.get("content").and_then(Value::as_array) {
+                for (index, block) in blocks.iter().enumerate() {
+                    start_content_block(state, &mut payload, index, block);
+                    stop_content_block(state, index);
+                }
+            }
+            if let Some(stop_reason) = event.get("stop_reason").and_then(Value::as_str) {
+                state.finish_reason = Some(stop_reason.to_string());
+            }
+        }
+        _ => {}
+    }
+    payload
+}
+
+pub fn finalize_anthropic_messages_stream_state(state: &mut StreamState) -> FinalizedStreamState {
+    let thinking_blocks = state
+        .content_blocks
+        .values()
+        .filter(|block| {
+            matches!(
+                block.get("type").and_then(Value::as_str),
+                Some("thinking") | Some("redacted_thinking")
+            )
+        })
+        .cloned()
+        .collect::<Vec<_>>();
+    let mut tool_calls = collect_stream_tool_calls(&state.tool_calls_map);
+    if !thinking_blocks.is_empty() {
+        if let Some(first) = tool_calls
+            .as_mut()
+            .and_then(Value::as_array_mut)
+            .and_then(|calls| calls.first_mut())
+        {
+            first[ANTHROPIC_THINKING_BLOCKS_KEY] = Value::Array(thinking_blocks);
+        }
+    }
+
+    FinalizedStreamState {
+        content: state.full_content.clone(),
+        reasoning: non_empty_trimmed(state.reasoning.as_str()),
+        tool_calls,
+        finish_reason: state.finish_reason.clone(),
+        provider_error: state.provider_error.clone(),
+        usage: state.usage.clone(),
+        response_id: state.response_id.clone(),
+        response_output_items: Vec::new(),
+    }
+}
+
+fn event_index(event: &Value) -> Option<usize> {
+    event
+        .get("index")
+        .and_then(Value::as_u64)
+        .and_then(|value| usize::try_from(value).ok())
+}
+
+fn ingest_message_envelope(state: &mut StreamState, message: &Value) {
+    if let Some(id) = message.get("id").and_then(Value::as_str) {
+        state.response_id = Some(id.to_string());
+    }
+    if let Some(usage) = message.get("usage") {
+        merge_usage(state, usage);
+    }
+    state.response_obj = Some(message.clone());
+}
+
+fn merge_usage(state: &mut StreamState, usage: &Value) {
+    let Some(update) = usage.as_object() else {
+        return;
+    };
+    let mut merged = state
+        .usage
+        .take()
+        .and_then(|value| value.as_object().cloned())
+        .unwrap_or_default();
+    for (key, value) in update {
+        if !value.is_null() {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+    state.usage = Some(Value::Object(merged));
+}
+
+fn start_content_block(
+    state: &mut StreamState,
+    payload: &mut StreamPayload,
+    index: usize,
+    block: &Value,
+) {
+    match block.get("type").and_then(Value::as_str).unwrap_or("") {
+        "text" => {
+            state
+                .content_blocks
+                .insert(index, json!({ "type": "text", "text": "" }));
+            if let Some(text) = block.get("text").and_then(Value::as_str) {
+                apply_text_delta(state, payload, index, text);
+            }
+        }
+        "thinking" => {
+            state.content_blocks.insert(
+                index,
+                json!({ "type": "thinking", "thinking": "", "signature": "" }),
+            );
+            if let Some(thinking) = block.get("thinking").and_then(Value::as_str) {
+                apply_thinking_delta(state, payload, index, thinking);
+            }
+            if let Some(signature) = block.get("signature").and_then(Value::as_str) {
+                state.content_blocks.entry(index).and_modify(|entry| {
+                    entry["signature"] = Value::String(signature.to_string());
+                });
+            }
+        }
+        "tool_use" => {
+            let id = block.get("id").and_then(Value::as_str).unwrap_or("");
+            let name = block.get("name").and_then(Value::as_str).unwrap_or("");
+            let arguments = block
+                .get("input")
+                .filter(|input| input.as_object().is_some_and(|map| !map.is_empty()))
+                .map(Value::to_string)
+                .unwrap_or_default();
+            state.tool_calls_map.insert(
+                index,
+                build_function_tool_call(id, name, arguments.as_str()),
+            );
+            state.content_blocks.insert(index, block.clone());
+        }
+        _ => {
+            state.content_blocks.insert(index, block.clone());
+        }
+    }
+}
+
+fn apply_content_block_delta(
+    state: &mut StreamState,
+    payload: &mut StreamPayload,
+    index: usize,
+    delta: &Value,
+) {
+    match delta.get("type").and_then(Value::as_str).unwrap_or("") {
+        "text_delta" => {
+            if let Some(text) = delta.get("text").and_then(Value::as_str) {
+                apply_text_delta(state, payload, index, text);
+            }
+        }
+        "thinking_delta" => {
+            if let Some(thinking) = delta.get("thinking").and_then(Value::as_str) {
+                apply_thinking_delta(state, payload, index, thinking);
+            }
+        }
+        "signature_delta" => {
+            if let Some(signature) = delta.get("signature").and_then(Value::as_str) {
+                if let Some(block) = state.content_blocks.get_mut(&index) {
+                    let current = block["signature"].as_str().unwrap_or("").to_string();
+                    block["signature"] = Value::String(current + signature);
+                }
+            }
+        }
+        "input_json_delta" => {
+            if let Some(partial_json) = delta.get("partial_json").and_then(Value::as_str) {
+                if let Some(entry) = state.tool_calls_map.get_mut(&index) {
+                    append_tool_call_arguments_delta(entry, partial_json);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn apply_text_delta(
+    state: &mut StreamState,
+    payload: &mut StreamPayload,
+    index: usize,
+    text: &str,
+) {
+    if text.is_empty() {
+        return;
+    }
+    append_stream_text(&mut state.full_content, text);
+    if let Some(block) = state.content_blocks.get_mut(&index) {
+        let current = block["text"].as_str().unwrap_or("").to_string();
+        block["text"] = Value::String(current + text);
+    }
+    state.sent_any_chunk = true;
+    payload.chunk.get_or_insert_with(String::new).push_str(text);
+}
+
+fn apply_thinking_delta(
+    state: &mut StreamState,
+    payload: &mut StreamPayload,
+    index: usize,
+    thinking: &str,
+) {
+    if thinking.is_empty() {
+        return;
+    }
+    append_stream_text(&mut state.reasoning, thinking);
+    if let Some(block) = state.content_blocks.get_mut(&index) {
+        let current = block["thinking"].as_str().unwrap_or("").to_string();
+        block["thinking"] = Value::String(current + thinking);
+    }
+    payload
+        .thinking
+        .get_or_insert_with(String::new)
+        .push_str(thinking);
+}
+
+fn stop_content_block(state: &mut StreamState, index: usize) {
+    if let Some(entry) = state.tool_calls_map.get_mut(&index) {
+        let arguments = entry["function"]["arguments"].as_str().unwrap_or("");
+        if arguments.trim().is_empty() {
+            entry["function"]["arguments"] = Value::String("{}".to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{apply_anthropic_messages_stream_event, finalize_anthropic_messages_stream_state};
+    use crate::request_payload::ANTHROPIC_THINKING_BLOCKS_KEY;
+    use crate::stream_parse::StreamState;
+
+    #[test]
+    fn streamed_tool_use_and_thinking_blocks_become_runtime_tool_calls() {
+        let mut state = StreamState::default();
+        let events = [
+            json!({"type": "message_start", "message": {"id": "msg_1", "usage": {"input_tokens": 10, "cache_read_input_tokens": 90, "output_tokens": 1}}}),
+            json!({"type": "content_block_start", "index": 0, "content_block": {"type": "thinking", "thinking": ""}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "thinking_delta", "thinking": "look it up"}}),
+            json!({"type": "content_block_delta", "index": 0, "delta": {"type": "signature_delta", "signature": "sig"}}),
+            json!({"type": "content_block_stop", "index": 0}),
+            json!({"type": "content_block_start", "index": 1, "content_block": {"type": "text", "text": ""}}),
+            json!({"type": "content_block_delta", "index": 1, "delta": {"type": "text_delta", "text": "Checking"}}),
+            json!({"type": "content_block_stop", "index": 1}),
+            json!({"type": "content_block_start", "index": 2, "content_block": {"type": "tool_use", "id": "toolu_1", "name": "lookup", "input": {}}}),
+            json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "{\"q\":"}}),
+            json!({"type": "content_block_delta", "index": 2, "delta": {"type": "input_json_delta", "partial_json": "\"x\"}"}}),
+            json!({"type": "content_block_stop", "index": 2}),
+            json!({"type": "message_delta", "delta": {"stop_reason": "tool_use"}, "usage": {"output_tokens": 42}}),
+            json!({"type": "message_stop"}),
+        ];
+        let mut streamed_thinking = String::new();
+        for event in &events {
+            let payload = apply_anthropic_messages_stream_event(&mut state, event);
+            streamed_thinking.push_str(payload.thinking.as_deref().unwrap_or(""));
+        }
+
+        let finalized = finalize_anthropic_messages_stream_state(&mut state);
+        assert_eq!(streamed_thinking, "look it up");
+        assert_eq!(finalized.content, "Checking");
+        assert_eq!(finalized.finish_reason.as_deref(), Some("tool_use"));
+        assert_eq!(finalized.response_id.as_deref(), Some("msg_1"));
+        let usage = finalized.usage.expect("usage");
+        assert_eq!(usage["output_tokens"], json!(42));
+        assert_eq!(usage["cache_read_input_tokens"], json!(90));
+        let tool_call = &finalized.tool_calls.expect("tool calls")[0];
+        assert_eq!(tool_call["id"], "toolu_1");
+        assert_eq!(tool_call["function"]["arguments"], "{\"q\":\"x\"}");
+        assert_eq!(
+            tool_call[ANTHROPIC_THINKING_BLOCKS_KEY][0]["signature"],
+            "sig"
+        );
+    }
+
+    #[test]
+    fn non_streaming_message_body_and_errors_are_parsed() {
+        let mut state = StreamState::default();
+        apply_anthropic_messages_stream_event(
+            &mut state,
+            &json!({
+                "id": "msg_2",
+                "type": "message",
+                "content": [
+                    {"type": "text", "text": "done"},
+                    {"type": "tool_use", "id": "toolu_2", "name": "noop", "input": {}}
+                ],
+                "stop_reason": "tool_use",
+                "usage": {"input_tokens": 3, "output_tokens": 4}
+            }),
+        );
+        let finalized = finalize_anthropic_messages_stream_state(&mut state);
+        assert_eq!(finalized.content, "done");
+        assert_eq!(
+            finalized.tool_calls.expect("tool calls")[0]["function"]["arguments"],
+            "{}"
+        );
+
+        let mut failed = StreamState::default();
+        apply_anthropic_messages_stream_event(
+            &mut failed,
+            &json!({"type": "error", "error": {"type": "overloaded_error", "message": "Overloaded"}}),
+        );
+        assert_eq!(failed.finish_reason.as_deref(), Some("failed"));
+        assert_eq!(
+            failed.provider_error.expect("provider error")["type"],
+            "overloaded_error"
+        );
+    }
+}
@@ -0,0 +1,215 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use serde_json::{json, Value};
+
+use crate::request_payload::GEMINI_THOUGHT_SIGNATURE_KEY;
+use crate::response_parse::append_stream_text;
+use crate::tool_call::build_function_tool_call;
+
+use super::text::non_empty_trimmed;
+use super::tool_calls::collect_stream_tool_calls;
+use super::{FinalizedStreamState, StreamPayload, StreamState};
+
+/// Applies one `GenerateContentResponse` chunk. Streaming responses repeat the
+/// envelope per SSE event; blocking responses arrive as a single chunk.
+pub fn apply_gemini_generate_content_stream_event(
+    state: &mut StreamState,
+    event: &Value,
+) -> StreamPayload {
+    let mut payload = StreamPayload::default();
+    if let Some(error) = event.get("error").filter(|value| !value.is_null()) {
+        state.finish_reason = Some("failed".to_string());
+        state.provider_error = Some(error.clone());
+        return payload;
+    }
+    if let Some(id) = event.get("responseId").and_then(Value::as_str) {
+        state.response_id = Some(id.to_string());
+    }
+    if let Some(usage) = event.get("usageMetadata").filter(|value| value.is_object()) {
+        // Every chunk reports cumulative usage, so the latest one wins.
+        state.usage = Some(usage.clone());
+    }
+    if let Some(block_reason) = event
+        .get("promptFeedback")
+        .and_then(|feedback| feedback.get("blockReason"))
+        .and_then(Value::as_str)
+    {
+        state.finish_reason = Some("blocked".to_string());
+        state.provider_error = Some(json!({
+            "code": "prompt_blocked",
+            "message": format!("prompt blocked by provider: {block_reason}"),
+        }));
+    }
+    state.response_obj = Some(event.clone());
+
+    let Some(candidate) = event
+        .get("candidates")
+        .and_then(Value::as_array)
+        .and_then(|candidates| candidates.first())
+    else {
+        return payload;
+    };
+    let parts = candidate
+        .get("content")
+        .and_then(|content| content.get("parts"))
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for part in &parts {
+        apply_part(state, &mut payload, part);
+    }
+    if let Some(finish_reason) = candidate.get("finishReason").and_then(Value::as_str) {
+        state.finish_reason = Some(finish_reason.to_ascii_lowercase());
+    }
+    payload
+}
+
+pub fn finalize_gemini_generate_content_stream_state(
+    state: &mut StreamState,
+) -> FinalizedStreamState {
+    FinalizedStreamState {
+        content: state.full_content.clone(),
+        reasoning: non_empty_trimmed(state.reasoning.as_str()),
+        tool_calls: collect_stream_tool_calls(&state.tool_calls_map),
+        finish_reason: state.finish_reason.clone(),
+        provider_error: state.provider_error.clone(),
+        usage: state.usage.clone(),
+        response_id: state.response_id.clone(),
+        response_output_items: Vec::new(),
+    }
+}
+
+fn apply_part(state: &mut StreamState, payload: &mut StreamPayload, part: &Value) {
+    if let Some(function_call) = part.get("functionCall") {
+        let name = function_call
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or("");
+        // Older models omit call ids; synthesize one so tool results can be
+        // matched back to the call in the next request.
+        let id = function_call
+            .get("id")
+            .and_then(Value::as_str)
+            .filter(|value| !value.trim().is_empty())
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| format!("call_{}", uuid::Uuid::new_v4().simple()));
+        let arguments = function_call
+            .get("args")
+            .filter(|args| args.is_object())
+            .map(Value::to_string)
+            .unwrap_or_else(|| "{}".to_string());
+        let mut entry = build_function_tool_call(id.as_str(), name, arguments.as_str());
+        if let Some(signature) = part
+            .get("thoughtSignature")
+            .and_then(Value::as_str)
+            .filter(|value| !value.is_empty())
+        {
+            entry[GEMINI_THOUGHT_SIGNATURE_KEY] = Value::String(signature.to_string());
+        }
+        let index = state.tool_calls_map.len();
+        state.tool_call_index_map.insert(id, index);
+        state.tool_calls_map.insert(index, entry);
+        return;
+    }
+
+    let Some(text) = part
+        .get("text")
+        .and_then(Value::as_str)
+        .filter(|text| !text.is_empty())
+    else {
+        return;
+    };
+    if part.get("thought").and_then(Value::as_bool) == Some(true) {
+        append_stream_text(&mut state.reasoning, text);
+        payload
+            .thinking
+            .get_or_insert_with(String::new)
+            .push_str(text);
+    } else {
+        append_stream_text(&mut state.full_content, text);
+        state.sent_any_chunk = true;
+        payload.chunk.get_or_insert_with(String::new).push_str(text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{
+        apply_gemini_generate_content_stream_event, finalize_gemini_generate_content_stream_state,
+    };
+    use crate::request_payload::GEMINI_THOUGHT_SIGNATURE_KEY;
+    use crate::stream_parse::StreamState;
+
+    #[test]
+    fn streams_text_thoughts_and_function_calls() {
+        let mut state = StreamState::default();
+        let events = [
+            json!({"responseId": "resp-1", "candidates": [{"content": {"role": "model", "parts": [
+                {"text": "planning", "thought": true}
+            ]}}]}),
+            json!({"candidates": [{"content": {"role": "model", "parts": [{"text": "Checking"}]}}]}),
+            json!({
+                "candidates": [{
+                    "content": {"role": "model", "parts": [{
+                        "functionCall": {"name": "lookup", "args": {"q": "rust"}},
+                        "thoughtSignature": "sig-a"
+                    }]},
+                    "finishReason": "STOP"
+                }],
+                "usageMetadata": {"promptTokenCount": 12, "candidatesTokenCount": 7, "totalTokenCount": 19}
+            }),
+        ];
+        let mut chunks = String::new();
+        let mut thinking = String::new();
+        for event in &events {
+            let payload = apply_gemini_generate_content_stream_event(&mut state, event);
+            chunks.push_str(payload.chunk.as_deref().unwrap_or(""));
+            thinking.push_str(payload.thinking.as_deref().unwrap_or(""));
+        }
+        let finalized = finalize_gemini_generate_content_stream_state(&mut state);
+
+        assert_eq!(chunks, "Checking");
+        assert_eq!(thinking, "planning");
+        assert_eq!(finalized.content, "Checking");
+        assert_eq!(finalized.reasoning.as_deref(), Some("planning"));
+        assert_eq!(finalized.finish_reason.as_deref(), Some("stop"));
+        assert_eq!(finalized.response_id.as_deref(), Some("resp-1"));
+        assert_eq!(finalized.usage.as_ref().unwrap()["totalTokenCount"], 19);
+        let tool_calls = finalized.tool_calls.expect("tool calls");
+        let call = &tool_calls[0];
+        assert!(call["id"].as_str().unwrap().starts_with("call_"));
+        assert_eq!(call["function"]["name"], "lookup");
+        assert_eq!(call["function"]["arguments"], "{\"q\":\"rust\"}");
+        assert_eq!(call[GEMINI_THOUGHT_SIGNATURE_KEY], "sig-a");
+    }
+
+    #[test]
+    fn surfaces_errors_and_blocked_prompts() {
+        let mut state = StreamState::default();
+        apply_gemini_generate_content_stream_event(
+            &mut state,
+            &json!({"promptFeedback": {"blockReason": "SAFETY"}}),
+        );
+        let finalized = finalize_gemini_generate_content_stream_state(&mut state);
+        assert_eq!(finalized.finish_reason.as_deref(), Some("blocked"));
+        assert_eq!(
+            finalized.provider_error.as_ref().unwrap()["code"],
+            "prompt_blocked"
+        );
+
+        let mut state = StreamState::default();
+        apply_gemini_generate_content_stream_event(
+            &mut state,
+            &json!({"error": {"code": 429, "status": "RESOURCE_EXHAUSTED", "message": "quota"}}),
+        );
+        let finalized = finalize_gemini_generate_content_stream_state(&mut state);
+        assert_eq!(finalized.finish_reason.as_deref(), Some("failed"));
+        assert_eq!(
+            finalized.provider_error.as_ref().unwrap()["status"],
+            "RESOURCE_EXHAUSTED"
+        );
+    }
+}
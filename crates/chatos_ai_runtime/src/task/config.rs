@@ -2,6 +2,7 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 
 use chatos_mcp_runtime::{
@@ -12,6 +13,7 @@ use chatos_mcp_runtime::{
 use super::memory::TaskMemoryRuntimeConfig;
 use super::runtime_builder::TaskRuntimeBuilder;
 use super::{TaskBuiltinMcpPromptMode, TaskRuntime};
+use crate::request::AiCassette;
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -43,6 +45,11 @@ pub struct TaskRuntimeConfig {
     pub ai_read_timeout_ms: Option<u64>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memory_engine: Option<TaskMemoryRuntimeConfig>,
+    /// Provider traffic recorder or replayer for this runtime. Never
+    /// serialized; runtimes without one fall back to the process-wide
+    /// cassette configured through the environment.
+    #[serde(skip)]
+    pub ai_cassette: Option<Arc<AiCassette>>,
 }
 
 impl TaskRuntimeConfig {
@@ -111,6 +118,11 @@ impl TaskRuntimeConfig {
         self
     }
 
+    pub fn with_ai_cassette(mut self, ai_cassette: Option<Arc<AiCassette>>) -> Self {
+        self.ai_cassette = ai_cassette;
+        self
+    }
+
     pub fn to_mcp_executor_builder(&self) -> McpExecutorBuilder {
         McpExecutorBuilder::new()
             .with_http_servers(self.http_servers.clone())
@@ -128,6 +140,9 @@ impl TaskRuntimeConfig {
         if let Some(ai_read_timeout_ms) = self.ai_read_timeout_ms {
             builder = builder.with_request_read_timeout(Duration::from_millis(ai_read_timeout_ms));
         }
+        if let Some(ai_cassette) = self.ai_cassette.clone() {
+            builder = builder.with_cassette(ai_cassette);
+        }
         builder
     }
 
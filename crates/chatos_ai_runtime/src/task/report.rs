@@ -5,6 +5,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 use crate::runtime::{AiTurnReport, AiTurnStatus};
+use crate::usage_cost::UsageTotals;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -202,6 +203,8 @@ impl TaskRunReport {
             finish_reason: self.finish_reason.clone(),
             usage: self.usage.clone(),
             response_id: self.response_id.clone(),
+            iteration_models: Vec::new(),
+            usage_totals: UsageTotals::default(),
             completed_at: self.completed_at.clone(),
         }
         .user_message()
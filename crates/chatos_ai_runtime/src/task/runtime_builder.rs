@@ -7,6 +7,7 @@ use std::time::Duration;
 use chatos_mcp_runtime::{BuiltinMcpPromptLocale, McpExecutor, McpExecutorBuilder};
 
 use crate::builder::AiRuntimeBuilder;
+use crate::request::AiCassette;
 use crate::runtime::MemoryContextOverflowRecovery;
 use crate::traits::{MemoryRecordWriter, ToolExecutor};
 
@@ -127,6 +128,11 @@ impl TaskRuntimeBuilder {
         self
     }
 
+    pub fn with_cassette(mut self, cassette: Arc<AiCassette>) -> Self {
+        self.ai_builder = self.ai_builder.with_cassette(cassette);
+        self
+    }
+
     pub fn with_context_overflow_recovery(
         mut self,
         context_overflow_recovery: Option<MemoryContextOverflowRecovery>,
@@ -6,6 +6,8 @@ use serde_json::Value;
 
 use chatos_mcp_runtime::ToolCallerModelRuntime;
 
+use crate::usage_cost::ModelPricing;
+
 pub const DEFAULT_MODEL_REQUEST_MAX_RETRIES: usize = 5;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -64,6 +66,13 @@ pub struct ModelRuntimeConfig {
     pub max_transient_retries: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub output_format: Option<JsonSchemaOutputFormat>,
+    /// Ordered models tried when this one is overloaded, rate limited or has no
+    /// upstream auth. Only their connection and sampling settings are used;
+    /// instructions, tools and output format stay with the turn.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_models: Vec<ModelRuntimeConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pricing: Option<ModelPricing>,
 }
 
 impl ModelRuntimeConfig {
@@ -150,6 +159,16 @@ impl ModelRuntimeConfig {
         self
     }
 
+    pub fn with_fallback_models(mut self, fallback_models: Vec<ModelRuntimeConfig>) -> Self {
+        self.fallback_models = fallback_models;
+        self
+    }
+
+    pub fn with_pricing(mut self, pricing: Option<ModelPricing>) -> Self {
+        self.pricing = pricing;
+        self
+    }
+
     pub fn to_model_request(&self, input: Value, tools: Vec<Value>) -> ModelRequest {
         let supports_responses = crate::model_config::effective_responses_support(
             self.provider.as_str(),
@@ -175,6 +194,8 @@ impl ModelRuntimeConfig {
             request_body_limit_bytes: self.request_body_limit_bytes,
             max_transient_retries: self.max_transient_retries,
             output_format: self.output_format.clone(),
+            fallback_models: self.fallback_models.clone(),
+            pricing: self.pricing.clone(),
         }
     }
 
@@ -221,6 +242,8 @@ pub struct ModelRequest {
     pub request_body_limit_bytes: Option<usize>,
     pub max_transient_retries: Option<usize>,
     pub output_format: Option<JsonSchemaOutputFormat>,
+    pub fallback_models: Vec<ModelRuntimeConfig>,
+    pub pricing: Option<ModelPricing>,
 }
 
 impl ModelRequest {
@@ -258,6 +281,8 @@ impl ModelRequest {
             request_body_limit_bytes: None,
             max_transient_retries: None,
             output_format: None,
+            fallback_models: Vec::new(),
+            pricing: None,
         }
     }
 
@@ -328,6 +353,16 @@ impl ModelRequest {
         self.output_format = output_format;
         self
     }
+
+    pub fn with_fallback_models(mut self, fallback_models: Vec<ModelRuntimeConfig>) -> Self {
+        self.fallback_models = fallback_models;
+        self
+    }
+
+    pub fn with_pricing(mut self, pricing: Option<ModelPricing>) -> Self {
+        self.pricing = pricing;
+        self
+    }
 }
 
 #[derive(Clone, Default)]
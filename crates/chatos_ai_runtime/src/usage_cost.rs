@@ -0,0 +1,224 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::compat::{extract_usage_snapshot, UsageSnapshot};
+
+const TOKENS_PER_PRICE_UNIT: f64 = 1_000_000.0;
+
+/// Per-million-token prices in USD for one model config. Cached input, cache
+/// writes and reasoning fall back to the plain input and output prices when a
+/// provider does not bill them differently.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPricing {
+    #[serde(default)]
+    pub input_per_million_usd: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input_per_million_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write_input_per_million_usd: Option<f64>,
+    #[serde(default)]
+    pub output_per_million_usd: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_per_million_usd: Option<f64>,
+}
+
+impl ModelPricing {
+    pub fn validate(&self) -> Result<(), String> {
+        let prices = [
+            Some(self.input_per_million_usd),
+            self.cached_input_per_million_usd,
+            self.cache_write_input_per_million_usd,
+            Some(self.output_per_million_usd),
+            self.reasoning_per_million_usd,
+        ];
+        if prices
+            .into_iter()
+            .flatten()
+            .any(|price| !price.is_finite() || price < 0.0)
+        {
+            return Err("model pricing must use non-negative finite prices".to_string());
+        }
+        Ok(())
+    }
+
+    pub fn cost_usd(&self, usage: &UsageSnapshot) -> f64 {
+        let input_tokens = usage.input_tokens.max(0);
+        let cached_tokens = usage.cached_tokens.clamp(0, input_tokens);
+        let cache_write_tokens = usage
+            .cache_write_tokens
+            .clamp(0, input_tokens - cached_tokens);
+        let output_tokens = usage.output_tokens.max(0);
+        let reasoning_tokens = usage.reasoning_tokens.clamp(0, output_tokens);
+        let cached_price = self
+            .cached_input_per_million_usd
+            .unwrap_or(self.input_per_million_usd);
+        let cache_write_price = self
+            .cache_write_input_per_million_usd
+            .unwrap_or(self.input_per_million_usd);
+        let reasoning_price = self
+            .reasoning_per_million_usd
+            .unwrap_or(self.output_per_million_usd);
+        ((input_tokens - cached_tokens - cache_write_tokens) as f64 * self.input_per_million_usd
+            + cached_tokens as f64 * cached_price
+            + cache_write_tokens as f64 * cache_write_price
+            + (output_tokens - reasoning_tokens) as f64 * self.output_per_million_usd
+            + reasoning_tokens as f64 * reasoning_price)
+            / TOKENS_PER_PRICE_UNIT
+    }
+}
+
+/// Token and cost totals accumulated over model requests. Requests made
+/// without a pricing table still count tokens and are tallied separately so
+/// a zero cost is never mistaken for a free run.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageTotals {
+    #[serde(default)]
+    pub requests: u64,
+    #[serde(default)]
+    pub unpriced_requests: u64,
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub cached_input_tokens: u64,
+    #[serde(default)]
+    pub cache_write_input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+    #[serde(default)]
+    pub reasoning_tokens: u64,
+    #[serde(default)]
+    pub cost_usd: f64,
+}
+
+impl UsageTotals {
+    pub fn is_empty(&self) -> bool {
+        self.requests == 0
+    }
+
+    pub fn record(&mut self, usage: &Value, pricing: Option<&ModelPricing>) {
+        self.record_snapshot(&extract_usage_snapshot(usage), pricing);
+    }
+
+    pub fn record_snapshot(&mut self, usage: &UsageSnapshot, pricing: Option<&ModelPricing>) {
+        self.requests += 1;
+        self.input_tokens += non_negative(usage.input_tokens);
+        self.cached_input_tokens += non_negative(usage.cached_tokens);
+        self.cache_write_input_tokens += non_negative(usage.cache_write_tokens);
+        self.output_tokens += non_negative(usage.output_tokens);
+        self.reasoning_tokens += non_negative(usage.reasoning_tokens);
+        match pricing {
+            Some(pricing) => self.cost_usd += pricing.cost_usd(usage),
+            None => self.unpriced_requests += 1,
+        }
+    }
+
+    pub fn merge(&mut self, other: &UsageTotals) {
+        self.requests += other.requests;
+        self.unpriced_requests += other.unpriced_requests;
+        self.input_tokens += other.input_tokens;
+        self.cached_input_tokens += other.cached_input_tokens;
+        self.cache_write_input_tokens += other.cache_write_input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.reasoning_tokens += other.reasoning_tokens;
+        self.cost_usd += other.cost_usd;
+    }
+}
+
+fn non_negative(value: i64) -> u64 {
+    u64::try_from(value).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::{ModelPricing, UsageTotals};
+
+    fn pricing() -> ModelPricing {
+        ModelPricing {
+            input_per_million_usd: 2.0,
+            cached_input_per_million_usd: Some(0.5),
+            cache_write_input_per_million_usd: Some(2.5),
+            output_per_million_usd: 8.0,
+            reasoning_per_million_usd: None,
+        }
+    }
+
+    #[test]
+    fn prices_cached_input_and_reasoning_separately() {
+        let mut totals = UsageTotals::default();
+        totals.record(
+            &json!({
+                "input_tokens": 1_000_000,
+                "output_tokens": 500_000,
+                "input_tokens_details": {"cached_tokens": 400_000},
+                "output_tokens_details": {"reasoning_tokens": 200_000}
+            }),
+            Some(&pricing()),
+        );
+
+        // 600k uncached * $2 + 400k cached * $0.5 + 500k output * $8
+        assert!((totals.cost_usd - 5.4).abs() < 1e-9);
+        assert_eq!(totals.requests, 1);
+        assert_eq!(totals.cached_input_tokens, 400_000);
+        assert_eq!(totals.reasoning_tokens, 200_000);
+    }
+
+    #[test]
+    fn prices_cache_writes_separately_from_uncached_input() {
+        let mut totals = UsageTotals::default();
+        totals.record(
+            &json!({
+                "input_tokens": 200_000,
+                "cache_creation_input_tokens": 600_000,
+                "cache_read_input_tokens": 200_000,
+                "output_tokens": 0
+            }),
+            Some(&pricing()),
+        );
+
+        // 200k uncached * $2 + 600k cache writes * $2.5 + 200k cached * $0.5
+        assert!((totals.cost_usd - 2.0).abs() < 1e-9);
+        assert_eq!(totals.input_tokens, 1_000_000);
+        assert_eq!(totals.cache_write_input_tokens, 600_000);
+        assert_eq!(totals.cached_input_tokens, 200_000);
+
+        let input_priced = ModelPricing {
+            cache_write_input_per_million_usd: None,
+            ..pricing()
+        };
+        let mut totals = UsageTotals::default();
+        totals.record(
+            &json!({"input_tokens": 0, "cache_creation_input_tokens": 1_000_000}),
+            Some(&input_priced),
+        );
+        assert!((totals.cost_usd - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn counts_unpriced_requests_and_merges_totals() {
+        let mut turn = UsageTotals::default();
+        turn.record(&json!({"prompt_tokens": 10, "completion_tokens": 5}), None);
+        let mut run = UsageTotals::default();
+        run.record(
+            &json!({"prompt_tokens": 1_000_000, "completion_tokens": 0}),
+            Some(&pricing()),
+        );
+        run.merge(&turn);
+
+        assert_eq!(run.requests, 2);
+        assert_eq!(run.unpriced_requests, 1);
+        assert_eq!(run.input_tokens, 1_000_010);
+        assert!((run.cost_usd - 2.0).abs() < 1e-9);
+        assert!(pricing().validate().is_ok());
+        assert!(ModelPricing {
+            input_per_million_usd: -1.0,
+            ..pricing()
+        }
+        .validate()
+        .is_err());
+    }
+}
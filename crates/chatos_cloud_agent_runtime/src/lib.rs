@@ -1357,6 +1357,8 @@ mod tests {
                     response_id: Some("response-1".to_string()),
                     response_output_items: Vec::new(),
                     request_input_items: Vec::new(),
+                    iteration_models: Vec::new(),
+                    usage_totals: chatos_ai_runtime::UsageTotals::default(),
                 },
                 tool_calls: serde_json::json!([{"id": "call-1"}]),
             },
@@ -1597,6 +1599,8 @@ mod tests {
                 response_id: Some("response-1".to_string()),
                 response_output_items: Vec::new(),
                 request_input_items: Vec::new(),
+                iteration_models: Vec::new(),
+                usage_totals: chatos_ai_runtime::UsageTotals::default(),
             },
             tool_calls: Value::Array(
                 (0..call_count)
@@ -1776,6 +1780,8 @@ mod tests {
                 response_id: Some("response-after-retry".to_string()),
                 response_output_items: Vec::new(),
                 request_input_items: durable_retry_items,
+                iteration_models: Vec::new(),
+                usage_totals: chatos_ai_runtime::UsageTotals::default(),
             }),
             seen_triggers: Arc::clone(&seen_triggers),
         };
@@ -2067,6 +2073,8 @@ mod tests {
                 response_id: Some("response-final".to_string()),
                 response_output_items: Vec::new(),
                 request_input_items: Vec::new(),
+                iteration_models: Vec::new(),
+                usage_totals: chatos_ai_runtime::UsageTotals::default(),
             }),
             seen_triggers: Arc::new(Mutex::new(Vec::new())),
         };
@@ -2167,6 +2175,8 @@ mod tests {
                     response_id: Some("response-slow".to_string()),
                     response_output_items: Vec::new(),
                     request_input_items: Vec::new(),
+                    iteration_models: Vec::new(),
+                    usage_totals: chatos_ai_runtime::UsageTotals::default(),
                 })),
             ))
         }
@@ -2190,6 +2200,8 @@ mod tests {
                     response_id: Some("response-final".to_string()),
                     response_output_items: Vec::new(),
                     request_input_items: Vec::new(),
+                    iteration_models: Vec::new(),
+                    usage_totals: chatos_ai_runtime::UsageTotals::default(),
                 })),
             ))
         }
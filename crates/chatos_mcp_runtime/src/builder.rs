@@ -6,7 +6,10 @@ use std::sync::Arc;
 
 use crate::executor::McpExecutor;
 use crate::registry::{BuiltinToolProvider, BuiltinToolRegistry};
-use crate::types::{McpBuiltinServer, McpHttpServer, McpStdioServer, ToolLifecycleHook};
+use crate::types::{
+    McpBuiltinServer, McpElicitationHandler, McpHttpServer, McpSamplingHandler, McpStdioServer,
+    ToolLifecycleHook,
+};
 use crate::{
     builtin_servers_from_kinds, default_runtime_builtin_kinds, BuiltinMcpKind,
     BuiltinMcpServerOptions,
@@ -22,6 +25,9 @@ pub struct McpExecutorBuilder {
     declared_allowed_tool_names: BTreeSet<String>,
     tool_lifecycle_hook: Option<Arc<dyn ToolLifecycleHook>>,
     tool_result_max_chars: Option<usize>,
+    resource_tools: bool,
+    sampling_handler: Option<Arc<dyn McpSamplingHandler>>,
+    elicitation_handler: Option<Arc<dyn McpElicitationHandler>>,
 }
 
 impl McpExecutorBuilder {
@@ -127,8 +133,25 @@ impl McpExecutorBuilder {
         self
     }
 
+    /// Exposes MCP resources to the model through `mcp_list_resources` and
+    /// `mcp_read_resource`.
+    pub fn with_resource_tools(mut self) -> Self {
+        self.resource_tools = true;
+        self
+    }
+
+    pub fn with_sampling_handler(mut self, handler: Arc<dyn McpSamplingHandler>) -> Self {
+        self.sampling_handler = Some(handler);
+        self
+    }
+
+    pub fn with_elicitation_handler(mut self, handler: Arc<dyn McpElicitationHandler>) -> Self {
+        self.elicitation_handler = Some(handler);
+        self
+    }
+
     pub fn build(self) -> McpExecutor {
-        McpExecutor::new_with_tool_constraints(
+        let mut executor = McpExecutor::new_with_tool_constraints(
             self.http_servers,
             self.stdio_servers,
             self.builtin_servers,
@@ -138,6 +161,14 @@ impl McpExecutorBuilder {
             self.tool_lifecycle_hook,
             self.tool_result_max_chars,
         )
+        .with_resource_tools(self.resource_tools);
+        if let Some(handler) = self.sampling_handler {
+            executor = executor.with_sampling_handler(handler);
+        }
+        if let Some(handler) = self.elicitation_handler {
+            executor = executor.with_elicitation_handler(handler);
+        }
+        executor
     }
 
     pub async fn build_initialized(self) -> Result<McpExecutor, String> {
@@ -163,7 +194,7 @@ mod tests {
 
     use crate::{
         BuiltinMcpServerOptions, BuiltinToolProvider, McpBuiltinServer, McpExecutorBuilder,
-        McpHttpServer, ToolCallContext, ToolLifecycleEvent, ToolLifecycleHook,
+        McpHttpServer, PreToolUseDecision, ToolCallContext, ToolLifecycleEvent, ToolLifecycleHook,
         ToolLifecycleOutcome, ToolStreamChunkCallback,
     };
 
@@ -303,6 +334,36 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
+    struct DecidingLifecycleHook {
+        decision: PreToolUseDecision,
+        post_events: Mutex<Vec<ToolLifecycleEvent>>,
+    }
+
+    #[async_trait]
+    impl ToolLifecycleHook for DecidingLifecycleHook {
+        async fn before_tool_use(&self, _event: &ToolLifecycleEvent) -> Result<(), String> {
+            unreachable!("pre_tool_use is overridden")
+        }
+
+        async fn pre_tool_use(
+            &self,
+            _event: &ToolLifecycleEvent,
+            arguments: &Value,
+        ) -> Result<PreToolUseDecision, String> {
+            assert!(arguments.get("secret").is_some());
+            Ok(self.decision.clone())
+        }
+
+        async fn after_tool_use(&self, event: &ToolLifecycleEvent) -> Result<(), String> {
+            self.post_events
+                .lock()
+                .expect("lifecycle events")
+                .push(event.clone());
+            Ok(())
+        }
+    }
+
     fn lifecycle_server() -> McpBuiltinServer {
         McpBuiltinServer {
             name: "lifecycle".to_string(),
@@ -632,4 +693,62 @@ mod tests {
             );
         }
     }
+
+    #[tokio::test]
+    async fn pre_tool_hook_decisions_deny_rewrite_and_annotate_single_calls() {
+        let run = |decision: PreToolUseDecision| async move {
+            let calls = Arc::new(AtomicUsize::new(0));
+            let hook = Arc::new(DecidingLifecycleHook {
+                decision,
+                post_events: Mutex::new(Vec::new()),
+            });
+            let executor = crate::McpExecutor::builder()
+                .with_builtin_server(lifecycle_server())
+                .with_builtin_provider(LifecycleProvider {
+                    calls: Arc::clone(&calls),
+                    fail: false,
+                })
+                .with_tool_lifecycle_hook(hook.clone())
+                .build_builtin_only()
+                .expect("lifecycle executor");
+            let results = executor
+                .execute_tools_stream(
+                    &[lifecycle_tool_call("call-1", "original")],
+                    ToolCallContext::default(),
+                    None,
+                )
+                .await;
+            let post_events = hook.post_events.lock().expect("lifecycle events").len();
+            (calls.load(Ordering::SeqCst), post_events, results)
+        };
+
+        let (calls, post_events, results) = run(PreToolUseDecision {
+            deny_reason: Some("migrations/ is read-only".to_string()),
+            ..PreToolUseDecision::default()
+        })
+        .await;
+        assert_eq!((calls, post_events), (0, 0));
+        assert!(!results[0].success);
+        assert!(!results[0].fatal_error);
+        assert!(results[0].content.contains("migrations/ is read-only"));
+
+        let (calls, post_events, results) = run(PreToolUseDecision {
+            updated_arguments: Some(json!({"secret": "rewritten"})),
+            additional_context: Some("ran under the plugin guardrail".to_string()),
+            ..PreToolUseDecision::default()
+        })
+        .await;
+        assert_eq!((calls, post_events), (1, 1));
+        assert!(results[0].success);
+        assert_eq!(
+            results[0]
+                .result
+                .as_ref()
+                .and_then(|result| result.get("echo")),
+            Some(&json!({"secret": "rewritten"}))
+        );
+        assert!(results[0]
+            .content
+            .ends_with("[PreToolUse Hook context]\nran under the plugin guardrail"));
+    }
 }
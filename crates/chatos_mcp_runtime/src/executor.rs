@@ -12,7 +12,10 @@ use crate::builtin_prompt::{BuiltinMcpPromptBuildResult, BuiltinMcpPromptLocale}
 use crate::parallelism::should_parallelize_tool_batch;
 use crate::registry::BuiltinToolRegistry;
 use crate::tool_call::extract_tool_call_name;
-use crate::types::{McpBuiltinServer, McpHttpServer, McpStdioServer, ToolInfo, ToolLifecycleHook};
+use crate::types::{
+    McpBuiltinServer, McpElicitationHandler, McpHttpServer, McpSamplingHandler, McpStdioServer,
+    ToolInfo, ToolLifecycleHook,
+};
 
 const PUBLIC_ISOLATED_WORKSPACE_CWD: &str = "/workspace";
 
@@ -30,10 +33,21 @@ pub struct McpExecutor {
     declared_allowed_tool_names: BTreeSet<String>,
     tool_lifecycle_hook: Option<Arc<dyn ToolLifecycleHook>>,
     tool_result_max_chars: Option<usize>,
+    resource_tools: bool,
+    sampling_handler: Option<Arc<dyn McpSamplingHandler>>,
+    elicitation_handler: Option<Arc<dyn McpElicitationHandler>>,
 }
 
 mod execution;
 mod registration;
+mod resources;
+mod server_messages;
+
+pub use resources::{
+    mcp_prompt_input_items, mcp_resource_context_text, MCP_LIST_RESOURCES_TOOL_NAME,
+    MCP_READ_RESOURCE_TOOL_NAME,
+};
+pub use server_messages::MCP_TOOL_NOTICE_STREAM_EVENT;
 
 impl McpExecutor {
     pub fn builder() -> crate::builder::McpExecutorBuilder {
@@ -81,9 +95,24 @@ impl McpExecutor {
             declared_allowed_tool_names,
             tool_lifecycle_hook,
             tool_result_max_chars,
+            resource_tools: false,
+            sampling_handler: None,
+            elicitation_handler: None,
         }
     }
 
+    /// Lets servers sample the calling turn's model while their tools run.
+    pub fn with_sampling_handler(mut self, handler: Arc<dyn McpSamplingHandler>) -> Self {
+        self.sampling_handler = Some(handler);
+        self
+    }
+
+    /// Lets servers ask the user for input while their tools run.
+    pub fn with_elicitation_handler(mut self, handler: Arc<dyn McpElicitationHandler>) -> Self {
+        self.elicitation_handler = Some(handler);
+        self
+    }
+
     pub async fn init(&mut self) -> Result<(), String> {
         let started_at = Instant::now();
         self.available_tools.clear();
@@ -93,6 +122,7 @@ impl McpExecutor {
         self.register_http_tools().await?;
         self.register_stdio_tools().await;
         self.register_builtin_tools();
+        self.register_resource_tools();
         self.apply_tool_allowlist()?;
         info!(
             mcp_init_mode = "full",
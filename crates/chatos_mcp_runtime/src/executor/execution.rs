@@ -7,7 +7,7 @@ use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
 
 use crate::naming::{canonical_prefixed_tool_name, legacy_prefixed_tool_name};
-use crate::rpc::{jsonrpc_http_tool_call_cancellable_with_client, jsonrpc_stdio_call};
+use crate::rpc::{jsonrpc_http_tool_call_with_handler, jsonrpc_stdio_call_with_handler};
 use crate::text::{
     inject_agent_builder_args, to_text_and_structured_result_with_transient,
     to_text_and_structured_result_with_transient_limit,
@@ -17,6 +17,7 @@ use crate::types::{
     ToolResultCallback, ToolStreamChunkCallback,
 };
 
+use super::server_messages::ToolCallMessageHandler;
 use super::McpExecutor;
 
 const TASK_RUNNER_MCP_SERVER_NAME: &str = "task_runner_service";
@@ -145,7 +146,7 @@ impl McpExecutor {
     async fn call_tool_once(
         &self,
         tool_name: &str,
-        args: Value,
+        mut args: Value,
         context: ToolCallContext,
         on_stream_chunk: Option<ToolStreamChunkCallback>,
     ) -> Result<(String, Option<Value>), ToolCallError> {
@@ -162,28 +163,48 @@ impl McpExecutor {
             arguments_sha256: sha256_json(&args)?,
             outcome: None,
             result_sha256: None,
+            notice: None,
         };
+        let mut additional_context = None;
         if let Some(hook) = &self.tool_lifecycle_hook {
-            hook.before_tool_use(&lifecycle_event)
+            let decision = hook
+                .pre_tool_use(&lifecycle_event, &args)
                 .await
                 .map_err(|error| {
                     ToolCallError::fatal(format!(
                         "PreToolUse Hook blocked tool {tool_name}: {error}"
                     ))
                 })?;
+            if let Some(reason) = decision.deny_reason {
+                return Err(ToolCallError::non_fatal(format!(
+                    "PreToolUse Hook denied tool {tool_name}: {reason}"
+                )));
+            }
+            if let Some(updated) = decision.updated_arguments {
+                lifecycle_event.arguments_sha256 = sha256_json(&updated)?;
+                args = updated;
+            }
+            additional_context = decision.additional_context;
         }
         let result = async {
             match info.server_type.as_str() {
                 "http" => {
                     let url = info.server_url.clone().ok_or("missing server url")?;
                     let headers = http_tool_call_headers(info, &context).await?;
-                    let result = jsonrpc_http_tool_call_cancellable_with_client(
+                    let handler = ToolCallMessageHandler {
+                        executor: self,
+                        event: &lifecycle_event,
+                        context: &context,
+                        on_stream_chunk: on_stream_chunk.as_ref(),
+                    };
+                    let result = jsonrpc_http_tool_call_with_handler(
                         url.as_str(),
                         headers.as_ref(),
-                        json!({"name": info.original_name, "arguments": args}),
+                        tool_call_params(info.original_name.as_str(), args),
                         info.server_timeout,
                         info.server_async_result_transport,
                         info.server_http_client.as_ref(),
+                        Some(&handler),
                     )
                     .await
                     .map_err(classify_remote_tool_call_error)?;
@@ -191,11 +212,18 @@ impl McpExecutor {
                 }
                 "stdio" => {
                     let config = info.server_config.clone().ok_or("missing server config")?;
-                    let result = jsonrpc_stdio_call(
+                    let handler = ToolCallMessageHandler {
+                        executor: self,
+                        event: &lifecycle_event,
+                        context: &context,
+                        on_stream_chunk: on_stream_chunk.as_ref(),
+                    };
+                    let result = jsonrpc_stdio_call_with_handler(
                         &config,
                         "tools/call",
-                        json!({"name": info.original_name, "arguments": args}),
-                        context.conversation_id.as_deref(),
+                        tool_call_params(info.original_name.as_str(), args),
+                        None,
+                        Some(&handler),
                     )
                     .await?;
                     Ok(self.normalize_tool_result(&result, tool_result_max_chars))
@@ -215,6 +243,12 @@ impl McpExecutor {
                         .await?;
                     Ok(self.normalize_tool_result(&result, tool_result_max_chars))
                 }
+                super::resources::MCP_RESOURCE_TOOLS_SERVER_TYPE => {
+                    let result = self
+                        .call_resource_tool(info.original_name.as_str(), &args)
+                        .await?;
+                    Ok(self.normalize_tool_result(&result, tool_result_max_chars))
+                }
                 other => Err(ToolCallError::non_fatal(format!(
                     "unsupported server type: {other}"
                 ))),
@@ -240,7 +274,15 @@ impl McpExecutor {
                     ))
                 })?;
         }
-        result
+        match additional_context {
+            Some(context) => result.map(|(text, structured)| {
+                (
+                    format!("{text}\n\n[PreToolUse Hook context]\n{context}"),
+                    structured,
+                )
+            }),
+            None => result,
+        }
     }
 
     fn normalize_tool_result(
@@ -254,6 +296,16 @@ impl McpExecutor {
     }
 }
 
+/// `tools/call` params with a progress token, so servers that report
+/// progress send `notifications/progress` for this call.
+fn tool_call_params(tool_name: &str, args: Value) -> Value {
+    json!({
+        "name": tool_name,
+        "arguments": args,
+        "_meta": {"progressToken": uuid::Uuid::new_v4().to_string()}
+    })
+}
+
 fn batch_error_results(
     tool_calls: &[Value],
     context: &ToolCallContext,
@@ -0,0 +1,473 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use serde_json::{json, Map, Value};
+use tracing::warn;
+
+use crate::rpc::{jsonrpc_http_call_with_client, jsonrpc_stdio_call};
+use crate::types::{McpAsyncResultTransport, ParsedToolDefinition, ToolCallError};
+
+use super::McpExecutor;
+
+pub const MCP_LIST_RESOURCES_TOOL_NAME: &str = "mcp_list_resources";
+pub const MCP_READ_RESOURCE_TOOL_NAME: &str = "mcp_read_resource";
+pub(in crate::executor) const MCP_RESOURCE_TOOLS_SERVER_TYPE: &str = "mcp_resources";
+const MCP_RESOURCE_TOOLS_SERVER_NAME: &str = "mcp_resources";
+/// Upper bound on `nextCursor` pages fetched per server, so a server that
+/// keeps returning cursors cannot stall executor calls.
+const MAX_LIST_PAGES: usize = 20;
+
+impl McpExecutor {
+    /// Registers `mcp_list_resources` and `mcp_read_resource` on the next
+    /// `init()`, letting the model pull server resources into its context.
+    pub fn with_resource_tools(mut self, enabled: bool) -> Self {
+        self.resource_tools = enabled;
+        self
+    }
+
+    /// Resources of every HTTP and stdio server, each tagged with the
+    /// `server` it came from. Servers without resource support are skipped.
+    pub async fn list_resources(&self) -> Vec<Value> {
+        self.list_from_all_servers("resources/list", "resources")
+            .await
+    }
+
+    pub async fn list_resource_templates(&self) -> Vec<Value> {
+        self.list_from_all_servers("resources/templates/list", "resourceTemplates")
+            .await
+    }
+
+    pub async fn read_resource(&self, server_name: &str, uri: &str) -> Result<Vec<Value>, String> {
+        let result = self
+            .server_rpc(server_name, "resources/read", json!({ "uri": uri }))
+            .await?;
+        result
+            .get("contents")
+            .and_then(Value::as_array)
+            .cloned()
+            .ok_or_else(|| format!("MCP 服务 {server_name} 的 resources/read 未返回 contents"))
+    }
+
+    pub async fn subscribe_resource(&self, server_name: &str, uri: &str) -> Result<(), String> {
+        self.server_rpc(server_name, "resources/subscribe", json!({ "uri": uri }))
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn unsubscribe_resource(&self, server_name: &str, uri: &str) -> Result<(), String> {
+        self.server_rpc(server_name, "resources/unsubscribe", json!({ "uri": uri }))
+            .await
+            .map(|_| ())
+    }
+
+    pub async fn list_prompts(&self) -> Vec<Value> {
+        self.list_from_all_servers("prompts/list", "prompts").await
+    }
+
+    /// Renders a server prompt. Pass the result to [`mcp_prompt_input_items`]
+    /// to start a chat turn from it.
+    pub async fn get_prompt(
+        &self,
+        server_name: &str,
+        name: &str,
+        arguments: Map<String, Value>,
+    ) -> Result<Value, String> {
+        self.server_rpc(
+            server_name,
+            "prompts/get",
+            json!({ "name": name, "arguments": arguments }),
+        )
+        .await
+    }
+
+    async fn server_rpc(
+        &self,
+        server_name: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, String> {
+        if let Some(server) = self
+            .http_servers
+            .iter()
+            .find(|server| server.name == server_name)
+        {
+            let headers = server.resolved_headers().await?;
+            return jsonrpc_http_call_with_client(
+                server.url.as_str(),
+                headers.as_ref(),
+                method,
+                params,
+                server.timeout_duration(),
+                server.http_client.as_ref(),
+            )
+            .await;
+        }
+        if let Some(server) = self
+            .stdio_servers
+            .iter()
+            .find(|server| server.name == server_name)
+        {
+            return jsonrpc_stdio_call(server, method, params, None).await;
+        }
+        Err(format!("未找到支持资源与提示词的 MCP 服务: {server_name}"))
+    }
+
+    async fn list_from_all_servers(&self, method: &str, key: &str) -> Vec<Value> {
+        let server_names = self
+            .http_servers
+            .iter()
+            .map(|server| server.name.clone())
+            .chain(self.stdio_servers.iter().map(|server| server.name.clone()))
+            .collect::<Vec<_>>();
+        let mut out = Vec::new();
+        for server_name in server_names {
+            match self
+                .list_server_pages(server_name.as_str(), method, key)
+                .await
+            {
+                Ok(items) => out.extend(items.into_iter().map(|mut item| {
+                    if let Some(item) = item.as_object_mut() {
+                        item.insert("server".to_string(), json!(server_name));
+                    }
+                    item
+                })),
+                Err(err) => warn!(
+                    server_name = server_name.as_str(),
+                    method,
+                    error = err.as_str(),
+                    "failed to list MCP server items"
+                ),
+            }
+        }
+        out
+    }
+
+    async fn list_server_pages(
+        &self,
+        server_name: &str,
+        method: &str,
+        key: &str,
+    ) -> Result<Vec<Value>, String> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_LIST_PAGES {
+            let params = match cursor.as_ref() {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let result = self.server_rpc(server_name, method, params).await?;
+            if let Some(page) = result.get(key).and_then(Value::as_array) {
+                items.extend(page.iter().cloned());
+            }
+            cursor = result
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .filter(|cursor| !cursor.is_empty())
+                .map(ToOwned::to_owned);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
+
+    pub(in crate::executor) fn register_resource_tools(&mut self) {
+        if !self.resource_tools || (self.http_servers.is_empty() && self.stdio_servers.is_empty()) {
+            return;
+        }
+        for def in resource_tool_definitions() {
+            let tool = json!({
+                "name": def.name,
+                "description": def.description,
+                "inputSchema": def.parameters
+            });
+            self.register_available_tool(
+                MCP_RESOURCE_TOOLS_SERVER_NAME,
+                MCP_RESOURCE_TOOLS_SERVER_NAME,
+                MCP_RESOURCE_TOOLS_SERVER_TYPE,
+                None,
+                None,
+                None,
+                None,
+                McpAsyncResultTransport::Disabled,
+                None,
+                None,
+                true,
+                def,
+                tool,
+            );
+        }
+    }
+
+    /// Runs a resource tool and returns a `tools/call` shaped result so it
+    /// goes through the same normalization as remote tools.
+    pub(in crate::executor) async fn call_resource_tool(
+        &self,
+        tool_name: &str,
+        args: &Value,
+    ) -> Result<Value, ToolCallError> {
+        match tool_name {
+            MCP_LIST_RESOURCES_TOOL_NAME => {
+                let server = optional_string_arg(args, "server");
+                let mut resources = self.list_resources().await;
+                if let Some(server) = server {
+                    resources.retain(|resource| resource["server"] == server);
+                }
+                let text = serde_json::to_string_pretty(&resources)
+                    .map_err(|err| ToolCallError::non_fatal(err.to_string()))?;
+                Ok(json!({
+                    "content": [{ "type": "text", "text": text }],
+                    "structuredContent": { "resources": resources }
+                }))
+            }
+            MCP_READ_RESOURCE_TOOL_NAME => {
+                let server = optional_string_arg(args, "server")
+                    .ok_or_else(|| ToolCallError::non_fatal("server is required"))?;
+                let uri = optional_string_arg(args, "uri")
+                    .ok_or_else(|| ToolCallError::non_fatal("uri is required"))?;
+                let contents = self
+                    .read_resource(server, uri)
+                    .await
+                    .map_err(ToolCallError::non_fatal)?;
+                Ok(json!({
+                    "content": [{ "type": "text", "text": mcp_resource_context_text(&contents) }]
+                }))
+            }
+            other => Err(ToolCallError::non_fatal(format!(
+                "unknown MCP resource tool: {other}"
+            ))),
+        }
+    }
+}
+
+/// Flattens `resources/read` contents into text that can be attached to a
+/// turn as context. Binary blobs are described instead of inlined.
+pub fn mcp_resource_context_text(contents: &[Value]) -> String {
+    contents
+        .iter()
+        .map(|content| {
+            let uri = content.get("uri").and_then(Value::as_str).unwrap_or("");
+            let mime_type = content
+                .get("mimeType")
+                .and_then(Value::as_str)
+                .unwrap_or("text/plain");
+            match (
+                content.get("text").and_then(Value::as_str),
+                content.get("blob").and_then(Value::as_str),
+            ) {
+                (Some(text), _) => format!("<resource uri=\"{uri}\" mimeType=\"{mime_type}\">\n{text}\n</resource>"),
+                (None, Some(blob)) => format!(
+                    "<resource uri=\"{uri}\" mimeType=\"{mime_type}\">[binary content, {} base64 chars]</resource>",
+                    blob.len()
+                ),
+                (None, None) => format!("<resource uri=\"{uri}\" mimeType=\"{mime_type}\" />"),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Converts a `prompts/get` result into runtime input items. Embedded
+/// resources become text so a user-invoked prompt can start a chat turn.
+pub fn mcp_prompt_input_items(prompt: &Value) -> Vec<Value> {
+    prompt
+        .get("messages")
+        .and_then(Value::as_array)
+        .map(|messages| {
+            messages
+                .iter()
+                .filter_map(|message| {
+                    let role = match message.get("role").and_then(Value::as_str) {
+                        Some("assistant") => "assistant",
+                        _ => "user",
+                    };
+                    let content = message.get("content")?;
+                    let text = match content.get("type").and_then(Value::as_str) {
+                        Some("text") => content.get("text").and_then(Value::as_str)?.to_string(),
+                        Some("resource") => mcp_resource_context_text(std::slice::from_ref(
+                            content.get("resource")?,
+                        )),
+                        Some(other) => format!("[{other} content omitted]"),
+                        None => return None,
+                    };
+                    Some(json!({ "role": role, "content": text }))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn resource_tool_definitions() -> Vec<ParsedToolDefinition> {
+    vec![
+        ParsedToolDefinition {
+            name: MCP_LIST_RESOURCES_TOOL_NAME.to_string(),
+            description: "List resources exposed by the connected MCP servers.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "server": {
+                        "type": "string",
+                        "description": "Only list resources of this MCP server."
+                    }
+                }
+            }),
+        },
+        ParsedToolDefinition {
+            name: MCP_READ_RESOURCE_TOOL_NAME.to_string(),
+            description: "Read an MCP resource and return its contents as context.".to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {
+                    "server": {
+                        "type": "string",
+                        "description": "MCP server that owns the resource."
+                    },
+                    "uri": { "type": "string", "description": "Resource URI." }
+                },
+                "required": ["server", "uri"]
+            }),
+        },
+    ]
+}
+
+fn optional_string_arg<'a>(args: &'a Value, key: &str) -> Option<&'a str> {
+    args.get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Map, Value};
+
+    use super::{
+        mcp_prompt_input_items, MCP_LIST_RESOURCES_TOOL_NAME, MCP_READ_RESOURCE_TOOL_NAME,
+    };
+    use crate::{McpExecutor, McpHttpServer, ToolCallContext};
+
+    async fn mcp(axum::Json(request): axum::Json<Value>) -> axum::Json<Value> {
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap_or("") {
+            "tools/list" => json!({"tools": []}),
+            "resources/list" if params.get("cursor").is_none() => json!({
+                "resources": [{"uri": "docs://readme", "name": "README"}],
+                "nextCursor": "page-2"
+            }),
+            "resources/list" => {
+                json!({"resources": [{"uri": "docs://changelog", "name": "CHANGELOG"}]})
+            }
+            "resources/read" => json!({"contents": [
+                {"uri": params["uri"], "mimeType": "text/markdown", "text": "# Docs"},
+                {"uri": "docs://logo", "mimeType": "image/png", "blob": "aGVsbG8="}
+            ]}),
+            "prompts/get" => json!({"messages": [
+                {"role": "user", "content": {"type": "text", "text": format!(
+                    "Summarize {}", params["arguments"]["topic"].as_str().unwrap_or("?")
+                )}},
+                {"role": "user", "content": {"type": "resource", "resource": {
+                    "uri": "docs://readme", "text": "# Docs"
+                }}}
+            ]}),
+            _ => {
+                return axum::Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "error": {"code": -32601, "message": "method not found"}
+                }))
+            }
+        };
+        axum::Json(json!({"jsonrpc": "2.0", "id": request["id"], "result": result}))
+    }
+
+    async fn spawn_server() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                axum::Router::new().route("/mcp", axum::routing::post(mcp)),
+            )
+            .await
+            .unwrap();
+        });
+        format!("http://{addr}/mcp")
+    }
+
+    #[tokio::test]
+    async fn lists_reads_and_renders_prompts_across_pages() {
+        let url = spawn_server().await;
+        let mut executor = McpExecutor::builder()
+            .with_http_server(McpHttpServer::new("docs", url.as_str()))
+            .with_resource_tools()
+            .build();
+        executor.init().await.unwrap();
+
+        let resources = executor.list_resources().await;
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[1]["uri"], "docs://changelog");
+        assert_eq!(resources[0]["server"], "docs");
+
+        let mut arguments = Map::new();
+        arguments.insert("topic".to_string(), json!("releases"));
+        let prompt = executor
+            .get_prompt("docs", "summarize", arguments)
+            .await
+            .unwrap();
+        let items = mcp_prompt_input_items(&prompt);
+        assert_eq!(
+            items[0],
+            json!({"role": "user", "content": "Summarize releases"})
+        );
+        assert!(items[1]["content"]
+            .as_str()
+            .unwrap()
+            .contains("<resource uri=\"docs://readme\""));
+        assert!(executor
+            .subscribe_resource("docs", "docs://readme")
+            .await
+            .unwrap_err()
+            .contains("method not found"));
+        assert!(executor
+            .read_resource("missing", "docs://readme")
+            .await
+            .is_err());
+
+        let tool_names = executor
+            .available_tools()
+            .iter()
+            .filter_map(|tool| tool["name"].as_str().map(ToOwned::to_owned))
+            .collect::<Vec<_>>();
+        assert!(tool_names.contains(&MCP_LIST_RESOURCES_TOOL_NAME.to_string()));
+        let results = executor
+            .execute_tools_stream(
+                &[json!({
+                    "id": "call-1",
+                    "type": "function",
+                    "function": {
+                        "name": MCP_READ_RESOURCE_TOOL_NAME,
+                        "arguments": r#"{"server":"docs","uri":"docs://readme"}"#
+                    }
+                })],
+                ToolCallContext::default(),
+                None,
+            )
+            .await;
+        assert!(results[0].success, "{}", results[0].content);
+        assert!(results[0].content.contains("# Docs"));
+        assert!(results[0]
+            .content
+            .contains("[binary content, 8 base64 chars]"));
+    }
+
+    #[tokio::test]
+    async fn resource_tools_stay_hidden_unless_enabled() {
+        let url = spawn_server().await;
+        let executor = McpExecutor::builder()
+            .with_http_server(McpHttpServer::new("docs", url.as_str()))
+            .build_initialized()
+            .await
+            .unwrap();
+        assert!(executor.available_tools().is_empty());
+    }
+}
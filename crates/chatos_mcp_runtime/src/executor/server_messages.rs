@@ -0,0 +1,502 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::rpc::McpServerMessageHandler;
+use crate::types::{
+    McpElicitationRequest, ToolCallContext, ToolLifecycleEvent, ToolServerNotice,
+    ToolStreamChunkCallback,
+};
+
+use super::McpExecutor;
+
+/// `event` of the stream chunks that carry server notices to the chat UI.
+pub const MCP_TOOL_NOTICE_STREAM_EVENT: &str = "tool_notice";
+
+/// Routes what a server sends while one tool call runs: notices go to the
+/// lifecycle hook and the tool's output stream, sampling and elicitation
+/// requests to the executor's handlers.
+pub(in crate::executor) struct ToolCallMessageHandler<'a> {
+    pub(in crate::executor) executor: &'a McpExecutor,
+    pub(in crate::executor) event: &'a ToolLifecycleEvent,
+    pub(in crate::executor) context: &'a ToolCallContext,
+    pub(in crate::executor) on_stream_chunk: Option<&'a ToolStreamChunkCallback>,
+}
+
+#[async_trait]
+impl McpServerMessageHandler for ToolCallMessageHandler<'_> {
+    async fn notification(&self, method: &str, params: Value) {
+        let Some(notice) = parse_server_notice(method, &params) else {
+            debug!(
+                server_name = self.event.server_name.as_str(),
+                method, "ignoring MCP server notification"
+            );
+            return;
+        };
+        if let Some(callback) = self.on_stream_chunk {
+            let chunk = json!({
+                "event": MCP_TOOL_NOTICE_STREAM_EVENT,
+                "data": {
+                    "tool_name": self.event.tool_name,
+                    "server_name": self.event.server_name,
+                    "notice": notice,
+                }
+            });
+            callback(chunk.to_string());
+        }
+        if let Some(hook) = self.executor.tool_lifecycle_hook.as_ref() {
+            let mut event = self.event.clone();
+            event.notice = Some(notice);
+            hook.tool_notice(&event).await;
+        }
+    }
+
+    async fn request(&self, method: &str, params: Value) -> Option<Result<Value, String>> {
+        match method {
+            "ping" => Some(Ok(json!({}))),
+            "sampling/createMessage" => Some(self.create_message(params).await),
+            "elicitation/create" => Some(self.elicit(params).await),
+            _ => None,
+        }
+    }
+}
+
+impl ToolCallMessageHandler<'_> {
+    async fn create_message(&self, params: Value) -> Result<Value, String> {
+        let handler = self
+            .executor
+            .sampling_handler
+            .as_ref()
+            .ok_or("MCP sampling is not enabled for this client")?;
+        let runtime = self
+            .context
+            .caller_model_runtime
+            .as_ref()
+            .ok_or("MCP sampling needs the calling turn's model, which is not available")?;
+        self.approve_sampling(&params).await?;
+        handler.create_message(runtime, params).await
+    }
+
+    /// Asks the user through the elicitation handler before a server may
+    /// spend the caller's model; without one, sampling is refused.
+    async fn approve_sampling(&self, params: &Value) -> Result<(), String> {
+        let handler =
+            self.executor.elicitation_handler.as_ref().ok_or(
+                "MCP sampling needs user approval, which is not available for this client",
+            )?;
+        let answer = handler
+            .elicit(
+                McpElicitationRequest {
+                    server_name: self.event.server_name.clone(),
+                    tool_name: self.event.tool_name.clone(),
+                    conversation_id: self.context.conversation_id.clone(),
+                    conversation_turn_id: self.context.conversation_turn_id.clone(),
+                    message: sampling_approval_message(self.event.server_name.as_str(), params),
+                    requested_schema: json!({
+                        "type": "object",
+                        "properties": {
+                            SAMPLING_APPROVAL_FIELD: {
+                                "type": "boolean",
+                                "title": "允许",
+                                "description": "是 / 否",
+                            }
+                        },
+                        "required": [SAMPLING_APPROVAL_FIELD],
+                    }),
+                },
+                self.on_stream_chunk.cloned(),
+            )
+            .await?;
+        let approved = answer.get("action").and_then(Value::as_str) == Some("accept")
+            && answer
+                .pointer(&format!("/content/{SAMPLING_APPROVAL_FIELD}"))
+                .and_then(Value::as_bool)
+                == Some(true);
+        if !approved {
+            return Err("The user did not approve this MCP sampling request".to_string());
+        }
+        Ok(())
+    }
+
+    async fn elicit(&self, params: Value) -> Result<Value, String> {
+        let handler = self
+            .executor
+            .elicitation_handler
+            .as_ref()
+            .ok_or("MCP elicitation is not enabled for this client")?;
+        handler
+            .elicit(
+                McpElicitationRequest {
+                    server_name: self.event.server_name.clone(),
+                    tool_name: self.event.tool_name.clone(),
+                    conversation_id: self.context.conversation_id.clone(),
+                    conversation_turn_id: self.context.conversation_turn_id.clone(),
+                    message: params
+                        .get("message")
+                        .and_then(Value::as_str)
+                        .unwrap_or_default()
+                        .to_string(),
+                    requested_schema: params
+                        .get("requestedSchema")
+                        .cloned()
+                        .unwrap_or_else(|| json!({"type": "object", "properties": {}})),
+                },
+                self.on_stream_chunk.cloned(),
+            )
+            .await
+    }
+}
+
+const SAMPLING_APPROVAL_FIELD: &str = "allow";
+const SAMPLING_APPROVAL_PREVIEW_CHARS: usize = 500;
+
+fn sampling_approval_message(server_name: &str, params: &Value) -> String {
+    let max_tokens = params
+        .get("maxTokens")
+        .and_then(Value::as_i64)
+        .map(|tokens| format!("，最多 {tokens} tokens"))
+        .unwrap_or_default();
+    let mut message = format!("{server_name} 请求使用当前模型生成回复{max_tokens}。是否允许？");
+    let last_text = params
+        .get("messages")
+        .and_then(Value::as_array)
+        .and_then(|messages| messages.last())
+        .and_then(|message| message.pointer("/content/text"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|text| !text.is_empty());
+    if let Some(text) = last_text {
+        let preview = text
+            .chars()
+            .take(SAMPLING_APPROVAL_PREVIEW_CHARS)
+            .collect::<String>();
+        let ellipsis = if preview.len() < text.len() {
+            "…"
+        } else {
+            ""
+        };
+        message.push_str(&format!("\n\n{preview}{ellipsis}"));
+    }
+    message
+}
+
+fn parse_server_notice(method: &str, params: &Value) -> Option<ToolServerNotice> {
+    match method {
+        "notifications/progress" => Some(ToolServerNotice::Progress {
+            progress: params.get("progress").and_then(Value::as_f64)?,
+            total: params.get("total").and_then(Value::as_f64),
+            message: params
+                .get("message")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned),
+        }),
+        "notifications/message" => Some(ToolServerNotice::Log {
+            level: params
+                .get("level")
+                .and_then(Value::as_str)
+                .unwrap_or("info")
+                .to_string(),
+            logger: params
+                .get("logger")
+                .and_then(Value::as_str)
+                .map(ToOwned::to_owned),
+            data: params.get("data").cloned().unwrap_or(Value::Null),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+
+    use crate::types::{
+        McpElicitationHandler, McpElicitationRequest, McpSamplingHandler, ToolCallerModelRuntime,
+        ToolLifecycleEvent, ToolLifecycleHook, ToolResult, ToolServerNotice,
+        ToolStreamChunkCallback,
+    };
+    use crate::{McpExecutor, McpHttpServer, ToolCallContext};
+
+    #[derive(Clone, Default)]
+    struct Server {
+        answers: Arc<Mutex<Vec<Value>>>,
+    }
+
+    async fn mcp(
+        axum::extract::State(server): axum::extract::State<Server>,
+        axum::Json(request): axum::Json<Value>,
+    ) -> axum::response::Response {
+        use axum::response::IntoResponse;
+
+        let Some(method) = request["method"].as_str() else {
+            server.answers.lock().unwrap().push(request);
+            return axum::http::StatusCode::ACCEPTED.into_response();
+        };
+        if method != "tools/call" {
+            return axum::Json(json!({
+                "jsonrpc": "2.0",
+                "id": request["id"],
+                "result": {"tools": [{"name": "deploy", "inputSchema": {"type": "object"}}]}
+            }))
+            .into_response();
+        }
+        let token = request["params"]["_meta"]["progressToken"].clone();
+        let messages = [
+            json!({"jsonrpc": "2.0", "method": "notifications/progress", "params": {
+                "progressToken": token, "progress": 1, "total": 4, "message": "building"
+            }}),
+            json!({"jsonrpc": "2.0", "method": "notifications/message", "params": {
+                "level": "warning", "logger": "deployer", "data": "slow mirror"
+            }}),
+            json!({"jsonrpc": "2.0", "id": "srv-1", "method": "elicitation/create", "params": {
+                "message": "Which branch?",
+                "requestedSchema": {"type": "object", "properties": {"branch": {"type": "string"}}}
+            }}),
+            json!({"jsonrpc": "2.0", "id": "srv-2", "method": "sampling/createMessage", "params": {
+                "messages": []
+            }}),
+            json!({"jsonrpc": "2.0", "id": request["id"], "result": {
+                "content": [{"type": "text", "text": "deployed"}]
+            }}),
+        ];
+        let body = messages
+            .iter()
+            .map(|message| format!("event: message\ndata: {message}\n\n"))
+            .collect::<String>();
+        axum::response::Response::builder()
+            .header("content-type", "text/event-stream")
+            .body(axum::body::Body::from(body))
+            .unwrap()
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingHooks {
+        notices: Mutex<Vec<ToolServerNotice>>,
+        elicitations: Mutex<Vec<McpElicitationRequest>>,
+    }
+
+    #[async_trait]
+    impl ToolLifecycleHook for RecordingHooks {
+        async fn before_tool_use(&self, _event: &ToolLifecycleEvent) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn after_tool_use(&self, _event: &ToolLifecycleEvent) -> Result<(), String> {
+            Ok(())
+        }
+
+        async fn tool_notice(&self, event: &ToolLifecycleEvent) {
+            self.notices.lock().unwrap().extend(event.notice.clone());
+        }
+    }
+
+    #[async_trait]
+    impl McpElicitationHandler for RecordingHooks {
+        async fn elicit(
+            &self,
+            request: McpElicitationRequest,
+            _on_stream_chunk: Option<ToolStreamChunkCallback>,
+        ) -> Result<Value, String> {
+            self.elicitations.lock().unwrap().push(request);
+            Ok(json!({"action": "accept", "content": {"branch": "main"}}))
+        }
+    }
+
+    #[tokio::test]
+    async fn server_notices_and_requests_are_routed_during_a_tool_call() {
+        let server = Server::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = axum::Router::new()
+            .route("/mcp", axum::routing::post(mcp))
+            .with_state(server.clone());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let hooks = Arc::new(RecordingHooks::default());
+        let mut executor = McpExecutor::builder()
+            .with_http_server(McpHttpServer::new("deployer", format!("http://{addr}/mcp")))
+            .with_tool_lifecycle_hook(hooks.clone())
+            .with_elicitation_handler(hooks.clone())
+            .build();
+        executor.init().await.unwrap();
+        let tool_name = executor.available_tools()[0]["name"]
+            .as_str()
+            .unwrap()
+            .to_string();
+
+        let streamed = Arc::new(Mutex::new(Vec::new()));
+        let sink = streamed.clone();
+        let results = executor
+            .execute_tools_stream(
+                &[json!({
+                    "id": "call-1",
+                    "type": "function",
+                    "function": {"name": tool_name, "arguments": "{}"}
+                })],
+                ToolCallContext::new(Some("conv-1".to_string()), None, None),
+                Some(Arc::new(move |result: &ToolResult| {
+                    if result.is_stream {
+                        sink.lock().unwrap().push(result.content.clone());
+                    }
+                })),
+            )
+            .await;
+        let result = results.iter().find(|result| !result.is_stream).unwrap();
+        assert!(result.success, "{}", result.content);
+        assert!(result.content.contains("deployed"));
+
+        assert_eq!(
+            hooks.notices.lock().unwrap().clone(),
+            vec![
+                ToolServerNotice::Progress {
+                    progress: 1.0,
+                    total: Some(4.0),
+                    message: Some("building".to_string()),
+                },
+                ToolServerNotice::Log {
+                    level: "warning".to_string(),
+                    logger: Some("deployer".to_string()),
+                    data: json!("slow mirror"),
+                },
+            ]
+        );
+        let streamed = streamed.lock().unwrap().clone();
+        assert_eq!(streamed.len(), 2);
+        let chunk: Value = serde_json::from_str(streamed[0].as_str()).unwrap();
+        assert_eq!(chunk["event"], super::MCP_TOOL_NOTICE_STREAM_EVENT);
+        assert_eq!(chunk["data"]["notice"]["kind"], "progress");
+
+        let elicitations = hooks.elicitations.lock().unwrap().clone();
+        assert_eq!(elicitations.len(), 1);
+        assert_eq!(elicitations[0].server_name, "deployer");
+        assert_eq!(elicitations[0].conversation_id.as_deref(), Some("conv-1"));
+        assert_eq!(elicitations[0].message, "Which branch?");
+
+        let answers = server.answers.lock().unwrap().clone();
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[0]["id"], "srv-1");
+        assert_eq!(answers[0]["result"]["content"]["branch"], "main");
+        assert_eq!(answers[1]["id"], "srv-2");
+        assert!(answers[1]["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("sampling is not enabled"));
+    }
+
+    #[derive(Debug)]
+    struct SamplingHooks {
+        allow: bool,
+        approvals: Mutex<Vec<McpElicitationRequest>>,
+        samples: Mutex<Vec<Value>>,
+    }
+
+    #[async_trait]
+    impl McpElicitationHandler for SamplingHooks {
+        async fn elicit(
+            &self,
+            request: McpElicitationRequest,
+            _on_stream_chunk: Option<ToolStreamChunkCallback>,
+        ) -> Result<Value, String> {
+            if request.requested_schema["properties"]
+                .get("allow")
+                .is_none()
+            {
+                return Ok(json!({"action": "accept", "content": {"branch": "main"}}));
+            }
+            self.approvals.lock().unwrap().push(request);
+            Ok(json!({"action": "accept", "content": {"allow": self.allow}}))
+        }
+    }
+
+    #[async_trait]
+    impl McpSamplingHandler for SamplingHooks {
+        async fn create_message(
+            &self,
+            _caller_model_runtime: &ToolCallerModelRuntime,
+            params: Value,
+        ) -> Result<Value, String> {
+            self.samples.lock().unwrap().push(params);
+            Ok(json!({
+                "role": "assistant",
+                "content": {"type": "text", "text": "ok"},
+                "model": "caller-model",
+                "stopReason": "endTurn",
+            }))
+        }
+    }
+
+    #[tokio::test]
+    async fn sampling_requests_run_only_after_the_user_approves_them() {
+        for allow in [true, false] {
+            let server = Server::default();
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = listener.local_addr().unwrap();
+            let app = axum::Router::new()
+                .route("/mcp", axum::routing::post(mcp))
+                .with_state(server.clone());
+            tokio::spawn(async move {
+                axum::serve(listener, app).await.unwrap();
+            });
+
+            let hooks = Arc::new(SamplingHooks {
+                allow,
+                approvals: Mutex::default(),
+                samples: Mutex::default(),
+            });
+            let mut executor = McpExecutor::builder()
+                .with_http_server(McpHttpServer::new("deployer", format!("http://{addr}/mcp")))
+                .with_sampling_handler(hooks.clone())
+                .with_elicitation_handler(hooks.clone())
+                .build();
+            executor.init().await.unwrap();
+            let tool_name = executor.available_tools()[0]["name"]
+                .as_str()
+                .unwrap()
+                .to_string();
+            let context = ToolCallContext::new(Some("conv-1".to_string()), None, None)
+                .with_caller_model_runtime(Some(ToolCallerModelRuntime::openai_compatible(
+                    "http://127.0.0.1:9/v1",
+                    "key",
+                    "caller-model",
+                    "openai",
+                )));
+            let results = executor
+                .execute_tools_stream(
+                    &[json!({
+                        "id": "call-1",
+                        "type": "function",
+                        "function": {"name": tool_name, "arguments": "{}"}
+                    })],
+                    context,
+                    None,
+                )
+                .await;
+            assert!(results.iter().any(|result| result.success));
+
+            let approvals = hooks.approvals.lock().unwrap().clone();
+            assert_eq!(approvals.len(), 1);
+            assert_eq!(approvals[0].server_name, "deployer");
+            assert!(approvals[0].message.contains("deployer"));
+            let answers = server.answers.lock().unwrap().clone();
+            assert_eq!(answers[1]["id"], "srv-2");
+            if allow {
+                assert_eq!(hooks.samples.lock().unwrap().len(), 1);
+                assert_eq!(answers[1]["result"]["content"]["text"], "ok");
+            } else {
+                assert!(hooks.samples.lock().unwrap().is_empty());
+                assert!(answers[1]["error"]["message"]
+                    .as_str()
+                    .unwrap()
+                    .contains("did not approve"));
+            }
+        }
+    }
+}
@@ -47,14 +47,18 @@ pub use builtin_prompt::{
     BuiltinMcpPromptLocale,
 };
 pub use execution::{execute_tool_calls_parallel, execute_tool_calls_stream};
-pub use executor::McpExecutor;
+pub use executor::{
+    mcp_prompt_input_items, mcp_resource_context_text, McpExecutor, MCP_LIST_RESOURCES_TOOL_NAME,
+    MCP_READ_RESOURCE_TOOL_NAME, MCP_TOOL_NOTICE_STREAM_EVENT,
+};
 pub use naming::{canonical_name_segment, canonical_prefixed_tool_name, legacy_prefixed_tool_name};
 pub use registry::{BuiltinToolProvider, BuiltinToolRegistry};
 pub use rpc::{
-    extract_tools, invalidate_stdio_session, jsonrpc_http_call, jsonrpc_http_call_with_client,
-    jsonrpc_http_tool_call_cancellable, jsonrpc_http_tool_call_cancellable_with_client,
-    jsonrpc_stdio_call, jsonrpc_stdio_call_with_timeout, list_tools_http,
-    list_tools_http_with_client, list_tools_stdio,
+    close_http_session, extract_tools, invalidate_stdio_session, jsonrpc_http_call,
+    jsonrpc_http_call_with_client, jsonrpc_http_tool_call_cancellable,
+    jsonrpc_http_tool_call_cancellable_with_client, jsonrpc_stdio_call,
+    jsonrpc_stdio_call_with_timeout, list_tools_http, list_tools_http_with_client,
+    list_tools_stdio,
 };
 pub use schema::{build_function_tool_schema, parse_mcp_tool_definition, parse_tool_definition};
 pub use stdio_policy::{
@@ -68,9 +72,10 @@ pub use text::{
     to_text_and_structured_result_with_transient_limit,
 };
 pub use types::{
-    McpAsyncResultTransport, McpBuiltinServer, McpHttpHeaderProvider, McpHttpServer,
-    McpStdioServer, McpToolNameAlias, ParsedToolDefinition, ToolAbortCheckCallback,
-    ToolCallContext, ToolCallError, ToolCallerModelRuntime, ToolInfo, ToolLifecycleEvent,
-    ToolLifecycleHook, ToolLifecycleOutcome, ToolResult, ToolResultCallback,
+    McpAsyncResultTransport, McpBuiltinServer, McpElicitationHandler, McpElicitationRequest,
+    McpHttpHeaderProvider, McpHttpServer, McpSamplingHandler, McpStdioServer, McpToolNameAlias,
+    ParsedToolDefinition, PreToolUseDecision, ToolAbortCheckCallback, ToolCallContext,
+    ToolCallError, ToolCallerModelRuntime, ToolInfo, ToolLifecycleEvent, ToolLifecycleHook,
+    ToolLifecycleOutcome, ToolResult, ToolResultCallback, ToolServerNotice,
     ToolStreamChunkCallback, TransientToolModelInput,
 };
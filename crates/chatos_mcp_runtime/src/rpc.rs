@@ -21,10 +21,21 @@ static MCP_HTTP_CLIENT: OnceLock<Result<reqwest::Client, String>> = OnceLock::ne
 static MCP_TOOLS_LIST_CACHE: OnceLock<Mutex<HashMap<String, ToolsListCacheEntry>>> =
     OnceLock::new();
 mod internal_headers;
+mod server_messages;
 mod stdio;
+mod streamable_http;
 
 pub use internal_headers::{headers_require_per_request_signing, prepare_http_headers};
+pub(crate) use server_messages::McpServerMessageHandler;
+pub use streamable_http::close_http_session;
+use streamable_http::{
+    cached_http_session, forget_http_session, http_session_cache_key, initialize_http_session,
+    mcp_http_request, send_jsonrpc_http_request, HttpRpcError,
+};
+#[cfg(test)]
+use streamable_http::{SseDecoder, SseEvent};
 
+pub(crate) use stdio::jsonrpc_stdio_call_with_handler;
 #[cfg(test)]
 use stdio::{ensure_stdio_response_line_within_limit, stdio_session_cache_key};
 pub use stdio::{invalidate_stdio_session, jsonrpc_stdio_call, jsonrpc_stdio_call_with_timeout};
@@ -109,7 +120,17 @@ pub async fn jsonrpc_http_call_with_client(
     client: Option<&reqwest::Client>,
 ) -> Result<Value, String> {
     let id = Uuid::new_v4().to_string();
-    jsonrpc_http_call_with_id(url, headers, method, params, timeout, id.as_str(), client).await
+    jsonrpc_http_call_with_id(
+        url,
+        headers,
+        method,
+        params,
+        timeout,
+        id.as_str(),
+        client,
+        None,
+    )
+    .await
 }
 
 pub async fn jsonrpc_http_tool_call_cancellable(
@@ -137,6 +158,29 @@ pub async fn jsonrpc_http_tool_call_cancellable_with_client(
     timeout: Option<Duration>,
     async_result_transport: McpAsyncResultTransport,
     client: Option<&reqwest::Client>,
+) -> Result<Value, String> {
+    jsonrpc_http_tool_call_with_handler(
+        url,
+        headers,
+        params,
+        timeout,
+        async_result_transport,
+        client,
+        None,
+    )
+    .await
+}
+
+/// Like [`jsonrpc_http_tool_call_cancellable_with_client`], handing progress,
+/// log notifications and server requests on the response stream to `handler`.
+pub(crate) async fn jsonrpc_http_tool_call_with_handler(
+    url: &str,
+    headers: Option<&HashMap<String, String>>,
+    params: Value,
+    timeout: Option<Duration>,
+    async_result_transport: McpAsyncResultTransport,
+    client: Option<&reqwest::Client>,
+    handler: Option<&dyn McpServerMessageHandler>,
 ) -> Result<Value, String> {
     let id = Uuid::new_v4().to_string();
     if async_result_transport == McpAsyncResultTransport::RabbitMq {
@@ -155,12 +199,14 @@ pub async fn jsonrpc_http_tool_call_cancellable_with_client(
         Some(request_timeout),
         id.as_str(),
         client,
+        handler,
     )
     .await;
     cancellation_guard.disarm();
     result
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn jsonrpc_http_call_with_id(
     url: &str,
     headers: Option<&HashMap<String, String>>,
@@ -169,6 +215,7 @@ async fn jsonrpc_http_call_with_id(
     timeout: Option<Duration>,
     id: &str,
     client: Option<&reqwest::Client>,
+    handler: Option<&dyn McpServerMessageHandler>,
 ) -> Result<Value, String> {
     let payload = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
     let default_client;
@@ -180,47 +227,50 @@ async fn jsonrpc_http_call_with_id(
         }
     };
     let request_timeout = timeout.unwrap_or(DEFAULT_MCP_RPC_TIMEOUT);
-    let mut request = client.post(url).timeout(request_timeout).json(&payload);
-    if let Some(headers) = headers {
-        for (key, value) in prepare_http_headers(headers)? {
-            request = request.header(key.as_str(), value.as_str());
-        }
-    }
-    let response = request
-        .send()
-        .await
-        .map_err(|err| format_http_send_error(method, url, request_timeout, &err))?;
-
-    let status = response.status();
-    let redirect_location = response
-        .headers()
-        .get(reqwest::header::LOCATION)
-        .and_then(|value| value.to_str().ok())
-        .map(ToOwned::to_owned);
-    if !status.is_success() {
-        let body = read_http_response_body_limited(response, MCP_HTTP_ERROR_BODY_PREVIEW_BYTES)
+    let session_key = http_session_cache_key(url, headers);
+    let session = cached_http_session(session_key.as_str());
+    let sent = send_jsonrpc_http_request(
+        client,
+        url,
+        headers,
+        method,
+        &payload,
+        request_timeout,
+        session_key.as_str(),
+        session.as_ref(),
+        handler,
+    )
+    .await;
+    let value = match sent {
+        Err(HttpRpcError::SessionRejected(message)) => {
+            // The server dropped our session (404) or needs one before it
+            // answers (400). Negotiate a fresh session and retry once.
+            forget_http_session(session_key.as_str());
+            let session = initialize_http_session(
+                client,
+                url,
+                headers,
+                request_timeout,
+                session_key.as_str(),
+            )
             .await
-            .map(|body| String::from_utf8_lossy(body.as_slice()).into_owned())
-            .unwrap_or_else(|err| err);
-        let location_suffix = redirect_location
-            .as_deref()
-            .map(|location| format!("; location={location}"))
-            .unwrap_or_default();
-        return Err(format!(
-            "{method} {url} failed after HTTP response: 外部 MCP 返回 HTTP {status}{location_suffix}; body={}",
-            response_preview(body.as_str())
-        ));
-    }
-    let body = read_http_response_body_limited(response, MCP_HTTP_RESPONSE_LIMIT_BYTES)
-        .await
-        .map_err(|err| format!("{method} {url} failed after HTTP response: {err}"))?;
-    let value: Value = serde_json::from_slice(body.as_slice()).map_err(|err| {
-        let body_text = String::from_utf8_lossy(body.as_slice());
-        format!(
-            "{method} {url} failed after HTTP response: 外部 MCP 返回的不是 JSON: {err}; body={}",
-            response_preview(body_text.as_ref())
-        )
-    })?;
+            .map_err(|err| format!("{message}; 重新初始化 MCP 会话失败: {err}"))?;
+            send_jsonrpc_http_request(
+                client,
+                url,
+                headers,
+                method,
+                &payload,
+                request_timeout,
+                session_key.as_str(),
+                session.as_ref(),
+                handler,
+            )
+            .await
+            .map_err(HttpRpcError::into_message)?
+        }
+        sent => sent.map_err(HttpRpcError::into_message)?,
+    };
     if value.get("error").is_some() {
         return Err(format!(
             "{method} {url} returned JSON-RPC error: {}",
@@ -320,16 +370,15 @@ async fn send_http_cancel_notification(
             &default_client
         }
     };
-    let mut request = client.post(url).timeout(timeout).json(&payload);
-    if let Some(headers) = headers {
-        for (key, value) in prepare_http_headers(headers)? {
-            request = request.header(key.as_str(), value.as_str());
-        }
-    }
-    let response = request
-        .send()
-        .await
-        .map_err(|error| format!("send MCP cancellation notification failed: {error}"))?;
+    let session = cached_http_session(http_session_cache_key(url, headers).as_str());
+    let response = mcp_http_request(
+        client.post(url).timeout(timeout).json(&payload),
+        headers,
+        session.as_ref(),
+    )?
+    .send()
+    .await
+    .map_err(|error| format!("send MCP cancellation notification failed: {error}"))?;
     if !response.status().is_success() {
         return Err(format!(
             "MCP cancellation notification returned HTTP {}",
@@ -0,0 +1,55 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+const JSONRPC_METHOD_NOT_FOUND: i64 = -32601;
+const JSONRPC_INTERNAL_ERROR: i64 = -32603;
+
+/// Receives the notifications and requests an MCP server sends while one of
+/// our requests is in flight, on an SSE response stream or on stdout.
+#[async_trait]
+pub(crate) trait McpServerMessageHandler: Send + Sync {
+    async fn notification(&self, method: &str, params: Value);
+
+    /// Returns `None` for methods the client does not implement.
+    async fn request(&self, method: &str, params: Value) -> Option<Result<Value, String>>;
+}
+
+/// Hands a server-initiated message to `handler` and returns the JSON-RPC
+/// response to send back when the message is a request. Requests are
+/// answered even without a handler so the server does not wait forever.
+pub(super) async fn dispatch_server_message(
+    handler: Option<&dyn McpServerMessageHandler>,
+    message: &Value,
+) -> Option<Value> {
+    let method = message.get("method").and_then(Value::as_str)?;
+    let params = message.get("params").cloned().unwrap_or(Value::Null);
+    let Some(id) = message.get("id").cloned() else {
+        if let Some(handler) = handler {
+            handler.notification(method, params).await;
+        }
+        return None;
+    };
+    let outcome = match handler {
+        Some(handler) => handler.request(method, params).await,
+        None => None,
+    };
+    Some(match outcome {
+        Some(Ok(result)) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Some(Err(message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {"code": JSONRPC_INTERNAL_ERROR, "message": message}
+        }),
+        None => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {
+                "code": JSONRPC_METHOD_NOT_FOUND,
+                "message": format!("client does not support {method}")
+            }
+        }),
+    })
+}
@@ -15,6 +15,7 @@ use uuid::Uuid;
 
 use crate::types::McpStdioServer;
 
+use super::server_messages::{dispatch_server_message, McpServerMessageHandler};
 use super::{tools_list_stdio_cache_key, DEFAULT_MCP_RPC_TIMEOUT};
 const MCP_STDIO_SESSION_MAX: usize = 32;
 const MCP_STDIO_SESSION_IDLE_TTL: Duration = Duration::from_secs(10 * 60);
@@ -85,10 +86,23 @@ pub async fn jsonrpc_stdio_call_with_timeout(
     _conversation_id: Option<&str>,
     timeout: Duration,
 ) -> Result<Value, String> {
+    jsonrpc_stdio_call_with_handler(cfg, method, params, Some(timeout), None).await
+}
+
+/// Stdio call that hands notifications and requests the server writes before
+/// its response to `handler`.
+pub(crate) async fn jsonrpc_stdio_call_with_handler(
+    cfg: &McpStdioServer,
+    method: &str,
+    params: Value,
+    timeout: Option<Duration>,
+    handler: Option<&dyn McpServerMessageHandler>,
+) -> Result<Value, String> {
+    let timeout = timeout.unwrap_or(DEFAULT_MCP_RPC_TIMEOUT);
     let session_key = stdio_session_cache_key(cfg);
     tokio::time::timeout(
         timeout,
-        jsonrpc_stdio_call_with_session(cfg, session_key.clone(), method, params),
+        jsonrpc_stdio_call_with_session(cfg, session_key.clone(), method, params, handler),
     )
     .await
     .map_err(|_| {
@@ -106,13 +120,14 @@ async fn jsonrpc_stdio_call_with_session(
     session_key: String,
     method: &str,
     params: Value,
+    handler: Option<&dyn McpServerMessageHandler>,
 ) -> Result<Value, String> {
     let id = Uuid::new_v4().to_string();
     let payload = json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
 
     let session = get_stdio_session(cfg, session_key.as_str()).await?;
     let mut guard = session.lock().await;
-    let result = guard.send_request(id.as_str(), &payload).await;
+    let result = guard.send_request(id.as_str(), &payload, handler).await;
     if result.is_err() || guard.is_finished().await {
         drop(guard);
         remove_stdio_session(session_key.as_str());
@@ -353,13 +368,13 @@ fn terminate_stdio_process_tree(child: &mut Child) {
 }
 
 impl StdioRpcSession {
-    async fn send_request(&mut self, id: &str, payload: &Value) -> Result<Value, String> {
-        let data = payload.to_string() + "\n";
-        self.stdin
-            .write_all(data.as_bytes())
-            .await
-            .map_err(|err| err.to_string())?;
-        self.stdin.flush().await.map_err(|err| err.to_string())?;
+    async fn send_request(
+        &mut self,
+        id: &str,
+        payload: &Value,
+        handler: Option<&dyn McpServerMessageHandler>,
+    ) -> Result<Value, String> {
+        self.write_message(payload).await?;
 
         loop {
             match read_stdio_response_line_limited(
@@ -373,12 +388,17 @@ impl StdioRpcSession {
                         continue;
                     }
                     if let Ok(value) = serde_json::from_str::<Value>(&line) {
-                        if value.get("id").and_then(Value::as_str) == Some(id) {
+                        if value.get("id").and_then(Value::as_str) == Some(id)
+                            && value.get("method").is_none()
+                        {
                             if value.get("error").is_some() {
                                 return Err(value.to_string());
                             }
                             return Ok(value.get("result").cloned().unwrap_or(value));
                         }
+                        if let Some(answer) = dispatch_server_message(handler, &value).await {
+                            self.write_message(&answer).await?;
+                        }
                     }
                 }
                 Ok(None) => break,
@@ -389,6 +409,15 @@ impl StdioRpcSession {
         Err("no response from stdio server".to_string())
     }
 
+    async fn write_message(&mut self, message: &Value) -> Result<(), String> {
+        let data = message.to_string() + "\n";
+        self.stdin
+            .write_all(data.as_bytes())
+            .await
+            .map_err(|err| err.to_string())?;
+        self.stdin.flush().await.map_err(|err| err.to_string())
+    }
+
     async fn is_finished(&mut self) -> bool {
         matches!(self.child.try_wait(), Ok(Some(_)))
     }
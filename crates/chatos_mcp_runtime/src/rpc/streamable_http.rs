@@ -0,0 +1,566 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use tracing::{debug, info};
+use uuid::Uuid;
+
+use super::server_messages::{dispatch_server_message, McpServerMessageHandler};
+use super::{
+    ensure_http_response_body_within_limit, format_http_send_error, prepare_http_headers,
+    read_http_response_body_limited, response_preview, tools_list_http_cache_key,
+    MCP_HTTP_ERROR_BODY_PREVIEW_BYTES, MCP_HTTP_RESPONSE_LIMIT_BYTES,
+};
+
+const MCP_SESSION_ID_HEADER: &str = "mcp-session-id";
+const MCP_PROTOCOL_VERSION_HEADER: &str = "mcp-protocol-version";
+const MCP_LAST_EVENT_ID_HEADER: &str = "last-event-id";
+const MCP_STREAMABLE_HTTP_ACCEPT: &str = "application/json, text/event-stream";
+const MCP_SSE_CONTENT_TYPE: &str = "text/event-stream";
+pub(super) const MCP_STREAMABLE_HTTP_PROTOCOL_VERSION: &str = "2025-06-18";
+const MCP_SSE_MAX_RESUME_ATTEMPTS: usize = 3;
+static MCP_HTTP_SESSIONS: OnceLock<Mutex<HashMap<String, McpHttpSession>>> = OnceLock::new();
+
+/// Session negotiated with a Streamable HTTP MCP server. Sessions are keyed
+/// by URL and header set, so callers with different credentials never share
+/// one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct McpHttpSession {
+    pub(super) session_id: String,
+    pub(super) protocol_version: String,
+}
+
+pub(super) enum HttpRpcError {
+    /// The server does not know the session we sent, or requires one we did
+    /// not send. The caller re-initializes and retries once.
+    SessionRejected(String),
+    Failed(String),
+}
+
+impl HttpRpcError {
+    pub(super) fn into_message(self) -> String {
+        match self {
+            Self::SessionRejected(message) | Self::Failed(message) => message,
+        }
+    }
+}
+
+pub(super) fn http_session_cache_key(
+    url: &str,
+    headers: Option<&HashMap<String, String>>,
+) -> String {
+    tools_list_http_cache_key(url, headers, None)
+}
+
+pub(super) fn cached_http_session(session_key: &str) -> Option<McpHttpSession> {
+    http_sessions().lock().ok()?.get(session_key).cloned()
+}
+
+fn store_http_session(session_key: &str, session: McpHttpSession) {
+    if let Ok(mut sessions) = http_sessions().lock() {
+        sessions.insert(session_key.to_string(), session);
+    }
+}
+
+pub(super) fn forget_http_session(session_key: &str) -> Option<McpHttpSession> {
+    http_sessions().lock().ok()?.remove(session_key)
+}
+
+fn http_sessions() -> &'static Mutex<HashMap<String, McpHttpSession>> {
+    MCP_HTTP_SESSIONS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub(super) fn mcp_http_request(
+    request: reqwest::RequestBuilder,
+    headers: Option<&HashMap<String, String>>,
+    session: Option<&McpHttpSession>,
+) -> Result<reqwest::RequestBuilder, String> {
+    let mut request = request.header(reqwest::header::ACCEPT, MCP_STREAMABLE_HTTP_ACCEPT);
+    if let Some(headers) = headers {
+        for (key, value) in prepare_http_headers(headers)? {
+            request = request.header(key.as_str(), value.as_str());
+        }
+    }
+    if let Some(session) = session {
+        request = request
+            .header(MCP_SESSION_ID_HEADER, session.session_id.as_str())
+            .header(
+                MCP_PROTOCOL_VERSION_HEADER,
+                session.protocol_version.as_str(),
+            );
+    }
+    Ok(request)
+}
+
+/// Posts one JSON-RPC request and returns the JSON-RPC response message,
+/// whether the server answered with a JSON body or an SSE stream. A session
+/// id the server assigns on the way is remembered for later calls.
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn send_jsonrpc_http_request(
+    client: &reqwest::Client,
+    url: &str,
+    headers: Option<&HashMap<String, String>>,
+    method: &str,
+    payload: &Value,
+    timeout: Duration,
+    session_key: &str,
+    session: Option<&McpHttpSession>,
+    handler: Option<&dyn McpServerMessageHandler>,
+) -> Result<Value, HttpRpcError> {
+    let request = mcp_http_request(
+        client.post(url).timeout(timeout).json(payload),
+        headers,
+        session,
+    )
+    .map_err(HttpRpcError::Failed)?;
+    let response = request
+        .send()
+        .await
+        .map_err(|err| HttpRpcError::Failed(format_http_send_error(method, url, timeout, &err)))?;
+
+    let status = response.status();
+    if !status.is_success() {
+        let redirect_location = header_text(response.headers(), reqwest::header::LOCATION.as_str());
+        let body = read_http_response_body_limited(response, MCP_HTTP_ERROR_BODY_PREVIEW_BYTES)
+            .await
+            .map(|body| String::from_utf8_lossy(body.as_slice()).into_owned())
+            .unwrap_or_else(|err| err);
+        let location_suffix = redirect_location
+            .as_deref()
+            .map(|location| format!("; location={location}"))
+            .unwrap_or_default();
+        let message = format!(
+            "{method} {url} failed after HTTP response: 外部 MCP 返回 HTTP {status}{location_suffix}; body={}",
+            response_preview(body.as_str())
+        );
+        return Err(
+            if session_was_rejected(status, session.is_some(), body.as_str()) {
+                HttpRpcError::SessionRejected(message)
+            } else {
+                HttpRpcError::Failed(message)
+            },
+        );
+    }
+    if let Some(session_id) = header_text(response.headers(), MCP_SESSION_ID_HEADER) {
+        if session.map(|session| session.session_id.as_str()) != Some(session_id.as_str()) {
+            let protocol_version = session
+                .map(|session| session.protocol_version.clone())
+                .unwrap_or_else(|| MCP_STREAMABLE_HTTP_PROTOCOL_VERSION.to_string());
+            store_http_session(
+                session_key,
+                McpHttpSession {
+                    session_id,
+                    protocol_version,
+                },
+            );
+        }
+    }
+    read_jsonrpc_http_response(
+        client, url, headers, method, payload, timeout, session, response, handler,
+    )
+    .await
+    .map_err(HttpRpcError::Failed)
+}
+
+/// Runs the `initialize` handshake and stores the session the server hands
+/// out. Servers that do not use sessions return `Ok(None)`.
+pub(super) async fn initialize_http_session(
+    client: &reqwest::Client,
+    url: &str,
+    headers: Option<&HashMap<String, String>>,
+    timeout: Duration,
+    session_key: &str,
+) -> Result<Option<McpHttpSession>, String> {
+    forget_http_session(session_key);
+    let payload = json!({
+        "jsonrpc": "2.0",
+        "id": Uuid::new_v4().to_string(),
+        "method": "initialize",
+        "params": {
+            "protocolVersion": MCP_STREAMABLE_HTTP_PROTOCOL_VERSION,
+            "capabilities": {"sampling": {}, "elicitation": {}},
+            "clientInfo": {"name": "chatos", "version": env!("CARGO_PKG_VERSION")}
+        }
+    });
+    let response = send_jsonrpc_http_request(
+        client,
+        url,
+        headers,
+        "initialize",
+        &payload,
+        timeout,
+        session_key,
+        None,
+        None,
+    )
+    .await
+    .map_err(HttpRpcError::into_message)?;
+    if response.get("error").is_some() {
+        return Err(format!(
+            "initialize {url} returned JSON-RPC error: {}",
+            response_preview(response.to_string().as_str())
+        ));
+    }
+    let Some(mut session) = cached_http_session(session_key) else {
+        return Ok(None);
+    };
+    if let Some(protocol_version) = response
+        .pointer("/result/protocolVersion")
+        .and_then(Value::as_str)
+        .filter(|value| !value.trim().is_empty())
+    {
+        session.protocol_version = protocol_version.to_string();
+        store_http_session(session_key, session.clone());
+    }
+    let initialized = json!({"jsonrpc": "2.0", "method": "notifications/initialized"});
+    let notified = match mcp_http_request(
+        client.post(url).timeout(timeout).json(&initialized),
+        headers,
+        Some(&session),
+    ) {
+        Ok(request) => request
+            .send()
+            .await
+            .map_err(|err| err.to_string())
+            .and_then(|response| {
+                response
+                    .status()
+                    .is_success()
+                    .then_some(())
+                    .ok_or_else(|| format!("HTTP {}", response.status()))
+            }),
+        Err(err) => Err(err),
+    };
+    if let Err(error) = notified {
+        debug!(
+            url,
+            error = error.as_str(),
+            "MCP initialized notification was not acknowledged"
+        );
+    }
+    info!(
+        url,
+        protocol_version = session.protocol_version.as_str(),
+        "MCP HTTP session initialized"
+    );
+    Ok(Some(session))
+}
+
+/// Ends the cached session for this endpoint, if any. Servers may refuse
+/// client-side termination with 405; the session is forgotten either way.
+pub async fn close_http_session(
+    url: &str,
+    headers: Option<&HashMap<String, String>>,
+    client: Option<&reqwest::Client>,
+) -> Result<(), String> {
+    let Some(session) = forget_http_session(http_session_cache_key(url, headers).as_str()) else {
+        return Ok(());
+    };
+    let default_client;
+    let client = match client {
+        Some(client) => client,
+        None => {
+            default_client = super::mcp_http_client()?;
+            &default_client
+        }
+    };
+    let response = mcp_http_request(
+        client.delete(url).timeout(super::DEFAULT_MCP_RPC_TIMEOUT),
+        headers,
+        Some(&session),
+    )?
+    .send()
+    .await
+    .map_err(|err| format_http_send_error("DELETE", url, super::DEFAULT_MCP_RPC_TIMEOUT, &err))?;
+    let status = response.status();
+    if status.is_success()
+        || status == reqwest::StatusCode::METHOD_NOT_ALLOWED
+        || status == reqwest::StatusCode::NOT_FOUND
+    {
+        return Ok(());
+    }
+    Err(format!("DELETE {url} 关闭 MCP 会话失败: HTTP {status}"))
+}
+
+fn session_was_rejected(status: reqwest::StatusCode, sent_session: bool, body: &str) -> bool {
+    match status {
+        reqwest::StatusCode::NOT_FOUND => sent_session,
+        reqwest::StatusCode::BAD_REQUEST => {
+            !sent_session && body.to_ascii_lowercase().contains("session")
+        }
+        _ => false,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn read_jsonrpc_http_response(
+    client: &reqwest::Client,
+    url: &str,
+    headers: Option<&HashMap<String, String>>,
+    method: &str,
+    payload: &Value,
+    timeout: Duration,
+    session: Option<&McpHttpSession>,
+    response: reqwest::Response,
+    handler: Option<&dyn McpServerMessageHandler>,
+) -> Result<Value, String> {
+    let is_event_stream = header_text(response.headers(), reqwest::header::CONTENT_TYPE.as_str())
+        .is_some_and(|value| {
+            value
+                .trim()
+                .to_ascii_lowercase()
+                .starts_with(MCP_SSE_CONTENT_TYPE)
+        });
+    if !is_event_stream {
+        let body = read_http_response_body_limited(response, MCP_HTTP_RESPONSE_LIMIT_BYTES)
+            .await
+            .map_err(|err| format!("{method} {url} failed after HTTP response: {err}"))?;
+        return serde_json::from_slice(body.as_slice()).map_err(|err| {
+            let body_text = String::from_utf8_lossy(body.as_slice());
+            format!(
+                "{method} {url} failed after HTTP response: 外部 MCP 返回的不是 JSON: {err}; body={}",
+                response_preview(body_text.as_ref())
+            )
+        });
+    }
+
+    let request_id = payload.get("id").cloned().unwrap_or(Value::Null);
+    // The session can change between the POST and a resume GET when another
+    // call re-initializes; resumption must keep using the stream's session.
+    let session = session.cloned().or_else(|| {
+        header_text(response.headers(), MCP_SESSION_ID_HEADER).map(|session_id| McpHttpSession {
+            session_id,
+            protocol_version: MCP_STREAMABLE_HTTP_PROTOCOL_VERSION.to_string(),
+        })
+    });
+    let reply = SseReplyTarget {
+        client,
+        url,
+        headers,
+        session: session.as_ref(),
+        timeout,
+        handler,
+    };
+    let mut last_event_id = None;
+    let mut response = response;
+    let mut resume_attempts = 0;
+    loop {
+        if let Some(message) =
+            read_sse_jsonrpc_response(response, &request_id, &mut last_event_id, &reply)
+                .await
+                .map_err(|err| format!("{method} {url} failed after HTTP response: {err}"))?
+        {
+            return Ok(message);
+        }
+        let Some(event_id) = last_event_id.clone() else {
+            return Err(format!(
+                "{method} {url} failed after HTTP response: 外部 MCP 的 SSE 流在返回 JSON-RPC 响应前结束，且没有可用于恢复的事件 id"
+            ));
+        };
+        if resume_attempts >= MCP_SSE_MAX_RESUME_ATTEMPTS {
+            return Err(format!(
+                "{method} {url} failed after HTTP response: 外部 MCP 的 SSE 流在 {resume_attempts} 次恢复后仍未返回 JSON-RPC 响应"
+            ));
+        }
+        resume_attempts += 1;
+        debug!(
+            url,
+            method,
+            last_event_id = event_id.as_str(),
+            resume_attempts,
+            "resuming MCP SSE stream"
+        );
+        let resumed = mcp_http_request(
+            client
+                .get(url)
+                .timeout(timeout)
+                .header(MCP_LAST_EVENT_ID_HEADER, event_id.as_str()),
+            headers,
+            session.as_ref(),
+        )?
+        .send()
+        .await
+        .map_err(|err| format_http_send_error(method, url, timeout, &err))?;
+        if !resumed.status().is_success() {
+            return Err(format!(
+                "{method} {url} failed after HTTP response: 恢复 SSE 流失败: HTTP {}",
+                resumed.status()
+            ));
+        }
+        response = resumed;
+    }
+}
+
+/// Where answers to server requests received on an SSE stream are posted.
+struct SseReplyTarget<'a> {
+    client: &'a reqwest::Client,
+    url: &'a str,
+    headers: Option<&'a HashMap<String, String>>,
+    session: Option<&'a McpHttpSession>,
+    timeout: Duration,
+    handler: Option<&'a dyn McpServerMessageHandler>,
+}
+
+impl SseReplyTarget<'_> {
+    async fn post(&self, message: &Value) -> Result<(), String> {
+        let response = mcp_http_request(
+            self.client
+                .post(self.url)
+                .timeout(self.timeout)
+                .json(message),
+            self.headers,
+            self.session,
+        )?
+        .send()
+        .await
+        .map_err(|err| err.to_string())?;
+        if response.status().is_success() {
+            Ok(())
+        } else {
+            Err(format!("HTTP {}", response.status()))
+        }
+    }
+}
+
+/// Reads SSE events until the JSON-RPC response for `request_id` arrives.
+/// Returns `Ok(None)` when the stream ends first; `last_event_id` then holds
+/// the id to resume from. Server notifications go to the handler and server
+/// requests are answered with a separate POST.
+async fn read_sse_jsonrpc_response(
+    mut response: reqwest::Response,
+    request_id: &Value,
+    last_event_id: &mut Option<String>,
+    reply: &SseReplyTarget<'_>,
+) -> Result<Option<Value>, String> {
+    let mut decoder = SseDecoder::default();
+    let mut received_bytes = 0usize;
+    loop {
+        let chunk = response.chunk().await.map_err(|err| err.to_string())?;
+        let events = match chunk.as_ref() {
+            Some(chunk) => {
+                received_bytes = received_bytes.saturating_add(chunk.len());
+                ensure_http_response_body_within_limit(
+                    received_bytes,
+                    MCP_HTTP_RESPONSE_LIMIT_BYTES,
+                )?;
+                decoder.push(chunk.as_ref())
+            }
+            None => decoder.finish(),
+        };
+        for event in events {
+            if let Some(id) = event.id {
+                *last_event_id = Some(id);
+            }
+            if event.data.trim().is_empty() {
+                continue;
+            }
+            let message = serde_json::from_str::<Value>(event.data.as_str()).map_err(|err| {
+                format!(
+                    "外部 MCP 的 SSE 事件不是 JSON: {err}; data={}",
+                    response_preview(event.data.as_str())
+                )
+            })?;
+            if is_jsonrpc_response_for(&message, request_id) {
+                return Ok(Some(message));
+            }
+            if let Some(answer) = dispatch_server_message(reply.handler, &message).await {
+                if let Err(error) = reply.post(&answer).await {
+                    debug!(
+                        url = reply.url,
+                        error = error.as_str(),
+                        "failed to answer MCP server request"
+                    );
+                }
+            }
+        }
+        if chunk.is_none() {
+            return Ok(None);
+        }
+    }
+}
+
+fn is_jsonrpc_response_for(message: &Value, request_id: &Value) -> bool {
+    message.get("id") == Some(request_id)
+        && message.get("method").is_none()
+        && (message.get("result").is_some() || message.get("error").is_some())
+}
+
+fn header_text(headers: &reqwest::header::HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(super) struct SseEvent {
+    pub(super) id: Option<String>,
+    pub(super) data: String,
+}
+
+/// Incremental `text/event-stream` decoder. Only the `id` and `data` fields
+/// matter for MCP; `event`, `retry` and comments are ignored.
+#[derive(Default)]
+pub(super) struct SseDecoder {
+    buffer: Vec<u8>,
+    id: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    pub(super) fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buffer.extend_from_slice(chunk);
+        let mut events = Vec::new();
+        while let Some(newline) = self.buffer.iter().position(|byte| *byte == b'\n') {
+            let mut line = self.buffer.drain(..=newline).collect::<Vec<_>>();
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            self.apply_line(
+                String::from_utf8_lossy(line.as_slice()).as_ref(),
+                &mut events,
+            );
+        }
+        events
+    }
+
+    pub(super) fn finish(&mut self) -> Vec<SseEvent> {
+        let mut events = self.push(b"\n");
+        self.dispatch(&mut events);
+        events
+    }
+
+    fn apply_line(&mut self, line: &str, events: &mut Vec<SseEvent>) {
+        if line.is_empty() {
+            self.dispatch(events);
+            return;
+        }
+        if line.starts_with(':') {
+            return;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "data" => self.data.push(value.to_string()),
+            "id" if !value.contains('\0') => self.id = Some(value.to_string()),
+            _ => {}
+        }
+    }
+
+    fn dispatch(&mut self, events: &mut Vec<SseEvent>) {
+        if self.data.is_empty() && self.id.is_none() {
+            return;
+        }
+        events.push(SseEvent {
+            id: self.id.take(),
+            data: std::mem::take(&mut self.data).join("\n"),
+        });
+    }
+}
@@ -2,6 +2,7 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use serde_json::json;
@@ -377,3 +378,225 @@ done
     super::stdio::remove_stdio_session(stdio_session_cache_key(&cfg).as_str());
     let _ = std::fs::remove_file(count_file);
 }
+
+#[test]
+fn sse_decoder_joins_split_chunks_and_multiline_data() {
+    let mut decoder = SseDecoder::default();
+    assert!(decoder.push(b": keep-alive\r\nid: 4\r\nda").is_empty());
+    let events = decoder.push(b"ta: {\"a\":\r\ndata: 1}\r\n\r\nevent: message\ndata: tail");
+    assert_eq!(
+        events,
+        vec![SseEvent {
+            id: Some("4".to_string()),
+            data: "{\"a\":\n1}".to_string(),
+        }]
+    );
+    assert_eq!(
+        decoder.finish(),
+        vec![SseEvent {
+            id: None,
+            data: "tail".to_string(),
+        }]
+    );
+}
+
+/// `(method, session id or Last-Event-ID)` per request the mock received.
+type StreamableHttpLog = Vec<(String, Option<String>)>;
+
+#[derive(Clone, Default)]
+struct StreamableHttpServer {
+    log: Arc<std::sync::Mutex<StreamableHttpLog>>,
+    active_session: Arc<std::sync::Mutex<Option<String>>>,
+    sessions_created: Arc<std::sync::atomic::AtomicUsize>,
+    pending_call_id: Arc<std::sync::Mutex<Option<Value>>>,
+}
+
+fn sse_response(body: String) -> axum::response::Response {
+    axum::response::Response::builder()
+        .header("content-type", "text/event-stream")
+        .body(axum::body::Body::from(body))
+        .unwrap()
+}
+
+fn sse_event(id: &str, message: Value) -> String {
+    format!("event: message\nid: {id}\ndata: {message}\n\n")
+}
+
+async fn streamable_http_post(
+    axum::extract::State(server): axum::extract::State<StreamableHttpServer>,
+    headers: axum::http::HeaderMap,
+    axum::Json(request): axum::Json<Value>,
+) -> axum::response::Response {
+    use axum::response::IntoResponse;
+
+    let method = request
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let session = headers
+        .get("mcp-session-id")
+        .and_then(|value| value.to_str().ok())
+        .map(ToOwned::to_owned);
+    server
+        .log
+        .lock()
+        .unwrap()
+        .push((method.clone(), session.clone()));
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    match method.as_str() {
+        "initialize" => {
+            let created = server
+                .sessions_created
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+                + 1;
+            let session_id = format!("session-{created}");
+            *server.active_session.lock().unwrap() = Some(session_id.clone());
+            return (
+                [("mcp-session-id", session_id)],
+                axum::Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "result": {"protocolVersion": "2025-03-26", "capabilities": {}}
+                })),
+            )
+                .into_response();
+        }
+        "notifications/initialized" => {
+            return axum::http::StatusCode::ACCEPTED.into_response();
+        }
+        _ => {}
+    }
+    let Some(session) = session else {
+        return (
+            axum::http::StatusCode::BAD_REQUEST,
+            "Bad Request: No valid session ID provided",
+        )
+            .into_response();
+    };
+    if server.active_session.lock().unwrap().as_deref() != Some(session.as_str()) {
+        return axum::http::StatusCode::NOT_FOUND.into_response();
+    }
+    let progress = json!({"jsonrpc": "2.0", "method": "notifications/progress", "params": {}});
+    if method == "tools/call" {
+        // The stream drops after a notification; the response only arrives
+        // on the resumed GET stream.
+        *server.pending_call_id.lock().unwrap() = Some(id);
+        return sse_response(sse_event("7", progress));
+    }
+    sse_response(format!(
+        "{}{}",
+        sse_event("1", progress),
+        sse_event(
+            "2",
+            json!({"jsonrpc": "2.0", "id": id, "result": {"tools": [{"name": "demo"}]}})
+        )
+    ))
+}
+
+async fn streamable_http_get(
+    axum::extract::State(server): axum::extract::State<StreamableHttpServer>,
+    headers: axum::http::HeaderMap,
+) -> axum::response::Response {
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .map(ToOwned::to_owned);
+    server
+        .log
+        .lock()
+        .unwrap()
+        .push(("GET".to_string(), last_event_id));
+    let id = server.pending_call_id.lock().unwrap().take().unwrap();
+    sse_response(sse_event(
+        "8",
+        json!({"jsonrpc": "2.0", "id": id, "result": {"content": [{"type": "text", "text": "resumed"}]}}),
+    ))
+}
+
+async fn streamable_http_delete(
+    axum::extract::State(server): axum::extract::State<StreamableHttpServer>,
+    headers: axum::http::HeaderMap,
+) -> axum::http::StatusCode {
+    let session = headers
+        .get("mcp-session-id")
+        .and_then(|value| value.to_str().ok())
+        .map(ToOwned::to_owned);
+    server
+        .log
+        .lock()
+        .unwrap()
+        .push(("DELETE".to_string(), session));
+    axum::http::StatusCode::OK
+}
+
+#[tokio::test]
+async fn streamable_http_negotiates_sessions_reads_sse_and_resumes_streams() {
+    let server = StreamableHttpServer::default();
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let app = axum::Router::new()
+        .route(
+            "/mcp",
+            axum::routing::post(streamable_http_post)
+                .get(streamable_http_get)
+                .delete(streamable_http_delete),
+        )
+        .with_state(server.clone());
+    let handle = tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    let url = format!("http://{address}/mcp");
+
+    let tools = list_tools_http(url.as_str(), None, Some(Duration::from_secs(5)))
+        .await
+        .expect("tools over SSE after session negotiation");
+    assert_eq!(tools, vec![json!({"name": "demo"})]);
+    jsonrpc_http_call(url.as_str(), None, "demo/ping", json!({}), None)
+        .await
+        .expect("reuses the negotiated session");
+
+    *server.active_session.lock().unwrap() = None;
+    let result = jsonrpc_http_tool_call_cancellable(
+        url.as_str(),
+        None,
+        json!({"name": "demo", "arguments": {}}),
+        Some(Duration::from_secs(5)),
+        McpAsyncResultTransport::Disabled,
+    )
+    .await
+    .expect("terminated session is re-initialized and the stream resumed");
+    assert_eq!(
+        result.pointer("/content/0/text").and_then(Value::as_str),
+        Some("resumed")
+    );
+    close_http_session(url.as_str(), None, None)
+        .await
+        .expect("close session");
+    handle.abort();
+
+    let log = server.log.lock().unwrap().clone();
+    let session = |id: &str| Some(id.to_string());
+    assert_eq!(
+        log,
+        vec![
+            ("tools/list".to_string(), None),
+            ("initialize".to_string(), None),
+            (
+                "notifications/initialized".to_string(),
+                session("session-1")
+            ),
+            ("tools/list".to_string(), session("session-1")),
+            ("demo/ping".to_string(), session("session-1")),
+            ("tools/call".to_string(), session("session-1")),
+            ("initialize".to_string(), None),
+            (
+                "notifications/initialized".to_string(),
+                session("session-2")
+            ),
+            ("tools/call".to_string(), session("session-2")),
+            ("GET".to_string(), Some("7".to_string())),
+            ("DELETE".to_string(), session("session-2")),
+        ]
+    );
+}
@@ -571,7 +571,27 @@ pub enum ToolLifecycleOutcome {
     Failed,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// A `notifications/progress` or `notifications/message` an MCP server sent
+/// while one of its tools was running.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ToolServerNotice {
+    Progress {
+        progress: f64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        total: Option<f64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        message: Option<String>,
+    },
+    Log {
+        level: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        logger: Option<String>,
+        data: Value,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct ToolLifecycleEvent {
     pub tool_name: String,
     pub original_name: String,
@@ -580,13 +600,77 @@ pub struct ToolLifecycleEvent {
     pub arguments_sha256: String,
     pub outcome: Option<ToolLifecycleOutcome>,
     pub result_sha256: Option<String>,
+    pub notice: Option<ToolServerNotice>,
+}
+
+/// What a `PreToolUse` Hook decided about one call; the default runs it unchanged.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreToolUseDecision {
+    /// Returned to the model as the tool's error instead of running the tool.
+    pub deny_reason: Option<String>,
+    /// Arguments the tool runs with instead of the model's.
+    pub updated_arguments: Option<Value>,
+    /// Appended to the tool result for the model.
+    pub additional_context: Option<String>,
 }
 
 #[async_trait]
 pub trait ToolLifecycleHook: Debug + Send + Sync {
     async fn before_tool_use(&self, event: &ToolLifecycleEvent) -> Result<(), String>;
 
+    /// Like `before_tool_use`, but sees the arguments and may deny, rewrite, or annotate the
+    /// call. An `Err` still blocks the whole batch.
+    async fn pre_tool_use(
+        &self,
+        event: &ToolLifecycleEvent,
+        _arguments: &Value,
+    ) -> Result<PreToolUseDecision, String> {
+        self.before_tool_use(event)
+            .await
+            .map(|()| PreToolUseDecision::default())
+    }
+
     async fn after_tool_use(&self, event: &ToolLifecycleEvent) -> Result<(), String>;
+
+    /// Called for each server notice between `before_tool_use` and
+    /// `after_tool_use`. `event.notice` is always set.
+    async fn tool_notice(&self, _event: &ToolLifecycleEvent) {}
+}
+
+/// Answers `sampling/createMessage` requests from MCP servers with the model
+/// of the turn that called the tool.
+#[async_trait]
+pub trait McpSamplingHandler: Debug + Send + Sync {
+    /// `params` are the request params as sent by the server; the result must
+    /// be a `CreateMessageResult`.
+    async fn create_message(
+        &self,
+        caller_model_runtime: &ToolCallerModelRuntime,
+        params: Value,
+    ) -> Result<Value, String>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpElicitationRequest {
+    pub server_name: String,
+    pub tool_name: String,
+    pub conversation_id: Option<String>,
+    pub conversation_turn_id: Option<String>,
+    pub message: String,
+    pub requested_schema: Value,
+}
+
+/// Answers `elicitation/create` requests from MCP servers by asking the user.
+#[async_trait]
+pub trait McpElicitationHandler: Debug + Send + Sync {
+    /// Returns an `ElicitResult`: `{"action": "accept" | "decline" | "cancel",
+    /// "content": {...}}`. `on_stream_chunk` is the calling tool's output
+    /// stream, for events that make the chat UI show the prompt.
+    async fn elicit(
+        &self,
+        request: McpElicitationRequest,
+        on_stream_chunk: Option<ToolStreamChunkCallback>,
+    ) -> Result<Value, String>;
 }
 
 #[cfg(test)]
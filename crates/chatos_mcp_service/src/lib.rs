@@ -21,12 +21,16 @@ pub use protocol::{
     JsonRpcResponse, McpToolCallCommand, McpToolCallCommandItem, McpToolCallResult,
     McpToolCallResultItem, McpToolCallResultStatus, MCP_ERROR_AUTH_REQUIRED,
     MCP_ERROR_CAPACITY_EXHAUSTED, MCP_ERROR_INTERNAL, MCP_ERROR_INVALID_PARAMS,
-    MCP_ERROR_INVOCATION_CANCELLED, MCP_ERROR_METHOD_NOT_FOUND, MCP_ERROR_UNKNOWN_EXECUTION_STATE,
-    METHOD_INITIALIZE, METHOD_NOTIFICATIONS_CANCELLED, METHOD_NOTIFICATIONS_INITIALIZED,
-    METHOD_PING, METHOD_TOOLS_CALL, METHOD_TOOLS_LIST,
+    MCP_ERROR_INVOCATION_CANCELLED, MCP_ERROR_METHOD_NOT_FOUND, MCP_ERROR_RESOURCE_NOT_FOUND,
+    MCP_ERROR_UNKNOWN_EXECUTION_STATE, METHOD_INITIALIZE, METHOD_NOTIFICATIONS_CANCELLED,
+    METHOD_NOTIFICATIONS_INITIALIZED, METHOD_NOTIFICATIONS_RESOURCES_UPDATED, METHOD_PING,
+    METHOD_PROMPTS_GET, METHOD_PROMPTS_LIST, METHOD_RESOURCES_LIST, METHOD_RESOURCES_READ,
+    METHOD_RESOURCES_SUBSCRIBE, METHOD_RESOURCES_TEMPLATES_LIST, METHOD_RESOURCES_UNSUBSCRIBE,
+    METHOD_TOOLS_CALL, METHOD_TOOLS_LIST,
 };
 pub use provider::{
-    tool_result_max_chars_from_params, CompositeToolProvider, McpRequestContext, McpToolProvider,
-    TOOL_RESULT_MAX_CHARS_META_KEY, TOOL_RESULT_MAX_CHARS_UPPER_BOUND,
+    tool_result_max_chars_from_params, CompositeToolProvider, McpPromptProvider, McpRequestContext,
+    McpResourceProvider, McpToolProvider, TOOL_RESULT_MAX_CHARS_META_KEY,
+    TOOL_RESULT_MAX_CHARS_UPPER_BOUND,
 };
 pub use service::{McpJsonRpcService, McpServerInfo};
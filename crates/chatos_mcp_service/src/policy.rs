@@ -202,10 +202,12 @@ pub fn normalize_builtin_kind_name(value: &str) -> Option<&'static str> {
 pub fn classify_builtin_tool(name: &str) -> Option<BuiltinToolAccess> {
     match name.trim() {
         "read_file_raw" | "read_file_range" | "read_file" | "list_dir" | "search_text"
-        | "search_files" => Some(BuiltinToolAccess::CodeRead),
-        "open_edit_session" | "stage_edit_batch" | "commit_edit_session" | "abort_edit_session" => {
-            Some(BuiltinToolAccess::CodeWrite)
-        }
+        | "search_files" | "list_edit_sessions" => Some(BuiltinToolAccess::CodeRead),
+        "open_edit_session"
+        | "stage_edit_batch"
+        | "commit_edit_session"
+        | "abort_edit_session"
+        | "revert_edit_session" => Some(BuiltinToolAccess::CodeWrite),
         "execute_command" | "get_recent_logs" | "process_list" | "process_poll" | "process_log"
         | "process_wait" | "process_write" | "process_kill" | "process" => {
             Some(BuiltinToolAccess::Terminal)
@@ -298,10 +300,18 @@ mod tests {
             classify_builtin_tool("search_text"),
             Some(BuiltinToolAccess::CodeRead)
         );
+        assert_eq!(
+            classify_builtin_tool("list_edit_sessions"),
+            Some(BuiltinToolAccess::CodeRead)
+        );
         assert_eq!(
             classify_builtin_tool("stage_edit_batch"),
             Some(BuiltinToolAccess::CodeWrite)
         );
+        assert_eq!(
+            classify_builtin_tool("revert_edit_session"),
+            Some(BuiltinToolAccess::CodeWrite)
+        );
         assert_eq!(
             classify_builtin_tool("process_wait"),
             Some(BuiltinToolAccess::Terminal)
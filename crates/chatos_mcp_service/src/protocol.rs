@@ -10,11 +10,20 @@ pub const METHOD_NOTIFICATIONS_CANCELLED: &str = "notifications/cancelled";
 pub const METHOD_PING: &str = "ping";
 pub const METHOD_TOOLS_LIST: &str = "tools/list";
 pub const METHOD_TOOLS_CALL: &str = "tools/call";
+pub const METHOD_RESOURCES_LIST: &str = "resources/list";
+pub const METHOD_RESOURCES_READ: &str = "resources/read";
+pub const METHOD_RESOURCES_TEMPLATES_LIST: &str = "resources/templates/list";
+pub const METHOD_RESOURCES_SUBSCRIBE: &str = "resources/subscribe";
+pub const METHOD_RESOURCES_UNSUBSCRIBE: &str = "resources/unsubscribe";
+pub const METHOD_NOTIFICATIONS_RESOURCES_UPDATED: &str = "notifications/resources/updated";
+pub const METHOD_PROMPTS_LIST: &str = "prompts/list";
+pub const METHOD_PROMPTS_GET: &str = "prompts/get";
 
 pub const MCP_ERROR_METHOD_NOT_FOUND: i32 = -32601;
 pub const MCP_ERROR_INVALID_PARAMS: i32 = -32602;
 pub const MCP_ERROR_INTERNAL: i32 = -32000;
 pub const MCP_ERROR_AUTH_REQUIRED: i32 = -32001;
+pub const MCP_ERROR_RESOURCE_NOT_FOUND: i32 = -32002;
 pub const MCP_ERROR_INVOCATION_CANCELLED: i32 = -32010;
 pub const MCP_ERROR_UNKNOWN_EXECUTION_STATE: i32 = -32011;
 pub const MCP_ERROR_CAPACITY_EXHAUSTED: i32 = -32012;
@@ -5,7 +5,7 @@ use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use serde_json::Value;
+use serde_json::{Map, Value};
 
 use crate::catalog::{contains_tool_name, sort_tools_by_name, tool_name};
 
@@ -115,3 +115,55 @@ impl McpToolProvider for CompositeToolProvider {
         Err(format!("tool not found: {name}"))
     }
 }
+
+/// Serves `resources/*` requests. Resources are entries such as
+/// `{"uri", "name", "mimeType"}`; reads return the MCP `contents` array, or
+/// `None` when the URI is unknown.
+#[async_trait]
+pub trait McpResourceProvider: Send + Sync {
+    fn list_resources(&self, context: &McpRequestContext) -> Vec<Value>;
+
+    fn list_resource_templates(&self, _context: &McpRequestContext) -> Vec<Value> {
+        Vec::new()
+    }
+
+    async fn read_resource(
+        &self,
+        uri: &str,
+        context: McpRequestContext,
+    ) -> Result<Option<Vec<Value>>, String>;
+
+    fn supports_subscribe(&self) -> bool {
+        false
+    }
+
+    async fn subscribe_resource(
+        &self,
+        _uri: &str,
+        _context: McpRequestContext,
+    ) -> Result<(), String> {
+        Err("resource subscriptions are not supported".to_string())
+    }
+
+    async fn unsubscribe_resource(
+        &self,
+        _uri: &str,
+        _context: McpRequestContext,
+    ) -> Result<(), String> {
+        Ok(())
+    }
+}
+
+/// Serves `prompts/*` requests. `get_prompt` returns the MCP result object
+/// (`{"description", "messages"}`), or `None` when the prompt is unknown.
+#[async_trait]
+pub trait McpPromptProvider: Send + Sync {
+    fn list_prompts(&self, context: &McpRequestContext) -> Vec<Value>;
+
+    async fn get_prompt(
+        &self,
+        name: &str,
+        arguments: Map<String, Value>,
+        context: McpRequestContext,
+    ) -> Result<Option<Value>, String>;
+}
@@ -3,14 +3,17 @@
 
 use std::sync::Arc;
 
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 
 use crate::protocol::{
     jsonrpc_error, jsonrpc_ok, JsonRpcRequest, JsonRpcResponse, MCP_ERROR_INTERNAL,
-    MCP_ERROR_INVALID_PARAMS, MCP_ERROR_METHOD_NOT_FOUND, METHOD_INITIALIZE,
-    METHOD_NOTIFICATIONS_INITIALIZED, METHOD_PING, METHOD_TOOLS_CALL, METHOD_TOOLS_LIST,
+    MCP_ERROR_INVALID_PARAMS, MCP_ERROR_METHOD_NOT_FOUND, MCP_ERROR_RESOURCE_NOT_FOUND,
+    METHOD_INITIALIZE, METHOD_NOTIFICATIONS_INITIALIZED, METHOD_PING, METHOD_PROMPTS_GET,
+    METHOD_PROMPTS_LIST, METHOD_RESOURCES_LIST, METHOD_RESOURCES_READ, METHOD_RESOURCES_SUBSCRIBE,
+    METHOD_RESOURCES_TEMPLATES_LIST, METHOD_RESOURCES_UNSUBSCRIBE, METHOD_TOOLS_CALL,
+    METHOD_TOOLS_LIST,
 };
-use crate::provider::{McpRequestContext, McpToolProvider};
+use crate::provider::{McpPromptProvider, McpRequestContext, McpResourceProvider, McpToolProvider};
 
 #[derive(Debug, Clone)]
 pub struct McpServerInfo {
@@ -33,6 +36,8 @@ impl McpServerInfo {
 pub struct McpJsonRpcService {
     server_info: McpServerInfo,
     provider: Arc<dyn McpToolProvider>,
+    resources: Option<Arc<dyn McpResourceProvider>>,
+    prompts: Option<Arc<dyn McpPromptProvider>>,
 }
 
 impl McpJsonRpcService {
@@ -40,9 +45,21 @@ impl McpJsonRpcService {
         Self {
             server_info,
             provider,
+            resources: None,
+            prompts: None,
         }
     }
 
+    pub fn with_resource_provider(mut self, resources: Arc<dyn McpResourceProvider>) -> Self {
+        self.resources = Some(resources);
+        self
+    }
+
+    pub fn with_prompt_provider(mut self, prompts: Arc<dyn McpPromptProvider>) -> Self {
+        self.prompts = Some(prompts);
+        self
+    }
+
     pub async fn handle(&self, request: JsonRpcRequest) -> JsonRpcResponse {
         self.handle_with_context(request, McpRequestContext::default())
             .await
@@ -62,6 +79,20 @@ impl McpJsonRpcService {
                 jsonrpc_ok(id, json!({ "tools": tools }))
             }
             METHOD_TOOLS_CALL => self.handle_tool_call(id, request.params, context).await,
+            METHOD_RESOURCES_LIST
+            | METHOD_RESOURCES_TEMPLATES_LIST
+            | METHOD_RESOURCES_READ
+            | METHOD_RESOURCES_SUBSCRIBE
+            | METHOD_RESOURCES_UNSUBSCRIBE
+                if self.resources.is_some() =>
+            {
+                self.handle_resource_request(id, request.method.as_str(), request.params, context)
+                    .await
+            }
+            METHOD_PROMPTS_LIST | METHOD_PROMPTS_GET if self.prompts.is_some() => {
+                self.handle_prompt_request(id, request.method.as_str(), request.params, context)
+                    .await
+            }
             other => jsonrpc_error(
                 id,
                 MCP_ERROR_METHOD_NOT_FOUND,
@@ -95,12 +126,120 @@ impl McpJsonRpcService {
         }
     }
 
+    async fn handle_resource_request(
+        &self,
+        id: Value,
+        method: &str,
+        params: Value,
+        context: McpRequestContext,
+    ) -> JsonRpcResponse {
+        let Some(resources) = self.resources.as_ref() else {
+            return jsonrpc_error(
+                id,
+                MCP_ERROR_METHOD_NOT_FOUND,
+                format!("method not found: {method}"),
+            );
+        };
+        match method {
+            METHOD_RESOURCES_LIST => jsonrpc_ok(
+                id,
+                json!({ "resources": resources.list_resources(&context) }),
+            ),
+            METHOD_RESOURCES_TEMPLATES_LIST => jsonrpc_ok(
+                id,
+                json!({ "resourceTemplates": resources.list_resource_templates(&context) }),
+            ),
+            _ => {
+                let Some(uri) = required_string_param(&params, "uri") else {
+                    return jsonrpc_error(
+                        id,
+                        MCP_ERROR_INVALID_PARAMS,
+                        format!("{method}.uri is required"),
+                    );
+                };
+                let result = match method {
+                    METHOD_RESOURCES_READ => resources
+                        .read_resource(uri, context)
+                        .await
+                        .map(|contents| contents.map(|contents| json!({ "contents": contents }))),
+                    METHOD_RESOURCES_SUBSCRIBE => resources
+                        .subscribe_resource(uri, context)
+                        .await
+                        .map(|()| Some(json!({}))),
+                    _ => resources
+                        .unsubscribe_resource(uri, context)
+                        .await
+                        .map(|()| Some(json!({}))),
+                };
+                match result {
+                    Ok(Some(result)) => jsonrpc_ok(id, result),
+                    Ok(None) => jsonrpc_error(
+                        id,
+                        MCP_ERROR_RESOURCE_NOT_FOUND,
+                        format!("resource not found: {uri}"),
+                    ),
+                    Err(message) => jsonrpc_error(id, MCP_ERROR_INTERNAL, message),
+                }
+            }
+        }
+    }
+
+    async fn handle_prompt_request(
+        &self,
+        id: Value,
+        method: &str,
+        params: Value,
+        context: McpRequestContext,
+    ) -> JsonRpcResponse {
+        let Some(prompts) = self.prompts.as_ref() else {
+            return jsonrpc_error(
+                id,
+                MCP_ERROR_METHOD_NOT_FOUND,
+                format!("method not found: {method}"),
+            );
+        };
+        if method == METHOD_PROMPTS_LIST {
+            return jsonrpc_ok(id, json!({ "prompts": prompts.list_prompts(&context) }));
+        }
+        let Some(name) = required_string_param(&params, "name") else {
+            return jsonrpc_error(id, MCP_ERROR_INVALID_PARAMS, "prompts/get.name is required");
+        };
+        let arguments = match params.get("arguments") {
+            None | Some(Value::Null) => Map::new(),
+            Some(Value::Object(arguments)) => arguments.clone(),
+            Some(_) => {
+                return jsonrpc_error(
+                    id,
+                    MCP_ERROR_INVALID_PARAMS,
+                    "prompts/get.arguments must be an object",
+                )
+            }
+        };
+        match prompts.get_prompt(name, arguments, context).await {
+            Ok(Some(result)) => jsonrpc_ok(id, result),
+            Ok(None) => jsonrpc_error(
+                id,
+                MCP_ERROR_INVALID_PARAMS,
+                format!("prompt not found: {name}"),
+            ),
+            Err(message) => jsonrpc_error(id, MCP_ERROR_INTERNAL, message),
+        }
+    }
+
     fn initialize_result(&self) -> Value {
+        let mut capabilities = json!({ "tools": {} });
+        if let Some(resources) = self.resources.as_ref() {
+            capabilities["resources"] = json!({
+                "subscribe": resources.supports_subscribe(),
+                "listChanged": false
+            });
+        }
+        if self.prompts.is_some() {
+            capabilities["prompts"] = json!({ "listChanged": false });
+        }
         json!({
             "protocolVersion": self.server_info.protocol_version,
-            "capabilities": {
-                "tools": {}
-            },
+            "capabilities": capabilities,
             "serverInfo": {
                 "name": self.server_info.name,
                 "version": self.server_info.version
@@ -109,6 +248,14 @@ impl McpJsonRpcService {
     }
 }
 
+fn required_string_param<'a>(params: &'a Value, key: &str) -> Option<&'a str> {
+    params
+        .get(key)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
 #[cfg(test)]
 mod tests {
     use async_trait::async_trait;
@@ -150,10 +297,136 @@ mod tests {
         }
     }
 
+    struct FakeContent;
+
+    #[async_trait]
+    impl McpResourceProvider for FakeContent {
+        fn list_resources(&self, _context: &McpRequestContext) -> Vec<Value> {
+            vec![json!({"uri": "docs://readme", "name": "README", "mimeType": "text/markdown"})]
+        }
+
+        async fn read_resource(
+            &self,
+            uri: &str,
+            _context: McpRequestContext,
+        ) -> Result<Option<Vec<Value>>, String> {
+            Ok((uri == "docs://readme")
+                .then(|| vec![json!({"uri": uri, "mimeType": "text/markdown", "text": "# Hi"})]))
+        }
+    }
+
+    #[async_trait]
+    impl McpPromptProvider for FakeContent {
+        fn list_prompts(&self, _context: &McpRequestContext) -> Vec<Value> {
+            vec![json!({"name": "review", "arguments": [{"name": "path", "required": true}]})]
+        }
+
+        async fn get_prompt(
+            &self,
+            name: &str,
+            arguments: Map<String, Value>,
+            _context: McpRequestContext,
+        ) -> Result<Option<Value>, String> {
+            Ok((name == "review").then(|| {
+                json!({"messages": [{"role": "user", "content": {
+                    "type": "text",
+                    "text": format!("Review {}", arguments["path"].as_str().unwrap_or("?"))
+                }}]})
+            }))
+        }
+    }
+
     fn service() -> McpJsonRpcService {
         McpJsonRpcService::new(McpServerInfo::new("fake", "0.1.0"), Arc::new(FakeProvider))
     }
 
+    fn request(method: &str, params: Value) -> JsonRpcRequest {
+        JsonRpcRequest {
+            jsonrpc: Some("2.0".to_string()),
+            id: Some(json!("req-1")),
+            method: method.to_string(),
+            params,
+        }
+    }
+
+    #[tokio::test]
+    async fn serves_resources_and_prompts_when_providers_are_attached() {
+        let content = Arc::new(FakeContent);
+        let service = service()
+            .with_resource_provider(content.clone())
+            .with_prompt_provider(content);
+
+        let init = service.handle(request(METHOD_INITIALIZE, json!({}))).await;
+        let capabilities = &init.result.as_ref().unwrap()["capabilities"];
+        assert_eq!(capabilities["resources"]["subscribe"], json!(false));
+        assert!(capabilities.get("prompts").is_some());
+
+        let read = service
+            .handle(request(
+                METHOD_RESOURCES_READ,
+                json!({"uri": "docs://readme"}),
+            ))
+            .await;
+        assert_eq!(read.result.unwrap()["contents"][0]["text"], "# Hi");
+        let missing = service
+            .handle(request(
+                METHOD_RESOURCES_READ,
+                json!({"uri": "docs://missing"}),
+            ))
+            .await;
+        assert_eq!(
+            missing.error.map(|error| error.code),
+            Some(MCP_ERROR_RESOURCE_NOT_FOUND)
+        );
+        let subscribe = service
+            .handle(request(
+                METHOD_RESOURCES_SUBSCRIBE,
+                json!({"uri": "docs://readme"}),
+            ))
+            .await;
+        assert_eq!(
+            subscribe.error.map(|error| error.code),
+            Some(MCP_ERROR_INTERNAL)
+        );
+
+        let prompt = service
+            .handle(request(
+                METHOD_PROMPTS_GET,
+                json!({"name": "review", "arguments": {"path": "src/lib.rs"}}),
+            ))
+            .await;
+        assert_eq!(
+            prompt.result.unwrap()["messages"][0]["content"]["text"],
+            "Review src/lib.rs"
+        );
+        let bad_arguments = service
+            .handle(request(
+                METHOD_PROMPTS_GET,
+                json!({"name": "review", "arguments": ["x"]}),
+            ))
+            .await;
+        assert_eq!(
+            bad_arguments.error.map(|error| error.code),
+            Some(MCP_ERROR_INVALID_PARAMS)
+        );
+    }
+
+    #[tokio::test]
+    async fn resource_and_prompt_methods_are_not_found_without_providers() {
+        let service = service();
+        let init = service.handle(request(METHOD_INITIALIZE, json!({}))).await;
+        assert!(init.result.unwrap()["capabilities"]
+            .get("resources")
+            .is_none());
+        for method in [METHOD_RESOURCES_LIST, METHOD_PROMPTS_LIST] {
+            let response = service.handle(request(method, json!({}))).await;
+            assert_eq!(
+                response.error.map(|error| error.code),
+                Some(MCP_ERROR_METHOD_NOT_FOUND)
+            );
+        }
+    }
+
     #[tokio::test]
     async fn handles_initialize() {
         let response = service()
@@ -24,6 +24,17 @@ pub const PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_READ: &str = "artifact.read";
 pub const PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_DOWNLOAD: &str = "artifact.download";
 pub const PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_CREATE: &str = "artifact.create";
 pub const PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_UPDATE: &str = "artifact.update";
+pub const PLUGIN_WASM_MAX_MODULES: usize = 32;
+pub const PLUGIN_WASM_MAX_ENV_GRANTS: usize = 32;
+pub const PLUGIN_WASM_DEFAULT_MAX_DURATION_MS: u64 = 5_000;
+pub const PLUGIN_WASM_MAX_DURATION_MS: u64 = 30_000;
+pub const PLUGIN_WASM_DEFAULT_MAX_MEMORY_BYTES: u64 = 64 * 1024 * 1024;
+pub const PLUGIN_WASM_MIN_MEMORY_BYTES: u64 = 1024 * 1024;
+pub const PLUGIN_WASM_MAX_MEMORY_BYTES: u64 = 512 * 1024 * 1024;
+/// Binary preamble of a WebAssembly component: `\0asm`, component-model version 0x0d, layer 1.
+/// Core modules use layer 0 and are not accepted as WASI preview 2 entrypoints.
+pub const PLUGIN_WASM_COMPONENT_PREAMBLE: [u8; 8] =
+    [0x00, 0x61, 0x73, 0x6d, 0x0d, 0x00, 0x01, 0x00];
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -191,6 +202,58 @@ pub struct PluginHook {
     pub source: PluginPathRef,
 }
 
+/// Capabilities granted to a Wasm module. Anything not granted here is unavailable to the guest:
+/// no filesystem unless `workspaceRead`, no environment variables besides `env`, and no network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PluginWasmCapabilities {
+    /// Preopens the workspace root read-only; requires the `workspace.read` permission.
+    #[serde(default)]
+    pub workspace_read: bool,
+    /// Host environment variable names copied into the guest environment.
+    #[serde(default)]
+    pub env: Vec<String>,
+    #[serde(default = "default_plugin_wasm_max_duration_ms")]
+    pub max_duration_ms: u64,
+    #[serde(default = "default_plugin_wasm_max_memory_bytes")]
+    pub max_memory_bytes: u64,
+}
+
+impl Default for PluginWasmCapabilities {
+    fn default() -> Self {
+        Self {
+            workspace_read: false,
+            env: Vec::new(),
+            max_duration_ms: PLUGIN_WASM_DEFAULT_MAX_DURATION_MS,
+            max_memory_bytes: PLUGIN_WASM_DEFAULT_MAX_MEMORY_BYTES,
+        }
+    }
+}
+
+/// WASI preview 2 component shipped in the package. `sha256` pins the module bytes inside the
+/// manifest, so the release signature over the manifest also covers the module.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PluginWasmModule {
+    pub module_key: String,
+    pub source: PluginPathRef,
+    pub sha256: String,
+    #[serde(default)]
+    pub capabilities: PluginWasmCapabilities,
+}
+
+pub const fn default_plugin_wasm_max_duration_ms() -> u64 {
+    PLUGIN_WASM_DEFAULT_MAX_DURATION_MS
+}
+
+pub const fn default_plugin_wasm_max_memory_bytes() -> u64 {
+    PLUGIN_WASM_DEFAULT_MAX_MEMORY_BYTES
+}
+
+pub fn is_plugin_wasm_component(bytes: &[u8]) -> bool {
+    bytes.starts_with(&PLUGIN_WASM_COMPONENT_PREAMBLE)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
 pub struct PluginUiContribution {
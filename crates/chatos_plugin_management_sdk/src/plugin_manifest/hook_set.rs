@@ -4,10 +4,11 @@
 use std::collections::{BTreeMap, HashSet};
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 
-use super::{normalize_plugin_relative_path, PluginPathRef};
+use super::{normalize_plugin_relative_path, PluginPathRef, PluginWasmModule};
 
 pub const PLUGIN_HOOK_SET_SCHEMA_VERSION_V1: u32 = 1;
 pub const PLUGIN_HOOK_MAX_DEFINITIONS: usize = 64;
@@ -15,6 +16,9 @@ pub const PLUGIN_HOOK_DEFAULT_TIMEOUT_MS: u64 = 5_000;
 pub const PLUGIN_HOOK_MAX_TIMEOUT_MS: u64 = 30_000;
 pub const PLUGIN_HOOK_DEFAULT_OUTPUT_BYTES: usize = 64 * 1024;
 pub const PLUGIN_HOOK_MAX_OUTPUT_BYTES: usize = 256 * 1024;
+pub const PLUGIN_HOOK_MAX_RESPONSE_TEXT_BYTES: usize = 8 * 1024;
+pub const PLUGIN_HOOK_MAX_TOOL_INPUT_BYTES: usize = 48 * 1024;
+pub const PLUGIN_HOOK_MAX_PROMPT_BYTES: usize = 32 * 1024;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum PluginHookEvent {
@@ -25,6 +29,8 @@ pub enum PluginHookEvent {
     RunCompleted,
     RunFailed,
     PluginDisabled,
+    UserPromptSubmit,
+    PreCompact,
 }
 
 impl PluginHookEvent {
@@ -37,8 +43,27 @@ impl PluginHookEvent {
             Self::RunCompleted => "RunCompleted",
             Self::RunFailed => "RunFailed",
             Self::PluginDisabled => "PluginDisabled",
+            Self::UserPromptSubmit => "UserPromptSubmit",
+            Self::PreCompact => "PreCompact",
         }
     }
+
+    /// Events whose Hooks may answer with a `decision`; the caller waits for them before acting.
+    pub const fn accepts_decision(self) -> bool {
+        matches!(self, Self::PreToolUse | Self::UserPromptSubmit)
+    }
+
+    /// Events whose Hooks may return `additionalContext` for the model.
+    pub const fn accepts_additional_context(self) -> bool {
+        matches!(
+            self,
+            Self::SessionStart
+                | Self::PreToolUse
+                | Self::PostToolUse
+                | Self::UserPromptSubmit
+                | Self::PreCompact
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -87,6 +112,12 @@ pub struct PluginHookEventContext {
     pub outcome: Option<PluginHookOutcome>,
     #[serde(default)]
     pub summary_sha256: Option<String>,
+    /// Tool arguments for `PreToolUse`, after any rewrite by an earlier Hook of the same dispatch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_input: Option<Value>,
+    /// Submitted user prompt for `UserPromptSubmit`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
 }
 
 impl PluginHookMatcher {
@@ -102,6 +133,51 @@ impl PluginHookMatcher {
     }
 }
 
+/// Ordered by strength so the strongest decision of a dispatch wins.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginHookDecision {
+    Allow,
+    Ask,
+    Deny,
+}
+
+impl PluginHookDecision {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Ask => "ask",
+            Self::Deny => "deny",
+        }
+    }
+}
+
+/// JSON object a Hook may print on stdout to steer the caller.
+///
+/// `deny` blocks the tool call or prompt and `reason` is returned to the model; `ask` defers to
+/// the user; `updatedInput` replaces the `PreToolUse` tool arguments. Unknown keys are ignored
+/// and stdout without any of these keys keeps its previous meaning of opaque audit output.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PluginHookResponse {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision: Option<PluginHookDecision>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_input: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub additional_context: Option<String>,
+}
+
+#[derive(Debug, Error)]
+pub enum PluginHookResponseError {
+    #[error("invalid Plugin Hook response JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("invalid Plugin Hook response field {field}: {message}")]
+    InvalidField { field: String, message: String },
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
 pub enum PluginHookEntrypoint {
@@ -110,18 +186,33 @@ pub enum PluginHookEntrypoint {
         #[serde(default)]
         args: Vec<String>,
     },
+    /// Runs a WASI preview 2 component declared in the manifest `wasmModules` with the
+    /// capabilities granted there. Event JSON and responses use stdin and stdout like commands.
+    Wasm {
+        module: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
 }
 
 impl PluginHookEntrypoint {
-    pub fn command(&self) -> &PluginPathRef {
+    pub fn command(&self) -> Option<&PluginPathRef> {
         match self {
-            Self::Command { command, .. } => command,
+            Self::Command { command, .. } => Some(command),
+            Self::Wasm { .. } => None,
+        }
+    }
+
+    pub fn wasm_module(&self) -> Option<&str> {
+        match self {
+            Self::Command { .. } => None,
+            Self::Wasm { module, .. } => Some(module.as_str()),
         }
     }
 
     pub fn args(&self) -> &[String] {
         match self {
-            Self::Command { args, .. } => args,
+            Self::Command { args, .. } | Self::Wasm { args, .. } => args,
         }
     }
 }
@@ -166,16 +257,19 @@ pub fn parse_plugin_hook_set(raw: &str) -> Result<PluginHookSet, PluginHookSetEr
         hook.id = hook.id.trim().to_string();
         hook.events.sort();
         normalize_matcher(&mut hook.matcher);
-        let command = hook.entrypoint.command().path.clone();
-        let command = normalize_plugin_relative_path(command.as_str()).map_err(|message| {
-            PluginHookSetError::InvalidField {
-                field: format!("hooks[{index}].entrypoint.command"),
-                message,
-            }
-        })?;
         match &mut hook.entrypoint {
-            PluginHookEntrypoint::Command { command: path, .. } => {
-                *path = PluginPathRef::new(command);
+            PluginHookEntrypoint::Command { command, .. } => {
+                let normalized =
+                    normalize_plugin_relative_path(command.path.as_str()).map_err(|message| {
+                        PluginHookSetError::InvalidField {
+                            field: format!("hooks[{index}].entrypoint.command"),
+                            message,
+                        }
+                    })?;
+                *command = PluginPathRef::new(normalized);
+            }
+            PluginHookEntrypoint::Wasm { module, .. } => {
+                *module = module.trim().to_string();
             }
         }
     }
@@ -207,22 +301,35 @@ pub fn validate_plugin_hook_set(hook_set: &PluginHookSet) -> Result<(), PluginHo
         if !ids.insert(hook.id.as_str()) {
             return invalid(format!("{field}.id"), "duplicate Hook id");
         }
-        if hook.events.is_empty() || hook.events.len() > 7 {
+        if hook.events.is_empty() || hook.events.len() > 9 {
             return invalid(
                 format!("{field}.events"),
-                "must contain between 1 and 7 supported lifecycle events",
+                "must contain between 1 and 9 supported lifecycle events",
             );
         }
         if hook.events.windows(2).any(|events| events[0] == events[1]) {
             return invalid(format!("{field}.events"), "contains a duplicate event");
         }
         validate_matcher(format!("{field}.matcher").as_str(), &hook.matcher)?;
-        let command = hook.entrypoint.command().path.trim_start_matches("./");
-        if !command.starts_with("scripts/") && !command.starts_with("binaries/") {
-            return invalid(
-                format!("{field}.entrypoint.command"),
-                "must point inside scripts/ or binaries/",
-            );
+        match &hook.entrypoint {
+            PluginHookEntrypoint::Command { command, .. } => {
+                let command = command.path.trim_start_matches("./");
+                if !command.starts_with("scripts/") && !command.starts_with("binaries/") {
+                    return invalid(
+                        format!("{field}.entrypoint.command"),
+                        "must point inside scripts/ or binaries/",
+                    );
+                }
+            }
+            PluginHookEntrypoint::Wasm { module, .. } => {
+                validate_identifier(format!("{field}.entrypoint.module").as_str(), module)?;
+                if hook.workspace_write {
+                    return invalid(
+                        format!("{field}.workspaceWrite"),
+                        "Wasm Hooks only get workspace access through module capabilities",
+                    );
+                }
+            }
         }
         if hook.entrypoint.args().len() > 32 {
             return invalid(
@@ -254,6 +361,24 @@ pub fn validate_plugin_hook_set(hook_set: &PluginHookSet) -> Result<(), PluginHo
     Ok(())
 }
 
+/// Checks that every Wasm entrypoint names a module declared in the plugin manifest.
+pub fn validate_plugin_hook_set_wasm_modules(
+    hook_set: &PluginHookSet,
+    modules: &[PluginWasmModule],
+) -> Result<(), PluginHookSetError> {
+    for (index, hook) in hook_set.hooks.iter().enumerate() {
+        if let Some(module) = hook.entrypoint.wasm_module() {
+            if !modules.iter().any(|declared| declared.module_key == module) {
+                return invalid(
+                    format!("hooks[{index}].entrypoint.module"),
+                    format!("Wasm module {module} is not declared in the plugin manifest"),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn normalized_plugin_hook_set_sha256(
     hook_set: &PluginHookSet,
 ) -> Result<String, serde_json::Error> {
@@ -294,6 +419,76 @@ pub fn plugin_hook_snapshot_sha256(
     .map(|bytes| hex::encode(Sha256::digest(bytes)))
 }
 
+/// Parses Hook stdout for `event`. Returns `None` when stdout carries no response.
+pub fn parse_plugin_hook_response(
+    event: PluginHookEvent,
+    stdout: &[u8],
+) -> Result<Option<PluginHookResponse>, PluginHookResponseError> {
+    let trimmed = stdout.trim_ascii();
+    if !trimmed.starts_with(b"{") {
+        return Ok(None);
+    }
+    let mut response: PluginHookResponse = serde_json::from_slice(trimmed)?;
+    if response == PluginHookResponse::default() {
+        return Ok(None);
+    }
+    for (field, value) in [
+        ("reason", &mut response.reason),
+        ("additionalContext", &mut response.additional_context),
+    ] {
+        if let Some(text) = value.as_mut() {
+            *text = text.trim().to_string();
+            if text.is_empty()
+                || text.len() > PLUGIN_HOOK_MAX_RESPONSE_TEXT_BYTES
+                || text.contains('\0')
+            {
+                return response_invalid(
+                    field,
+                    format!("must be 1-{PLUGIN_HOOK_MAX_RESPONSE_TEXT_BYTES} bytes without NUL"),
+                );
+            }
+        }
+    }
+    if let Some(decision) = response.decision {
+        if !event.accepts_decision() {
+            return response_invalid(
+                "decision",
+                format!("is not supported for {}", event.as_str()),
+            );
+        }
+        if event == PluginHookEvent::UserPromptSubmit && decision == PluginHookDecision::Ask {
+            return response_invalid("decision", "UserPromptSubmit accepts only allow or deny");
+        }
+        if decision != PluginHookDecision::Allow && response.reason.is_none() {
+            return response_invalid("reason", format!("is required for {}", decision.as_str()));
+        }
+    }
+    if let Some(input) = &response.updated_input {
+        if event != PluginHookEvent::PreToolUse {
+            return response_invalid("updatedInput", "is only supported for PreToolUse");
+        }
+        if response.decision == Some(PluginHookDecision::Deny) {
+            return response_invalid("updatedInput", "cannot be combined with deny");
+        }
+        if !input.is_object() || serde_json::to_vec(input)?.len() > PLUGIN_HOOK_MAX_TOOL_INPUT_BYTES
+        {
+            return response_invalid(
+                "updatedInput",
+                format!(
+                    "must be a JSON object of at most {PLUGIN_HOOK_MAX_TOOL_INPUT_BYTES} bytes"
+                ),
+            );
+        }
+    }
+    if response.additional_context.is_some() && !event.accepts_additional_context() {
+        return response_invalid(
+            "additionalContext",
+            format!("is not supported for {}", event.as_str()),
+        );
+    }
+    Ok(Some(response))
+}
+
 fn normalize_matcher(matcher: &mut PluginHookMatcher) {
     for values in [
         &mut matcher.tool_names,
@@ -370,6 +565,16 @@ fn invalid<T>(
     })
 }
 
+fn response_invalid<T>(
+    field: impl Into<String>,
+    message: impl Into<String>,
+) -> Result<T, PluginHookResponseError> {
+    Err(PluginHookResponseError::InvalidField {
+        field: field.into(),
+        message: message.into(),
+    })
+}
+
 const fn default_hook_schema_version() -> u32 {
     PLUGIN_HOOK_SET_SCHEMA_VERSION_V1
 }
@@ -405,8 +610,11 @@ mod tests {
         let hook_set = parse_plugin_hook_set(raw.as_str()).expect("Hook set");
         assert_eq!(hook_set.hooks[0].id, "audit-run");
         assert_eq!(
-            hook_set.hooks[0].entrypoint.command().path,
-            "./scripts/audit"
+            hook_set.hooks[0]
+                .entrypoint
+                .command()
+                .map(|command| command.path.as_str()),
+            Some("./scripts/audit")
         );
         assert_eq!(hook_set.hooks[0].events[0], PluginHookEvent::RunCompleted);
         assert!(hook_set.hooks[0].workspace_write);
@@ -444,4 +652,99 @@ mod tests {
             .replace("./scripts/audit", "./audit");
         assert!(parse_plugin_hook_set(outside.as_str()).is_err());
     }
+
+    #[test]
+    fn wasm_entrypoints_reference_declared_modules_without_write_access() {
+        let raw = r#"{
+          "hooks": [{
+            "id": "guard",
+            "events": ["PreToolUse"],
+            "entrypoint": {"type": "wasm", "module": " guard ", "args": ["--strict"]}
+          }]
+        }"#;
+        let hook_set = parse_plugin_hook_set(raw).expect("Wasm Hook set");
+        let entrypoint = &hook_set.hooks[0].entrypoint;
+        assert_eq!(entrypoint.wasm_module(), Some("guard"));
+        assert_eq!(entrypoint.command(), None);
+        assert_eq!(entrypoint.args(), ["--strict"]);
+
+        let module = |module_key: &str| PluginWasmModule {
+            module_key: module_key.to_string(),
+            source: PluginPathRef::new("./wasm/guard.wasm"),
+            sha256: "ab".repeat(32),
+            capabilities: Default::default(),
+        };
+        validate_plugin_hook_set_wasm_modules(&hook_set, &[module("guard")])
+            .expect("declared module");
+        assert!(validate_plugin_hook_set_wasm_modules(&hook_set, &[module("other")]).is_err());
+
+        let writable = raw.replace(
+            "\"id\": \"guard\",",
+            "\"id\": \"guard\", \"workspaceWrite\": true,",
+        );
+        assert!(parse_plugin_hook_set(writable.as_str()).is_err());
+    }
+
+    #[test]
+    fn hook_response_accepts_decisions_only_where_the_caller_waits_for_them() {
+        for audit_output in [&b"audited 3 files\n"[..], br#"{"ok":true}"#] {
+            assert_eq!(
+                parse_plugin_hook_response(PluginHookEvent::PostToolUse, audit_output)
+                    .expect("audit output"),
+                None
+            );
+        }
+        let deny = parse_plugin_hook_response(
+            PluginHookEvent::PreToolUse,
+            br#" {"decision":"deny","reason":" migrations/ is read-only "} "#,
+        )
+        .expect("deny")
+        .expect("response");
+        assert_eq!(deny.decision, Some(PluginHookDecision::Deny));
+        assert_eq!(deny.reason.as_deref(), Some("migrations/ is read-only"));
+        let rewrite = parse_plugin_hook_response(
+            PluginHookEvent::PreToolUse,
+            br#"{"decision":"allow","updatedInput":{"path":"src/lib.rs"},"additionalContext":"lint ran"}"#,
+        )
+        .expect("rewrite")
+        .expect("response");
+        assert_eq!(
+            rewrite.updated_input,
+            Some(serde_json::json!({"path": "src/lib.rs"}))
+        );
+
+        for (event, raw) in [
+            (
+                PluginHookEvent::PostToolUse,
+                r#"{"decision":"deny","reason":"x"}"#,
+            ),
+            (PluginHookEvent::PreToolUse, r#"{"decision":"ask"}"#),
+            (
+                PluginHookEvent::UserPromptSubmit,
+                r#"{"decision":"ask","reason":"x"}"#,
+            ),
+            (
+                PluginHookEvent::PreToolUse,
+                r#"{"decision":"deny","reason":"x","updatedInput":{}}"#,
+            ),
+            (
+                PluginHookEvent::PreToolUse,
+                r#"{"updatedInput":["not","an","object"]}"#,
+            ),
+            (PluginHookEvent::UserPromptSubmit, r#"{"updatedInput":{}}"#),
+            (
+                PluginHookEvent::RunCompleted,
+                r#"{"additionalContext":"late"}"#,
+            ),
+            (
+                PluginHookEvent::PreToolUse,
+                r#"{"decision":"block","reason":"x"}"#,
+            ),
+        ] {
+            assert!(
+                parse_plugin_hook_response(event, raw.as_bytes()).is_err(),
+                "{event:?}: {raw}"
+            );
+        }
+    }
 }
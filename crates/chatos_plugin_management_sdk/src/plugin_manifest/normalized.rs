@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use super::components::{
     PluginAgent, PluginApp, PluginAuthor, PluginCommand, PluginDependencySpec, PluginHook,
     PluginInterfaceMetadata, PluginMcpServer, PluginPathRef, PluginPermissionRequirement,
-    PluginUiContribution,
+    PluginUiContribution, PluginWasmModule,
 };
 
 pub const PLUGIN_MANIFEST_SCHEMA_VERSION_V1: u32 = 1;
@@ -109,4 +109,6 @@ pub struct PluginManifest {
     pub permissions: Vec<PluginPermissionRequirement>,
     #[serde(default)]
     pub bundled_content_variant: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wasm_modules: Vec<PluginWasmModule>,
 }
@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 use super::components::{
     component_key_from_path, PluginAgent, PluginApp, PluginAuthor, PluginCommand,
     PluginDependencySpec, PluginHook, PluginInterfaceMetadata, PluginMcpServer, PluginPathRef,
-    PluginPermissionRequirement, PluginUiContribution,
+    PluginPermissionRequirement, PluginUiContribution, PluginWasmModule,
 };
 use super::normalized::{
     PluginExecutionPolicy, PluginManifest, PLUGIN_MANIFEST_SCHEMA_VERSION_V1,
@@ -64,6 +64,8 @@ struct RawPluginManifest {
     permissions: Vec<PermissionInput>,
     #[serde(default)]
     bundled_content_variant: Option<String>,
+    #[serde(default)]
+    wasm_modules: Vec<PluginWasmModule>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -206,6 +208,7 @@ pub fn parse_plugin_manifest(
         dependencies: raw.dependencies,
         permissions: normalize_permissions(raw.permissions),
         bundled_content_variant: normalize_optional(raw.bundled_content_variant),
+        wasm_modules: normalize_wasm_modules(raw.wasm_modules)?,
     };
     validate_plugin_manifest(&manifest)?;
     Ok(manifest)
@@ -422,6 +425,23 @@ fn normalize_hooks(input: Option<HooksInput>) -> Result<Vec<PluginHook>, PluginM
         .collect()
 }
 
+fn normalize_wasm_modules(
+    modules: Vec<PluginWasmModule>,
+) -> Result<Vec<PluginWasmModule>, PluginManifestError> {
+    modules
+        .into_iter()
+        .enumerate()
+        .map(|(index, mut module)| {
+            module.module_key = module.module_key.trim().to_string();
+            module.source =
+                normalize_path(module.source.path, format!("wasmModules[{index}].source"))?;
+            module.sha256 = module.sha256.trim().to_ascii_lowercase();
+            module.capabilities.env = normalize_strings(module.capabilities.env);
+            Ok(module)
+        })
+        .collect()
+}
+
 fn normalize_mcp_servers(
     input: Option<McpServersInput>,
 ) -> Result<Vec<PluginMcpServer>, PluginManifestError> {
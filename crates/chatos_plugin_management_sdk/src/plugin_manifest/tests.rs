@@ -619,3 +619,78 @@ fn normalized_manifest_signature_hash_is_stable_across_round_trip() {
         normalized_plugin_manifest_sha256(&decoded).expect("round-trip manifest hash")
     );
 }
+
+#[test]
+fn wasm_module_grants_are_normalized_and_pinned_by_the_manifest_hash() {
+    let module_sha256 = "AB".repeat(32);
+    let raw = CODEX_FIGMA_MANIFEST.replace(
+        "\"interface\": {",
+        format!(
+            "\"permissions\": [\"workspace.read\"], \"wasmModules\": [{{\"moduleKey\": \" guard \", \"source\": \"wasm/guard.wasm\", \"sha256\": \"{module_sha256}\", \"capabilities\": {{\"workspaceRead\": true, \"env\": [\"RUST_LOG\", \" CI \", \"CI\"], \"maxDurationMs\": 250}}}}], \"interface\": {{"
+        )
+        .as_str(),
+    );
+    let manifest = parse_plugin_manifest(raw.as_str(), PluginManifestSource::Codex)
+        .expect("Wasm module should parse");
+    let module = &manifest.wasm_modules[0];
+    assert_eq!(module.module_key, "guard");
+    assert_eq!(module.source.path, "./wasm/guard.wasm");
+    assert_eq!(module.sha256, "ab".repeat(32));
+    assert_eq!(module.capabilities.env, vec!["CI", "RUST_LOG"]);
+    assert_eq!(module.capabilities.max_duration_ms, 250);
+    assert_eq!(
+        module.capabilities.max_memory_bytes,
+        PLUGIN_WASM_DEFAULT_MAX_MEMORY_BYTES
+    );
+
+    let mut swapped = manifest.clone();
+    swapped.wasm_modules[0].sha256 = "cd".repeat(32);
+    assert_ne!(
+        normalized_plugin_manifest_sha256(&manifest).expect("manifest hash"),
+        normalized_plugin_manifest_sha256(&swapped).expect("swapped module hash")
+    );
+    let without_modules =
+        parse_plugin_manifest(CODEX_FIGMA_MANIFEST, PluginManifestSource::Codex).expect("manifest");
+    assert!(!serde_json::to_string(&without_modules)
+        .expect("manifest JSON")
+        .contains("wasmModules"));
+}
+
+#[test]
+fn wasm_module_grants_reject_unpermitted_or_unbounded_capabilities() {
+    for (module, expected_field) in [
+        (
+            json!({"moduleKey": "guard", "source": "./scripts/guard.wasm", "sha256": "ab".repeat(32)}),
+            "wasmModules[0].source",
+        ),
+        (
+            json!({"moduleKey": "guard", "source": "./wasm/guard.wasm", "sha256": "not-a-digest"}),
+            "wasmModules[0].sha256",
+        ),
+        (
+            json!({"moduleKey": "guard", "source": "./wasm/guard.wasm", "sha256": "ab".repeat(32), "capabilities": {"workspaceRead": true}}),
+            "wasmModules[0].capabilities.workspaceRead",
+        ),
+        (
+            json!({"moduleKey": "guard", "source": "./wasm/guard.wasm", "sha256": "ab".repeat(32), "capabilities": {"env": ["LD_PRELOAD"]}}),
+            "wasmModules[0].capabilities.env[0]",
+        ),
+        (
+            json!({"moduleKey": "guard", "source": "./wasm/guard.wasm", "sha256": "ab".repeat(32), "capabilities": {"maxDurationMs": 120000}}),
+            "wasmModules[0].capabilities.maxDurationMs",
+        ),
+    ] {
+        let mut manifest = schema_v2_prompt_manifest();
+        manifest["wasmModules"] = json!([module]);
+        let err =
+            parse_plugin_manifest(manifest.to_string().as_str(), PluginManifestSource::Chatos)
+                .expect_err("invalid Wasm module");
+        let PluginManifestError::Validation(err) = err else {
+            panic!("unexpected error {err}");
+        };
+        assert!(
+            err.issues.iter().any(|issue| issue.field == expected_field),
+            "{expected_field}: {err}"
+        );
+    }
+}
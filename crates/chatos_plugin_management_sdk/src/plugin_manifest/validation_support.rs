@@ -65,7 +65,7 @@ pub(super) fn validate_stdio_environment(
     }
 }
 
-fn is_host_controlled_environment_name(name: &str) -> bool {
+pub(super) fn is_host_controlled_environment_name(name: &str) -> bool {
     let name = name.to_ascii_uppercase();
     matches!(
         name.as_str(),
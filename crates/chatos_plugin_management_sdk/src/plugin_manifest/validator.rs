@@ -14,7 +14,9 @@ use super::components::{
     PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_UPDATE, PLUGIN_UI_BRIDGE_CAPABILITY_HOST_CONTEXT_READ,
     PLUGIN_UI_MAX_ARTIFACT_MIME_TYPES, PLUGIN_UI_MAX_ASSETS, PLUGIN_UI_MAX_BRIDGE_CAPABILITIES,
     PLUGIN_UI_SURFACE_ARTIFACT_VIEWER, PLUGIN_UI_SURFACE_DETAIL_PANEL,
-    PLUGIN_UI_SURFACE_MESSAGE_PANEL, PLUGIN_UI_SURFACE_WORKBENCH,
+    PLUGIN_UI_SURFACE_MESSAGE_PANEL, PLUGIN_UI_SURFACE_WORKBENCH, PLUGIN_WASM_MAX_DURATION_MS,
+    PLUGIN_WASM_MAX_ENV_GRANTS, PLUGIN_WASM_MAX_MEMORY_BYTES, PLUGIN_WASM_MAX_MODULES,
+    PLUGIN_WASM_MIN_MEMORY_BYTES,
 };
 use super::normalized::{
     PluginExecutionHost, PluginManifest, PLUGIN_MANIFEST_SCHEMA_VERSION_V1,
@@ -22,8 +24,9 @@ use super::normalized::{
 };
 use super::paths::normalize_plugin_relative_path;
 use super::validation_support::{
-    issue, required_text, validate_brand_color, validate_mcp_http_url, validate_optional_email,
-    validate_optional_https_url, validate_stdio_environment,
+    is_host_controlled_environment_name, issue, required_text, validate_brand_color,
+    validate_mcp_http_url, validate_optional_email, validate_optional_https_url,
+    validate_stdio_environment,
 };
 use super::PluginManifestValidationIssue;
 use crate::SystemAgentKey;
@@ -330,6 +333,7 @@ pub fn validate_plugin_manifest(
 
     validate_dependencies(manifest, &mut issues);
     validate_permissions(manifest, &component_keys, &mut issues);
+    validate_wasm_modules(manifest, &mut issues);
     validate_execution_policy(manifest, &component_keys, &mut issues);
 
     let component_count = manifest.skills.len()
@@ -757,6 +761,118 @@ fn validate_dependencies(
     }
 }
 
+fn validate_wasm_modules(
+    manifest: &PluginManifest,
+    issues: &mut Vec<PluginManifestValidationIssue>,
+) {
+    if manifest.wasm_modules.len() > PLUGIN_WASM_MAX_MODULES {
+        issue(
+            issues,
+            "wasmModules",
+            format!("must contain at most {PLUGIN_WASM_MAX_MODULES} modules"),
+        );
+    }
+    let mut module_keys = HashSet::new();
+    for (index, module) in manifest.wasm_modules.iter().enumerate() {
+        let field = format!("wasmModules[{index}]");
+        let key = module.module_key.as_str();
+        let valid_key = !key.is_empty()
+            && key.len() <= 128
+            && key
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'-'));
+        if !valid_key {
+            issue(
+                issues,
+                format!("{field}.moduleKey").as_str(),
+                "module key must be a 1-128 byte identifier using letters, digits, '_' or '-'",
+            );
+        } else if !module_keys.insert(key) {
+            issue(
+                issues,
+                format!("{field}.moduleKey").as_str(),
+                "duplicate module key",
+            );
+        }
+        validate_path(format!("{field}.source"), &module.source, issues);
+        if !module.source.path.starts_with("./wasm/") || !module.source.path.ends_with(".wasm") {
+            issue(
+                issues,
+                format!("{field}.source").as_str(),
+                "Wasm modules must be .wasm files stored under ./wasm/",
+            );
+        }
+        if module.sha256.len() != 64
+            || !module
+                .sha256
+                .bytes()
+                .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
+        {
+            issue(
+                issues,
+                format!("{field}.sha256").as_str(),
+                "sha256 must be 64 lower-case hex characters",
+            );
+        }
+
+        let capabilities = &module.capabilities;
+        if capabilities.workspace_read
+            && !manifest
+                .permissions
+                .iter()
+                .any(|requirement| requirement.permission == "workspace.read")
+        {
+            issue(
+                issues,
+                format!("{field}.capabilities.workspaceRead").as_str(),
+                "workspace read requires the workspace.read permission",
+            );
+        }
+        if capabilities.env.len() > PLUGIN_WASM_MAX_ENV_GRANTS {
+            issue(
+                issues,
+                format!("{field}.capabilities.env").as_str(),
+                format!("must grant at most {PLUGIN_WASM_MAX_ENV_GRANTS} environment variables"),
+            );
+        }
+        for (env_index, name) in capabilities.env.iter().enumerate() {
+            let valid_name = name.len() <= 128
+                && name
+                    .bytes()
+                    .next()
+                    .is_some_and(|byte| byte.is_ascii_alphabetic() || byte == b'_')
+                && name
+                    .bytes()
+                    .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_');
+            if !valid_name || is_host_controlled_environment_name(name) {
+                issue(
+                    issues,
+                    format!("{field}.capabilities.env[{env_index}]").as_str(),
+                    "environment grant must name a variable that is not controlled by the Host",
+                );
+            }
+        }
+        if !(100..=PLUGIN_WASM_MAX_DURATION_MS).contains(&capabilities.max_duration_ms) {
+            issue(
+                issues,
+                format!("{field}.capabilities.maxDurationMs").as_str(),
+                format!("must be between 100 and {PLUGIN_WASM_MAX_DURATION_MS}"),
+            );
+        }
+        if !(PLUGIN_WASM_MIN_MEMORY_BYTES..=PLUGIN_WASM_MAX_MEMORY_BYTES)
+            .contains(&capabilities.max_memory_bytes)
+        {
+            issue(
+                issues,
+                format!("{field}.capabilities.maxMemoryBytes").as_str(),
+                format!(
+                    "must be between {PLUGIN_WASM_MIN_MEMORY_BYTES} and {PLUGIN_WASM_MAX_MEMORY_BYTES}"
+                ),
+            );
+        }
+    }
+}
+
 fn validate_permissions(
     manifest: &PluginManifest,
     component_keys: &HashSet<String>,
@@ -19,6 +19,8 @@ pub const PLUGIN_RELEASE_SIGNATURE_PURPOSE_V1: &str = "chatos.plugin.release.v1"
 pub const PLUGIN_SIGNING_KEY_USAGE_CATALOG: &str = "catalog";
 pub const PLUGIN_SIGNING_KEY_USAGE_RELEASE: &str = "release";
 
+/// Hash signed as `manifest_sha256`. It includes every `wasmModules[].sha256`, so a release
+/// signature also pins the bytes of each Wasm module in the package.
 pub fn normalized_plugin_manifest_sha256(
     manifest: &PluginManifest,
 ) -> Result<String, serde_json::Error> {
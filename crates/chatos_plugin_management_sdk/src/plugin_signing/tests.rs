@@ -8,8 +8,8 @@ use ring::signature::{Ed25519KeyPair, KeyPair};
 use super::*;
 use crate::{
     parse_plugin_manifest, plugin_component_descriptors, PluginCatalogRecord,
-    PluginComponentSnapshot, PluginLicenseMetadata, PluginManifestSource, PluginPublisher,
-    PluginReleaseRecord,
+    PluginComponentSnapshot, PluginLicenseMetadata, PluginManifestSource, PluginPathRef,
+    PluginPublisher, PluginReleaseRecord, PluginWasmModule,
 };
 
 const ARTIFACT_SHA256: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
@@ -58,6 +58,26 @@ fn rejects_artifact_or_version_tampering() {
     ));
 }
 
+#[test]
+fn release_signature_covers_wasm_module_digests_through_the_manifest() {
+    let mut fixture = signed_fixture();
+    fixture.manifest.wasm_modules.push(PluginWasmModule {
+        module_key: "guard".to_string(),
+        source: PluginPathRef::new("./wasm/guard.wasm"),
+        sha256: "cd".repeat(32),
+        capabilities: Default::default(),
+    });
+    assert!(matches!(
+        verify_plugin_release_signature(
+            fixture.context(),
+            &fixture.manifest,
+            &fixture.signature,
+            &fixture.key,
+        ),
+        Err(PluginSignatureVerificationError::ManifestHashMismatch)
+    ));
+}
+
 #[test]
 fn rejects_placeholder_signatures_and_revoked_keys() {
     let mut fixture = signed_fixture();
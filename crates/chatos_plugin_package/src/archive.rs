@@ -8,11 +8,11 @@ use std::path::Path;
 
 use chatos_plugin_management_sdk::{
     build_plugin_mcp_cloud_runtime_bundle,
-    build_plugin_mcp_cloud_runtime_bundle_with_resolved_runtime, normalize_plugin_relative_path,
-    normalized_plugin_manifest_sha256, parse_plugin_manifest, parse_plugin_mcp_config_servers,
-    plugin_mcp_cloud_runtime_bundle_sha256, PluginComponentKind, PluginExecutionHost,
-    PluginManifest, PluginManifestSource, PluginMcpCloudRuntimeBundle, PluginMcpServer,
-    PluginReleaseRecord,
+    build_plugin_mcp_cloud_runtime_bundle_with_resolved_runtime, is_plugin_wasm_component,
+    normalize_plugin_relative_path, normalized_plugin_manifest_sha256, parse_plugin_manifest,
+    parse_plugin_mcp_config_servers, plugin_mcp_cloud_runtime_bundle_sha256, PluginComponentKind,
+    PluginExecutionHost, PluginManifest, PluginManifestSource, PluginMcpCloudRuntimeBundle,
+    PluginMcpServer, PluginReleaseRecord,
 };
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
@@ -359,6 +359,7 @@ fn verified_package(
     let manifest = parse_plugin_manifest(manifest_raw, manifest_source)
         .map_err(|error| PluginPackageError::Invalid(error.to_string()))?;
     verify_checksums(&files, &file_sha256, checksum_path)?;
+    verify_wasm_modules(&manifest, &files, &file_sha256)?;
     Ok(VerifiedPluginPackage {
         manifest,
         manifest_source,
@@ -402,6 +403,7 @@ pub fn load_verified_plugin_package_directory(
     let manifest = parse_plugin_manifest(manifest_raw, manifest_source)
         .map_err(|error| PluginPackageError::Invalid(error.to_string()))?;
     verify_checksums(&files, &file_sha256, checksum_path)?;
+    verify_wasm_modules(&manifest, &files, &file_sha256)?;
     Ok(VerifiedPluginPackage {
         manifest,
         manifest_source,
@@ -562,6 +564,69 @@ fn verify_checksums(
     Ok(())
 }
 
+/// Binds each declared Wasm module to the digest in the Manifest and requires Hook sets to only
+/// reference declared modules. Hook sets are scanned loosely so Codex Hook files still load.
+fn verify_wasm_modules(
+    manifest: &PluginManifest,
+    files: &BTreeMap<String, Vec<u8>>,
+    file_sha256: &BTreeMap<String, String>,
+) -> Result<(), PluginPackageError> {
+    for module in &manifest.wasm_modules {
+        let path = module.source.path.trim_start_matches("./");
+        let Some(bytes) = files.get(path) else {
+            return invalid(format!("Wasm module {} is missing", module.module_key));
+        };
+        if file_sha256.get(path) != Some(&module.sha256) {
+            return invalid(format!(
+                "Wasm module {} does not match its Manifest digest",
+                module.module_key
+            ));
+        }
+        if !is_plugin_wasm_component(bytes) {
+            return invalid(format!(
+                "Wasm module {} is not a WASI preview 2 component",
+                module.module_key
+            ));
+        }
+    }
+    for hook in &manifest.hooks {
+        let path = hook.source.path.trim_start_matches("./");
+        let Some(document) = files
+            .get(path)
+            .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(bytes).ok())
+        else {
+            continue;
+        };
+        let entrypoints = document
+            .get("hooks")
+            .and_then(serde_json::Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|definition| definition.get("entrypoint"));
+        for entrypoint in entrypoints {
+            if entrypoint.get("type").and_then(serde_json::Value::as_str) != Some("wasm") {
+                continue;
+            }
+            let module = entrypoint
+                .get("module")
+                .and_then(serde_json::Value::as_str)
+                .map(str::trim)
+                .unwrap_or_default();
+            if !manifest
+                .wasm_modules
+                .iter()
+                .any(|declared| declared.module_key == module)
+            {
+                return invalid(format!(
+                    "Hook set {} references undeclared Wasm module {module:?}",
+                    hook.component_key
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 fn verify_sbom(
     files: &BTreeMap<String, Vec<u8>>,
     release: &PluginReleaseRecord,
@@ -440,6 +440,110 @@ fn archive_entry_and_size_limits_are_enforced() {
         .contains("file exceeds the size limit"));
 }
 
+fn wasm_package_files(
+    module: &[u8],
+    manifest_sha256: &str,
+    hook_module: &str,
+) -> BTreeMap<String, Vec<u8>> {
+    let manifest = json!({
+        "name": "wasm-demo",
+        "version": "1.0.0",
+        "description": "Wasm Hook fixture",
+        "author": {"name": "ChatOS"},
+        "hooks": [{"componentKey": "guards", "source": "./hooks/hooks.json"}],
+        "wasmModules": [{
+            "moduleKey": "guard",
+            "source": "./wasm/guard.wasm",
+            "sha256": manifest_sha256,
+            "capabilities": {"workspaceRead": true, "maxDurationMs": 500}
+        }],
+        "interface": {
+            "displayName": "Wasm Demo",
+            "shortDescription": "Wasm demo",
+            "longDescription": "Wasm Hook package fixture.",
+            "developerName": "ChatOS",
+            "category": "Developer Tools"
+        },
+        "permissions": ["workspace.read"]
+    });
+    let hooks = json!({
+        "hooks": [{
+            "id": "guard",
+            "events": ["PreToolUse"],
+            "entrypoint": {"type": "wasm", "module": hook_module}
+        }]
+    });
+    with_checksums(BTreeMap::from([
+        (
+            ".chatos-plugin/plugin.json".to_string(),
+            serde_json::to_vec(&manifest).expect("manifest JSON"),
+        ),
+        (
+            "hooks/hooks.json".to_string(),
+            serde_json::to_vec(&hooks).expect("Hook JSON"),
+        ),
+        ("wasm/guard.wasm".to_string(), module.to_vec()),
+    ]))
+}
+
+fn load_directory(
+    files: &BTreeMap<String, Vec<u8>>,
+) -> Result<VerifiedPluginPackage, chatos_plugin_package::PluginPackageError> {
+    let directory = TempDir::new().expect("temporary Plugin directory");
+    for (path, body) in files {
+        let output = directory.path().join(path);
+        fs::create_dir_all(output.parent().expect("file parent")).expect("create parent");
+        fs::write(output, body).expect("write package file");
+    }
+    let file_sha256 = files
+        .iter()
+        .map(|(path, bytes)| (path.clone(), sha256(bytes)))
+        .collect();
+    load_verified_plugin_package_directory(
+        directory.path(),
+        "a".repeat(64).as_str(),
+        &file_sha256,
+        PluginPackageLimits::default(),
+    )
+}
+
+#[test]
+fn wasm_modules_are_pinned_components_referenced_by_declared_hooks() {
+    let component = b"\0asm\x0d\x00\x01\x00component-body".to_vec();
+    let component_sha256 = sha256(&component);
+    let package = load_directory(&wasm_package_files(
+        &component,
+        component_sha256.as_str(),
+        "guard",
+    ))
+    .expect("verified Wasm package");
+    assert_eq!(package.manifest.wasm_modules[0].sha256, component_sha256);
+    assert_eq!(
+        package.file_sha256.get("wasm/guard.wasm"),
+        Some(&component_sha256)
+    );
+
+    let core_module = b"\0asm\x01\x00\x00\x00".to_vec();
+    let cases = [
+        (
+            wasm_package_files(&core_module, sha256(&core_module).as_str(), "guard"),
+            "not a WASI preview 2 component",
+        ),
+        (
+            wasm_package_files(&component, "b".repeat(64).as_str(), "guard"),
+            "does not match its Manifest digest",
+        ),
+        (
+            wasm_package_files(&component, component_sha256.as_str(), "other"),
+            "undeclared Wasm module",
+        ),
+    ];
+    for (files, expected) in cases {
+        let error = load_directory(&files).expect_err("invalid Wasm package");
+        assert!(error.to_string().contains(expected), "{expected}: {error}");
+    }
+}
+
 fn sha256(bytes: &[u8]) -> String {
     hex::encode(Sha256::digest(bytes))
 }
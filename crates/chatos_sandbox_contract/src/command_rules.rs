@@ -0,0 +1,364 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use serde::{Deserialize, Serialize};
+use toml::Value;
+
+/// Argv token that matches any number of remaining arguments. Only valid as the last token.
+pub const COMMAND_RULE_REST_TOKEN: &str = "**";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandRuleDecision {
+    Allow,
+    Deny,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandRuleScope {
+    #[default]
+    Project,
+    Global,
+}
+
+/// Glob constraints on one argv position; position 0 is the program.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandArgumentConstraint {
+    pub position: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allow: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deny: Vec<String>,
+}
+
+/// Argv pattern rule used by command approval.
+///
+/// `pattern` is matched token by token against argv: `*` and `?` are globs inside one argument,
+/// a bare `*` accepts exactly one argument, and a trailing `**` accepts any remaining arguments.
+/// Without a trailing `**` the argv length must match exactly. `forbidden_args` rejects the rule
+/// when any argument after the program matches, which keeps `git push **` from covering
+/// `git push origin main`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandRule {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub decision: CommandRuleDecision,
+    #[serde(default)]
+    pub scope: CommandRuleScope,
+    pub pattern: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbidden_args: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<CommandArgumentConstraint>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl CommandRule {
+    pub fn validate(&self) -> Result<(), String> {
+        let label = self.label();
+        let Some(program) = self.pattern.first() else {
+            return Err(format!("command rule {label} pattern must not be empty"));
+        };
+        if let Some(position) = self
+            .pattern
+            .iter()
+            .position(|token| token == COMMAND_RULE_REST_TOKEN)
+        {
+            if position + 1 != self.pattern.len() {
+                return Err(format!(
+                    "command rule {label} may only use `**` as the last pattern token"
+                ));
+            }
+        }
+        if self.pattern.iter().any(|token| token.is_empty()) {
+            return Err(format!(
+                "command rule {label} pattern tokens must not be empty"
+            ));
+        }
+        if self.decision == CommandRuleDecision::Allow && contains_command_glob(program) {
+            return Err(format!(
+                "allow rule {label} must name its program without globs"
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns whether `argv` (program first) satisfies the pattern and argument constraints.
+    pub fn matches_argv(&self, argv: &[String]) -> bool {
+        let Some((program, rest)) = argv.split_first() else {
+            return false;
+        };
+        let Some((program_pattern, rest_pattern)) = self.pattern.split_first() else {
+            return false;
+        };
+        if !program_matches(program_pattern, program) || !argv_matches(rest_pattern, rest) {
+            return false;
+        }
+        if rest.iter().any(|arg| {
+            self.forbidden_args
+                .iter()
+                .any(|glob| glob_matches(glob, arg))
+        }) {
+            return false;
+        }
+        self.args.iter().all(|constraint| {
+            let Some(value) = argv.get(constraint.position) else {
+                return constraint.allow.is_empty();
+            };
+            (constraint.allow.is_empty()
+                || constraint
+                    .allow
+                    .iter()
+                    .any(|glob| glob_matches(glob, value)))
+                && !constraint.deny.iter().any(|glob| glob_matches(glob, value))
+        })
+    }
+
+    pub fn display_pattern(&self) -> String {
+        self.pattern.join(" ")
+    }
+
+    fn label(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("`{}`", self.display_pattern()))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CommandRuleDocument {
+    pub rules: Vec<CommandRule>,
+}
+
+/// Parse `[[command_rules]]` entries from a TOML document.
+///
+/// Like the permission profile parser, unrelated top-level keys are ignored so rules can live in
+/// the same `config.toml` as `[permissions]`; keys inside a rule are strict.
+pub fn parse_command_rules_toml(input: &str) -> Result<CommandRuleDocument, String> {
+    let root = toml::from_str::<Value>(input)
+        .map_err(|err| format!("parse command rules TOML failed: {err}"))?;
+    let root = root
+        .as_table()
+        .ok_or_else(|| "command rules TOML root must be a table".to_string())?;
+    let Some(rules) = root.get("command_rules") else {
+        return Ok(CommandRuleDocument::default());
+    };
+    let rules = rules
+        .as_array()
+        .ok_or_else(|| "command_rules must be an array of tables".to_string())?
+        .iter()
+        .enumerate()
+        .map(|(index, value)| parse_rule(index, value))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(CommandRuleDocument { rules })
+}
+
+fn parse_rule(index: usize, value: &Value) -> Result<CommandRule, String> {
+    let label = format!("command_rules[{index}]");
+    let table = value
+        .as_table()
+        .ok_or_else(|| format!("{label} must be a table"))?;
+    if let Some(key) = table.keys().find(|key| {
+        !matches!(
+            key.as_str(),
+            "name" | "decision" | "scope" | "pattern" | "forbidden_args" | "args" | "description"
+        )
+    }) {
+        return Err(format!("unsupported {label} key {key:?}"));
+    }
+    let decision = match required_str(table, "decision", label.as_str())? {
+        "allow" => CommandRuleDecision::Allow,
+        "deny" => CommandRuleDecision::Deny,
+        other => {
+            return Err(format!(
+                "{label}.decision must be allow or deny, got {other:?}"
+            ))
+        }
+    };
+    let scope = match table.get("scope").map(|value| {
+        value
+            .as_str()
+            .ok_or_else(|| format!("{label}.scope must be a string"))
+    }) {
+        None => CommandRuleScope::Project,
+        Some(Ok("project")) => CommandRuleScope::Project,
+        Some(Ok("global")) => CommandRuleScope::Global,
+        Some(Ok(other)) => {
+            return Err(format!(
+                "{label}.scope must be project or global, got {other:?}"
+            ))
+        }
+        Some(Err(err)) => return Err(err),
+    };
+    let pattern = match table.get("pattern") {
+        Some(Value::String(pattern)) => pattern.split_whitespace().map(str::to_string).collect(),
+        Some(value) => string_list(value, format!("{label}.pattern").as_str())?,
+        None => return Err(format!("{label}.pattern is required")),
+    };
+    let forbidden_args = table
+        .get("forbidden_args")
+        .map(|value| string_list(value, format!("{label}.forbidden_args").as_str()))
+        .transpose()?
+        .unwrap_or_default();
+    let args = table
+        .get("args")
+        .map(|value| parse_argument_constraints(value, label.as_str()))
+        .transpose()?
+        .unwrap_or_default();
+    let rule = CommandRule {
+        name: optional_string(table, "name", label.as_str())?,
+        decision,
+        scope,
+        pattern,
+        forbidden_args,
+        args,
+        description: optional_string(table, "description", label.as_str())?,
+    };
+    rule.validate()?;
+    Ok(rule)
+}
+
+fn parse_argument_constraints(
+    value: &Value,
+    label: &str,
+) -> Result<Vec<CommandArgumentConstraint>, String> {
+    let entries = value
+        .as_array()
+        .ok_or_else(|| format!("{label}.args must be an array of tables"))?;
+    entries
+        .iter()
+        .enumerate()
+        .map(|(index, value)| {
+            let label = format!("{label}.args[{index}]");
+            let table = value
+                .as_table()
+                .ok_or_else(|| format!("{label} must be a table"))?;
+            if let Some(key) = table
+                .keys()
+                .find(|key| !matches!(key.as_str(), "position" | "allow" | "deny"))
+            {
+                return Err(format!("unsupported {label} key {key:?}"));
+            }
+            let position = table
+                .get("position")
+                .and_then(Value::as_integer)
+                .and_then(|position| usize::try_from(position).ok())
+                .ok_or_else(|| format!("{label}.position must be a non-negative integer"))?;
+            let list = |key: &str| {
+                table
+                    .get(key)
+                    .map(|value| string_list(value, format!("{label}.{key}").as_str()))
+                    .transpose()
+                    .map(Option::unwrap_or_default)
+            };
+            Ok(CommandArgumentConstraint {
+                position,
+                allow: list("allow")?,
+                deny: list("deny")?,
+            })
+        })
+        .collect()
+}
+
+fn required_str<'a>(
+    table: &'a toml::map::Map<String, Value>,
+    key: &str,
+    label: &str,
+) -> Result<&'a str, String> {
+    table
+        .get(key)
+        .ok_or_else(|| format!("{label}.{key} is required"))?
+        .as_str()
+        .ok_or_else(|| format!("{label}.{key} must be a string"))
+}
+
+fn optional_string(
+    table: &toml::map::Map<String, Value>,
+    key: &str,
+    label: &str,
+) -> Result<Option<String>, String> {
+    table
+        .get(key)
+        .map(|value| {
+            value
+                .as_str()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToOwned::to_owned)
+                .ok_or_else(|| format!("{label}.{key} must be a non-empty string"))
+        })
+        .transpose()
+}
+
+fn string_list(value: &Value, label: &str) -> Result<Vec<String>, String> {
+    value
+        .as_array()
+        .ok_or_else(|| format!("{label} must be an array of strings"))?
+        .iter()
+        .map(|item| {
+            item.as_str()
+                .map(ToOwned::to_owned)
+                .ok_or_else(|| format!("{label} must be an array of strings"))
+        })
+        .collect()
+}
+
+fn program_matches(pattern: &str, program: &str) -> bool {
+    if glob_matches(pattern, program) {
+        return true;
+    }
+    // A bare program name also matches the same program invoked through a path.
+    !pattern.contains(['/', '\\'])
+        && program
+            .rsplit(['/', '\\'])
+            .next()
+            .is_some_and(|base| glob_matches(pattern, base))
+}
+
+fn argv_matches(pattern: &[String], argv: &[String]) -> bool {
+    match pattern.split_first() {
+        None => argv.is_empty(),
+        Some((token, _)) if token == COMMAND_RULE_REST_TOKEN => true,
+        Some((token, rest_pattern)) => argv.split_first().is_some_and(|(arg, rest)| {
+            glob_matches(token, arg) && argv_matches(rest_pattern, rest)
+        }),
+    }
+}
+
+/// Matches `value` against a glob where `*` is any run of characters and `?` is one character.
+pub fn glob_matches(pattern: &str, value: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let value = value.chars().collect::<Vec<_>>();
+    let (mut p, mut v) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while v < value.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, v));
+                p += 1;
+            }
+            Some(expected) if *expected == '?' || *expected == value[v] => {
+                p += 1;
+                v += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    v = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|character| *character == '*')
+}
+
+fn contains_command_glob(value: &str) -> bool {
+    value.contains(['*', '?'])
+}
+
+#[cfg(test)]
+mod tests;
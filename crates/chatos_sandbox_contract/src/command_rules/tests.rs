@@ -0,0 +1,132 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use super::*;
+
+fn argv(line: &str) -> Vec<String> {
+    line.split_whitespace().map(str::to_string).collect()
+}
+
+#[test]
+fn parses_rules_next_to_permission_profiles() {
+    let document = parse_command_rules_toml(
+        r#"
+default_permissions = "project-edit"
+
+[permissions.project-edit.filesystem]
+":minimal" = "read"
+
+[[command_rules]]
+name = "cargo tests"
+decision = "allow"
+pattern = "cargo test **"
+
+[[command_rules]]
+decision = "allow"
+scope = "global"
+pattern = ["git", "push", "**"]
+forbidden_args = ["--force", "-f", "main", "*:main"]
+
+[[command_rules.args]]
+position = 2
+allow = ["origin"]
+
+[[command_rules]]
+decision = "deny"
+pattern = ["terraform", "destroy", "**"]
+"#,
+    )
+    .expect("parse command rules");
+
+    assert_eq!(document.rules.len(), 3);
+    assert_eq!(document.rules[0].pattern, argv("cargo test **"));
+    assert_eq!(document.rules[0].scope, CommandRuleScope::Project);
+    assert_eq!(document.rules[1].scope, CommandRuleScope::Global);
+    assert_eq!(document.rules[1].args[0].allow, vec!["origin".to_string()]);
+    assert_eq!(document.rules[2].decision, CommandRuleDecision::Deny);
+}
+
+#[test]
+fn matches_prefixes_globs_and_argument_constraints() {
+    let document = parse_command_rules_toml(
+        r#"
+[[command_rules]]
+decision = "allow"
+pattern = "cargo test **"
+
+[[command_rules]]
+decision = "allow"
+pattern = "git push **"
+forbidden_args = ["--force", "-f", "main", "*:main"]
+
+[[command_rules.args]]
+position = 2
+allow = ["origin"]
+
+[[command_rules]]
+decision = "allow"
+pattern = "npm run test:* *"
+"#,
+    )
+    .expect("parse command rules");
+    let [cargo, git, npm] = document.rules.as_slice() else {
+        panic!("expected three rules");
+    };
+
+    assert!(cargo.matches_argv(&argv("cargo test")));
+    assert!(cargo.matches_argv(&argv("cargo test -p foo")));
+    assert!(cargo.matches_argv(&argv("/home/me/.cargo/bin/cargo test -p bar")));
+    assert!(!cargo.matches_argv(&argv("cargo build")));
+
+    assert!(git.matches_argv(&argv("git push origin feature/x")));
+    assert!(!git.matches_argv(&argv("git push origin main")));
+    assert!(!git.matches_argv(&argv("git push origin HEAD:main")));
+    assert!(!git.matches_argv(&argv("git push -f origin feature/x")));
+    assert!(!git.matches_argv(&argv("git push upstream feature/x")));
+
+    assert!(npm.matches_argv(&argv("npm run test:unit --silent")));
+    assert!(!npm.matches_argv(&argv("npm run test:unit")));
+    assert!(!npm.matches_argv(&argv("npm run build --silent")));
+}
+
+#[test]
+fn rejects_ambiguous_or_unknown_rule_fields() {
+    for (input, expected) in [
+        (
+            "[[command_rules]]\ndecision = \"allow\"\npattern = \"* **\"\n",
+            "without globs",
+        ),
+        (
+            "[[command_rules]]\ndecision = \"allow\"\npattern = \"git ** push\"\n",
+            "last pattern token",
+        ),
+        (
+            "[[command_rules]]\ndecision = \"maybe\"\npattern = \"ls\"\n",
+            "allow or deny",
+        ),
+        (
+            "[[command_rules]]\ndecision = \"deny\"\npattern = \"ls\"\ncwd = \".\"\n",
+            "unsupported",
+        ),
+        (
+            "[[command_rules]]\ndecision = \"deny\"\npattern = []\n",
+            "empty",
+        ),
+    ] {
+        let err = parse_command_rules_toml(input).expect_err(input);
+        assert!(err.contains(expected), "{input}: {err}");
+    }
+    assert!(parse_command_rules_toml(
+        "[[command_rules]]\ndecision = \"deny\"\npattern = \"* --no-verify **\"\n"
+    )
+    .is_ok());
+}
+
+#[test]
+fn glob_matching_supports_stars_and_single_characters() {
+    assert!(glob_matches("release/*", "release/1.2"));
+    assert!(glob_matches("v?.*", "v1.20"));
+    assert!(glob_matches("*", ""));
+    assert!(!glob_matches("release/*", "main"));
+    assert!(!glob_matches("v?", "v10"));
+}
@@ -3,12 +3,14 @@
 
 use serde::{Deserialize, Serialize};
 
+mod command_rules;
 mod filesystem;
 mod managed_requirements;
 mod permissions;
 mod profiles;
 mod toml_profiles;
 
+pub use command_rules::*;
 pub use filesystem::*;
 pub use managed_requirements::*;
 pub use permissions::*;
@@ -21,6 +23,8 @@ pub enum SandboxBackendKind {
     LocalProcess,
     #[default]
     Docker,
+    /// Local process confined with unprivileged namespaces, Landlock and seccomp.
+    LinuxNative,
 }
 
 impl SandboxBackendKind {
@@ -28,6 +32,7 @@ impl SandboxBackendKind {
         match self {
             Self::LocalProcess => "local_process",
             Self::Docker => "docker",
+            Self::LinuxNative => "linux_native",
         }
     }
 }
@@ -39,6 +44,7 @@ impl std::str::FromStr for SandboxBackendKind {
         match value.trim().to_ascii_lowercase().as_str() {
             "local_process" | "process" => Ok(Self::LocalProcess),
             "docker" => Ok(Self::Docker),
+            "linux_native" | "linux" => Ok(Self::LinuxNative),
             other => Err(format!("unsupported sandbox backend: {other}")),
         }
     }
@@ -343,6 +349,21 @@ mod tests {
         assert_eq!(effective.additional_writable_roots, vec!["C:/tmp"]);
     }
 
+    #[test]
+    fn linux_native_backend_round_trips_through_serde_and_from_str() {
+        let request: SandboxLeasePolicyRequest = serde_json::from_value(serde_json::json!({
+            "sandbox_mode": "linux_native"
+        }))
+        .expect("policy");
+
+        assert_eq!(request.sandbox_mode, Some(SandboxBackendKind::LinuxNative));
+        assert_eq!(SandboxBackendKind::LinuxNative.as_str(), "linux_native");
+        assert_eq!(
+            "Linux".parse::<SandboxBackendKind>(),
+            Ok(SandboxBackendKind::LinuxNative)
+        );
+    }
+
     #[test]
     fn permission_profiles_have_a_stable_restrictiveness_order() {
         assert!(
@@ -4,9 +4,14 @@
 use reqwest::Method;
 
 use crate::models::{
-    EngineSubjectMemory, EngineSubjectMemoryScope, ListResponse, QuerySubjectMemoriesRequest,
-    SdkQuerySubjectMemoriesRequest, SdkUpsertSubjectMemoryScopeRequest,
-    SystemQuerySubjectMemoriesRequest, SystemUpsertSubjectMemoryScopeRequest,
+    EngineSubjectMemory, EngineSubjectMemoryConflict, EngineSubjectMemoryScope,
+    EngineSubjectMemoryVersion, ListResponse, QuerySubjectMemoriesRequest,
+    QuerySubjectMemoryConflictsRequest, ResolveSubjectMemoryConflictRequest,
+    SdkGetSubjectMemoryRequest, SdkQuerySubjectMemoriesRequest,
+    SdkQuerySubjectMemoryConflictsRequest, SdkResolveSubjectMemoryConflictRequest,
+    SdkUpdateSubjectMemoryRequest, SdkUpsertSubjectMemoryScopeRequest,
+    SubjectMemoryProvenanceResponse, SystemQuerySubjectMemoriesRequest,
+    SystemUpsertSubjectMemoryScopeRequest, UpdateSubjectMemoryRequest,
     UpsertSubjectMemoryScopeRequest,
 };
 
@@ -149,4 +154,183 @@ impl MemoryEngineClient {
         };
         Ok(resp.items)
     }
+
+    /// Applies a user edit (text, pin, expiry) to a subject memory. Text and pin
+    /// changes bump the memory version and are kept in its version history.
+    pub async fn update_subject_memory(
+        &self,
+        memory_id: &str,
+        req: &UpdateSubjectMemoryRequest,
+    ) -> Result<EngineSubjectMemory, String> {
+        match &self.auth {
+            AuthMode::Direct { .. } => {
+                self.send_json(
+                    Method::PATCH,
+                    &format!("/subject-memories/{}", urlencoding::encode(memory_id)),
+                    Some(req),
+                )
+                .await
+            }
+            AuthMode::SystemKey { .. } => {
+                let direct = SdkUpdateSubjectMemoryRequest {
+                    tenant_id: req.tenant_id.clone(),
+                    text: req.text.clone(),
+                    pinned: req.pinned,
+                    expires_at: req.expires_at.clone(),
+                    ttl_seconds: req.ttl_seconds,
+                    clear_expiry: req.clear_expiry,
+                    expected_version: req.expected_version,
+                    edited_by: req.edited_by.clone(),
+                    reason: req.reason.clone(),
+                };
+                self.send_json(
+                    Method::PATCH,
+                    &format!("/sdk/subject-memories/{}", urlencoding::encode(memory_id)),
+                    Some(&direct),
+                )
+                .await
+            }
+        }
+    }
+
+    pub async fn list_subject_memory_versions(
+        &self,
+        memory_id: &str,
+        tenant_id: &str,
+    ) -> Result<Vec<EngineSubjectMemoryVersion>, String> {
+        let resp: ListResponse<EngineSubjectMemoryVersion> = match &self.auth {
+            AuthMode::Direct { source_id } => {
+                let source_id =
+                    require_direct_source_id(source_id, "list_subject_memory_versions")?;
+                self.send_json(
+                    Method::GET,
+                    &format!(
+                        "/subject-memories/{}/versions?tenant_id={}&source_id={}",
+                        urlencoding::encode(memory_id),
+                        urlencoding::encode(tenant_id),
+                        urlencoding::encode(source_id)
+                    ),
+                    Option::<&()>::None,
+                )
+                .await?
+            }
+            AuthMode::SystemKey { .. } => {
+                self.send_json(
+                    Method::POST,
+                    &format!(
+                        "/sdk/subject-memories/{}/versions",
+                        urlencoding::encode(memory_id)
+                    ),
+                    Some(&SdkGetSubjectMemoryRequest {
+                        tenant_id: tenant_id.to_string(),
+                    }),
+                )
+                .await?
+            }
+        };
+        Ok(resp.items)
+    }
+
+    pub async fn get_subject_memory_provenance(
+        &self,
+        memory_id: &str,
+        tenant_id: &str,
+    ) -> Result<SubjectMemoryProvenanceResponse, String> {
+        match &self.auth {
+            AuthMode::Direct { source_id } => {
+                let source_id =
+                    require_direct_source_id(source_id, "get_subject_memory_provenance")?;
+                self.send_json::<SubjectMemoryProvenanceResponse, _>(
+                    Method::GET,
+                    &format!(
+                        "/subject-memories/{}/provenance?tenant_id={}&source_id={}",
+                        urlencoding::encode(memory_id),
+                        urlencoding::encode(tenant_id),
+                        urlencoding::encode(source_id)
+                    ),
+                    Option::<&()>::None,
+                )
+                .await
+            }
+            AuthMode::SystemKey { .. } => {
+                self.send_json(
+                    Method::POST,
+                    &format!(
+                        "/sdk/subject-memories/{}/provenance",
+                        urlencoding::encode(memory_id)
+                    ),
+                    Some(&SdkGetSubjectMemoryRequest {
+                        tenant_id: tenant_id.to_string(),
+                    }),
+                )
+                .await
+            }
+        }
+    }
+
+    /// Lists contradictions that rollups flagged for review.
+    pub async fn query_subject_memory_conflicts(
+        &self,
+        req: &QuerySubjectMemoryConflictsRequest,
+    ) -> Result<Vec<EngineSubjectMemoryConflict>, String> {
+        let resp: ListResponse<EngineSubjectMemoryConflict> = match &self.auth {
+            AuthMode::Direct { .. } => {
+                self.send_json(Method::POST, "/subject-memory-conflicts/query", Some(req))
+                    .await?
+            }
+            AuthMode::SystemKey { .. } => {
+                let direct = SdkQuerySubjectMemoryConflictsRequest {
+                    tenant_id: req.tenant_id.clone(),
+                    subject_id: req.subject_id.clone(),
+                    status: req.status.clone(),
+                    limit: req.limit,
+                    offset: req.offset,
+                };
+                self.send_json(
+                    Method::POST,
+                    "/sdk/subject-memory-conflicts/query",
+                    Some(&direct),
+                )
+                .await?
+            }
+        };
+        Ok(resp.items)
+    }
+
+    pub async fn resolve_subject_memory_conflict(
+        &self,
+        conflict_id: &str,
+        req: &ResolveSubjectMemoryConflictRequest,
+    ) -> Result<EngineSubjectMemoryConflict, String> {
+        match &self.auth {
+            AuthMode::Direct { .. } => {
+                self.send_json(
+                    Method::POST,
+                    &format!(
+                        "/subject-memory-conflicts/{}/resolve",
+                        urlencoding::encode(conflict_id)
+                    ),
+                    Some(req),
+                )
+                .await
+            }
+            AuthMode::SystemKey { .. } => {
+                let direct = SdkResolveSubjectMemoryConflictRequest {
+                    tenant_id: req.tenant_id.clone(),
+                    action: req.action.clone(),
+                    resolved_by: req.resolved_by.clone(),
+                    note: req.note.clone(),
+                };
+                self.send_json(
+                    Method::POST,
+                    &format!(
+                        "/sdk/subject-memory-conflicts/{}/resolve",
+                        urlencoding::encode(conflict_id)
+                    ),
+                    Some(&direct),
+                )
+                .await
+            }
+        }
+    }
 }
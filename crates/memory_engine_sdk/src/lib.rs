@@ -13,25 +13,30 @@ pub use self::models::{
     ComposeContextPolicy, ComposeContextRequest, ComposeContextResponse,
     CountThreadRecordsResponse, DashboardOverviewResponse, DeleteThreadResponse, EngineJobPolicy,
     EngineJobRun, EngineModelProfile, EngineRecord, EngineSource, EngineSubjectMemory,
-    EngineSubjectMemoryScope, EngineSummary, EngineThread, EngineThreadSnapshot,
-    GenerateJobPolicyPromptRequest, GenerateJobPolicyPromptResponse, GetThreadResponse,
-    JobRunsBundleResponse, ListJobRunsRequest, ListResponse, ListSourcesRequest,
-    ListSummariesByThreadLabelRequest, ManagedMemoryPolicy, ManagedMemoryPolicyBundle,
-    MemoryPolicyKind, QuerySubjectMemoriesRequest, RotateSourceSecretResponse,
-    RunPendingRollupsResponse, RunPendingSummariesResponse, RunSubjectMemoryScopesResponse,
-    RunThreadActiveSummaryResponse, RunThreadRepairSummaryResponse, RunThreadSummaryResponse,
-    SdkAuthStatusResponse, SdkBatchSyncRecordsRequest, SdkComposeContextRequest,
-    SdkCountThreadRecordsRequest, SdkDeleteThreadRecordsRequest, SdkDeleteThreadSummaryRequest,
-    SdkGetLatestThreadSnapshotRequest, SdkGetRecordRequest, SdkGetThreadActiveSummaryStatusRequest,
-    SdkGetThreadRequest, SdkGetThreadSnapshotByTurnRequest, SdkGetTurnProcessRecordsRequest,
-    SdkListCompactTurnsRequest, SdkListSummariesByThreadLabelRequest, SdkListThreadRecordsRequest,
+    EngineSubjectMemoryConflict, EngineSubjectMemoryScope, EngineSubjectMemoryVersion,
+    EngineSummary, EngineThread, EngineThreadSnapshot, GenerateJobPolicyPromptRequest,
+    GenerateJobPolicyPromptResponse, GetThreadResponse, JobRunsBundleResponse, ListJobRunsRequest,
+    ListResponse, ListSourcesRequest, ListSummariesByThreadLabelRequest, ManagedMemoryPolicy,
+    ManagedMemoryPolicyBundle, MemoryPolicyKind, QuerySubjectMemoriesRequest,
+    QuerySubjectMemoryConflictsRequest, ResolveSubjectMemoryConflictRequest,
+    RotateSourceSecretResponse, RunPendingRollupsResponse, RunPendingSummariesResponse,
+    RunSubjectMemoryScopesResponse, RunThreadActiveSummaryResponse, RunThreadRepairSummaryResponse,
+    RunThreadSummaryResponse, SdkAuthStatusResponse, SdkBatchSyncRecordsRequest,
+    SdkComposeContextRequest, SdkCountThreadRecordsRequest, SdkDeleteThreadRecordsRequest,
+    SdkDeleteThreadSummaryRequest, SdkGetLatestThreadSnapshotRequest, SdkGetRecordRequest,
+    SdkGetSubjectMemoryRequest, SdkGetThreadActiveSummaryStatusRequest, SdkGetThreadRequest,
+    SdkGetThreadSnapshotByTurnRequest, SdkGetTurnProcessRecordsRequest, SdkListCompactTurnsRequest,
+    SdkListSummariesByThreadLabelRequest, SdkListThreadRecordsRequest,
     SdkListThreadSummariesRequest, SdkListThreadsRequest, SdkQuerySubjectMemoriesRequest,
+    SdkQuerySubjectMemoryConflictsRequest, SdkResolveSubjectMemoryConflictRequest,
     SdkRunPendingRollupsRequest, SdkRunPendingSummariesRequest, SdkRunSubjectMemoryScopesRequest,
     SdkRunThreadActiveSummaryRequest, SdkRunThreadRepairSummaryRequest, SdkRunThreadSummaryRequest,
-    SdkUpsertSubjectMemoryScopeRequest, SdkUpsertThreadRequest, SdkUpsertThreadSnapshotRequest,
+    SdkUpdateSubjectMemoryRequest, SdkUpsertSubjectMemoryScopeRequest, SdkUpsertThreadRequest,
+    SdkUpsertThreadSnapshotRequest, SubjectMemoryProvenance, SubjectMemoryProvenanceResponse,
     SystemListSummariesByThreadLabelRequest, SystemQuerySubjectMemoriesRequest,
     SystemUpsertSubjectMemoryScopeRequest, ThreadRecordsPageResponse, ThreadSnapshotLookupResponse,
-    TurnProcessRecordsResponse, TurnRecordSlice, UpsertEngineJobPolicyRequest,
-    UpsertEngineModelProfileRequest, UpsertRecordInput, UpsertSourceRequest,
-    UpsertSubjectMemoryScopeRequest, UpsertThreadSnapshotRequest, MEMORY_POLICY_CONFIG_PREFIX,
+    TurnProcessRecordsResponse, TurnRecordSlice, UpdateSubjectMemoryRequest,
+    UpsertEngineJobPolicyRequest, UpsertEngineModelProfileRequest, UpsertRecordInput,
+    UpsertSourceRequest, UpsertSubjectMemoryScopeRequest, UpsertThreadSnapshotRequest,
+    MEMORY_POLICY_CONFIG_PREFIX,
 };
@@ -16,6 +16,10 @@ pub fn default_pending() -> String {
     "pending".to_string()
 }
 
+pub fn default_memory_version() -> i64 {
+    1
+}
+
 pub fn default_idle() -> String {
     "idle".to_string()
 }
@@ -5,13 +5,21 @@ use serde::{Deserialize, Serialize};
 
 use super::records::EngineRecord;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ComposeContextPolicy {
     pub include_recent_records: Option<bool>,
     pub include_thread_summary: Option<bool>,
     pub include_subject_memory: Option<bool>,
     pub recent_record_limit: Option<usize>,
     pub summary_limit: Option<usize>,
+    /// Text the caller is about to answer; enables semantic recall when set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub semantic_top_k: Option<usize>,
+    /// Approximate token budget shared by semantic hits and recent records.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_budget: Option<usize>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -43,6 +51,8 @@ pub struct ComposeContextBlock {
 pub struct ComposeContextMeta {
     pub summary_count: usize,
     pub recent_record_count: usize,
+    #[serde(default)]
+    pub semantic_hit_count: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
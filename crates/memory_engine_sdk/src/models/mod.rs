@@ -17,7 +17,9 @@ pub use self::admin::{
     ListJobRunsRequest, ListSourcesRequest, RotateSourceSecretResponse, SdkAuthStatusResponse,
     UpsertEngineJobPolicyRequest, UpsertEngineModelProfileRequest, UpsertSourceRequest,
 };
-pub use self::common::{default_active, default_idle, default_pending, ListResponse};
+pub use self::common::{
+    default_active, default_idle, default_memory_version, default_pending, ListResponse,
+};
 pub use self::context::{
     ComposeContextBlock, ComposeContextMeta, ComposeContextPolicy, ComposeContextRequest,
     ComposeContextResponse, SdkComposeContextRequest,
@@ -38,11 +40,15 @@ pub use self::snapshots::{
     SdkUpsertThreadSnapshotRequest, ThreadSnapshotLookupResponse, UpsertThreadSnapshotRequest,
 };
 pub use self::subject_memories::{
-    EngineSubjectMemory, EngineSubjectMemoryScope, QuerySubjectMemoriesRequest,
-    RunSubjectMemoryScopesResponse, SdkQuerySubjectMemoriesRequest,
-    SdkRunSubjectMemoryScopesRequest, SdkUpsertSubjectMemoryScopeRequest,
+    EngineSubjectMemory, EngineSubjectMemoryConflict, EngineSubjectMemoryScope,
+    EngineSubjectMemoryVersion, QuerySubjectMemoriesRequest, QuerySubjectMemoryConflictsRequest,
+    ResolveSubjectMemoryConflictRequest, RunSubjectMemoryScopesResponse,
+    SdkGetSubjectMemoryRequest, SdkQuerySubjectMemoriesRequest,
+    SdkQuerySubjectMemoryConflictsRequest, SdkResolveSubjectMemoryConflictRequest,
+    SdkRunSubjectMemoryScopesRequest, SdkUpdateSubjectMemoryRequest,
+    SdkUpsertSubjectMemoryScopeRequest, SubjectMemoryProvenance, SubjectMemoryProvenanceResponse,
     SystemQuerySubjectMemoriesRequest, SystemUpsertSubjectMemoryScopeRequest,
-    UpsertSubjectMemoryScopeRequest,
+    UpdateSubjectMemoryRequest, UpsertSubjectMemoryScopeRequest,
 };
 pub use self::summaries::{
     EngineSummary, ListSummariesByThreadLabelRequest, RunPendingRollupsResponse,
@@ -121,4 +127,26 @@ mod contract_tests {
             snapshot
         );
     }
+
+    #[test]
+    fn compose_context_policy_round_trips_semantic_fields() {
+        let snapshot = serde_json::json!({
+            "include_recent_records": true,
+            "include_thread_summary": null,
+            "include_subject_memory": null,
+            "recent_record_limit": null,
+            "summary_limit": null,
+            "query": "how do we deploy the worker?",
+            "semantic_top_k": 4,
+            "token_budget": 1200
+        });
+
+        let policy: ComposeContextPolicy =
+            serde_json::from_value(snapshot.clone()).expect("decode context policy");
+        assert_eq!(policy.semantic_top_k, Some(4));
+        assert_eq!(
+            serde_json::to_value(policy).expect("encode context policy"),
+            snapshot
+        );
+    }
 }
@@ -4,7 +4,7 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use super::{default_active, default_pending};
+use super::{default_active, default_memory_version, default_pending, EngineRecord, EngineSummary};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EngineSubjectMemory {
@@ -26,10 +26,187 @@ pub struct EngineSubjectMemory {
     pub rollup_status: String,
     pub rollup_memory_key: Option<String>,
     pub rolled_up_at: Option<String>,
+    /// Pinned memories are never selected, rewritten or dropped by rollup jobs.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Memories past this instant are hidden from listings and context.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    #[serde(default = "default_memory_version")]
+    pub version: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<SubjectMemoryProvenance>,
+    /// Id of the memory that replaced this one. Superseded memories carry
+    /// `status = "superseded"` and are left out of listings and context.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub superseded_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supersession_reason: Option<String>,
     pub created_at: String,
     pub updated_at: String,
 }
 
+/// Inputs a generated memory was built from: the source summaries of a level0
+/// memory, or the lower-level memories of a rollup.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubjectMemoryProvenance {
+    #[serde(default)]
+    pub source_summary_ids: Vec<String>,
+    #[serde(default)]
+    pub source_memory_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineSubjectMemoryVersion {
+    pub id: String,
+    pub tenant_id: String,
+    pub source_id: String,
+    pub memory_id: String,
+    pub subject_id: String,
+    pub memory_key: String,
+    pub version: i64,
+    pub text: String,
+    #[serde(default)]
+    pub pinned: bool,
+    pub expires_at: Option<String>,
+    pub edited_by: Option<String>,
+    pub reason: Option<String>,
+    pub created_at: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UpdateSubjectMemoryRequest {
+    pub tenant_id: String,
+    pub source_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_seconds: Option<i64>,
+    #[serde(default)]
+    pub clear_expiry: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_version: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SdkUpdateSubjectMemoryRequest {
+    pub tenant_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ttl_seconds: Option<i64>,
+    #[serde(default)]
+    pub clear_expiry: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_version: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SdkGetSubjectMemoryRequest {
+    pub tenant_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubjectMemoryProvenanceResponse {
+    pub memory: EngineSubjectMemory,
+    pub source_summaries: Vec<EngineSummary>,
+    pub source_memories: Vec<EngineSubjectMemory>,
+    pub source_records: Vec<EngineRecord>,
+}
+
+/// A contradiction between two memories that a rollup could not settle on its
+/// own. `left` is the older memory and `right` the newer one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineSubjectMemoryConflict {
+    pub id: String,
+    pub tenant_id: String,
+    pub source_id: String,
+    pub subject_id: String,
+    pub memory_type: String,
+    pub left_memory_id: String,
+    pub left_memory_key: String,
+    pub right_memory_id: String,
+    pub right_memory_key: String,
+    pub rollup_memory_key: Option<String>,
+    pub reason: String,
+    #[serde(default = "default_open")]
+    pub status: String,
+    pub resolution: Option<String>,
+    pub resolved_by: Option<String>,
+    pub resolution_note: Option<String>,
+    pub resolved_at: Option<String>,
+    pub created_at: String,
+    pub updated_at: String,
+}
+
+fn default_open() -> String {
+    "open".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuerySubjectMemoryConflictsRequest {
+    pub tenant_id: String,
+    pub source_id: String,
+    pub subject_id: Option<String>,
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SdkQuerySubjectMemoryConflictsRequest {
+    pub tenant_id: String,
+    pub subject_id: Option<String>,
+    pub status: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// `action` is `keep_left` or `keep_right` to supersede the other memory, or
+/// `dismiss` when both memories should stay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolveSubjectMemoryConflictRequest {
+    pub tenant_id: String,
+    pub source_id: String,
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SdkResolveSubjectMemoryConflictRequest {
+    pub tenant_id: String,
+    pub action: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_by: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EngineSubjectMemoryScope {
     pub id: String,
@@ -128,7 +305,10 @@ pub struct RunSubjectMemoryScopesResponse {
 
 #[cfg(test)]
 mod tests {
-    use super::{EngineSubjectMemory, EngineSubjectMemoryScope};
+    use super::{
+        EngineSubjectMemory, EngineSubjectMemoryConflict, EngineSubjectMemoryScope,
+        UpdateSubjectMemoryRequest,
+    };
 
     #[test]
     fn engine_subject_memory_defaults_status_fields() {
@@ -154,6 +334,57 @@ mod tests {
 
         assert_eq!(memory.status, "active");
         assert_eq!(memory.rollup_status, "pending");
+        assert!(!memory.pinned);
+        assert_eq!(memory.version, 1);
+        assert!(memory.expires_at.is_none());
+        assert!(memory.provenance.is_none());
+        assert!(memory.superseded_by.is_none());
+    }
+
+    #[test]
+    fn update_subject_memory_request_omits_unset_fields() {
+        let request = UpdateSubjectMemoryRequest {
+            tenant_id: "tenant-1".to_string(),
+            source_id: "source-1".to_string(),
+            pinned: Some(true),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(&request).expect("serialize"),
+            serde_json::json!({
+                "tenant_id": "tenant-1",
+                "source_id": "source-1",
+                "pinned": true,
+                "clear_expiry": false
+            })
+        );
+    }
+
+    #[test]
+    fn engine_subject_memory_conflict_defaults_status_to_open() {
+        let conflict: EngineSubjectMemoryConflict = serde_json::from_value(serde_json::json!({
+            "id": "conflict-1",
+            "tenant_id": "tenant-1",
+            "source_id": "source-1",
+            "subject_id": "subject-1",
+            "memory_type": "agent_recall",
+            "left_memory_id": "mem-1",
+            "left_memory_key": "agent_recall:l0:a",
+            "right_memory_id": "mem-2",
+            "right_memory_key": "agent_recall:l0:b",
+            "rollup_memory_key": null,
+            "reason": "database choice differs",
+            "resolution": null,
+            "resolved_by": null,
+            "resolution_note": null,
+            "resolved_at": null,
+            "created_at": "2026-05-21T00:00:00Z",
+            "updated_at": "2026-05-21T00:00:00Z"
+        }))
+        .expect("conflict");
+
+        assert_eq!(conflict.status, "open");
     }
 
     #[test]
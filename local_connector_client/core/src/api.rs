@@ -35,20 +35,20 @@ use handlers::{
     local_check_agent_prompt_updates, local_check_plugin_updates, local_chrome_integration_status,
     local_chrome_native_connect, local_chrome_native_disconnect, local_chrome_native_event,
     local_chrome_native_next, local_clear_command_history, local_command_history,
-    local_complete_plugin_oauth, local_complete_plugin_oauth_query, local_delete_mcp_config,
-    local_delete_plugin_credential, local_deny_pending_approval, local_desktop_ticket,
-    local_disable_chrome_integration, local_disable_mcp_config, local_disconnect_plugin_oauth,
-    local_enable_chrome_integration, local_enable_mcp_config, local_fs_list_handler,
-    local_get_mcp_config, local_install_plugin, local_login, local_logout, local_mcp_configs,
-    local_model_configs, local_model_settings, local_pending_approvals, local_plugin_catalog,
-    local_plugin_credentials, local_plugin_events, local_plugin_oauth_connections,
-    local_plugin_status, local_recover_plugin_transactions, local_refresh_model_configs,
-    local_register, local_remove_workspace, local_request_system_permission, local_rollback_plugin,
-    local_runtime_settings, local_sandbox_capabilities, local_sandbox_leases,
-    local_sandbox_settings, local_save_mcp_config, local_send_register_email_code,
-    local_shutdown_sandboxes, local_skills, local_status, local_sync_mcp_config,
-    local_sync_skill_inventory, local_system_permissions, local_terminal_exec,
-    local_test_mcp_config, local_toggle_sandbox, local_uninstall_plugin,
+    local_complete_plugin_oauth, local_complete_plugin_oauth_query, local_delete_command_rule,
+    local_delete_mcp_config, local_delete_plugin_credential, local_deny_pending_approval,
+    local_desktop_ticket, local_disable_chrome_integration, local_disable_mcp_config,
+    local_disconnect_plugin_oauth, local_enable_chrome_integration, local_enable_mcp_config,
+    local_fs_list_handler, local_get_mcp_config, local_import_command_rules, local_install_plugin,
+    local_login, local_logout, local_mcp_configs, local_model_configs, local_model_settings,
+    local_pending_approvals, local_plugin_catalog, local_plugin_credentials, local_plugin_events,
+    local_plugin_oauth_connections, local_plugin_status, local_recover_plugin_transactions,
+    local_refresh_model_configs, local_register, local_remove_workspace,
+    local_request_system_permission, local_rollback_plugin, local_runtime_settings,
+    local_sandbox_capabilities, local_sandbox_leases, local_sandbox_settings,
+    local_save_mcp_config, local_send_register_email_code, local_shutdown_sandboxes, local_skills,
+    local_status, local_sync_mcp_config, local_sync_skill_inventory, local_system_permissions,
+    local_terminal_exec, local_test_mcp_config, local_toggle_sandbox, local_uninstall_plugin,
     local_update_agent_prompt_bundle, local_update_approval_settings, local_update_mcp_config,
     local_update_model_settings, local_update_plugin_preference, local_update_runtime_settings,
     local_update_sandbox_settings, local_update_skill_preference,
@@ -304,6 +304,14 @@ fn local_api_routes(desktop_auth_token: Option<String>) -> Router<LocalRuntime>
             "/api/local/approval/settings",
             get(local_approval_settings).post(local_update_approval_settings),
         )
+        .route(
+            "/api/local/approval/rules/import",
+            post(local_import_command_rules),
+        )
+        .route(
+            "/api/local/approval/rules/{id}",
+            delete(local_delete_command_rule),
+        )
         .route("/api/local/approval/pending", get(local_pending_approvals))
         .route(
             "/api/local/approval/pending/{id}/approve",
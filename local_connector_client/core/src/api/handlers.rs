@@ -23,8 +23,9 @@ pub(super) use agent_prompts::{
     local_agent_prompt_status, local_check_agent_prompt_updates, local_update_agent_prompt_bundle,
 };
 pub(super) use approval::{
-    local_approval_settings, local_approve_pending_approval, local_deny_pending_approval,
-    local_pending_approvals, local_update_approval_settings,
+    local_approval_settings, local_approve_pending_approval, local_delete_command_rule,
+    local_deny_pending_approval, local_import_command_rules, local_pending_approvals,
+    local_update_approval_settings,
 };
 pub(super) use auth::{
     local_desktop_ticket, local_login, local_logout, local_register, local_send_register_email_code,
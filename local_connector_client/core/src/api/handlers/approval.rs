@@ -4,17 +4,22 @@
 use axum::extract::{Path, State};
 use axum::Json;
 use chatos_sandbox_contract::{
-    CommandExecutionApprovalDecision, SimpleCommandExecutionApprovalDecision,
+    parse_command_rules_toml, CommandExecutionApprovalDecision, CommandRuleDecision,
+    SimpleCommandExecutionApprovalDecision,
 };
 use serde_json::{json, Value};
 
 use crate::approval::{
-    approve_pending_approval, deny_pending_approval, list_in_progress_approvals,
-    list_pending_approvals, ApprovalMode, ProjectApprovalState,
+    approve_pending_approval, build_command_rule_entries, deny_pending_approval,
+    list_in_progress_approvals, list_pending_approvals, ApprovalMode, ProjectApprovalState,
 };
 use crate::{local_now_rfc3339, LocalRuntime};
 
-use super::super::types::{LocalApiError, ResolveApprovalRequest, UpdateApprovalSettingsRequest};
+use super::super::types::{
+    ImportCommandRulesRequest, LocalApiError, ResolveApprovalRequest, UpdateApprovalSettingsRequest,
+};
+
+const MAX_COMMAND_RULES: usize = 500;
 
 pub(crate) async fn local_approval_settings(
     State(runtime): State<LocalRuntime>,
@@ -47,6 +52,67 @@ pub(crate) async fn local_update_approval_settings(
     Ok(Json(approval_settings_payload(&state.approval)))
 }
 
+pub(crate) async fn local_import_command_rules(
+    State(runtime): State<LocalRuntime>,
+    Json(req): Json<ImportCommandRulesRequest>,
+) -> Result<Json<Value>, LocalApiError> {
+    let document =
+        parse_command_rules_toml(req.toml.as_str()).map_err(LocalApiError::bad_request)?;
+    if document.rules.is_empty() {
+        return Err(LocalApiError::bad_request(
+            "TOML does not contain any [[command_rules]] entries",
+        ));
+    }
+    if document
+        .rules
+        .iter()
+        .any(|rule| rule.decision == CommandRuleDecision::Allow)
+        && !req.risk_acknowledged
+    {
+        return Err(LocalApiError::conflict_code(
+            "approval_risk_ack_required",
+            "importing allow rules requires explicit risk acknowledgement",
+        ));
+    }
+    let entries = build_command_rule_entries(document, req.project_key.as_ref())
+        .map_err(LocalApiError::bad_request)?;
+
+    let mut state = runtime.state.write().await;
+    let mut rules = state.approval.command_rules.clone();
+    if req.replace {
+        rules.retain(|existing| {
+            !entries
+                .iter()
+                .any(|entry| entry.project_key == existing.project_key)
+        });
+    }
+    rules.extend(entries);
+    if rules.len() > MAX_COMMAND_RULES {
+        return Err(LocalApiError::bad_request(format!(
+            "at most {MAX_COMMAND_RULES} command rules can be stored"
+        )));
+    }
+    state.approval.command_rules = rules;
+    state.approval.settings_revision = Some(format!("local-{}", local_now_rfc3339()));
+    state.save(runtime.state_path.as_path())?;
+    Ok(Json(approval_settings_payload(&state.approval)))
+}
+
+pub(crate) async fn local_delete_command_rule(
+    State(runtime): State<LocalRuntime>,
+    Path(id): Path<String>,
+) -> Result<Json<Value>, LocalApiError> {
+    let mut state = runtime.state.write().await;
+    let before = state.approval.command_rules.len();
+    state.approval.command_rules.retain(|entry| entry.id != id);
+    if state.approval.command_rules.len() == before {
+        return Err(LocalApiError::bad_request("command rule not found"));
+    }
+    state.approval.settings_revision = Some(format!("local-{}", local_now_rfc3339()));
+    state.save(runtime.state_path.as_path())?;
+    Ok(Json(approval_settings_payload(&state.approval)))
+}
+
 fn validate_approval_settings_update(
     req: &UpdateApprovalSettingsRequest,
     current: &crate::approval::ApprovalState,
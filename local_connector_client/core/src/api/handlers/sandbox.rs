@@ -11,8 +11,9 @@ use chatos_sandbox_contract::{
 use serde_json::{json, Value};
 
 use crate::sandbox::lease::shutdown_local_sandboxes;
-use crate::sandbox::local_connector_execution_capability;
+use crate::sandbox::linux_native::linux_native_execution_capability;
 use crate::sandbox::types::LocalSandboxNetworkAccess;
+use crate::sandbox::{local_connector_execution_capability, local_execution_capability};
 use crate::{local_now_rfc3339, LocalRuntime};
 
 use super::super::types::{LocalApiError, ToggleSandboxRequest, UpdateSandboxSettingsRequest};
@@ -161,10 +162,10 @@ fn validate_sandbox_settings_update(
 ) -> Result<(), LocalApiError> {
     if req
         .default_backend
-        .is_some_and(|backend| backend != SandboxBackendKind::LocalProcess)
+        .is_some_and(|backend| !is_local_execution_backend(backend))
     {
         return Err(LocalApiError::bad_request(
-            "the local client only supports the local_process execution backend",
+            "the local client only supports the local_process and linux_native execution backends",
         ));
     }
     let prospective = prospective_sandbox_state(req, current);
@@ -519,16 +520,24 @@ fn sandbox_settings_payload(sandbox: &crate::sandbox::types::LocalSandboxState)
 
 async fn local_sandbox_backend_capabilities() -> Vec<SandboxBackendCapability> {
     let process_capability = local_connector_execution_capability();
-    vec![process_capability]
+    let linux_native_capability = linux_native_execution_capability();
+    vec![process_capability, linux_native_capability]
+}
+
+fn is_local_execution_backend(backend: SandboxBackendKind) -> bool {
+    matches!(
+        backend,
+        SandboxBackendKind::LocalProcess | SandboxBackendKind::LinuxNative
+    )
 }
 
 async fn ensure_sandbox_backend_ready(backend: SandboxBackendKind) -> Result<(), LocalApiError> {
-    if backend != SandboxBackendKind::LocalProcess {
+    if !is_local_execution_backend(backend) {
         return Err(LocalApiError::bad_request(
-            "the local client only supports the local_process execution backend",
+            "the local client only supports the local_process and linux_native execution backends",
         ));
     }
-    let capability = local_connector_execution_capability();
+    let capability = local_execution_capability(backend);
     if capability.status == SandboxBackendReadinessStatus::Ready {
         Ok(())
     } else {
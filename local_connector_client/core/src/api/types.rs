@@ -10,7 +10,7 @@ use chatos_sandbox_contract::{
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 
-use crate::approval::{ApprovalMode, ProjectApprovalState};
+use crate::approval::{ApprovalMode, ApprovalProjectKey, ProjectApprovalState};
 use crate::model_configs::{LocalModelConfigPublic, LocalModelSettings};
 use crate::AuthUserState;
 
@@ -243,6 +243,19 @@ pub(super) struct UpdateApprovalSettingsRequest {
     pub(super) risk_acknowledged: bool,
 }
 
+#[derive(Debug, Deserialize)]
+pub(super) struct ImportCommandRulesRequest {
+    pub(super) toml: String,
+    /// Project that `scope = "project"` rules are imported into.
+    #[serde(default)]
+    pub(super) project_key: Option<ApprovalProjectKey>,
+    /// Drop existing rules of the scopes being imported before adding the new ones.
+    #[serde(default)]
+    pub(super) replace: bool,
+    #[serde(default)]
+    pub(super) risk_acknowledged: bool,
+}
+
 #[derive(Debug, Deserialize)]
 pub(super) struct ResolveApprovalRequest {
     pub(super) remember_allow: Option<bool>,
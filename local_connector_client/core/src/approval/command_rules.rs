@@ -0,0 +1,296 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use chatos_sandbox_contract::{CommandRuleDecision, CommandRuleDocument, CommandRuleScope};
+use uuid::Uuid;
+
+use crate::local_now_rfc3339;
+
+use super::risk::{
+    command_line_for_analysis, shell_command_argvs, shell_executed_argvs, ShellCommandArgv,
+};
+use super::types::{ApprovalProjectKey, CommandRuleEntry};
+
+#[derive(Debug)]
+pub(crate) enum CommandRuleVerdict<'a> {
+    /// A deny rule matched one of the simple commands; this wins over any allow rule.
+    Deny {
+        entry: &'a CommandRuleEntry,
+        command: String,
+    },
+    /// Every simple command in the line is covered by an allow rule.
+    Allow { entries: Vec<&'a CommandRuleEntry> },
+}
+
+impl CommandRuleVerdict<'_> {
+    pub(crate) fn reason(&self) -> String {
+        match self {
+            Self::Deny { entry, command } => format!(
+                "`{command}` is denied by command rule {}",
+                rule_label(entry)
+            ),
+            Self::Allow { entries } => format!(
+                "matched command rule {}",
+                entries
+                    .iter()
+                    .map(|entry| rule_label(entry))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// Checks a request against the global rules and the rules of its project.
+///
+/// The command line is parsed into simple commands so that `cargo test && rm -rf x` is not
+/// covered by a `cargo test **` allow rule. Deny rules also see the commands hidden behind
+/// wrappers, `sh -c` and `eval`, so `sudo terraform destroy` still hits `terraform destroy **`.
+/// Allow verdicts require every simple command to match an allow rule without shell expansions
+/// or file redirections; a line that cannot be parsed is only checked against deny rules, using
+/// a whitespace split.
+pub(crate) fn evaluate_command_rules<'a>(
+    entries: &'a [CommandRuleEntry],
+    project_key: &ApprovalProjectKey,
+    command: &str,
+    args: &[String],
+) -> Option<CommandRuleVerdict<'a>> {
+    let applicable = entries
+        .iter()
+        .filter(|entry| {
+            entry.enabled
+                && entry
+                    .project_key
+                    .as_ref()
+                    .is_none_or(|key| key == project_key)
+        })
+        .collect::<Vec<_>>();
+    if applicable.is_empty() {
+        return None;
+    }
+    let line = command_line_for_analysis(command, args);
+    let parsed = shell_command_argvs(line.as_str());
+    let commands = match &parsed {
+        Ok(commands) => commands.clone(),
+        Err(_) => vec![ShellCommandArgv {
+            argv: line.split_whitespace().map(str::to_string).collect(),
+            dynamic: true,
+            writes_files: false,
+        }],
+    };
+
+    let executed = match shell_executed_argvs(line.as_str()) {
+        Ok(argvs) => argvs,
+        Err(_) => commands.iter().map(|simple| simple.argv.clone()).collect(),
+    };
+    for argv in &executed {
+        if let Some(entry) = applicable.iter().find(|entry| {
+            entry.rule.decision == CommandRuleDecision::Deny
+                && entry.rule.matches_argv(argv.as_slice())
+        }) {
+            return Some(CommandRuleVerdict::Deny {
+                entry,
+                command: argv.join(" "),
+            });
+        }
+    }
+
+    if parsed.is_err() || commands.is_empty() {
+        return None;
+    }
+    let mut allowed: Vec<&CommandRuleEntry> = Vec::new();
+    for simple in &commands {
+        if simple.dynamic || simple.writes_files {
+            return None;
+        }
+        let entry = applicable.iter().find(|entry| {
+            entry.rule.decision == CommandRuleDecision::Allow
+                && entry.rule.matches_argv(simple.argv.as_slice())
+        })?;
+        if !allowed.iter().any(|seen| std::ptr::eq(*seen, *entry)) {
+            allowed.push(*entry);
+        }
+    }
+    Some(CommandRuleVerdict::Allow { entries: allowed })
+}
+
+/// Turns an imported document into stored entries. Project-scoped rules need the project they
+/// were imported for.
+pub(crate) fn build_command_rule_entries(
+    document: CommandRuleDocument,
+    project_key: Option<&ApprovalProjectKey>,
+) -> Result<Vec<CommandRuleEntry>, String> {
+    let created_at = local_now_rfc3339();
+    document
+        .rules
+        .into_iter()
+        .map(|rule| {
+            rule.validate()?;
+            let project_key = match rule.scope {
+                CommandRuleScope::Global => None,
+                CommandRuleScope::Project => Some(project_key.cloned().ok_or_else(|| {
+                    format!(
+                        "project-scoped command rule `{}` needs a project to import into",
+                        rule.display_pattern()
+                    )
+                })?),
+            };
+            Ok(CommandRuleEntry {
+                id: format!("rule-{}", Uuid::new_v4()),
+                project_key,
+                rule,
+                created_at: created_at.clone(),
+                enabled: true,
+            })
+        })
+        .collect()
+}
+
+fn rule_label(entry: &CommandRuleEntry) -> String {
+    match entry.rule.name.as_deref() {
+        Some(name) => format!("`{name}`"),
+        None => format!("`{}`", entry.rule.display_pattern()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chatos_sandbox_contract::parse_command_rules_toml;
+
+    use super::*;
+
+    fn project_key(project_id: &str) -> ApprovalProjectKey {
+        ApprovalProjectKey {
+            owner_user_id: "owner".to_string(),
+            device_id: "device".to_string(),
+            workspace_id: "workspace".to_string(),
+            project_id: Some(project_id.to_string()),
+            project_root_relative_path: ".".to_string(),
+            project_anchor_relative_path: None,
+        }
+    }
+
+    fn entries() -> Vec<CommandRuleEntry> {
+        let document = parse_command_rules_toml(
+            r#"
+[[command_rules]]
+decision = "allow"
+pattern = "cargo test **"
+
+[[command_rules]]
+name = "feature pushes"
+decision = "allow"
+pattern = "git push origin *"
+forbidden_args = ["main", "*:main", "--force", "-f"]
+
+[[command_rules]]
+decision = "deny"
+scope = "global"
+pattern = "git push origin release/*"
+
+[[command_rules]]
+decision = "deny"
+scope = "global"
+pattern = "terraform destroy **"
+"#,
+        )
+        .expect("parse rules");
+        build_command_rule_entries(document, Some(&project_key("a"))).expect("build entries")
+    }
+
+    fn verdict(command: &str, args: &[&str], project: &str) -> Option<String> {
+        let entries = entries();
+        let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
+        evaluate_command_rules(
+            entries.as_slice(),
+            &project_key(project),
+            command,
+            args.as_slice(),
+        )
+        .map(|verdict| match verdict {
+            CommandRuleVerdict::Deny { .. } => format!("deny: {}", verdict.reason()),
+            CommandRuleVerdict::Allow { .. } => format!("allow: {}", verdict.reason()),
+        })
+    }
+
+    #[test]
+    fn allow_rules_cover_argument_variations_within_their_project() {
+        for (command, args) in [
+            ("cargo test -p foo", &[][..]),
+            ("cargo test -p bar", &[][..]),
+            ("cargo", &["test", "--lib"][..]),
+        ] {
+            let allowed = verdict(command, args, "a").expect("allowed");
+            assert!(allowed.starts_with("allow"), "{allowed}");
+        }
+        assert!(verdict("cargo test --lib", &[], "b").is_none());
+        let allowed =
+            verdict("git push origin feature/x && cargo test", &[], "a").expect("allowed");
+        assert!(allowed.contains("`feature pushes`"), "{allowed}");
+        assert!(verdict("git push origin main", &[], "a").is_none());
+        assert!(verdict("git push -f origin feature/x", &[], "a").is_none());
+    }
+
+    #[test]
+    fn every_simple_command_must_be_allowed_without_expansion_or_writes() {
+        assert!(verdict("cargo test && rm -rf target", &[], "a").is_none());
+        assert!(verdict("cargo test $(cat args)", &[], "a").is_none());
+        assert!(verdict("cargo test > out.txt", &[], "a").is_none());
+        assert!(verdict("cargo test 2>/dev/null", &[], "a")
+            .is_some_and(|verdict| verdict.starts_with("allow")));
+    }
+
+    #[test]
+    fn deny_rules_take_precedence_and_apply_everywhere() {
+        let denied = verdict("git push origin release/1.2", &[], "a").expect("denied");
+        assert!(denied.starts_with("deny"), "{denied}");
+        for command in [
+            "terraform destroy -auto-approve",
+            "cargo test && echo $(terraform destroy)",
+            "terraform destroy 'unterminated",
+        ] {
+            let denied = verdict(command, &[], "b").expect("denied");
+            assert!(denied.starts_with("deny"), "{command}: {denied}");
+        }
+    }
+
+    #[test]
+    fn deny_rules_see_through_wrappers() {
+        for command in [
+            "env TF_LOG=debug terraform destroy -auto-approve",
+            "env -u HOME terraform destroy",
+            "sudo -u deploy terraform destroy",
+            "sudo env A=1 nice -n 5 terraform destroy",
+            "nohup terraform destroy -auto-approve &",
+            "echo -auto-approve | xargs terraform destroy",
+            "bash -c 'terraform destroy -auto-approve'",
+            "sh -lc \"cd infra && terraform destroy\"",
+            "bash -c \"sh -c 'terraform destroy'\"",
+            "bash <<'EOF'\nterraform destroy\nEOF",
+            "eval 'terraform destroy -auto-approve'",
+            "eval terraform destroy",
+        ] {
+            let denied = verdict(command, &[], "b").expect("denied");
+            assert!(
+                denied.starts_with("deny: `terraform destroy"),
+                "{command}: {denied}"
+            );
+        }
+    }
+
+    #[test]
+    fn wrappers_do_not_widen_allow_rules() {
+        assert!(verdict("sudo cargo test", &[], "a").is_none());
+        assert!(verdict("bash -c 'cargo test'", &[], "a").is_none());
+    }
+
+    #[test]
+    fn project_rules_need_a_project_to_import_into() {
+        let document = parse_command_rules_toml(
+            "[[command_rules]]\ndecision = \"allow\"\npattern = \"make test\"\n",
+        )
+        .expect("parse rules");
+        let err = build_command_rule_entries(document, None).expect_err("project scope");
+        assert!(err.contains("needs a project"), "{err}");
+    }
+}
@@ -2,6 +2,7 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 mod ai_agent;
+mod command_rules;
 mod decision_tool;
 mod fingerprint;
 mod pending;
@@ -11,6 +12,7 @@ mod types;
 mod whitelist;
 
 pub(crate) use ai_agent::{run_auto_approval_agent, AutoApprovalDecision};
+pub(crate) use command_rules::build_command_rule_entries;
 pub(crate) use decision_tool::approval_decision_tool_result;
 pub(crate) use pending::{
     approve_pending_approval, cancel_pending_approvals_for_session, deny_pending_approval,
@@ -21,9 +23,7 @@ pub(crate) use service::{
     approval_project_key_for_relay_scope, approval_project_key_from_request,
     clear_session_approvals, CommandApprovalService,
 };
-#[cfg(test)]
-pub(crate) use types::ApprovalProjectKey;
 pub(crate) use types::{
-    ApprovalActionAudit, ApprovalActionAuditDetail, ApprovalDecision, ApprovalMode, ApprovalState,
-    CommandApprovalRequest, ProjectApprovalState,
+    ApprovalActionAudit, ApprovalActionAuditDetail, ApprovalDecision, ApprovalMode,
+    ApprovalProjectKey, ApprovalState, CommandApprovalRequest, ProjectApprovalState,
 };
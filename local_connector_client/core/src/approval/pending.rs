@@ -14,6 +14,7 @@ use uuid::Uuid;
 use crate::local_now_rfc3339;
 
 use super::fingerprint::normalized_command;
+use super::risk::RiskFinding;
 use super::types::{ApprovalConfirmationRequirement, CommandApprovalRequest, PendingApprovalItem};
 
 #[derive(Debug)]
@@ -54,6 +55,7 @@ fn pending_item_for_request(
     request: &CommandApprovalRequest,
     risk: String,
     reason: Option<String>,
+    risk_findings: Vec<RiskFinding>,
 ) -> PendingApprovalItem {
     let single_use_only = request.action_audit.as_ref().is_some_and(|audit| {
         matches!(
@@ -83,6 +85,7 @@ fn pending_item_for_request(
         source: request.source.clone(),
         risk,
         reason,
+        risk_findings,
         created_at: local_now_rfc3339(),
         requested_permissions: request.requested_permissions.clone(),
         action_audit: request.action_audit.clone(),
@@ -149,9 +152,10 @@ pub(crate) async fn request_pending_approval(
     request: &CommandApprovalRequest,
     risk: String,
     reason: Option<String>,
+    risk_findings: Vec<RiskFinding>,
 ) -> PendingApprovalDecision {
     let id = format!("approval-{}", Uuid::new_v4());
-    let item = pending_item_for_request(id.clone(), request, risk, reason);
+    let item = pending_item_for_request(id.clone(), request, risk, reason, risk_findings);
     let (tx, rx) = oneshot::channel();
     {
         let mut pending = pending_store().lock().await;
@@ -223,9 +227,10 @@ pub(crate) async fn start_in_progress_approval(
     request: &CommandApprovalRequest,
     risk: String,
     reason: Option<String>,
+    risk_findings: Vec<RiskFinding>,
 ) -> String {
     let id = format!("approval-running-{}", Uuid::new_v4());
-    let item = pending_item_for_request(id.clone(), request, risk, reason);
+    let item = pending_item_for_request(id.clone(), request, risk, reason, risk_findings);
     in_progress_store().lock().await.insert(id.clone(), item);
     id
 }
@@ -429,7 +434,7 @@ mod tests {
         let request = computer_use_request("computer_type_text", Vec::new());
         let request_id = request.request_id.clone();
         let waiter = tokio::spawn(async move {
-            request_pending_approval(&request, "high".to_string(), None).await
+            request_pending_approval(&request, "high".to_string(), None, Vec::new()).await
         });
         let item = loop {
             if let Some(item) = list_pending_approvals()
@@ -507,7 +512,7 @@ mod tests {
             action_audit: None,
         };
         let waiter = tokio::spawn(async move {
-            request_pending_approval(&request, "high".to_string(), None).await
+            request_pending_approval(&request, "high".to_string(), None, Vec::new()).await
         });
         let id = loop {
             if let Some(item) = list_pending_approvals()
@@ -575,7 +580,7 @@ mod tests {
             action_audit: Some(action_audit.clone()),
         };
         let pending = tokio::spawn(async move {
-            request_pending_approval(&request, "high".to_string(), None).await
+            request_pending_approval(&request, "high".to_string(), None, Vec::new()).await
         });
         tokio::time::timeout(Duration::from_secs(1), async {
             loop {
@@ -637,7 +642,7 @@ mod tests {
             action_audit: None,
         };
         let pending = tokio::spawn(async move {
-            request_pending_approval(&request, "high".to_string(), None).await
+            request_pending_approval(&request, "high".to_string(), None, Vec::new()).await
         });
         tokio::time::timeout(Duration::from_secs(1), async {
             loop {
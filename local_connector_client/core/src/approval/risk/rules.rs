@@ -0,0 +1,1234 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use super::shell::{
+    parse_shell, CommandOrigin, RedirectionKind, ShellScript, ShellWord, SimpleCommand,
+};
+use super::{RiskCategory, RiskFinding, RiskLevel};
+
+/// `bash -c`, `eval` and heredoc scripts are analysed recursively up to this depth.
+const MAX_SCRIPT_DEPTH: usize = 8;
+const MAX_RENDERED_COMMAND_CHARS: usize = 200;
+
+const SHELLS: &[&str] = &["sh", "bash", "zsh", "dash", "ksh", "mksh", "ash", "fish"];
+const INTERPRETERS: &[&str] = &[
+    "python", "python2", "python3", "perl", "ruby", "node", "nodejs", "php", "deno", "bun",
+];
+const NETWORK_FETCHERS: &[&str] = &["curl", "wget", "aria2c", "fetch", "http", "https", "xh"];
+const REMOTE_TRANSFER_PROGRAMS: &[&str] = &[
+    "ssh", "scp", "sftp", "nc", "ncat", "netcat", "socat", "telnet", "ftp", "tftp",
+];
+const PRIVILEGE_PROGRAMS: &[&str] = &["sudo", "doas", "pkexec", "su", "runas", "gosu", "run0"];
+const CLUSTER_PROGRAMS: &[&str] = &["kubectl", "helm", "oc", "aws", "gcloud", "az", "eksctl"];
+const SYSTEM_PROGRAMS: &[&str] = &[
+    "systemctl",
+    "service",
+    "launchctl",
+    "shutdown",
+    "reboot",
+    "halt",
+    "poweroff",
+    "crontab",
+    "iptables",
+    "ip6tables",
+    "nft",
+    "ufw",
+    "useradd",
+    "userdel",
+    "usermod",
+    "groupadd",
+    "passwd",
+    "visudo",
+    "mount",
+    "umount",
+    "modprobe",
+    "insmod",
+    "rmmod",
+    "csrutil",
+    "spctl",
+    "scutil",
+    "networksetup",
+    "setx",
+    "reg",
+];
+const DISK_PROGRAMS: &[&str] = &[
+    "mkfs", "wipefs", "shred", "fdisk", "sfdisk", "parted", "format",
+];
+/// Programs whose operands are text, not paths they open.
+const TEXT_PROGRAMS: &[&str] = &[
+    "echo", "printf", "print", "true", "false", ":", "[", "test", "expr", "let", "alias", "read",
+    "type", "which", "whereis", "hash", "help", "man", "basename", "dirname", "export", "declare",
+    "typeset", "local", "readonly", "unset",
+];
+/// Programs whose first operand is a pattern or program text rather than a path.
+const PATTERN_PROGRAMS: &[&str] = &["grep", "egrep", "fgrep", "rg", "ag", "ack", "sed", "awk"];
+const WRITES_EVERY_OPERAND: &[&str] = &[
+    "rm", "rmdir", "unlink", "shred", "truncate", "touch", "mkdir", "chmod", "chown", "chgrp",
+    "tee",
+];
+const WRITES_LAST_OPERAND: &[&str] = &["cp", "mv", "install", "ln", "rsync", "scp"];
+const HARMLESS_DEVICES: &[&str] = &[
+    "/dev/null",
+    "/dev/zero",
+    "/dev/full",
+    "/dev/tty",
+    "/dev/stdin",
+    "/dev/stdout",
+    "/dev/stderr",
+    "/dev/random",
+    "/dev/urandom",
+];
+const SYSTEM_PATH_PREFIXES: &[&str] = &[
+    "/etc/",
+    "/usr/",
+    "/bin/",
+    "/sbin/",
+    "/lib/",
+    "/lib64/",
+    "/boot/",
+    "/system/",
+    "/library/",
+    "/private/etc/",
+    "/var/lib/",
+    "/var/db/",
+    "c:\\windows\\",
+    "c:/windows/",
+];
+const CREDENTIAL_PATH_FRAGMENTS: &[&str] = &[
+    ".ssh/",
+    "/.ssh",
+    ".aws/credentials",
+    ".kube/config",
+    ".docker/config.json",
+    ".gnupg",
+    ".config/gcloud",
+    "/etc/shadow",
+    "/etc/gshadow",
+    "/etc/sudoers",
+];
+const CREDENTIAL_FILE_NAMES: &[&str] = &[
+    ".env",
+    "id_rsa",
+    "id_dsa",
+    "id_ecdsa",
+    "id_ed25519",
+    ".npmrc",
+    ".pypirc",
+    ".netrc",
+    ".git-credentials",
+    ".pgpass",
+];
+const CREDENTIAL_FILE_SUFFIXES: &[&str] = &[".pem", ".key", ".p12", ".pfx", ".jks", ".keystore"];
+
+/// Argv-level rule evaluated against every command in a wrapper chain (`sudo env X=1 rm`).
+struct CommandRule {
+    id: &'static str,
+    category: RiskCategory,
+    level: RiskLevel,
+    matches: fn(&CommandView<'_>) -> Option<String>,
+}
+
+const COMMAND_RULES: &[CommandRule] = &[
+    CommandRule {
+        id: "privilege_escalation",
+        category: RiskCategory::PrivilegeEscalation,
+        level: RiskLevel::High,
+        matches: privilege_escalation,
+    },
+    CommandRule {
+        id: "recursive_force_delete",
+        category: RiskCategory::DestructiveFilesystem,
+        level: RiskLevel::High,
+        matches: recursive_force_delete,
+    },
+    CommandRule {
+        id: "recursive_delete",
+        category: RiskCategory::DestructiveFilesystem,
+        level: RiskLevel::Medium,
+        matches: recursive_delete,
+    },
+    CommandRule {
+        id: "find_delete",
+        category: RiskCategory::DestructiveFilesystem,
+        level: RiskLevel::Medium,
+        matches: find_delete,
+    },
+    CommandRule {
+        id: "disk_overwrite",
+        category: RiskCategory::DestructiveFilesystem,
+        level: RiskLevel::High,
+        matches: disk_overwrite,
+    },
+    CommandRule {
+        id: "git_history_rewrite",
+        category: RiskCategory::DestructiveFilesystem,
+        level: RiskLevel::Medium,
+        matches: git_history_rewrite,
+    },
+    CommandRule {
+        id: "recursive_permission_change",
+        category: RiskCategory::PermissionChange,
+        level: RiskLevel::High,
+        matches: recursive_permission_change,
+    },
+    CommandRule {
+        id: "permission_broadening",
+        category: RiskCategory::PermissionChange,
+        level: RiskLevel::Medium,
+        matches: permission_broadening,
+    },
+    CommandRule {
+        id: "network_transfer",
+        category: RiskCategory::NetworkEgress,
+        level: RiskLevel::High,
+        matches: network_transfer,
+    },
+    CommandRule {
+        id: "package_install",
+        category: RiskCategory::NetworkEgress,
+        level: RiskLevel::Medium,
+        matches: package_install,
+    },
+    CommandRule {
+        id: "cluster_administration",
+        category: RiskCategory::ClusterAdministration,
+        level: RiskLevel::High,
+        matches: cluster_administration,
+    },
+    CommandRule {
+        id: "system_configuration",
+        category: RiskCategory::SystemConfiguration,
+        level: RiskLevel::High,
+        matches: system_configuration,
+    },
+    CommandRule {
+        id: "dynamic_program",
+        category: RiskCategory::DynamicCode,
+        level: RiskLevel::Medium,
+        matches: dynamic_program,
+    },
+    CommandRule {
+        id: "dynamic_eval",
+        category: RiskCategory::DynamicCode,
+        level: RiskLevel::Medium,
+        matches: dynamic_eval,
+    },
+    CommandRule {
+        id: "inline_interpreter_code",
+        category: RiskCategory::DynamicCode,
+        level: RiskLevel::Medium,
+        matches: inline_interpreter_code,
+    },
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PathAccess {
+    Read,
+    Write,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PathSensitivity {
+    Credential,
+    System,
+    Device,
+}
+
+/// One program invocation after stripping a wrapper such as `sudo` or `env`.
+struct CommandView<'a> {
+    program: String,
+    program_word: &'a ShellWord,
+    args: &'a [ShellWord],
+}
+
+impl<'a> CommandView<'a> {
+    fn new(program_word: &'a ShellWord, args: &'a [ShellWord]) -> Self {
+        let base = program_word
+            .text
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let program = base
+            .strip_suffix(".exe")
+            .unwrap_or(base.as_str())
+            .to_string();
+        Self {
+            program,
+            program_word,
+            args,
+        }
+    }
+
+    fn is(&self, programs: &[&str]) -> bool {
+        programs.contains(&self.program.as_str())
+    }
+
+    fn subcommand(&self) -> Option<&str> {
+        self.operands().next()
+    }
+
+    /// Options before `--`, including long options with inline values.
+    fn options(&self) -> impl Iterator<Item = &str> {
+        self.args
+            .iter()
+            .map(|arg| arg.text.as_str())
+            .take_while(|text| *text != "--")
+            .filter(|text| text.starts_with('-') && text.len() > 1)
+    }
+
+    fn operands(&self) -> impl Iterator<Item = &str> {
+        let mut after_separator = false;
+        self.args.iter().filter_map(move |arg| {
+            let text = arg.text.as_str();
+            if after_separator {
+                return Some(text);
+            }
+            if text == "--" {
+                after_separator = true;
+                return None;
+            }
+            (!(text.starts_with('-') && text.len() > 1)).then_some(text)
+        })
+    }
+
+    /// Matches `-x` inside short option clusters (`-rf`, `-r -f`) or the long spelling.
+    fn has_flag(&self, short: Option<char>, long: &str) -> bool {
+        self.options().any(|option| {
+            if let Some(name) = option.strip_prefix("--") {
+                return !long.is_empty()
+                    && (name == long || name.split_once('=').is_some_and(|(n, _)| n == long));
+            }
+            short.is_some_and(|short| option[1..].contains(short))
+        })
+    }
+
+    fn has_option(&self, option: &str) -> bool {
+        self.args.iter().any(|arg| arg.text == option)
+    }
+}
+
+/// Analyses a command line and returns every finding, in the order the shell would run them.
+pub(super) fn analyze_command(source: &str) -> Vec<RiskFinding> {
+    let mut analyzer = Analyzer::default();
+    analyzer.analyze_source(source, None, 0);
+    analyzer.findings
+}
+
+#[derive(Default)]
+struct Analyzer {
+    findings: Vec<RiskFinding>,
+}
+
+impl Analyzer {
+    fn report(
+        &mut self,
+        rule: &'static str,
+        category: RiskCategory,
+        level: RiskLevel,
+        command: &str,
+        context: Option<&str>,
+        detail: String,
+    ) {
+        let finding = RiskFinding {
+            rule,
+            category,
+            level,
+            command: command.to_string(),
+            context: context.map(ToOwned::to_owned),
+            detail,
+        };
+        if !self.findings.contains(&finding) {
+            self.findings.push(finding);
+        }
+    }
+
+    fn analyze_source(&mut self, source: &str, context: Option<&str>, depth: usize) {
+        let rendered = truncate_rendered(source.trim());
+        if depth > MAX_SCRIPT_DEPTH {
+            self.report(
+                "unparseable_command",
+                RiskCategory::Unparseable,
+                RiskLevel::High,
+                rendered.as_str(),
+                context,
+                "nested scripts are too deep to analyse; review the command manually".to_string(),
+            );
+            return;
+        }
+        match parse_shell(source) {
+            Ok(script) => self.analyze_script(&script, context, depth),
+            Err(err) => self.report(
+                "unparseable_command",
+                RiskCategory::Unparseable,
+                RiskLevel::High,
+                rendered.as_str(),
+                context,
+                format!("command could not be parsed ({err}); review it manually"),
+            ),
+        }
+    }
+
+    fn analyze_script(&mut self, script: &ShellScript, context: Option<&str>, depth: usize) {
+        for command in &script.commands {
+            let context = command_context(command.origin, context);
+            self.analyze_simple_command(command, context.as_deref(), depth);
+        }
+        for stages in &script.pipelines {
+            self.analyze_pipeline(script, stages, context);
+        }
+    }
+
+    fn analyze_simple_command(
+        &mut self,
+        command: &SimpleCommand,
+        context: Option<&str>,
+        depth: usize,
+    ) {
+        let rendered = render_command(command);
+        for (name, _) in &command.assignments {
+            self.check_assignment(name.as_str(), rendered.as_str(), context);
+        }
+        for redirection in &command.redirections {
+            let access = match redirection.kind {
+                RedirectionKind::Read => PathAccess::Read,
+                RedirectionKind::Write | RedirectionKind::Append | RedirectionKind::ReadWrite => {
+                    PathAccess::Write
+                }
+                _ => continue,
+            };
+            self.check_path(
+                redirection.target.text.as_str(),
+                access,
+                rendered.as_str(),
+                context,
+            );
+        }
+        let views = unwrap_command(command.words.as_slice());
+        for view in &views {
+            for rule in COMMAND_RULES {
+                if let Some(detail) = (rule.matches)(view) {
+                    self.report(
+                        rule.id,
+                        rule.category,
+                        rule.level,
+                        rendered.as_str(),
+                        context,
+                        detail,
+                    );
+                }
+            }
+            if view.is(&["env", "export", "declare", "typeset", "local", "readonly"]) {
+                for operand in view.operands() {
+                    if let Some((name, _)) = split_assignment(operand) {
+                        self.check_assignment(name, rendered.as_str(), context);
+                    }
+                }
+            }
+        }
+        let Some(view) = views.last() else {
+            return;
+        };
+        for (path, access) in path_operands(view) {
+            self.check_path(path, access, rendered.as_str(), context);
+        }
+        self.analyze_embedded_scripts(view, command, rendered.as_str(), context, depth);
+    }
+
+    fn analyze_embedded_scripts(
+        &mut self,
+        view: &CommandView<'_>,
+        command: &SimpleCommand,
+        rendered: &str,
+        context: Option<&str>,
+        depth: usize,
+    ) {
+        if view.is(SHELLS) {
+            if let Some(script) = shell_inline_script(view.args) {
+                let nested = nested_context(format!("`{} -c` script", view.program), context);
+                self.analyze_source(script, Some(nested.as_str()), depth + 1);
+            } else {
+                for body in shell_stdin_scripts(view, command) {
+                    let nested =
+                        nested_context(format!("`{}` stdin script", view.program), context);
+                    self.analyze_source(body, Some(nested.as_str()), depth + 1);
+                }
+            }
+        }
+        if view.is(SHELLS) || view.is(INTERPRETERS) || view.is(&["source", "."]) {
+            if let Some(operand) = view
+                .operands()
+                .next()
+                .filter(|operand| operand.starts_with("<("))
+            {
+                self.report(
+                    "pipe_to_interpreter",
+                    RiskCategory::RemoteCodeExecution,
+                    RiskLevel::High,
+                    rendered,
+                    context,
+                    format!(
+                        "runs the output of `{operand}` as a `{}` script",
+                        view.program
+                    ),
+                );
+            }
+        }
+        match view.program.as_str() {
+            "eval" => {
+                let script = eval_script(view.args);
+                let nested = nested_context("`eval` string".to_string(), context);
+                self.analyze_source(script.as_str(), Some(nested.as_str()), depth + 1);
+            }
+            "su" => {
+                if let Some(script) = su_inline_script(view.args) {
+                    let nested = nested_context("`su -c` script".to_string(), context);
+                    self.analyze_source(script, Some(nested.as_str()), depth + 1);
+                }
+            }
+            "find" => {
+                for words in find_exec_commands(view.args) {
+                    let nested = SimpleCommand {
+                        assignments: Vec::new(),
+                        words: words.to_vec(),
+                        redirections: Vec::new(),
+                        origin: CommandOrigin::TopLevel,
+                    };
+                    let context = nested_context("`find -exec`".to_string(), context);
+                    self.analyze_simple_command(&nested, Some(context.as_str()), depth + 1);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn analyze_pipeline(
+        &mut self,
+        script: &ShellScript,
+        stages: &[Vec<usize>],
+        context: Option<&str>,
+    ) {
+        let mut fetcher: Option<String> = None;
+        for (position, stage) in stages.iter().enumerate() {
+            for (offset, index) in stage.iter().enumerate() {
+                let command = &script.commands[*index];
+                let views = unwrap_command(command.words.as_slice());
+                let Some(view) = views.last() else {
+                    continue;
+                };
+                let stage_reader = offset + 1 == stage.len();
+                if position > 0 && stage_reader && reads_script_from_stdin(view) {
+                    let detail = match fetcher.as_deref() {
+                        Some(fetcher) => format!(
+                            "pipes content downloaded by `{fetcher}` into `{}`",
+                            view.program
+                        ),
+                        None => format!("pipes data into the `{}` interpreter", view.program),
+                    };
+                    let context = command_context(command.origin, context);
+                    self.report(
+                        "pipe_to_interpreter",
+                        RiskCategory::RemoteCodeExecution,
+                        RiskLevel::High,
+                        render_command(command).as_str(),
+                        context.as_deref(),
+                        detail,
+                    );
+                }
+                if fetcher.is_none() && view.is(NETWORK_FETCHERS) {
+                    fetcher = Some(view.program.clone());
+                }
+            }
+        }
+    }
+
+    fn check_assignment(&mut self, name: &str, rendered: &str, context: Option<&str>) {
+        let (level, detail) = match name {
+            "LD_PRELOAD"
+            | "LD_AUDIT"
+            | "LD_LIBRARY_PATH"
+            | "DYLD_INSERT_LIBRARIES"
+            | "DYLD_LIBRARY_PATH"
+            | "DYLD_FRAMEWORK_PATH"
+            | "BASH_ENV"
+            | "ENV"
+            | "PROMPT_COMMAND"
+            | "GIT_SSH_COMMAND"
+            | "GIT_EXEC_PATH" => (
+                RiskLevel::High,
+                format!("sets `{name}`, which injects code into the programs it starts"),
+            ),
+            "PATH" | "PYTHONPATH" | "NODE_OPTIONS" | "PERL5LIB" | "RUBYOPT" | "RUBYLIB" => (
+                RiskLevel::Medium,
+                format!("sets `{name}`, which changes where programs and modules load from"),
+            ),
+            _ => return,
+        };
+        self.report(
+            "environment_override",
+            RiskCategory::EnvironmentOverride,
+            level,
+            rendered,
+            context,
+            detail,
+        );
+    }
+
+    fn check_path(
+        &mut self,
+        path: &str,
+        access: PathAccess,
+        rendered: &str,
+        context: Option<&str>,
+    ) {
+        let Some(sensitivity) = path_sensitivity(path) else {
+            return;
+        };
+        let (rule, level, detail) = match (sensitivity, access) {
+            (PathSensitivity::Credential, PathAccess::Read) => (
+                "credential_read",
+                RiskLevel::High,
+                format!("reads credential file `{path}`"),
+            ),
+            (PathSensitivity::Credential, PathAccess::Write) => (
+                "credential_write",
+                RiskLevel::High,
+                format!("modifies credential file `{path}`"),
+            ),
+            (PathSensitivity::System, PathAccess::Read) => (
+                "system_path_read",
+                RiskLevel::Medium,
+                format!("reads system path `{path}`"),
+            ),
+            (PathSensitivity::System, PathAccess::Write) => (
+                "system_path_write",
+                RiskLevel::High,
+                format!("writes to system path `{path}`"),
+            ),
+            (PathSensitivity::Device, PathAccess::Read) => (
+                "device_read",
+                RiskLevel::Medium,
+                format!("reads device `{path}`"),
+            ),
+            (PathSensitivity::Device, PathAccess::Write) => (
+                "device_write",
+                RiskLevel::High,
+                format!("writes to device `{path}`"),
+            ),
+        };
+        self.report(
+            rule,
+            RiskCategory::SensitivePath,
+            level,
+            rendered,
+            context,
+            detail,
+        );
+    }
+}
+
+/// Argvs of every program the line ends up running: each stage of a wrapper chain
+/// (`sudo env X=1 rm` yields `sudo ...`, `env ...` and `rm ...`), plus the commands of
+/// `sh -c`, `eval`, `su -c`, heredoc and `find -exec` scripts. Nested scripts that cannot be
+/// parsed, or are nested deeper than [`MAX_SCRIPT_DEPTH`], are split on whitespace instead.
+pub(super) fn executed_argvs(source: &str) -> Result<Vec<Vec<String>>, String> {
+    let script = parse_shell(source)?;
+    let mut argvs = Vec::new();
+    collect_script_argvs(&script, 0, &mut argvs);
+    Ok(argvs)
+}
+
+fn collect_source_argvs(source: &str, depth: usize, argvs: &mut Vec<Vec<String>>) {
+    match parse_shell(source) {
+        Ok(script) if depth <= MAX_SCRIPT_DEPTH => collect_script_argvs(&script, depth, argvs),
+        _ => push_argv(
+            argvs,
+            source.split_whitespace().map(str::to_string).collect(),
+        ),
+    }
+}
+
+fn collect_script_argvs(script: &ShellScript, depth: usize, argvs: &mut Vec<Vec<String>>) {
+    for command in &script.commands {
+        collect_command_argvs(command, depth, argvs);
+    }
+}
+
+fn collect_command_argvs(command: &SimpleCommand, depth: usize, argvs: &mut Vec<Vec<String>>) {
+    let views = unwrap_command(command.words.as_slice());
+    for view in &views {
+        push_argv(
+            argvs,
+            std::iter::once(view.program_word)
+                .chain(view.args)
+                .map(|word| word.text.clone())
+                .collect(),
+        );
+    }
+    let Some(view) = views.last() else {
+        return;
+    };
+    if view.is(SHELLS) {
+        if let Some(script) = shell_inline_script(view.args) {
+            collect_source_argvs(script, depth + 1, argvs);
+        } else {
+            for body in shell_stdin_scripts(view, command) {
+                collect_source_argvs(body, depth + 1, argvs);
+            }
+        }
+    }
+    match view.program.as_str() {
+        "eval" => collect_source_argvs(eval_script(view.args).as_str(), depth + 1, argvs),
+        "su" => {
+            if let Some(script) = su_inline_script(view.args) {
+                collect_source_argvs(script, depth + 1, argvs);
+            }
+        }
+        "find" if depth < MAX_SCRIPT_DEPTH => {
+            for words in find_exec_commands(view.args) {
+                let nested = SimpleCommand {
+                    assignments: Vec::new(),
+                    words: words.to_vec(),
+                    redirections: Vec::new(),
+                    origin: CommandOrigin::TopLevel,
+                };
+                collect_command_argvs(&nested, depth + 1, argvs);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn push_argv(argvs: &mut Vec<Vec<String>>, argv: Vec<String>) {
+    if !argv.is_empty() && !argvs.contains(&argv) {
+        argvs.push(argv);
+    }
+}
+
+/// Peels wrappers that run another program (`sudo`, `env`, `nice`, `xargs`, ...), returning the
+/// wrapper chain with the program that finally runs last.
+fn unwrap_command(words: &[ShellWord]) -> Vec<CommandView<'_>> {
+    let mut views = Vec::new();
+    let mut rest = words;
+    while let Some((program, args)) = rest.split_first() {
+        let view = CommandView::new(program, args);
+        let wrapped = wrapped_command_start(&view);
+        views.push(view);
+        match wrapped {
+            Some(start) if start < args.len() && views.len() < 16 => rest = &args[start..],
+            _ => break,
+        }
+    }
+    views
+}
+
+fn wrapped_command_start(view: &CommandView<'_>) -> Option<usize> {
+    let (value_options, skip_assignments, fixed_operands): (&[&str], bool, usize) =
+        match view.program.as_str() {
+            "sudo" => (
+                &[
+                    "-u",
+                    "-g",
+                    "-h",
+                    "-p",
+                    "-C",
+                    "-D",
+                    "-r",
+                    "-t",
+                    "-U",
+                    "-T",
+                    "--user",
+                    "--group",
+                    "--host",
+                    "--prompt",
+                    "--chdir",
+                    "--role",
+                    "--type",
+                    "--other-user",
+                    "--close-from",
+                    "--command-timeout",
+                ],
+                true,
+                0,
+            ),
+            "doas" => (&["-u", "-C"], false, 0),
+            "run0" => (
+                &["-u", "--user", "-g", "--group", "-D", "--chdir"],
+                false,
+                0,
+            ),
+            "env" => (&["-u", "-C", "--unset", "--chdir"], true, 0),
+            "nice" => (&["-n", "--adjustment"], false, 0),
+            "ionice" => (&["-c", "-n", "--class", "--classdata"], false, 0),
+            "stdbuf" => (
+                &["-i", "-o", "-e", "--input", "--output", "--error"],
+                false,
+                0,
+            ),
+            "timeout" => (&["-s", "-k", "--signal", "--kill-after"], false, 1),
+            "xargs" => (
+                &[
+                    "-I",
+                    "-n",
+                    "-P",
+                    "-L",
+                    "-d",
+                    "-E",
+                    "-s",
+                    "-a",
+                    "--max-args",
+                    "--max-procs",
+                    "--delimiter",
+                    "--arg-file",
+                    "--max-lines",
+                    "--max-chars",
+                ],
+                false,
+                0,
+            ),
+            "command" if view.has_option("-v") || view.has_option("-V") => return None,
+            "nohup" | "setsid" | "exec" | "builtin" | "command" | "unbuffer" | "chronic"
+            | "time" | "caffeinate" | "torsocks" | "proxychains" | "proxychains4" | "busybox"
+            | "gosu" => (&[], false, usize::from(view.program == "gosu")),
+            _ => return None,
+        };
+    let mut index = 0;
+    while let Some(arg) = view.args.get(index) {
+        let text = arg.text.as_str();
+        if text == "--" {
+            index += 1;
+            break;
+        }
+        if text.starts_with('-') && text.len() > 1 {
+            index += 1;
+            if value_options.contains(&text) {
+                index += 1;
+            }
+            continue;
+        }
+        if skip_assignments && split_assignment(text).is_some() {
+            index += 1;
+            continue;
+        }
+        break;
+    }
+    Some(index + fixed_operands)
+}
+
+/// Script passed with `-c` to a POSIX shell, e.g. `bash -lc '...'`.
+fn shell_inline_script(args: &[ShellWord]) -> Option<&str> {
+    let mut inline = false;
+    let mut index = 0;
+    while let Some(arg) = args.get(index) {
+        let text = arg.text.as_str();
+        if text == "--" {
+            index += 1;
+            break;
+        }
+        if matches!(text, "-o" | "+o" | "-O" | "+O" | "--rcfile" | "--init-file") {
+            index += 2;
+            continue;
+        }
+        if text.starts_with("--") {
+            index += 1;
+            continue;
+        }
+        if (text.starts_with('-') || text.starts_with('+')) && text.len() > 1 {
+            inline |= text.starts_with('-') && text[1..].contains('c');
+            index += 1;
+            continue;
+        }
+        break;
+    }
+    inline
+        .then(|| args.get(index).map(|arg| arg.text.as_str()))
+        .flatten()
+}
+
+/// Heredoc and here-string bodies a shell without a script operand runs, e.g. `bash <<EOF`.
+fn shell_stdin_scripts<'c>(view: &CommandView<'_>, command: &'c SimpleCommand) -> Vec<&'c str> {
+    if view.operands().next().is_some() {
+        return Vec::new();
+    }
+    command
+        .redirections
+        .iter()
+        .filter_map(|redirection| redirection.body.as_deref())
+        .collect()
+}
+
+/// `eval` joins its arguments with spaces and runs the result as shell code.
+fn eval_script(args: &[ShellWord]) -> String {
+    args.iter()
+        .map(|arg| arg.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Script passed to `su -c` / `su --command`.
+fn su_inline_script(args: &[ShellWord]) -> Option<&str> {
+    args.iter()
+        .position(|arg| arg.text == "-c" || arg.text == "--command")
+        .and_then(|index| args.get(index + 1))
+        .map(|arg| arg.text.as_str())
+}
+
+fn reads_script_from_stdin(view: &CommandView<'_>) -> bool {
+    if view.is(SHELLS) {
+        return (shell_inline_script(view.args).is_none()
+            && view.operands().next().is_none_or(|operand| operand == "-"))
+            || view.has_flag(Some('s'), "");
+    }
+    view.is(INTERPRETERS)
+        && !view.has_option("-c")
+        && !view.has_option("-e")
+        && !view.has_option("-m")
+        && view.operands().next().is_none_or(|operand| operand == "-")
+}
+
+fn find_exec_commands(args: &[ShellWord]) -> Vec<&[ShellWord]> {
+    let mut commands = Vec::new();
+    let mut index = 0;
+    while index < args.len() {
+        if matches!(
+            args[index].text.as_str(),
+            "-exec" | "-execdir" | "-ok" | "-okdir"
+        ) {
+            let start = index + 1;
+            let end = args[start..]
+                .iter()
+                .position(|arg| arg.text == ";" || arg.text == "+")
+                .map_or(args.len(), |offset| start + offset);
+            if end > start {
+                commands.push(&args[start..end]);
+            }
+            index = end;
+        }
+        index += 1;
+    }
+    commands
+}
+
+fn path_operands<'v>(view: &'v CommandView<'_>) -> Vec<(&'v str, PathAccess)> {
+    if view.is(TEXT_PROGRAMS) || (view.is(SHELLS) && shell_inline_script(view.args).is_some()) {
+        return Vec::new();
+    }
+    if view.program == "dd" {
+        return view
+            .operands()
+            .filter_map(|operand| match operand.split_once('=') {
+                Some(("of", path)) => Some((path, PathAccess::Write)),
+                Some(("if", path)) => Some((path, PathAccess::Read)),
+                _ => None,
+            })
+            .collect();
+    }
+    let mut operands = view.operands().collect::<Vec<_>>();
+    if view.is(PATTERN_PROGRAMS) && !view.has_option("-e") && !view.has_option("-f") {
+        operands.drain(..operands.len().min(1));
+    }
+    let in_place_edit = view.is(&["sed", "perl"]) && view.has_flag(Some('i'), "in-place");
+    let last = operands.len().saturating_sub(1);
+    let mut paths = operands
+        .into_iter()
+        .enumerate()
+        .map(|(index, operand)| {
+            let writes = view.is(WRITES_EVERY_OPERAND)
+                || in_place_edit
+                || (view.is(WRITES_LAST_OPERAND) && index == last && index > 0);
+            let access = if writes {
+                PathAccess::Write
+            } else {
+                PathAccess::Read
+            };
+            (operand, access)
+        })
+        .collect::<Vec<_>>();
+    paths.extend(view.options().filter_map(|option| {
+        option
+            .strip_prefix("--")
+            .and_then(|option| option.split_once('='))
+            .map(|(_, value)| (value, PathAccess::Read))
+    }));
+    paths
+}
+
+fn path_sensitivity(path: &str) -> Option<PathSensitivity> {
+    let lower = path.to_ascii_lowercase();
+    if lower.is_empty() || lower.contains(char::is_whitespace) || lower.contains("://") {
+        return None;
+    }
+    let name = lower.rsplit(['/', '\\']).next().unwrap_or_default();
+    let credential_name = CREDENTIAL_FILE_NAMES.contains(&name)
+        || name.strip_prefix(".env.").is_some_and(|variant| {
+            !matches!(
+                variant,
+                "example" | "sample" | "template" | "dist" | "defaults"
+            )
+        })
+        || CREDENTIAL_FILE_SUFFIXES
+            .iter()
+            .any(|suffix| name.len() > suffix.len() && name.ends_with(suffix))
+        || name.contains("private_key");
+    if credential_name
+        || CREDENTIAL_PATH_FRAGMENTS
+            .iter()
+            .any(|fragment| lower.contains(fragment))
+    {
+        return Some(PathSensitivity::Credential);
+    }
+    if HARMLESS_DEVICES.contains(&lower.as_str()) || lower.starts_with("/dev/fd/") {
+        return None;
+    }
+    if lower.starts_with("/dev/") {
+        return Some(PathSensitivity::Device);
+    }
+    SYSTEM_PATH_PREFIXES
+        .iter()
+        .any(|prefix| lower.starts_with(prefix) || lower == prefix.trim_end_matches(['/', '\\']))
+        .then_some(PathSensitivity::System)
+}
+
+fn privilege_escalation(view: &CommandView<'_>) -> Option<String> {
+    view.is(PRIVILEGE_PROGRAMS)
+        .then(|| format!("runs with elevated privileges via `{}`", view.program))
+}
+
+fn recursive_force_delete(view: &CommandView<'_>) -> Option<String> {
+    (view.program == "rm" && recursive_rm(view) && view.has_flag(Some('f'), "force"))
+        .then(|| format!("recursively force-deletes {}", describe_operands(view)))
+}
+
+fn recursive_delete(view: &CommandView<'_>) -> Option<String> {
+    (view.program == "rm" && recursive_rm(view) && !view.has_flag(Some('f'), "force"))
+        .then(|| format!("recursively deletes {}", describe_operands(view)))
+}
+
+fn recursive_rm(view: &CommandView<'_>) -> bool {
+    view.has_flag(Some('r'), "recursive") || view.has_flag(Some('R'), "")
+}
+
+fn find_delete(view: &CommandView<'_>) -> Option<String> {
+    (view.program == "find" && view.has_option("-delete"))
+        .then(|| "deletes every file `find` matches".to_string())
+}
+
+fn disk_overwrite(view: &CommandView<'_>) -> Option<String> {
+    if view.program == "dd" && view.operands().any(|operand| operand.starts_with("of=")) {
+        return Some("writes raw blocks with `dd`".to_string());
+    }
+    (view.is(DISK_PROGRAMS) || view.program.starts_with("mkfs."))
+        .then(|| format!("`{}` erases or repartitions storage", view.program))
+}
+
+fn git_history_rewrite(view: &CommandView<'_>) -> Option<String> {
+    if view.program != "git" {
+        return None;
+    }
+    match view.subcommand()? {
+        "push" if view.has_flag(Some('f'), "force") || view.has_flag(None, "force-with-lease") => {
+            Some("force-pushes and can overwrite remote history".to_string())
+        }
+        "reset" if view.has_option("--hard") => {
+            Some("discards uncommitted changes with `git reset --hard`".to_string())
+        }
+        "clean" if view.has_flag(Some('f'), "force") => {
+            Some("deletes untracked files with `git clean`".to_string())
+        }
+        "branch" if view.has_option("-D") => Some("force-deletes a git branch".to_string()),
+        _ => None,
+    }
+}
+
+fn recursive_permission_change(view: &CommandView<'_>) -> Option<String> {
+    (view.is(&["chmod", "chown", "chgrp"]) && view.has_flag(Some('R'), "recursive")).then(|| {
+        format!(
+            "recursively changes ownership or permissions of {}",
+            describe_operands(view)
+        )
+    })
+}
+
+fn permission_broadening(view: &CommandView<'_>) -> Option<String> {
+    if view.program != "chmod" {
+        return None;
+    }
+    let mode = view.operands().next()?;
+    let broad = mode.ends_with("777")
+        || mode.ends_with("666")
+        || mode.contains("+s")
+        || mode.contains("o+w")
+        || mode.contains("a+w")
+        || (mode.len() == 4 && mode.starts_with(['4', '2', '6']));
+    broad.then(|| format!("sets broad or setuid permissions `{mode}`"))
+}
+
+fn network_transfer(view: &CommandView<'_>) -> Option<String> {
+    if view.is(NETWORK_FETCHERS) || view.is(REMOTE_TRANSFER_PROGRAMS) {
+        return Some(format!("opens network connections with `{}`", view.program));
+    }
+    (view.program == "rsync" && view.operands().any(|operand| operand.contains(':')))
+        .then(|| "copies files to or from a remote host with `rsync`".to_string())
+}
+
+fn package_install(view: &CommandView<'_>) -> Option<String> {
+    let subcommand = view.subcommand();
+    let installs = match view.program.as_str() {
+        "npm" | "pnpm" | "yarn" | "bun" => matches!(
+            subcommand,
+            Some("install" | "i" | "add" | "ci" | "exec" | "dlx")
+        ),
+        "npx" | "pnpx" | "bunx" | "uvx" | "pipx" => true,
+        "pip" | "pip3" | "uv" | "gem" | "cargo" | "go" | "brew" | "apt" | "apt-get" | "yum"
+        | "dnf" | "apk" | "pacman" | "zypper" | "port" | "choco" | "winget" | "conda" => {
+            matches!(
+                subcommand,
+                Some("install" | "add" | "get" | "upgrade" | "update" | "-S" | "-Syu")
+            ) || view.has_option("-S")
+        }
+        "python" | "python3" => {
+            view.has_option("-m") && view.operands().take(2).eq(["pip", "install"])
+        }
+        _ => false,
+    };
+    installs.then(|| {
+        format!(
+            "downloads and installs packages with `{}`",
+            view.program_word.text
+        )
+    })
+}
+
+fn cluster_administration(view: &CommandView<'_>) -> Option<String> {
+    if view.is(CLUSTER_PROGRAMS) {
+        return Some(format!(
+            "operates on cloud or cluster resources with `{}`",
+            view.program
+        ));
+    }
+    (matches!(view.program.as_str(), "terraform" | "tofu" | "pulumi")
+        && matches!(
+            view.subcommand(),
+            Some("apply" | "destroy" | "import" | "up")
+        ))
+    .then(|| format!("changes infrastructure with `{}`", view.program))
+}
+
+fn system_configuration(view: &CommandView<'_>) -> Option<String> {
+    if view.is(SYSTEM_PROGRAMS) {
+        return Some(format!(
+            "changes system configuration with `{}`",
+            view.program
+        ));
+    }
+    let writes = match view.program.as_str() {
+        "sysctl" => view.has_option("-w") || view.operands().any(|operand| operand.contains('=')),
+        "defaults" => matches!(view.subcommand(), Some("write" | "delete")),
+        _ => false,
+    };
+    writes.then(|| format!("changes system configuration with `{}`", view.program))
+}
+
+fn dynamic_program(view: &CommandView<'_>) -> Option<String> {
+    view.program_word.dynamic.then(|| {
+        format!(
+            "runs a program chosen at runtime (`{}`)",
+            view.program_word.text
+        )
+    })
+}
+
+fn dynamic_eval(view: &CommandView<'_>) -> Option<String> {
+    (view.program == "eval" && view.args.iter().any(|arg| arg.dynamic))
+        .then(|| "evaluates shell code assembled at runtime".to_string())
+}
+
+fn inline_interpreter_code(view: &CommandView<'_>) -> Option<String> {
+    (view.is(INTERPRETERS)
+        && ["-c", "-e", "-E", "-r", "--eval"]
+            .iter()
+            .any(|option| view.has_option(option)))
+    .then(|| format!("runs inline `{}` code that is not analysed", view.program))
+}
+
+fn describe_operands(view: &CommandView<'_>) -> String {
+    let operands = view
+        .operands()
+        .map(|operand| format!("`{operand}`"))
+        .collect::<Vec<_>>();
+    if operands.is_empty() {
+        "its operands".to_string()
+    } else {
+        operands.join(", ")
+    }
+}
+
+fn split_assignment(text: &str) -> Option<(&str, &str)> {
+    let (name, value) = text.split_once('=')?;
+    let name = name.strip_suffix('+').unwrap_or(name);
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    valid.then_some((name, value))
+}
+
+fn command_context(origin: CommandOrigin, outer: Option<&str>) -> Option<String> {
+    let label = match origin {
+        CommandOrigin::TopLevel => return outer.map(ToOwned::to_owned),
+        CommandOrigin::Subshell => "subshell",
+        CommandOrigin::CommandSubstitution => "command substitution",
+        CommandOrigin::ProcessSubstitution => "process substitution",
+    };
+    Some(nested_context(label.to_string(), outer))
+}
+
+fn nested_context(label: String, outer: Option<&str>) -> String {
+    match outer {
+        Some(outer) => format!("{label} in {outer}"),
+        None => label,
+    }
+}
+
+fn render_command(command: &SimpleCommand) -> String {
+    let mut parts = command
+        .assignments
+        .iter()
+        .map(|(name, value)| format!("{name}={}", render_word(value.text.as_str())))
+        .collect::<Vec<_>>();
+    parts.extend(
+        command
+            .words
+            .iter()
+            .map(|word| render_word(word.text.as_str())),
+    );
+    parts.extend(command.redirections.iter().map(|redirection| {
+        format!(
+            "{} {}",
+            redirection.kind.operator(),
+            render_word(redirection.target.text.as_str())
+        )
+    }));
+    truncate_rendered(parts.join(" ").as_str())
+}
+
+fn render_word(text: &str) -> String {
+    if text.is_empty() || text.contains(char::is_whitespace) {
+        format!("'{}'", text.replace('\'', "'\\''"))
+    } else {
+        text.to_string()
+    }
+}
+
+fn truncate_rendered(text: &str) -> String {
+    if text.chars().count() <= MAX_RENDERED_COMMAND_CHARS {
+        return text.to_string();
+    }
+    let mut truncated = text
+        .chars()
+        .take(MAX_RENDERED_COMMAND_CHARS)
+        .collect::<String>();
+    truncated.push('…');
+    truncated
+}
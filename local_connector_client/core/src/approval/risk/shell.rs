@@ -0,0 +1,989 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+/// Word after quote removal. `dynamic` marks words containing expansions whose value is only
+/// known when the shell runs; `quoted` marks words that used any quoting or escaping.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct ShellWord {
+    pub(super) text: String,
+    pub(super) dynamic: bool,
+    pub(super) quoted: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum RedirectionKind {
+    Read,
+    Write,
+    Append,
+    ReadWrite,
+    Duplicate,
+    Heredoc,
+    HereString,
+}
+
+impl RedirectionKind {
+    pub(super) fn operator(self) -> &'static str {
+        match self {
+            Self::Read => "<",
+            Self::Write => ">",
+            Self::Append => ">>",
+            Self::ReadWrite => "<>",
+            Self::Duplicate => ">&",
+            Self::Heredoc => "<<",
+            Self::HereString => "<<<",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Redirection {
+    pub(super) kind: RedirectionKind,
+    pub(super) target: ShellWord,
+    /// Heredoc or here-string contents, which the command receives on stdin.
+    pub(super) body: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CommandOrigin {
+    TopLevel,
+    Subshell,
+    CommandSubstitution,
+    ProcessSubstitution,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct SimpleCommand {
+    pub(super) assignments: Vec<(String, ShellWord)>,
+    pub(super) words: Vec<ShellWord>,
+    pub(super) redirections: Vec<Redirection>,
+    pub(super) origin: CommandOrigin,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(super) struct ShellScript {
+    /// Every simple command, including those nested in subshells, substitutions and heredocs.
+    pub(super) commands: Vec<SimpleCommand>,
+    /// Pipelines with at least two stages; each stage lists indices into `commands`.
+    pub(super) pipelines: Vec<Vec<Vec<usize>>>,
+}
+
+/// Words that are syntax rather than commands when they start a command.
+const RESERVED_WORDS: &[&str] = &[
+    "if", "then", "elif", "else", "fi", "while", "until", "do", "done", "{", "}", "!", "time",
+    "for", "select", "case", "function", "[[",
+];
+
+/// Deeper nesting is rejected rather than risking the stack on adversarial input.
+const MAX_NESTING: usize = 32;
+
+const REDIRECTION_OPERATORS: &[(&str, RedirectionKind)] = &[
+    ("&>>", RedirectionKind::Append),
+    ("&>", RedirectionKind::Write),
+    ("<<<", RedirectionKind::HereString),
+    ("<<-", RedirectionKind::Heredoc),
+    ("<<", RedirectionKind::Heredoc),
+    ("<>", RedirectionKind::ReadWrite),
+    ("<&", RedirectionKind::Duplicate),
+    (">&", RedirectionKind::Duplicate),
+    (">>", RedirectionKind::Append),
+    (">|", RedirectionKind::Write),
+    ("<", RedirectionKind::Read),
+    (">", RedirectionKind::Write),
+];
+
+/// Parses POSIX/bash command syntax far enough to recover every simple command a script can
+/// run: lists, pipelines, subshells, groups, control flow, command and process substitution,
+/// heredocs and redirections. Expansions are kept verbatim and marked dynamic.
+pub(super) fn parse_shell(source: &str) -> Result<ShellScript, String> {
+    let mut script = ShellScript::default();
+    Parser::new(source, &mut script, CommandOrigin::TopLevel, 0).parse_list(ListEnd::Eof)?;
+    Ok(script)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ListEnd {
+    Eof,
+    Paren,
+    CaseItem,
+}
+
+struct PendingHeredoc {
+    command: usize,
+    redirection: usize,
+    delimiter: String,
+    strip_tabs: bool,
+    expand: bool,
+}
+
+#[derive(Default)]
+struct CommandBuilder {
+    assignments: Vec<(String, ShellWord)>,
+    words: Vec<ShellWord>,
+    redirections: Vec<Redirection>,
+    heredocs: Vec<(usize, String, bool, bool)>,
+}
+
+struct Parser<'s> {
+    chars: Vec<char>,
+    pos: usize,
+    script: &'s mut ShellScript,
+    origin: CommandOrigin,
+    depth: usize,
+    pending_heredocs: Vec<PendingHeredoc>,
+}
+
+impl<'s> Parser<'s> {
+    fn new(source: &str, script: &'s mut ShellScript, origin: CommandOrigin, depth: usize) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            pos: 0,
+            script,
+            origin,
+            depth,
+            pending_heredocs: Vec::new(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn at(&self, text: &str) -> bool {
+        text.chars()
+            .enumerate()
+            .all(|(offset, expected)| self.peek_at(offset) == Some(expected))
+    }
+
+    fn at_keyword(&self, keyword: &str) -> bool {
+        self.at(keyword)
+            && self
+                .peek_at(keyword.chars().count())
+                .is_none_or(is_metachar)
+    }
+
+    fn skip_blanks(&mut self) {
+        loop {
+            match self.peek() {
+                Some(' ' | '\t' | '\r') => self.pos += 1,
+                Some('\\') if self.peek_at(1) == Some('\n') => self.pos += 2,
+                _ => return,
+            }
+        }
+    }
+
+    fn skip_comment(&mut self) {
+        while self.peek().is_some_and(|c| c != '\n') {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_blank_lines(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_blanks();
+            match self.peek() {
+                Some('\n') => self.newline()?,
+                Some('#') => self.skip_comment(),
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn newline(&mut self) -> Result<(), String> {
+        self.pos += 1;
+        self.read_heredoc_bodies()
+    }
+
+    fn parse_list(&mut self, end: ListEnd) -> Result<(), String> {
+        loop {
+            self.skip_blanks();
+            match self.peek() {
+                None if end == ListEnd::Eof => return Ok(()),
+                None if end == ListEnd::Paren => return Err("unterminated `(`".to_string()),
+                None => return Err("unterminated `case`".to_string()),
+                Some('#') => self.skip_comment(),
+                Some('\n') => self.newline()?,
+                Some(')') if end == ListEnd::Paren => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(')') => return Err("unexpected `)`".to_string()),
+                Some(';')
+                    if end == ListEnd::CaseItem && matches!(self.peek_at(1), Some(';' | '&')) =>
+                {
+                    self.pos += 2;
+                    if self.peek() == Some('&') {
+                        self.pos += 1;
+                    }
+                    return Ok(());
+                }
+                Some(';') => self.pos += 1,
+                Some('&') if self.at("&&") => self.pos += 2,
+                Some('&') if !self.at("&>") => self.pos += 1,
+                Some('|') if self.at("||") => self.pos += 2,
+                _ if end == ListEnd::CaseItem && self.at_keyword("esac") => return Ok(()),
+                _ => self.parse_pipeline()?,
+            }
+        }
+    }
+
+    fn parse_nested_list(&mut self, origin: CommandOrigin) -> Result<(), String> {
+        if self.depth >= MAX_NESTING {
+            return Err("command nests too deeply to analyse".to_string());
+        }
+        let outer = std::mem::replace(&mut self.origin, origin);
+        self.depth += 1;
+        let result = self.parse_list(ListEnd::Paren);
+        self.depth -= 1;
+        self.origin = outer;
+        result
+    }
+
+    fn parse_nested_source(&mut self, source: &str, origin: CommandOrigin) -> Result<(), String> {
+        if self.depth >= MAX_NESTING {
+            return Err("command nests too deeply to analyse".to_string());
+        }
+        let depth = self.depth + 1;
+        Parser::new(source, &mut *self.script, origin, depth).parse_list(ListEnd::Eof)
+    }
+
+    fn parse_pipeline(&mut self) -> Result<(), String> {
+        let mut stages = Vec::new();
+        loop {
+            let start = self.script.commands.len();
+            self.parse_command()?;
+            stages.push((start..self.script.commands.len()).collect::<Vec<_>>());
+            self.skip_blanks();
+            if self.peek() != Some('|') || self.at("||") {
+                break;
+            }
+            self.pos += if self.at("|&") { 2 } else { 1 };
+            self.skip_blank_lines()?;
+        }
+        stages.retain(|stage| !stage.is_empty());
+        if stages.len() > 1 {
+            self.script.pipelines.push(stages);
+        }
+        Ok(())
+    }
+
+    fn parse_command(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_blanks();
+            if self.at("((") {
+                return self.skip_arithmetic();
+            }
+            let Some(keyword) = RESERVED_WORDS
+                .iter()
+                .find(|keyword| self.at_keyword(keyword))
+            else {
+                break;
+            };
+            self.pos += keyword.len();
+            match *keyword {
+                "for" | "select" => return self.skip_loop_head(),
+                "case" => return self.parse_case(),
+                "[[" => return self.skip_conditional(),
+                "function" => {
+                    self.skip_blanks();
+                    self.read_word()?;
+                    self.skip_blanks();
+                    if self.peek() == Some('(') {
+                        self.skip_function_parens()?;
+                    }
+                    return Ok(());
+                }
+                _ => {}
+            }
+        }
+        let mut builder = CommandBuilder::default();
+        if self.peek() == Some('(') {
+            self.pos += 1;
+            self.parse_nested_list(CommandOrigin::Subshell)?;
+            self.parse_words(&mut builder, true)?;
+        } else {
+            self.parse_words(&mut builder, false)?;
+        }
+        self.push_command(builder);
+        Ok(())
+    }
+
+    fn parse_words(
+        &mut self,
+        builder: &mut CommandBuilder,
+        redirections_only: bool,
+    ) -> Result<(), String> {
+        loop {
+            self.skip_blanks();
+            let Some(c) = self.peek() else {
+                return Ok(());
+            };
+            match c {
+                '\n' | ';' | '|' | ')' => return Ok(()),
+                '&' if !self.at("&>") => return Ok(()),
+                '#' => {
+                    self.skip_comment();
+                    return Ok(());
+                }
+                '<' | '>' if self.peek_at(1) == Some('(') => {
+                    let word = self.read_process_substitution()?;
+                    builder.words.push(word);
+                }
+                '<' | '>' | '&' => self.parse_redirection(builder)?,
+                c if c.is_ascii_digit() && self.fd_redirection_ahead() => {
+                    self.parse_redirection(builder)?
+                }
+                '(' if builder.words.len() == 1 && builder.assignments.is_empty() => {
+                    self.skip_function_parens()?;
+                    builder.words.clear();
+                    return Ok(());
+                }
+                '(' => return Err("unexpected `(`".to_string()),
+                _ if redirections_only => {
+                    return Err("unexpected word after a subshell".to_string())
+                }
+                _ => {
+                    if builder.words.is_empty() && self.try_assignment(builder)? {
+                        continue;
+                    }
+                    let word = self.read_word()?;
+                    builder.words.push(word);
+                }
+            }
+        }
+    }
+
+    fn push_command(&mut self, builder: CommandBuilder) {
+        if builder.words.is_empty()
+            && builder.assignments.is_empty()
+            && builder.redirections.is_empty()
+        {
+            return;
+        }
+        let command = self.script.commands.len();
+        for (redirection, delimiter, strip_tabs, expand) in builder.heredocs {
+            self.pending_heredocs.push(PendingHeredoc {
+                command,
+                redirection,
+                delimiter,
+                strip_tabs,
+                expand,
+            });
+        }
+        self.script.commands.push(SimpleCommand {
+            assignments: builder.assignments,
+            words: builder.words,
+            redirections: builder.redirections,
+            origin: self.origin,
+        });
+    }
+
+    fn fd_redirection_ahead(&self) -> bool {
+        let digits = self.chars[self.pos..]
+            .iter()
+            .take_while(|c| c.is_ascii_digit())
+            .count();
+        matches!(self.peek_at(digits), Some('<' | '>'))
+    }
+
+    fn parse_redirection(&mut self, builder: &mut CommandBuilder) -> Result<(), String> {
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+        let Some((operator, kind)) = REDIRECTION_OPERATORS
+            .iter()
+            .find(|(operator, _)| self.at(operator))
+            .copied()
+        else {
+            return Err("malformed redirection".to_string());
+        };
+        self.pos += operator.len();
+        self.skip_blanks();
+        if self.peek().is_none_or(is_metachar) {
+            return Err(format!("redirection `{operator}` is missing a target"));
+        }
+        let target = self.read_word()?;
+        let kind = match kind {
+            RedirectionKind::Duplicate
+                if target.text != "-" && !target.text.chars().all(|c| c.is_ascii_digit()) =>
+            {
+                if operator.starts_with('<') {
+                    RedirectionKind::Read
+                } else {
+                    RedirectionKind::Write
+                }
+            }
+            kind => kind,
+        };
+        let body = (kind == RedirectionKind::HereString).then(|| target.text.clone());
+        if kind == RedirectionKind::Heredoc {
+            builder.heredocs.push((
+                builder.redirections.len(),
+                target.text.clone(),
+                operator == "<<-",
+                !target.quoted,
+            ));
+        }
+        builder
+            .redirections
+            .push(Redirection { kind, target, body });
+        Ok(())
+    }
+
+    fn read_heredoc_bodies(&mut self) -> Result<(), String> {
+        for heredoc in std::mem::take(&mut self.pending_heredocs) {
+            let mut body = String::new();
+            while self.pos < self.chars.len() {
+                let end = self.chars[self.pos..]
+                    .iter()
+                    .position(|c| *c == '\n')
+                    .map_or(self.chars.len(), |offset| self.pos + offset);
+                let line = self.chars[self.pos..end].iter().collect::<String>();
+                self.pos = (end + 1).min(self.chars.len());
+                let line = if heredoc.strip_tabs {
+                    line.trim_start_matches('\t')
+                } else {
+                    line.as_str()
+                };
+                if line == heredoc.delimiter {
+                    break;
+                }
+                body.push_str(line);
+                body.push('\n');
+            }
+            if heredoc.expand {
+                self.parse_heredoc_expansions(body.as_str())?;
+            }
+            if let Some(redirection) = self
+                .script
+                .commands
+                .get_mut(heredoc.command)
+                .and_then(|command| command.redirections.get_mut(heredoc.redirection))
+            {
+                redirection.body = Some(body);
+            }
+        }
+        Ok(())
+    }
+
+    /// Unquoted heredoc bodies expand `$(...)` and backquotes, so those run too.
+    fn parse_heredoc_expansions(&mut self, body: &str) -> Result<(), String> {
+        if self.depth >= MAX_NESTING {
+            return Err("command nests too deeply to analyse".to_string());
+        }
+        let depth = self.depth + 1;
+        let mut nested = Parser::new(
+            body,
+            &mut *self.script,
+            CommandOrigin::CommandSubstitution,
+            depth,
+        );
+        let mut scratch = ShellWord::default();
+        while let Some(c) = nested.peek() {
+            match c {
+                '\\' => nested.pos += 2,
+                '$' => nested.read_dollar(&mut scratch, true)?,
+                '`' => nested.read_backtick(&mut scratch)?,
+                _ => nested.pos += 1,
+            }
+        }
+        Ok(())
+    }
+
+    fn try_assignment(&mut self, builder: &mut CommandBuilder) -> Result<bool, String> {
+        let name_len = self.chars[self.pos..]
+            .iter()
+            .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+            .count();
+        if name_len == 0 || self.chars[self.pos].is_ascii_digit() {
+            return Ok(false);
+        }
+        let mut equals = self.pos + name_len;
+        if self.chars.get(equals) == Some(&'+') {
+            equals += 1;
+        }
+        if self.chars.get(equals) != Some(&'=') {
+            return Ok(false);
+        }
+        let name = self.chars[self.pos..self.pos + name_len]
+            .iter()
+            .collect::<String>();
+        self.pos = equals + 1;
+        let value = if self.peek() == Some('(') {
+            self.pos += 1;
+            let mut value = ShellWord::default();
+            loop {
+                self.skip_blank_lines()?;
+                match self.peek() {
+                    Some(')') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    Some(c) if !is_metachar(c) => {
+                        let element = self.read_word()?;
+                        if !value.text.is_empty() {
+                            value.text.push(' ');
+                        }
+                        value.text.push_str(element.text.as_str());
+                        value.dynamic |= element.dynamic;
+                        value.quoted |= element.quoted;
+                    }
+                    _ => return Err("unterminated array assignment".to_string()),
+                }
+            }
+            value
+        } else if self.peek().is_none_or(is_metachar) {
+            ShellWord::default()
+        } else {
+            self.read_word()?
+        };
+        builder.assignments.push((name, value));
+        Ok(true)
+    }
+
+    fn skip_function_parens(&mut self) -> Result<(), String> {
+        self.pos += 1;
+        self.skip_blanks();
+        if self.peek() != Some(')') {
+            return Err("unexpected `(`".to_string());
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    fn skip_arithmetic(&mut self) -> Result<(), String> {
+        let mut open = 0usize;
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                '(' => open += 1,
+                ')' => {
+                    open -= 1;
+                    if open == 0 {
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Err("unterminated arithmetic expression".to_string())
+    }
+
+    /// `for name in words` and `select`: the words may hold substitutions, the rest is syntax.
+    fn skip_loop_head(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_blanks();
+            match self.peek() {
+                None | Some('\n' | ';' | '&' | '|' | ')') => return Ok(()),
+                Some('(') if self.at("((") => self.skip_arithmetic()?,
+                Some(c) if is_metachar(c) => self.pos += 1,
+                Some(_) => {
+                    self.read_word()?;
+                }
+            }
+        }
+    }
+
+    /// `[[ ... ]]` treats `<`, `>`, `&&` and `||` as test operators, not shell syntax.
+    fn skip_conditional(&mut self) -> Result<(), String> {
+        loop {
+            self.skip_blanks();
+            if self.at("]]") {
+                self.pos += 2;
+                return Ok(());
+            }
+            match self.peek() {
+                None => return Err("unterminated `[[`".to_string()),
+                Some('\n') => self.newline()?,
+                Some(c) if is_metachar(c) => self.pos += 1,
+                Some(_) => {
+                    self.read_word()?;
+                }
+            }
+        }
+    }
+
+    fn parse_case(&mut self) -> Result<(), String> {
+        self.skip_blanks();
+        self.read_word()?;
+        self.skip_blank_lines()?;
+        if !self.at_keyword("in") {
+            return Err("malformed `case` statement".to_string());
+        }
+        self.pos += 2;
+        loop {
+            self.skip_blank_lines()?;
+            if self.at_keyword("esac") {
+                self.pos += 4;
+                return Ok(());
+            }
+            if self.peek().is_none() {
+                return Err("unterminated `case`".to_string());
+            }
+            if self.peek() == Some('(') {
+                self.pos += 1;
+            }
+            loop {
+                self.skip_blanks();
+                match self.peek() {
+                    Some(')') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    Some('|') => self.pos += 1,
+                    Some(c) if !is_metachar(c) => {
+                        self.read_word()?;
+                    }
+                    _ => return Err("malformed `case` pattern".to_string()),
+                }
+            }
+            self.parse_list(ListEnd::CaseItem)?;
+        }
+    }
+
+    fn read_word(&mut self) -> Result<ShellWord, String> {
+        let mut word = ShellWord::default();
+        while let Some(c) = self.peek() {
+            match c {
+                c if is_metachar(c) => break,
+                '\\' => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('\n') => self.pos += 1,
+                        Some(escaped) => {
+                            word.text.push(escaped);
+                            word.quoted = true;
+                            self.pos += 1;
+                        }
+                        None => {}
+                    }
+                }
+                '\'' => {
+                    self.pos += 1;
+                    word.quoted = true;
+                    self.read_single_quoted(&mut word)?;
+                }
+                '"' => {
+                    self.pos += 1;
+                    word.quoted = true;
+                    self.read_double_quoted(&mut word)?;
+                }
+                '$' => self.read_dollar(&mut word, false)?,
+                '`' => self.read_backtick(&mut word)?,
+                _ => {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+        Ok(word)
+    }
+
+    fn read_single_quoted(&mut self, word: &mut ShellWord) -> Result<(), String> {
+        loop {
+            match self.peek() {
+                None => return Err("unterminated single quote".to_string()),
+                Some('\'') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(c) => {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn read_double_quoted(&mut self, word: &mut ShellWord) -> Result<(), String> {
+        loop {
+            match self.peek() {
+                None => return Err("unterminated double quote".to_string()),
+                Some('"') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some('\\') => match self.peek_at(1) {
+                    Some(escaped @ ('$' | '`' | '"' | '\\')) => {
+                        word.text.push(escaped);
+                        self.pos += 2;
+                    }
+                    Some('\n') => self.pos += 2,
+                    _ => {
+                        word.text.push('\\');
+                        self.pos += 1;
+                    }
+                },
+                Some('$') => self.read_dollar(word, true)?,
+                Some('`') => self.read_backtick(word)?,
+                Some(c) => {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn read_ansi_c_quoted(&mut self, word: &mut ShellWord) -> Result<(), String> {
+        loop {
+            match self.peek() {
+                None => return Err("unterminated `$'` string".to_string()),
+                Some('\'') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some('\\') => {
+                    let Some(escaped) = self.peek_at(1) else {
+                        return Err("unterminated `$'` string".to_string());
+                    };
+                    self.pos += 2;
+                    match escaped {
+                        'n' => word.text.push('\n'),
+                        't' => word.text.push('\t'),
+                        'r' => word.text.push('\r'),
+                        'e' | 'E' => word.text.push('\u{1b}'),
+                        'x' => {
+                            let digits = self.chars[self.pos..]
+                                .iter()
+                                .take(2)
+                                .take_while(|c| c.is_ascii_hexdigit())
+                                .collect::<String>();
+                            self.pos += digits.len();
+                            if let Some(decoded) = u32::from_str_radix(digits.as_str(), 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                            {
+                                word.text.push(decoded);
+                            }
+                        }
+                        other => word.text.push(other),
+                    }
+                }
+                Some(c) => {
+                    word.text.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+    }
+
+    fn read_dollar(&mut self, word: &mut ShellWord, in_double_quotes: bool) -> Result<(), String> {
+        let start = self.pos;
+        match self.peek_at(1) {
+            Some('(') if self.peek_at(2) == Some('(') => {
+                self.pos += 1;
+                self.skip_arithmetic()?;
+            }
+            Some('(') => {
+                self.pos += 2;
+                self.parse_nested_list(CommandOrigin::CommandSubstitution)?;
+            }
+            Some('{') => {
+                self.pos += 2;
+                self.skip_parameter_expansion()?;
+            }
+            Some('\'') if !in_double_quotes => {
+                self.pos += 2;
+                word.quoted = true;
+                return self.read_ansi_c_quoted(word);
+            }
+            // `$"..."` is a translated string; the caller reads the quote next.
+            Some('"') if !in_double_quotes => {
+                self.pos += 1;
+                return Ok(());
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == '_' => {
+                self.pos += 2;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_ascii_alphanumeric() || c == '_')
+                {
+                    self.pos += 1;
+                }
+            }
+            Some(c) if c.is_ascii_digit() || "@*#?$!-".contains(c) => self.pos += 2,
+            _ => {
+                word.text.push('$');
+                self.pos += 1;
+                return Ok(());
+            }
+        }
+        word.text.extend(&self.chars[start..self.pos]);
+        word.dynamic = true;
+        Ok(())
+    }
+
+    /// `${...}` may embed substitutions in its operands, e.g. `${x:-$(cmd)}`.
+    fn skip_parameter_expansion(&mut self) -> Result<(), String> {
+        let mut open = 1usize;
+        let mut scratch = ShellWord::default();
+        loop {
+            match self.peek() {
+                None => return Err("unterminated `${`".to_string()),
+                Some('{') => {
+                    open += 1;
+                    self.pos += 1;
+                }
+                Some('}') => {
+                    self.pos += 1;
+                    open -= 1;
+                    if open == 0 {
+                        return Ok(());
+                    }
+                }
+                Some('\\') => self.pos += 2,
+                Some('\'') => {
+                    self.pos += 1;
+                    self.read_single_quoted(&mut scratch)?;
+                }
+                Some('"') => {
+                    self.pos += 1;
+                    self.read_double_quoted(&mut scratch)?;
+                }
+                Some('$') => self.read_dollar(&mut scratch, true)?,
+                Some('`') => self.read_backtick(&mut scratch)?,
+                Some(_) => self.pos += 1,
+            }
+        }
+    }
+
+    fn read_backtick(&mut self, word: &mut ShellWord) -> Result<(), String> {
+        let start = self.pos;
+        self.pos += 1;
+        let mut body = String::new();
+        loop {
+            match self.peek() {
+                None => return Err("unterminated backquote".to_string()),
+                Some('`') => {
+                    self.pos += 1;
+                    break;
+                }
+                Some('\\') if matches!(self.peek_at(1), Some('`' | '\\' | '$')) => {
+                    body.push(self.chars[self.pos + 1]);
+                    self.pos += 2;
+                }
+                Some(c) => {
+                    body.push(c);
+                    self.pos += 1;
+                }
+            }
+        }
+        self.parse_nested_source(body.as_str(), CommandOrigin::CommandSubstitution)?;
+        word.text.extend(&self.chars[start..self.pos]);
+        word.dynamic = true;
+        Ok(())
+    }
+
+    fn read_process_substitution(&mut self) -> Result<ShellWord, String> {
+        let start = self.pos;
+        self.pos += 2;
+        self.parse_nested_list(CommandOrigin::ProcessSubstitution)?;
+        Ok(ShellWord {
+            text: self.chars[start..self.pos].iter().collect(),
+            dynamic: true,
+            quoted: false,
+        })
+    }
+}
+
+fn is_metachar(c: char) -> bool {
+    matches!(
+        c,
+        ' ' | '\t' | '\r' | '\n' | ';' | '&' | '|' | '<' | '>' | '(' | ')'
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(command: &SimpleCommand) -> Vec<&str> {
+        command
+            .words
+            .iter()
+            .map(|word| word.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn splits_lists_pipelines_and_substitutions_into_simple_commands() {
+        let script = parse_shell(
+            "cd app && FOO=1 make -j4 2>&1 | tee \"build log.txt\"; echo $(date +%s) `id -u`",
+        )
+        .expect("parse");
+        let commands = script.commands.iter().map(argv).collect::<Vec<_>>();
+        assert_eq!(
+            commands,
+            vec![
+                vec!["cd", "app"],
+                vec!["make", "-j4"],
+                vec!["tee", "build log.txt"],
+                vec!["date", "+%s"],
+                vec!["id", "-u"],
+                vec!["echo", "$(date +%s)", "`id -u`"],
+            ]
+        );
+        assert_eq!(script.commands[1].assignments[0].0, "FOO");
+        assert_eq!(
+            script.commands[1].redirections[0].kind,
+            RedirectionKind::Duplicate
+        );
+        assert_eq!(script.pipelines, vec![vec![vec![1], vec![2]]]);
+        assert_eq!(
+            script.commands[3].origin,
+            CommandOrigin::CommandSubstitution
+        );
+        assert!(script.commands[5].words[1].dynamic);
+    }
+
+    #[test]
+    fn reads_heredoc_bodies_and_their_expansions() {
+        let script = parse_shell("bash <<-EOF > out.txt\n\trm -rf $(pwd)\n\tEOF\necho done\n")
+            .expect("parse");
+        let commands = script.commands.iter().map(argv).collect::<Vec<_>>();
+        assert_eq!(
+            commands,
+            vec![vec!["bash"], vec!["pwd"], vec!["echo", "done"]]
+        );
+        let bash = &script.commands[0];
+        assert_eq!(bash.redirections[0].kind, RedirectionKind::Heredoc);
+        assert_eq!(
+            bash.redirections[0].body.as_deref(),
+            Some("rm -rf $(pwd)\n")
+        );
+        assert_eq!(bash.redirections[1].kind, RedirectionKind::Write);
+        assert_eq!(bash.redirections[1].target.text, "out.txt");
+    }
+
+    #[test]
+    fn understands_control_flow_and_subshells() {
+        let script = parse_shell(
+            "if [[ -f a && $x > 1 ]]; then (cd b; ls) | wc -l; fi\nfor f in $(ls); do case $f in *.sh) sh \"$f\";; *) :;; esac; done",
+        )
+        .expect("parse");
+        let commands = script.commands.iter().map(argv).collect::<Vec<_>>();
+        assert_eq!(
+            commands,
+            vec![
+                vec!["cd", "b"],
+                vec!["ls"],
+                vec!["wc", "-l"],
+                vec!["ls"],
+                vec!["sh", "$f"],
+                vec![":"],
+            ]
+        );
+        assert_eq!(script.commands[0].origin, CommandOrigin::Subshell);
+        assert_eq!(script.pipelines, vec![vec![vec![0, 1], vec![2]]]);
+    }
+
+    #[test]
+    fn rejects_unterminated_syntax() {
+        for source in ["echo 'open", "echo \"open", "echo $(date", "(ls", "cat <"] {
+            assert!(parse_shell(source).is_err(), "{source}");
+        }
+    }
+}
@@ -17,9 +17,10 @@ use uuid::Uuid;
 use crate::{local_now_rfc3339, tracing_stdout, LocalState};
 use crate::{relay::RelayRequest, WorkspaceState};
 
+use super::command_rules::{evaluate_command_rules, CommandRuleVerdict};
 use super::fingerprint::normalized_command;
 use super::pending::request_pending_approval;
-use super::risk::classify_command_request;
+use super::risk::{classify_command_request, command_line_for_analysis};
 use super::types::{
     ApprovalDecision, ApprovalHistoryEntry, ApprovalMode, ApprovalProjectKey, ApprovalSource,
     CommandApprovalRequest, WhitelistCwdScope,
@@ -88,9 +89,25 @@ impl CommandApprovalService {
         let mode =
             forced_mode.unwrap_or_else(|| approval_mode_for_request(&state_snapshot, &request));
         let risk = classify_command_request(
-            normalized_command(request.command.as_str(), request.args.as_slice()).as_str(),
+            command_line_for_analysis(request.command.as_str(), request.args.as_slice()).as_str(),
             request.requested_permissions.as_ref(),
         );
+        let rule_verdict = evaluate_command_rules(
+            state_snapshot.approval.command_rules.as_slice(),
+            &request.project_key,
+            request.command.as_str(),
+            request.args.as_slice(),
+        );
+
+        if let Some(verdict @ CommandRuleVerdict::Deny { .. }) = rule_verdict.as_ref() {
+            let decision = ApprovalDecision::Denied {
+                source: ApprovalSource::CommandRule,
+                reason: verdict.reason(),
+            };
+            self.append_history(&request, mode, &decision, risk.level, risk.reason)
+                .await?;
+            return Ok(decision);
+        }
 
         if allow_session_approval && session_approval_matches(&request).await {
             let decision = ApprovalDecision::Approved {
@@ -129,12 +146,32 @@ impl CommandApprovalService {
             return Ok(decision);
         }
 
+        // Allow rules never cover high-risk findings such as `LD_PRELOAD=` or `curl | sh`,
+        // which argv patterns cannot see.
+        if let Some(verdict @ CommandRuleVerdict::Allow { .. }) =
+            rule_verdict.as_ref().filter(|_| {
+                allow_whitelist && request.requested_permissions.is_none() && risk.level != "high"
+            })
+        {
+            let decision = ApprovalDecision::Approved {
+                source: ApprovalSource::CommandRule,
+                reason: Some(verdict.reason()),
+                whitelist_entry_id: None,
+                granted_permissions: None,
+                permission_scope: PermissionGrantScope::Turn,
+            };
+            self.append_history(&request, mode, &decision, risk.level, risk.reason)
+                .await?;
+            return Ok(decision);
+        }
+
         let in_progress_id = if mode == ApprovalMode::AutoApproval {
             Some(
                 start_in_progress_approval(
                     &request,
                     risk.level.clone(),
                     Some("AI 正在结合项目文件审核这条命令".to_string()),
+                    risk.findings.clone(),
                 )
                 .await,
             )
@@ -305,7 +342,9 @@ impl CommandApprovalService {
         reason_override: Option<String>,
     ) -> Result<ApprovalDecision> {
         let reason = reason_override.or_else(|| risk.reason.clone());
-        let pending = request_pending_approval(request, risk.level.clone(), reason).await;
+        let pending =
+            request_pending_approval(request, risk.level.clone(), reason, risk.findings.clone())
+                .await;
         match pending.decision {
             CommandExecutionApprovalDecision::Simple(
                 SimpleCommandExecutionApprovalDecision::Accept,
@@ -784,6 +823,60 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn command_rules_deny_before_full_control_and_allow_without_prompting() {
+        let document = chatos_sandbox_contract::parse_command_rules_toml(
+            r#"
+[[command_rules]]
+decision = "allow"
+pattern = "cargo test **"
+
+[[command_rules]]
+decision = "deny"
+scope = "global"
+pattern = "cargo publish **"
+"#,
+        )
+        .expect("parse rules");
+        let base = request("command-rules", false);
+        let mut state = LocalState::default();
+        state.approval.command_rules = super::super::command_rules::build_command_rule_entries(
+            document,
+            Some(&base.project_key),
+        )
+        .expect("build rules");
+        let state = Arc::new(RwLock::new(state));
+        let temp = tempfile::tempdir().expect("approval state directory");
+        let service = CommandApprovalService::new(temp.path().join("state.json"), state.clone());
+
+        let mut allowed = base.clone();
+        allowed.args = vec!["test".to_string(), "-p".to_string(), "foo".to_string()];
+        assert!(matches!(
+            service
+                .approve_with_mode(allowed, ApprovalMode::RequestApproval)
+                .await
+                .expect("allow rule decision"),
+            ApprovalDecision::Approved {
+                source: ApprovalSource::CommandRule,
+                ..
+            }
+        ));
+
+        let mut denied = base.clone();
+        denied.args = vec!["publish".to_string()];
+        assert!(matches!(
+            service
+                .approve_with_mode(denied, ApprovalMode::FullControl)
+                .await
+                .expect("deny rule decision"),
+            ApprovalDecision::Denied {
+                source: ApprovalSource::CommandRule,
+                ..
+            }
+        ));
+        assert_eq!(state.read().await.approval.history.len(), 2);
+    }
+
     #[tokio::test]
     async fn sensitive_interactive_arguments_are_redacted_from_persisted_history() {
         let secret = "private text for the focused field";
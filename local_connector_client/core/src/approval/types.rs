@@ -2,11 +2,13 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 use chatos_sandbox_contract::{
-    CommandExecutionApprovalDecision, GrantedPermissionProfile, PermissionGrantScope,
+    CommandExecutionApprovalDecision, CommandRule, GrantedPermissionProfile, PermissionGrantScope,
     RequestPermissionProfile,
 };
 use serde::{Deserialize, Serialize};
 
+use super::risk::RiskFinding;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub(crate) struct ApprovalState {
     #[serde(default)]
@@ -18,6 +20,8 @@ pub(crate) struct ApprovalState {
     #[serde(default)]
     pub(crate) whitelist: Vec<CommandWhitelistEntry>,
     #[serde(default)]
+    pub(crate) command_rules: Vec<CommandRuleEntry>,
+    #[serde(default)]
     pub(crate) history: Vec<ApprovalHistoryEntry>,
 }
 
@@ -28,6 +32,7 @@ impl Default for ApprovalState {
             settings_revision: None,
             projects: Vec::new(),
             whitelist: Vec::new(),
+            command_rules: Vec::new(),
             history: Vec::new(),
         }
     }
@@ -73,6 +78,17 @@ pub(crate) struct CommandWhitelistEntry {
     pub(crate) enabled: bool,
 }
 
+/// Argv pattern rule; `project_key` is `None` for rules that apply to every project.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub(crate) struct CommandRuleEntry {
+    pub(crate) id: String,
+    pub(crate) project_key: Option<ApprovalProjectKey>,
+    pub(crate) rule: CommandRule,
+    pub(crate) created_at: String,
+    #[serde(default = "default_true")]
+    pub(crate) enabled: bool,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub(crate) enum WhitelistCwdScope {
@@ -92,6 +108,7 @@ pub(crate) enum ApprovalSource {
     Ai,
     FullControl,
     StaticRule,
+    CommandRule,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -182,6 +199,8 @@ pub(crate) struct PendingApprovalItem {
     pub(crate) source: String,
     pub(crate) risk: String,
     pub(crate) reason: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(crate) risk_findings: Vec<RiskFinding>,
     pub(crate) created_at: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) requested_permissions: Option<RequestPermissionProfile>,
@@ -478,6 +478,7 @@ fn expected_manifest_and_inventory(
         dependencies: PluginDependencySpec::default(),
         permissions: permissions.clone(),
         bundled_content_variant: Some("chatos-internal-skill-bundles-v2".to_string()),
+        wasm_modules: Vec::new(),
     };
     let by_name = skills
         .iter()
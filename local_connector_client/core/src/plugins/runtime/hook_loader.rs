@@ -10,21 +10,29 @@ use std::time::{Duration, Instant};
 use anyhow::{anyhow, bail, Context, Result};
 use chatos_mcp_runtime::McpStdioServer;
 use chatos_plugin_management_sdk::{
-    normalized_plugin_hook_set_sha256, parse_plugin_hook_set, plugin_component_descriptors,
-    plugin_hook_snapshot_sha256, PluginComponentKind, PluginHook, PluginHookEntrypoint,
-    PluginHookEvent, PluginHookEventContext, PluginHookFailurePolicy, PluginHookSet,
+    is_plugin_wasm_component, normalized_plugin_hook_set_sha256, parse_plugin_hook_response,
+    parse_plugin_hook_set, plugin_component_descriptors, plugin_hook_snapshot_sha256,
+    validate_plugin_hook_set_wasm_modules, PluginComponentKind, PluginHook, PluginHookDecision,
+    PluginHookEntrypoint, PluginHookEvent, PluginHookEventContext, PluginHookFailurePolicy,
+    PluginHookResponse, PluginHookSet, PLUGIN_HOOK_MAX_PROMPT_BYTES,
+    PLUGIN_HOOK_MAX_TOOL_INPUT_BYTES,
 };
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 
 use super::mcp_runtime::load_verified_manifest;
 use crate::plugins::{ActivePluginInstallation, PluginInstaller};
 
+mod wasm_runtime;
+
+use wasm_runtime::{run_wasm_hook, WasmHookInvocation};
+
 const MAX_HOOK_SET_BYTES: u64 = 512 * 1024;
 const MAX_HOOK_COMMAND_BYTES: u64 = 16 * 1024 * 1024;
-const MAX_HOOK_INPUT_BYTES: usize = 64 * 1024;
+const MAX_HOOK_WASM_MODULE_BYTES: u64 = 32 * 1024 * 1024;
+const MAX_HOOK_INPUT_BYTES: usize = 128 * 1024;
 const DISPATCH_HOOK_EVENT_OPERATION: &str = "dispatch_hook_event";
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -62,6 +70,20 @@ pub struct PluginHookExecutionRecord {
     pub workspace_write_approved: Option<bool>,
     #[serde(default)]
     pub error: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response: Option<PluginHookResponse>,
+}
+
+/// The strongest decision of one dispatch: `deny` over `ask` over `allow`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginHookDispatchDecision {
+    pub decision: PluginHookDecision,
+    pub hook_id: String,
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Set once the local user answered an `ask` decision.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_approved: Option<bool>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -70,6 +92,13 @@ pub struct PluginHookDispatchResult {
     pub snapshot_sha256: String,
     pub blocking_failure: bool,
     pub executions: Vec<PluginHookExecutionRecord>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub decision: Option<PluginHookDispatchDecision>,
+    /// Tool arguments after every rewrite; absent when no Hook rewrote them or the call was denied.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub updated_input: Option<Value>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub additional_context: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -115,6 +144,8 @@ impl PluginHookLoader {
             "Plugin Hook set source",
         )?;
         let hook_set = parse_plugin_hook_set(raw.as_str()).context("parse Plugin Hook set")?;
+        validate_plugin_hook_set_wasm_modules(&hook_set, manifest.wasm_modules.as_slice())
+            .context("resolve Plugin Hook Wasm modules")?;
         if hook_set.hooks.iter().any(|hook| hook.workspace_write) {
             if !permission_snapshot.contains("workspace.write") {
                 bail!(
@@ -133,20 +164,52 @@ impl PluginHookLoader {
             normalized_plugin_hook_set_sha256(&hook_set).context("hash Plugin Hook set")?;
         let mut command_sha256_by_hook = BTreeMap::new();
         for definition in &hook_set.hooks {
-            let command = definition.entrypoint.command().path.as_str();
-            let (_, command_sha256) = read_verified_package_bytes(
-                &installation,
-                command,
-                installation
-                    .version
-                    .package_file_sha256
-                    .get(command.trim_start_matches("./"))
-                    .context("Plugin Hook command is not covered by package checksums")?,
-                MAX_HOOK_COMMAND_BYTES,
-                "Plugin Hook command",
-            )?;
-            validate_executable(installation.installation_path.join(command).as_path())?;
-            command_sha256_by_hook.insert(definition.id.clone(), command_sha256);
+            let entrypoint_sha256 = match &definition.entrypoint {
+                PluginHookEntrypoint::Command { command, .. } => {
+                    let command = command.path.as_str();
+                    let (_, command_sha256) = read_verified_package_bytes(
+                        &installation,
+                        command,
+                        installation
+                            .version
+                            .package_file_sha256
+                            .get(command.trim_start_matches("./"))
+                            .context("Plugin Hook command is not covered by package checksums")?,
+                        MAX_HOOK_COMMAND_BYTES,
+                        "Plugin Hook command",
+                    )?;
+                    validate_executable(installation.installation_path.join(command).as_path())?;
+                    command_sha256
+                }
+                PluginHookEntrypoint::Wasm { module, .. } => {
+                    let module = manifest
+                        .wasm_modules
+                        .iter()
+                        .find(|declared| declared.module_key == *module)
+                        .context(
+                            "Plugin Hook Wasm module is not declared in the active Manifest",
+                        )?;
+                    if module.capabilities.workspace_read
+                        && !permission_snapshot.contains("workspace.read")
+                    {
+                        bail!("Plugin Hook Wasm workspace.read permission is missing from the prepared snapshot");
+                    }
+                    // The Manifest digest is covered by the release signature, so checking it as
+                    // well as the package checksum ties the module bytes to the signed release.
+                    let (bytes, module_sha256) = read_verified_package_bytes(
+                        &installation,
+                        module.source.path.as_str(),
+                        module.sha256.as_str(),
+                        MAX_HOOK_WASM_MODULE_BYTES,
+                        "Plugin Hook Wasm module",
+                    )?;
+                    if !is_plugin_wasm_component(bytes.as_slice()) {
+                        bail!("Plugin Hook Wasm module is not a WASI preview 2 component");
+                    }
+                    module_sha256
+                }
+            };
+            command_sha256_by_hook.insert(definition.id.clone(), entrypoint_sha256);
         }
         let snapshot_sha256 = plugin_hook_snapshot_sha256(
             plugin_id,
@@ -183,7 +246,7 @@ impl PluginHookLoader {
         event: PluginHookEvent,
         context: &PluginHookEventContext,
     ) -> Result<Vec<String>> {
-        validate_event_context(context)?;
+        validate_event_context(event, context)?;
         Ok(snapshot
             .hook_set
             .hooks
@@ -205,8 +268,9 @@ impl PluginHookLoader {
         event: PluginHookEvent,
         context: &PluginHookEventContext,
         workspace_write_decisions: &BTreeMap<String, PluginHookWorkspaceWriteDecision>,
+        workspace_read_root: Option<&Path>,
     ) -> Result<PluginHookDispatchResult> {
-        validate_event_context(context)?;
+        validate_event_context(event, context)?;
         let active = self.load(
             snapshot.plugin_id.as_str(),
             snapshot.component_key.as_str(),
@@ -220,13 +284,17 @@ impl PluginHookLoader {
             .installer
             .active_installation(snapshot.plugin_id.as_str())?
             .context("Plugin is not installed and active")?;
+        let mut context = context.clone();
         let mut executions = Vec::new();
         let mut blocking_failure = false;
+        let mut decision: Option<PluginHookDispatchDecision> = None;
+        let mut updated_input = None;
+        let mut additional_context = Vec::new();
         for hook in &snapshot.hook_set.hooks {
             if !hook.events.contains(&event) {
                 continue;
             }
-            if !hook.matcher.matches(context) {
+            if !hook.matcher.matches(&context) {
                 executions.push(unmatched_execution(
                     hook.id.as_str(),
                     event,
@@ -244,7 +312,7 @@ impl PluginHookLoader {
                             hook,
                             run_id,
                             event,
-                            context,
+                            &context,
                             Some(workspace_root.as_path()),
                         )
                         .await
@@ -265,19 +333,67 @@ impl PluginHookLoader {
                     ),
                 }
             } else {
-                self.execute_command(&installation, snapshot, hook, run_id, event, context, None)
-                    .await
+                // Wasm Hooks read the workspace through a read-only preopen when their module
+                // grants `workspaceRead`; command Hooks without workspace-write get no root.
+                let workspace_root = hook.entrypoint.wasm_module().and(workspace_read_root);
+                self.execute_command(
+                    &installation,
+                    snapshot,
+                    hook,
+                    run_id,
+                    event,
+                    &context,
+                    workspace_root,
+                )
+                .await
             };
             if !record.succeeded && hook.failure_policy == PluginHookFailurePolicy::FailRun {
                 blocking_failure = true;
             }
+            let response = record.response.clone();
             executions.push(record);
+            let Some(response) = response else {
+                continue;
+            };
+            if let Some(text) = response.additional_context {
+                additional_context.push(text);
+            }
+            if let Some(input) = response.updated_input {
+                // Later Hooks see, and may rewrite again, the arguments the tool will receive.
+                context.tool_input = Some(input.clone());
+                updated_input = Some(input);
+            }
+            if let Some(next) = response.decision {
+                if decision
+                    .as_ref()
+                    .is_none_or(|current| next > current.decision)
+                {
+                    decision = Some(PluginHookDispatchDecision {
+                        decision: next,
+                        hook_id: hook.id.clone(),
+                        reason: response.reason,
+                        user_approved: None,
+                    });
+                }
+                if next == PluginHookDecision::Deny {
+                    break;
+                }
+            }
+        }
+        if decision
+            .as_ref()
+            .is_some_and(|decision| decision.decision == PluginHookDecision::Deny)
+        {
+            updated_input = None;
         }
         Ok(PluginHookDispatchResult {
             event,
             snapshot_sha256: snapshot.snapshot_sha256.clone(),
             blocking_failure,
             executions,
+            decision,
+            updated_input,
+            additional_context,
         })
     }
 
@@ -304,24 +420,41 @@ impl PluginHookLoader {
             )
             .await
         {
-            Ok(output) => PluginHookExecutionRecord {
-                hook_id: hook.id.clone(),
-                event,
-                failure_policy: hook.failure_policy,
-                matched: true,
-                succeeded: output.exit_code == Some(0) && !output.timed_out,
-                timed_out: output.timed_out,
-                exit_code: output.exit_code,
-                duration_ms: elapsed_millis(started),
-                stdout_bytes: output.stdout.total_bytes,
-                stderr_bytes: output.stderr.total_bytes,
-                stdout_sha256: output.stdout.sha256,
-                stderr_sha256: output.stderr.sha256,
-                output_truncated: output.stdout.truncated || output.stderr.truncated,
-                workspace_write: hook.workspace_write,
-                workspace_write_approved: hook.workspace_write.then_some(true),
-                error: output.error,
-            },
+            Ok(output) => {
+                let exited_cleanly = output.exit_code == Some(0) && !output.timed_out;
+                let (response, response_error) = if !exited_cleanly {
+                    (None, None)
+                } else if output.stdout.truncated {
+                    (
+                        None,
+                        Some("Plugin Hook stdout exceeds maxOutputBytes".to_string()),
+                    )
+                } else {
+                    match parse_plugin_hook_response(event, output.stdout.bytes.as_slice()) {
+                        Ok(response) => (response, None),
+                        Err(error) => (None, Some(sanitize_error(error.to_string().as_str()))),
+                    }
+                };
+                PluginHookExecutionRecord {
+                    hook_id: hook.id.clone(),
+                    event,
+                    failure_policy: hook.failure_policy,
+                    matched: true,
+                    succeeded: exited_cleanly && response_error.is_none(),
+                    timed_out: output.timed_out,
+                    exit_code: output.exit_code,
+                    duration_ms: elapsed_millis(started),
+                    stdout_bytes: output.stdout.total_bytes,
+                    stderr_bytes: output.stderr.total_bytes,
+                    stdout_sha256: output.stdout.sha256,
+                    stderr_sha256: output.stderr.sha256,
+                    output_truncated: output.stdout.truncated || output.stderr.truncated,
+                    workspace_write: hook.workspace_write,
+                    workspace_write_approved: hook.workspace_write.then_some(true),
+                    error: output.error.or(response_error),
+                    response,
+                }
+            }
             Err(error) => PluginHookExecutionRecord {
                 hook_id: hook.id.clone(),
                 event,
@@ -339,6 +472,7 @@ impl PluginHookLoader {
                 workspace_write: hook.workspace_write,
                 workspace_write_approved: hook.workspace_write.then_some(true),
                 error: Some(sanitize_error(error.to_string().as_str())),
+                response: None,
             },
         }
     }
@@ -355,6 +489,21 @@ impl PluginHookLoader {
     ) -> Result<HookCommandOutput> {
         let (command, args) = match &hook.entrypoint {
             PluginHookEntrypoint::Command { command, args } => (command, args),
+            PluginHookEntrypoint::Wasm { module, args } => {
+                return self
+                    .run_wasm(
+                        installation,
+                        snapshot,
+                        hook,
+                        module.as_str(),
+                        args,
+                        run_id,
+                        event,
+                        context,
+                        workspace_root,
+                    )
+                    .await;
+            }
         };
         let command_path = installation.installation_path.join(command.path.as_str());
         let server = McpStdioServer::new(
@@ -366,20 +515,7 @@ impl PluginHookLoader {
         if hook.workspace_write {
             workspace_root.context("Plugin Hook workspace-write approval is unavailable")?;
         }
-        let input = serde_json::to_vec(&json!({
-            "schemaVersion": 1,
-            "event": event,
-            "runId": run_id,
-            "pluginId": snapshot.plugin_id,
-            "releaseId": snapshot.release_id,
-            "componentKey": snapshot.component_key,
-            "hookId": hook.id,
-            "hookSnapshotSha256": snapshot.snapshot_sha256,
-            "context": context,
-        }))?;
-        if input.len() > MAX_HOOK_INPUT_BYTES {
-            bail!("Plugin Hook input exceeds its size limit");
-        }
+        let input = hook_input(snapshot, hook, run_id, event, context)?;
         let mut command = tokio::process::Command::new(server.command.as_str());
         command
             .args(server.args.unwrap_or_default())
@@ -411,6 +547,8 @@ impl PluginHookLoader {
         stdin.write_all(input.as_slice()).await?;
         stdin.write_all(b"\n").await?;
         stdin.shutdown().await?;
+        // Closing the pipe is what gives the Hook EOF on stdin.
+        drop(stdin);
         let stdout = child
             .stdout
             .take()
@@ -455,6 +593,80 @@ impl PluginHookLoader {
             error,
         })
     }
+
+    /// Runs a Wasm Hook in-process. The module is re-read and checked against the digest pinned
+    /// in the snapshot, and its grants come from the active signed Manifest. `workspace_root` is
+    /// only preopened, read-only, when the module declares `workspaceRead`.
+    #[allow(clippy::too_many_arguments)]
+    async fn run_wasm(
+        &self,
+        installation: &ActivePluginInstallation,
+        snapshot: &PluginHookSetSnapshot,
+        hook: &chatos_plugin_management_sdk::PluginHookDefinition,
+        module_key: &str,
+        args: &[String],
+        run_id: &str,
+        event: PluginHookEvent,
+        context: &PluginHookEventContext,
+        workspace_root: Option<&Path>,
+    ) -> Result<HookCommandOutput> {
+        let manifest = load_verified_manifest(installation)?;
+        let module = manifest
+            .wasm_modules
+            .iter()
+            .find(|declared| declared.module_key == module_key)
+            .context("Plugin Hook Wasm module is not declared in the active Manifest")?;
+        let pinned_sha256 = snapshot
+            .command_sha256_by_hook
+            .get(hook.id.as_str())
+            .context("Plugin Hook Wasm module is missing from the snapshot")?;
+        if module.sha256 != *pinned_sha256 {
+            bail!("Plugin Hook Wasm module does not match the immutable Hook snapshot");
+        }
+        let (module_bytes, _) = read_verified_package_bytes(
+            installation,
+            module.source.path.as_str(),
+            pinned_sha256.as_str(),
+            MAX_HOOK_WASM_MODULE_BYTES,
+            "Plugin Hook Wasm module",
+        )?;
+        let input = hook_input(snapshot, hook, run_id, event, context)?;
+        run_wasm_hook(WasmHookInvocation {
+            module_key,
+            module_bytes,
+            capabilities: &module.capabilities,
+            args,
+            input,
+            workspace_root,
+            timeout_ms: hook.timeout_ms,
+            max_output_bytes: hook.max_output_bytes,
+        })
+        .await
+    }
+}
+
+fn hook_input(
+    snapshot: &PluginHookSetSnapshot,
+    hook: &chatos_plugin_management_sdk::PluginHookDefinition,
+    run_id: &str,
+    event: PluginHookEvent,
+    context: &PluginHookEventContext,
+) -> Result<Vec<u8>> {
+    let input = serde_json::to_vec(&json!({
+        "schemaVersion": 1,
+        "event": event,
+        "runId": run_id,
+        "pluginId": snapshot.plugin_id,
+        "releaseId": snapshot.release_id,
+        "componentKey": snapshot.component_key,
+        "hookId": hook.id,
+        "hookSnapshotSha256": snapshot.snapshot_sha256,
+        "context": context,
+    }))?;
+    if input.len() > MAX_HOOK_INPUT_BYTES {
+        bail!("Plugin Hook input exceeds its size limit");
+    }
+    Ok(input)
 }
 
 #[derive(Debug)]
@@ -468,6 +680,7 @@ struct HookCommandOutput {
 
 #[derive(Debug)]
 struct BoundedOutput {
+    bytes: Vec<u8>,
     total_bytes: usize,
     sha256: String,
     truncated: bool,
@@ -475,7 +688,7 @@ struct BoundedOutput {
 
 async fn read_bounded(mut reader: impl AsyncRead + Unpin, limit: usize) -> Result<BoundedOutput> {
     let mut total_bytes = 0usize;
-    let mut retained = 0usize;
+    let mut retained = Vec::new();
     let mut hasher = Sha256::new();
     let mut buffer = [0u8; 8192];
     loop {
@@ -485,12 +698,11 @@ async fn read_bounded(mut reader: impl AsyncRead + Unpin, limit: usize) -> Resul
         }
         total_bytes = total_bytes.saturating_add(read);
         hasher.update(&buffer[..read]);
-        let keep = read.min(limit.saturating_sub(retained));
-        if keep > 0 {
-            retained += keep;
-        }
+        let keep = read.min(limit.saturating_sub(retained.len()));
+        retained.extend_from_slice(&buffer[..keep]);
     }
     Ok(BoundedOutput {
+        bytes: retained,
         total_bytes,
         sha256: hex::encode(hasher.finalize()),
         truncated: total_bytes > limit,
@@ -620,7 +832,7 @@ fn validate_executable(path: &std::path::Path) -> Result<()> {
     Ok(())
 }
 
-fn validate_event_context(context: &PluginHookEventContext) -> Result<()> {
+fn validate_event_context(event: PluginHookEvent, context: &PluginHookEventContext) -> Result<()> {
     for (field, value) in [
         ("agentKey", context.agent_key.as_deref()),
         ("toolName", context.tool_name.as_deref()),
@@ -645,6 +857,23 @@ fn validate_event_context(context: &PluginHookEventContext) -> Result<()> {
     }) {
         bail!("Plugin Hook event context summarySha256 is invalid");
     }
+    if let Some(input) = &context.tool_input {
+        if !matches!(
+            event,
+            PluginHookEvent::PreToolUse | PluginHookEvent::PostToolUse
+        ) || !input.is_object()
+            || serde_json::to_vec(input)?.len() > PLUGIN_HOOK_MAX_TOOL_INPUT_BYTES
+        {
+            bail!("Plugin Hook event context toolInput is invalid");
+        }
+    }
+    if context.prompt.as_deref().is_some_and(|prompt| {
+        event != PluginHookEvent::UserPromptSubmit
+            || prompt.len() > PLUGIN_HOOK_MAX_PROMPT_BYTES
+            || prompt.contains('\0')
+    }) {
+        bail!("Plugin Hook event context prompt is invalid");
+    }
     Ok(())
 }
 
@@ -671,6 +900,7 @@ fn unmatched_execution(
         workspace_write,
         workspace_write_approved: None,
         error: None,
+        response: None,
     }
 }
 
@@ -697,6 +927,7 @@ fn workspace_write_denied_execution(
         workspace_write: true,
         workspace_write_approved: Some(false),
         error: Some(sanitize_error(reason)),
+        response: None,
     }
 }
 
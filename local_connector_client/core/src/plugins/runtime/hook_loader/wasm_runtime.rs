@@ -0,0 +1,294 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use bytes::Bytes;
+use chatos_plugin_management_sdk::PluginWasmCapabilities;
+use sha2::{Digest, Sha256};
+use wasmtime::component::{Component, Linker, ResourceTable};
+use wasmtime::{Config, Engine, Store, StoreLimits, StoreLimitsBuilder, Trap};
+use wasmtime_wasi::bindings::Command;
+use wasmtime_wasi::pipe::MemoryInputPipe;
+use wasmtime_wasi::{
+    DirPerms, FilePerms, I32Exit, IoView, OutputStream, Pollable, StdoutStream, StreamError,
+    StreamResult, WasiCtx, WasiCtxBuilder, WasiView,
+};
+
+use super::{sanitize_error, BoundedOutput, HookCommandOutput};
+
+/// Guest path of the read-only workspace preopen.
+const WASM_WORKSPACE_GUEST_PATH: &str = "/workspace";
+
+/// One Wasm Hook invocation. The guest only gets what the signed Manifest grants: the workspace
+/// preopened read-only when `workspaceRead` is set, the declared environment variables, no
+/// sockets, and a memory cap. Execution stops at the shorter of the Hook timeout and the module's
+/// `maxDurationMs`.
+pub(super) struct WasmHookInvocation<'a> {
+    pub(super) module_key: &'a str,
+    pub(super) module_bytes: Vec<u8>,
+    pub(super) capabilities: &'a PluginWasmCapabilities,
+    pub(super) args: &'a [String],
+    pub(super) input: Vec<u8>,
+    pub(super) workspace_root: Option<&'a Path>,
+    pub(super) timeout_ms: u64,
+    pub(super) max_output_bytes: usize,
+}
+
+struct WasmHookState {
+    ctx: WasiCtx,
+    table: ResourceTable,
+    limits: StoreLimits,
+}
+
+impl IoView for WasmHookState {
+    fn table(&mut self) -> &mut ResourceTable {
+        &mut self.table
+    }
+}
+
+impl WasiView for WasmHookState {
+    fn ctx(&mut self) -> &mut WasiCtx {
+        &mut self.ctx
+    }
+}
+
+pub(super) async fn run_wasm_hook(invocation: WasmHookInvocation<'_>) -> Result<HookCommandOutput> {
+    let mut config = Config::new();
+    config
+        .async_support(true)
+        .wasm_component_model(true)
+        .epoch_interruption(true);
+    let engine = Engine::new(&config).context("create Plugin Hook Wasm engine")?;
+    let component = {
+        let engine = engine.clone();
+        let bytes = invocation.module_bytes;
+        tokio::task::spawn_blocking(move || Component::new(&engine, bytes))
+            .await
+            .map_err(|_| anyhow!("Plugin Hook Wasm compilation failed"))?
+            .context("compile Plugin Hook Wasm module")?
+    };
+    let mut linker = Linker::new(&engine);
+    wasmtime_wasi::add_to_linker_async(&mut linker).context("link Plugin Hook WASI imports")?;
+
+    let stdout = HookOutputPipe::new(invocation.max_output_bytes);
+    let stderr = HookOutputPipe::new(invocation.max_output_bytes);
+    let mut input = invocation.input;
+    input.push(b'\n');
+    let mut builder = WasiCtxBuilder::new();
+    builder
+        .stdin(MemoryInputPipe::new(input))
+        .stdout(stdout.clone())
+        .stderr(stderr.clone())
+        .args(
+            std::iter::once(invocation.module_key)
+                .chain(invocation.args.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .as_slice(),
+        )
+        .envs(granted_env(invocation.capabilities, |name| std::env::var(name).ok()).as_slice())
+        .allow_tcp(false)
+        .allow_udp(false)
+        .allow_ip_name_lookup(false);
+    if invocation.capabilities.workspace_read {
+        if let Some(workspace_root) = invocation.workspace_root {
+            builder
+                .preopened_dir(
+                    workspace_root,
+                    WASM_WORKSPACE_GUEST_PATH,
+                    DirPerms::READ,
+                    FilePerms::READ,
+                )
+                .context("preopen Plugin Hook workspace")?;
+        }
+    }
+    let memory_limit =
+        usize::try_from(invocation.capabilities.max_memory_bytes).unwrap_or(usize::MAX);
+    let mut store = Store::new(
+        &engine,
+        WasmHookState {
+            ctx: builder.build(),
+            table: ResourceTable::new(),
+            limits: StoreLimitsBuilder::new().memory_size(memory_limit).build(),
+        },
+    );
+    store.limiter(|state| &mut state.limits);
+    store.set_epoch_deadline(1);
+
+    let limit_ms = invocation
+        .timeout_ms
+        .min(invocation.capabilities.max_duration_ms);
+    let limit = Duration::from_millis(limit_ms);
+    // The epoch interrupts guest code that never yields; the outer timeout covers time spent
+    // waiting inside host calls. The ticker is an OS thread because a spinning guest holds the
+    // executor thread it runs on.
+    let (cancel_ticker, ticker_cancelled) = mpsc::channel::<()>();
+    let ticker = {
+        let engine = engine.clone();
+        std::thread::spawn(move || {
+            if let Err(RecvTimeoutError::Timeout) = ticker_cancelled.recv_timeout(limit) {
+                engine.increment_epoch();
+            }
+        })
+    };
+    let run = tokio::time::timeout(limit, async {
+        let command = Command::instantiate_async(&mut store, &component, &linker).await?;
+        command.wasi_cli_run().call_run(&mut store).await
+    })
+    .await;
+    drop(cancel_ticker);
+    let _ = ticker.join();
+
+    let timed_out_error = || format!("Plugin Hook timed out after {limit_ms} ms");
+    let (exit_code, timed_out, error) = match run {
+        Ok(Ok(Ok(()))) => (Some(0), false, None),
+        Ok(Ok(Err(()))) => (Some(1), false, None),
+        Ok(Err(error)) => {
+            if let Some(exit) = error.downcast_ref::<I32Exit>() {
+                (Some(exit.0), false, None)
+            } else if error.downcast_ref::<Trap>() == Some(&Trap::Interrupt) {
+                (None, true, Some(timed_out_error()))
+            } else {
+                (
+                    None,
+                    false,
+                    Some(sanitize_error(
+                        format!("Plugin Hook Wasm module failed: {error:#}").as_str(),
+                    )),
+                )
+            }
+        }
+        Err(_) => (None, true, Some(timed_out_error())),
+    };
+    drop(store);
+    Ok(HookCommandOutput {
+        exit_code,
+        timed_out,
+        stdout: stdout.finish(),
+        stderr: stderr.finish(),
+        error,
+    })
+}
+
+/// Values of the environment variables the module declares, taken from the Local Connector
+/// environment. Undeclared variables are never visible to the guest.
+fn granted_env(
+    capabilities: &PluginWasmCapabilities,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Vec<(String, String)> {
+    capabilities
+        .env
+        .iter()
+        .filter_map(|name| lookup(name).map(|value| (name.clone(), value)))
+        .collect()
+}
+
+/// Guest stdout or stderr: every byte is counted and hashed like a command Hook's pipes, but only
+/// the first `limit` bytes are kept.
+#[derive(Clone)]
+struct HookOutputPipe {
+    limit: usize,
+    state: Arc<Mutex<HookOutputState>>,
+}
+
+#[derive(Default)]
+struct HookOutputState {
+    retained: Vec<u8>,
+    total_bytes: usize,
+    hasher: Sha256,
+}
+
+impl HookOutputPipe {
+    fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            state: Arc::default(),
+        }
+    }
+
+    fn finish(&self) -> BoundedOutput {
+        let state = std::mem::take(&mut *self.state.lock().unwrap_or_else(|e| e.into_inner()));
+        BoundedOutput {
+            truncated: state.total_bytes > self.limit,
+            bytes: state.retained,
+            total_bytes: state.total_bytes,
+            sha256: hex::encode(state.hasher.finalize()),
+        }
+    }
+}
+
+impl StdoutStream for HookOutputPipe {
+    fn stream(&self) -> Box<dyn OutputStream> {
+        Box::new(self.clone())
+    }
+
+    fn isatty(&self) -> bool {
+        false
+    }
+}
+
+#[wasmtime_wasi::async_trait]
+impl OutputStream for HookOutputPipe {
+    fn write(&mut self, bytes: Bytes) -> StreamResult<()> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_| StreamError::Trap(anyhow!("Plugin Hook output pipe is poisoned")))?;
+        state.total_bytes = state.total_bytes.saturating_add(bytes.len());
+        state.hasher.update(bytes.as_ref());
+        let keep = bytes
+            .len()
+            .min(self.limit.saturating_sub(state.retained.len()));
+        state.retained.extend_from_slice(&bytes[..keep]);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> StreamResult<()> {
+        Ok(())
+    }
+
+    fn check_write(&mut self) -> StreamResult<usize> {
+        Ok(64 * 1024)
+    }
+}
+
+#[wasmtime_wasi::async_trait]
+impl Pollable for HookOutputPipe {
+    async fn ready(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_declared_environment_variables_reach_the_guest() {
+        let capabilities = PluginWasmCapabilities {
+            env: vec!["HOOK_MODE".to_string(), "HOOK_UNSET".to_string()],
+            ..PluginWasmCapabilities::default()
+        };
+        let env = granted_env(&capabilities, |name| match name {
+            "HOOK_MODE" => Some("strict".to_string()),
+            "HOME" => Some("/home/user".to_string()),
+            _ => None,
+        });
+        assert_eq!(env, vec![("HOOK_MODE".to_string(), "strict".to_string())]);
+    }
+
+    #[test]
+    fn output_pipe_hashes_everything_but_keeps_only_the_limit() {
+        let pipe = HookOutputPipe::new(4);
+        let mut stream = pipe.stream();
+        stream.write(Bytes::from_static(b"abc")).expect("write");
+        stream.write(Bytes::from_static(b"defg")).expect("write");
+        let output = pipe.finish();
+        assert_eq!(output.bytes, b"abcd");
+        assert_eq!(output.total_bytes, 7);
+        assert!(output.truncated);
+        assert_eq!(output.sha256, hex::encode(Sha256::digest(b"abcdefg")));
+    }
+}
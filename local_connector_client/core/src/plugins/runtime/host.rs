@@ -13,10 +13,11 @@ use base64::Engine;
 use chatos_plugin_management_sdk::{
     PluginArtifactCreateRequest, PluginArtifactListRequest, PluginArtifactReadMode,
     PluginArtifactReadRequest, PluginArtifactUiAccess, PluginArtifactUpdateRequest,
-    PluginComponentKind, PluginHookEvent, PluginHookEventContext, PluginHookOutcome,
-    PluginUiAssetReadResponse, PluginUiSnapshot, PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_CREATE,
-    PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_DOWNLOAD, PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_LIST,
-    PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_READ, PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_UPDATE,
+    PluginComponentKind, PluginHookDecision, PluginHookEvent, PluginHookEventContext,
+    PluginHookOutcome, PluginUiAssetReadResponse, PluginUiSnapshot,
+    PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_CREATE, PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_DOWNLOAD,
+    PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_LIST, PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_READ,
+    PLUGIN_UI_BRIDGE_CAPABILITY_ARTIFACT_UPDATE,
 };
 use chatos_sandbox_contract::{GrantedPermissionProfile, PermissionGrantScope};
 use chrono::Utc;
@@ -27,7 +28,7 @@ use tokio::sync::{Mutex as AsyncMutex, RwLock};
 use uuid::Uuid;
 
 use super::artifact_store::{PluginArtifactProducer, PluginArtifactStore, PluginUiArtifactGrant};
-use super::hook_loader::PluginHookWorkspaceWriteDecision;
+use super::hook_loader::{PluginHookDispatchResult, PluginHookWorkspaceWriteDecision};
 use super::mcp_runtime::{PluginMcpAdapter, PluginMcpInvocationCancelOutcome, PreparedPluginMcp};
 use super::protocol::*;
 use super::telemetry::{
@@ -520,6 +521,22 @@ impl PluginRuntimeHost {
         }
     }
 
+    /// Registered root of the session workspace for Wasm Hooks whose module grants
+    /// `workspaceRead`; absent unless `workspace.read` was part of the prepared snapshot.
+    async fn hook_workspace_read_root(
+        &self,
+        session: &PreparedPluginSession,
+    ) -> Result<Option<PathBuf>, (u16, String)> {
+        if !session.permission_snapshot.contains("workspace.read") {
+            return Ok(None);
+        }
+        let state = self.state_snapshot().await?;
+        state
+            .workspace_by_id(session.workspace_id.as_str())
+            .map(|workspace| approved_workspace_root(workspace.absolute_root.as_path()))
+            .transpose()
+    }
+
     async fn approve_hook_workspace_writes(
         &self,
         request: &RelayRequest,
@@ -668,6 +685,108 @@ impl PluginRuntimeHost {
         Ok(decisions)
     }
 
+    /// Turns an `ask` decision into `allow` or `deny` by asking the local user, so callers only
+    /// ever see a settled decision.
+    async fn resolve_hook_ask(
+        &self,
+        request: &RelayRequest,
+        adapter_session_id: &str,
+        session: &PreparedPluginSession,
+        hook_set: &PluginHookSetSnapshot,
+        context: &PluginHookEventContext,
+        result: &mut PluginHookDispatchResult,
+    ) -> Result<(), (u16, String)> {
+        let Some(decision) = result
+            .decision
+            .as_mut()
+            .filter(|decision| decision.decision == PluginHookDecision::Ask)
+        else {
+            return Ok(());
+        };
+        let state = self.state_snapshot().await?;
+        let project_key = approval_project_key_for_relay_scope(&state, request);
+        let reason = decision.reason.clone().unwrap_or_default();
+        let tool_name = context.tool_name.clone().unwrap_or_default();
+        let approval = self
+            .approval_service()?
+            .approve_interactive(CommandApprovalRequest {
+                request_id: format!(
+                    "{}:plugin-hook-ask:{}",
+                    request.request_id, decision.hook_id
+                ),
+                project_key,
+                command: "plugin-hook-ask".to_string(),
+                args: vec![
+                    session.plugin_id.clone(),
+                    decision.hook_id.clone(),
+                    result.event.as_str().to_string(),
+                    tool_name.clone(),
+                ],
+                redact_arguments_in_history: false,
+                cwd: ".".to_string(),
+                source: "plugin_hook_ask".to_string(),
+                requested_permissions: None,
+                session_id: Some(adapter_session_id.to_string()),
+                action_audit: Some(ApprovalActionAudit {
+                    kind: "plugin_hook_ask".to_string(),
+                    operation: result.event.as_str().to_string(),
+                    details: vec![
+                        ApprovalActionAuditDetail {
+                            key: "plugin_id".to_string(),
+                            value: session.plugin_id.clone(),
+                        },
+                        ApprovalActionAuditDetail {
+                            key: "hook_id".to_string(),
+                            value: decision.hook_id.clone(),
+                        },
+                        ApprovalActionAuditDetail {
+                            key: "tool_name".to_string(),
+                            value: tool_name,
+                        },
+                        ApprovalActionAuditDetail {
+                            key: "reason".to_string(),
+                            value: reason,
+                        },
+                        ApprovalActionAuditDetail {
+                            key: "hook_snapshot_sha256".to_string(),
+                            value: hook_set.snapshot_sha256.clone(),
+                        },
+                    ],
+                    privacy: Some(
+                        "The approval and audit omit tool arguments and Hook output other than the Hook's reason."
+                            .to_string(),
+                    ),
+                    safety: Some(
+                        "The Plugin Hook asked for confirmation before this tool call runs; approving lets this one call continue."
+                            .to_string(),
+                    ),
+                    recovery: Some(
+                        "Deny to return the Hook's reason to the model instead of running the tool."
+                            .to_string(),
+                    ),
+                }),
+            })
+            .await
+            .map_err(internal_error)?;
+        self.load_exact_session(request, adapter_session_id)?;
+        match approval {
+            ApprovalDecision::Approved { .. } => {
+                decision.decision = PluginHookDecision::Allow;
+                decision.user_approved = Some(true);
+            }
+            ApprovalDecision::Denied { reason, .. } => {
+                decision.decision = PluginHookDecision::Deny;
+                decision.user_approved = Some(false);
+                decision.reason = Some(match decision.reason.take() {
+                    Some(hook_reason) => format!("{hook_reason} (user denied: {reason})"),
+                    None => format!("user denied: {reason}"),
+                });
+                result.updated_input = None;
+            }
+        }
+        Ok(())
+    }
+
     async fn state_snapshot(&self) -> Result<LocalState, (u16, String)> {
         let state = self.local_state.as_ref().ok_or_else(|| {
             (
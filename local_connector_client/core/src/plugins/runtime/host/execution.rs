@@ -229,7 +229,8 @@ impl PluginRuntimeHost {
                         &context,
                     )
                     .await?;
-                let result = self
+                let workspace_read_root = self.hook_workspace_read_root(session).await?;
+                let mut result = self
                     .hook_loader
                     .dispatch(
                         hook_set,
@@ -238,9 +239,19 @@ impl PluginRuntimeHost {
                         event,
                         &context,
                         &workspace_write_decisions,
+                        workspace_read_root.as_deref(),
                     )
                     .await
                     .map_err(|error| (409, error.to_string()))?;
+                self.resolve_hook_ask(
+                    request,
+                    adapter_session_id,
+                    session,
+                    hook_set,
+                    &context,
+                    &mut result,
+                )
+                .await?;
                 Ok(json!({
                     "plugin_id": session.plugin_id,
                     "release_id": session.release_id,
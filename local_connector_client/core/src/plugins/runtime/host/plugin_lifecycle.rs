@@ -83,6 +83,7 @@ impl PluginRuntimeHost {
                             ..PluginHookEventContext::default()
                         },
                         &BTreeMap::new(),
+                        None,
                     )
                     .await
             }
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
 use std::panic::{resume_unwind, AssertUnwindSafe};
 use std::path::Path;
@@ -53,7 +53,10 @@ use super::mcp_runtime::{
 };
 use super::*;
 use crate::approval::{approve_pending_approval, list_pending_approvals};
-use crate::plugins::tests::fixtures::{ArchiveMutation, TestSigner, PLUGIN_ID};
+use crate::plugins::tests::fixtures::{
+    spinning_wasm_hook_component, wasm_hook_component_writing, ArchiveMutation, TestSigner,
+    PLUGIN_ID,
+};
 use crate::plugins::PluginInstaller;
 use crate::plugins::{PluginCredentialScope, PluginCredentialVault};
 use crate::secure_storage::SecureStorage;
@@ -2300,6 +2303,291 @@ async fn workspace_write_hook_requires_one_invocation_user_approval() {
     assert!(!workspace_root.join("hook-was-here").exists());
 }
 
+#[tokio::test]
+async fn pre_tool_use_hooks_deny_rewrite_and_ask_the_local_user() {
+    let temp = TempDir::new().expect("temp directory");
+    let package = TestSigner::new().package_with_guardrail_hooks(temp.path(), "1.0.0");
+    let installer = PluginInstaller::new(temp.path().join("plugins"));
+    let installed = installer
+        .install_archive(package.install_request())
+        .expect("install guardrail Hook Plugin");
+    let release_id = installed.installed_version.release_id.clone();
+    let artifact_sha256 = installed.installed_version.artifact_sha256.clone();
+    let hook_sha256 = installed
+        .installed_version
+        .package_file_sha256
+        .get("hooks.json")
+        .expect("Hook source hash")
+        .clone();
+    let host = PluginRuntimeHost::new(
+        PluginSkillLoader::new(installer.clone()),
+        PluginMcpAdapter::new(installer),
+    )
+    .with_local_state(Arc::new(RwLock::new(LocalState::default())))
+    .with_approval_state_path(temp.path().join("approval-state.json"));
+    let prepare = host
+        .handle_prepare(plugin_request(
+            "plugin_prepare_request",
+            json!({
+                "plugin_id": PLUGIN_ID,
+                "release_id": release_id,
+                "artifact_sha256": artifact_sha256,
+                "component_key": "guardrail-hooks",
+                "content_sha256": hook_sha256,
+                "permission_snapshot": ["process.spawn"],
+            }),
+        ))
+        .await;
+    assert_eq!(prepare.get("status").and_then(Value::as_u64), Some(200));
+    let adapter_session_id = prepare
+        .pointer("/body/adapter_session_id")
+        .and_then(Value::as_str)
+        .expect("adapter session")
+        .to_string();
+    let dispatch_request = |path: &str| {
+        plugin_request(
+            "plugin_execute_request",
+            json!({
+                "plugin_id": PLUGIN_ID,
+                "release_id": release_id,
+                "artifact_sha256": artifact_sha256,
+                "component_key": "guardrail-hooks",
+                "adapter_session_id": adapter_session_id,
+                "operation": "dispatch_hook_event",
+                "event": "PreToolUse",
+                "context": {"toolName": "write_file", "toolInput": {"path": path}},
+            }),
+        )
+    };
+
+    let denied = host
+        .handle_execute(dispatch_request("migrations/001_init.sql"))
+        .await;
+    assert_eq!(denied.get("status").and_then(Value::as_u64), Some(200));
+    let result = denied.pointer("/body/result").expect("dispatch result");
+    assert_eq!(result.pointer("/decision/decision"), Some(&json!("deny")));
+    assert_eq!(result.pointer("/decision/hook_id"), Some(&json!("b-guard")));
+    assert_eq!(
+        result.pointer("/decision/reason"),
+        Some(&json!("never edit files under migrations/"))
+    );
+    assert_eq!(result.get("blocking_failure"), Some(&json!(false)));
+    assert!(result.get("updated_input").is_none());
+
+    let rewritten = host.handle_execute(dispatch_request("docs/notes.md")).await;
+    let result = rewritten.pointer("/body/result").expect("dispatch result");
+    assert_eq!(result.pointer("/decision/decision"), Some(&json!("allow")));
+    assert_eq!(
+        result.get("updated_input"),
+        Some(&json!({"path": "docs/guide.md", "dryRun": true}))
+    );
+    assert_eq!(
+        result.get("additional_context"),
+        Some(&json!(["docs writes run as dry runs"]))
+    );
+    assert!(result
+        .pointer("/executions/0/response/updatedInput")
+        .is_some());
+
+    let ask_request = dispatch_request("config/.env");
+    let approval_request_id = format!(
+        "{}:plugin-hook-ask:b-guard",
+        ask_request
+            .get("request_id")
+            .and_then(Value::as_str)
+            .expect("ask request id")
+    );
+    let ask_task = tokio::spawn({
+        let host = host.clone();
+        async move { host.handle_execute(ask_request).await }
+    });
+    let pending = tokio::time::timeout(std::time::Duration::from_secs(2), async {
+        loop {
+            if let Some(item) = list_pending_approvals()
+                .await
+                .into_iter()
+                .find(|item| item.request_id == approval_request_id)
+            {
+                break item;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Hook ask approval request");
+    assert_eq!(pending.source, "plugin_hook_ask");
+    assert!(approve_pending_approval(
+        pending.id.as_str(),
+        CommandExecutionApprovalDecision::Simple(SimpleCommandExecutionApprovalDecision::Decline),
+        None,
+        None,
+    )
+    .await
+    .expect("deny Hook ask"));
+    let asked = ask_task.await.expect("ask task");
+    assert_eq!(asked.get("status").and_then(Value::as_u64), Some(200));
+    let decision = asked
+        .pointer("/body/result/decision")
+        .expect("settled decision");
+    assert_eq!(decision.get("decision"), Some(&json!("deny")));
+    assert_eq!(decision.get("user_approved"), Some(&json!(false)));
+    assert!(decision
+        .get("reason")
+        .and_then(Value::as_str)
+        .is_some_and(|reason| reason.starts_with("writes a secrets file")));
+}
+
+/// Installs the signed Wasm Hook fixture with `module`, prepares it with `workspace.read` and
+/// dispatches one `PreToolUse` event, returning the dispatch result.
+async fn dispatch_signed_wasm_hook(temp: &TempDir, module: Vec<u8>) -> Value {
+    let package = TestSigner::new().package_with_wasm_hook(temp.path(), "1.0.0", module);
+    let installer = PluginInstaller::new(temp.path().join("plugins"));
+    let installed = installer
+        .install_archive(package.install_request())
+        .expect("install Wasm Hook Plugin");
+    let release_id = installed.installed_version.release_id.clone();
+    let artifact_sha256 = installed.installed_version.artifact_sha256.clone();
+    let hook_sha256 = installed.installed_version.package_file_sha256["hooks.json"].clone();
+    let host = PluginRuntimeHost::new(
+        PluginSkillLoader::new(installer.clone()),
+        PluginMcpAdapter::new(installer),
+    )
+    .with_local_state(Arc::new(RwLock::new(LocalState::default())))
+    .with_approval_state_path(temp.path().join("approval-state.json"));
+    let prepare = host
+        .handle_prepare(plugin_request(
+            "plugin_prepare_request",
+            json!({
+                "plugin_id": PLUGIN_ID,
+                "release_id": release_id,
+                "artifact_sha256": artifact_sha256,
+                "component_key": "wasm-hooks",
+                "content_sha256": hook_sha256,
+                "permission_snapshot": ["workspace.read"],
+            }),
+        ))
+        .await;
+    assert_eq!(prepare.get("status").and_then(Value::as_u64), Some(200));
+    let adapter_session_id = prepare
+        .pointer("/body/adapter_session_id")
+        .and_then(Value::as_str)
+        .expect("adapter session");
+    let dispatched = host
+        .handle_execute(plugin_request(
+            "plugin_execute_request",
+            json!({
+                "plugin_id": PLUGIN_ID,
+                "release_id": release_id,
+                "artifact_sha256": artifact_sha256,
+                "component_key": "wasm-hooks",
+                "adapter_session_id": adapter_session_id,
+                "operation": "dispatch_hook_event",
+                "event": "PreToolUse",
+                "context": {"toolName": "write_file", "toolInput": {"path": "src/lib.rs"}},
+            }),
+        ))
+        .await;
+    assert_eq!(dispatched.get("status").and_then(Value::as_u64), Some(200));
+    dispatched
+        .pointer("/body/result")
+        .cloned()
+        .expect("dispatch result")
+}
+
+#[tokio::test]
+async fn wasm_hooks_pin_their_signed_module_and_fail_closed_on_an_invalid_component() {
+    let temp = TempDir::new().expect("temp directory");
+    let module = b"\0asm\x0d\x00\x01\x00guard-component".to_vec();
+    let package = TestSigner::new().package_with_wasm_hook(temp.path(), "1.0.0", module.clone());
+    let installer = PluginInstaller::new(temp.path().join("pinned"));
+    let installed = installer
+        .install_archive(package.install_request())
+        .expect("install Wasm Hook Plugin");
+    let package_file_sha256 = &installed.installed_version.package_file_sha256;
+    let hook_sha256 = package_file_sha256["hooks.json"].clone();
+    let module_sha256 = package_file_sha256["wasm/guard.wasm"].clone();
+
+    let loader = PluginHookLoader::new(installer);
+    let unpermitted = loader
+        .load(
+            PLUGIN_ID,
+            "wasm-hooks",
+            hook_sha256.as_str(),
+            &BTreeSet::new(),
+        )
+        .expect_err("workspace.read grant needs the prepared permission");
+    assert!(format!("{unpermitted:#}").contains("workspace.read"));
+    let snapshot = loader
+        .load(
+            PLUGIN_ID,
+            "wasm-hooks",
+            hook_sha256.as_str(),
+            &BTreeSet::from(["workspace.read".to_string()]),
+        )
+        .expect("load Wasm Hook set");
+    assert_eq!(
+        snapshot.command_sha256_by_hook.get("wasm-guard"),
+        Some(&module_sha256)
+    );
+
+    let result = dispatch_signed_wasm_hook(&temp, module).await;
+    assert_eq!(result.get("blocking_failure"), Some(&json!(true)));
+    assert_eq!(
+        result.pointer("/executions/0/succeeded"),
+        Some(&json!(false))
+    );
+    assert!(result
+        .pointer("/executions/0/error")
+        .and_then(Value::as_str)
+        .is_some_and(|error| error.contains("compile Plugin Hook Wasm module")));
+}
+
+#[tokio::test]
+async fn signed_wasm_hook_runs_in_the_wasi_runtime_and_returns_its_decision() {
+    let temp = TempDir::new().expect("temp directory");
+    let stdout = br#"{"decision":"deny","reason":"blocked by the Wasm guard"}"#;
+    let result =
+        dispatch_signed_wasm_hook(&temp, wasm_hook_component_writing(stdout.as_slice())).await;
+    let execution = result
+        .pointer("/executions/0")
+        .expect("Wasm Hook execution");
+    assert_eq!(
+        execution.get("succeeded"),
+        Some(&json!(true)),
+        "{execution:#}"
+    );
+    assert_eq!(execution.get("exit_code"), Some(&json!(0)));
+    assert_eq!(execution.get("timed_out"), Some(&json!(false)));
+    assert_eq!(
+        execution.get("stdout_sha256").and_then(Value::as_str),
+        Some(hex::encode(Sha256::digest(stdout.as_slice())).as_str())
+    );
+    assert_eq!(result.get("blocking_failure"), Some(&json!(false)));
+    assert_eq!(result.pointer("/decision/decision"), Some(&json!("deny")));
+    assert_eq!(
+        result.pointer("/decision/reason"),
+        Some(&json!("blocked by the Wasm guard"))
+    );
+}
+
+#[tokio::test]
+async fn wasm_hook_that_never_returns_is_interrupted_at_its_duration_limit() {
+    let temp = TempDir::new().expect("temp directory");
+    let started = std::time::Instant::now();
+    let result = dispatch_signed_wasm_hook(&temp, spinning_wasm_hook_component()).await;
+    assert!(started.elapsed() < std::time::Duration::from_secs(30));
+    let execution = result
+        .pointer("/executions/0")
+        .expect("Wasm Hook execution");
+    assert_eq!(execution.get("succeeded"), Some(&json!(false)));
+    assert_eq!(execution.get("timed_out"), Some(&json!(true)));
+    assert!(execution
+        .get("error")
+        .and_then(Value::as_str)
+        .is_some_and(|error| error.contains("timed out after 500 ms")));
+    assert_eq!(result.get("blocking_failure"), Some(&json!(true)));
+}
+
 #[cfg(target_os = "macos")]
 #[tokio::test]
 #[ignore = "packaged hook end-to-end fixture"]
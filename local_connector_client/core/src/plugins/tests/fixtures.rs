@@ -280,6 +280,142 @@ impl TestSigner {
         )
     }
 
+    pub(in crate::plugins) fn package_with_guardrail_hooks(
+        &self,
+        root: &Path,
+        version: &str,
+    ) -> TestPackage {
+        let hook = |id: &str, command: &str| {
+            json!({
+                "id": id,
+                "events": ["PreToolUse"],
+                "matcher": {"toolNames": ["write_file"]},
+                "entrypoint": {"type": "command", "command": command},
+                "timeoutMs": 2500,
+                "maxOutputBytes": 4096,
+                "failurePolicy": "continue"
+            })
+        };
+        let hook_set = json!({
+            "schemaVersion": 1,
+            "hooks": [
+                hook("a-rewrite", "./scripts/rewrite-hook.sh"),
+                hook("b-guard", "./scripts/guard-hook.sh"),
+            ]
+        })
+        .to_string();
+        self.package_from_manifest(
+            root,
+            version,
+            ArchiveMutation::None,
+            json!({
+                "name": "demo-plugin",
+                "version": version,
+                "description": "A signed guardrail Hook fixture",
+                "author": {"name": "Demo Publisher"},
+                "hooks": [{
+                    "componentKey": "guardrail-hooks",
+                    "source": "./hooks.json"
+                }],
+                "interface": {
+                    "displayName": "Demo Plugin",
+                    "shortDescription": "Signed test Plugin",
+                    "longDescription": "A signed guardrail Hook fixture",
+                    "developerName": "Demo Publisher",
+                    "category": "Developer Tools"
+                },
+                "permissions": [{
+                    "permission": "process.spawn",
+                    "required": true,
+                    "components": ["guardrail-hooks"]
+                }]
+            })
+            .to_string(),
+            BTreeMap::from([
+                ("hooks.json".to_string(), hook_set.into_bytes()),
+                (
+                    "scripts/rewrite-hook.sh".to_string(),
+                    br#"#!/bin/sh
+input=$(cat)
+case "$input" in
+  *'"path":"docs/'*) printf '{"updatedInput":{"path":"docs/guide.md","dryRun":true},"additionalContext":"docs writes run as dry runs"}
+' ;;
+esac
+"#
+                    .to_vec(),
+                ),
+                (
+                    "scripts/guard-hook.sh".to_string(),
+                    br#"#!/bin/sh
+input=$(cat)
+case "$input" in
+  *migrations/*) printf '{"decision":"deny","reason":"never edit files under migrations/"}
+' ;;
+  *.env*) printf '{"decision":"ask","reason":"writes a secrets file"}
+' ;;
+  *dryRun*) printf '{"decision":"allow"}
+' ;;
+esac
+"#
+                    .to_vec(),
+                ),
+            ]),
+        )
+    }
+
+    pub(in crate::plugins) fn package_with_wasm_hook(
+        &self,
+        root: &Path,
+        version: &str,
+        module: Vec<u8>,
+    ) -> TestPackage {
+        let hook_set = json!({
+            "schemaVersion": 1,
+            "hooks": [{
+                "id": "wasm-guard",
+                "events": ["PreToolUse"],
+                "entrypoint": {"type": "wasm", "module": "guard"},
+                "failurePolicy": "fail_run"
+            }]
+        })
+        .to_string();
+        self.package_from_manifest(
+            root,
+            version,
+            ArchiveMutation::None,
+            json!({
+                "name": "demo-plugin",
+                "version": version,
+                "description": "A signed Wasm Hook fixture",
+                "author": {"name": "Demo Publisher"},
+                "hooks": [{"componentKey": "wasm-hooks", "source": "./hooks.json"}],
+                "wasmModules": [{
+                    "moduleKey": "guard",
+                    "source": "./wasm/guard.wasm",
+                    "sha256": hex::encode(Sha256::digest(module.as_slice())),
+                    "capabilities": {"workspaceRead": true, "env": ["RUST_LOG"], "maxDurationMs": 500}
+                }],
+                "interface": {
+                    "displayName": "Demo Plugin",
+                    "shortDescription": "Signed test Plugin",
+                    "longDescription": "A signed Wasm Hook fixture",
+                    "developerName": "Demo Publisher",
+                    "category": "Developer Tools"
+                },
+                "permissions": [{
+                    "permission": "workspace.read",
+                    "required": true,
+                    "components": ["wasm-hooks"]
+                }]
+            })
+            .to_string(),
+            BTreeMap::from([
+                ("hooks.json".to_string(), hook_set.into_bytes()),
+                ("wasm/guard.wasm".to_string(), module),
+            ]),
+        )
+    }
+
     pub(in crate::plugins) fn package_with_packaged_hook_suite(
         &self,
         root: &Path,
@@ -1315,3 +1451,90 @@ fn write_test_archive(
 fn sha256_bytes(value: &[u8]) -> String {
     hex::encode(Sha256::digest(value))
 }
+
+/// WASI preview 2 command component that writes `stdout` to its stdout and exits successfully.
+pub(in crate::plugins) fn wasm_hook_component_writing(stdout: &[u8]) -> Vec<u8> {
+    let data = stdout
+        .iter()
+        .map(|byte| format!("\\{byte:02x}"))
+        .collect::<String>();
+    let len = stdout.len();
+    wat::parse_str(format!(
+        r#"(component $hook
+  (import "wasi:io/error@0.2.0" (instance $error
+    (export "error" (type (sub resource)))
+  ))
+  (alias export $error "error" (type $error-type))
+  (import "wasi:io/streams@0.2.0" (instance $streams
+    (alias outer $hook $error-type (type $error))
+    (export "error" (type $error-export (eq $error)))
+    (export "output-stream" (type $output-stream (sub resource)))
+    (type $stream-error (variant
+      (case "last-operation-failed" (own $error-export))
+      (case "closed")
+    ))
+    (export "stream-error" (type $stream-error-export (eq $stream-error)))
+    (export "[method]output-stream.blocking-write-and-flush" (func
+      (param "self" (borrow $output-stream))
+      (param "contents" (list u8))
+      (result (result (error $stream-error-export)))
+    ))
+  ))
+  (alias export $streams "output-stream" (type $output-stream-type))
+  (import "wasi:cli/stdout@0.2.0" (instance $stdout
+    (alias outer $hook $output-stream-type (type $output-stream))
+    (export "output-stream" (type $output-stream-export (eq $output-stream)))
+    (export "get-stdout" (func (result (own $output-stream-export))))
+  ))
+  (core module $memory (memory (export "memory") 1))
+  (core instance $memory (instantiate $memory))
+  (alias core export $memory "memory" (core memory $mem))
+  (core func $get-stdout (canon lower (func $stdout "get-stdout")))
+  (core func $write (canon lower
+    (func $streams "[method]output-stream.blocking-write-and-flush")
+    (memory $mem)
+  ))
+  (core module $main
+    (import "env" "memory" (memory 1))
+    (import "host" "get-stdout" (func $get-stdout (result i32)))
+    (import "host" "write" (func $write (param i32 i32 i32 i32)))
+    (data (i32.const 64) "{data}")
+    (func (export "run") (result i32)
+      (call $write (call $get-stdout) (i32.const 64) (i32.const {len}) (i32.const 0))
+      (i32.const 0)
+    )
+  )
+  (core instance $host
+    (export "get-stdout" (func $get-stdout))
+    (export "write" (func $write))
+  )
+  (core instance $main (instantiate $main
+    (with "env" (instance $memory))
+    (with "host" (instance $host))
+  ))
+  (func $run (result (result)) (canon lift (core func $main "run")))
+  (instance $run-instance (export "run" (func $run)))
+  (export "wasi:cli/run@0.2.0" (instance $run-instance))
+)"#
+    ))
+    .expect("assemble Wasm Hook component")
+}
+
+/// WASI preview 2 command component whose `run` never returns.
+pub(in crate::plugins) fn spinning_wasm_hook_component() -> Vec<u8> {
+    wat::parse_str(
+        r#"(component
+  (core module $main
+    (func (export "run") (result i32)
+      (loop $spin (br $spin))
+      (unreachable)
+    )
+  )
+  (core instance $main (instantiate $main))
+  (func $run (result (result)) (canon lift (core func $main "run")))
+  (instance $run-instance (export "run" (func $run)))
+  (export "wasi:cli/run@0.2.0" (instance $run-instance))
+)"#,
+    )
+    .expect("assemble spinning Wasm Hook component")
+}
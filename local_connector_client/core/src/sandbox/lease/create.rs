@@ -13,6 +13,7 @@ use serde_json::{json, Value};
 use uuid::Uuid;
 
 use crate::relay::RelayRequest;
+use crate::sandbox::linux_native::register_linux_native_lease;
 use crate::sandbox::project_permissions::load_trusted_project_permission_document;
 use crate::sandbox::types::{
     CreateLocalSandboxLeaseRequest, LocalSandboxLease, LocalSandboxNetworkPolicy,
@@ -95,6 +96,9 @@ pub(crate) async fn create_local_sandbox_lease(
         effective_policy,
         effective_permissions,
     };
+    if lease.effective_policy.sandbox_mode == SandboxBackendKind::LinuxNative {
+        register_linux_native_lease(&lease).map_err(anyhow::Error::msg)?;
+    }
     let response = local_sandbox_lease_response(&lease);
     sandbox_runtime
         .leases
@@ -132,10 +136,10 @@ fn local_effective_policy(
         .effective_permission_profile_configuration_with_project(project)
         .map_err(anyhow::Error::msg)?;
     let mut maximum = sandbox.effective_policy_defaults_with_project(project);
-    maximum.sandbox_mode = SandboxBackendKind::LocalProcess;
+    maximum.sandbox_mode = crate::sandbox::local_execution_backend(maximum.sandbox_mode);
     let maximum_profile_name = effective_configuration.default_profile_name;
     let mut constrained_request = request.clone();
-    constrained_request.sandbox_mode = Some(SandboxBackendKind::LocalProcess);
+    constrained_request.sandbox_mode = Some(maximum.sandbox_mode);
     if let Some(requested_profile) = request.permission_profile_id {
         if !effective_configuration
             .configuration
@@ -8,6 +8,7 @@ use tokio::task::JoinHandle;
 use tokio::time::MissedTickBehavior;
 
 use crate::approval::clear_session_approvals;
+use crate::sandbox::linux_native::release_linux_native_lease;
 use crate::sandbox::types::{LocalSandboxLease, LocalSandboxRuntime};
 use crate::{local_now_rfc3339, LOCAL_SANDBOX_STATUS_DESTROYED};
 
@@ -47,6 +48,7 @@ async fn reap_expired_local_sandboxes(sandbox_runtime: &LocalSandboxRuntime) {
     for lease in expired {
         let runtime_id = lease.runtime_id.clone();
         clear_session_approvals(runtime_id.as_str()).await;
+        release_linux_native_lease(&lease);
 
         let mut leases = sandbox_runtime.leases.write().await;
         let Some(stored) = leases.get_mut(runtime_id.as_str()) else {
@@ -8,6 +8,7 @@ use serde_json::{json, Value};
 
 use crate::approval::clear_session_approvals;
 use crate::relay::RelayRequest;
+use crate::sandbox::linux_native::{release_all_linux_native_leases, release_linux_native_lease};
 use crate::sandbox::types::{LocalSandboxRuntime, ReleaseLocalSandboxRequest};
 use crate::{local_now_rfc3339, LOCAL_SANDBOX_STATUS_DESTROYED};
 
@@ -56,6 +57,7 @@ pub(crate) async fn release_local_sandbox(
         lease.status = LOCAL_SANDBOX_STATUS_DESTROYED.to_string();
         lease.destroyed_at = Some(local_now_rfc3339());
         clear_session_approvals(runtime_id).await;
+        release_linux_native_lease(&lease);
     }
     lease.updated_at = local_now_rfc3339();
     sandbox_runtime
@@ -89,6 +91,7 @@ pub(crate) async fn shutdown_local_sandboxes(sandbox_runtime: &LocalSandboxRunti
         clear_session_approvals(runtime_id).await;
     }
     sandbox_runtime.leases.write().await.clear();
+    release_all_linux_native_leases();
     json!({
         "ok": true,
         "released_leases": lease_ids.len(),
@@ -0,0 +1,196 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use chatos_mcp::TerminalControllerContext;
+use chatos_sandbox_contract::{
+    SandboxBackendCapability, SandboxBackendKind, SandboxBackendReadinessStatus,
+};
+
+use crate::sandbox::types::LocalSandboxLease;
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod landlock;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod namespaces;
+mod plan;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod process;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod proxy_bridge;
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+mod seccomp;
+
+pub(crate) use plan::LinuxNativeSandboxPlan;
+
+/// Header the lease proxy pins to the lease's run so terminal contexts resolve to its plan.
+pub(crate) const LINUX_NATIVE_RUN_HEADER: &str = "x-task-runner-run-id";
+
+/// Plans for active `linux_native` leases keyed by owner and run, which is how terminal contexts
+/// created for a lease are scoped.
+type LinuxNativeLeasePlans = HashMap<(String, String), Arc<LinuxNativeSandboxPlan>>;
+
+fn linux_native_leases() -> &'static RwLock<LinuxNativeLeasePlans> {
+    static LEASES: OnceLock<RwLock<LinuxNativeLeasePlans>> = OnceLock::new();
+    LEASES.get_or_init(RwLock::default)
+}
+
+fn lease_scope(lease: &LocalSandboxLease) -> (String, String) {
+    (lease.tenant_id.clone(), lease.run_id.clone())
+}
+
+pub(crate) fn linux_native_execution_capability() -> SandboxBackendCapability {
+    static CAPABILITY: OnceLock<SandboxBackendCapability> = OnceLock::new();
+    CAPABILITY
+        .get_or_init(probe_linux_native_capability)
+        .clone()
+}
+
+fn probe_linux_native_capability() -> SandboxBackendCapability {
+    let (status, message) = match linux_native_readiness() {
+        Ok(message) => (SandboxBackendReadinessStatus::Ready, message),
+        Err((status, message)) => (status, message),
+    };
+    let ready = status == SandboxBackendReadinessStatus::Ready;
+    SandboxBackendCapability {
+        backend: SandboxBackendKind::LinuxNative,
+        status,
+        selectable: ready,
+        filesystem_isolation: ready,
+        network_isolation: ready,
+        process_tree_control: ready,
+        message,
+    }
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+fn linux_native_readiness() -> Result<String, (SandboxBackendReadinessStatus, String)> {
+    let setup_required = |message: String| (SandboxBackendReadinessStatus::SetupRequired, message);
+    let abi = landlock::landlock_abi_version().ok_or_else(|| {
+        setup_required(
+            "Landlock is not enabled; use Linux 5.13+ with landlock in the lsm= boot parameter"
+                .to_string(),
+        )
+    })?;
+    if !seccomp::seccomp_filter_supported() {
+        return Err(setup_required(
+            "the kernel was built without seccomp filter support".to_string(),
+        ));
+    }
+    process::probe_user_namespaces().map_err(|err| {
+        setup_required(format!(
+            "{err}; allow unprivileged user namespaces (kernel.unprivileged_userns_clone or the AppArmor userns restriction)"
+        ))
+    })?;
+    Ok(format!(
+        "Local Connector confines tools with user namespaces, Landlock ABI {abi} and seccomp"
+    ))
+}
+
+#[cfg(not(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+fn linux_native_readiness() -> Result<String, (SandboxBackendReadinessStatus, String)> {
+    Err((
+        SandboxBackendReadinessStatus::Unsupported,
+        "the linux_native backend requires Linux on x86_64 or aarch64".to_string(),
+    ))
+}
+
+/// Builds and records the plan for a `linux_native` lease. Fails when the host is not ready or
+/// the lease's permissions cannot be enforced natively.
+pub(crate) fn register_linux_native_lease(lease: &LocalSandboxLease) -> Result<(), String> {
+    let capability = linux_native_execution_capability();
+    if capability.status != SandboxBackendReadinessStatus::Ready {
+        return Err(capability.message);
+    }
+    let tmpdir = std::env::var_os("TMPDIR").map(PathBuf::from);
+    let plan =
+        LinuxNativeSandboxPlan::from_permissions(&lease.effective_permissions, tmpdir.as_deref())?;
+    linux_native_leases()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(lease_scope(lease), Arc::new(plan));
+    Ok(())
+}
+
+pub(crate) fn release_linux_native_lease(lease: &LocalSandboxLease) {
+    linux_native_leases()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .remove(&lease_scope(lease));
+}
+
+pub(crate) fn release_all_linux_native_leases() {
+    linux_native_leases()
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clear();
+}
+
+fn linux_native_plan_for_context(
+    context: &TerminalControllerContext,
+) -> Option<Arc<LinuxNativeSandboxPlan>> {
+    let scope = (context.user_id.clone()?, context.project_id.clone()?);
+    linux_native_leases()
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .get(&scope)
+        .cloned()
+}
+
+/// Applies the lease's native sandbox to a terminal process before it is spawned. Commands
+/// outside a `linux_native` lease are left untouched.
+pub(crate) fn confine_terminal_command(
+    context: &TerminalControllerContext,
+    command: &mut tokio::process::Command,
+) -> Result<(), String> {
+    let Some(plan) = linux_native_plan_for_context(context) else {
+        return Ok(());
+    };
+    confine_command(command, plan.as_ref())
+}
+
+#[cfg(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+))]
+fn confine_command(
+    command: &mut tokio::process::Command,
+    plan: &LinuxNativeSandboxPlan,
+) -> Result<(), String> {
+    process::confine_command(command, plan)
+}
+
+#[cfg(not(all(
+    target_os = "linux",
+    any(target_arch = "x86_64", target_arch = "aarch64")
+)))]
+fn confine_command(
+    _command: &mut tokio::process::Command,
+    _plan: &LinuxNativeSandboxPlan,
+) -> Result<(), String> {
+    Err("the linux_native backend requires Linux on x86_64 or aarch64".to_string())
+}
@@ -0,0 +1,165 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::ffi::CString;
+use std::io;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+use super::plan::LinuxNativeFileSystem;
+
+const LANDLOCK_CREATE_RULESET_VERSION: libc::c_uint = 1 << 0;
+const LANDLOCK_RULE_PATH_BENEATH: libc::c_int = 1;
+
+const ACCESS_FS_EXECUTE: u64 = 1 << 0;
+const ACCESS_FS_WRITE_FILE: u64 = 1 << 1;
+const ACCESS_FS_READ_FILE: u64 = 1 << 2;
+const ACCESS_FS_READ_DIR: u64 = 1 << 3;
+const ACCESS_FS_MAKE_SYM: u64 = 1 << 12;
+const ACCESS_FS_REFER: u64 = 1 << 13;
+const ACCESS_FS_TRUNCATE: u64 = 1 << 14;
+const ACCESS_FS_READ: u64 = ACCESS_FS_EXECUTE | ACCESS_FS_READ_FILE | ACCESS_FS_READ_DIR;
+/// Rights Landlock accepts on a rule whose parent is a regular file or device.
+const ACCESS_FS_FILE: u64 =
+    ACCESS_FS_EXECUTE | ACCESS_FS_WRITE_FILE | ACCESS_FS_READ_FILE | ACCESS_FS_TRUNCATE;
+
+#[repr(C)]
+struct RulesetAttr {
+    handled_access_fs: u64,
+}
+
+#[repr(C, packed)]
+struct PathBeneathAttr {
+    allowed_access: u64,
+    parent_fd: libc::c_int,
+}
+
+pub(super) fn landlock_abi_version() -> Option<u32> {
+    // SAFETY: a null attribute with size 0 and the VERSION flag only queries the ABI.
+    let version = unsafe {
+        libc::syscall(
+            libc::SYS_landlock_create_ruleset,
+            std::ptr::null::<RulesetAttr>(),
+            0usize,
+            LANDLOCK_CREATE_RULESET_VERSION,
+        )
+    };
+    (version > 0).then_some(version as u32)
+}
+
+fn handled_fs_access(abi: u32) -> u64 {
+    let mut access = (ACCESS_FS_MAKE_SYM << 1) - 1;
+    if abi >= 2 {
+        access |= ACCESS_FS_REFER;
+    }
+    if abi >= 3 {
+        access |= ACCESS_FS_TRUNCATE;
+    }
+    access
+}
+
+/// Ruleset assembled in the parent so the forked child only has to call `restrict_self`.
+pub(super) struct LandlockRuleset {
+    fd: OwnedFd,
+}
+
+impl LandlockRuleset {
+    pub(super) fn build(
+        file_system: Option<&LinuxNativeFileSystem>,
+    ) -> Result<Option<Self>, String> {
+        let Some(file_system) = file_system else {
+            return Ok(None);
+        };
+        let abi = landlock_abi_version()
+            .ok_or_else(|| "Landlock is not available in this kernel".to_string())?;
+        let handled_fs = handled_fs_access(abi);
+        let attr = RulesetAttr {
+            handled_access_fs: handled_fs,
+        };
+        // SAFETY: `attr` outlives the call and the size passed is exactly its size.
+        let fd = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_create_ruleset,
+                &attr as *const RulesetAttr,
+                std::mem::size_of::<RulesetAttr>(),
+                0u32,
+            )
+        };
+        if fd < 0 {
+            return Err(format!(
+                "create Landlock ruleset failed: {}",
+                io::Error::last_os_error()
+            ));
+        }
+        // SAFETY: the syscall returned a fresh descriptor that nothing else owns.
+        let ruleset = Self {
+            fd: unsafe { OwnedFd::from_raw_fd(fd as libc::c_int) },
+        };
+        for root in &file_system.readable_roots {
+            ruleset.allow_path(root, ACCESS_FS_READ & handled_fs)?;
+        }
+        for root in &file_system.writable_roots {
+            ruleset.allow_path(root, handled_fs)?;
+        }
+        Ok(Some(ruleset))
+    }
+
+    fn allow_path(&self, path: &Path, access: u64) -> Result<(), String> {
+        let c_path = CString::new(path.as_os_str().as_bytes())
+            .map_err(|_| format!("sandbox path contains NUL: {}", path.display()))?;
+        // SAFETY: `c_path` is a valid NUL-terminated string.
+        let fd = unsafe { libc::open(c_path.as_ptr(), libc::O_PATH | libc::O_CLOEXEC) };
+        if fd < 0 {
+            let error = io::Error::last_os_error();
+            if error.kind() == io::ErrorKind::NotFound {
+                return Ok(());
+            }
+            return Err(format!("open {} failed: {error}", path.display()));
+        }
+        // SAFETY: `open` returned a fresh descriptor that nothing else owns.
+        let parent = unsafe { OwnedFd::from_raw_fd(fd) };
+        let allowed_access = if path.is_dir() {
+            access
+        } else {
+            access & ACCESS_FS_FILE
+        };
+        let attr = PathBeneathAttr {
+            allowed_access,
+            parent_fd: parent.as_raw_fd(),
+        };
+        self.add_rule(
+            LANDLOCK_RULE_PATH_BENEATH,
+            &attr as *const PathBeneathAttr as *const libc::c_void,
+        )
+        .map_err(|err| format!("allow {} failed: {err}", path.display()))
+    }
+
+    fn add_rule(&self, rule_type: libc::c_int, attr: *const libc::c_void) -> io::Result<()> {
+        // SAFETY: callers pass a pointer to the attribute struct matching `rule_type`.
+        let result = unsafe {
+            libc::syscall(
+                libc::SYS_landlock_add_rule,
+                self.fd.as_raw_fd(),
+                rule_type,
+                attr,
+                0u32,
+            )
+        };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+
+    /// Runs in the forked child after `no_new_privs` is set; only issues one syscall.
+    pub(super) fn restrict_self(&self) -> io::Result<()> {
+        // SAFETY: the ruleset descriptor stays open for the lifetime of `self`.
+        let result =
+            unsafe { libc::syscall(libc::SYS_landlock_restrict_self, self.fd.as_raw_fd(), 0u32) };
+        if result < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
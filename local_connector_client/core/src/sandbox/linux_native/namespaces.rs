@@ -0,0 +1,202 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::ffi::{CStr, CString};
+use std::io;
+use std::os::unix::ffi::OsStrExt;
+use std::path::Path;
+
+use super::plan::{LinuxNativeMount, LinuxNativeNetwork, LinuxNativeSandboxPlan};
+
+const SIOCGIFFLAGS: libc::c_ulong = 0x8913;
+const SIOCSIFFLAGS: libc::c_ulong = 0x8914;
+
+enum PreparedMount {
+    ReadOnly(CString),
+    MaskedDirectory(CString),
+    MaskedFile(CString),
+}
+
+#[repr(C)]
+struct InterfaceFlagsRequest {
+    name: [libc::c_char; libc::IFNAMSIZ],
+    flags: libc::c_short,
+    _padding: [u8; 22],
+}
+
+/// Everything the child needs to enter its namespaces, prepared in the parent so the code that
+/// runs between `fork` and `exec` does not allocate.
+pub(super) struct NamespaceSetup {
+    flags: libc::c_int,
+    uid_map: Vec<u8>,
+    gid_map: Vec<u8>,
+    mounts: Vec<PreparedMount>,
+    network_namespace: bool,
+}
+
+impl NamespaceSetup {
+    pub(super) fn prepare(plan: &LinuxNativeSandboxPlan) -> Result<Option<Self>, String> {
+        if !plan.needs_user_namespace() {
+            return Ok(None);
+        }
+        let network_namespace = plan.network != LinuxNativeNetwork::Host;
+        let mut mounts = Vec::new();
+        for mount in plan
+            .file_system
+            .iter()
+            .flat_map(|file_system| file_system.mounts.iter())
+        {
+            let Ok(metadata) = mount.path().metadata() else {
+                continue;
+            };
+            let path = c_path(mount.path())?;
+            mounts.push(match mount {
+                LinuxNativeMount::ReadOnly(_) => PreparedMount::ReadOnly(path),
+                LinuxNativeMount::Masked(_) if metadata.is_dir() => {
+                    PreparedMount::MaskedDirectory(path)
+                }
+                LinuxNativeMount::Masked(_) => PreparedMount::MaskedFile(path),
+            });
+        }
+        let mut flags = libc::CLONE_NEWUSER | libc::CLONE_NEWNS;
+        if network_namespace {
+            flags |= libc::CLONE_NEWNET;
+        }
+        // SAFETY: getuid and getgid cannot fail.
+        let (uid, gid) = unsafe { (libc::getuid(), libc::getgid()) };
+        Ok(Some(Self {
+            flags,
+            uid_map: format!("{uid} {uid} 1\n").into_bytes(),
+            gid_map: format!("{gid} {gid} 1\n").into_bytes(),
+            mounts,
+            network_namespace,
+        }))
+    }
+
+    /// Runs in the forked child before `exec`.
+    pub(super) fn enter(&self) -> io::Result<()> {
+        // SAFETY: unshare only changes the calling (single-threaded, forked) process.
+        check(unsafe { libc::unshare(self.flags) })?;
+        write_proc_file(c"/proc/self/setgroups", b"deny")?;
+        write_proc_file(c"/proc/self/uid_map", self.uid_map.as_slice())?;
+        write_proc_file(c"/proc/self/gid_map", self.gid_map.as_slice())?;
+        if !self.mounts.is_empty() {
+            mount(None, c"/", None, libc::MS_REC | libc::MS_PRIVATE, None)?;
+        }
+        for prepared in &self.mounts {
+            let result = match prepared {
+                PreparedMount::ReadOnly(path) => bind_read_only(path),
+                PreparedMount::MaskedDirectory(path) => mount(
+                    Some(c"tmpfs"),
+                    path,
+                    Some(c"tmpfs"),
+                    libc::MS_RDONLY | libc::MS_NOSUID | libc::MS_NODEV | libc::MS_NOEXEC,
+                    Some(c"size=4k,mode=0500"),
+                ),
+                PreparedMount::MaskedFile(path) => {
+                    mount(Some(c"/dev/null"), path, None, libc::MS_BIND, None)
+                }
+            };
+            match result {
+                Err(error) if error.raw_os_error() == Some(libc::ENOENT) => {}
+                other => other?,
+            }
+        }
+        if self.network_namespace {
+            bring_up_loopback()?;
+        }
+        Ok(())
+    }
+}
+
+fn c_path(path: &Path) -> Result<CString, String> {
+    CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| format!("sandbox path contains NUL: {}", path.display()))
+}
+
+fn check(result: libc::c_int) -> io::Result<()> {
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+fn write_proc_file(path: &CStr, contents: &[u8]) -> io::Result<()> {
+    // SAFETY: `path` is NUL-terminated and the descriptor is closed before returning.
+    unsafe {
+        let fd = libc::open(path.as_ptr(), libc::O_WRONLY | libc::O_CLOEXEC);
+        check(fd)?;
+        let written = libc::write(fd, contents.as_ptr().cast(), contents.len());
+        let error = io::Error::last_os_error();
+        libc::close(fd);
+        if written != contents.len() as isize {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
+
+fn mount(
+    source: Option<&CStr>,
+    target: &CStr,
+    file_system: Option<&CStr>,
+    flags: libc::c_ulong,
+    data: Option<&CStr>,
+) -> io::Result<()> {
+    let pointer = |value: Option<&CStr>| value.map_or(std::ptr::null(), CStr::as_ptr);
+    // SAFETY: every pointer is either null or a NUL-terminated string that outlives the call.
+    check(unsafe {
+        libc::mount(
+            pointer(source),
+            target.as_ptr(),
+            pointer(file_system),
+            flags,
+            pointer(data).cast(),
+        )
+    })
+}
+
+/// Bind mounts `path` onto itself and remounts it read-only, keeping the flags the kernel locks
+/// on mounts inherited from the parent namespace.
+fn bind_read_only(path: &CStr) -> io::Result<()> {
+    mount(Some(path), path, None, libc::MS_BIND | libc::MS_REC, None)?;
+    // SAFETY: `stat` is plain data and `path` is NUL-terminated.
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    check(unsafe { libc::statvfs(path.as_ptr(), &mut stat) })?;
+    let mut flags = libc::MS_BIND | libc::MS_REMOUNT | libc::MS_RDONLY;
+    for (statvfs_flag, mount_flag) in [
+        (libc::ST_NOSUID, libc::MS_NOSUID),
+        (libc::ST_NODEV, libc::MS_NODEV),
+        (libc::ST_NOEXEC, libc::MS_NOEXEC),
+        (libc::ST_NOATIME, libc::MS_NOATIME),
+        (libc::ST_NODIRATIME, libc::MS_NODIRATIME),
+        (libc::ST_RELATIME, libc::MS_RELATIME),
+    ] {
+        if stat.f_flag & statvfs_flag != 0 {
+            flags |= mount_flag;
+        }
+    }
+    mount(None, path, None, flags, None)
+}
+
+fn bring_up_loopback() -> io::Result<()> {
+    // SAFETY: the socket is only used for the two interface ioctls and closed before returning.
+    unsafe {
+        let fd = libc::socket(libc::AF_INET, libc::SOCK_DGRAM | libc::SOCK_CLOEXEC, 0);
+        check(fd)?;
+        let mut request: InterfaceFlagsRequest = std::mem::zeroed();
+        request.name[0] = b'l' as libc::c_char;
+        request.name[1] = b'o' as libc::c_char;
+        let mut result = libc::ioctl(fd, SIOCGIFFLAGS as _, &mut request);
+        if result == 0 {
+            request.flags |= libc::IFF_UP as libc::c_short;
+            result = libc::ioctl(fd, SIOCSIFFLAGS as _, &request);
+        }
+        let error = io::Error::last_os_error();
+        libc::close(fd);
+        if result != 0 {
+            return Err(error);
+        }
+    }
+    Ok(())
+}
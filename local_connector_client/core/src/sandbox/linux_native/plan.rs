@@ -0,0 +1,415 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::path::{Component, Path, PathBuf};
+
+use chatos_sandbox_contract::{
+    EffectivePermissionSnapshot, FileSystemAccessMode, FileSystemPath, FileSystemPermissionPolicy,
+    FileSystemSpecialPath, NetworkPermissionPolicy,
+};
+
+/// Directories a shell needs to start when a profile only grants `:minimal` access.
+const MINIMAL_READ_ROOTS: &[&str] = &[
+    "/bin", "/sbin", "/usr", "/lib", "/lib32", "/lib64", "/etc", "/dev", "/proc",
+];
+
+/// Devices every shell writes to; granted whenever the filesystem is restricted.
+const DEVICE_WRITE_ROOTS: &[&str] = &[
+    "/dev/null",
+    "/dev/zero",
+    "/dev/full",
+    "/dev/tty",
+    "/dev/ptmx",
+    "/dev/pts",
+    "/dev/shm",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LinuxNativeNetwork {
+    /// Fresh network namespace that only has the loopback interface.
+    Loopback,
+    /// Fresh network namespace whose loopback only bridges the proxy ports to the host's local
+    /// proxy, so every connection leaves through the proxy.
+    Proxied {
+        http_port: Option<u16>,
+        socks_port: Option<u16>,
+    },
+    Host,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum LinuxNativeMount {
+    /// Read-only bind mount over a path that sits inside a writable root.
+    ReadOnly(PathBuf),
+    /// Empty read-only mount hiding a denied path that an allowed root would expose.
+    Masked(PathBuf),
+}
+
+impl LinuxNativeMount {
+    pub(crate) fn path(&self) -> &Path {
+        match self {
+            Self::ReadOnly(path) | Self::Masked(path) => path.as_path(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct LinuxNativeFileSystem {
+    pub(crate) readable_roots: Vec<PathBuf>,
+    pub(crate) writable_roots: Vec<PathBuf>,
+    pub(crate) mounts: Vec<LinuxNativeMount>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct LinuxNativeSandboxPlan {
+    /// `None` when the permission profile leaves the filesystem unrestricted.
+    pub(crate) file_system: Option<LinuxNativeFileSystem>,
+    pub(crate) network: LinuxNativeNetwork,
+}
+
+impl LinuxNativeSandboxPlan {
+    /// Translates an effective permission snapshot into the roots, mounts and network mode the
+    /// native backend enforces. Anything the kernel primitives cannot express fails closed.
+    pub(crate) fn from_permissions(
+        permissions: &EffectivePermissionSnapshot,
+        tmpdir: Option<&Path>,
+    ) -> Result<Self, String> {
+        let project_roots = permissions
+            .runtime_workspace_roots
+            .iter()
+            .map(|root| normalize_path(Path::new(root.trim())))
+            .filter(|root| root.is_absolute())
+            .collect::<Vec<_>>();
+        let file_system = match &permissions.file_system {
+            FileSystemPermissionPolicy::Unrestricted => None,
+            FileSystemPermissionPolicy::Restricted { entries, .. } => {
+                let mut readable = Vec::new();
+                let mut writable = Vec::new();
+                let mut denied = Vec::new();
+                for entry in entries {
+                    let paths = resolve_entry_paths(
+                        &entry.path,
+                        entry.access,
+                        project_roots.as_slice(),
+                        tmpdir,
+                    )?;
+                    match entry.access {
+                        FileSystemAccessMode::Read => readable.extend(paths),
+                        FileSystemAccessMode::Write => writable.extend(paths),
+                        FileSystemAccessMode::Deny => denied.extend(paths),
+                    }
+                }
+                Some(file_system_plan(readable, writable, denied))
+            }
+        };
+        let network = match &permissions.network {
+            NetworkPermissionPolicy::Unrestricted => LinuxNativeNetwork::Host,
+            NetworkPermissionPolicy::Restricted { requirements }
+                if requirements.enabled == Some(true) =>
+            {
+                let http_port = requirements.http_port;
+                let socks_port = requirements
+                    .socks_port
+                    .filter(|_| requirements.enable_socks5 != Some(false));
+                if http_port.is_none() && socks_port.is_none() {
+                    return Err(
+                        "linux_native needs an httpPort or socksPort to route controlled network access"
+                            .to_string(),
+                    );
+                }
+                LinuxNativeNetwork::Proxied {
+                    http_port,
+                    socks_port,
+                }
+            }
+            NetworkPermissionPolicy::Restricted { .. } => LinuxNativeNetwork::Loopback,
+        };
+        Ok(Self {
+            file_system,
+            network,
+        })
+    }
+
+    pub(crate) fn needs_user_namespace(&self) -> bool {
+        self.network != LinuxNativeNetwork::Host
+            || self
+                .file_system
+                .as_ref()
+                .is_some_and(|file_system| !file_system.mounts.is_empty())
+    }
+
+    pub(crate) fn proxy_ports(&self) -> Vec<u16> {
+        match self.network {
+            LinuxNativeNetwork::Proxied {
+                http_port,
+                socks_port,
+            } => http_port.into_iter().chain(socks_port).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub(crate) fn proxy_environment(&self) -> Vec<(&'static str, String)> {
+        let LinuxNativeNetwork::Proxied {
+            http_port,
+            socks_port,
+        } = self.network
+        else {
+            return Vec::new();
+        };
+        let mut environment = Vec::new();
+        let http_proxy = http_port.map(|port| format!("http://127.0.0.1:{port}"));
+        if let Some(proxy) = http_proxy.as_ref() {
+            for name in ["HTTP_PROXY", "http_proxy", "HTTPS_PROXY", "https_proxy"] {
+                environment.push((name, proxy.clone()));
+            }
+        }
+        if let Some(proxy) = socks_port
+            .map(|port| format!("socks5h://127.0.0.1:{port}"))
+            .or(http_proxy)
+        {
+            environment.push(("ALL_PROXY", proxy.clone()));
+            environment.push(("all_proxy", proxy));
+        }
+        for name in ["NO_PROXY", "no_proxy"] {
+            environment.push((name, "localhost,127.0.0.1,::1".to_string()));
+        }
+        environment
+    }
+}
+
+fn resolve_entry_paths(
+    path: &FileSystemPath,
+    access: FileSystemAccessMode,
+    project_roots: &[PathBuf],
+    tmpdir: Option<&Path>,
+) -> Result<Vec<PathBuf>, String> {
+    let paths = match path {
+        FileSystemPath::Path { path } => {
+            let path = Path::new(path.trim());
+            if path.is_absolute() {
+                vec![path.to_path_buf()]
+            } else {
+                project_roots.iter().map(|root| root.join(path)).collect()
+            }
+        }
+        FileSystemPath::GlobPattern { pattern } => {
+            return Err(format!(
+                "linux_native cannot enforce glob filesystem permission {pattern}"
+            ));
+        }
+        FileSystemPath::Special { value } => match value {
+            FileSystemSpecialPath::Root => vec![PathBuf::from("/")],
+            FileSystemSpecialPath::Minimal => {
+                MINIMAL_READ_ROOTS.iter().map(PathBuf::from).collect()
+            }
+            FileSystemSpecialPath::ProjectRoots { subpath } => project_roots
+                .iter()
+                .map(|root| match subpath.as_deref() {
+                    Some(subpath) => root.join(subpath),
+                    None => root.clone(),
+                })
+                .collect(),
+            FileSystemSpecialPath::Tmpdir => tmpdir.map(Path::to_path_buf).into_iter().collect(),
+            FileSystemSpecialPath::SlashTmp => vec![PathBuf::from("/tmp")],
+            FileSystemSpecialPath::Unknown { path, .. } => {
+                if access == FileSystemAccessMode::Deny {
+                    return Err(format!(
+                        "linux_native cannot resolve denied special path {path}"
+                    ));
+                }
+                Vec::new()
+            }
+        },
+    };
+    Ok(paths.iter().map(|path| normalize_path(path)).collect())
+}
+
+fn file_system_plan(
+    readable: Vec<PathBuf>,
+    mut writable: Vec<PathBuf>,
+    denied: Vec<PathBuf>,
+) -> LinuxNativeFileSystem {
+    writable.extend(DEVICE_WRITE_ROOTS.iter().map(PathBuf::from));
+    let writable = sorted_unique(writable);
+    let readable = sorted_unique(readable)
+        .into_iter()
+        .filter(|path| !writable.contains(path))
+        .collect::<Vec<_>>();
+
+    let mut mounts = readable
+        .iter()
+        .filter(|path| writable.iter().any(|root| is_strictly_within(path, root)))
+        .cloned()
+        .map(LinuxNativeMount::ReadOnly)
+        .collect::<Vec<_>>();
+    mounts.extend(
+        sorted_unique(denied)
+            .into_iter()
+            .filter(|path| {
+                readable
+                    .iter()
+                    .chain(writable.iter())
+                    .any(|root| path.starts_with(root))
+            })
+            .map(LinuxNativeMount::Masked),
+    );
+    // Parents are mounted before the paths nested inside them.
+    mounts.sort_by_key(|mount| mount.path().components().count());
+    LinuxNativeFileSystem {
+        readable_roots: readable,
+        writable_roots: writable,
+        mounts,
+    }
+}
+
+fn is_strictly_within(path: &Path, root: &Path) -> bool {
+    path != root && path.starts_with(root)
+}
+
+fn sorted_unique(mut paths: Vec<PathBuf>) -> Vec<PathBuf> {
+    paths.sort();
+    paths.dedup();
+    paths
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use chatos_sandbox_contract::{
+        legacy_policy_permission_snapshot, EffectiveSandboxPolicy, FileSystemSandboxEntry,
+        NetworkRequirements, PermissionProfileId,
+    };
+
+    use super::*;
+
+    fn snapshot(profile: PermissionProfileId) -> EffectivePermissionSnapshot {
+        legacy_policy_permission_snapshot(
+            &EffectiveSandboxPolicy {
+                permission_profile_id: profile,
+                ..EffectiveSandboxPolicy::default()
+            },
+            vec!["/home/dev/project".to_string()],
+        )
+    }
+
+    #[test]
+    fn workspace_write_grants_project_and_protects_metadata_directories() {
+        let plan = LinuxNativeSandboxPlan::from_permissions(
+            &snapshot(PermissionProfileId::WorkspaceWrite),
+            Some(Path::new("/tmp/chatos")),
+        )
+        .expect("plan");
+
+        let file_system = plan.file_system.as_ref().expect("restricted filesystem");
+        assert_eq!(file_system.readable_roots[0], PathBuf::from("/"));
+        assert!(file_system
+            .writable_roots
+            .contains(&PathBuf::from("/home/dev/project")));
+        assert!(file_system
+            .writable_roots
+            .contains(&PathBuf::from("/dev/null")));
+        assert!(file_system
+            .mounts
+            .contains(&LinuxNativeMount::ReadOnly(PathBuf::from(
+                "/home/dev/project/.git"
+            ))));
+        assert_eq!(plan.network, LinuxNativeNetwork::Loopback);
+        assert!(plan.needs_user_namespace());
+    }
+
+    #[test]
+    fn full_access_leaves_filesystem_and_network_alone() {
+        let plan = LinuxNativeSandboxPlan::from_permissions(
+            &snapshot(PermissionProfileId::FullAccess),
+            None,
+        )
+        .expect("plan");
+
+        assert_eq!(plan.file_system, None);
+        assert_eq!(plan.network, LinuxNativeNetwork::Host);
+        assert!(!plan.needs_user_namespace());
+    }
+
+    #[test]
+    fn denied_paths_are_masked_only_when_an_allowed_root_exposes_them() {
+        let mut permissions = snapshot(PermissionProfileId::WorkspaceWrite);
+        let FileSystemPermissionPolicy::Restricted { entries, .. } = &mut permissions.file_system
+        else {
+            panic!("restricted filesystem");
+        };
+        entries.push(FileSystemSandboxEntry {
+            access: FileSystemAccessMode::Deny,
+            path: FileSystemPath::Path {
+                path: "secrets/../.env".to_string(),
+            },
+        });
+
+        let plan = LinuxNativeSandboxPlan::from_permissions(&permissions, None).expect("plan");
+        let mounts = plan.file_system.expect("restricted filesystem").mounts;
+
+        assert!(mounts.contains(&LinuxNativeMount::Masked(PathBuf::from(
+            "/home/dev/project/.env"
+        ))));
+    }
+
+    #[test]
+    fn glob_denies_and_portless_controlled_network_fail_closed() {
+        let mut permissions = snapshot(PermissionProfileId::ReadOnly);
+        if let FileSystemPermissionPolicy::Restricted { entries, .. } = &mut permissions.file_system
+        {
+            entries.push(FileSystemSandboxEntry {
+                access: FileSystemAccessMode::Deny,
+                path: FileSystemPath::GlobPattern {
+                    pattern: "**/*.pem".to_string(),
+                },
+            });
+        }
+        assert!(LinuxNativeSandboxPlan::from_permissions(&permissions, None).is_err());
+
+        let mut permissions = snapshot(PermissionProfileId::ReadOnly);
+        permissions.network = NetworkPermissionPolicy::Restricted {
+            requirements: NetworkRequirements {
+                enabled: Some(true),
+                ..NetworkRequirements::default()
+            },
+        };
+        assert!(LinuxNativeSandboxPlan::from_permissions(&permissions, None).is_err());
+    }
+
+    #[test]
+    fn controlled_network_routes_through_the_local_proxy() {
+        let mut permissions = snapshot(PermissionProfileId::ReadOnly);
+        permissions.network = NetworkPermissionPolicy::Restricted {
+            requirements: NetworkRequirements {
+                enabled: Some(true),
+                http_port: Some(3128),
+                socks_port: Some(1080),
+                ..NetworkRequirements::default()
+            },
+        };
+
+        let plan = LinuxNativeSandboxPlan::from_permissions(&permissions, None).expect("plan");
+
+        assert_eq!(plan.proxy_ports(), vec![3128, 1080]);
+        assert!(plan
+            .proxy_environment()
+            .contains(&("HTTPS_PROXY", "http://127.0.0.1:3128".to_string())));
+        assert!(plan
+            .proxy_environment()
+            .contains(&("ALL_PROXY", "socks5h://127.0.0.1:1080".to_string())));
+        assert!(plan.needs_user_namespace());
+    }
+}
@@ -0,0 +1,180 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::io;
+use std::sync::Arc;
+
+use super::landlock::LandlockRuleset;
+use super::namespaces::NamespaceSetup;
+use super::plan::{LinuxNativeNetwork, LinuxNativeSandboxPlan};
+use super::proxy_bridge::ProxyBridge;
+use super::seccomp::SeccompFilter;
+
+struct PreparedSandbox {
+    namespaces: Option<NamespaceSetup>,
+    bridge: Option<ProxyBridge>,
+    ruleset: Option<LandlockRuleset>,
+    filter: SeccompFilter,
+}
+
+impl PreparedSandbox {
+    fn prepare(plan: &LinuxNativeSandboxPlan) -> Result<Self, String> {
+        Ok(Self {
+            namespaces: NamespaceSetup::prepare(plan)?,
+            bridge: ProxyBridge::start(plan)?,
+            ruleset: LandlockRuleset::build(plan.file_system.as_ref())?,
+            filter: SeccompFilter::new(),
+        })
+    }
+
+    /// Order matters: namespaces need the privileges that `no_new_privs`, Landlock and seccomp
+    /// take away, the proxy bridge listens inside the new network namespace, and seccomp goes
+    /// last because it forbids `mount` and `unshare`.
+    fn enter(&self) -> io::Result<()> {
+        if let Some(namespaces) = &self.namespaces {
+            namespaces.enter()?;
+        }
+        if let Some(bridge) = &self.bridge {
+            bridge.enter()?;
+        }
+        // SAFETY: PR_SET_NO_NEW_PRIVS takes integer arguments only.
+        if unsafe { libc::prctl(libc::PR_SET_NO_NEW_PRIVS, 1, 0, 0, 0) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if let Some(ruleset) = &self.ruleset {
+            ruleset.restrict_self()?;
+        }
+        self.filter.install()
+    }
+}
+
+pub(super) fn confine_command(
+    command: &mut tokio::process::Command,
+    plan: &LinuxNativeSandboxPlan,
+) -> Result<(), String> {
+    let prepared = Arc::new(PreparedSandbox::prepare(plan)?);
+    for (name, value) in plan.proxy_environment() {
+        command.env(name, value);
+    }
+    // SAFETY: `enter` only issues syscalls on data prepared before the fork.
+    unsafe {
+        command.pre_exec(move || prepared.enter());
+    }
+    Ok(())
+}
+
+/// Spawns `true` inside the namespaces a loopback-only sandbox uses, so hosts that block
+/// unprivileged user namespaces report setup-required instead of failing at the first command.
+pub(super) fn probe_user_namespaces() -> Result<(), String> {
+    use std::os::unix::process::CommandExt;
+
+    let plan = LinuxNativeSandboxPlan {
+        file_system: None,
+        network: LinuxNativeNetwork::Loopback,
+    };
+    let namespaces = NamespaceSetup::prepare(&plan)?
+        .ok_or_else(|| "loopback sandbox must use namespaces".to_string())?;
+    let mut command = std::process::Command::new("true");
+    command
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    // SAFETY: `enter` only issues syscalls on data prepared before the fork.
+    unsafe {
+        command.pre_exec(move || namespaces.enter());
+    }
+    let status = command
+        .status()
+        .map_err(|err| format!("unprivileged user namespaces are unavailable: {err}"))?;
+    if !status.success() {
+        return Err(format!("namespace probe exited with {status}"));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{ErrorKind, Write};
+    use std::path::PathBuf;
+
+    use super::super::plan::LinuxNativeFileSystem;
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "needs Landlock enabled in the host kernel"]
+    async fn confined_process_cannot_write_outside_its_writable_roots() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let allowed = temp.path().join("allowed");
+        let outside = temp.path().join("outside");
+        std::fs::create_dir_all(&allowed).expect("create allowed root");
+        std::fs::create_dir_all(&outside).expect("create outside directory");
+        let plan = LinuxNativeSandboxPlan {
+            file_system: Some(LinuxNativeFileSystem {
+                readable_roots: vec![PathBuf::from("/")],
+                writable_roots: vec![allowed.clone(), PathBuf::from("/dev/null")],
+                mounts: Vec::new(),
+            }),
+            network: LinuxNativeNetwork::Host,
+        };
+
+        let mut command = tokio::process::Command::new("sh");
+        command
+            .arg("-c")
+            .arg(r#"echo inside > "$1/inside"; echo escaped > "$2/escaped""#)
+            .arg("sh")
+            .arg(&allowed)
+            .arg(&outside)
+            .stderr(std::process::Stdio::null());
+        confine_command(&mut command, &plan).expect("confine command");
+        let status = command.status().await.expect("run confined command");
+
+        assert!(!status.success());
+        assert_eq!(
+            std::fs::read_to_string(allowed.join("inside")).expect("write inside root"),
+            "inside\n"
+        );
+        assert!(!outside.join("escaped").exists());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs unprivileged user namespaces and bash"]
+    async fn proxied_process_only_reaches_the_host_through_the_proxy_port() {
+        let proxy = std::net::TcpListener::bind("127.0.0.1:0").expect("bind proxy");
+        let other = std::net::TcpListener::bind("127.0.0.1:0").expect("bind other service");
+        other.set_nonblocking(true).expect("nonblocking listener");
+        let proxy_port = proxy.local_addr().expect("proxy address").port();
+        let other_port = other.local_addr().expect("other address").port();
+        let proxy_thread = std::thread::spawn(move || {
+            let (mut stream, _) = proxy.accept().expect("accept bridged connection");
+            stream
+                .write_all(b"proxied\n")
+                .expect("answer bridged connection");
+        });
+        let plan = LinuxNativeSandboxPlan {
+            file_system: None,
+            network: LinuxNativeNetwork::Proxied {
+                http_port: Some(proxy_port),
+                socks_port: None,
+            },
+        };
+
+        let mut command = tokio::process::Command::new("bash");
+        command
+            .arg("-c")
+            .arg(r#"cat < "/dev/tcp/127.0.0.1/$1" && exec 3<> "/dev/tcp/127.0.0.1/$2""#)
+            .arg("bash")
+            .arg(proxy_port.to_string())
+            .arg(other_port.to_string())
+            .stderr(std::process::Stdio::null());
+        confine_command(&mut command, &plan).expect("confine command");
+        let output = command.output().await.expect("run confined command");
+        proxy_thread.join().expect("proxy thread");
+
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "proxied\n");
+        assert!(!output.status.success());
+        assert_eq!(
+            other.accept().expect_err("no direct connection").kind(),
+            ErrorKind::WouldBlock
+        );
+    }
+}
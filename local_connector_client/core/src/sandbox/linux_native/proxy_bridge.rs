@@ -0,0 +1,200 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::io;
+use std::net::Ipv4Addr;
+use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+use std::os::unix::net::UnixStream;
+
+use tokio::io::{AsyncReadExt, Interest};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinSet;
+
+use super::plan::LinuxNativeSandboxPlan;
+use crate::tracing_stdout;
+
+/// A proxied plan routes at most an HTTP and a SOCKS port.
+const MAX_BRIDGED_PORTS: usize = 2;
+
+/// Bridges the proxy ports into a proxied sandbox's loopback-only network namespace. The child
+/// listens on `127.0.0.1:<port>` inside the namespace and hands the listeners to the connector,
+/// which accepts there and forwards each connection to the same port on the host's loopback.
+/// Nothing else in the namespace can reach the host network.
+pub(super) struct ProxyBridge {
+    addresses: Vec<libc::sockaddr_in>,
+    sandbox_end: OwnedFd,
+}
+
+impl ProxyBridge {
+    /// Starts the connector half on the current Tokio runtime. The relay stops once every
+    /// sandboxed process holding the other end of the control socket has exited.
+    pub(super) fn start(plan: &LinuxNativeSandboxPlan) -> Result<Option<Self>, String> {
+        let ports = plan.proxy_ports();
+        if ports.is_empty() {
+            return Ok(None);
+        }
+        let runtime = tokio::runtime::Handle::try_current()
+            .map_err(|_| "bridging the sandbox proxy needs a Tokio runtime".to_string())?;
+        let (connector_end, sandbox_end) =
+            UnixStream::pair().map_err(|err| format!("create proxy bridge failed: {err}"))?;
+        connector_end
+            .set_nonblocking(true)
+            .map_err(|err| format!("create proxy bridge failed: {err}"))?;
+        let addresses = ports.iter().map(|port| loopback_address(*port)).collect();
+        runtime.spawn(async move {
+            if let Err(err) = relay(connector_end, ports).await {
+                tracing_stdout(format!("linux_native proxy bridge stopped: {err}").as_str());
+            }
+        });
+        Ok(Some(Self {
+            addresses,
+            sandbox_end: sandbox_end.into(),
+        }))
+    }
+
+    /// Runs in the forked child inside its network namespace. Only issues syscalls on data
+    /// prepared before the fork.
+    pub(super) fn enter(&self) -> io::Result<()> {
+        let mut listeners = [-1; MAX_BRIDGED_PORTS];
+        for (listener, address) in listeners.iter_mut().zip(&self.addresses) {
+            // SAFETY: plain socket syscalls on a descriptor this function owns; exec closes it.
+            unsafe {
+                *listener = check(libc::socket(
+                    libc::AF_INET,
+                    libc::SOCK_STREAM | libc::SOCK_CLOEXEC,
+                    0,
+                ))?;
+                check(libc::bind(
+                    *listener,
+                    (address as *const libc::sockaddr_in).cast(),
+                    std::mem::size_of::<libc::sockaddr_in>() as libc::socklen_t,
+                ))?;
+                check(libc::listen(*listener, libc::SOMAXCONN))?;
+            }
+        }
+        let fd = self.sandbox_end.as_raw_fd();
+        send_descriptors(fd, &listeners[..self.addresses.len()])?;
+        // The sandboxed processes inherit the control socket so the relay can tell when the
+        // last of them exits.
+        // SAFETY: clears FD_CLOEXEC on a descriptor owned by `self`.
+        check(unsafe { libc::fcntl(fd, libc::F_SETFD, 0) })?;
+        Ok(())
+    }
+}
+
+async fn relay(control: UnixStream, ports: Vec<u16>) -> io::Result<()> {
+    let fd = control.as_raw_fd();
+    let mut control = tokio::net::UnixStream::from_std(control)?;
+    let listeners = loop {
+        control.readable().await?;
+        match control.try_io(Interest::READABLE, || receive_descriptors(fd)) {
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => continue,
+            result => break result?,
+        }
+    };
+    let mut forwarders = JoinSet::new();
+    for (listener, port) in listeners.into_iter().zip(ports) {
+        let listener = std::net::TcpListener::from(listener);
+        listener.set_nonblocking(true)?;
+        forwarders.spawn(forward_connections(TcpListener::from_std(listener)?, port));
+    }
+    let mut discard = [0u8; 64];
+    while !matches!(control.read(&mut discard).await, Ok(0) | Err(_)) {}
+    Ok(())
+}
+
+async fn forward_connections(listener: TcpListener, port: u16) {
+    while let Ok((mut inbound, _)) = listener.accept().await {
+        tokio::spawn(async move {
+            if let Ok(mut outbound) = TcpStream::connect((Ipv4Addr::LOCALHOST, port)).await {
+                let _ = tokio::io::copy_bidirectional(&mut inbound, &mut outbound).await;
+            }
+        });
+    }
+}
+
+fn loopback_address(port: u16) -> libc::sockaddr_in {
+    libc::sockaddr_in {
+        sin_family: libc::AF_INET as libc::sa_family_t,
+        sin_port: port.to_be(),
+        sin_addr: libc::in_addr {
+            s_addr: u32::from(Ipv4Addr::LOCALHOST).to_be(),
+        },
+        sin_zero: [0; 8],
+    }
+}
+
+fn check(result: libc::c_int) -> io::Result<libc::c_int> {
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(result)
+}
+
+fn send_descriptors(socket: RawFd, descriptors: &[RawFd]) -> io::Result<()> {
+    let payload_len = std::mem::size_of_val(descriptors) as libc::c_uint;
+    let mut data = [1u8];
+    let mut control = [0u64; 4];
+    // SAFETY: the control buffer is aligned and large enough for `MAX_BRIDGED_PORTS`
+    // descriptors, and every pointer in `message` outlives the call.
+    unsafe {
+        let mut iov = libc::iovec {
+            iov_base: data.as_mut_ptr().cast(),
+            iov_len: data.len(),
+        };
+        let mut message: libc::msghdr = std::mem::zeroed();
+        message.msg_iov = &mut iov;
+        message.msg_iovlen = 1;
+        message.msg_control = control.as_mut_ptr().cast();
+        message.msg_controllen = libc::CMSG_SPACE(payload_len) as _;
+        let header = libc::CMSG_FIRSTHDR(&message);
+        (*header).cmsg_level = libc::SOL_SOCKET;
+        (*header).cmsg_type = libc::SCM_RIGHTS;
+        (*header).cmsg_len = libc::CMSG_LEN(payload_len) as _;
+        std::ptr::copy_nonoverlapping(
+            descriptors.as_ptr(),
+            libc::CMSG_DATA(header).cast::<RawFd>(),
+            descriptors.len(),
+        );
+        if libc::sendmsg(socket, &message, libc::MSG_NOSIGNAL) != data.len() as isize {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Returns no descriptors when the child closed the control socket before entering the
+/// sandbox, for example because its setup failed.
+fn receive_descriptors(socket: RawFd) -> io::Result<Vec<OwnedFd>> {
+    let mut data = [0u8];
+    let mut control = [0u64; 4];
+    let mut descriptors = Vec::new();
+    // SAFETY: the kernel writes at most `msg_controllen` bytes into the aligned control
+    // buffer, and only SCM_RIGHTS payloads are read back as descriptors.
+    unsafe {
+        let mut iov = libc::iovec {
+            iov_base: data.as_mut_ptr().cast(),
+            iov_len: data.len(),
+        };
+        let mut message: libc::msghdr = std::mem::zeroed();
+        message.msg_iov = &mut iov;
+        message.msg_iovlen = 1;
+        message.msg_control = control.as_mut_ptr().cast();
+        message.msg_controllen = std::mem::size_of_val(&control) as _;
+        if libc::recvmsg(socket, &mut message, libc::MSG_CMSG_CLOEXEC) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut header = libc::CMSG_FIRSTHDR(&message);
+        while !header.is_null() {
+            if (*header).cmsg_level == libc::SOL_SOCKET && (*header).cmsg_type == libc::SCM_RIGHTS {
+                let payload_len = (*header).cmsg_len as usize - libc::CMSG_LEN(0) as usize;
+                let payload = libc::CMSG_DATA(header).cast::<RawFd>();
+                for index in 0..payload_len / std::mem::size_of::<RawFd>() {
+                    descriptors.push(OwnedFd::from_raw_fd(payload.add(index).read_unaligned()));
+                }
+            }
+            header = libc::CMSG_NXTHDR(&message, header);
+        }
+    }
+    Ok(descriptors)
+}
@@ -0,0 +1,158 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::io;
+
+#[cfg(target_arch = "x86_64")]
+const AUDIT_ARCH_CURRENT: u32 = 0xC000_003E;
+#[cfg(target_arch = "aarch64")]
+const AUDIT_ARCH_CURRENT: u32 = 0xC000_00B7;
+#[cfg(target_arch = "x86_64")]
+const X32_SYSCALL_BIT: u32 = 0x4000_0000;
+
+const SECCOMP_DATA_NR: u32 = 0;
+const SECCOMP_DATA_ARCH: u32 = 4;
+const SECCOMP_DATA_ARGS: u32 = 16;
+
+/// Namespace flags a sandboxed process may not pass to `clone`.
+const CLONE_NAMESPACE_FLAGS: u32 = (libc::CLONE_NEWNS
+    | libc::CLONE_NEWUSER
+    | libc::CLONE_NEWNET
+    | libc::CLONE_NEWPID
+    | libc::CLONE_NEWIPC
+    | libc::CLONE_NEWUTS
+    | libc::CLONE_NEWCGROUP) as u32;
+
+/// Syscalls that could undo the namespace and Landlock confinement or reach the kernel directly.
+const DENIED_SYSCALLS: &[libc::c_long] = &[
+    libc::SYS_ptrace,
+    libc::SYS_process_vm_readv,
+    libc::SYS_process_vm_writev,
+    libc::SYS_mount,
+    libc::SYS_umount2,
+    libc::SYS_pivot_root,
+    libc::SYS_chroot,
+    libc::SYS_fsopen,
+    libc::SYS_fsmount,
+    libc::SYS_move_mount,
+    libc::SYS_open_tree,
+    libc::SYS_open_by_handle_at,
+    libc::SYS_unshare,
+    libc::SYS_setns,
+    libc::SYS_swapon,
+    libc::SYS_swapoff,
+    libc::SYS_reboot,
+    libc::SYS_kexec_load,
+    libc::SYS_kexec_file_load,
+    libc::SYS_init_module,
+    libc::SYS_finit_module,
+    libc::SYS_delete_module,
+    libc::SYS_bpf,
+    libc::SYS_perf_event_open,
+    libc::SYS_userfaultfd,
+    libc::SYS_keyctl,
+    libc::SYS_add_key,
+    libc::SYS_request_key,
+    libc::SYS_io_uring_setup,
+    libc::SYS_io_uring_enter,
+    libc::SYS_io_uring_register,
+];
+
+/// Compiled classic BPF program installed with `PR_SET_SECCOMP` in the forked child.
+pub(super) struct SeccompFilter {
+    program: Vec<libc::sock_filter>,
+}
+
+impl SeccompFilter {
+    pub(super) fn new() -> Self {
+        let mut program = vec![
+            load(SECCOMP_DATA_ARCH),
+            jump(libc::BPF_JEQ, AUDIT_ARCH_CURRENT, 1, 0),
+            ret(libc::SECCOMP_RET_KILL_PROCESS),
+            load(SECCOMP_DATA_NR),
+        ];
+        #[cfg(target_arch = "x86_64")]
+        program.extend([
+            jump(libc::BPF_JGE, X32_SYSCALL_BIT, 0, 1),
+            ret(errno(libc::EPERM)),
+        ]);
+        for syscall in DENIED_SYSCALLS {
+            program.extend([
+                jump(libc::BPF_JEQ, *syscall as u32, 0, 1),
+                ret(errno(libc::EPERM)),
+            ]);
+        }
+        // Make libc fall back from clone3, whose flags live in memory BPF cannot inspect.
+        program.extend([
+            jump(libc::BPF_JEQ, libc::SYS_clone3 as u32, 0, 1),
+            ret(errno(libc::ENOSYS)),
+            jump(libc::BPF_JEQ, libc::SYS_clone as u32, 0, 4),
+            load(argument(0)),
+            jump(libc::BPF_JSET, CLONE_NAMESPACE_FLAGS, 0, 1),
+            ret(errno(libc::EPERM)),
+            ret(libc::SECCOMP_RET_ALLOW),
+        ]);
+        program.push(ret(libc::SECCOMP_RET_ALLOW));
+        Self { program }
+    }
+
+    /// Runs in the forked child after `no_new_privs` is set; only issues one syscall.
+    pub(super) fn install(&self) -> io::Result<()> {
+        let program = libc::sock_fprog {
+            len: self.program.len() as libc::c_ushort,
+            filter: self.program.as_ptr() as *mut libc::sock_filter,
+        };
+        // SAFETY: `program` points at instructions owned by `self` for the whole call.
+        let result = unsafe {
+            libc::prctl(
+                libc::PR_SET_SECCOMP,
+                libc::SECCOMP_MODE_FILTER as libc::c_ulong,
+                &program as *const libc::sock_fprog,
+            )
+        };
+        if result != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(())
+    }
+}
+
+pub(super) fn seccomp_filter_supported() -> bool {
+    // SAFETY: PR_GET_SECCOMP takes no pointer arguments.
+    unsafe { libc::prctl(libc::PR_GET_SECCOMP) >= 0 }
+}
+
+/// Offset of the low 32 bits of a syscall argument on the little-endian targets we build for.
+const fn argument(index: u32) -> u32 {
+    SECCOMP_DATA_ARGS + index * 8
+}
+
+const fn errno(code: libc::c_int) -> u32 {
+    libc::SECCOMP_RET_ERRNO | (code as u32 & libc::SECCOMP_RET_DATA)
+}
+
+fn statement(code: u32, k: u32) -> libc::sock_filter {
+    libc::sock_filter {
+        code: code as u16,
+        jt: 0,
+        jf: 0,
+        k,
+    }
+}
+
+fn load(offset: u32) -> libc::sock_filter {
+    statement(libc::BPF_LD | libc::BPF_W | libc::BPF_ABS, offset)
+}
+
+fn ret(value: u32) -> libc::sock_filter {
+    statement(libc::BPF_RET | libc::BPF_K, value)
+}
+
+fn jump(condition: u32, k: u32, jt: u8, jf: u8) -> libc::sock_filter {
+    libc::sock_filter {
+        code: (libc::BPF_JMP | condition | libc::BPF_K) as u16,
+        jt,
+        jf,
+        k,
+    }
+}
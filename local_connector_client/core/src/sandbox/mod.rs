@@ -2,6 +2,7 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 pub(crate) mod lease;
+pub(crate) mod linux_native;
 pub(crate) mod managed_requirements;
 pub(crate) mod managed_requirements_cache;
 pub(crate) mod pairing;
@@ -14,6 +15,32 @@ pub(crate) mod types;
 pub(crate) mod windows_security;
 pub(crate) mod workspace;
 
+/// The client has no container runtime, so every backend other than `linux_native` runs tools as
+/// plain local processes.
+pub(crate) fn local_execution_backend(
+    configured: chatos_sandbox_contract::SandboxBackendKind,
+) -> chatos_sandbox_contract::SandboxBackendKind {
+    use chatos_sandbox_contract::SandboxBackendKind;
+
+    match configured {
+        SandboxBackendKind::LinuxNative => SandboxBackendKind::LinuxNative,
+        SandboxBackendKind::LocalProcess | SandboxBackendKind::Docker => {
+            SandboxBackendKind::LocalProcess
+        }
+    }
+}
+
+pub(crate) fn local_execution_capability(
+    backend: chatos_sandbox_contract::SandboxBackendKind,
+) -> chatos_sandbox_contract::SandboxBackendCapability {
+    match local_execution_backend(backend) {
+        chatos_sandbox_contract::SandboxBackendKind::LinuxNative => {
+            linux_native::linux_native_execution_capability()
+        }
+        _ => local_connector_execution_capability(),
+    }
+}
+
 pub(crate) fn local_connector_execution_capability(
 ) -> chatos_sandbox_contract::SandboxBackendCapability {
     use chatos_sandbox_contract::{
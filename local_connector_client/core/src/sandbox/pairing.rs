@@ -4,15 +4,13 @@
 use std::sync::Arc;
 
 use anyhow::{Context, Result};
-use chatos_sandbox_contract::{
-    SandboxBackendCapability, SandboxBackendKind, SandboxBackendReadinessStatus,
-};
+use chatos_sandbox_contract::{SandboxBackendCapability, SandboxBackendReadinessStatus};
 use serde_json::json;
 use tokio::sync::RwLock;
 
 use crate::config::{api_url, ClientConfig};
 use crate::registration::ensure_success;
-use crate::sandbox::local_connector_execution_capability;
+use crate::sandbox::{local_execution_backend, local_execution_capability};
 use crate::LocalState;
 
 pub(crate) async fn reconcile_sandbox_pairings(
@@ -29,9 +27,9 @@ pub(crate) async fn reconcile_sandbox_pairings(
             state.sandbox.effective_policy_defaults(),
         )
     };
-    policy.sandbox_mode = SandboxBackendKind::LocalProcess;
-    let process_capability = local_connector_execution_capability();
-    let readiness = sandbox_pairing_readiness(&process_capability);
+    policy.sandbox_mode = local_execution_backend(policy.sandbox_mode);
+    let capability = local_execution_capability(policy.sandbox_mode);
+    let readiness = sandbox_pairing_readiness(&capability);
     let mut synced = 0;
     for workspace in workspaces {
         let response = client
@@ -75,6 +73,7 @@ fn sandbox_pairing_readiness(process_capability: &SandboxBackendCapability) -> &
 #[cfg(test)]
 mod tests {
     use super::*;
+    use chatos_sandbox_contract::SandboxBackendKind;
 
     fn process_capability(status: SandboxBackendReadinessStatus) -> SandboxBackendCapability {
         SandboxBackendCapability {
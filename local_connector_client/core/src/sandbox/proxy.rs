@@ -70,6 +70,13 @@ pub(crate) async fn proxy_local_sandbox_mcp(
         .headers
         .entry(chatos_mcp_service::LOCAL_CONNECTOR_ENABLED_BUILTIN_KINDS_HEADER.to_string())
         .or_insert_with(|| "CodeMaintainerRead,CodeMaintainerWrite,TerminalController".to_string());
+    if lease.effective_policy.sandbox_mode == SandboxBackendKind::LinuxNative {
+        // Terminal contexts are scoped by this header; pin it so every spawn finds the lease plan.
+        direct_request.headers.insert(
+            crate::sandbox::linux_native::LINUX_NATIVE_RUN_HEADER.to_string(),
+            lease.run_id.clone(),
+        );
+    }
     let result = (
         200,
         BTreeMap::new(),
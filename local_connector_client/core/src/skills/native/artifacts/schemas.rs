@@ -110,7 +110,7 @@ fn spreadsheet_cell_schema() -> Value {
                 "type":"object",
                 "properties":{
                     "value":{"oneOf":[{"type":"null"},{"type":"boolean"},{"type":"number"},{"type":"string","maxLength":32767}]},
-                    "formula":{"type":"string","minLength":1,"maxLength":4096,"description":"Safe local formula, with or without a leading equals sign. Only ABS, AND, AVERAGE, COUNT, COUNTA, IF, MAX, MIN, NOT, OR, ROUND, and SUM are allowed; external links and string literals are rejected."},
+                    "formula":{"type":"string","minLength":1,"maxLength":4096,"description":"Safe local formula, with or without a leading equals sign. Allowed functions are ABS, AND, AVERAGE, CONCATENATE, COUNT, COUNTA, DATE, DAY, IF, IFERROR, INDEX, INT, ISBLANK, ISERROR, ISNUMBER, ISTEXT, LEFT, LEN, LOWER, MATCH, MAX, MID, MIN, MOD, MONTH, NOT, OR, POWER, PRODUCT, RIGHT, ROUND, ROUNDDOWN, ROUNDUP, SQRT, SUM, TRIM, UPPER, VALUE, VLOOKUP, WEEKDAY, and YEAR; string literals and & are allowed, external links are rejected. create_xlsx computes formulas and caches the results; cached_value is only kept for cells it cannot compute."},
                     "cached_value":{"oneOf":[{"type":"null"},{"type":"boolean"},{"type":"number"},{"type":"string","maxLength":32767}]},
                     "number_format":{"type":"string","enum":["general","integer","decimal_2","percent_2","date","datetime"]}
                 },
@@ -53,7 +53,7 @@ fn render_spreadsheet_pages_tool() -> Value {
 fn create_xlsx_tool() -> Value {
     tool(
         "create_xlsx",
-        "Create a bounded XLSX workbook locally with one to 64 worksheets, typed values, safe formulas, built-in number formats, column widths, and frozen header rows.",
+        "Create a bounded XLSX workbook locally with one to 64 worksheets, typed values, safe formulas, built-in number formats, column widths, and frozen header rows. Formulas are computed before writing so the file carries cached results; per-cell Excel errors and circular or unsupported formulas are reported.",
         json!({
             "type":"object",
             "properties":{
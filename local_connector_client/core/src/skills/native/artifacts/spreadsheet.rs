@@ -1,14 +1,15 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs::File;
 use std::path::Path;
 
 use anyhow::{anyhow, Context, Result};
 use quick_xml::events::{BytesEnd, BytesStart, Event};
 use quick_xml::{Reader, Writer, XmlVersion};
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 use zip::ZipArchive;
 
 use crate::relay::RelayRequest;
@@ -19,6 +20,10 @@ use super::{
     safe_workspace_path, MAX_XML_BYTES,
 };
 
+mod xlsx_formula;
+mod xlsx_formula_functions;
+mod xlsx_formula_parse;
+mod xlsx_formula_value;
 mod xlsx_generation;
 mod xlsx_input;
 mod xlsx_inspection;
@@ -26,16 +31,21 @@ mod xlsx_model;
 mod xlsx_package;
 mod xlsx_package_write;
 mod xlsx_rewrite;
+mod xlsx_values;
 
+use xlsx_formula::{calculate_cells, CellKey, WorkbookCalculation};
 use xlsx_input::{parse_cell_rows, validate_sheet_name};
 use xlsx_model::{CellInput, CellValue, NumberFormat};
 use xlsx_package::{
     optional_attribute, read_workbook_parts, required_attribute, validate_xlsx_package,
-    workbook_sheet_parts, workbook_styles_part,
+    workbook_shared_strings_part, workbook_sheet_parts, workbook_styles_part, SheetPart,
 };
 use xlsx_package_write::{ensure_distinct_xlsx_paths, rewrite_xlsx_package, write_new_xlsx};
 use xlsx_rewrite::{event_name, force_formula_recalculation, rewrite_worksheet};
+use xlsx_values::{read_shared_strings, read_worksheet_cells};
 
+#[cfg(test)]
+use xlsx_formula::calculate_workbook;
 #[cfg(test)]
 use xlsx_generation::workbook_entries;
 #[cfg(test)]
@@ -120,10 +130,11 @@ pub(super) fn update_xlsx_range(
     let package_names = validate_xlsx_package(source.as_path())?;
     let (workbook_xml, relationships_xml) = read_workbook_parts(source.as_path())?;
     let sheets = workbook_sheet_parts(workbook_xml.as_str(), relationships_xml.as_str())?;
-    let sheet = sheets
+    let sheet_index = sheets
         .iter()
-        .find(|sheet| sheet.name == sheet_name)
+        .position(|sheet| sheet.name == sheet_name)
         .ok_or_else(|| anyhow!("XLSX worksheet does not exist: {sheet_name}"))?;
+    let sheet = &sheets[sheet_index];
     if !package_names.contains(sheet.path.as_str()) {
         return Err(anyhow!("XLSX is missing worksheet part: {}", sheet.path));
     }
@@ -153,13 +164,26 @@ pub(super) fn update_xlsx_range(
         replacements.insert(styles_path, updated_styles.into_bytes());
         style_ids
     };
-    drop(archive);
-
     let formula_cells = updates
         .values()
         .flat_map(BTreeMap::values)
         .filter(|cell| matches!(cell.value, CellValue::Formula { .. }))
         .count();
+    let calculation = if formula_cells > 0 {
+        Some(calculate_updated_formulas(
+            &mut archive,
+            &package_names,
+            relationships_xml.as_str(),
+            sheets.as_slice(),
+            sheet_index,
+            sheet_xml.as_str(),
+            &mut updates,
+        )?)
+    } else {
+        None
+    };
+    drop(archive);
+
     let updated_sheet = rewrite_worksheet(sheet_xml.as_str(), updates, &style_ids)?;
     replacements.insert(sheet.path.clone(), updated_sheet.into_bytes());
     if formula_cells > 0 {
@@ -190,7 +214,7 @@ pub(super) fn update_xlsx_range(
         &replacements,
         optional_bool(arguments, "overwrite"),
     )?;
-    Ok(json!({
+    let mut response = json!({
         "updated": true,
         "source_path": source_relative,
         "path": target_relative,
@@ -200,7 +224,92 @@ pub(super) fn update_xlsx_range(
         "formula_cells": formula_cells,
         "source_unchanged": true,
         "bytes": bytes,
-    }))
+    });
+    if let (Some(fields), Some(calculation)) = (response.as_object_mut(), calculation) {
+        fields.extend(calculation);
+    }
+    Ok(response)
+}
+
+/// Evaluates the workbook as it will read after the update and stores the results as the
+/// cached values of the updated formula cells. Only those cells are reported; other formulas
+/// keep their cached values until the workbook is recalculated on open.
+fn calculate_updated_formulas(
+    archive: &mut ZipArchive<File>,
+    package_names: &HashSet<String>,
+    relationships_xml: &str,
+    sheets: &[SheetPart],
+    target: usize,
+    target_xml: &str,
+    updates: &mut BTreeMap<u32, BTreeMap<u16, CellInput>>,
+) -> Result<Map<String, Value>> {
+    let shared_strings = match workbook_shared_strings_part(relationships_xml)? {
+        Some(path) if package_names.contains(path.as_str()) => {
+            read_shared_strings(read_zip_text(archive, path.as_str())?.as_str())?
+        }
+        Some(path) => return Err(anyhow!("XLSX is missing shared strings part: {path}")),
+        None => Vec::new(),
+    };
+    let mut cells = BTreeMap::<CellKey, CellValue>::new();
+    for (index, sheet) in sheets.iter().enumerate() {
+        let xml = if index == target {
+            Cow::Borrowed(target_xml)
+        } else {
+            if !package_names.contains(sheet.path.as_str()) {
+                return Err(anyhow!("XLSX is missing worksheet part: {}", sheet.path));
+            }
+            Cow::Owned(read_zip_text(archive, sheet.path.as_str())?)
+        };
+        cells.extend(
+            read_worksheet_cells(xml.as_ref(), shared_strings.as_slice())?
+                .into_iter()
+                .map(|((row, column), value)| ((index, row, column), value)),
+        );
+    }
+    for (row, row_updates) in updates.iter() {
+        for (column, cell) in row_updates {
+            cells.insert((target, *row, *column), cell.value.clone());
+        }
+    }
+
+    let sheet_names = sheets
+        .iter()
+        .map(|sheet| sheet.name.clone())
+        .collect::<Vec<_>>();
+    let (calculation, results) = calculate_cells(
+        sheet_names.as_slice(),
+        cells.iter().map(|(key, value)| (*key, value)),
+    );
+    let updated = |(sheet, row, column): &CellKey| {
+        *sheet == target
+            && updates
+                .get(row)
+                .is_some_and(|cells| cells.contains_key(column))
+    };
+    let mut report = WorkbookCalculation {
+        calculated: 0,
+        errors: calculation
+            .errors
+            .into_iter()
+            .filter(|(key, _)| updated(key))
+            .collect(),
+        uncalculated: calculation
+            .uncalculated
+            .into_iter()
+            .filter(|(key, _)| updated(key))
+            .collect(),
+    };
+    for ((sheet, row, column), value) in results {
+        let cell = updates
+            .get_mut(&row)
+            .and_then(|cells| cells.get_mut(&column))
+            .filter(|_| sheet == target);
+        if let Some(CellValue::Formula { cached_value, .. }) = cell.map(|cell| &mut cell.value) {
+            *cached_value = Some(value);
+            report.calculated += 1;
+        }
+    }
+    Ok(report.report(sheet_names.as_slice()))
 }
 
 fn reject_unsupported_update_intersections(
@@ -518,6 +627,89 @@ mod tests {
         assert!(parse_cell_reference("XFE1").is_err());
     }
 
+    #[test]
+    fn calculates_formulas_across_sheets_in_dependency_order() {
+        assert!(validate_formula("IF(A1>0,\"yes \"&B1,\"no\")").is_ok());
+        assert!(validate_formula("VLOOKUP(\"Pear\",A1:B3,2,FALSE)").is_ok());
+        assert!(validate_formula("CONCAT(A1,B1)").is_err());
+        assert!(validate_formula("\"unterminated").is_err());
+
+        let mut worksheets = parse_worksheets(&json!({
+            "worksheets":[
+                {
+                    "name":"Data",
+                    "rows":[
+                        ["Item","Qty","Price","Total"],
+                        ["Apple",3,1.5,{"formula":"B2*C2"}],
+                        ["Pear",2,0.25,{"formula":"B3*C3"}],
+                        ["Sum",null,null,{"formula":"SUM(D2:D3)*(1+Summary!B1)"}]
+                    ]
+                },
+                {
+                    "name":"Summary",
+                    "rows":[
+                        ["Rate",{"formula":"ROUND(50%/2,2)"}],
+                        [{"formula":"CONCATENATE(\"Total \",Data!D4)"}],
+                        [{"formula":"VLOOKUP(\"pear\",Data!A2:D3,4,FALSE)"}],
+                        [{"formula":"INDEX(Data!A1:D3,MATCH(\"Pear\",Data!A1:A3,0),2)"}],
+                        [{"formula":"1/0"}],
+                        [{"formula":"Missing!A1"}],
+                        [{"formula":"IFERROR(A5,\"n/a\")"}],
+                        [{"formula":"YEAR(DATE(2024,14,1))&\"-\"&WEEKDAY(DATE(2024,1,1))"}],
+                        [{"formula":"A10+1","cached_value":7}],
+                        [{"formula":"A9*2"}],
+                        [{"formula":"-2^2"}],
+                        [{"formula":"LEFT(UPPER(TRIM(\"  big   cat \")),5)"}]
+                    ]
+                }
+            ]
+        }))
+        .expect("worksheets");
+        let calculation = calculate_workbook(worksheets.as_mut_slice());
+        let cached = |sheet: usize, row: usize, column: usize| match &worksheets[sheet].rows[row]
+            [column]
+            .value
+        {
+            CellValue::Formula { cached_value, .. } => format!("{cached_value:?}"),
+            CellValue::Primitive(_) => panic!("expected a formula cell"),
+        };
+        assert_eq!(cached(0, 1, 3), "Some(Number(\"4.5\"))");
+        assert_eq!(cached(0, 3, 3), "Some(Number(\"6.25\"))");
+        assert_eq!(cached(1, 0, 1), "Some(Number(\"0.25\"))");
+        assert_eq!(cached(1, 1, 0), "Some(Text(\"Total 6.25\"))");
+        assert_eq!(cached(1, 2, 0), "Some(Number(\"0.5\"))");
+        assert_eq!(cached(1, 3, 0), "Some(Number(\"2\"))");
+        assert_eq!(cached(1, 4, 0), "Some(Error(DivisionByZero))");
+        assert_eq!(cached(1, 5, 0), "Some(Error(Reference))");
+        assert_eq!(cached(1, 6, 0), "Some(Text(\"n/a\"))");
+        assert_eq!(cached(1, 7, 0), "Some(Text(\"2025-2\"))");
+        assert_eq!(cached(1, 8, 0), "Some(Number(\"7\"))");
+        assert_eq!(cached(1, 9, 0), "None");
+        assert_eq!(cached(1, 10, 0), "Some(Number(\"4\"))");
+        assert_eq!(cached(1, 11, 0), "Some(Text(\"BIG C\"))");
+
+        let sheet_names = worksheets
+            .iter()
+            .map(|sheet| sheet.name.clone())
+            .collect::<Vec<_>>();
+        let report = calculation.report(sheet_names.as_slice());
+        assert_eq!(report["calculated_formula_cells"], json!(13));
+        assert_eq!(
+            report["formula_errors"],
+            json!([
+                {"sheet_name":"Summary","cell":"A5","error":"#DIV/0!"},
+                {"sheet_name":"Summary","cell":"A6","error":"#REF!"}
+            ])
+        );
+        assert_eq!(
+            report["uncalculated_formulas"],
+            json!([
+                {"sheet_name":"Summary","cell":"A9","reason":"circular_reference"},
+                {"sheet_name":"Summary","cell":"A10","reason":"circular_reference"}
+            ])
+        );
+    }
+
     #[test]
     fn rewrites_missing_rows_and_cells_without_touching_unrelated_cells() {
         let xml = r#"<?xml version="1.0"?><worksheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><dimension ref="A1:A3"/><sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>keep</t></is></c></row><row r="3"><c r="A3"><v>3</v></c></row></sheetData></worksheet>"#;
@@ -560,6 +752,41 @@ mod tests {
             .contains("shared"));
     }
 
+    #[test]
+    fn reads_existing_worksheet_values_for_update_calculation() {
+        let shared = read_shared_strings(
+            r#"<sst><si><t>Units</t></si><si><r><t>Net </t></r><r><t>&amp; gross</t></r><rPh><t>ignored</t></rPh></si></sst>"#,
+        )
+        .expect("shared strings");
+        assert_eq!(shared, ["Units", "Net & gross"]);
+        let cells = read_worksheet_cells(
+            r#"<worksheet><sheetData><row r="2"><c t="s"><v>1</v></c><c><v>4.5</v></c><c t="b"><v>1</v></c></row><row><c r="B3" t="e"><v>#N/A</v></c><c t="inlineStr"><is><t>inline</t></is></c><c r="E3"><f t="shared" si="0"/><v>9</v></c><c r="F3" s="1"/></row></sheetData></worksheet>"#,
+            shared.as_slice(),
+        )
+        .expect("worksheet cells");
+        let summary = cells
+            .iter()
+            .map(|((row, column), value)| {
+                let value = match value {
+                    CellValue::Primitive(value) => format!("{value:?}"),
+                    CellValue::Formula { expression, .. } => format!("={expression}"),
+                };
+                (cell_reference(*column, *row), value)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            [
+                ("A2".to_string(), "Text(\"Net & gross\")".to_string()),
+                ("B2".to_string(), "Number(\"4.5\")".to_string()),
+                ("C2".to_string(), "Bool(true)".to_string()),
+                ("B3".to_string(), "Error(NotAvailable)".to_string()),
+                ("C3".to_string(), "Text(\"inline\")".to_string()),
+                ("E3".to_string(), "=".to_string()),
+            ]
+        );
+    }
+
     #[test]
     fn render_validation_rejects_active_external_and_network_formula_content() {
         let worksheets = parse_worksheets(&json!({
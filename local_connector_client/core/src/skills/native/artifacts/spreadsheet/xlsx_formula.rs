@@ -0,0 +1,425 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+use serde_json::{json, Map, Value};
+
+use super::cell_reference;
+use super::xlsx_formula_functions::{call_function, power, text_value};
+use super::xlsx_formula_parse::{parse_formula, BinaryOperator, FormulaExpression};
+use super::xlsx_formula_value::{compare_values, FormulaArgument, FormulaValue, RangeValues};
+use super::xlsx_model::{CellValue, FormulaError, PrimitiveCellValue, WorksheetInput};
+
+const MAX_REPORTED_FORMULA_CELLS: usize = 100;
+
+/// (worksheet index, row, column), ordered row-major within a worksheet so rectangular ranges
+/// can be scanned with one `BTreeMap::range` call.
+pub(super) type CellKey = (usize, u32, u16);
+
+/// Why a formula cell keeps the caller's `cached_value` instead of a computed one.
+#[derive(Clone, Debug)]
+pub(super) enum UncalculatedReason {
+    /// The formula is in or downstream of a reference cycle.
+    CircularReference,
+    /// The formula passed the safety allowlist but uses syntax the evaluator does not model.
+    Unsupported(String),
+    /// A precedent could not be calculated, so this result would be unreliable.
+    UncalculatedPrecedent,
+}
+
+#[derive(Debug, Default)]
+pub(super) struct WorkbookCalculation {
+    pub(super) calculated: usize,
+    pub(super) errors: Vec<(CellKey, FormulaError)>,
+    pub(super) uncalculated: Vec<(CellKey, UncalculatedReason)>,
+}
+
+impl WorkbookCalculation {
+    /// Summary fields merged into the `create_xlsx` and `update_xlsx_range` responses. Cell
+    /// lists are capped; the counts are exact.
+    pub(super) fn report(&self, sheet_names: &[String]) -> Map<String, Value> {
+        let cell = |(sheet, row, column): &CellKey| {
+            json!({
+                "sheet_name": sheet_names[*sheet],
+                "cell": cell_reference(*column, *row),
+            })
+        };
+        let errors = self
+            .errors
+            .iter()
+            .take(MAX_REPORTED_FORMULA_CELLS)
+            .map(|(key, error)| {
+                let mut entry = cell(key);
+                entry["error"] = json!(error.code());
+                entry
+            })
+            .collect::<Vec<_>>();
+        let uncalculated = self
+            .uncalculated
+            .iter()
+            .take(MAX_REPORTED_FORMULA_CELLS)
+            .map(|(key, reason)| {
+                let mut entry = cell(key);
+                match reason {
+                    UncalculatedReason::CircularReference => {
+                        entry["reason"] = json!("circular_reference");
+                    }
+                    UncalculatedReason::Unsupported(detail) => {
+                        entry["reason"] = json!("unsupported_formula");
+                        entry["detail"] = json!(detail);
+                    }
+                    UncalculatedReason::UncalculatedPrecedent => {
+                        entry["reason"] = json!("uncalculated_precedent");
+                    }
+                }
+                entry
+            })
+            .collect::<Vec<_>>();
+        Map::from_iter([
+            (
+                "calculated_formula_cells".to_string(),
+                json!(self.calculated),
+            ),
+            ("formula_error_cells".to_string(), json!(self.errors.len())),
+            ("formula_errors".to_string(), json!(errors)),
+            (
+                "uncalculated_formula_cells".to_string(),
+                json!(self.uncalculated.len()),
+            ),
+            ("uncalculated_formulas".to_string(), json!(uncalculated)),
+        ])
+    }
+}
+
+/// Computes every formula in the workbook and stores the results as cached values.
+///
+/// Formulas are evaluated in dependency order (Kahn's algorithm over formula-to-formula
+/// references, including references through ranges and other worksheets). Cells left over once
+/// no formula is ready are on or behind a cycle; they and any cell the evaluator cannot parse
+/// keep whatever `cached_value` the caller supplied. Excel error results such as #DIV/0! are
+/// written as error cells and reported.
+pub(super) fn calculate_workbook(worksheets: &mut [WorksheetInput]) -> WorkbookCalculation {
+    let sheet_names = worksheets
+        .iter()
+        .map(|sheet| sheet.name.clone())
+        .collect::<Vec<_>>();
+    let cells = worksheets
+        .iter()
+        .enumerate()
+        .flat_map(|(sheet_index, sheet)| {
+            sheet
+                .rows
+                .iter()
+                .enumerate()
+                .flat_map(move |(row_index, row)| {
+                    row.iter().enumerate().map(move |(column_index, cell)| {
+                        (
+                            (sheet_index, row_index as u32 + 1, column_index as u16 + 1),
+                            &cell.value,
+                        )
+                    })
+                })
+        });
+    let (calculation, results) = calculate_cells(sheet_names.as_slice(), cells);
+    for ((sheet, row, column), value) in results {
+        if let CellValue::Formula { cached_value, .. } =
+            &mut worksheets[sheet].rows[row as usize - 1][column as usize - 1].value
+        {
+            *cached_value = Some(value);
+        }
+    }
+    calculation
+}
+
+/// Calculates the formulas among `cells`, which may be sparse, the same way as
+/// `calculate_workbook`. Returns the cached value of every calculated formula; the caller decides
+/// which of those cells it writes.
+pub(super) fn calculate_cells<'a>(
+    sheet_names: &[String],
+    cells: impl IntoIterator<Item = (CellKey, &'a CellValue)>,
+) -> (WorkbookCalculation, Vec<(CellKey, PrimitiveCellValue)>) {
+    let mut values = BTreeMap::<CellKey, FormulaValue>::new();
+    let mut formulas = BTreeMap::<CellKey, Result<FormulaExpression, String>>::new();
+    for (key, value) in cells {
+        match value {
+            CellValue::Primitive(value) => match FormulaValue::from_cell(value) {
+                FormulaValue::Blank => {}
+                value => {
+                    values.insert(key, value);
+                }
+            },
+            CellValue::Formula { expression, .. } => {
+                formulas.insert(
+                    key,
+                    parse_formula(expression).map_err(|err| err.to_string()),
+                );
+            }
+        }
+    }
+    if formulas.is_empty() {
+        return (WorkbookCalculation::default(), Vec::new());
+    }
+
+    let keys = formulas.keys().copied().collect::<Vec<_>>();
+    let positions = keys
+        .iter()
+        .enumerate()
+        .map(|(position, key)| (*key, position))
+        .collect::<BTreeMap<_, _>>();
+    let mut precedents = vec![BTreeSet::<usize>::new(); keys.len()];
+    let mut dependents = vec![Vec::<usize>::new(); keys.len()];
+    for (position, key) in keys.iter().enumerate() {
+        if let Ok(expression) = &formulas[key] {
+            collect_precedents(
+                expression,
+                key.0,
+                sheet_names,
+                &positions,
+                &mut precedents[position],
+            );
+        }
+        for precedent in &precedents[position] {
+            dependents[*precedent].push(position);
+        }
+    }
+
+    let mut waiting = precedents.iter().map(BTreeSet::len).collect::<Vec<_>>();
+    let mut ready = (0..keys.len())
+        .filter(|position| waiting[*position] == 0)
+        .collect::<VecDeque<_>>();
+    let mut outcomes = BTreeMap::<CellKey, Result<FormulaValue, UncalculatedReason>>::new();
+    while let Some(position) = ready.pop_front() {
+        let key = keys[position];
+        let outcome = match &formulas[&key] {
+            Err(detail) => Err(UncalculatedReason::Unsupported(detail.clone())),
+            Ok(_)
+                if precedents[position]
+                    .iter()
+                    .any(|precedent| matches!(outcomes.get(&keys[*precedent]), Some(Err(_)))) =>
+            {
+                Err(UncalculatedReason::UncalculatedPrecedent)
+            }
+            Ok(expression) => {
+                let context = FormulaContext {
+                    sheet: key.0,
+                    sheet_names,
+                    values: &values,
+                };
+                let value = match context.evaluate(expression) {
+                    FormulaValue::Blank => FormulaValue::Number(0.0),
+                    value => value,
+                };
+                values.insert(key, value.clone());
+                Ok(value)
+            }
+        };
+        outcomes.insert(key, outcome);
+        for dependent in &dependents[position] {
+            waiting[*dependent] -= 1;
+            if waiting[*dependent] == 0 {
+                ready.push_back(*dependent);
+            }
+        }
+    }
+
+    let mut calculation = WorkbookCalculation::default();
+    let mut results = Vec::new();
+    for key in &keys {
+        match outcomes.remove(key) {
+            Some(Ok(value)) => {
+                if let FormulaValue::Error(error) = value {
+                    calculation.errors.push((*key, error));
+                }
+                calculation.calculated += 1;
+                results.push((*key, value.into_cached_value()));
+            }
+            Some(Err(reason)) => calculation.uncalculated.push((*key, reason)),
+            None => calculation
+                .uncalculated
+                .push((*key, UncalculatedReason::CircularReference)),
+        }
+    }
+    (calculation, results)
+}
+
+fn collect_precedents(
+    expression: &FormulaExpression,
+    sheet: usize,
+    sheet_names: &[String],
+    positions: &BTreeMap<CellKey, usize>,
+    precedents: &mut BTreeSet<usize>,
+) {
+    match expression {
+        FormulaExpression::Cell(target) => {
+            if let Some(sheet) = resolve_sheet(sheet, target.sheet.as_deref(), sheet_names) {
+                if let Some(position) = positions.get(&(sheet, target.row, target.column)) {
+                    precedents.insert(*position);
+                }
+            }
+        }
+        FormulaExpression::Range(target) => {
+            if let Some(sheet) = resolve_sheet(sheet, target.sheet.as_deref(), sheet_names) {
+                let (start_column, start_row) = target.start;
+                let (end_column, end_row) = target.end;
+                precedents.extend(
+                    positions
+                        .range((sheet, start_row, start_column)..=(sheet, end_row, end_column))
+                        .filter(|((_, _, column), _)| (start_column..=end_column).contains(column))
+                        .map(|(_, position)| *position),
+                );
+            }
+        }
+        FormulaExpression::Negate(inner) | FormulaExpression::Percent(inner) => {
+            collect_precedents(inner, sheet, sheet_names, positions, precedents);
+        }
+        FormulaExpression::Binary(_, left, right) => {
+            collect_precedents(left, sheet, sheet_names, positions, precedents);
+            collect_precedents(right, sheet, sheet_names, positions, precedents);
+        }
+        FormulaExpression::Call(_, arguments) => {
+            for argument in arguments {
+                collect_precedents(argument, sheet, sheet_names, positions, precedents);
+            }
+        }
+        FormulaExpression::Number(_)
+        | FormulaExpression::Text(_)
+        | FormulaExpression::Bool(_)
+        | FormulaExpression::Missing => {}
+    }
+}
+
+/// Worksheet names compare case-insensitively, as in Excel. Unknown names evaluate to #REF!.
+fn resolve_sheet(current: usize, name: Option<&str>, sheet_names: &[String]) -> Option<usize> {
+    match name {
+        None => Some(current),
+        Some(name) => {
+            let name = name.to_lowercase();
+            sheet_names
+                .iter()
+                .position(|candidate| candidate.to_lowercase() == name)
+        }
+    }
+}
+
+/// Read-only view of the workbook while one formula is evaluated.
+pub(super) struct FormulaContext<'a> {
+    sheet: usize,
+    sheet_names: &'a [String],
+    values: &'a BTreeMap<CellKey, FormulaValue>,
+}
+
+impl FormulaContext<'_> {
+    pub(super) fn evaluate(&self, expression: &FormulaExpression) -> FormulaValue {
+        match expression {
+            FormulaExpression::Number(value) => FormulaValue::Number(*value),
+            FormulaExpression::Text(value) => FormulaValue::Text(value.clone()),
+            FormulaExpression::Bool(value) => FormulaValue::Bool(*value),
+            FormulaExpression::Missing => FormulaValue::Blank,
+            FormulaExpression::Cell(target) => {
+                match resolve_sheet(self.sheet, target.sheet.as_deref(), self.sheet_names) {
+                    Some(sheet) => self
+                        .values
+                        .get(&(sheet, target.row, target.column))
+                        .cloned()
+                        .unwrap_or(FormulaValue::Blank),
+                    None => FormulaValue::Error(FormulaError::Reference),
+                }
+            }
+            // Ranges only have a scalar value when they cover a single cell; Excel's implicit
+            // intersection is not modeled.
+            FormulaExpression::Range(_) => match self.argument(expression) {
+                FormulaArgument::Range(range) if range.rows == 1 && range.columns == 1 => {
+                    range.get(0, 0)
+                }
+                FormulaArgument::Range(_) => FormulaValue::Error(FormulaError::Value),
+                FormulaArgument::Value(value) => value,
+            },
+            FormulaExpression::Negate(inner) => match self.evaluate(inner).to_number() {
+                Ok(value) => FormulaValue::number(-value),
+                Err(error) => FormulaValue::Error(error),
+            },
+            FormulaExpression::Percent(inner) => match self.evaluate(inner).to_number() {
+                Ok(value) => FormulaValue::number(value / 100.0),
+                Err(error) => FormulaValue::Error(error),
+            },
+            FormulaExpression::Binary(operator, left, right) => {
+                binary_operation(*operator, self.evaluate(left), self.evaluate(right))
+                    .unwrap_or_else(FormulaValue::Error)
+            }
+            FormulaExpression::Call(name, arguments) => call_function(self, name, arguments),
+        }
+    }
+
+    /// Evaluates a function argument, keeping cell and range references as ranges so
+    /// aggregates and lookups can tell referenced cells from literal values.
+    pub(super) fn argument(&self, expression: &FormulaExpression) -> FormulaArgument {
+        let (sheet, start, end) = match expression {
+            FormulaExpression::Cell(target) => (
+                target.sheet.as_deref(),
+                (target.column, target.row),
+                (target.column, target.row),
+            ),
+            FormulaExpression::Range(target) => (target.sheet.as_deref(), target.start, target.end),
+            other => return FormulaArgument::Value(self.evaluate(other)),
+        };
+        let Some(sheet) = resolve_sheet(self.sheet, sheet, self.sheet_names) else {
+            return FormulaArgument::Value(FormulaValue::Error(FormulaError::Reference));
+        };
+        let (start_column, start_row) = start;
+        let (end_column, end_row) = end;
+        let cells = self
+            .values
+            .range((sheet, start_row, start_column)..=(sheet, end_row, end_column))
+            .filter(|((_, _, column), _)| (start_column..=end_column).contains(column))
+            .map(|((_, row, column), value)| {
+                ((row - start_row, column - start_column), value.clone())
+            })
+            .collect();
+        FormulaArgument::Range(RangeValues {
+            rows: end_row - start_row + 1,
+            columns: end_column - start_column + 1,
+            cells,
+        })
+    }
+}
+
+fn binary_operation(
+    operator: BinaryOperator,
+    left: FormulaValue,
+    right: FormulaValue,
+) -> Result<FormulaValue, FormulaError> {
+    let arithmetic = |apply: fn(f64, f64) -> Result<f64, FormulaError>| {
+        let (left, right) = (left.to_number()?, right.to_number()?);
+        apply(left, right).map(FormulaValue::number)
+    };
+    match operator {
+        BinaryOperator::Add => arithmetic(|left, right| Ok(left + right)),
+        BinaryOperator::Subtract => arithmetic(|left, right| Ok(left - right)),
+        BinaryOperator::Multiply => arithmetic(|left, right| Ok(left * right)),
+        BinaryOperator::Divide => arithmetic(|left, right| {
+            if right == 0.0 {
+                Err(FormulaError::DivisionByZero)
+            } else {
+                Ok(left / right)
+            }
+        }),
+        BinaryOperator::Power => power(left.to_number()?, right.to_number()?),
+        BinaryOperator::Concatenate => {
+            let mut text = left.to_text()?;
+            text.push_str(right.to_text()?.as_str());
+            text_value(text)
+        }
+        comparison => {
+            let ordering = compare_values(&left, &right)?;
+            Ok(FormulaValue::Bool(match comparison {
+                BinaryOperator::Equal => ordering.is_eq(),
+                BinaryOperator::NotEqual => ordering.is_ne(),
+                BinaryOperator::Less => ordering.is_lt(),
+                BinaryOperator::LessOrEqual => ordering.is_le(),
+                BinaryOperator::Greater => ordering.is_gt(),
+                _ => ordering.is_ge(),
+            }))
+        }
+    }
+}
@@ -0,0 +1,463 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::cmp::Ordering;
+
+use super::xlsx_formula::FormulaContext;
+use super::xlsx_formula_parse::FormulaExpression;
+use super::xlsx_formula_value::{
+    compare_values, date_serial, round_significant, serial_date, FormulaArgument, FormulaValue,
+    RangeValues,
+};
+use super::xlsx_model::FormulaError;
+use super::MAX_CELL_TEXT_CHARS;
+
+const MAX_FUNCTION_ARGUMENTS: usize = 255;
+
+type FunctionResult = Result<FormulaValue, FormulaError>;
+
+#[derive(Clone, Copy)]
+enum Rounding {
+    Nearest,
+    Up,
+    Down,
+}
+
+/// Accepted argument counts for every function in the artifact formula allowlist.
+pub(super) fn function_arity(name: &str) -> Option<(usize, usize)> {
+    Some(match name {
+        "AND" | "AVERAGE" | "CONCATENATE" | "COUNT" | "COUNTA" | "MAX" | "MIN" | "OR"
+        | "PRODUCT" | "SUM" => (1, MAX_FUNCTION_ARGUMENTS),
+        "ABS" | "DAY" | "INT" | "ISBLANK" | "ISERROR" | "ISNUMBER" | "ISTEXT" | "LEN" | "LOWER"
+        | "MONTH" | "NOT" | "SQRT" | "TRIM" | "UPPER" | "VALUE" | "YEAR" => (1, 1),
+        "IFERROR" | "MOD" | "POWER" | "ROUND" | "ROUNDDOWN" | "ROUNDUP" => (2, 2),
+        "LEFT" | "RIGHT" | "WEEKDAY" => (1, 2),
+        "IF" | "INDEX" | "MATCH" => (2, 3),
+        "DATE" | "MID" => (3, 3),
+        "VLOOKUP" => (3, 4),
+        _ => return None,
+    })
+}
+
+pub(super) fn call_function(
+    context: &FormulaContext<'_>,
+    name: &str,
+    arguments: &[FormulaExpression],
+) -> FormulaValue {
+    evaluate_function(context, name, arguments).unwrap_or_else(FormulaValue::Error)
+}
+
+fn evaluate_function(
+    context: &FormulaContext<'_>,
+    name: &str,
+    arguments: &[FormulaExpression],
+) -> FunctionResult {
+    let scalar = |index: usize| context.evaluate(&arguments[index]);
+    let number = |index: usize| scalar(index).to_number();
+    let text = |index: usize| scalar(index).to_text();
+    let optional_number = |index: usize, default: f64| {
+        if index < arguments.len() {
+            number(index)
+        } else {
+            Ok(default)
+        }
+    };
+    match name {
+        "IF" => {
+            let branch = if scalar(0).to_bool()? {
+                Some(1)
+            } else {
+                (arguments.len() > 2).then_some(2)
+            };
+            Ok(branch.map_or(FormulaValue::Bool(false), scalar))
+        }
+        "IFERROR" => Ok(match scalar(0) {
+            FormulaValue::Error(_) => scalar(1),
+            value => value,
+        }),
+        "AND" | "OR" => {
+            let values = logical_values(context, arguments)?;
+            if values.is_empty() {
+                return Err(FormulaError::Value);
+            }
+            Ok(FormulaValue::Bool(if name == "AND" {
+                values.iter().all(|value| *value)
+            } else {
+                values.iter().any(|value| *value)
+            }))
+        }
+        "NOT" => Ok(FormulaValue::Bool(!scalar(0).to_bool()?)),
+        "SUM" => Ok(FormulaValue::number(
+            numeric_values(context, arguments)?.iter().sum(),
+        )),
+        "PRODUCT" => {
+            let values = numeric_values(context, arguments)?;
+            Ok(FormulaValue::number(if values.is_empty() {
+                0.0
+            } else {
+                values.iter().product()
+            }))
+        }
+        "AVERAGE" => {
+            let values = numeric_values(context, arguments)?;
+            if values.is_empty() {
+                return Err(FormulaError::DivisionByZero);
+            }
+            Ok(FormulaValue::number(
+                values.iter().sum::<f64>() / values.len() as f64,
+            ))
+        }
+        "MIN" | "MAX" => {
+            let values = numeric_values(context, arguments)?;
+            let extreme = if name == "MIN" {
+                values.iter().copied().reduce(f64::min)
+            } else {
+                values.iter().copied().reduce(f64::max)
+            };
+            Ok(FormulaValue::number(extreme.unwrap_or(0.0)))
+        }
+        "COUNT" => Ok(FormulaValue::Number(count(context, arguments, false) as f64)),
+        "COUNTA" => Ok(FormulaValue::Number(count(context, arguments, true) as f64)),
+        "ABS" => Ok(FormulaValue::number(number(0)?.abs())),
+        "INT" => Ok(FormulaValue::number(number(0)?.floor())),
+        "SQRT" => {
+            let value = number(0)?;
+            if value < 0.0 {
+                return Err(FormulaError::Number);
+            }
+            Ok(FormulaValue::number(value.sqrt()))
+        }
+        "MOD" => {
+            let (value, divisor) = (number(0)?, number(1)?);
+            if divisor == 0.0 {
+                return Err(FormulaError::DivisionByZero);
+            }
+            Ok(FormulaValue::number(
+                value - divisor * (value / divisor).floor(),
+            ))
+        }
+        "POWER" => power(number(0)?, number(1)?),
+        "ROUND" => round(number(0)?, number(1)?, Rounding::Nearest),
+        "ROUNDUP" => round(number(0)?, number(1)?, Rounding::Up),
+        "ROUNDDOWN" => round(number(0)?, number(1)?, Rounding::Down),
+        "CONCATENATE" => {
+            let mut output = String::new();
+            for index in 0..arguments.len() {
+                output.push_str(text(index)?.as_str());
+            }
+            text_value(output)
+        }
+        "LEFT" | "RIGHT" => {
+            let value = text(0)?;
+            let count = character_count(optional_number(1, 1.0)?)?;
+            let length = value.chars().count();
+            Ok(FormulaValue::Text(if name == "LEFT" {
+                value.chars().take(count).collect()
+            } else {
+                value.chars().skip(length.saturating_sub(count)).collect()
+            }))
+        }
+        "MID" => {
+            let value = text(0)?;
+            let start = number(1)?.trunc();
+            if start < 1.0 {
+                return Err(FormulaError::Value);
+            }
+            let count = character_count(number(2)?)?;
+            Ok(FormulaValue::Text(
+                value
+                    .chars()
+                    .skip((start as usize).saturating_sub(1))
+                    .take(count)
+                    .collect(),
+            ))
+        }
+        "LEN" => Ok(FormulaValue::Number(text(0)?.chars().count() as f64)),
+        "UPPER" => Ok(FormulaValue::Text(text(0)?.to_uppercase())),
+        "LOWER" => Ok(FormulaValue::Text(text(0)?.to_lowercase())),
+        "TRIM" => Ok(FormulaValue::Text(
+            text(0)?
+                .split(' ')
+                .filter(|word| !word.is_empty())
+                .collect::<Vec<_>>()
+                .join(" "),
+        )),
+        "VALUE" => match scalar(0) {
+            FormulaValue::Bool(_) => Err(FormulaError::Value),
+            value => Ok(FormulaValue::number(value.to_number()?)),
+        },
+        "DATE" => Ok(FormulaValue::Number(date_serial(
+            number(0)?,
+            number(1)?,
+            number(2)?,
+        )?)),
+        "YEAR" | "MONTH" | "DAY" => {
+            let (year, month, day) = serial_date(number(0)?)?;
+            Ok(FormulaValue::Number(match name {
+                "YEAR" => f64::from(year),
+                "MONTH" => f64::from(month),
+                _ => f64::from(day),
+            }))
+        }
+        "WEEKDAY" => {
+            let serial = number(0)?;
+            serial_date(serial)?;
+            let serial = serial.floor() as i64;
+            Ok(FormulaValue::Number(
+                match optional_number(1, 1.0)?.trunc() as i64 {
+                    1 => (serial - 1).rem_euclid(7) + 1,
+                    2 => (serial - 2).rem_euclid(7) + 1,
+                    3 => (serial - 2).rem_euclid(7),
+                    _ => return Err(FormulaError::Number),
+                } as f64,
+            ))
+        }
+        "ISBLANK" => Ok(FormulaValue::Bool(scalar(0) == FormulaValue::Blank)),
+        "ISERROR" => Ok(FormulaValue::Bool(matches!(
+            scalar(0),
+            FormulaValue::Error(_)
+        ))),
+        "ISNUMBER" => Ok(FormulaValue::Bool(matches!(
+            scalar(0),
+            FormulaValue::Number(_)
+        ))),
+        "ISTEXT" => Ok(FormulaValue::Bool(matches!(
+            scalar(0),
+            FormulaValue::Text(_)
+        ))),
+        "VLOOKUP" => {
+            let lookup = lookup_value(scalar(0))?;
+            let table = range_argument(context, &arguments[1])?;
+            let column = number(2)?.trunc();
+            if column < 1.0 {
+                return Err(FormulaError::Value);
+            }
+            if column > f64::from(table.columns) {
+                return Err(FormulaError::Reference);
+            }
+            let approximate = arguments.len() < 4 || scalar(3).to_bool()?;
+            let keys = table
+                .cells
+                .iter()
+                .filter(|((_, column), _)| *column == 0)
+                .map(|((row, _), value)| (*row, value));
+            let row = if approximate {
+                approximate_match(&lookup, keys, Ordering::Greater)?
+            } else {
+                exact_match(&lookup, keys)?
+            };
+            Ok(table.get(row, column as u16 - 1))
+        }
+        "INDEX" => {
+            let table = range_argument(context, &arguments[0])?;
+            let row = number(1)?.trunc();
+            let column = optional_number(2, 0.0)?.trunc();
+            if row < 0.0 || column < 0.0 {
+                return Err(FormulaError::Value);
+            }
+            let (row, column) = if arguments.len() < 3 && table.rows == 1 {
+                (1.0, row)
+            } else if arguments.len() < 3 && table.columns == 1 {
+                (row, 1.0)
+            } else {
+                (row, column)
+            };
+            let row = index_position(row, table.rows)?;
+            let column = index_position(column, u32::from(table.columns))?;
+            Ok(table.get(row, column as u16))
+        }
+        "MATCH" => {
+            let lookup = lookup_value(scalar(0))?;
+            let table = range_argument(context, &arguments[1])?;
+            let vector = table.vector().ok_or(FormulaError::NotAvailable)?;
+            let match_type = optional_number(2, 1.0)?;
+            let entries = vector.into_iter();
+            let position = if match_type == 0.0 {
+                exact_match(&lookup, entries)?
+            } else if match_type > 0.0 {
+                approximate_match(&lookup, entries, Ordering::Greater)?
+            } else {
+                approximate_match(&lookup, entries, Ordering::Less)?
+            };
+            Ok(FormulaValue::Number(f64::from(position) + 1.0))
+        }
+        _ => Err(FormulaError::Name),
+    }
+}
+
+/// Numbers for SUM-style aggregates. References contribute only their numeric cells; values
+/// typed directly as arguments are coerced, so `SUM("3")` is 3 and `SUM("x")` is #VALUE!.
+fn numeric_values(
+    context: &FormulaContext<'_>,
+    arguments: &[FormulaExpression],
+) -> Result<Vec<f64>, FormulaError> {
+    let mut values = Vec::new();
+    for argument in arguments {
+        match context.argument(argument) {
+            FormulaArgument::Range(range) => {
+                for value in range.cells.values() {
+                    match value {
+                        FormulaValue::Number(value) => values.push(*value),
+                        FormulaValue::Error(error) => return Err(*error),
+                        _ => {}
+                    }
+                }
+            }
+            FormulaArgument::Value(value) => values.push(value.to_number()?),
+        }
+    }
+    Ok(values)
+}
+
+fn logical_values(
+    context: &FormulaContext<'_>,
+    arguments: &[FormulaExpression],
+) -> Result<Vec<bool>, FormulaError> {
+    let mut values = Vec::new();
+    for argument in arguments {
+        match context.argument(argument) {
+            FormulaArgument::Range(range) => {
+                for value in range.cells.values() {
+                    match value {
+                        FormulaValue::Bool(_) | FormulaValue::Number(_) => {
+                            values.push(value.to_bool()?)
+                        }
+                        FormulaValue::Error(error) => return Err(*error),
+                        _ => {}
+                    }
+                }
+            }
+            FormulaArgument::Value(value) => values.push(value.to_bool()?),
+        }
+    }
+    Ok(values)
+}
+
+fn count(context: &FormulaContext<'_>, arguments: &[FormulaExpression], non_blank: bool) -> usize {
+    arguments
+        .iter()
+        .map(|argument| match context.argument(argument) {
+            FormulaArgument::Range(range) => range
+                .cells
+                .values()
+                .filter(|value| non_blank || matches!(value, FormulaValue::Number(_)))
+                .count(),
+            FormulaArgument::Value(FormulaValue::Blank) => 0,
+            FormulaArgument::Value(FormulaValue::Error(_)) => usize::from(non_blank),
+            FormulaArgument::Value(value) => usize::from(non_blank || value.to_number().is_ok()),
+        })
+        .sum()
+}
+
+pub(super) fn power(base: f64, exponent: f64) -> FunctionResult {
+    if base == 0.0 && exponent == 0.0 {
+        return Err(FormulaError::Number);
+    }
+    if base == 0.0 && exponent < 0.0 {
+        return Err(FormulaError::DivisionByZero);
+    }
+    let value = base.powf(exponent);
+    if value.is_nan() {
+        return Err(FormulaError::Number);
+    }
+    Ok(FormulaValue::number(value))
+}
+
+fn round(value: f64, digits: f64, rounding: Rounding) -> FunctionResult {
+    let factor = 10f64.powi(digits.trunc().clamp(-308.0, 308.0) as i32);
+    let scaled = value * factor;
+    if !scaled.is_finite() || factor == 0.0 {
+        return Ok(FormulaValue::number(value));
+    }
+    // Round at Excel's 15 significant digits first so ROUND(2.675, 2) is 2.68, not 2.67.
+    let scaled = round_significant(scaled);
+    let rounded = match rounding {
+        Rounding::Nearest => scaled.round(),
+        Rounding::Up => scaled.abs().ceil() * scaled.signum(),
+        Rounding::Down => scaled.trunc(),
+    };
+    Ok(FormulaValue::number(rounded / factor))
+}
+
+pub(super) fn text_value(value: String) -> FunctionResult {
+    if value.chars().count() > MAX_CELL_TEXT_CHARS {
+        return Err(FormulaError::Value);
+    }
+    Ok(FormulaValue::Text(value))
+}
+
+fn character_count(value: f64) -> Result<usize, FormulaError> {
+    if value < 0.0 {
+        return Err(FormulaError::Value);
+    }
+    Ok(value.trunc().min(MAX_CELL_TEXT_CHARS as f64) as usize)
+}
+
+fn lookup_value(value: FormulaValue) -> FunctionResult {
+    match value {
+        FormulaValue::Error(error) => Err(error),
+        value => Ok(value),
+    }
+}
+
+fn range_argument(
+    context: &FormulaContext<'_>,
+    expression: &FormulaExpression,
+) -> Result<RangeValues, FormulaError> {
+    match context.argument(expression) {
+        FormulaArgument::Range(range) => Ok(range),
+        FormulaArgument::Value(FormulaValue::Error(error)) => Err(error),
+        FormulaArgument::Value(_) => Err(FormulaError::NotAvailable),
+    }
+}
+
+fn index_position(index: f64, length: u32) -> Result<u32, FormulaError> {
+    let index = if index == 0.0 && length == 1 {
+        1.0
+    } else {
+        index
+    };
+    if index == 0.0 {
+        return Err(FormulaError::Value);
+    }
+    if index > f64::from(length) {
+        return Err(FormulaError::Reference);
+    }
+    Ok(index as u32 - 1)
+}
+
+fn same_kind(left: &FormulaValue, right: &FormulaValue) -> bool {
+    std::mem::discriminant(left) == std::mem::discriminant(right)
+}
+
+fn exact_match<'v>(
+    lookup: &FormulaValue,
+    entries: impl Iterator<Item = (u32, &'v FormulaValue)>,
+) -> Result<u32, FormulaError> {
+    for (position, value) in entries {
+        if same_kind(lookup, value) && compare_values(lookup, value)? == Ordering::Equal {
+            return Ok(position);
+        }
+    }
+    Err(FormulaError::NotAvailable)
+}
+
+/// Sorted-range lookup: returns the last entry not past `lookup`, where `past` is Greater for
+/// ascending data and Less for descending data. Entries of another type are skipped.
+fn approximate_match<'v>(
+    lookup: &FormulaValue,
+    entries: impl Iterator<Item = (u32, &'v FormulaValue)>,
+    past: Ordering,
+) -> Result<u32, FormulaError> {
+    let mut found = None;
+    for (position, value) in entries {
+        if !same_kind(lookup, value) {
+            continue;
+        }
+        if compare_values(value, lookup)? == past {
+            break;
+        }
+        found = Some(position);
+    }
+    found.ok_or(FormulaError::NotAvailable)
+}
@@ -0,0 +1,309 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use anyhow::{anyhow, Result};
+
+use crate::skills::native::excel_live::formula_safety::{
+    tokenize_formula, FormulaDialect, FormulaToken,
+};
+
+use super::parse_cell_reference;
+use super::xlsx_formula_functions::function_arity;
+
+const MAX_FORMULA_NESTING: usize = 64;
+const MAX_FORMULA_NODES: usize = 512;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum FormulaExpression {
+    Number(f64),
+    Text(String),
+    Bool(bool),
+    /// An omitted argument such as the middle of `IF(A1,,2)`.
+    Missing,
+    Cell(CellTarget),
+    Range(RangeTarget),
+    Negate(Box<Self>),
+    Percent(Box<Self>),
+    Binary(BinaryOperator, Box<Self>, Box<Self>),
+    Call(String, Vec<Self>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct CellTarget {
+    pub(super) sheet: Option<String>,
+    pub(super) column: u16,
+    pub(super) row: u32,
+}
+
+/// A range normalized so `start` is the top-left corner.
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct RangeTarget {
+    pub(super) sheet: Option<String>,
+    pub(super) start: (u16, u32),
+    pub(super) end: (u16, u32),
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum BinaryOperator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Power,
+    Concatenate,
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+const COMPARISON_OPERATORS: [(&str, BinaryOperator); 6] = [
+    ("=", BinaryOperator::Equal),
+    ("<>", BinaryOperator::NotEqual),
+    ("<", BinaryOperator::Less),
+    ("<=", BinaryOperator::LessOrEqual),
+    (">", BinaryOperator::Greater),
+    (">=", BinaryOperator::GreaterOrEqual),
+];
+const CONCATENATION_OPERATORS: [(&str, BinaryOperator); 1] = [("&", BinaryOperator::Concatenate)];
+const ADDITIVE_OPERATORS: [(&str, BinaryOperator); 2] =
+    [("+", BinaryOperator::Add), ("-", BinaryOperator::Subtract)];
+const MULTIPLICATIVE_OPERATORS: [(&str, BinaryOperator); 2] = [
+    ("*", BinaryOperator::Multiply),
+    ("/", BinaryOperator::Divide),
+];
+const POWER_OPERATORS: [(&str, BinaryOperator); 1] = [("^", BinaryOperator::Power)];
+
+/// Parses a validated artifact formula (without its leading `=`) into an expression tree.
+///
+/// Excel precedence applies from loosest to tightest: comparison, `&`, `+ -`, `* /`, `^`,
+/// postfix `%`, then unary minus, so `-2^2` is 4. Whole-row or whole-column references, range
+/// intersections, and array constants are not supported and fail to parse.
+pub(super) fn parse_formula(expression: &str) -> Result<FormulaExpression> {
+    let tokens = tokenize_formula(expression, FormulaDialect::HeadlessArtifact)?;
+    let mut parser = FormulaParser {
+        tokens: tokens.as_slice(),
+        cursor: 0,
+        depth: 0,
+        nodes: 0,
+    };
+    let parsed = parser.comparison()?;
+    if let Some(token) = parser.peek() {
+        return Err(anyhow!("unexpected formula token {token:?}"));
+    }
+    Ok(parsed)
+}
+
+struct FormulaParser<'a> {
+    tokens: &'a [FormulaToken],
+    cursor: usize,
+    depth: usize,
+    nodes: usize,
+}
+
+impl<'a> FormulaParser<'a> {
+    fn comparison(&mut self) -> Result<FormulaExpression> {
+        self.binary(&COMPARISON_OPERATORS, Self::concatenation)
+    }
+
+    fn concatenation(&mut self) -> Result<FormulaExpression> {
+        self.binary(&CONCATENATION_OPERATORS, Self::additive)
+    }
+
+    fn additive(&mut self) -> Result<FormulaExpression> {
+        self.binary(&ADDITIVE_OPERATORS, Self::multiplicative)
+    }
+
+    fn multiplicative(&mut self) -> Result<FormulaExpression> {
+        self.binary(&MULTIPLICATIVE_OPERATORS, Self::power)
+    }
+
+    fn power(&mut self) -> Result<FormulaExpression> {
+        self.binary(&POWER_OPERATORS, Self::percent)
+    }
+
+    fn binary(
+        &mut self,
+        operators: &[(&str, BinaryOperator)],
+        operand: fn(&mut Self) -> Result<FormulaExpression>,
+    ) -> Result<FormulaExpression> {
+        let mut left = operand(self)?;
+        while let Some(operator) = self.peek().and_then(|token| match token {
+            FormulaToken::Operator(symbol) => operators
+                .iter()
+                .find(|(candidate, _)| candidate == symbol)
+                .map(|(_, operator)| *operator),
+            _ => None,
+        }) {
+            self.cursor += 1;
+            self.count_node()?;
+            let right = operand(self)?;
+            left = FormulaExpression::Binary(operator, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn percent(&mut self) -> Result<FormulaExpression> {
+        let mut value = self.unary()?;
+        while self.peek() == Some(&FormulaToken::Operator("%")) {
+            self.cursor += 1;
+            self.count_node()?;
+            value = FormulaExpression::Percent(Box::new(value));
+        }
+        Ok(value)
+    }
+
+    fn unary(&mut self) -> Result<FormulaExpression> {
+        match self.peek() {
+            Some(FormulaToken::Operator(symbol @ ("-" | "+"))) => {
+                let negate = *symbol == "-";
+                self.cursor += 1;
+                self.enter()?;
+                let operand = self.unary()?;
+                self.depth -= 1;
+                Ok(if negate {
+                    FormulaExpression::Negate(Box::new(operand))
+                } else {
+                    operand
+                })
+            }
+            _ => self.primary(),
+        }
+    }
+
+    fn primary(&mut self) -> Result<FormulaExpression> {
+        let token = self
+            .next()
+            .ok_or_else(|| anyhow!("formula ends unexpectedly"))?;
+        match token {
+            FormulaToken::Number(literal) => literal
+                .parse::<f64>()
+                .ok()
+                .filter(|value| value.is_finite())
+                .map(FormulaExpression::Number)
+                .ok_or_else(|| anyhow!("formula number literal is invalid: {literal}")),
+            FormulaToken::Text(text) => Ok(FormulaExpression::Text(text.clone())),
+            FormulaToken::Function(name) => self.call(name.as_str()),
+            FormulaToken::OpenParen => {
+                self.enter()?;
+                let inner = self.comparison()?;
+                self.expect(&FormulaToken::CloseParen)?;
+                self.depth -= 1;
+                Ok(inner)
+            }
+            FormulaToken::Sheet(sheet) => match self.next() {
+                Some(FormulaToken::Identifier(reference)) => {
+                    self.reference(Some(sheet.clone()), reference.as_str())
+                }
+                _ => Err(anyhow!(
+                    "worksheet prefix must be followed by a cell reference"
+                )),
+            },
+            FormulaToken::Identifier(identifier) => {
+                match identifier.to_ascii_uppercase().as_str() {
+                    "TRUE" => Ok(FormulaExpression::Bool(true)),
+                    "FALSE" => Ok(FormulaExpression::Bool(false)),
+                    _ => self.reference(None, identifier.as_str()),
+                }
+            }
+            other => Err(anyhow!("unexpected formula token {other:?}")),
+        }
+    }
+
+    fn call(&mut self, name: &str) -> Result<FormulaExpression> {
+        let (minimum, maximum) = function_arity(name)
+            .ok_or_else(|| anyhow!("formula function is unsupported: {name}"))?;
+        self.expect(&FormulaToken::OpenParen)?;
+        self.enter()?;
+        self.count_node()?;
+        let mut arguments = Vec::new();
+        if self.peek() == Some(&FormulaToken::CloseParen) {
+            self.cursor += 1;
+        } else {
+            loop {
+                let argument = match self.peek() {
+                    Some(FormulaToken::Comma | FormulaToken::CloseParen) => {
+                        FormulaExpression::Missing
+                    }
+                    _ => self.comparison()?,
+                };
+                arguments.push(argument);
+                match self.next() {
+                    Some(FormulaToken::Comma) => {}
+                    Some(FormulaToken::CloseParen) => break,
+                    _ => return Err(anyhow!("{name} arguments must be separated by commas")),
+                }
+            }
+        }
+        self.depth -= 1;
+        if arguments.len() < minimum || arguments.len() > maximum {
+            return Err(anyhow!(
+                "{name} was given {} arguments but accepts {minimum} to {maximum}",
+                arguments.len()
+            ));
+        }
+        Ok(FormulaExpression::Call(name.to_string(), arguments))
+    }
+
+    fn reference(&mut self, sheet: Option<String>, start: &str) -> Result<FormulaExpression> {
+        let start = parse_cell_reference(start.replace('$', "").as_str())?;
+        if self.peek() != Some(&FormulaToken::Colon) {
+            return Ok(FormulaExpression::Cell(CellTarget {
+                sheet,
+                column: start.0,
+                row: start.1,
+            }));
+        }
+        self.cursor += 1;
+        let end = match self.next() {
+            Some(FormulaToken::Identifier(end)) => {
+                parse_cell_reference(end.replace('$', "").as_str())?
+            }
+            _ => return Err(anyhow!("range reference must end with a cell reference")),
+        };
+        Ok(FormulaExpression::Range(RangeTarget {
+            sheet,
+            start: (start.0.min(end.0), start.1.min(end.1)),
+            end: (start.0.max(end.0), start.1.max(end.1)),
+        }))
+    }
+
+    fn peek(&self) -> Option<&'a FormulaToken> {
+        self.tokens.get(self.cursor)
+    }
+
+    fn next(&mut self) -> Option<&'a FormulaToken> {
+        let token = self.tokens.get(self.cursor);
+        self.cursor += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: &FormulaToken) -> Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            other => Err(anyhow!("expected {expected:?} in formula, found {other:?}")),
+        }
+    }
+
+    fn enter(&mut self) -> Result<()> {
+        self.depth += 1;
+        if self.depth > MAX_FORMULA_NESTING {
+            return Err(anyhow!(
+                "formula nests more than {MAX_FORMULA_NESTING} levels"
+            ));
+        }
+        Ok(())
+    }
+
+    fn count_node(&mut self) -> Result<()> {
+        self.nodes += 1;
+        if self.nodes > MAX_FORMULA_NODES {
+            return Err(anyhow!(
+                "formula has more than {MAX_FORMULA_NODES} operations"
+            ));
+        }
+        Ok(())
+    }
+}
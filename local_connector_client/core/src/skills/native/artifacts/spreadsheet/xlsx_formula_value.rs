@@ -0,0 +1,270 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, Days, NaiveDate};
+
+use super::xlsx_model::{FormulaError, PrimitiveCellValue};
+
+// Excel's 1900 date system: serial 1 is 1900-01-01 and serial 60 is the nonexistent
+// 1900-02-29 kept for Lotus compatibility, so real dates from March 1900 count from 1899-12-30.
+const LAST_DATE_SERIAL: i64 = 2_958_465;
+const FIRST_REAL_LEAP_SERIAL: i64 = 61;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum FormulaValue {
+    Blank,
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    Error(FormulaError),
+}
+
+/// Cells of a rectangular range keyed by zero-based (row, column) offsets; blanks are omitted.
+#[derive(Debug, Default)]
+pub(super) struct RangeValues {
+    pub(super) rows: u32,
+    pub(super) columns: u16,
+    pub(super) cells: BTreeMap<(u32, u16), FormulaValue>,
+}
+
+impl RangeValues {
+    pub(super) fn get(&self, row: u32, column: u16) -> FormulaValue {
+        self.cells
+            .get(&(row, column))
+            .cloned()
+            .unwrap_or(FormulaValue::Blank)
+    }
+
+    /// Values along a one-row or one-column range, or `None` for a two-dimensional range.
+    pub(super) fn vector(&self) -> Option<Vec<(u32, &FormulaValue)>> {
+        if self.rows == 1 {
+            Some(
+                self.cells
+                    .iter()
+                    .map(|((_, column), value)| (u32::from(*column), value))
+                    .collect(),
+            )
+        } else if self.columns == 1 {
+            Some(
+                self.cells
+                    .iter()
+                    .map(|((row, _), value)| (*row, value))
+                    .collect(),
+            )
+        } else {
+            None
+        }
+    }
+}
+
+pub(super) enum FormulaArgument {
+    Value(FormulaValue),
+    Range(RangeValues),
+}
+
+impl FormulaValue {
+    pub(super) fn number(value: f64) -> Self {
+        if value.is_finite() {
+            Self::Number(if value == 0.0 { 0.0 } else { value })
+        } else {
+            Self::Error(FormulaError::Number)
+        }
+    }
+
+    pub(super) fn from_cell(value: &PrimitiveCellValue) -> Self {
+        match value {
+            PrimitiveCellValue::Blank => Self::Blank,
+            PrimitiveCellValue::Bool(value) => Self::Bool(*value),
+            PrimitiveCellValue::Number(value) => value
+                .parse::<f64>()
+                .map(Self::number)
+                .unwrap_or(Self::Error(FormulaError::Value)),
+            PrimitiveCellValue::Text(value) => Self::Text(value.clone()),
+            PrimitiveCellValue::Error(error) => Self::Error(*error),
+        }
+    }
+
+    /// Cached representation for a formula cell. A formula that resolves to an empty cell
+    /// displays as zero in Excel, so blanks are cached as 0.
+    pub(super) fn into_cached_value(self) -> PrimitiveCellValue {
+        match self {
+            Self::Blank => PrimitiveCellValue::Number("0".to_string()),
+            Self::Bool(value) => PrimitiveCellValue::Bool(value),
+            Self::Number(value) => PrimitiveCellValue::Number(value.to_string()),
+            Self::Text(value) => PrimitiveCellValue::Text(value),
+            Self::Error(error) => PrimitiveCellValue::Error(error),
+        }
+    }
+
+    pub(super) fn to_number(&self) -> Result<f64, FormulaError> {
+        match self {
+            Self::Blank => Ok(0.0),
+            Self::Bool(value) => Ok(if *value { 1.0 } else { 0.0 }),
+            Self::Number(value) => Ok(*value),
+            Self::Text(value) => parse_number_text(value).ok_or(FormulaError::Value),
+            Self::Error(error) => Err(*error),
+        }
+    }
+
+    pub(super) fn to_text(&self) -> Result<String, FormulaError> {
+        match self {
+            Self::Blank => Ok(String::new()),
+            Self::Bool(value) => Ok(if *value { "TRUE" } else { "FALSE" }.to_string()),
+            Self::Number(value) => Ok(number_text(*value)),
+            Self::Text(value) => Ok(value.clone()),
+            Self::Error(error) => Err(*error),
+        }
+    }
+
+    pub(super) fn to_bool(&self) -> Result<bool, FormulaError> {
+        match self {
+            Self::Blank => Ok(false),
+            Self::Bool(value) => Ok(*value),
+            Self::Number(value) => Ok(*value != 0.0),
+            Self::Text(value) if value.eq_ignore_ascii_case("TRUE") => Ok(true),
+            Self::Text(value) if value.eq_ignore_ascii_case("FALSE") => Ok(false),
+            Self::Text(_) => Err(FormulaError::Value),
+            Self::Error(error) => Err(*error),
+        }
+    }
+}
+
+/// Parses text the way Excel coerces it in arithmetic: an optional sign, decimal or exponent
+/// notation, and an optional trailing percent sign.
+pub(super) fn parse_number_text(value: &str) -> Option<f64> {
+    let trimmed = value.trim();
+    let (body, scale) = match trimmed.strip_suffix('%') {
+        Some(body) => (body.trim_end(), 0.01),
+        None => (trimmed, 1.0),
+    };
+    if body.is_empty()
+        || !body
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || matches!(byte, b'.' | b'+' | b'-' | b'e' | b'E'))
+    {
+        return None;
+    }
+    body.parse::<f64>()
+        .ok()
+        .map(|value| value * scale)
+        .filter(|value| value.is_finite())
+}
+
+/// Formats a number as Excel's General format does in text contexts, rounded to 15 significant
+/// digits so `0.1+0.2` reads as `0.3`.
+pub(super) fn number_text(value: f64) -> String {
+    let rounded = round_significant(value);
+    if rounded == 0.0 {
+        "0".to_string()
+    } else {
+        rounded.to_string()
+    }
+}
+
+pub(super) fn round_significant(value: f64) -> f64 {
+    format!("{value:.14e}").parse::<f64>().unwrap_or(value)
+}
+
+/// Orders two values with Excel's comparison rules: blanks take the other side's type, text
+/// compares case-insensitively, and numbers sort before text before booleans.
+pub(super) fn compare_values(
+    left: &FormulaValue,
+    right: &FormulaValue,
+) -> Result<Ordering, FormulaError> {
+    let (left, right) = match (left, right) {
+        (FormulaValue::Error(error), _) | (_, FormulaValue::Error(error)) => return Err(*error),
+        (FormulaValue::Blank, other) => (blank_like(other), other.clone()),
+        (other, FormulaValue::Blank) => (other.clone(), blank_like(other)),
+        (left, right) => (left.clone(), right.clone()),
+    };
+    Ok(match (&left, &right) {
+        (FormulaValue::Number(left), FormulaValue::Number(right)) => {
+            left.partial_cmp(right).unwrap_or(Ordering::Equal)
+        }
+        (FormulaValue::Text(left), FormulaValue::Text(right)) => {
+            left.to_lowercase().cmp(&right.to_lowercase())
+        }
+        (FormulaValue::Bool(left), FormulaValue::Bool(right)) => left.cmp(right),
+        _ => type_rank(&left).cmp(&type_rank(&right)),
+    })
+}
+
+fn blank_like(value: &FormulaValue) -> FormulaValue {
+    match value {
+        FormulaValue::Text(_) => FormulaValue::Text(String::new()),
+        FormulaValue::Bool(_) => FormulaValue::Bool(false),
+        _ => FormulaValue::Number(0.0),
+    }
+}
+
+fn type_rank(value: &FormulaValue) -> u8 {
+    match value {
+        FormulaValue::Blank | FormulaValue::Number(_) => 0,
+        FormulaValue::Text(_) => 1,
+        FormulaValue::Bool(_) => 2,
+        FormulaValue::Error(_) => 3,
+    }
+}
+
+/// Serial number for `DATE(year, month, day)`, normalizing month and day overflow like Excel.
+pub(super) fn date_serial(year: f64, month: f64, day: f64) -> Result<f64, FormulaError> {
+    let mut year = year.trunc() as i64;
+    if (0..1900).contains(&year) {
+        year += 1900;
+    }
+    if !(1900..10_000).contains(&year) {
+        return Err(FormulaError::Number);
+    }
+    let months = year * 12 + month.trunc() as i64 - 1;
+    let first = NaiveDate::from_ymd_opt(
+        i32::try_from(months.div_euclid(12)).map_err(|_| FormulaError::Number)?,
+        months.rem_euclid(12) as u32 + 1,
+        1,
+    )
+    .ok_or(FormulaError::Number)?;
+    let serial = serial_for_date(first)? + day.trunc() as i64 - 1;
+    if !(0..=LAST_DATE_SERIAL).contains(&serial) {
+        return Err(FormulaError::Number);
+    }
+    Ok(serial as f64)
+}
+
+/// Calendar (year, month, day) for a serial number; serial 0 is Excel's 1900-01-00.
+pub(super) fn serial_date(serial: f64) -> Result<(i32, u32, u32), FormulaError> {
+    let serial = serial.floor() as i64;
+    match serial {
+        0 => Ok((1900, 1, 0)),
+        60 => Ok((1900, 2, 29)),
+        1..=LAST_DATE_SERIAL => {
+            let offset = if serial < FIRST_REAL_LEAP_SERIAL {
+                serial + 1
+            } else {
+                serial
+            };
+            let date = excel_epoch()
+                .checked_add_days(Days::new(offset as u64))
+                .ok_or(FormulaError::Number)?;
+            Ok((date.year(), date.month(), date.day()))
+        }
+        _ => Err(FormulaError::Number),
+    }
+}
+
+fn serial_for_date(date: NaiveDate) -> Result<i64, FormulaError> {
+    let days = date.signed_duration_since(excel_epoch()).num_days();
+    if days < 2 {
+        return Err(FormulaError::Number);
+    }
+    Ok(if days < FIRST_REAL_LEAP_SERIAL {
+        days - 1
+    } else {
+        days
+    })
+}
+
+fn excel_epoch() -> NaiveDate {
+    NaiveDate::from_ymd_opt(1899, 12, 30).expect("valid Excel epoch")
+}
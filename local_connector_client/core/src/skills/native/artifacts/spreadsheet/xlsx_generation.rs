@@ -8,6 +8,7 @@ use crate::relay::RelayRequest;
 use crate::LocalState;
 
 use super::super::{optional_bool, require_extension, required_text, safe_workspace_path};
+use super::xlsx_formula::calculate_workbook;
 use super::xlsx_input::parse_worksheets;
 use super::xlsx_model::{CellInput, CellValue, NumberFormat, PrimitiveCellValue, WorksheetInput};
 use super::{cell_reference, write_new_xlsx};
@@ -19,7 +20,8 @@ pub(super) fn create_xlsx(
 ) -> Result<Value> {
     let target = required_text(arguments, "target_path")?;
     require_extension(target, ".xlsx")?;
-    let worksheets = parse_worksheets(arguments)?;
+    let mut worksheets = parse_worksheets(arguments)?;
+    let calculation = calculate_workbook(worksheets.as_mut_slice());
     let entries = workbook_entries(worksheets.as_slice())?;
     let (path, relative) = safe_workspace_path(state, request, target)?;
     let bytes = write_new_xlsx(
@@ -38,7 +40,7 @@ pub(super) fn create_xlsx(
         .flatten()
         .filter(|cell| matches!(cell.value, CellValue::Formula { .. }))
         .count();
-    Ok(json!({
+    let mut response = json!({
         "created": true,
         "path": relative,
         "bytes": bytes,
@@ -47,7 +49,15 @@ pub(super) fn create_xlsx(
         "cells": cells,
         "formula_cells": formulas,
         "recalculation_on_open": formulas > 0,
-    }))
+    });
+    if let Some(fields) = response.as_object_mut().filter(|_| formulas > 0) {
+        let sheet_names = worksheets
+            .iter()
+            .map(|sheet| sheet.name.clone())
+            .collect::<Vec<_>>();
+        fields.extend(calculation.report(sheet_names.as_slice()));
+    }
+    Ok(response)
 }
 
 pub(super) fn workbook_entries(worksheets: &[WorksheetInput]) -> Result<Vec<(String, Vec<u8>)>> {
@@ -224,6 +234,9 @@ pub(super) fn cell_xml(
                     " t=\"str\"".to_string(),
                     format!("<v>{}</v>", escape_xml(value)),
                 ),
+                Some(PrimitiveCellValue::Error(error)) => {
+                    (" t=\"e\"".to_string(), format!("<v>{}</v>", error.code()))
+                }
             };
             Ok(format!(
                 "<c r=\"{reference}\"{style}{cell_type}><f>{}</f>{cached}</c>",
@@ -247,6 +260,10 @@ fn primitive_cell_xml(reference: &str, style: &str, value: &PrimitiveCellValue)
             "<c r=\"{reference}\"{style} t=\"inlineStr\"><is><t xml:space=\"preserve\">{}</t></is></c>",
             escape_xml(value)
         ),
+        PrimitiveCellValue::Error(error) => format!(
+            "<c r=\"{reference}\"{style} t=\"e\"><v>{}</v></c>",
+            error.code()
+        ),
     })
 }
 
@@ -6,7 +6,9 @@ use std::collections::{BTreeMap, HashSet};
 use anyhow::{anyhow, Result};
 use serde_json::Value;
 
-use crate::skills::native::excel_live::formula_safety::validate_local_formula_expression;
+use crate::skills::native::excel_live::formula_safety::{
+    validate_formula_expression, FormulaDialect,
+};
 
 use super::super::{optional_text, MAX_TABLE_CELLS};
 use super::xlsx_model::{CellInput, CellValue, NumberFormat, PrimitiveCellValue, WorksheetInput};
@@ -291,7 +293,7 @@ pub(super) fn validate_formula(value: &str) -> Result<String> {
             "formula must contain between 1 and {MAX_FORMULA_BYTES} bytes"
         ));
     }
-    validate_local_formula_expression(expression, |identifier| {
+    validate_formula_expression(expression, FormulaDialect::HeadlessArtifact, |identifier| {
         let plain_identifier = identifier.replace('$', "");
         matches!(
             plain_identifier.to_ascii_uppercase().as_str(),
@@ -58,12 +58,49 @@ impl NumberFormat {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum FormulaError {
+    DivisionByZero,
+    NotAvailable,
+    Name,
+    Number,
+    Reference,
+    Value,
+}
+
+impl FormulaError {
+    pub(super) fn code(self) -> &'static str {
+        match self {
+            Self::DivisionByZero => "#DIV/0!",
+            Self::NotAvailable => "#N/A",
+            Self::Name => "#NAME?",
+            Self::Number => "#NUM!",
+            Self::Reference => "#REF!",
+            Self::Value => "#VALUE!",
+        }
+    }
+
+    pub(super) fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "#DIV/0!" => Some(Self::DivisionByZero),
+            "#N/A" => Some(Self::NotAvailable),
+            "#NAME?" => Some(Self::Name),
+            "#NUM!" => Some(Self::Number),
+            "#REF!" => Some(Self::Reference),
+            "#VALUE!" => Some(Self::Value),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(super) enum PrimitiveCellValue {
     Blank,
     Bool(bool),
     Number(String),
     Text(String),
+    /// Only produced as a computed formula result; callers cannot supply error values.
+    Error(FormulaError),
 }
 
 #[derive(Clone, Debug)]
@@ -138,10 +138,22 @@ pub(super) fn parse_relationships(xml: &str) -> Result<RelationshipMap> {
 }
 
 pub(super) fn workbook_styles_part(relationships_xml: &str) -> Result<Option<String>> {
+    workbook_part(relationships_xml, "/styles", "styles")
+}
+
+pub(super) fn workbook_shared_strings_part(relationships_xml: &str) -> Result<Option<String>> {
+    workbook_part(relationships_xml, "/sharedStrings", "shared strings")
+}
+
+fn workbook_part(
+    relationships_xml: &str,
+    type_suffix: &str,
+    label: &str,
+) -> Result<Option<String>> {
     for (_, (target, relationship_type, external)) in parse_relationships(relationships_xml)? {
-        if relationship_type.ends_with("/styles") {
+        if relationship_type.ends_with(type_suffix) {
             if external {
-                return Err(anyhow!("XLSX styles relationship cannot be external"));
+                return Err(anyhow!("XLSX {label} relationship cannot be external"));
             }
             return Ok(Some(resolve_workbook_target(target.as_str())?));
         }
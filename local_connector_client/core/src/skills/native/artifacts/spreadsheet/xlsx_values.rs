@@ -0,0 +1,219 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Context, Result};
+use quick_xml::escape::{resolve_xml_entity, unescape};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, XmlVersion};
+
+use super::parse_cell_reference;
+use super::xlsx_model::{CellValue, FormulaError, PrimitiveCellValue};
+use super::xlsx_package::optional_attribute;
+use super::{MAX_XLSX_COLUMNS, MAX_XLSX_ROWS};
+
+/// Non-blank cells of an existing worksheet keyed by (row, column).
+pub(super) type WorksheetCells = BTreeMap<(u32, u16), CellValue>;
+
+/// Reads the shared string table in index order. Rich-text runs are concatenated and phonetic
+/// hints are dropped, which is the text Excel exposes to formulas.
+pub(super) fn read_shared_strings(xml: &str) -> Result<Vec<String>> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(false);
+    let mut strings = Vec::new();
+    loop {
+        match reader
+            .read_event()
+            .context("parse XLSX shared strings XML")?
+        {
+            Event::Start(event) if event.local_name().as_ref() == b"si" => {
+                strings.push(read_element_text(&mut reader, b"si")?);
+            }
+            Event::Empty(event) if event.local_name().as_ref() == b"si" => {
+                strings.push(String::new());
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(strings)
+}
+
+/// Reads the stored values and formulas of a worksheet. Rows and cells without an `r`
+/// attribute follow the previous one, as in the SpreadsheetML schema. Formulas come back
+/// without cached values; a shared-formula follower has no text of its own and so is left for
+/// the evaluator to report as unsupported.
+pub(super) fn read_worksheet_cells(xml: &str, shared_strings: &[String]) -> Result<WorksheetCells> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(false);
+    let mut cells = WorksheetCells::new();
+    let mut row = 0u32;
+    let mut column = 0u16;
+    loop {
+        match reader.read_event().context("parse XLSX worksheet XML")? {
+            Event::Start(event) | Event::Empty(event) if event.local_name().as_ref() == b"row" => {
+                row = match optional_attribute(&reader, &event, "r")? {
+                    Some(value) => value
+                        .parse::<u32>()
+                        .context("XLSX row reference is not numeric")?,
+                    None => row.saturating_add(1),
+                };
+                if row == 0 || row > MAX_XLSX_ROWS {
+                    return Err(anyhow!("XLSX worksheet rows are invalid"));
+                }
+                column = 0;
+            }
+            Event::Empty(event) if event.local_name().as_ref() == b"c" => {
+                advance_cell(&reader, &event, &mut row, &mut column)?;
+            }
+            Event::Start(event) if event.local_name().as_ref() == b"c" => {
+                advance_cell(&reader, &event, &mut row, &mut column)?;
+                let cell_type = optional_attribute(&reader, &event, "t")?;
+                if let Some(value) =
+                    read_cell_value(&mut reader, cell_type.as_deref(), shared_strings)?
+                {
+                    cells.insert((row, column), value);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(cells)
+}
+
+fn advance_cell(
+    reader: &Reader<&[u8]>,
+    event: &BytesStart<'_>,
+    row: &mut u32,
+    column: &mut u16,
+) -> Result<()> {
+    match optional_attribute(reader, event, "r")? {
+        Some(reference) => {
+            (*column, *row) = parse_cell_reference(reference.as_str())?;
+        }
+        None => *column = column.saturating_add(1),
+    }
+    if *row == 0 || *column == 0 || *column > MAX_XLSX_COLUMNS {
+        return Err(anyhow!("XLSX worksheet cells are invalid"));
+    }
+    Ok(())
+}
+
+fn read_cell_value(
+    reader: &mut Reader<&[u8]>,
+    cell_type: Option<&str>,
+    shared_strings: &[String],
+) -> Result<Option<CellValue>> {
+    let mut stored = None;
+    let mut formula = None;
+    let mut inline = None;
+    loop {
+        match reader.read_event().context("parse XLSX cell")? {
+            Event::Start(event) => match event.local_name().as_ref() {
+                b"v" => stored = Some(read_element_text(reader, b"v")?),
+                b"f" => formula = Some(read_element_text(reader, b"f")?),
+                b"is" => inline = Some(read_element_text(reader, b"is")?),
+                name => {
+                    read_element_text(reader, name)?;
+                }
+            },
+            Event::Empty(event) if event.local_name().as_ref() == b"f" => {
+                formula = Some(String::new());
+            }
+            Event::End(event) if event.local_name().as_ref() == b"c" => break,
+            Event::Eof => return Err(anyhow!("XLSX cell is not closed")),
+            _ => {}
+        }
+    }
+    if let Some(expression) = formula {
+        return Ok(Some(CellValue::Formula {
+            expression,
+            cached_value: None,
+        }));
+    }
+    let value = match (cell_type.unwrap_or("n"), stored) {
+        ("inlineStr", _) => inline.map(PrimitiveCellValue::Text),
+        (_, None) => None,
+        ("s", Some(index)) => {
+            let text = index
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| shared_strings.get(index))
+                .ok_or_else(|| anyhow!("XLSX cell references a missing shared string"))?;
+            Some(PrimitiveCellValue::Text(text.clone()))
+        }
+        ("str" | "d", Some(text)) => Some(PrimitiveCellValue::Text(text)),
+        ("b", Some(value)) => Some(PrimitiveCellValue::Bool(value.trim() == "1")),
+        // Error codes the evaluator does not model, such as #NULL! or #SPILL!, still propagate
+        // as an error.
+        ("e", Some(code)) => Some(PrimitiveCellValue::Error(
+            FormulaError::from_code(code.trim()).unwrap_or(FormulaError::Value),
+        )),
+        (_, Some(number)) => Some(PrimitiveCellValue::Number(number.trim().to_string())),
+    };
+    Ok(value.map(CellValue::Primitive))
+}
+
+/// Concatenated text inside the element whose start tag was just read, skipping phonetic
+/// (`rPh`) runs. Consumes the matching end tag.
+fn read_element_text(reader: &mut Reader<&[u8]>, local_name: &[u8]) -> Result<String> {
+    let mut text = String::new();
+    let mut depth = 0usize;
+    let mut phonetic_depth = 0usize;
+    loop {
+        match reader.read_event().context("parse XLSX text")? {
+            Event::Start(event) => {
+                depth += 1;
+                if phonetic_depth > 0 || event.local_name().as_ref() == b"rPh" {
+                    phonetic_depth += 1;
+                }
+            }
+            Event::End(event) => {
+                if depth == 0 {
+                    if event.local_name().as_ref() != local_name {
+                        return Err(anyhow!("XLSX XML elements are not balanced"));
+                    }
+                    return Ok(text);
+                }
+                depth -= 1;
+                phonetic_depth = phonetic_depth.saturating_sub(1);
+            }
+            Event::Text(value) if phonetic_depth == 0 => {
+                let decoded = value
+                    .xml_content(XmlVersion::Explicit1_0)
+                    .context("decode XLSX text")?;
+                text.push_str(
+                    unescape(decoded.as_ref())
+                        .context("unescape XLSX text")?
+                        .as_ref(),
+                );
+            }
+            Event::GeneralRef(reference) if phonetic_depth == 0 => {
+                if let Some(character) = reference
+                    .resolve_char_ref()
+                    .context("resolve XLSX character reference")?
+                {
+                    text.push(character);
+                } else {
+                    let entity = reference.decode().context("decode XLSX entity reference")?;
+                    text.push_str(resolve_xml_entity(entity.as_ref()).ok_or_else(|| {
+                        anyhow!("XLSX text contains an unsupported entity reference")
+                    })?);
+                }
+            }
+            Event::CData(value) if phonetic_depth == 0 => {
+                text.push_str(
+                    value
+                        .xml_content(XmlVersion::Explicit1_0)
+                        .context("decode XLSX CDATA")?
+                        .as_ref(),
+                );
+            }
+            Event::Eof => return Err(anyhow!("XLSX XML element is not closed")),
+            _ => {}
+        }
+    }
+}
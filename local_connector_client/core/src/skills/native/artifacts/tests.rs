@@ -812,6 +812,116 @@ fn creates_inspects_and_updates_bounded_multi_sheet_xlsx() {
     let _ = fs::remove_dir_all(root);
 }
 
+#[test]
+fn created_xlsx_caches_computed_formula_values_and_reports_cell_errors() {
+    let (root, state, request) = test_context();
+    let created = spreadsheet::create_xlsx(
+        &json!({
+            "target_path":"computed.xlsx",
+            "rows":[
+                ["Units","Cost",{"formula":"IF(B1>0,A1/B1,\"none\")"}],
+                [4,0,{"formula":"A2/B2","cached_value":99}],
+                [{"formula":"C3"},null,{"formula":"A3"}]
+            ]
+        }),
+        &state,
+        &request,
+    )
+    .expect("computed XLSX");
+    assert_eq!(
+        created
+            .get("calculated_formula_cells")
+            .and_then(Value::as_u64),
+        Some(2)
+    );
+    assert_eq!(
+        created.get("formula_errors"),
+        Some(&json!([
+            {"sheet_name":"Sheet1","cell":"C1","error":"#VALUE!"},
+            {"sheet_name":"Sheet1","cell":"C2","error":"#DIV/0!"}
+        ]))
+    );
+    assert_eq!(
+        created
+            .get("uncalculated_formula_cells")
+            .and_then(Value::as_u64),
+        Some(2)
+    );
+    assert_eq!(
+        created
+            .get("recalculation_on_open")
+            .and_then(Value::as_bool),
+        Some(true)
+    );
+
+    let mut archive =
+        ZipArchive::new(File::open(root.join("computed.xlsx")).expect("XLSX")).expect("ZIP");
+    let sheet = read_zip_text(&mut archive, "xl/worksheets/sheet1.xml").expect("sheet1");
+    assert!(sheet.contains(
+        "<c r=\"C1\" t=\"e\"><f>IF(B1&gt;0,A1/B1,&quot;none&quot;)</f><v>#VALUE!</v></c>"
+    ));
+    assert!(sheet.contains("<c r=\"C2\" t=\"e\"><f>A2/B2</f><v>#DIV/0!</v></c>"));
+    assert!(sheet.contains("<c r=\"A3\"><f>C3</f></c>"));
+    let _ = fs::remove_dir_all(root);
+}
+
+#[test]
+fn updated_xlsx_formulas_cache_values_computed_from_existing_cells() {
+    let (root, state, request) = test_context();
+    spreadsheet::create_xlsx(
+        &json!({
+            "target_path":"ledger.xlsx",
+            "worksheets":[
+                {"name":"Data","rows":[["Units","Cost"],[4,2],[6,0]]},
+                {"name":"Summary","rows":[["Totals"]]}
+            ]
+        }),
+        &state,
+        &request,
+    )
+    .expect("source XLSX");
+    let updated = spreadsheet::update_xlsx_range(
+        &json!({
+            "path":"ledger.xlsx",
+            "target_path":"ledger-updated.xlsx",
+            "sheet_name":"Summary",
+            "start_cell":"A2",
+            "values":[
+                [{"formula":"SUM(Data!A2:A3)","cached_value":1}],
+                [{"formula":"Data!A3/Data!B3"}],
+                [{"formula":"A2&\" \"&Data!A1"}]
+            ]
+        }),
+        &state,
+        &request,
+    )
+    .expect("update XLSX formulas");
+    assert_eq!(
+        updated
+            .get("calculated_formula_cells")
+            .and_then(Value::as_u64),
+        Some(3)
+    );
+    assert_eq!(
+        updated.get("formula_errors"),
+        Some(&json!([{"sheet_name":"Summary","cell":"A3","error":"#DIV/0!"}]))
+    );
+    assert_eq!(
+        updated
+            .get("uncalculated_formula_cells")
+            .and_then(Value::as_u64),
+        Some(0)
+    );
+
+    let mut archive =
+        ZipArchive::new(File::open(root.join("ledger-updated.xlsx")).expect("XLSX")).expect("ZIP");
+    let sheet = read_zip_text(&mut archive, "xl/worksheets/sheet2.xml").expect("sheet2");
+    assert!(sheet.contains("<c r=\"A2\"><f>SUM(Data!A2:A3)</f><v>10</v></c>"));
+    assert!(sheet.contains("<c r=\"A3\" t=\"e\"><f>Data!A3/Data!B3</f><v>#DIV/0!</v></c>"));
+    assert!(sheet.contains("t=\"str\"><f>A2&amp;&quot; &quot;&amp;Data!A1</f><v>10 Units</v>"));
+    let _ = fs::remove_dir_all(root);
+}
+
 #[test]
 fn xlsx_updates_fail_closed_for_in_place_and_unsafe_formula_requests() {
     let (root, state, request) = test_context();
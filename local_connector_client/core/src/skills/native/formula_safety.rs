@@ -7,21 +7,154 @@ const ALLOWED_FUNCTIONS: [&str; 12] = [
     "ABS", "AND", "AVERAGE", "COUNT", "COUNTA", "IF", "MAX", "MIN", "NOT", "OR", "ROUND", "SUM",
 ];
 
+// Functions introduced after Excel 2010 (CONCAT, DAYS, IFNA, ...) are stored with an `_xlfn.`
+// prefix in workbook XML and would show #NAME? if written bare, so they are left out.
+const ARTIFACT_FUNCTIONS: [&str; 29] = [
+    "CONCATENATE",
+    "DATE",
+    "DAY",
+    "IFERROR",
+    "INDEX",
+    "INT",
+    "ISBLANK",
+    "ISERROR",
+    "ISNUMBER",
+    "ISTEXT",
+    "LEFT",
+    "LEN",
+    "LOWER",
+    "MATCH",
+    "MID",
+    "MOD",
+    "MONTH",
+    "POWER",
+    "PRODUCT",
+    "RIGHT",
+    "ROUNDDOWN",
+    "ROUNDUP",
+    "SQRT",
+    "TRIM",
+    "UPPER",
+    "VALUE",
+    "VLOOKUP",
+    "WEEKDAY",
+    "YEAR",
+];
+
+/// Formula grammar accepted from a caller.
+///
+/// Live Excel writes keep the original string-free allowlist. Headless artifacts are computed by
+/// the XLSX evaluator before they are written, so they also accept string literals, `&`, and the
+/// lookup, text, and date functions that evaluator implements.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(in crate::skills::native) enum FormulaDialect {
+    LiveWorkbook,
+    HeadlessArtifact,
+}
+
+impl FormulaDialect {
+    fn allows_function(self, function: &str) -> bool {
+        ALLOWED_FUNCTIONS.contains(&function)
+            || (self == Self::HeadlessArtifact && ARTIFACT_FUNCTIONS.contains(&function))
+    }
+
+    fn allows_strings(self) -> bool {
+        self == Self::HeadlessArtifact
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(in crate::skills::native) enum FormulaToken {
+    /// Numeric literal as written, including any decimal point and exponent.
+    Number(String),
+    /// String literal with `""` escapes already collapsed.
+    Text(String),
+    /// Upper-cased function name; the opening parenthesis follows as its own token.
+    Function(String),
+    /// Worksheet prefix of a reference, with quotes and the trailing `!` removed.
+    Sheet(String),
+    /// Cell reference or boolean name as written, including any `$` anchors.
+    Identifier(String),
+    /// One of `+ - * / ^ % & = <> < > <= >=`.
+    Operator(&'static str),
+    Colon,
+    Comma,
+    OpenParen,
+    CloseParen,
+    /// Any other allowed character, such as a stray `.` or `$`.
+    Other(char),
+}
+
 pub(in crate::skills::native) fn validate_local_formula_expression(
     expression: &str,
     is_plain_identifier_allowed: impl Fn(&str) -> bool,
 ) -> Result<()> {
-    if !has_supported_formula_characters(expression) {
-        return Err(anyhow!(
-            "formula contains unsupported dynamic, string, or external-link syntax"
-        ));
+    validate_formula_expression(
+        expression,
+        FormulaDialect::LiveWorkbook,
+        is_plain_identifier_allowed,
+    )
+    .map(|_| ())
+}
+
+/// Tokenizes and validates a formula, returning the tokens so callers that evaluate the formula
+/// work from exactly what was checked.
+pub(in crate::skills::native) fn validate_formula_expression(
+    expression: &str,
+    dialect: FormulaDialect,
+    is_plain_identifier_allowed: impl Fn(&str) -> bool,
+) -> Result<Vec<FormulaToken>> {
+    let tokens = tokenize_formula(expression, dialect)?;
+    for token in &tokens {
+        match token {
+            FormulaToken::Function(function) if !dialect.allows_function(function) => {
+                return Err(anyhow!(
+                    "formula function is not in the local safety allowlist: {function}"
+                ));
+            }
+            FormulaToken::Identifier(identifier) if !is_plain_identifier_allowed(identifier) => {
+                return Err(anyhow!(
+                    "formula named ranges are disabled; use cells, booleans, safe functions, or worksheet references"
+                ));
+            }
+            _ => {}
+        }
     }
+    Ok(tokens)
+}
 
+pub(in crate::skills::native) fn tokenize_formula(
+    expression: &str,
+    dialect: FormulaDialect,
+) -> Result<Vec<FormulaToken>> {
     let bytes = expression.as_bytes();
+    let mut tokens = Vec::new();
     let mut cursor = 0usize;
     while cursor < bytes.len() {
-        if bytes[cursor] == b'\'' {
-            cursor = validate_quoted_sheet_reference(expression, cursor)?;
+        let byte = bytes[cursor];
+        if byte == b'"' && dialect.allows_strings() {
+            let (text, end) = string_literal(expression, cursor)?;
+            tokens.push(FormulaToken::Text(text));
+            cursor = end;
+            continue;
+        }
+        if !is_supported_formula_byte(byte, dialect) {
+            return Err(anyhow!(
+                "formula contains unsupported dynamic, string, or external-link syntax"
+            ));
+        }
+        if byte == b'\'' {
+            let (sheet, end) = quoted_sheet_reference(expression, cursor)?;
+            if !sheet
+                .bytes()
+                .all(|byte| is_supported_formula_byte(byte, dialect))
+            {
+                return Err(anyhow!(
+                    "formula contains unsupported dynamic, string, or external-link syntax"
+                ));
+            }
+            tokens.push(FormulaToken::Sheet(sheet));
+            cursor = end;
             continue;
         }
         if starts_formula_identifier(bytes, cursor) {
@@ -29,74 +162,114 @@ pub(in crate::skills::native) fn validate_local_formula_expression(
             cursor = identifier_end(bytes, cursor + 1);
             let lookahead = skip_spaces(bytes, cursor);
             let identifier = &expression[start..cursor];
-            if bytes.get(lookahead) == Some(&b'(') {
-                let function = identifier.to_ascii_uppercase();
-                if !ALLOWED_FUNCTIONS.contains(&function.as_str()) {
-                    return Err(anyhow!(
-                        "formula function is not in the local safety allowlist: {function}"
-                    ));
+            match bytes.get(lookahead) {
+                Some(b'(') => tokens.push(FormulaToken::Function(identifier.to_ascii_uppercase())),
+                Some(b'!') => {
+                    validate_formula_sheet_name(identifier)?;
+                    tokens.push(FormulaToken::Sheet(identifier.to_string()));
+                    cursor = lookahead + 1;
                 }
+                _ => tokens.push(FormulaToken::Identifier(identifier.to_string())),
+            }
+            continue;
+        }
+        if byte.is_ascii_digit()
+            || (byte == b'.' && bytes.get(cursor + 1).is_some_and(u8::is_ascii_digit))
+        {
+            let end = number_end(bytes, cursor);
+            tokens.push(FormulaToken::Number(expression[cursor..end].to_string()));
+            cursor = end;
+            continue;
+        }
+        let next = bytes.get(cursor + 1).copied();
+        let (token, width) = match (byte, next) {
+            (b'<', Some(b'=')) => (FormulaToken::Operator("<="), 2),
+            (b'<', Some(b'>')) => (FormulaToken::Operator("<>"), 2),
+            (b'>', Some(b'=')) => (FormulaToken::Operator(">="), 2),
+            (b'+', _) => (FormulaToken::Operator("+"), 1),
+            (b'-', _) => (FormulaToken::Operator("-"), 1),
+            (b'*', _) => (FormulaToken::Operator("*"), 1),
+            (b'/', _) => (FormulaToken::Operator("/"), 1),
+            (b'^', _) => (FormulaToken::Operator("^"), 1),
+            (b'%', _) => (FormulaToken::Operator("%"), 1),
+            (b'&', _) => (FormulaToken::Operator("&"), 1),
+            (b'=', _) => (FormulaToken::Operator("="), 1),
+            (b'<', _) => (FormulaToken::Operator("<"), 1),
+            (b'>', _) => (FormulaToken::Operator(">"), 1),
+            (b':', _) => (FormulaToken::Colon, 1),
+            (b',', _) => (FormulaToken::Comma, 1),
+            (b'(', _) => (FormulaToken::OpenParen, 1),
+            (b')', _) => (FormulaToken::CloseParen, 1),
+            (b' ', _) => {
+                cursor += 1;
                 continue;
             }
+            (other, _) => (FormulaToken::Other(char::from(other)), 1),
+        };
+        tokens.push(token);
+        cursor += width;
+    }
+    Ok(tokens)
+}
 
-            let is_sheet_reference = bytes.get(lookahead) == Some(&b'!');
-            if is_sheet_reference {
-                validate_formula_sheet_name(identifier)?;
-            } else if !is_plain_identifier_allowed(identifier)
-                && !is_numeric_exponent(bytes, identifier, start, lookahead)
-            {
-                return Err(anyhow!(
-                    "formula named ranges are disabled; use cells, booleans, safe functions, or worksheet references"
-                ));
-            }
+fn is_supported_formula_byte(byte: u8, dialect: FormulaDialect) -> bool {
+    byte.is_ascii_alphanumeric()
+        || matches!(
+            byte,
+            b'_' | b'.'
+                | b'$'
+                | b':'
+                | b','
+                | b'+'
+                | b'-'
+                | b'*'
+                | b'/'
+                | b'%'
+                | b'^'
+                | b'<'
+                | b'>'
+                | b'='
+                | b'('
+                | b')'
+                | b'!'
+                | b'\''
+                | b' '
+        )
+        || (byte == b'&' && dialect.allows_strings())
+}
+
+fn string_literal(expression: &str, cursor: usize) -> Result<(String, usize)> {
+    let mut text = String::new();
+    let mut start = cursor + 1;
+    loop {
+        let end = expression[start..]
+            .find('"')
+            .map(|offset| start + offset)
+            .ok_or_else(|| anyhow!("formula contains an unterminated string literal"))?;
+        text.push_str(&expression[start..end]);
+        if expression.as_bytes().get(end + 1) == Some(&b'"') {
+            text.push('"');
+            start = end + 2;
         } else {
-            cursor += 1;
+            return Ok((text, end + 1));
         }
     }
-    Ok(())
-}
-
-fn has_supported_formula_characters(expression: &str) -> bool {
-    expression.is_ascii()
-        && expression.bytes().all(|byte| {
-            byte.is_ascii_alphanumeric()
-                || matches!(
-                    byte,
-                    b'_' | b'.'
-                        | b'$'
-                        | b':'
-                        | b','
-                        | b'+'
-                        | b'-'
-                        | b'*'
-                        | b'/'
-                        | b'%'
-                        | b'^'
-                        | b'<'
-                        | b'>'
-                        | b'='
-                        | b'('
-                        | b')'
-                        | b'!'
-                        | b'\''
-                        | b' '
-                )
-        })
 }
 
-fn validate_quoted_sheet_reference(expression: &str, cursor: usize) -> Result<usize> {
+fn quoted_sheet_reference(expression: &str, cursor: usize) -> Result<(String, usize)> {
     let bytes = expression.as_bytes();
     let end = expression[cursor + 1..]
         .find('\'')
         .map(|offset| cursor + 1 + offset)
         .ok_or_else(|| anyhow!("formula contains an unterminated worksheet name"))?;
-    validate_formula_sheet_name(&expression[cursor + 1..end])?;
+    let sheet = &expression[cursor + 1..end];
+    validate_formula_sheet_name(sheet)?;
     if bytes.get(end + 1) != Some(&b'!') {
         return Err(anyhow!(
             "quoted formula identifiers are only allowed as worksheet references"
         ));
     }
-    Ok(end + 2)
+    Ok((sheet.to_string(), end + 2))
 }
 
 fn starts_formula_identifier(bytes: &[u8], cursor: usize) -> bool {
@@ -121,18 +294,24 @@ fn skip_spaces(bytes: &[u8], mut cursor: usize) -> usize {
     cursor
 }
 
-fn is_numeric_exponent(bytes: &[u8], identifier: &str, start: usize, lookahead: usize) -> bool {
-    let exponent_digit_index = if matches!(bytes.get(lookahead), Some(b'+' | b'-')) {
-        lookahead + 1
-    } else {
-        lookahead
-    };
-    matches!(identifier, "E" | "e")
-        && start > 0
-        && bytes[start - 1].is_ascii_digit()
-        && bytes
-            .get(exponent_digit_index)
-            .is_some_and(u8::is_ascii_digit)
+fn number_end(bytes: &[u8], mut cursor: usize) -> usize {
+    while cursor < bytes.len() && (bytes[cursor].is_ascii_digit() || bytes[cursor] == b'.') {
+        cursor += 1;
+    }
+    if matches!(bytes.get(cursor), Some(b'E' | b'e')) {
+        let digits = if matches!(bytes.get(cursor + 1), Some(b'+' | b'-')) {
+            cursor + 2
+        } else {
+            cursor + 1
+        };
+        if bytes.get(digits).is_some_and(u8::is_ascii_digit) {
+            cursor = digits;
+            while cursor < bytes.len() && bytes[cursor].is_ascii_digit() {
+                cursor += 1;
+            }
+        }
+    }
+    cursor
 }
 
 fn validate_formula_sheet_name(value: &str) -> Result<()> {
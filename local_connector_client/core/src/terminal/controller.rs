@@ -94,6 +94,7 @@ impl LocalConnectorTerminalControllerStore {
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .kill_on_drop(true);
+        crate::sandbox::linux_native::confine_terminal_command(&context, &mut child)?;
         let child = child.spawn().map_err(|err| err.to_string())?;
         let session = register_local_mcp_terminal_session(
             context.clone(),
@@ -30,6 +30,7 @@ pub(super) async fn execute_local_mcp_standalone_command(
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .kill_on_drop(true);
+    crate::sandbox::linux_native::confine_terminal_command(&context, &mut child)?;
     let child = child.spawn().map_err(|err| err.to_string())?;
     let session = register_local_mcp_terminal_session(
         context.clone(),
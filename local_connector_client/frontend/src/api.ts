@@ -6,6 +6,7 @@ export type * from './apiTypes';
 import { request } from './apiTransport';
 import type {
   AgentPromptUpdateStatus,
+  ApprovalProjectKey,
   ApprovalSettings,
   CommandExecutionApprovalDecision,
   CommandHistoryResponse,
@@ -200,6 +201,20 @@ export const api = {
       method: 'POST',
       body: JSON.stringify(payload),
     }),
+  importCommandRules: (payload: {
+    toml: string;
+    project_key?: ApprovalProjectKey | null;
+    replace?: boolean;
+    risk_acknowledged?: boolean;
+  }) =>
+    request<ApprovalSettings>('/api/local/approval/rules/import', {
+      method: 'POST',
+      body: JSON.stringify(payload),
+    }),
+  deleteCommandRule: (id: string) =>
+    request<ApprovalSettings>(`/api/local/approval/rules/${encodeURIComponent(id)}`, {
+      method: 'DELETE',
+    }),
   pendingApprovals: () => request<PendingApprovalsResponse>('/api/local/approval/pending'),
   approvePendingApproval: (
     id: string,
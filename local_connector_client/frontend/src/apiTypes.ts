@@ -113,6 +113,30 @@ export interface CommandWhitelistEntry {
   enabled: boolean;
 }
 
+export interface CommandArgumentConstraint {
+  position: number;
+  allow?: string[];
+  deny?: string[];
+}
+
+export interface CommandRule {
+  name?: string | null;
+  decision: 'allow' | 'deny';
+  scope: 'project' | 'global';
+  pattern: string[];
+  forbidden_args?: string[];
+  args?: CommandArgumentConstraint[];
+  description?: string | null;
+}
+
+export interface CommandRuleEntry {
+  id: string;
+  project_key?: ApprovalProjectKey | null;
+  rule: CommandRule;
+  created_at: string;
+  enabled: boolean;
+}
+
 export interface ApprovalActionAuditDetail {
   key: string;
   value: string;
@@ -182,6 +206,15 @@ export type CommandExecutionApprovalDecision =
   | 'decline'
   | 'cancel';
 
+export interface ApprovalRiskFinding {
+  rule: string;
+  category: string;
+  level: 'low' | 'medium' | 'high';
+  command: string;
+  context?: string | null;
+  detail: string;
+}
+
 export interface PendingApprovalItem {
   id: string;
   request_id: string;
@@ -191,6 +224,7 @@ export interface PendingApprovalItem {
   source: string;
   risk: string;
   reason?: string | null;
+  risk_findings?: ApprovalRiskFinding[];
   created_at: string;
   requested_permissions?: RequestPermissionProfile | null;
   action_audit?: ApprovalActionAudit | null;
@@ -203,6 +237,7 @@ export interface ApprovalSettings {
   settings_revision?: string | null;
   projects: ProjectApprovalState[];
   whitelist: CommandWhitelistEntry[];
+  command_rules?: CommandRuleEntry[];
   history: ApprovalHistoryEntry[];
 }
 
@@ -686,7 +721,7 @@ export interface LocalSkillCatalogResponse {
   total: number;
 }
 
-export type SandboxBackendKind = 'local_process';
+export type SandboxBackendKind = 'local_process' | 'linux_native';
 export type PermissionProfileId = 'read_only' | 'workspace_write' | 'full_access';
 export type SandboxApprovalPolicy = 'on_request' | 'never';
 export type SandboxApprovalReviewer = 'user' | 'auto_review';
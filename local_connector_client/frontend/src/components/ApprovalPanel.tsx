@@ -18,6 +18,7 @@ import {
   type ApprovalActionAudit,
   type ApprovalMode,
   type ApprovalProjectKey,
+  type ApprovalRiskFinding,
   type ApprovalSettings,
   type CommandWhitelistEntry,
   type PendingApprovalItem,
@@ -37,6 +38,7 @@ import {
   formatHistoryTime,
   sourceLabel,
 } from '../utils/terminalFormat';
+import { CommandRulesSection } from './CommandRulesSection';
 
 const actionLabels: Record<string, string> = {
   computer_click: '鼠标点击',
@@ -52,6 +54,8 @@ const actionLabels: Record<string, string> = {
   RunCompleted: '运行完成',
   RunFailed: '运行失败',
   PluginDisabled: '插件禁用',
+  UserPromptSubmit: '提交提示词前',
+  PreCompact: '上下文压缩前',
 };
 
 const auditDetailLabels: Record<string, string> = {
@@ -80,6 +84,14 @@ const auditDetailLabels: Record<string, string> = {
   hook_id: 'Hook ID',
   hook_snapshot_sha256: '签名快照 SHA-256',
   workspace_id: '工作区 ID',
+  tool_name: '工具',
+  reason: 'Hook 说明',
+};
+
+const auditCardTitles: Record<string, string> = {
+  computer_use: 'Computer Use 操作审计',
+  plugin_hook_workspace_write: 'Plugin Hook 工作区写入审批',
+  plugin_hook_ask: 'Plugin Hook 请求确认',
 };
 
 const auditValueLabels: Record<string, string> = {
@@ -241,16 +253,49 @@ function auditValue(value: string): string {
   return auditValueLabels[value] || value;
 }
 
+const riskCategoryLabels: Record<string, string> = {
+  privilege_escalation: '提权',
+  destructive_filesystem: '破坏性文件操作',
+  permission_change: '权限变更',
+  network_egress: '网络外联',
+  remote_code_execution: '远程代码执行',
+  sensitive_path: '敏感路径',
+  system_configuration: '系统配置',
+  cluster_administration: '集群管理',
+  dynamic_code: '动态代码',
+  environment_override: '环境变量覆盖',
+  unparseable: '无法解析',
+};
+
+function RiskFindingList({ findings }: { findings?: ApprovalRiskFinding[] }) {
+  if (!findings?.length) return null;
+  return (
+    <ul className="riskFindingList">
+      {findings.map((finding) => (
+        <li key={`${finding.rule}:${finding.context || ''}:${finding.command}`}>
+          <div className="riskFindingHeader">
+            <span className={riskStatusClass(finding.level)}>{riskLabel(finding.level)}</span>
+            <strong>{riskCategoryLabels[finding.category] || finding.category}</strong>
+            <span>{finding.detail}</span>
+          </div>
+          <code>{finding.command}</code>
+          {finding.context ? <span className="riskFindingContext">位于 {finding.context}</span> : null}
+        </li>
+      ))}
+    </ul>
+  );
+}
+
 function ActionAuditCard({ audit }: { audit?: ApprovalActionAudit | null }) {
-  if (!audit || !['computer_use', 'plugin_hook_workspace_write'].includes(audit.kind)) return null;
-  const isPluginHook = audit.kind === 'plugin_hook_workspace_write';
+  if (!audit || !['computer_use', 'plugin_hook_workspace_write', 'plugin_hook_ask'].includes(audit.kind)) return null;
+  const title = auditCardTitles[audit.kind] || audit.kind;
   const notes = [audit.privacy, audit.safety, audit.recovery]
     .filter((value): value is string => Boolean(value))
     .map((value) => auditNoteLabels[value] || value);
   return (
     <div className="actionAuditCard">
       <div className="actionAuditHeader">
-        <span>{isPluginHook ? 'Plugin Hook 工作区写入审批' : 'Computer Use 操作审计'}</span>
+        <span>{title}</span>
         <strong>{actionLabels[audit.operation] || audit.operation}</strong>
       </div>
       {audit.details?.length ? (
@@ -523,6 +568,7 @@ export function ApprovalPanel() {
                 {sourceLabel(item.source)} · {projectLabel(item.project_key)} · {item.cwd} · {formatHistoryTime(item.created_at)}
               </div>
               {item.reason ? <div className="approvalReason">{item.reason}</div> : null}
+              <RiskFindingList findings={item.risk_findings} />
               {item.requested_permissions ? (
                 <div className="approvalReason">
                   请求的临时权限：{formatRequestedPermissions(item.requested_permissions)}
@@ -546,6 +592,7 @@ export function ApprovalPanel() {
                 {sourceLabel(item.source)} · {projectLabel(item.project_key)} · {item.cwd} · {formatHistoryTime(item.created_at)}
               </div>
               {item.reason ? <div className="approvalReason">{item.reason}</div> : null}
+              <RiskFindingList findings={item.risk_findings} />
               {item.requested_permissions ? (
                 <div className="approvalReason">
                   请求的临时权限：{formatRequestedPermissions(item.requested_permissions)}
@@ -664,6 +711,8 @@ export function ApprovalPanel() {
         />
       </section>
 
+      <CommandRulesSection settings={settings} onSettingsChange={setSettings} />
+
       <section className="panel">
         <div className="panelHeader">
           <div>
@@ -0,0 +1,175 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+import React from 'react';
+import { FileCode2, Trash2 } from 'lucide-react';
+
+import {
+  api,
+  type ApprovalProjectKey,
+  type ApprovalSettings,
+  type CommandRuleEntry,
+} from '../api';
+import { projectLabel } from '../utils/approvalFormat';
+import { formatHistoryTime } from '../utils/terminalFormat';
+
+const exampleRules = `[[command_rules]]
+decision = "allow"
+pattern = "cargo test **"
+
+[[command_rules]]
+name = "feature pushes"
+decision = "allow"
+pattern = "git push origin *"
+forbidden_args = ["main", "*:main", "--force", "-f"]
+
+[[command_rules]]
+decision = "deny"
+scope = "global"
+pattern = "terraform destroy **"
+`;
+
+function projectKeyId(projectKey: ApprovalProjectKey) {
+  return JSON.stringify(projectKey);
+}
+
+function knownProjects(settings: ApprovalSettings): ApprovalProjectKey[] {
+  const keys = new Map<string, ApprovalProjectKey>();
+  for (const key of [
+    ...settings.projects.map((project) => project.project_key),
+    ...settings.whitelist.map((entry) => entry.project_key),
+    ...(settings.command_rules || []).flatMap((entry) => (entry.project_key ? [entry.project_key] : [])),
+  ]) {
+    keys.set(projectKeyId(key), key);
+  }
+  return Array.from(keys.values());
+}
+
+function ruleSummary(entry: CommandRuleEntry) {
+  const rule = entry.rule;
+  const parts = [rule.pattern.join(' ')];
+  if (rule.forbidden_args?.length) {
+    parts.push(`排除 ${rule.forbidden_args.join(', ')}`);
+  }
+  for (const constraint of rule.args || []) {
+    if (constraint.allow?.length) parts.push(`参数 ${constraint.position} ∈ ${constraint.allow.join(', ')}`);
+    if (constraint.deny?.length) parts.push(`参数 ${constraint.position} ∉ ${constraint.deny.join(', ')}`);
+  }
+  return parts.join(' · ');
+}
+
+export function CommandRulesSection({
+  settings,
+  onSettingsChange,
+}: {
+  settings: ApprovalSettings;
+  onSettingsChange: (settings: ApprovalSettings) => void;
+}) {
+  const [toml, setToml] = React.useState('');
+  const [projectId, setProjectId] = React.useState('');
+  const [replace, setReplace] = React.useState(false);
+  const [busy, setBusy] = React.useState(false);
+  const [message, setMessage] = React.useState<string | null>(null);
+  const [error, setError] = React.useState<string | null>(null);
+  const projects = React.useMemo(() => knownProjects(settings), [settings]);
+  const rules = settings.command_rules || [];
+
+  const importRules = async () => {
+    const hasAllow = /decision\s*=\s*["']allow["']/.test(toml);
+    if (
+      hasAllow
+      && !window.confirm('导入允许规则后，匹配的命令将不再弹出审批（高风险命令除外）。确认继续吗？')
+    ) {
+      return;
+    }
+    setBusy(true);
+    setMessage(null);
+    setError(null);
+    try {
+      const next = await api.importCommandRules({
+        toml,
+        project_key: projects.find((key) => projectKeyId(key) === projectId) || null,
+        replace,
+        risk_acknowledged: hasAllow,
+      });
+      onSettingsChange(next);
+      setToml('');
+      setMessage(`已导入，当前共 ${next.command_rules?.length || 0} 条命令规则`);
+    } catch (err) {
+      setError(err instanceof Error ? err.message : '导入命令规则失败');
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const removeRule = async (entry: CommandRuleEntry) => {
+    setError(null);
+    try {
+      onSettingsChange(await api.deleteCommandRule(entry.id));
+    } catch (err) {
+      setError(err instanceof Error ? err.message : '删除命令规则失败');
+    }
+  };
+
+  return (
+    <section className="panel">
+      <div className="panelHeader">
+        <div>
+          <h2><FileCode2 size={18} />命令规则</h2>
+          <p>
+            {rules.length
+              ? `${rules.length} 条规则 · 拒绝规则优先于允许规则和白名单`
+              : '按参数模式允许或拒绝命令，拒绝规则优先生效'}
+          </p>
+        </div>
+      </div>
+      <div className="approvalList">
+        {rules.map((entry) => (
+          <div className="approvalSimpleRow" key={entry.id}>
+            <div>
+              <strong>{entry.rule.name || entry.rule.pattern.join(' ')}</strong>
+              <span>
+                {ruleSummary(entry)} · {entry.project_key ? projectLabel(entry.project_key) : '全部项目'} · {formatHistoryTime(entry.created_at)}
+              </span>
+              {entry.rule.description ? <span>{entry.rule.description}</span> : null}
+            </div>
+            <span className={entry.rule.decision === 'deny' ? 'status bad' : 'status ok'}>
+              {entry.rule.decision === 'deny' ? '拒绝' : '允许'}
+            </span>
+            <button className="ghostButton compact dangerText" onClick={() => void removeRule(entry)}>
+              <Trash2 size={15} />删除
+            </button>
+          </div>
+        ))}
+        {!rules.length ? <div className="emptyState">还没有命令规则。</div> : null}
+      </div>
+      <div className="commandRuleImport">
+        <textarea
+          spellCheck={false}
+          placeholder={exampleRules}
+          value={toml}
+          onChange={(event) => setToml(event.target.value)}
+        />
+        <div className="commandRuleImportActions">
+          <select value={projectId} onChange={(event) => setProjectId(event.target.value)}>
+            <option value="">仅导入全局规则</option>
+            {projects.map((key) => (
+              <option key={projectKeyId(key)} value={projectKeyId(key)}>
+                {projectLabel(key)}
+              </option>
+            ))}
+          </select>
+          <label className="inlineCheck">
+            <input type="checkbox" checked={replace} onChange={(event) => setReplace(event.target.checked)} />
+            替换同范围的已有规则
+          </label>
+          <button className="primaryButton compact" disabled={busy || !toml.trim()} onClick={() => void importRules()}>
+            导入 TOML
+          </button>
+        </div>
+        {message ? <div className="banner">{message}</div> : null}
+        {error ? <div className="formError">{error}</div> : null}
+      </div>
+    </section>
+  );
+}
@@ -189,7 +189,7 @@ export function SandboxPanel({
                   <div className="leaseRow" key={lease.id}>
                     <span className="mono">{lease.lease_id || lease.id}</span>
                     <span className="mono">{lease.run_id}</span>
-                    <span>{lease.backend === 'local_process' ? '本机进程' : lease.backend}</span>
+                    <span>{lease.backend === 'local_process' ? '本机进程' : lease.backend === 'linux_native' ? 'Linux 内核隔离' : lease.backend}</span>
                     <span className={lease.status === 'ready' ? 'status ok' : 'status warn'}>{lease.status}</span>
                   </div>
                 ))}
@@ -5,6 +5,7 @@ import { Cloud, CloudOff, Shield, Sparkles } from 'lucide-react';
 
 import type {
   PermissionProfileId,
+  SandboxBackendKind,
   SandboxCapabilities,
   SandboxNetworkAccess,
   SandboxSettings,
@@ -17,6 +18,7 @@ import {
   recommendedSandboxSettings,
   resolveSandboxPolicyView,
   sandboxBackendDescription,
+  sandboxBackendLabel,
 } from './sandboxPolicyModel';
 
 export function SandboxPolicySettings({
@@ -50,6 +52,13 @@ export function SandboxPolicySettings({
     );
   };
 
+  const setBackend = async (backend: SandboxBackendKind) => {
+    if (backend === view.backend) {
+      return;
+    }
+    await onSave({ default_backend: backend }, '任务运行方式');
+  };
+
   const setAiApproval = async (enabled: boolean) => {
     if (enabled && !window.confirm(
       '开启后，命令审批模型会审核联网和项目外文件请求。AI 可以批准、拒绝或转交给你；模型不可用时会默认拒绝。确定开启吗？',
@@ -88,7 +97,7 @@ export function SandboxPolicySettings({
 
   const restoreRecommendedSettings = async () => {
     await onSave(
-      recommendedSandboxSettings(view.localProcessSelectable),
+      recommendedSandboxSettings(view.localProcessSelectable && view.backend !== 'linux_native'),
       '推荐保护设置',
     );
   };
@@ -116,7 +125,20 @@ export function SandboxPolicySettings({
       <div className="sandboxSimpleSettingsGrid">
         <div className="sandboxSimpleSetting">
           <span className="settingLabel">任务运行方式</span>
-          <strong>本机进程隔离</strong>
+          {view.linuxNativeSelectable || view.backend === 'linux_native' ? (
+            <select
+              value={view.backend}
+              disabled={saving}
+              onChange={(event) => void setBackend(
+                event.target.value === 'linux_native' ? 'linux_native' : 'local_process',
+              )}
+            >
+              <option value="local_process">{sandboxBackendLabel('local_process')}</option>
+              <option value="linux_native">{sandboxBackendLabel('linux_native')}</option>
+            </select>
+          ) : (
+            <strong>本机进程隔离</strong>
+          )}
           <small>{sandboxBackendDescription(view.backend)}</small>
         </div>
 
@@ -46,19 +46,23 @@ export function resolveSandboxPolicyView(
   const localProcessSelectable = capabilities?.backends.some(
     (capability) => capability.backend === 'local_process' && capability.selectable,
   ) === true;
+  const linuxNativeSelectable = capabilities?.backends.some(
+    (capability) => capability.backend === 'linux_native' && capability.selectable,
+  ) === true;
   return {
     approvalMode,
     approvalReviewer,
     backend,
     builtinProfiles,
     customPermissionProfileActive,
+    linuxNativeSelectable,
     localProcessSelectable,
     networkPresentation: describeNetworkAccess(network, approvalMode, backend),
     permissionProfile,
     permissionProfileName,
     recommended:
       !customPermissionProfileActive
-      && backend === 'local_process'
+      && (backend === 'local_process' || backend === 'linux_native')
       && permissionProfile === 'workspace_write'
       && approvalMode === 'user'
       && network.unrestricted !== true
@@ -110,11 +114,17 @@ export function approvalModeDescription(mode: SandboxApprovalMode) {
   return '访问项目外文件或互联网前会先征求你的同意。';
 }
 
-export function sandboxBackendLabel(_backend: SandboxBackendKind) {
+export function sandboxBackendLabel(backend: SandboxBackendKind) {
+  if (backend === 'linux_native') {
+    return 'Linux 内核隔离';
+  }
   return '本机进程权限';
 }
 
-export function sandboxBackendDescription(_backend: SandboxBackendKind) {
+export function sandboxBackendDescription(backend: SandboxBackendKind) {
+  if (backend === 'linux_native') {
+    return '任务进程运行在用户命名空间中，由 Landlock 限制文件访问、seccomp 限制系统调用，网络只能走本机代理或回环地址。';
+  }
   return '任务在授权工作区内通过本机进程运行；文件、网络与审批由 Local Connector 权限策略控制。';
 }
 
@@ -216,8 +226,8 @@ function hasNetworkDomainRules(requirements: SandboxNetworkRequirements) {
     || (requirements.deniedDomains?.length || 0) > 0;
 }
 
-function normalizeSandboxBackend(_value?: string | null): SandboxBackendKind {
-  return 'local_process';
+function normalizeSandboxBackend(value?: string | null): SandboxBackendKind {
+  return value === 'linux_native' ? 'linux_native' : 'local_process';
 }
 
 function normalizePermissionProfile(value?: string | null): PermissionProfileId {
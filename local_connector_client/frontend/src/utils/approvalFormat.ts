@@ -89,6 +89,7 @@ export function decisionSourceLabel(source: string) {
     ai: 'AI',
     full_control: '从不询问',
     static_rule: '静态规则',
+    command_rule: '命令规则',
   };
   return labels[source] || source;
 }
@@ -20,6 +20,7 @@ export function sourceLabel(source: string) {
     plugin_privileged_browser: 'Browser 特权操作',
     browser_privileged_action: 'Browser 特权操作',
     plugin_hook_workspace_write: 'Plugin Hook 工作区写入',
+    plugin_hook_ask: 'Plugin Hook 确认',
   };
   return labels[source] || source;
 }
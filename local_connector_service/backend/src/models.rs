@@ -18,6 +18,7 @@ pub const BINDING_MODE_TERMINAL: &str = "local_terminal";
 pub const BINDING_MODE_SANDBOX: &str = "local_sandbox";
 
 pub const SANDBOX_MODE_LOCAL_PROCESS: &str = "local_process";
+pub const SANDBOX_MODE_LINUX_NATIVE: &str = "linux_native";
 pub const SANDBOX_READINESS_READY: &str = "ready";
 pub const SANDBOX_READINESS_SETUP_REQUIRED: &str = "setup_required";
 pub const SANDBOX_READINESS_UNSUPPORTED: &str = "unsupported";
@@ -404,8 +405,11 @@ pub fn normalize_binding_mode(value: Option<String>) -> String {
     }
 }
 
-pub fn normalize_sandbox_mode(_value: Option<String>) -> String {
-    SANDBOX_MODE_LOCAL_PROCESS.to_string()
+pub fn normalize_sandbox_mode(value: Option<String>) -> String {
+    match value.as_deref().map(str::trim).map(str::to_ascii_lowercase) {
+        Some(value) if value == SANDBOX_MODE_LINUX_NATIVE => SANDBOX_MODE_LINUX_NATIVE.to_string(),
+        _ => SANDBOX_MODE_LOCAL_PROCESS.to_string(),
+    }
 }
 
 pub fn normalize_sandbox_readiness(value: Option<String>) -> String {
@@ -500,6 +504,18 @@ mod tests {
             normalize_sandbox_mode(Some("legacy".to_string())),
             SANDBOX_MODE_LOCAL_PROCESS
         );
+        assert_eq!(
+            normalize_sandbox_mode(Some("docker".to_string())),
+            SANDBOX_MODE_LOCAL_PROCESS
+        );
+    }
+
+    #[test]
+    fn sandbox_mode_keeps_linux_native_backend() {
+        assert_eq!(
+            normalize_sandbox_mode(Some(" Linux_Native ".to_string())),
+            SANDBOX_MODE_LINUX_NATIVE
+        );
     }
 
     #[test]
@@ -10,11 +10,13 @@ use uuid::Uuid;
 
 use crate::tool_registry::{block_on_result, text_result, ToolRegistry};
 
+mod elicitation;
 mod payload;
 mod schema;
 #[cfg(test)]
 mod tests;
 
+pub use self::elicitation::AskUserElicitationHandler;
 use self::payload::{
     build_mixed_choice_input, build_mixed_payload_map, choice_to_value, kv_fields_to_value,
     normalize_choice_limits, normalize_choice_options, normalize_default_selection,
@@ -0,0 +1,168 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use async_trait::async_trait;
+use chatos_mcp_runtime::{McpElicitationHandler, McpElicitationRequest, ToolStreamChunkCallback};
+use serde_json::{json, Map, Value};
+
+use super::{
+    kv_fields_to_value, make_prompt_id, normalize_kv_fields, AskUserPromptPayload, AskUserStoreRef,
+    ASK_USER_PROMPT_TIMEOUT_MS_DEFAULT,
+};
+
+const ELICITATION_MAX_FIELDS: usize = 50;
+
+/// Shows MCP `elicitation/create` requests as ask_user key/value prompts and
+/// turns the user's answer into an `ElicitResult`.
+#[derive(Debug, Clone)]
+pub struct AskUserElicitationHandler {
+    store: AskUserStoreRef,
+    timeout_ms: u64,
+}
+
+impl AskUserElicitationHandler {
+    pub fn new(store: AskUserStoreRef) -> Self {
+        Self {
+            store,
+            timeout_ms: ASK_USER_PROMPT_TIMEOUT_MS_DEFAULT,
+        }
+    }
+
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = timeout_ms;
+        self
+    }
+}
+
+#[async_trait]
+impl McpElicitationHandler for AskUserElicitationHandler {
+    async fn elicit(
+        &self,
+        request: McpElicitationRequest,
+        on_stream_chunk: Option<ToolStreamChunkCallback>,
+    ) -> Result<Value, String> {
+        let conversation_id = request
+            .conversation_id
+            .clone()
+            .filter(|value| !value.trim().is_empty())
+            .ok_or("MCP elicitation needs a conversation to ask in")?;
+        let fields = elicitation_fields(&request.requested_schema)?;
+        let payload = AskUserPromptPayload {
+            prompt_id: make_prompt_id(),
+            conversation_id,
+            conversation_turn_id: request.conversation_turn_id.clone().unwrap_or_default(),
+            tool_call_id: None,
+            kind: "kv".to_string(),
+            title: format!("{} 需要更多信息", request.server_name),
+            message: request.message.clone(),
+            allow_cancel: true,
+            timeout_ms: self.timeout_ms,
+            payload: json!({ "fields": fields }),
+        };
+        let decision = self
+            .store
+            .inner()
+            .execute_prompt(payload, on_stream_chunk)
+            .await?;
+        Ok(match decision.status.as_str() {
+            "ok" | "submitted" => json!({
+                "action": "accept",
+                "content": elicitation_content(
+                    &request.requested_schema,
+                    decision.response.values.as_ref(),
+                ),
+            }),
+            "canceled" | "cancelled" => json!({ "action": "decline" }),
+            _ => json!({ "action": "cancel" }),
+        })
+    }
+}
+
+/// Key/value fields for the flat object schema elicitation allows.
+fn elicitation_fields(schema: &Value) -> Result<Value, String> {
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .filter(|properties| !properties.is_empty())
+        .ok_or("elicitation requestedSchema has no properties")?;
+    let required = schema_required(schema);
+    let fields = properties
+        .iter()
+        .map(|(key, property)| {
+            let mut description = property
+                .get("description")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            if let Some(options) = property.get("enum").and_then(Value::as_array) {
+                let options = options
+                    .iter()
+                    .filter_map(Value::as_str)
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+                description = format!("{description} ({options})").trim().to_string();
+            }
+            json!({
+                "key": key,
+                "label": property.get("title").and_then(Value::as_str).unwrap_or(key),
+                "description": description,
+                "default": property.get("default").map(default_text).unwrap_or_default(),
+                "required": required.contains(&key.as_str()),
+            })
+        })
+        .collect::<Vec<_>>();
+    let fields = normalize_kv_fields(Some(&Value::Array(fields)), ELICITATION_MAX_FIELDS)?;
+    Ok(Value::Array(kv_fields_to_value(fields.as_slice())))
+}
+
+/// Converts the submitted strings back to the types the schema declares.
+/// Values that do not parse are left out so the server can ask again.
+fn elicitation_content(schema: &Value, values: Option<&Value>) -> Value {
+    let empty = Map::new();
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let mut content = Map::new();
+    for (key, property) in properties {
+        let Some(raw) = values.and_then(|values| values.get(key)) else {
+            continue;
+        };
+        let text = match raw {
+            Value::String(text) => text.trim().to_string(),
+            other => other.to_string(),
+        };
+        if text.is_empty() {
+            continue;
+        }
+        let value = match property.get("type").and_then(Value::as_str) {
+            Some("integer") => text.parse::<i64>().ok().map(Value::from),
+            Some("number") => text.parse::<f64>().ok().map(Value::from),
+            Some("boolean") => match text.to_ascii_lowercase().as_str() {
+                "true" | "yes" | "1" | "是" => Some(Value::Bool(true)),
+                "false" | "no" | "0" | "否" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            _ => Some(Value::String(text)),
+        };
+        if let Some(value) = value {
+            content.insert(key.clone(), value);
+        }
+    }
+    Value::Object(content)
+}
+
+fn schema_required(schema: &Value) -> Vec<&str> {
+    schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+fn default_text(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
@@ -112,3 +112,64 @@ fn key_value_prompt_normalizes_fields() {
     );
     assert_eq!(fields[1].get("key").and_then(Value::as_str), Some("repo"));
 }
+
+#[derive(Debug, Clone)]
+struct FixedValuesPromptStore(Value);
+
+#[async_trait]
+impl AskUserStore for FixedValuesPromptStore {
+    async fn execute_prompt(
+        &self,
+        payload: AskUserPromptPayload,
+        _on_stream_chunk: Option<AskUserStreamChunkCallback>,
+    ) -> Result<AskUserDecision, String> {
+        assert_eq!(payload.kind, "kv");
+        assert_eq!(payload.payload["fields"][0]["key"], "branch");
+        Ok(AskUserDecision {
+            status: "ok".to_string(),
+            response: AskUserResponseSubmission {
+                status: "ok".to_string(),
+                values: Some(self.0.clone()),
+                selection: None,
+                reason: None,
+            },
+        })
+    }
+}
+
+#[tokio::test]
+async fn elicitation_asks_through_the_prompt_store_and_types_the_answer() {
+    use chatos_mcp_runtime::{McpElicitationHandler, McpElicitationRequest};
+
+    let handler = AskUserElicitationHandler::new(AskUserStoreRef::new(Arc::new(
+        FixedValuesPromptStore(json!({"branch": "main", "depth": "3", "force": "no"})),
+    )));
+    let request = McpElicitationRequest {
+        server_name: "deployer".to_string(),
+        tool_name: "deployer_deploy".to_string(),
+        conversation_id: Some("conv-1".to_string()),
+        conversation_turn_id: Some("turn-1".to_string()),
+        message: "Which branch?".to_string(),
+        requested_schema: json!({
+            "type": "object",
+            "properties": {
+                "branch": {"type": "string", "title": "Branch"},
+                "depth": {"type": "integer"},
+                "force": {"type": "boolean"}
+            },
+            "required": ["branch"]
+        }),
+    };
+
+    let result = handler.elicit(request.clone(), None).await.unwrap();
+    assert_eq!(
+        result,
+        json!({"action": "accept", "content": {"branch": "main", "depth": 3, "force": false}})
+    );
+
+    let without_conversation = McpElicitationRequest {
+        conversation_id: None,
+        ..request
+    };
+    assert!(handler.elicit(without_conversation, None).await.is_err());
+}
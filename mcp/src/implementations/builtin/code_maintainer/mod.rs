@@ -6,6 +6,7 @@ mod diff;
 mod edit;
 mod fs_ops;
 mod outcome;
+mod patch;
 mod registration_read;
 mod registration_write;
 mod revision;
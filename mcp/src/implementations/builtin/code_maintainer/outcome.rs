@@ -80,6 +80,10 @@ pub fn classify_file_modification_error(error: &str) -> FileModificationOutcome
         "failed to parse patch",
         "fallback parse failed",
         "move target already exists",
+        "move source is not a file",
+        "destination must differ",
+        "already reverted",
+        "cannot be restored",
         "multiple conflicting actions",
         "outside workspace",
         "path traversal",
@@ -0,0 +1,482 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use serde::Serialize;
+
+/// Context lines that may be dropped from either end of a hunk when it does
+/// not match as written, like `patch --fuzz=2`.
+const MAX_FUZZ: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HunkLine {
+    Context,
+    Remove,
+    Add,
+}
+
+#[derive(Debug, Clone)]
+struct Hunk {
+    old_start: Option<usize>,
+    lines: Vec<(HunkLine, String)>,
+    no_newline_at_end: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HunkStatus {
+    Applied,
+    AlreadyApplied,
+    Failed,
+}
+
+/// What happened to one hunk. `line` is the 1-based line in the patched
+/// file where the hunk landed.
+#[derive(Debug, Clone, Serialize)]
+pub struct HunkReport {
+    pub hunk: usize,
+    pub status: HunkStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    pub fuzz: usize,
+    pub whitespace_insensitive: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct PatchOutput {
+    pub content: String,
+    pub changed: bool,
+    pub hunks: Vec<HunkReport>,
+}
+
+/// A patch that did not apply. Every hunk is still reported so the caller can
+/// fix only the ones that failed.
+#[derive(Debug, Clone)]
+pub struct PatchError {
+    pub message: String,
+    pub hunks: Vec<HunkReport>,
+}
+
+impl From<String> for PatchError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            hunks: Vec::new(),
+        }
+    }
+}
+
+/// Applies a single-file unified diff to `original`. Hunks are located near
+/// their `@@` line numbers, falling back to whitespace-insensitive matching
+/// and then to trimmed context. The patch applies completely or not at all.
+pub fn apply_unified_patch(original: &str, patch: &str) -> Result<PatchOutput, PatchError> {
+    let hunks = parse_unified_patch(patch)?;
+    let line_ending = if original.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut lines = original
+        .split('\n')
+        .map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
+        .collect::<Vec<_>>();
+    let mut trailing_newline = original.is_empty() || original.ends_with('\n');
+    if trailing_newline {
+        lines.pop();
+    }
+
+    let mut reports = Vec::with_capacity(hunks.len());
+    let mut delta = 0isize;
+    let mut floor = 0usize;
+    for (index, hunk) in hunks.iter().enumerate() {
+        let report = apply_hunk(&mut lines, hunk, index + 1, &mut delta, &mut floor);
+        if report.status != HunkStatus::Failed && hunk.no_newline_at_end {
+            trailing_newline = false;
+        }
+        reports.push(report);
+    }
+
+    let failed = reports
+        .iter()
+        .filter(|report| report.status == HunkStatus::Failed)
+        .collect::<Vec<_>>();
+    if !failed.is_empty() {
+        let ambiguous = failed.iter().all(|report| {
+            report
+                .error
+                .as_deref()
+                .is_some_and(|error| error.contains("candidate matches"))
+        });
+        let numbers = failed
+            .iter()
+            .map(|report| report.hunk.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let message = if ambiguous {
+            format!("Patch hunk(s) {numbers} have several candidate matches; add more context.")
+        } else {
+            format!("Patch context not found in file for hunk(s) {numbers}.")
+        };
+        return Err(PatchError {
+            message,
+            hunks: reports,
+        });
+    }
+
+    let mut content = lines.join(line_ending);
+    if trailing_newline && !lines.is_empty() {
+        content.push_str(line_ending);
+    }
+    Ok(PatchOutput {
+        changed: content != original,
+        content,
+        hunks: reports,
+    })
+}
+
+fn apply_hunk(
+    lines: &mut Vec<String>,
+    hunk: &Hunk,
+    number: usize,
+    delta: &mut isize,
+    floor: &mut usize,
+) -> HunkReport {
+    let mut report = HunkReport {
+        hunk: number,
+        status: HunkStatus::Failed,
+        line: None,
+        fuzz: 0,
+        whitespace_insensitive: false,
+        error: None,
+    };
+    let expected = hunk
+        .old_start
+        .map(|start| (start as isize + *delta).max(0) as usize);
+    let leading = hunk
+        .lines
+        .iter()
+        .take_while(|(kind, _)| *kind == HunkLine::Context)
+        .count();
+    let trailing = hunk
+        .lines
+        .iter()
+        .rev()
+        .take_while(|(kind, _)| *kind == HunkLine::Context)
+        .count();
+
+    let mut ambiguous = None;
+    for fuzz in 0..=MAX_FUZZ {
+        let skip_front = fuzz.min(leading);
+        let skip_back = fuzz.min(trailing).min(hunk.lines.len() - skip_front);
+        if fuzz > 0 && skip_front == 0 && skip_back == 0 {
+            break;
+        }
+        let body = &hunk.lines[skip_front..hunk.lines.len() - skip_back];
+        let expected = expected.map(|at| at + skip_front);
+        let old = side(body, HunkLine::Add);
+        let new = side(body, HunkLine::Remove);
+        if fuzz > 0 && old.is_empty() {
+            // Trimming all context would turn the hunk into a blind insert.
+            break;
+        }
+        for normalize in [Normalize::None, Normalize::TrimEnd, Normalize::Trim] {
+            if old.is_empty() {
+                let at = expected.unwrap_or(lines.len()).clamp(*floor, lines.len());
+                if new.is_empty()
+                    || find_nearest(lines, &new, *floor, Some(at), normalize) == Ok(at)
+                {
+                    report.status = HunkStatus::AlreadyApplied;
+                } else {
+                    splice(lines, at, 0, body);
+                    report.status = HunkStatus::Applied;
+                    *delta += new.len() as isize;
+                }
+                *floor = at + new.len();
+                report.line = Some(at + 1);
+                return report;
+            }
+            match find_nearest(lines, &old, *floor, expected, normalize) {
+                Ok(at) => {
+                    splice(lines, at, old.len(), body);
+                    *delta += new.len() as isize - old.len() as isize;
+                    *floor = at + new.len();
+                    report.status = HunkStatus::Applied;
+                    report.line = Some(at + 1);
+                    report.fuzz = fuzz;
+                    report.whitespace_insensitive = normalize != Normalize::None;
+                    return report;
+                }
+                Err(Some(count)) => {
+                    ambiguous.get_or_insert(count);
+                }
+                Err(None) => {}
+            }
+            if old != new {
+                if let Ok(at) = find_nearest(lines, &new, *floor, expected, normalize) {
+                    *floor = at + new.len();
+                    report.status = HunkStatus::AlreadyApplied;
+                    report.line = Some(at + 1);
+                    report.fuzz = fuzz;
+                    report.whitespace_insensitive = normalize != Normalize::None;
+                    return report;
+                }
+            }
+        }
+    }
+    report.error = Some(match ambiguous {
+        Some(count) => format!("{count} candidate matches and no line number to choose one"),
+        None => "context not found".to_string(),
+    });
+    report
+}
+
+/// Replaces `old_len` lines at `at` with the hunk's new side. Context lines
+/// keep the file's own text so whitespace-insensitive matches do not rewrite
+/// them.
+fn splice(lines: &mut Vec<String>, at: usize, old_len: usize, body: &[(HunkLine, String)]) {
+    let existing = lines[at..at + old_len].to_vec();
+    let mut old_index = 0usize;
+    let mut replacement = Vec::with_capacity(body.len());
+    for (kind, text) in body {
+        match kind {
+            HunkLine::Context => {
+                replacement.push(existing[old_index].clone());
+                old_index += 1;
+            }
+            HunkLine::Remove => old_index += 1,
+            HunkLine::Add => replacement.push(text.clone()),
+        }
+    }
+    lines.splice(at..at + old_len, replacement);
+}
+
+fn side(body: &[(HunkLine, String)], skipped: HunkLine) -> Vec<&str> {
+    body.iter()
+        .filter(|(kind, _)| *kind != skipped)
+        .map(|(_, text)| text.as_str())
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Normalize {
+    None,
+    TrimEnd,
+    Trim,
+}
+
+impl Normalize {
+    fn eq(self, left: &str, right: &str) -> bool {
+        match self {
+            Self::None => left == right,
+            Self::TrimEnd => left.trim_end() == right.trim_end(),
+            Self::Trim => left.trim() == right.trim(),
+        }
+    }
+}
+
+/// Position of `needle` at or after `floor`, closest to `expected`.
+/// `Err(Some(n))` means `n` matches with no line number to pick one.
+fn find_nearest(
+    lines: &[String],
+    needle: &[&str],
+    floor: usize,
+    expected: Option<usize>,
+    normalize: Normalize,
+) -> Result<usize, Option<usize>> {
+    if needle.len() > lines.len() {
+        return Err(None);
+    }
+    let matches = (floor..=lines.len() - needle.len())
+        .filter(|start| {
+            needle
+                .iter()
+                .enumerate()
+                .all(|(offset, text)| normalize.eq(lines[start + offset].as_str(), text))
+        })
+        .collect::<Vec<_>>();
+    match (matches.as_slice(), expected) {
+        ([], _) => Err(None),
+        ([only], _) => Ok(*only),
+        (_, Some(expected)) => Ok(*matches
+            .iter()
+            .min_by_key(|start| start.abs_diff(expected))
+            .unwrap_or(&matches[0])),
+        (_, None) => Err(Some(matches.len())),
+    }
+}
+
+fn parse_unified_patch(patch: &str) -> Result<Vec<Hunk>, String> {
+    let lines = patch
+        .trim_end_matches(['\n', '\r'])
+        .lines()
+        .collect::<Vec<_>>();
+    let mut hunks: Vec<Hunk> = Vec::new();
+    // Lines the current hunk header still promises, used to tell a removed
+    // `-- x` line from a `--- a/file` header.
+    let mut remaining = 0usize;
+    for (index, line) in lines.iter().enumerate() {
+        let in_body = remaining > 0;
+        if line.starts_with("@@") {
+            let (old_start, counts) = parse_hunk_header(line)?;
+            remaining = counts;
+            hunks.push(Hunk {
+                old_start,
+                lines: Vec::new(),
+                no_newline_at_end: false,
+            });
+            continue;
+        }
+        let is_file_header = line.starts_with("diff ")
+            || (line.starts_with("--- ")
+                && lines
+                    .get(index + 1)
+                    .is_some_and(|next| next.starts_with("+++ ")));
+        if !in_body && is_file_header && !hunks.is_empty() {
+            return Err("Failed to parse patch: it touches more than one file; stage one apply_patch operation per path.".to_string());
+        }
+        let Some(hunk) = hunks.last_mut() else {
+            // `diff --git`, `index`, `---` and `+++` headers before the first hunk.
+            continue;
+        };
+        let entry = match line.chars().next() {
+            Some(' ') => (HunkLine::Context, &line[1..]),
+            Some('-') => (HunkLine::Remove, &line[1..]),
+            Some('+') => (HunkLine::Add, &line[1..]),
+            Some('\\') => {
+                if hunk
+                    .lines
+                    .last()
+                    .is_some_and(|(kind, _)| *kind != HunkLine::Remove)
+                {
+                    hunk.no_newline_at_end = true;
+                }
+                continue;
+            }
+            // Blank context lines often lose their leading space in transit.
+            None => (HunkLine::Context, ""),
+            Some(_) => {
+                return Err(format!(
+                    "Failed to parse patch: unexpected line {} in hunk {}: {line}",
+                    index + 1,
+                    hunks.len()
+                ))
+            }
+        };
+        remaining = remaining.saturating_sub(1);
+        hunk.lines.push((entry.0, entry.1.to_string()));
+    }
+    if hunks.is_empty() {
+        return Err("Patch does not contain any @@ hunks.".to_string());
+    }
+    if let Some(number) = hunks.iter().position(|hunk| {
+        hunk.lines
+            .iter()
+            .all(|(kind, _)| *kind == HunkLine::Context)
+    }) {
+        return Err(format!(
+            "Patch does not contain any changes in hunk {}.",
+            number + 1
+        ));
+    }
+    Ok(hunks)
+}
+
+/// Reads `@@ -l,s +l,s @@` into the 0-based line the old side starts at and
+/// the number of body lines the header promises. A bare `@@` has no position,
+/// so the hunk is located by content alone.
+fn parse_hunk_header(header: &str) -> Result<(Option<usize>, usize), String> {
+    let invalid = || format!("Failed to parse patch hunk header: {header}");
+    let ranges = header.trim_start_matches('@').trim_start();
+    if ranges.is_empty() || ranges.starts_with('@') {
+        return Ok((None, 0));
+    }
+    let mut parts = ranges.split_whitespace();
+    let old = parts
+        .next()
+        .and_then(|range| range.strip_prefix('-'))
+        .ok_or_else(invalid)?;
+    let new = parts
+        .next()
+        .and_then(|range| range.strip_prefix('+'))
+        .unwrap_or("0,0");
+    let (old_start, old_count) = parse_range(old).ok_or_else(invalid)?;
+    let (_, new_count) = parse_range(new).ok_or_else(invalid)?;
+    // An empty old side names the line to insert after, not the first line.
+    let index = if old_count == 0 {
+        old_start
+    } else {
+        old_start.saturating_sub(1)
+    };
+    // Context lines count toward both sides; the total is only a lower bound.
+    Ok((Some(index), old_count.max(new_count)))
+}
+
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_several_hunks_with_shifted_line_numbers() {
+        let original = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let patch = "--- a/file.txt\n+++ b/file.txt\n@@ -1,3 +1,4 @@\n a\n+a2\n b\n c\n@@ -6,3 +7,3 @@\n f\n-g\n+G\n h\n";
+        let output = apply_unified_patch(original, patch).unwrap();
+        assert_eq!(output.content, "a\na2\nb\nc\nd\ne\nf\nG\nh\n");
+        assert!(output.changed);
+        assert_eq!(output.hunks[1].line, Some(7));
+    }
+
+    #[test]
+    fn fuzzy_matching_tolerates_whitespace_and_stale_context() {
+        let original = "fn main() {\n    let x = 1;\n    println!(\"{x}\");\n}\n";
+        let patch = "@@ -1,4 +1,4 @@\n fn main() {  \n-  let x = 1;\n+  let x = 2;\n     println!(\"{x}\");\n-}\n+} // end\n";
+        let output = apply_unified_patch(original, patch).unwrap();
+        assert_eq!(
+            output.content,
+            "fn main() {\n  let x = 2;\n    println!(\"{x}\");\n} // end\n"
+        );
+        assert!(output.hunks[0].whitespace_insensitive);
+
+        let stale = "@@ -2,3 +2,3 @@\n WRONG\n     let x = 1;\n-    println!(\"{x}\");\n+    dbg!(x);\n }\n";
+        let output = apply_unified_patch(original, stale).unwrap();
+        assert!(output.content.contains("    dbg!(x);\n"));
+        assert_eq!(output.hunks[0].fuzz, 1);
+    }
+
+    #[test]
+    fn reports_every_hunk_when_one_fails_and_detects_reapplied_patches() {
+        let original = "one\ntwo\nthree\n";
+        let patch = "@@ -1,1 +1,1 @@\n-one\n+ONE\n@@ -3,1 +3,1 @@\n-missing\n+gone\n";
+        let error = apply_unified_patch(original, patch).unwrap_err();
+        assert!(error.message.contains("Patch context not found"));
+        assert_eq!(error.hunks[0].status, HunkStatus::Applied);
+        assert_eq!(error.hunks[1].status, HunkStatus::Failed);
+
+        let patch = "@@ -1,1 +1,1 @@\n-one\n+ONE\n";
+        let once = apply_unified_patch(original, patch).unwrap();
+        let twice = apply_unified_patch(once.content.as_str(), patch).unwrap();
+        assert!(!twice.changed);
+        assert_eq!(twice.hunks[0].status, HunkStatus::AlreadyApplied);
+    }
+
+    #[test]
+    fn creates_files_and_rejects_patches_without_hunks() {
+        let output = apply_unified_patch(
+            "",
+            "--- /dev/null\n+++ b/new.txt\n@@ -0,0 +1,2 @@\n+hello\n+world\n",
+        )
+        .unwrap();
+        assert_eq!(output.content, "hello\nworld\n");
+        assert!(apply_unified_patch("a\n", "just text")
+            .unwrap_err()
+            .message
+            .contains("does not contain"));
+    }
+}
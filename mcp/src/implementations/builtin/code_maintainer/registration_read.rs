@@ -5,8 +5,11 @@ use serde_json::{json, Value};
 use std::sync::{Arc, Mutex};
 
 use super::fs_ops::FsOps;
+use super::outcome::FileModificationOutcome;
+use super::registration_write::{committed_session_summary, optional_usize};
 use super::revision::ModificationRevisionGuard;
 use super::service::CodeMaintainerService;
+use super::storage::ChangeLogStore;
 use super::utils::format_bytes;
 
 use crate::tool_registry::text_result;
@@ -14,6 +17,7 @@ use crate::tool_registry::text_result;
 pub(super) fn register_read_tools(
     service: &mut CodeMaintainerService,
     fs_ops: FsOps,
+    change_log: Arc<Mutex<ChangeLogStore>>,
     revision_guard: Arc<Mutex<ModificationRevisionGuard>>,
     workspace_note: &str,
     max_file_bytes: i64,
@@ -33,6 +37,7 @@ pub(super) fn register_read_tools(
     );
     register_list_dir_tool(service, fs_ops.clone(), workspace_note);
     register_search_text_tool(service, fs_ops, workspace_note);
+    register_list_edit_sessions_tool(service, change_log);
 }
 
 fn register_read_file_raw_tool(
@@ -237,3 +242,55 @@ fn register_search_text_tool(
         }),
     );
 }
+
+fn register_list_edit_sessions_tool(
+    service: &mut CodeMaintainerService,
+    change_log: Arc<Mutex<ChangeLogStore>>,
+) {
+    service.register_tool(
+        "list_edit_sessions",
+        "List recently committed edit sessions, newest first, with the paths each one changed and whether it was reverted. scope=conversation (default) covers the current conversation, scope=run only the current run.",
+        json!({
+            "type": "object",
+            "properties": {
+                "scope": { "type": "string", "enum": ["conversation", "run"] },
+                "limit": { "type": "integer", "minimum": 1, "maximum": MAX_LISTED_SESSIONS }
+            },
+            "additionalProperties": false
+        }),
+        Arc::new(move |args, ctx| {
+            let scope = args
+                .get("scope")
+                .and_then(Value::as_str)
+                .unwrap_or("conversation");
+            if !matches!(scope, "conversation" | "run") {
+                return Err("scope must be conversation or run".to_string());
+            }
+            let limit = optional_usize(&args, "limit")
+                .unwrap_or(DEFAULT_LISTED_SESSIONS)
+                .clamp(1, MAX_LISTED_SESSIONS);
+            let sessions = change_log
+                .lock()
+                .map_err(|_| "change log unavailable".to_string())?
+                .list_sessions()?
+                .into_iter()
+                .filter(|session| session.conversation_id == ctx.conversation_id)
+                .filter(|session| scope == "conversation" || session.run_id == ctx.run_id)
+                .take(limit)
+                .map(|session| committed_session_summary(&session))
+                .collect::<Vec<_>>();
+            Ok(text_result(json!({
+                "outcome": FileModificationOutcome::AlreadyApplied,
+                "changed": false,
+                "changed_target_count": 0,
+                "result": {
+                    "scope": scope,
+                    "sessions": sessions,
+                }
+            })))
+        }),
+    );
+}
+
+const DEFAULT_LISTED_SESSIONS: usize = 20;
+const MAX_LISTED_SESSIONS: usize = 100;
@@ -11,11 +11,12 @@ use super::diff::{build_diff, read_text_for_diff, DiffInput};
 use super::edit::{apply_edit_text, EditMatchInfo, EditRequest};
 use super::fs_ops::FsOps;
 use super::outcome::{classify_file_modification_error, FileModificationOutcome};
+use super::patch::{apply_unified_patch, HunkReport, PatchError};
 use super::revision::ModificationRevisionGuard;
 use super::service::{CodeMaintainerHooksRef, CodeMaintainerService, ToolContext};
 use super::session::{EditSession, EditSessionStore, EntryKind, EntrySnapshot, SessionFileState};
-use super::storage::ChangeLogStore;
-use super::utils::{generate_id, sha256_bytes};
+use super::storage::{ChangeLogStore, CommittedFile, CommittedSession};
+use super::utils::{generate_id, now_iso, sha256_bytes};
 
 use crate::tool_registry::text_result;
 
@@ -46,19 +47,30 @@ pub(super) fn register_write_tools(
         workspace_note,
     );
     register_commit_edit_session_tool(
+        service,
+        fs_ops.clone(),
+        change_log.clone(),
+        revision_guard.clone(),
+        session_store.clone(),
+        allow_writes,
+        max_file_bytes,
+        max_write_bytes,
+        writes_note,
+        workspace_note,
+        hooks.clone(),
+    );
+    register_abort_edit_session_tool(service, session_store, workspace_note);
+    register_revert_edit_session_tool(
         service,
         fs_ops,
         change_log,
         revision_guard,
-        session_store.clone(),
         allow_writes,
         max_file_bytes,
-        max_write_bytes,
         writes_note,
         workspace_note,
         hooks,
     );
-    register_abort_edit_session_tool(service, session_store, workspace_note);
 }
 
 fn register_open_edit_session_tool(
@@ -126,7 +138,7 @@ fn register_stage_edit_batch_tool(
     service.register_tool(
         "stage_edit_batch",
         &format!(
-            "Stage one or more ordered edit operations into an existing write session without touching the file system yet. Multiple operations may target the same file; they will be applied sequentially to the session snapshot. For the first operation that touches a path, expected_sha256 must match the latest successful read of the current file, or be null only when the path is confirmed absent (and for directory deletes).\n{}\nSupported operation kinds: write, replace_text, append, delete.",
+            "Stage one or more ordered edit operations into an existing write session without touching the file system yet. Multiple operations may target the same file; they will be applied sequentially to the session snapshot. For the first operation that touches a path, expected_sha256 must match the latest successful read of the current file, or be null only when the path is confirmed absent (and for directory deletes).\n{}\nSupported operation kinds: write, replace_text, append, delete, apply_patch (a unified diff for one file in `patch`; hunks are matched near their line numbers with whitespace and context fuzz, and the whole patch fails with a per-hunk report if any hunk does not apply), move/rename (moves the file at `path` to the absent `destination`, keeping its content; expected_sha256 guards the source).",
             workspace_note
         ),
         json!({
@@ -141,10 +153,18 @@ fn register_stage_edit_batch_tool(
                         "properties": {
                             "kind": {
                                 "type": "string",
-                                "enum": ["write", "replace_text", "append", "delete"]
+                                "enum": ["write", "replace_text", "append", "delete", "apply_patch", "move", "rename"]
                             },
                             "path": { "type": "string" },
                             "content": { "type": "string" },
+                            "patch": {
+                                "type": "string",
+                                "description": "Unified diff with one or more @@ hunks for this path. File headers are optional."
+                            },
+                            "destination": {
+                                "type": "string",
+                                "description": "New workspace path for move/rename. It must not exist."
+                            },
                             "old_text": { "type": "string" },
                             "new_text": { "type": "string" },
                             "start_line": {
@@ -209,6 +229,7 @@ fn register_stage_edit_batch_tool(
                     )?;
                     if outcome.changed {
                         batch_changed_paths.insert(outcome.path.clone());
+                        batch_changed_paths.extend(outcome.moved_from.clone());
                     }
                     if let Some(info) = outcome.match_info {
                         batch_matches.push(json!({
@@ -216,6 +237,12 @@ fn register_stage_edit_batch_tool(
                             "match": info,
                         }));
                     }
+                    if let Some(hunks) = outcome.hunks {
+                        batch_matches.push(json!({
+                            "path": outcome.path,
+                            "hunks": hunks,
+                        }));
+                    }
                 }
 
                 staged_session.staged_operation_count += operations.len();
@@ -288,6 +315,7 @@ fn register_commit_edit_session_tool(
                     .take(session_id, ctx.run_id, ctx.conversation_id)?;
                 commit_session(
                     session,
+                    "commit_edit_session",
                     &fs_ops,
                     &change_log,
                     &revision_guard,
@@ -296,6 +324,7 @@ fn register_commit_edit_session_tool(
                     max_write_bytes,
                     hooks.as_ref(),
                 )
+                .map(text_result)
             })();
             record_file_modification_outcome("commit_edit_session", ctx, &invocation);
             invocation
@@ -347,11 +376,73 @@ fn register_abort_edit_session_tool(
     );
 }
 
+fn register_revert_edit_session_tool(
+    service: &mut CodeMaintainerService,
+    fs_ops: FsOps,
+    change_log: Arc<Mutex<ChangeLogStore>>,
+    revision_guard: SharedRevisionGuard,
+    allow_writes: bool,
+    max_file_bytes: i64,
+    writes_note: &str,
+    workspace_note: &str,
+    hooks: Option<CodeMaintainerHooksRef>,
+) {
+    service.register_tool(
+        "revert_edit_session",
+        &format!(
+            "Undo a committed edit session of this conversation: every path it touched is restored to its pre-commit content in one atomic commit (created files are deleted, deleted files come back, moves are moved back). Refuses with a stale_context conflict report, changing nothing, if any of those paths changed after the session was committed. Use list_edit_sessions to find session ids. {}\n{}",
+            writes_note, workspace_note
+        ),
+        json!({
+            "type": "object",
+            "properties": {
+                "session_id": { "type": "string", "minLength": 1 }
+            },
+            "additionalProperties": false,
+            "required": ["session_id"]
+        }),
+        Arc::new(move |args, ctx| {
+            let invocation = (|| {
+                if !allow_writes {
+                    return Err("Writes are disabled.".to_string());
+                }
+                let session_id = required_string(&args, "session_id")?;
+                revert_session(
+                    session_id,
+                    &fs_ops,
+                    &change_log,
+                    &revision_guard,
+                    ctx,
+                    max_file_bytes,
+                    hooks.as_ref(),
+                )
+                .map(text_result)
+            })();
+            record_file_modification_outcome("revert_edit_session", ctx, &invocation);
+            invocation
+        }),
+    );
+}
+
 #[derive(Debug)]
 struct StageOutcome {
     path: String,
     changed: bool,
     match_info: Option<EditMatchInfo>,
+    hunks: Option<Vec<HunkReport>>,
+    moved_from: Option<String>,
+}
+
+impl StageOutcome {
+    fn new(path: String, changed: bool) -> Self {
+        Self {
+            path,
+            changed,
+            match_info: None,
+            hunks: None,
+            moved_from: None,
+        }
+    }
 }
 
 fn apply_stage_operation(
@@ -395,6 +486,16 @@ fn apply_stage_operation(
             max_write_bytes,
         ),
         "delete" => stage_delete(session, operation, path, fs_ops, revision_guard, ctx),
+        "apply_patch" => stage_apply_patch(
+            session,
+            operation,
+            path,
+            fs_ops,
+            revision_guard,
+            ctx,
+            max_write_bytes,
+        ),
+        "move" | "rename" => stage_move(session, operation, path, fs_ops, revision_guard, ctx),
         other => Err(format!("unsupported operation kind: {other}")),
     }
 }
@@ -415,11 +516,7 @@ fn stage_write(
             && state.working.content.as_deref() == Some(content.as_str())
         {
             state.staged_operations += 1;
-            return Ok(StageOutcome {
-                path: state.path.clone(),
-                changed: false,
-                match_info: None,
-            });
+            return Ok(StageOutcome::new(state.path.clone(), false));
         }
     } else {
         let snapshot = load_entry_snapshot(fs_ops, path)?;
@@ -428,11 +525,7 @@ fn stage_write(
             let mut state = SessionFileState::new(path, snapshot);
             state.staged_operations += 1;
             session.files.insert(path.to_string(), state);
-            return Ok(StageOutcome {
-                path: path.to_string(),
-                changed: false,
-                match_info: None,
-            });
+            return Ok(StageOutcome::new(path.to_string(), false));
         }
     }
     let expected = expected_revision(operation, "expected_sha256")?;
@@ -444,11 +537,7 @@ fn stage_write(
         || state.working.kind != EntryKind::File;
     state.working = EntrySnapshot::file(content.clone(), sha256_bytes(content.as_bytes()));
     state.staged_operations += 1;
-    Ok(StageOutcome {
-        path: state.path.clone(),
-        changed,
-        match_info: None,
-    })
+    Ok(StageOutcome::new(state.path.clone(), changed))
 }
 
 fn stage_replace_text(
@@ -487,9 +576,8 @@ fn stage_replace_text(
                 if !edit_result.changed {
                     state.staged_operations += 1;
                     return Ok(StageOutcome {
-                        path: state.path.clone(),
-                        changed: false,
                         match_info: Some(edit_result.info),
+                        ..StageOutcome::new(state.path.clone(), false)
                     });
                 }
             }
@@ -504,9 +592,8 @@ fn stage_replace_text(
                     state.staged_operations += 1;
                     session.files.insert(path.to_string(), state);
                     return Ok(StageOutcome {
-                        path: path.to_string(),
-                        changed: false,
                         match_info: Some(edit_result.info),
+                        ..StageOutcome::new(path.to_string(), false)
                     });
                 }
             }
@@ -548,9 +635,8 @@ fn stage_replace_text(
     );
     state.staged_operations += 1;
     Ok(StageOutcome {
-        path: state.path.clone(),
-        changed,
         match_info: Some(edit_result.info),
+        ..StageOutcome::new(state.path.clone(), changed)
     })
 }
 
@@ -576,11 +662,7 @@ fn stage_append(
         || next != state.working.content.clone().unwrap_or_default();
     state.working = EntrySnapshot::file(next.clone(), sha256_bytes(next.as_bytes()));
     state.staged_operations += 1;
-    Ok(StageOutcome {
-        path: state.path.clone(),
-        changed,
-        match_info: None,
-    })
+    Ok(StageOutcome::new(state.path.clone(), changed))
 }
 
 fn stage_delete(
@@ -596,10 +678,152 @@ fn stage_delete(
     let changed = state.working.kind != EntryKind::Missing;
     state.working = EntrySnapshot::missing();
     state.staged_operations += 1;
+    Ok(StageOutcome::new(state.path.clone(), changed))
+}
+
+fn stage_apply_patch(
+    session: &mut EditSession,
+    operation: &Value,
+    path: &str,
+    fs_ops: &FsOps,
+    revision_guard: &SharedRevisionGuard,
+    ctx: &ToolContext<'_>,
+    max_write_bytes: i64,
+) -> Result<StageOutcome, String> {
+    let patch = required_string(operation, "patch")?;
+
+    if let Some(state) = session.files.get_mut(path) {
+        if state.working.kind == EntryKind::File {
+            let current = state.working.content.clone().unwrap_or_default();
+            if let Ok(output) = apply_unified_patch(current.as_str(), patch) {
+                if !output.changed {
+                    state.staged_operations += 1;
+                    return Ok(StageOutcome {
+                        hunks: Some(output.hunks),
+                        ..StageOutcome::new(state.path.clone(), false)
+                    });
+                }
+            }
+        }
+    } else {
+        let snapshot = load_entry_snapshot(fs_ops, path)?;
+        if snapshot.kind == EntryKind::File {
+            let current = snapshot.content.clone().unwrap_or_default();
+            if let Ok(output) = apply_unified_patch(current.as_str(), patch) {
+                if !output.changed {
+                    let mut state = SessionFileState::new(path, snapshot);
+                    state.staged_operations += 1;
+                    session.files.insert(path.to_string(), state);
+                    return Ok(StageOutcome {
+                        hunks: Some(output.hunks),
+                        ..StageOutcome::new(path.to_string(), false)
+                    });
+                }
+            }
+        }
+    }
+
+    let expected = expected_revision(operation, "expected_sha256")?;
+    let state = get_or_load_session_file(session, path, expected, fs_ops, revision_guard, ctx)?;
+    if state.working.kind == EntryKind::Directory {
+        return Err("Target path is a directory.".to_string());
+    }
+    let current = state.working.content.clone().unwrap_or_default();
+    let output = apply_unified_patch(current.as_str(), patch).map_err(|err| {
+        let outcome = classify_file_modification_error(err.message.as_str());
+        if matches!(
+            outcome,
+            FileModificationOutcome::StaleContext | FileModificationOutcome::ExpectedMatch
+        ) {
+            mark_failed_modification(revision_guard, ctx, path);
+            patch_modification_error(outcome, &err, path, state.base.sha256.as_deref())
+        } else {
+            err.message
+        }
+    })?;
+    enforce_write_size(&output.content, max_write_bytes)?;
+    let changed = output.changed || state.working.kind != EntryKind::File;
+    state.working = EntrySnapshot::file(
+        output.content.clone(),
+        sha256_bytes(output.content.as_bytes()),
+    );
+    state.staged_operations += 1;
     Ok(StageOutcome {
-        path: state.path.clone(),
-        changed,
-        match_info: None,
+        hunks: Some(output.hunks),
+        ..StageOutcome::new(state.path.clone(), changed)
+    })
+}
+
+/// Stages a move as a delete of `path` plus a destination carrying the same
+/// snapshot, so later operations in the session can still edit either side.
+/// The content is not a new write and is exempt from `max_write_bytes`.
+fn stage_move(
+    session: &mut EditSession,
+    operation: &Value,
+    path: &str,
+    fs_ops: &FsOps,
+    revision_guard: &SharedRevisionGuard,
+    ctx: &ToolContext<'_>,
+) -> Result<StageOutcome, String> {
+    let source = normalize_path(path);
+    let destination = normalize_path(required_string(operation, "destination")?);
+    if destination == source {
+        return Err("destination must differ from path".to_string());
+    }
+    fs_ops.resolve_write_path(destination.as_str())?;
+    validate_session_path_overlaps(session, destination.as_str(), "move")?;
+    if let Some(target) = session.files.get(destination.as_str()) {
+        let source_gone = session
+            .files
+            .get(source.as_str())
+            .is_some_and(|state| state.working.kind == EntryKind::Missing);
+        if source_gone
+            && target.moved_from.as_deref() == Some(source.as_str())
+            && target.working.kind == EntryKind::File
+        {
+            return Ok(StageOutcome {
+                moved_from: Some(source),
+                ..StageOutcome::new(destination, false)
+            });
+        }
+        if target.working.kind != EntryKind::Missing {
+            return Err(format!("Move target already exists: {destination}"));
+        }
+    } else if load_entry_snapshot(fs_ops, destination.as_str())?.kind != EntryKind::Missing {
+        return Err(format!("Move target already exists: {destination}"));
+    }
+
+    let expected = expected_revision(operation, "expected_sha256")?;
+    let state = get_or_load_session_file(
+        session,
+        source.as_str(),
+        expected,
+        fs_ops,
+        revision_guard,
+        ctx,
+    )?;
+    if state.working.kind != EntryKind::File {
+        return Err("Move source is not a file.".to_string());
+    }
+    let moved = std::mem::replace(&mut state.working, EntrySnapshot::missing());
+    // A file moved twice in one session still records its original path.
+    let origin = state.moved_from.take().unwrap_or_else(|| source.clone());
+    state.staged_operations += 1;
+
+    let target = get_or_load_session_file(
+        session,
+        destination.as_str(),
+        ExpectedRevision::Value(None),
+        fs_ops,
+        revision_guard,
+        ctx,
+    )?;
+    target.working = moved;
+    target.moved_from = Some(origin);
+    target.staged_operations += 1;
+    Ok(StageOutcome {
+        moved_from: Some(source),
+        ..StageOutcome::new(destination, true)
     })
 }
 
@@ -659,6 +883,7 @@ fn snapshot_matches_expected(snapshot: &EntrySnapshot, expected_sha256: Option<&
 
 fn commit_session(
     session: EditSession,
+    action: &str,
     fs_ops: &FsOps,
     change_log: &Arc<Mutex<ChangeLogStore>>,
     revision_guard: &SharedRevisionGuard,
@@ -667,14 +892,16 @@ fn commit_session(
     max_write_bytes: i64,
     hooks: Option<&CodeMaintainerHooksRef>,
 ) -> Result<Value, String> {
-    let changed_states = session
+    let mut changed_states = session
         .files
         .values()
         .filter(|state| state.has_change())
         .cloned()
         .collect::<Vec<_>>();
+    // Moves go first so a rename still finds its source on disk.
+    changed_states.sort_by_key(|state| state.moved_from.is_none());
     if changed_states.is_empty() {
-        return Ok(text_result(json!({
+        return Ok(json!({
             "outcome": FileModificationOutcome::AlreadyApplied,
             "changed": false,
             "changed_target_count": 0,
@@ -684,7 +911,7 @@ fn commit_session(
                 "staged_operation_count": session.staged_operation_count,
             },
             "message": "Session had no pending file-system changes. Nothing was committed."
-        })));
+        }));
     }
 
     let conflicts = changed_states
@@ -696,6 +923,9 @@ fn commit_session(
     }
 
     for state in &changed_states {
+        if is_pure_move(state, &changed_states) {
+            continue;
+        }
         if let Some(content) = state.working.content.as_deref() {
             enforce_write_size(content, max_write_bytes)?;
         }
@@ -705,9 +935,19 @@ fn commit_session(
     let mut rollback_applied = Vec::new();
     for state in &changed_states {
         let resolved = fs_ops.resolve_write_path(state.path.as_str())?;
+        let diff_source = match state.moved_from.as_deref() {
+            Some(source) if state.working.kind == EntryKind::File => {
+                fs_ops.resolve_write_path(source)?
+            }
+            _ => resolved.clone(),
+        };
         let before_diff =
-            read_text_for_diff(&resolved, max_file_bytes).unwrap_or_else(DiffInput::omitted);
-        let commit_result = apply_path_commit(state, resolved.as_path())?;
+            read_text_for_diff(&diff_source, max_file_bytes).unwrap_or_else(DiffInput::omitted);
+        let commit_result = if is_pure_move(state, &changed_states) {
+            apply_move_commit(diff_source.as_path(), resolved.as_path())?
+        } else {
+            apply_path_commit(state, resolved.as_path())?
+        };
         rollback_applied.push(commit_result.rollback.clone());
         applied.push(CommittedPath {
             state: state.clone(),
@@ -727,9 +967,14 @@ fn commit_session(
 
     if let Some(error) = failure {
         rollback_commits(rollback_applied.into_iter().rev().collect());
-        return Err(format!("commit_edit_session failed: {error}"));
+        return Err(format!("{action} failed: {error}"));
     }
 
+    let moved_to = changed_states
+        .iter()
+        .filter(|state| state.working.kind == EntryKind::File)
+        .filter_map(|state| Some((state.moved_from.clone()?, state.path.clone())))
+        .collect::<std::collections::HashMap<_, _>>();
     let store = change_log
         .lock()
         .map_err(|_| "change log unavailable".to_string())?;
@@ -739,30 +984,43 @@ fn commit_session(
         let path = committed.state.path.clone();
         match committed.state.working.kind {
             EntryKind::Missing => {
-                let diff = build_diff(committed.before_diff, DiffInput::text(String::new()));
+                let destination = moved_to.get(path.as_str());
+                // The destination record carries the content diff of a move.
+                let diff = match destination {
+                    Some(destination) => Some(format!("moved to {destination}")),
+                    None => build_diff(committed.before_diff, DiffInput::text(String::new())),
+                };
                 let record = store.log_change(
                     path.as_str(),
-                    "commit_edit_session",
+                    action,
                     "delete",
                     0,
                     "",
+                    None,
                     ctx.conversation_id,
                     ctx.run_id,
                     diff,
                 )?;
                 note_workspace_path_changed(hooks, full_path.as_str());
-                files.push(json!({
+                let mut file = json!({
                     "path": path,
                     "change_kind": "delete",
                     "deleted": true,
                     "change": record,
-                }));
+                });
+                if let Some(destination) = destination {
+                    file["moved_to"] = json!(destination);
+                }
+                files.push(file);
             }
             EntryKind::File => {
                 let content = committed.state.working.content.clone().unwrap_or_default();
                 let sha256 = committed.state.working.sha256.clone().unwrap_or_default();
                 let diff = build_diff(committed.before_diff, DiffInput::text(content.clone()));
-                let change_kind = if committed.state.base.kind == EntryKind::Missing {
+                let moved_from = committed.state.moved_from.as_deref();
+                let change_kind = if moved_from.is_some() {
+                    "move"
+                } else if committed.state.base.kind == EntryKind::Missing {
                     "create"
                 } else {
                     "edit"
@@ -771,29 +1029,64 @@ fn commit_session(
                     i64::try_from(content.len()).map_err(|_| "write too large".to_string())?;
                 let record = store.log_change(
                     path.as_str(),
-                    "commit_edit_session",
+                    action,
                     change_kind,
                     bytes,
                     sha256.as_str(),
+                    moved_from,
                     ctx.conversation_id,
                     ctx.run_id,
                     diff,
                 )?;
                 note_workspace_path_changed(hooks, full_path.as_str());
-                files.push(json!({
+                let mut file = json!({
                     "path": path,
                     "change_kind": change_kind,
                     "bytes": bytes,
                     "sha256": sha256,
                     "deleted": false,
                     "change": record,
-                }));
+                });
+                if let Some(moved_from) = moved_from {
+                    file["moved_from"] = json!(moved_from);
+                }
+                files.push(file);
             }
             EntryKind::Directory => {}
         }
     }
 
-    Ok(text_result(json!({
+    let history = CommittedSession {
+        session_id: session.id.clone(),
+        run_id: session.run_id.clone(),
+        conversation_id: session.conversation_id.clone(),
+        committed_at: now_iso(),
+        reverted_at: None,
+        reverted_by_session_id: None,
+        files: changed_states
+            .iter()
+            .map(|state| CommittedFile {
+                path: state.path.clone(),
+                before_kind: state.base.kind.clone(),
+                before_sha256: state.base.sha256.clone(),
+                before_content: state.base.content.clone(),
+                after_kind: state.working.kind.clone(),
+                after_sha256: state.working.sha256.clone(),
+                moved_from: state.moved_from.clone(),
+            })
+            .collect(),
+    };
+    // The files are already committed; a lost history entry only costs the
+    // ability to revert this session.
+    if let Err(error) = store.save_session(&history) {
+        tracing::warn!(
+            session_id = session.id.as_str(),
+            error = error.as_str(),
+            "failed to record committed edit session"
+        );
+    }
+
+    Ok(json!({
         "outcome": FileModificationOutcome::Changed,
         "changed": true,
         "changed_target_count": files.len(),
@@ -803,7 +1096,148 @@ fn commit_session(
             "committed_paths": files,
             "session_closed": true,
         }
-    })))
+    }))
+}
+
+/// Commits the inverse of a recorded session: each touched path goes back to
+/// its `before` state, provided it still holds the `after` state.
+fn revert_session(
+    session_id: &str,
+    fs_ops: &FsOps,
+    change_log: &Arc<Mutex<ChangeLogStore>>,
+    revision_guard: &SharedRevisionGuard,
+    ctx: &ToolContext<'_>,
+    max_file_bytes: i64,
+    hooks: Option<&CodeMaintainerHooksRef>,
+) -> Result<Value, String> {
+    let mut record = change_log
+        .lock()
+        .map_err(|_| "change log unavailable".to_string())?
+        .load_session(session_id)?;
+    if record.conversation_id != ctx.conversation_id {
+        return Err(format!("committed edit session not found: {session_id}"));
+    }
+    if let Some(reverted_by) = record.reverted_by_session_id.as_deref() {
+        return Err(format!(
+            "edit session {session_id} was already reverted by {reverted_by}"
+        ));
+    }
+    if let Some(file) = record
+        .files
+        .iter()
+        .find(|file| file.before_kind == EntryKind::Directory)
+    {
+        return Err(format!(
+            "edit session {session_id} deleted directory {}; directories cannot be restored",
+            file.path
+        ));
+    }
+
+    let mut inverse = EditSession::new(ctx.run_id, ctx.conversation_id);
+    let mut conflicts = Vec::new();
+    for file in &record.files {
+        let current = load_entry_snapshot(fs_ops, file.path.as_str())?;
+        if current.kind != file.after_kind || current.sha256 != file.after_sha256 {
+            conflicts.push(json!({
+                "path": file.path,
+                "committed_kind": entry_kind_name(&file.after_kind),
+                "committed_sha256": file.after_sha256,
+                "latest_kind": entry_kind_name(&current.kind),
+                "latest_sha256": current.sha256,
+            }));
+            continue;
+        }
+        let mut state = SessionFileState::new(file.path.as_str(), current);
+        state.working = match (&file.before_content, &file.before_sha256) {
+            (Some(content), Some(sha256)) if file.before_kind == EntryKind::File => {
+                EntrySnapshot::file(content.clone(), sha256.clone())
+            }
+            _ => EntrySnapshot::missing(),
+        };
+        inverse.files.insert(state.path.clone(), state);
+    }
+    if !conflicts.is_empty() {
+        return Err(revert_conflict_error(session_id, &conflicts));
+    }
+    // A move is undone by moving the destination back onto the source.
+    for file in &record.files {
+        if let Some(source) = file.moved_from.as_deref() {
+            if let Some(state) = inverse.files.get_mut(source) {
+                state.moved_from = Some(file.path.clone());
+            }
+        }
+    }
+
+    // Restored content was on disk before, so the write limit does not apply.
+    let mut payload = commit_session(
+        inverse,
+        "revert_edit_session",
+        fs_ops,
+        change_log,
+        revision_guard,
+        ctx,
+        max_file_bytes,
+        i64::MAX,
+        hooks,
+    )?;
+    let revert_session_id = payload["result"]["session_id"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string();
+    record.reverted_at = Some(now_iso());
+    record.reverted_by_session_id = Some(revert_session_id);
+    change_log
+        .lock()
+        .map_err(|_| "change log unavailable".to_string())?
+        .save_session(&record)?;
+    payload["result"]["reverted_session_id"] = json!(session_id);
+    payload["message"] = json!(format!(
+        "Edit session {session_id} was reverted. The revert is itself a committed session and can be reverted the same way."
+    ));
+    Ok(payload)
+}
+
+pub(super) fn committed_session_summary(session: &CommittedSession) -> Value {
+    let files = session
+        .files
+        .iter()
+        .map(|file| {
+            let mut summary = json!({
+                "path": file.path,
+                "change_kind": committed_change_kind(file),
+            });
+            if let Some(moved_from) = file.moved_from.as_deref() {
+                summary["moved_from"] = json!(moved_from);
+            }
+            summary
+        })
+        .collect::<Vec<_>>();
+    let revertible = session.reverted_by_session_id.is_none()
+        && session
+            .files
+            .iter()
+            .all(|file| file.before_kind != EntryKind::Directory);
+    json!({
+        "session_id": session.session_id,
+        "run_id": session.run_id,
+        "committed_at": session.committed_at,
+        "reverted_at": session.reverted_at,
+        "reverted_by_session_id": session.reverted_by_session_id,
+        "revertible": revertible,
+        "files": files,
+    })
+}
+
+fn committed_change_kind(file: &CommittedFile) -> &'static str {
+    if file.after_kind == EntryKind::Missing {
+        "delete"
+    } else if file.moved_from.is_some() {
+        "move"
+    } else if file.before_kind == EntryKind::Missing {
+        "create"
+    } else {
+        "edit"
+    }
 }
 
 #[derive(Debug)]
@@ -828,6 +1262,10 @@ impl PathCommitResult {
 #[derive(Debug, Clone)]
 enum RollbackAction {
     None,
+    Moved {
+        from: PathBuf,
+        to: PathBuf,
+    },
     CreatedFile {
         path: PathBuf,
     },
@@ -846,6 +1284,9 @@ impl RollbackAction {
     fn rollback(self) {
         match self {
             Self::None => {}
+            Self::Moved { from, to } => {
+                let _ = fs::rename(to, from);
+            }
             Self::CreatedFile { path } => {
                 let _ = fs::remove_file(path);
             }
@@ -866,7 +1307,7 @@ impl RollbackAction {
 
     fn cleanup(&self) -> Result<(), String> {
         match self {
-            Self::None | Self::CreatedFile { .. } => Ok(()),
+            Self::None | Self::Moved { .. } | Self::CreatedFile { .. } => Ok(()),
             Self::ReplacedFile {
                 backup, created, ..
             } => {
@@ -903,6 +1344,35 @@ fn apply_path_commit(
     }
 }
 
+/// True when `state` is a move destination whose content is still exactly
+/// the source file, so the commit can rename instead of rewriting.
+fn is_pure_move(state: &SessionFileState, changed_states: &[SessionFileState]) -> bool {
+    let Some(source) = state.moved_from.as_deref() else {
+        return false;
+    };
+    state.base.kind == EntryKind::Missing
+        && state.working.kind == EntryKind::File
+        && changed_states.iter().any(|candidate| {
+            candidate.path == source
+                && candidate.working.kind == EntryKind::Missing
+                && candidate.base.kind == EntryKind::File
+                && candidate.base.sha256 == state.working.sha256
+        })
+}
+
+fn apply_move_commit(from: &Path, to: &Path) -> Result<PathCommitResult, String> {
+    if let Some(parent) = to.parent() {
+        fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+    }
+    fs::rename(from, to).map_err(|err| err.to_string())?;
+    Ok(PathCommitResult {
+        rollback: RollbackAction::Moved {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+        },
+    })
+}
+
 fn apply_delete_commit(resolved: &Path) -> Result<PathCommitResult, String> {
     if !resolved.exists() {
         return Ok(PathCommitResult {
@@ -1203,6 +1673,22 @@ fn commit_conflict_error(conflicts: &[Value]) -> String {
     .unwrap_or_else(|_| "stale_context: staged session conflict".to_string())
 }
 
+fn revert_conflict_error(session_id: &str, conflicts: &[Value]) -> String {
+    let first = conflicts.first().cloned().unwrap_or_else(|| json!({}));
+    serde_json::to_string(&json!({
+        "category": "stale_context",
+        "error": format!("One or more paths changed after edit session {session_id} was committed. Nothing was reverted."),
+        "path": first.get("path").cloned().unwrap_or(Value::Null),
+        "latest_sha256": first.get("latest_sha256").cloned().unwrap_or(Value::Null),
+        "conflicts": conflicts,
+        "recovery": {
+            "required_next_tool": "read_file_raw",
+            "guidance": "Re-read the conflicted paths and undo the remaining changes with a normal edit session, keeping the later edits that should survive."
+        }
+    }))
+    .unwrap_or_else(|_| "stale_context: revert conflict".to_string())
+}
+
 fn edit_modification_error(
     outcome: FileModificationOutcome,
     message: &str,
@@ -1229,6 +1715,28 @@ fn edit_modification_error(
     serde_json::to_string(&payload).unwrap_or(base)
 }
 
+fn patch_modification_error(
+    outcome: FileModificationOutcome,
+    error: &PatchError,
+    path: &str,
+    latest_sha256: Option<&str>,
+) -> String {
+    let base = file_revision_error(
+        outcome.as_str(),
+        error.message.as_str(),
+        path,
+        latest_sha256,
+        None,
+        None,
+        "read_file_raw",
+    );
+    let Ok(mut payload) = serde_json::from_str::<Value>(base.as_str()) else {
+        return base;
+    };
+    payload["hunks"] = json!(error.hunks);
+    serde_json::to_string(&payload).unwrap_or(base)
+}
+
 fn edit_candidate_summary(content: &str, old_text: &str) -> Value {
     if old_text.is_empty() {
         return json!({ "count": 0, "candidates": [] });
@@ -1328,7 +1836,7 @@ fn required_string<'a>(value: &'a Value, field: &str) -> Result<&'a str, String>
         .ok_or_else(|| format!("{field} is required"))
 }
 
-fn optional_usize(value: &Value, field: &str) -> Option<usize> {
+pub(super) fn optional_usize(value: &Value, field: &str) -> Option<usize> {
     value
         .get(field)
         .and_then(Value::as_u64)
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
-use serde_json::Value;
+use serde_json::{json, Value};
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 
@@ -76,8 +76,11 @@ impl CodeMaintainerService {
             .canonicalize()
             .map_err(|err| format!("canonicalize workspace dir failed: {err}"))?;
 
-        let change_log =
+        let mut change_log =
             ChangeLogStore::new(&server_name, opts.project_id.clone(), opts.db_path.clone())?;
+        if opts.db_path.is_none() {
+            change_log = change_log.with_workspace_session_history(root.as_path());
+        }
         let change_log = Arc::new(Mutex::new(change_log));
         let revision_guard = guard_for_workspace(root.as_path())?;
         let session_store = store_for_workspace(root.as_path())?;
@@ -123,6 +126,7 @@ impl CodeMaintainerService {
             register_read_tools(
                 &mut service,
                 fs_ops.clone(),
+                change_log.clone(),
                 revision_guard.clone(),
                 workspace_note.as_str(),
                 opts.max_file_bytes,
@@ -181,6 +185,35 @@ impl CodeMaintainerService {
         self.call_registered_tool(name, args, &ctx)
     }
 
+    /// Restores every path touched by a committed edit session to its
+    /// pre-commit state. Same contract as the `revert_edit_session` tool.
+    pub fn revert_edit_session(
+        &self,
+        session_id: &str,
+        conversation_id: Option<&str>,
+    ) -> Result<Value, String> {
+        self.call_tool(
+            "revert_edit_session",
+            json!({ "session_id": session_id }),
+            conversation_id,
+        )
+    }
+
+    /// Recently committed edit sessions of a conversation (or, with
+    /// `run_only`, of the current run), newest first.
+    pub fn list_edit_sessions(
+        &self,
+        conversation_id: Option<&str>,
+        run_only: bool,
+        limit: Option<usize>,
+    ) -> Result<Value, String> {
+        let mut args = json!({ "scope": if run_only { "run" } else { "conversation" } });
+        if let Some(limit) = limit {
+            args["limit"] = json!(limit);
+        }
+        self.call_tool("list_edit_sessions", args, conversation_id)
+    }
+
     pub(super) fn has_tool(&self, name: &str) -> bool {
         self.registry.get(name).is_some()
     }
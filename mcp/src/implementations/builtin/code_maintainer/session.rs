@@ -6,6 +6,7 @@ use std::path::Path;
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
+use serde::{Deserialize, Serialize};
 use serde_json::json;
 
 use super::utils::{generate_id, now_iso};
@@ -171,7 +172,7 @@ pub(super) struct EditSession {
 }
 
 impl EditSession {
-    fn new(run_id: &str, conversation_id: &str) -> Self {
+    pub(super) fn new(run_id: &str, conversation_id: &str) -> Self {
         let now = now_iso();
         Self {
             id: generate_id("edit_session"),
@@ -218,6 +219,8 @@ pub(super) struct SessionFileState {
     pub base: EntrySnapshot,
     pub working: EntrySnapshot,
     pub staged_operations: usize,
+    /// Source path when the staged content arrived here through a move.
+    pub moved_from: Option<String>,
 }
 
 impl SessionFileState {
@@ -227,6 +230,7 @@ impl SessionFileState {
             base: snapshot.clone(),
             working: snapshot,
             staged_operations: 0,
+            moved_from: None,
         }
     }
 
@@ -239,7 +243,8 @@ impl SessionFileState {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub(super) enum EntryKind {
     Missing,
     File,
@@ -1,17 +1,29 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
-use super::utils::{generate_id, now_iso, resolve_state_dir};
-use serde::Serialize;
+use super::session::EntryKind;
+use super::utils::{generate_id, now_iso, resolve_state_dir, sha256_bytes};
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+const SESSION_HISTORY_STATE_NAME: &str = "code_maintainer_sessions";
+/// Not a `.json` file, so it can never clash with a session id.
+const SESSION_INDEX_FILE_NAME: &str = "sessions.index";
+/// Older committed sessions are dropped, and can no longer be reverted, once
+/// either limit is exceeded. The newest session is always kept.
+const MAX_RETAINED_SESSIONS: usize = 200;
+const MAX_RETAINED_SESSION_BYTES: u64 = 64 * 1024 * 1024;
 
 #[derive(Clone)]
 pub struct ChangeLogStore {
     path: PathBuf,
+    sessions_dir: Option<PathBuf>,
     server_name: String,
     project_id: Option<String>,
+    max_retained_sessions: usize,
+    max_retained_session_bytes: u64,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -24,6 +36,8 @@ pub struct ChangeRecord {
     pub change_kind: String,
     pub bytes: i64,
     pub sha256: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moved_from: Option<String>,
     pub diff: Option<String>,
     pub conversation_id: String,
     pub run_id: String,
@@ -33,6 +47,51 @@ pub struct ChangeRecord {
     pub created_at: String,
 }
 
+/// Before and after state of every path a committed edit session touched,
+/// kept so the session can be reverted later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct CommittedSession {
+    pub session_id: String,
+    pub run_id: String,
+    pub conversation_id: String,
+    pub committed_at: String,
+    #[serde(default)]
+    pub reverted_at: Option<String>,
+    #[serde(default)]
+    pub reverted_by_session_id: Option<String>,
+    pub files: Vec<CommittedFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(super) struct CommittedFile {
+    pub path: String,
+    pub before_kind: EntryKind,
+    pub before_sha256: Option<String>,
+    pub before_content: Option<String>,
+    pub after_kind: EntryKind,
+    pub after_sha256: Option<String>,
+    #[serde(default)]
+    pub moved_from: Option<String>,
+}
+
+/// Index entry of one session file: the session without file contents, which
+/// is all listing needs, and the file's size for retention.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SessionIndexEntry {
+    session: CommittedSession,
+    bytes: u64,
+}
+
+impl SessionIndexEntry {
+    fn new(session: &CommittedSession, bytes: u64) -> Self {
+        let mut session = session.clone();
+        for file in &mut session.files {
+            file.before_content = None;
+        }
+        Self { session, bytes }
+    }
+}
+
 impl ChangeLogStore {
     pub fn new(
         server_name: &str,
@@ -50,11 +109,30 @@ impl ChangeLogStore {
         }
         Ok(Self {
             path,
+            sessions_dir: None,
             server_name: server_name.to_string(),
             project_id,
+            max_retained_sessions: MAX_RETAINED_SESSIONS,
+            max_retained_session_bytes: MAX_RETAINED_SESSION_BYTES,
         })
     }
 
+    #[cfg(test)]
+    pub(super) fn with_session_retention(mut self, max_sessions: usize, max_bytes: u64) -> Self {
+        self.max_retained_sessions = max_sessions;
+        self.max_retained_session_bytes = max_bytes;
+        self
+    }
+
+    /// Keeps committed session history per workspace instead of per server,
+    /// so the read and write servers of one workspace see the same sessions.
+    pub(super) fn with_workspace_session_history(mut self, root: &Path) -> Self {
+        let workspace_key = sha256_bytes(root.to_string_lossy().as_bytes());
+        self.sessions_dir =
+            Some(resolve_state_dir(SESSION_HISTORY_STATE_NAME).join(&workspace_key[..24]));
+        self
+    }
+
     pub fn log_change(
         &self,
         path: &str,
@@ -62,6 +140,7 @@ impl ChangeLogStore {
         change_kind: &str,
         bytes: i64,
         sha256: &str,
+        moved_from: Option<&str>,
         conversation_id: &str,
         run_id: &str,
         diff: Option<String>,
@@ -75,6 +154,7 @@ impl ChangeLogStore {
             change_kind: change_kind.to_string(),
             bytes,
             sha256: sha256.to_string(),
+            moved_from: moved_from.map(ToOwned::to_owned),
             diff,
             conversation_id: conversation_id.to_string(),
             run_id: run_id.to_string(),
@@ -94,6 +174,120 @@ impl ChangeLogStore {
         file.write_all(b"\n").map_err(|err| err.to_string())?;
         Ok(record)
     }
+
+    /// Session history lives next to the change log unless it is kept per
+    /// workspace, one JSON file per committed session.
+    fn sessions_dir(&self) -> PathBuf {
+        self.sessions_dir
+            .clone()
+            .unwrap_or_else(|| self.path.with_extension("sessions"))
+    }
+
+    fn session_path(&self, session_id: &str) -> Result<PathBuf, String> {
+        let valid = !session_id.is_empty()
+            && session_id
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || ch == '_' || ch == '-');
+        if !valid {
+            return Err(format!("invalid edit session id: {session_id}"));
+        }
+        Ok(self.sessions_dir().join(format!("{session_id}.json")))
+    }
+
+    /// Writes the session and its index entry, then drops the oldest sessions
+    /// beyond the retention limits.
+    pub(super) fn save_session(&self, session: &CommittedSession) -> Result<(), String> {
+        let path = self.session_path(session.session_id.as_str())?;
+        fs::create_dir_all(self.sessions_dir()).map_err(|err| err.to_string())?;
+        let body = serde_json::to_vec(session).map_err(|err| err.to_string())?;
+        let bytes = body.len() as u64;
+        write_atomically(path.as_path(), body)?;
+
+        let mut entries = self.load_index()?;
+        entries.retain(|entry| entry.session.session_id != session.session_id);
+        entries.push(SessionIndexEntry::new(session, bytes));
+        sort_newest_first(&mut entries);
+        let mut retained_bytes = 0u64;
+        let mut retained = Vec::with_capacity(entries.len());
+        for entry in entries {
+            retained_bytes = retained_bytes.saturating_add(entry.bytes);
+            let within_limits = retained.len() < self.max_retained_sessions
+                && retained_bytes <= self.max_retained_session_bytes;
+            if retained.is_empty() || within_limits {
+                retained.push(entry);
+            } else if let Ok(path) = self.session_path(entry.session.session_id.as_str()) {
+                // A file left behind is only disk space; the index no longer lists it.
+                let _ = fs::remove_file(path);
+            }
+        }
+        self.write_index(&retained)
+    }
+
+    pub(super) fn load_session(&self, session_id: &str) -> Result<CommittedSession, String> {
+        let path = self.session_path(session_id)?;
+        let body = fs::read(path.as_path())
+            .map_err(|_| format!("committed edit session not found: {session_id}"))?;
+        serde_json::from_slice(body.as_slice()).map_err(|err| err.to_string())
+    }
+
+    /// Committed sessions, newest first, read from the index; their files
+    /// carry no `before_content`. Use `load_session` to revert one.
+    pub(super) fn list_sessions(&self) -> Result<Vec<CommittedSession>, String> {
+        Ok(self
+            .load_index()?
+            .into_iter()
+            .map(|entry| entry.session)
+            .collect())
+    }
+
+    fn index_path(&self) -> PathBuf {
+        self.sessions_dir().join(SESSION_INDEX_FILE_NAME)
+    }
+
+    /// Index entries, newest first. History written before the index existed
+    /// is indexed from the session files once; unreadable files are skipped.
+    fn load_index(&self) -> Result<Vec<SessionIndexEntry>, String> {
+        match fs::read(self.index_path()) {
+            Ok(body) => return serde_json::from_slice(&body).map_err(|err| err.to_string()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err.to_string()),
+        }
+        let files = match fs::read_dir(self.sessions_dir()) {
+            Ok(files) => files,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.to_string()),
+        };
+        let mut entries = files
+            .filter_map(Result::ok)
+            .map(|file| file.path())
+            .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .filter_map(|path| fs::read(path).ok())
+            .filter_map(|body| {
+                let session = serde_json::from_slice::<CommittedSession>(&body).ok()?;
+                Some(SessionIndexEntry::new(&session, body.len() as u64))
+            })
+            .collect::<Vec<_>>();
+        sort_newest_first(&mut entries);
+        if !entries.is_empty() {
+            self.write_index(&entries)?;
+        }
+        Ok(entries)
+    }
+
+    fn write_index(&self, entries: &[SessionIndexEntry]) -> Result<(), String> {
+        let body = serde_json::to_vec(entries).map_err(|err| err.to_string())?;
+        write_atomically(self.index_path().as_path(), body)
+    }
+}
+
+fn sort_newest_first(entries: &mut [SessionIndexEntry]) {
+    entries.sort_by(|left, right| right.session.committed_at.cmp(&left.session.committed_at));
+}
+
+fn write_atomically(path: &Path, body: Vec<u8>) -> Result<(), String> {
+    let staged = path.with_extension("json.tmp");
+    fs::write(staged.as_path(), body).map_err(|err| err.to_string())?;
+    fs::rename(staged.as_path(), path).map_err(|err| err.to_string())
 }
 
 fn default_jsonl_path(server_name: &str) -> PathBuf {
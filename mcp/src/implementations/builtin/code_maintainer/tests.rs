@@ -1079,4 +1079,397 @@ fn separate_read_and_write_services_share_reread_gate() {
             conversation,
         )
         .expect("commit updated file");
+
+    let listed = reader
+        .list_edit_sessions(conversation, false, None)
+        .expect("read service lists edit sessions");
+    let sessions = response_json(&listed)["result"]["sessions"]
+        .as_array()
+        .cloned()
+        .expect("listed sessions");
+    assert!(sessions
+        .iter()
+        .any(|session| session["session_id"] == session_id.as_str()));
+    reader
+        .revert_edit_session(session_id.as_str(), conversation)
+        .expect_err("read service cannot revert");
+}
+
+fn read_hash(service: &CodeMaintainerService, path: &str) -> String {
+    response_json(
+        &service
+            .call_tool("read_file_raw", json!({ "path": path }), None)
+            .expect("read file"),
+    )["sha256"]
+        .as_str()
+        .expect("file hash")
+        .to_string()
+}
+
+#[test]
+fn apply_patch_stages_hunks_and_reports_the_failing_one() {
+    let (service, root) = build_service(true);
+    fs::create_dir_all(&root).expect("create workspace");
+    fs::write(root.join("lib.rs"), "fn a() {}\n\nfn b() {}\n\nfn c() {}\n").expect("write lib");
+    let hash = read_hash(&service, "lib.rs");
+    let session_id = open_session(&service, None);
+
+    let error = service
+        .call_tool(
+            "stage_edit_batch",
+            json!({
+                "session_id": session_id,
+                "operations": [{
+                    "kind": "apply_patch",
+                    "path": "lib.rs",
+                    "patch": "@@ -1,1 +1,1 @@\n-fn a() {}\n+fn a() -> u8 { 1 }\n@@ -5,1 +5,1 @@\n-fn z() {}\n+fn z() -> u8 { 2 }\n",
+                    "expected_sha256": hash
+                }]
+            }),
+            None,
+        )
+        .expect_err("missing hunk context fails the whole patch");
+    let payload: serde_json::Value = serde_json::from_str(error.as_str()).expect("error payload");
+    assert_eq!(payload["category"], "stale_context");
+    assert_eq!(payload["hunks"][0]["status"], "applied");
+    assert_eq!(payload["hunks"][1]["status"], "failed");
+
+    let hash = read_hash(&service, "lib.rs");
+    let session_id = open_session(&service, None);
+    service
+        .call_tool(
+            "stage_edit_batch",
+            json!({
+                "session_id": session_id,
+                "operations": [{
+                    "kind": "apply_patch",
+                    "path": "lib.rs",
+                    "patch": "--- a/lib.rs\n+++ b/lib.rs\n@@ -1,3 +1,3 @@\n-fn a() {}\n+fn a() -> u8 { 1 }\n \n fn b() {}\n@@ -5,1 +5,1 @@\n-fn c() {}\n+fn c() -> u8 { 3 }\n",
+                    "expected_sha256": hash
+                }]
+            }),
+            None,
+        )
+        .expect("stage patch");
+    service
+        .call_tool(
+            "commit_edit_session",
+            json!({ "session_id": session_id }),
+            None,
+        )
+        .expect("commit patch");
+    assert_eq!(
+        fs::read_to_string(root.join("lib.rs")).expect("patched lib"),
+        "fn a() -> u8 { 1 }\n\nfn b() {}\n\nfn c() -> u8 { 3 }\n"
+    );
+}
+
+#[test]
+fn move_keeps_content_beyond_the_write_limit_and_logs_one_move() {
+    let root = unique_temp_dir("code_maintainer_move_workspace");
+    fs::create_dir_all(root.join("src")).expect("create workspace");
+    let content = "0123456789".repeat(10);
+    fs::write(root.join("src/old.txt"), content.as_str()).expect("write source");
+    let db_path = unique_temp_dir("code_maintainer_move_db").join("changes.jsonl");
+    let service = CodeMaintainerService::new(CodeMaintainerOptions {
+        server_name: "code_maintainer_move_test".to_string(),
+        root: root.clone(),
+        project_id: None,
+        allow_writes: true,
+        allowed_write_paths: None,
+        max_file_bytes: 256 * 1024,
+        max_write_bytes: 16,
+        search_limit: 40,
+        enable_read_tools: true,
+        enable_write_tools: true,
+        conversation_id: None,
+        run_id: None,
+        db_path: Some(db_path.to_string_lossy().to_string()),
+        hooks: None,
+    })
+    .expect("build move service");
+    let hash = read_hash(&service, "src/old.txt");
+    let session_id = open_session(&service, None);
+
+    service
+        .call_tool(
+            "stage_edit_batch",
+            json!({
+                "session_id": session_id,
+                "operations": [{
+                    "kind": "rename",
+                    "path": "src/old.txt",
+                    "destination": "docs/new.txt",
+                    "expected_sha256": hash
+                }]
+            }),
+            None,
+        )
+        .expect("stage move");
+    let committed = response_json(
+        &service
+            .call_tool(
+                "commit_edit_session",
+                json!({ "session_id": session_id }),
+                None,
+            )
+            .expect("commit move"),
+    );
+
+    assert!(!root.join("src/old.txt").exists());
+    assert_eq!(
+        fs::read_to_string(root.join("docs/new.txt")).expect("moved file"),
+        content
+    );
+    let paths = committed["result"]["committed_paths"]
+        .as_array()
+        .expect("committed paths");
+    assert_eq!(paths[0]["change_kind"], "move");
+    assert_eq!(paths[0]["moved_from"], "src/old.txt");
+    assert_eq!(paths[1]["moved_to"], "docs/new.txt");
+    let log = fs::read_to_string(db_path).expect("change log");
+    assert!(log.contains("\"moved_from\":\"src/old.txt\""));
+    assert!(log.contains("No changes."));
+
+    let session_id = open_session(&service, None);
+    let error = service
+        .call_tool(
+            "stage_edit_batch",
+            json!({
+                "session_id": session_id,
+                "operations": [{
+                    "kind": "move",
+                    "path": "docs/new.txt",
+                    "destination": "docs/new.txt",
+                    "expected_sha256": hash
+                }]
+            }),
+            None,
+        )
+        .expect_err("a move needs a different destination");
+    assert!(error.contains("destination must differ"));
+}
+
+fn commit_batch(service: &CodeMaintainerService, operations: serde_json::Value) -> String {
+    let session_id = open_session(service, None);
+    service
+        .call_tool(
+            "stage_edit_batch",
+            json!({ "session_id": session_id, "operations": operations }),
+            None,
+        )
+        .expect("stage batch");
+    service
+        .call_tool(
+            "commit_edit_session",
+            json!({ "session_id": session_id }),
+            None,
+        )
+        .expect("commit batch");
+    session_id
+}
+
+#[test]
+fn revert_edit_session_restores_edits_creates_deletes_and_moves() {
+    let (service, root) = build_service(true);
+    fs::write(root.join("edit.txt"), "before\n").expect("write edit");
+    fs::write(root.join("gone.txt"), "keep me\n").expect("write gone");
+    fs::write(root.join("old.txt"), "moving\n").expect("write old");
+    let operations = json!([
+        {
+            "kind": "write",
+            "path": "edit.txt",
+            "content": "after\n",
+            "expected_sha256": read_hash(&service, "edit.txt")
+        },
+        { "kind": "write", "path": "new.txt", "content": "fresh\n", "expected_sha256": null },
+        { "kind": "delete", "path": "gone.txt", "expected_sha256": read_hash(&service, "gone.txt") },
+        {
+            "kind": "move",
+            "path": "old.txt",
+            "destination": "moved/new_name.txt",
+            "expected_sha256": read_hash(&service, "old.txt")
+        }
+    ]);
+    let session_id = commit_batch(&service, operations);
+
+    let listed = response_json(
+        &service
+            .list_edit_sessions(None, true, None)
+            .expect("list sessions"),
+    );
+    let sessions = listed["result"]["sessions"].as_array().expect("sessions");
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0]["session_id"], session_id.as_str());
+    assert_eq!(sessions[0]["revertible"], true);
+    let kinds = sessions[0]["files"]
+        .as_array()
+        .expect("files")
+        .iter()
+        .map(|file| {
+            format!(
+                "{}:{}",
+                file["path"].as_str().unwrap_or_default(),
+                file["change_kind"].as_str().unwrap_or_default()
+            )
+        })
+        .collect::<Vec<_>>();
+    assert!(kinds.contains(&"edit.txt:edit".to_string()));
+    assert!(kinds.contains(&"new.txt:create".to_string()));
+    assert!(kinds.contains(&"gone.txt:delete".to_string()));
+    assert!(kinds.contains(&"moved/new_name.txt:move".to_string()));
+
+    let reverted = response_json(
+        &service
+            .revert_edit_session(session_id.as_str(), None)
+            .expect("revert session"),
+    );
+    assert_eq!(reverted["changed"], true);
+    assert_eq!(
+        reverted["result"]["reverted_session_id"],
+        session_id.as_str()
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("edit.txt")).expect("edit restored"),
+        "before\n"
+    );
+    assert!(!root.join("new.txt").exists());
+    assert_eq!(
+        fs::read_to_string(root.join("gone.txt")).expect("gone restored"),
+        "keep me\n"
+    );
+    assert_eq!(
+        fs::read_to_string(root.join("old.txt")).expect("move undone"),
+        "moving\n"
+    );
+    assert!(!root.join("moved/new_name.txt").exists());
+
+    let listed = response_json(
+        &service
+            .list_edit_sessions(None, false, Some(10))
+            .expect("list after revert"),
+    );
+    let sessions = listed["result"]["sessions"].as_array().expect("sessions");
+    assert_eq!(sessions.len(), 2);
+    let original = sessions
+        .iter()
+        .find(|session| session["session_id"] == session_id.as_str())
+        .expect("original session listed");
+    assert_eq!(original["revertible"], false);
+    assert_eq!(
+        original["reverted_by_session_id"],
+        reverted["result"]["session_id"]
+    );
+    let error = service
+        .revert_edit_session(session_id.as_str(), None)
+        .expect_err("a session is reverted once");
+    assert!(error.contains("already reverted"));
+}
+
+#[test]
+fn revert_edit_session_refuses_when_a_path_changed_after_commit() {
+    let (service, root) = build_service(true);
+    fs::write(root.join("a.txt"), "a1\n").expect("write a");
+    fs::write(root.join("b.txt"), "b1\n").expect("write b");
+    let operations = json!([
+        { "kind": "write", "path": "a.txt", "content": "a2\n", "expected_sha256": read_hash(&service, "a.txt") },
+        { "kind": "write", "path": "b.txt", "content": "b2\n", "expected_sha256": read_hash(&service, "b.txt") }
+    ]);
+    let session_id = commit_batch(&service, operations);
+    fs::write(root.join("b.txt"), "b3\n").expect("external edit");
+
+    let error = service
+        .call_tool(
+            "revert_edit_session",
+            json!({ "session_id": session_id }),
+            None,
+        )
+        .expect_err("changed path blocks the revert");
+    let payload: serde_json::Value = serde_json::from_str(error.as_str()).expect("error payload");
+    assert_eq!(payload["category"], "stale_context");
+    assert_eq!(payload["conflicts"].as_array().map(Vec::len), Some(1));
+    assert_eq!(payload["conflicts"][0]["path"], "b.txt");
+    assert_eq!(
+        fs::read_to_string(root.join("a.txt")).expect("a untouched"),
+        "a2\n"
+    );
+
+    let error = service
+        .revert_edit_session(session_id.as_str(), Some("another_conversation"))
+        .expect_err("sessions are scoped to their conversation");
+    assert!(error.contains("not found"));
+}
+
+#[test]
+fn committed_session_history_is_indexed_and_capped() {
+    use super::session::EntryKind;
+    use super::storage::{ChangeLogStore, CommittedFile, CommittedSession};
+
+    let dir = unique_temp_dir("code_maintainer_session_history");
+    let store = ChangeLogStore::new(
+        "session_history_test",
+        None,
+        Some(dir.join("changes.jsonl").to_string_lossy().to_string()),
+    )
+    .expect("build change log")
+    .with_session_retention(3, u64::MAX);
+    let session = |index: usize, before: &str| CommittedSession {
+        session_id: format!("session_{index}"),
+        run_id: "run".to_string(),
+        conversation_id: "conversation".to_string(),
+        committed_at: format!("2026-01-01T00:00:0{index}Z"),
+        reverted_at: None,
+        reverted_by_session_id: None,
+        files: vec![CommittedFile {
+            path: "notes.txt".to_string(),
+            before_kind: EntryKind::File,
+            before_sha256: Some("before".to_string()),
+            before_content: Some(before.to_string()),
+            after_kind: EntryKind::File,
+            after_sha256: Some("after".to_string()),
+            moved_from: None,
+        }],
+    };
+    let listed_ids = |store: &ChangeLogStore| {
+        store
+            .list_sessions()
+            .expect("list sessions")
+            .into_iter()
+            .map(|session| session.session_id)
+            .collect::<Vec<_>>()
+    };
+
+    for index in 0..5 {
+        store
+            .save_session(&session(index, "old text"))
+            .expect("save session");
+    }
+    assert_eq!(
+        listed_ids(&store),
+        vec!["session_4", "session_3", "session_2"]
+    );
+    assert!(store
+        .list_sessions()
+        .expect("list sessions")
+        .iter()
+        .all(|session| session.files[0].before_content.is_none()));
+    assert!(store.load_session("session_1").is_err());
+    assert_eq!(
+        store.load_session("session_4").expect("load session").files[0]
+            .before_content
+            .as_deref(),
+        Some("old text")
+    );
+
+    // The newest session is kept even when it alone exceeds the byte limit.
+    let store = store.with_session_retention(10, 1024);
+    store
+        .save_session(&session(6, "x".repeat(4096).as_str()))
+        .expect("save large session");
+    assert_eq!(listed_ids(&store), vec!["session_6"]);
+
+    // History written before the index existed is indexed from the files.
+    fs::remove_file(dir.join("changes.sessions").join("sessions.index")).expect("drop index");
+    assert_eq!(listed_ids(&store), vec!["session_6"]);
+    let _ = fs::remove_dir_all(dir);
 }
@@ -32,9 +32,9 @@ pub use agent_builder::{
     AgentBuilderStoreRef,
 };
 pub use ask_user::{
-    normalize_kv_fields, prepare_prompt, AskUserDecision, AskUserOptions, AskUserPromptPayload,
-    AskUserResponseSubmission, AskUserService, AskUserStore, AskUserStoreRef,
-    AskUserStreamChunkCallback, ASK_USER_PROMPT_TIMEOUT_MS_DEFAULT,
+    normalize_kv_fields, prepare_prompt, AskUserDecision, AskUserElicitationHandler,
+    AskUserOptions, AskUserPromptPayload, AskUserResponseSubmission, AskUserService, AskUserStore,
+    AskUserStoreRef, AskUserStreamChunkCallback, ASK_USER_PROMPT_TIMEOUT_MS_DEFAULT,
 };
 pub use browser_tools::{
     browser_interactive_approval_command, BrowserToolCallContext, BrowserToolsOptions,
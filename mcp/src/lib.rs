@@ -22,12 +22,12 @@ pub use implementations::builtin::{
     terminal_process_log_response, terminal_process_poll_response, terminal_process_wait_response,
     terminal_recent_logs_entry, terminal_recent_logs_response, terminal_result_scope,
     terminate_child_process_tree, AgentBuilderOptions, AgentBuilderService, AgentBuilderSkill,
-    AgentBuilderStore, AgentBuilderStoreRef, AskUserDecision, AskUserOptions, AskUserPromptPayload,
-    AskUserResponseSubmission, AskUserService, AskUserStore, AskUserStoreRef,
-    AskUserStreamChunkCallback, BrowserToolCallContext, BrowserToolsOptions, BrowserToolsService,
-    BrowserVisionAdapter, BrowserVisionAdapterRef, BrowserVisionFailure, BrowserVisionRequest,
-    BrowserVisionResponse, BuiltinToolServiceDependencies, CodeMaintainerHooks,
-    CodeMaintainerHooksRef, CodeMaintainerOptions, CodeMaintainerService,
+    AgentBuilderStore, AgentBuilderStoreRef, AskUserDecision, AskUserElicitationHandler,
+    AskUserOptions, AskUserPromptPayload, AskUserResponseSubmission, AskUserService, AskUserStore,
+    AskUserStoreRef, AskUserStreamChunkCallback, BrowserToolCallContext, BrowserToolsOptions,
+    BrowserToolsService, BrowserVisionAdapter, BrowserVisionAdapterRef, BrowserVisionFailure,
+    BrowserVisionRequest, BrowserVisionResponse, BuiltinToolServiceDependencies,
+    CodeMaintainerHooks, CodeMaintainerHooksRef, CodeMaintainerOptions, CodeMaintainerService,
     MemoryCommandReaderOptions, MemoryCommandReaderService, MemoryFullPlugin, MemoryFullSkill,
     MemoryInlineSkill, MemoryPluginReaderOptions, MemoryPluginReaderService, MemoryReaderStore,
     MemoryReaderStoreRef, MemoryRuntimeCommand, MemoryRuntimeContext, MemoryRuntimePlugin,
@@ -17,6 +17,7 @@ use chatos_mcp_service::{
     McpToolCallResultItem, McpToolCallResultStatus, MCP_ERROR_AUTH_REQUIRED, MCP_ERROR_INTERNAL,
     MCP_ERROR_INVALID_PARAMS, MCP_ERROR_METHOD_NOT_FOUND, METHOD_INITIALIZE,
     METHOD_NOTIFICATIONS_CANCELLED, METHOD_NOTIFICATIONS_INITIALIZED, METHOD_PING,
+    METHOD_PROMPTS_GET, METHOD_PROMPTS_LIST, METHOD_RESOURCES_LIST, METHOD_RESOURCES_READ,
     METHOD_TOOLS_LIST,
 };
 use mongodb::bson::DateTime;
@@ -106,7 +107,7 @@ async fn handle_session_request(
             id,
             json!({
                 "protocolVersion": "2024-11-05",
-                "capabilities": {"tools": {}},
+                "capabilities": {"tools": {}, "prompts": {}, "resources": {}},
                 "serverInfo": {"name": "chatos-mcp-management", "version": "0.1.0"}
             }),
         ),
@@ -124,6 +125,38 @@ async fn handle_session_request(
                     .collect::<Vec<_>>()
             }),
         ),
+        METHOD_PROMPTS_LIST => jsonrpc_ok(
+            id,
+            json!({ "prompts": state.providers.list_prompts(snapshot).await }),
+        ),
+        METHOD_PROMPTS_GET => {
+            let Some(name) = request.params.get("name").and_then(Value::as_str) else {
+                return jsonrpc_error(id, MCP_ERROR_INVALID_PARAMS, "prompt name is required");
+            };
+            let arguments = request
+                .params
+                .get("arguments")
+                .filter(|arguments| arguments.is_object())
+                .cloned()
+                .unwrap_or_else(|| json!({}));
+            match state.providers.get_prompt(snapshot, name, arguments).await {
+                Ok(result) => jsonrpc_ok(id, result),
+                Err(error) => jsonrpc_error(id, error.code, error.message),
+            }
+        }
+        METHOD_RESOURCES_LIST => jsonrpc_ok(
+            id,
+            json!({ "resources": state.providers.list_resources(snapshot).await }),
+        ),
+        METHOD_RESOURCES_READ => {
+            let Some(uri) = request.params.get("uri").and_then(Value::as_str) else {
+                return jsonrpc_error(id, MCP_ERROR_INVALID_PARAMS, "resource uri is required");
+            };
+            match state.providers.read_resource(snapshot, uri).await {
+                Ok(result) => jsonrpc_ok(id, result),
+                Err(error) => jsonrpc_error(id, error.code, error.message),
+            }
+        }
         other => jsonrpc_error(
             id,
             MCP_ERROR_METHOD_NOT_FOUND,
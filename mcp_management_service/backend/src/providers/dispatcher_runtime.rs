@@ -3,3 +3,4 @@
 
 mod call;
 mod cancel;
+mod content;
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use serde_json::Value;
+
+use crate::runtime::RuntimeSessionSnapshot;
+
+use super::super::{ProviderCallError, ProviderDispatcher};
+
+// Prompts and resources are only proxied for External HTTP MCP servers; the
+// built-in providers expose tools only.
+impl ProviderDispatcher {
+    pub async fn list_prompts(&self, snapshot: &RuntimeSessionSnapshot) -> Vec<Value> {
+        self.external_http.list_prompts(snapshot).await
+    }
+
+    pub async fn get_prompt(
+        &self,
+        snapshot: &RuntimeSessionSnapshot,
+        name: &str,
+        arguments: Value,
+    ) -> Result<Value, ProviderCallError> {
+        self.external_http
+            .get_prompt(snapshot, name, arguments)
+            .await
+    }
+
+    pub async fn list_resources(&self, snapshot: &RuntimeSessionSnapshot) -> Vec<Value> {
+        self.external_http.list_resources(snapshot).await
+    }
+
+    pub async fn read_resource(
+        &self,
+        snapshot: &RuntimeSessionSnapshot,
+        uri: &str,
+    ) -> Result<Value, ProviderCallError> {
+        self.external_http.read_resource(snapshot, uri).await
+    }
+}
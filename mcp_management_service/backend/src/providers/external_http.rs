@@ -5,6 +5,7 @@ use std::time::Duration;
 
 use super::ProviderCallError;
 
+mod content;
 mod init;
 mod prepare;
 mod runtime_calls;
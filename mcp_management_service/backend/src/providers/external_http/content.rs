@@ -0,0 +1,179 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use chatos_mcp_service::{
+    MCP_ERROR_INVALID_PARAMS, MCP_ERROR_RESOURCE_NOT_FOUND, METHOD_PROMPTS_GET,
+    METHOD_PROMPTS_LIST, METHOD_RESOURCES_LIST, METHOD_RESOURCES_READ,
+};
+use serde_json::{json, Value};
+
+use crate::runtime::{ExternalHttpProviderBinding, RuntimeSessionSnapshot};
+
+use super::{ExternalHttpProvider, ProviderCallError};
+
+/// Joins the owning MCP resource id and the upstream prompt name in the
+/// names the gateway lists, so `prompts/get` can be routed back.
+const PROMPT_NAME_SEPARATOR: char = '/';
+/// Upper bound on `nextCursor` pages fetched per server.
+const MAX_LIST_PAGES: usize = 20;
+const PROVIDER_LABEL: &str = "External HTTP MCP";
+
+impl ExternalHttpProvider {
+    /// Prompts of every External HTTP MCP bound to the session, renamed to
+    /// `<resource id>/<prompt>`. Servers without prompt support are skipped.
+    pub(in crate::providers) async fn list_prompts(
+        &self,
+        snapshot: &RuntimeSessionSnapshot,
+    ) -> Vec<Value> {
+        let mut prompts = Vec::new();
+        for (resource_id, binding) in sorted_bindings(snapshot) {
+            match self
+                .list_binding_pages(binding, METHOD_PROMPTS_LIST, "prompts")
+                .await
+            {
+                Ok(items) => prompts.extend(items.into_iter().filter_map(|mut prompt| {
+                    let name = prompt.get("name").and_then(Value::as_str)?.trim();
+                    if name.is_empty() {
+                        return None;
+                    }
+                    let name = format!("{resource_id}{PROMPT_NAME_SEPARATOR}{name}");
+                    prompt["name"] = Value::String(name);
+                    Some(prompt)
+                })),
+                Err(error) => tracing::warn!(
+                    resource_id,
+                    error = error.message.as_str(),
+                    "list External HTTP MCP prompts failed"
+                ),
+            }
+        }
+        prompts
+    }
+
+    pub(in crate::providers) async fn get_prompt(
+        &self,
+        snapshot: &RuntimeSessionSnapshot,
+        name: &str,
+        arguments: Value,
+    ) -> Result<Value, ProviderCallError> {
+        let (resource_id, prompt_name) = name
+            .split_once(PROMPT_NAME_SEPARATOR)
+            .filter(|(resource_id, prompt_name)| !resource_id.is_empty() && !prompt_name.is_empty())
+            .ok_or_else(|| ProviderCallError {
+                code: MCP_ERROR_INVALID_PARAMS,
+                message: "prompt name must be <resource id>/<prompt>".to_string(),
+            })?;
+        let binding = snapshot
+            .external_http_bindings
+            .get(resource_id)
+            .ok_or_else(|| ProviderCallError {
+                code: MCP_ERROR_RESOURCE_NOT_FOUND,
+                message: format!(
+                    "prompt server is not bound to this runtime session: {resource_id}"
+                ),
+            })?;
+        let request_id = format!("prompts-get-{}", uuid::Uuid::new_v4());
+        self.post_jsonrpc(
+            binding,
+            request_id.as_str(),
+            METHOD_PROMPTS_GET,
+            json!({ "name": prompt_name, "arguments": arguments }),
+            PROVIDER_LABEL,
+        )
+        .await
+        .map(|(result, _)| result)
+    }
+
+    pub(in crate::providers) async fn list_resources(
+        &self,
+        snapshot: &RuntimeSessionSnapshot,
+    ) -> Vec<Value> {
+        let mut resources = Vec::new();
+        for (resource_id, binding) in sorted_bindings(snapshot) {
+            match self
+                .list_binding_pages(binding, METHOD_RESOURCES_LIST, "resources")
+                .await
+            {
+                Ok(items) => resources.extend(items),
+                Err(error) => tracing::warn!(
+                    resource_id,
+                    error = error.message.as_str(),
+                    "list External HTTP MCP resources failed"
+                ),
+            }
+        }
+        resources
+    }
+
+    /// Reads `uri` from the first bound server that returns contents for it.
+    pub(in crate::providers) async fn read_resource(
+        &self,
+        snapshot: &RuntimeSessionSnapshot,
+        uri: &str,
+    ) -> Result<Value, ProviderCallError> {
+        for (_, binding) in sorted_bindings(snapshot) {
+            let request_id = format!("resources-read-{}", uuid::Uuid::new_v4());
+            if let Ok((result, _)) = self
+                .post_jsonrpc(
+                    binding,
+                    request_id.as_str(),
+                    METHOD_RESOURCES_READ,
+                    json!({ "uri": uri }),
+                    PROVIDER_LABEL,
+                )
+                .await
+            {
+                if result.get("contents").is_some_and(Value::is_array) {
+                    return Ok(result);
+                }
+            }
+        }
+        Err(ProviderCallError {
+            code: MCP_ERROR_RESOURCE_NOT_FOUND,
+            message: format!("resource not found: {uri}"),
+        })
+    }
+
+    async fn list_binding_pages(
+        &self,
+        binding: &ExternalHttpProviderBinding,
+        method: &str,
+        key: &str,
+    ) -> Result<Vec<Value>, ProviderCallError> {
+        let mut items = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..MAX_LIST_PAGES {
+            let params = match cursor.as_ref() {
+                Some(cursor) => json!({ "cursor": cursor }),
+                None => json!({}),
+            };
+            let request_id = format!("{}-{}", method.replace('/', "-"), uuid::Uuid::new_v4());
+            let (result, _) = self
+                .post_jsonrpc(binding, request_id.as_str(), method, params, PROVIDER_LABEL)
+                .await?;
+            if let Some(page) = result.get(key).and_then(Value::as_array) {
+                items.extend(page.iter().filter(|item| item.is_object()).cloned());
+            }
+            cursor = result
+                .get("nextCursor")
+                .and_then(Value::as_str)
+                .filter(|cursor| !cursor.is_empty())
+                .map(ToOwned::to_owned);
+            if cursor.is_none() {
+                break;
+            }
+        }
+        Ok(items)
+    }
+}
+
+/// Bindings in resource id order so listings are stable across calls.
+fn sorted_bindings(snapshot: &RuntimeSessionSnapshot) -> Vec<(&str, &ExternalHttpProviderBinding)> {
+    let mut bindings = snapshot
+        .external_http_bindings
+        .iter()
+        .map(|(resource_id, binding)| (resource_id.as_str(), binding))
+        .collect::<Vec<_>>();
+    bindings.sort_by_key(|(resource_id, _)| *resource_id);
+    bindings
+}
@@ -65,45 +65,9 @@ impl ExternalHttpProvider {
         request_id: &str,
         provider_label: &str,
     ) -> Result<Vec<Value>, ProviderCallError> {
-        let response = binding
-            .http
-            .post(binding.endpoint.clone())
-            .headers(binding.headers.clone())
-            .header(CONTENT_TYPE, JSON_CONTENT_TYPE)
-            .header(ACCEPT, JSON_CONTENT_TYPE)
-            .json(&json!({
-                "jsonrpc": "2.0",
-                "id": request_id,
-                "method": "tools/list",
-                "params": {}
-            }))
-            .send()
-            .await
-            .map_err(|_| {
-                ProviderCallError::provider_unavailable(format!("{provider_label} request failed"))
-            })?;
-        let status = response.status();
-        let bytes = read_response_bytes_limited(response, self.response_limit_bytes)
-            .await
-            .map_err(|err| {
-                ProviderCallError::invalid_response(format!(
-                    "{provider_label} response could not be read: {err}"
-                ))
-            })?;
-        if !status.is_success() {
-            return Err(if matches!(status.as_u16(), 401 | 403) {
-                ProviderCallError {
-                    code: MCP_ERROR_AUTH_REQUIRED,
-                    message: format!("{provider_label} rejected its configured credentials"),
-                }
-            } else {
-                ProviderCallError::provider_unavailable(format!(
-                    "{provider_label} returned HTTP {}",
-                    status.as_u16()
-                ))
-            });
-        }
-        let result = decode_jsonrpc_response(bytes.as_slice(), request_id, provider_label)?;
+        let (result, _) = self
+            .post_jsonrpc(binding, request_id, "tools/list", json!({}), provider_label)
+            .await?;
         let tools = result
             .get("tools")
             .and_then(Value::as_array)
@@ -143,6 +107,31 @@ impl ExternalHttpProvider {
                 message: format!("tool is blocked by the {provider_label} policy"),
             });
         }
+        let (result, response_bytes) = self
+            .post_jsonrpc(
+                binding,
+                invocation_id,
+                METHOD_TOOLS_CALL,
+                managed_tool_call_params(original_tool_name, arguments, tool_result_max_chars),
+                provider_label,
+            )
+            .await?;
+        Ok(ProviderCallOutcome {
+            result,
+            response_bytes,
+        })
+    }
+
+    /// Sends one JSON-RPC request to a bound server and returns its `result`
+    /// with the raw response size.
+    pub(in crate::providers) async fn post_jsonrpc(
+        &self,
+        binding: &ExternalHttpProviderBinding,
+        request_id: &str,
+        method: &str,
+        params: Value,
+        provider_label: &str,
+    ) -> Result<(Value, usize), ProviderCallError> {
         let response = binding
             .http
             .post(binding.endpoint.clone())
@@ -151,13 +140,9 @@ impl ExternalHttpProvider {
             .header(ACCEPT, JSON_CONTENT_TYPE)
             .json(&json!({
                 "jsonrpc": "2.0",
-                "id": invocation_id,
-                "method": METHOD_TOOLS_CALL,
-                "params": managed_tool_call_params(
-                    original_tool_name,
-                    arguments,
-                    tool_result_max_chars,
-                )
+                "id": request_id,
+                "method": method,
+                "params": params
             }))
             .send()
             .await
@@ -185,11 +170,8 @@ impl ExternalHttpProvider {
                 ))
             });
         }
-        let result = decode_jsonrpc_response(bytes.as_slice(), invocation_id, provider_label)?;
-        Ok(ProviderCallOutcome {
-            result,
-            response_bytes: bytes.len(),
-        })
+        let result = decode_jsonrpc_response(bytes.as_slice(), request_id, provider_label)?;
+        Ok((result, bytes.len()))
     }
 
     pub(in crate::providers) async fn cancel_invocation(
@@ -326,3 +326,106 @@ async fn bound_http_cancellation_forwards_the_exact_invocation_id_and_headers()
     assert_eq!(outcome, ProviderCancelOutcome::Cancelled);
     server.abort();
 }
+
+#[tokio::test]
+async fn prompts_and_resources_are_proxied_under_the_binding_resource_id() {
+    async fn handler(Json(request): Json<Value>) -> Json<Value> {
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap_or("") {
+            "prompts/list" => json!({"prompts": [
+                {"name": "summarize", "arguments": [{"name": "topic", "required": true}]},
+                {"name": "  "}
+            ]}),
+            "prompts/get" => {
+                assert_eq!(params["name"], "summarize");
+                json!({"messages": [{"role": "user", "content": {
+                    "type": "text",
+                    "text": format!("Summarize {}", params["arguments"]["topic"].as_str().unwrap())
+                }}]})
+            }
+            "resources/list" if params.get("cursor").is_none() => json!({
+                "resources": [{"uri": "docs://readme"}],
+                "nextCursor": "page-2"
+            }),
+            "resources/list" => json!({"resources": [{"uri": "docs://changelog"}]}),
+            "resources/read" if params["uri"] == "docs://readme" => {
+                json!({"contents": [{"uri": "docs://readme", "text": "# Docs"}]})
+            }
+            _ => {
+                return Json(json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "error": {"code": -32002, "message": "not found"}
+                }))
+            }
+        };
+        Json(json!({"jsonrpc": "2.0", "id": request["id"], "result": result}))
+    }
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = tokio::spawn(async move {
+        axum::serve(listener, Router::new().route("/mcp", post(handler)))
+            .await
+            .unwrap();
+    });
+    let binding = ExternalHttpProviderBinding {
+        provider_ref: "mcp-resource:external-1".to_string(),
+        endpoint: reqwest::Url::parse(format!("http://{address}/mcp").as_str()).unwrap(),
+        headers: HeaderMap::new(),
+        http: reqwest::Client::builder()
+            .redirect(Policy::none())
+            .timeout(Duration::from_secs(5))
+            .build()
+            .unwrap(),
+        resolved_addresses: vec![address],
+        allow_writes: false,
+        allowed_tool_names: HashSet::new(),
+        blocked_tool_names: HashSet::new(),
+    };
+    let snapshot = snapshot(binding);
+    let provider = ExternalHttpProvider::new(Duration::from_secs(5), 64 * 1024);
+
+    let prompts = provider.list_prompts(&snapshot).await;
+    assert_eq!(prompts.len(), 1);
+    assert_eq!(prompts[0]["name"], "external-1/summarize");
+    let prompt = provider
+        .get_prompt(
+            &snapshot,
+            "external-1/summarize",
+            json!({"topic": "releases"}),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        prompt.pointer("/messages/0/content/text"),
+        Some(&json!("Summarize releases"))
+    );
+    assert!(provider
+        .get_prompt(&snapshot, "other/summarize", json!({}))
+        .await
+        .is_err());
+    assert!(provider
+        .get_prompt(&snapshot, "summarize", json!({}))
+        .await
+        .is_err());
+
+    let resources = provider.list_resources(&snapshot).await;
+    assert_eq!(
+        resources
+            .iter()
+            .map(|resource| resource["uri"].as_str().unwrap())
+            .collect::<Vec<_>>(),
+        vec!["docs://readme", "docs://changelog"]
+    );
+    let contents = provider
+        .read_resource(&snapshot, "docs://readme")
+        .await
+        .unwrap();
+    assert_eq!(contents.pointer("/contents/0/text"), Some(&json!("# Docs")));
+    assert!(provider
+        .read_resource(&snapshot, "docs://missing")
+        .await
+        .is_err());
+    server.abort();
+}
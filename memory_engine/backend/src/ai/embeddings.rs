@@ -0,0 +1,294 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use chatos_service_runtime::http_body::{
+    read_response_preview_text_limited_or_message, ERROR_BODY_PREVIEW_LIMIT_BYTES,
+};
+use reqwest::Client;
+use serde_json::{json, Value};
+use tokio::time;
+
+use crate::config::AppConfig;
+
+use super::protocol::normalize_base_url;
+
+const EMBEDDING_PROVIDER_DISABLED: &str = "disabled";
+const EMBEDDING_PROVIDER_OPENAI_COMPATIBLE: &str = "openai_compatible";
+const EMBEDDING_PROVIDER_LOCAL_HASHING: &str = "local_hashing";
+
+const EMBEDDING_REQUEST_BATCH_SIZE: usize = 64;
+
+/// Turns memory text into vectors for semantic recall. Vectors are only ever
+/// compared with vectors that share the same `model_key`.
+#[async_trait]
+pub(crate) trait EmbeddingProvider: Send + Sync {
+    fn model_key(&self) -> String;
+
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, String>;
+}
+
+pub(crate) fn build_embedding_provider(
+    config: &AppConfig,
+) -> Result<Option<Arc<dyn EmbeddingProvider>>, String> {
+    match config.embedding_provider.as_str() {
+        EMBEDDING_PROVIDER_DISABLED => Ok(None),
+        EMBEDDING_PROVIDER_LOCAL_HASHING => Ok(Some(Arc::new(HashingEmbeddingProvider::new(
+            config.embedding_dimensions,
+        )))),
+        EMBEDDING_PROVIDER_OPENAI_COMPATIBLE => {
+            let http = Client::builder()
+                .connect_timeout(Duration::from_secs(10))
+                .build()
+                .map_err(|err| err.to_string())?;
+            let base_url = config
+                .embedding_base_url
+                .clone()
+                .unwrap_or_else(|| config.openai_base_url.clone());
+            Ok(Some(Arc::new(OpenAiCompatibleEmbeddingProvider {
+                http,
+                api_key: config
+                    .embedding_api_key
+                    .clone()
+                    .or_else(|| config.openai_api_key.clone()),
+                base_url: normalize_base_url(base_url.as_str()),
+                model: config.embedding_model.clone(),
+                timeout_secs: config.ai_request_timeout_secs,
+            })))
+        }
+        other => Err(format!("unsupported embedding provider: {other}")),
+    }
+}
+
+pub(crate) fn cosine_similarity(left: &[f32], right: &[f32]) -> f32 {
+    if left.is_empty() || left.len() != right.len() {
+        return 0.0;
+    }
+    let mut dot = 0.0f32;
+    let mut left_norm = 0.0f32;
+    let mut right_norm = 0.0f32;
+    for (left_value, right_value) in left.iter().zip(right) {
+        dot += left_value * right_value;
+        left_norm += left_value * left_value;
+        right_norm += right_value * right_value;
+    }
+    if left_norm <= f32::EPSILON || right_norm <= f32::EPSILON {
+        return 0.0;
+    }
+    dot / (left_norm.sqrt() * right_norm.sqrt())
+}
+
+pub(crate) fn build_embeddings_endpoint(base_url: &str) -> String {
+    let normalized = normalize_base_url(base_url);
+    if normalized.ends_with("/embeddings") {
+        normalized
+    } else {
+        format!("{}/embeddings", normalized)
+    }
+}
+
+pub(crate) struct OpenAiCompatibleEmbeddingProvider {
+    http: Client,
+    api_key: Option<String>,
+    base_url: String,
+    model: String,
+    timeout_secs: u64,
+}
+
+#[async_trait]
+impl EmbeddingProvider for OpenAiCompatibleEmbeddingProvider {
+    fn model_key(&self) -> String {
+        format!("{EMBEDDING_PROVIDER_OPENAI_COMPATIBLE}:{}", self.model)
+    }
+
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let mut vectors = Vec::with_capacity(inputs.len());
+        for chunk in inputs.chunks(EMBEDDING_REQUEST_BATCH_SIZE) {
+            vectors.extend(self.embed_batch(chunk).await?);
+        }
+        Ok(vectors)
+    }
+}
+
+impl OpenAiCompatibleEmbeddingProvider {
+    async fn embed_batch(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        let endpoint = build_embeddings_endpoint(self.base_url.as_str());
+        let mut request = self
+            .http
+            .post(endpoint.as_str())
+            .header("Content-Type", "application/json")
+            .json(&json!({
+                "model": self.model,
+                "input": inputs,
+            }));
+        if let Some(api_key) = self
+            .api_key
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+        {
+            request = request.bearer_auth(api_key);
+        }
+        let response = time::timeout(Duration::from_secs(self.timeout_secs), request.send())
+            .await
+            .map_err(|_| format!("embedding request timed out after {}s", self.timeout_secs))?
+            .map_err(|err| format!("embedding request failed: {err}"))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let response_body = read_response_preview_text_limited_or_message(
+                response,
+                ERROR_BODY_PREVIEW_LIMIT_BYTES,
+            )
+            .await;
+            return Err(format!(
+                "embedding request status={} endpoint={} body={}",
+                status, endpoint, response_body
+            ));
+        }
+
+        let body = time::timeout(
+            Duration::from_secs(self.timeout_secs),
+            response.json::<Value>(),
+        )
+        .await
+        .map_err(|_| format!("embedding response timed out after {}s", self.timeout_secs))?
+        .map_err(|err| format!("embedding response decode failed: {err}"))?;
+        parse_embeddings_response(&body, inputs.len())
+    }
+}
+
+pub(crate) fn parse_embeddings_response(
+    body: &Value,
+    expected: usize,
+) -> Result<Vec<Vec<f32>>, String> {
+    let data = body
+        .get("data")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "embedding response missing data array".to_string())?;
+    let mut vectors = vec![None; expected];
+    for (position, item) in data.iter().enumerate() {
+        let index = item
+            .get("index")
+            .and_then(Value::as_u64)
+            .map(|value| value as usize)
+            .unwrap_or(position);
+        let vector = item
+            .get("embedding")
+            .and_then(Value::as_array)
+            .ok_or_else(|| format!("embedding response item {index} missing embedding"))?
+            .iter()
+            .map(|value| value.as_f64().map(|number| number as f32))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| format!("embedding response item {index} is not numeric"))?;
+        let slot = vectors
+            .get_mut(index)
+            .ok_or_else(|| format!("embedding response index {index} out of range"))?;
+        *slot = Some(vector);
+    }
+    vectors
+        .into_iter()
+        .enumerate()
+        .map(|(index, vector)| {
+            vector.ok_or_else(|| format!("embedding response missing item {index}"))
+        })
+        .collect()
+}
+
+/// Deterministic feature-hashing embedder. It needs no model server, so it
+/// keeps semantic recall usable in local and air-gapped deployments; lexical
+/// overlap (words for Latin text, character bigrams for CJK) drives similarity.
+pub(crate) struct HashingEmbeddingProvider {
+    dimensions: usize,
+}
+
+impl HashingEmbeddingProvider {
+    pub(crate) fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions: dimensions.max(1),
+        }
+    }
+
+    pub(crate) fn embed_text(&self, text: &str) -> Vec<f32> {
+        let mut vector = vec![0.0f32; self.dimensions];
+        for feature in hashing_features(text) {
+            let hash = fnv1a_64(feature.as_bytes());
+            let index = (hash % self.dimensions as u64) as usize;
+            let sign = if (hash >> 63) == 0 { 1.0 } else { -1.0 };
+            vector[index] += sign;
+        }
+        let norm = vector.iter().map(|value| value * value).sum::<f32>().sqrt();
+        if norm > f32::EPSILON {
+            for value in &mut vector {
+                *value /= norm;
+            }
+        }
+        vector
+    }
+}
+
+#[async_trait]
+impl EmbeddingProvider for HashingEmbeddingProvider {
+    fn model_key(&self) -> String {
+        format!("{EMBEDDING_PROVIDER_LOCAL_HASHING}:{}", self.dimensions)
+    }
+
+    async fn embed(&self, inputs: &[String]) -> Result<Vec<Vec<f32>>, String> {
+        Ok(inputs
+            .iter()
+            .map(|input| self.embed_text(input.as_str()))
+            .collect())
+    }
+}
+
+fn hashing_features(text: &str) -> Vec<String> {
+    let mut features = Vec::new();
+    let mut word = String::new();
+    let mut previous_cjk: Option<char> = None;
+    for ch in text.chars().flat_map(char::to_lowercase) {
+        if is_cjk(ch) {
+            flush_word(&mut word, &mut features);
+            features.push(ch.to_string());
+            if let Some(previous) = previous_cjk {
+                features.push(format!("{previous}{ch}"));
+            }
+            previous_cjk = Some(ch);
+            continue;
+        }
+        previous_cjk = None;
+        if ch.is_alphanumeric() || ch == '_' {
+            word.push(ch);
+        } else {
+            flush_word(&mut word, &mut features);
+        }
+    }
+    flush_word(&mut word, &mut features);
+    features
+}
+
+fn flush_word(word: &mut String, features: &mut Vec<String>) {
+    if word.chars().count() > 1 {
+        features.push(std::mem::take(word));
+    } else {
+        word.clear();
+    }
+}
+
+fn is_cjk(ch: char) -> bool {
+    matches!(
+        ch as u32,
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF
+    )
+}
+
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
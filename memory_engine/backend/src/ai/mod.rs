@@ -2,10 +2,14 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 mod client;
+mod embeddings;
 mod parsing;
 mod protocol;
 
 pub(crate) use client::{AiClient, AiGenerateTextError, SUMMARY_SYSTEM_PROMPT};
+#[cfg(test)]
+pub(crate) use embeddings::HashingEmbeddingProvider;
+pub(crate) use embeddings::{build_embedding_provider, cosine_similarity, EmbeddingProvider};
 
 #[cfg(test)]
 mod tests;
@@ -3,6 +3,7 @@
 
 use serde_json::json;
 
+use super::embeddings::{build_embeddings_endpoint, parse_embeddings_response};
 use super::parsing::{
     extract_chat_completion_stream_text, extract_chat_completion_text,
     extract_responses_output_text, extract_responses_stream_text, extract_stream_error_message,
@@ -173,3 +174,46 @@ fn stream_error_parser_accepts_response_failed_event() {
         Some("provider failure")
     );
 }
+
+#[test]
+fn embeddings_endpoint_appends_path_once() {
+    assert_eq!(
+        build_embeddings_endpoint("http://localhost:11434/v1/"),
+        "http://localhost:11434/v1/embeddings"
+    );
+    assert_eq!(
+        build_embeddings_endpoint("https://api.openai.com/v1/embeddings"),
+        "https://api.openai.com/v1/embeddings"
+    );
+}
+
+#[test]
+fn embeddings_response_parser_orders_items_by_index() {
+    let value = json!({
+        "data": [
+            {"index": 1, "embedding": [0.0, 1.0]},
+            {"index": 0, "embedding": [1.0, 0.0]}
+        ]
+    });
+    assert_eq!(
+        parse_embeddings_response(&value, 2).expect("parse embeddings"),
+        vec![vec![1.0, 0.0], vec![0.0, 1.0]]
+    );
+    assert!(parse_embeddings_response(&value, 3).is_err());
+}
+
+#[test]
+fn hashing_embeddings_rank_lexical_overlap_above_unrelated_text() {
+    let provider = super::HashingEmbeddingProvider::new(256);
+    let query = provider.embed_text("How do we deploy the worker to staging?");
+    let related = provider.embed_text("Deploy the worker with the staging pipeline");
+    let unrelated = provider.embed_text("用户喜欢深色主题");
+
+    assert!(
+        super::cosine_similarity(&query, &related) > super::cosine_similarity(&query, &unrelated)
+    );
+    assert_eq!(
+        provider.embed_text("部署工作节点"),
+        provider.embed_text("部署工作节点")
+    );
+}
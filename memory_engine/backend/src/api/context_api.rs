@@ -17,7 +17,7 @@ pub async fn compose_context(
 ) -> Result<Json<ComposeContextResponse>, (axum::http::StatusCode, String)> {
     auth.ensure_tenant_scope(req.tenant_id.as_str())?;
     source_guard::ensure_write_source_allowed(&state.pool, req.source_id.as_str()).await?;
-    context::compose_context(&state.pool, req)
+    context::compose_context(&state.pool, state.embedding_provider.as_deref(), req)
         .await
         .map(Json)
         .map_err(internal_error)
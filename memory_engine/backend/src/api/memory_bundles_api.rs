@@ -0,0 +1,51 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::sync::Arc;
+
+use axum::{extract::State, http::StatusCode, Json};
+
+use super::source_guard;
+use crate::models::{
+    ExportMemoryBundleRequest, ImportMemoryBundleRequest, MemoryBundle, MemoryBundleImportReport,
+};
+use crate::repositories::memory_bundles;
+use crate::state::AppState;
+
+pub async fn export_memory_bundle(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<ExportMemoryBundleRequest>,
+) -> Result<Json<MemoryBundle>, (StatusCode, String)> {
+    let selector = memory_bundles::resolve_memory_bundle_selector(&state.pool, &req)
+        .await
+        .map_err(bad_request)?;
+    memory_bundles::export_memory_bundle(&state.pool, selector)
+        .await
+        .map(Json)
+        .map_err(internal_error)
+}
+
+pub async fn import_memory_bundle(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<ImportMemoryBundleRequest>,
+) -> Result<Json<MemoryBundleImportReport>, (StatusCode, String)> {
+    memory_bundles::verify_memory_bundle(&req.bundle).map_err(bad_request)?;
+    let source_id = req
+        .options
+        .source_id
+        .as_deref()
+        .unwrap_or(req.bundle.selector.source_id.as_str());
+    source_guard::ensure_write_source_allowed(&state.pool, source_id).await?;
+    memory_bundles::import_memory_bundle(&state.pool, req.bundle, &req.options)
+        .await
+        .map(Json)
+        .map_err(internal_error)
+}
+
+fn bad_request(message: String) -> (StatusCode, String) {
+    (StatusCode::BAD_REQUEST, message)
+}
+
+fn internal_error(message: String) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, message)
+}
@@ -14,6 +14,7 @@ mod internal_audit;
 mod internal_auth;
 mod jobs_api;
 mod memory_auth;
+mod memory_bundles_api;
 mod model_profile_auth;
 mod operator_auth;
 mod queue_operations_api;
@@ -4,17 +4,21 @@
 use std::sync::Arc;
 
 use axum::{
-    routing::{get, post, put},
+    extract::DefaultBodyLimit,
+    routing::{get, patch, post, put},
     Router,
 };
 
 use crate::api::{
-    context_api, health_api, jobs_api, queue_operations_api, records_api, sources_api,
-    subject_memories_api, subject_memory_scopes_api, subjects_api, summaries_api, system_api,
-    thread_snapshots_api, threads_api,
+    context_api, health_api, jobs_api, memory_bundles_api, queue_operations_api, records_api,
+    sources_api, subject_memories_api, subject_memory_scopes_api, subjects_api, summaries_api,
+    system_api, thread_snapshots_api, threads_api,
 };
 use crate::state::AppState;
 
+/// Bundles carry whole sources, so imports get more room than axum's 2 MiB default.
+const MEMORY_BUNDLE_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
 pub fn public_routes() -> Router<Arc<AppState>> {
     Router::new()
         .route("/health", get(health_api::health))
@@ -48,6 +52,15 @@ pub fn operator_routes() -> Router<Arc<AppState>> {
             "/api/memory-engine/v1/queue-operations/replay",
             post(queue_operations_api::replay_queue_dead_letter),
         )
+        .route(
+            "/api/memory-engine/v1/bundles/export",
+            post(memory_bundles_api::export_memory_bundle),
+        )
+        .route(
+            "/api/memory-engine/v1/bundles/import",
+            post(memory_bundles_api::import_memory_bundle)
+                .layer(DefaultBodyLimit::max(MEMORY_BUNDLE_BODY_LIMIT)),
+        )
 }
 
 pub fn data_routes() -> Router<Arc<AppState>> {
@@ -80,6 +93,26 @@ pub fn data_routes() -> Router<Arc<AppState>> {
             "/api/memory-engine/v1/subject-memories/query",
             post(subject_memories_api::query_subject_memories),
         )
+        .route(
+            "/api/memory-engine/v1/subject-memories/{memory_id}",
+            patch(subject_memories_api::update_subject_memory),
+        )
+        .route(
+            "/api/memory-engine/v1/subject-memories/{memory_id}/versions",
+            get(subject_memories_api::list_subject_memory_versions),
+        )
+        .route(
+            "/api/memory-engine/v1/subject-memories/{memory_id}/provenance",
+            get(subject_memories_api::get_subject_memory_provenance),
+        )
+        .route(
+            "/api/memory-engine/v1/subject-memory-conflicts/query",
+            post(subject_memories_api::query_subject_memory_conflicts),
+        )
+        .route(
+            "/api/memory-engine/v1/subject-memory-conflicts/{conflict_id}/resolve",
+            post(subject_memories_api::resolve_subject_memory_conflict),
+        )
         .route(
             "/api/memory-engine/v1/threads/{thread_id}",
             get(threads_api::get_thread)
@@ -273,6 +273,7 @@ mod tests {
                 queue_critical_messages: 1_000,
             }),
             cloud_agent_store: chatos_cloud_agent_runtime::CloudAgentStateStore::memory(),
+            embedding_provider: None,
             config,
         })
     }
@@ -291,6 +292,12 @@ mod tests {
             openai_base_url: "https://api.openai.com/v1".to_string(),
             openai_model: "test".to_string(),
             openai_temperature: 0.0,
+            embedding_provider: "disabled".to_string(),
+            embedding_base_url: None,
+            embedding_api_key: None,
+            embedding_model: "text-embedding-3-small".to_string(),
+            embedding_dimensions: 512,
+            embedding_backfill_limit: 64,
             api_enabled: true,
             worker_enabled: false,
             worker_interval_secs: 30,
@@ -4,7 +4,7 @@
 use std::sync::Arc;
 
 use axum::{
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 
@@ -95,6 +95,26 @@ pub fn routes() -> Router<Arc<AppState>> {
             "/api/memory-engine/v1/sdk/subject-memories/query",
             post(sdk_api::query_subject_memories),
         )
+        .route(
+            "/api/memory-engine/v1/sdk/subject-memories/{memory_id}",
+            patch(sdk_api::update_subject_memory),
+        )
+        .route(
+            "/api/memory-engine/v1/sdk/subject-memories/{memory_id}/versions",
+            post(sdk_api::list_subject_memory_versions),
+        )
+        .route(
+            "/api/memory-engine/v1/sdk/subject-memories/{memory_id}/provenance",
+            post(sdk_api::get_subject_memory_provenance),
+        )
+        .route(
+            "/api/memory-engine/v1/sdk/subject-memory-conflicts/query",
+            post(sdk_api::query_subject_memory_conflicts),
+        )
+        .route(
+            "/api/memory-engine/v1/sdk/subject-memory-conflicts/{conflict_id}/resolve",
+            post(sdk_api::resolve_subject_memory_conflict),
+        )
         .route(
             "/api/memory-engine/v1/sdk/summaries/query-by-thread-label",
             post(sdk_api::list_summaries_by_thread_label),
@@ -27,7 +27,7 @@ pub async fn compose_context(
         thread_id: req.thread_id,
         policy: req.policy,
     };
-    context::compose_context(&state.pool, direct)
+    context::compose_context(&state.pool, state.embedding_provider.as_deref(), direct)
         .await
         .map(Json)
         .map_err(internal_error)
@@ -26,7 +26,9 @@ pub use snapshots::{
     get_latest_thread_snapshot, get_thread_snapshot_by_turn, upsert_thread_snapshot,
 };
 pub use subject_memories::{
-    list_summaries_by_thread_label, query_subject_memories, upsert_subject_memory_scope,
+    get_subject_memory_provenance, list_subject_memory_versions, list_summaries_by_thread_label,
+    query_subject_memories, query_subject_memory_conflicts, resolve_subject_memory_conflict,
+    update_subject_memory, upsert_subject_memory_scope,
 };
 pub use summaries::{
     delete_thread_summary, get_thread_active_summary_status, list_thread_summaries,
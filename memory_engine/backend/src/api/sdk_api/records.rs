@@ -15,6 +15,7 @@ use crate::models::{
     ThreadRecordsPageResponse, TurnProcessRecordsResponse,
 };
 use crate::repositories::records;
+use crate::services::context;
 use crate::state::AppState;
 
 use super::auth::{SdkAuthContext, SdkTenantQuery};
@@ -57,6 +58,12 @@ pub async fn batch_sync_records(
         records::batch_sync_records(&state.config, &state.pool, thread_id.as_str(), &direct)
             .await
             .map_err(internal_error)?;
+    context::spawn_thread_embedding_index(
+        &state,
+        direct.tenant_id.as_str(),
+        direct.source_id.as_str(),
+        thread_id.as_str(),
+    );
     Ok(Json(BatchSyncRecordsResponse {
         thread_id,
         received_count: direct.records.len(),
@@ -25,7 +25,9 @@ pub use snapshots::{
     SdkUpsertThreadSnapshotRequest,
 };
 pub use subject_memories::{
-    SdkListSummariesByThreadLabelRequest, SdkQuerySubjectMemoriesRequest,
+    SdkGetSubjectMemoryRequest, SdkListSummariesByThreadLabelRequest,
+    SdkQuerySubjectMemoriesRequest, SdkQuerySubjectMemoryConflictsRequest,
+    SdkResolveSubjectMemoryConflictRequest, SdkUpdateSubjectMemoryRequest,
     SdkUpsertSubjectMemoryScopeRequest,
 };
 pub use summaries::{
@@ -2,6 +2,8 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 pub use memory_engine_sdk::{
-    SdkListSummariesByThreadLabelRequest, SdkQuerySubjectMemoriesRequest,
+    SdkGetSubjectMemoryRequest, SdkListSummariesByThreadLabelRequest,
+    SdkQuerySubjectMemoriesRequest, SdkQuerySubjectMemoryConflictsRequest,
+    SdkResolveSubjectMemoryConflictRequest, SdkUpdateSubjectMemoryRequest,
     SdkUpsertSubjectMemoryScopeRequest,
 };
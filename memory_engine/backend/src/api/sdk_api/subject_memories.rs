@@ -10,8 +10,14 @@ use axum::{
 };
 use serde_json::json;
 
+use crate::api::subject_memories_api::{
+    apply_subject_memory_conflict_resolution, apply_subject_memory_update,
+    subject_memory_provenance,
+};
 use crate::models::{
-    EngineSubjectMemory, EngineSubjectMemoryScope, UpsertSubjectMemoryScopeRequest,
+    EngineSubjectMemory, EngineSubjectMemoryConflict, EngineSubjectMemoryScope,
+    ResolveSubjectMemoryConflictRequest, SubjectMemoryProvenanceResponse,
+    UpdateSubjectMemoryRequest, UpsertSubjectMemoryScopeRequest,
 };
 use crate::repositories::{subject_memories, subject_memory_scopes, summaries};
 use crate::state::AppState;
@@ -19,7 +25,9 @@ use crate::state::AppState;
 use super::auth::SdkAuthContext;
 use super::internal_error;
 use super::requests::{
-    SdkListSummariesByThreadLabelRequest, SdkQuerySubjectMemoriesRequest,
+    SdkGetSubjectMemoryRequest, SdkListSummariesByThreadLabelRequest,
+    SdkQuerySubjectMemoriesRequest, SdkQuerySubjectMemoryConflictsRequest,
+    SdkResolveSubjectMemoryConflictRequest, SdkUpdateSubjectMemoryRequest,
     SdkUpsertSubjectMemoryScopeRequest,
 };
 
@@ -91,6 +99,66 @@ pub async fn query_subject_memories(
     Ok(Json(json!({ "items": items })))
 }
 
+pub async fn update_subject_memory(
+    State(state): State<Arc<AppState>>,
+    auth: SdkAuthContext,
+    Path(memory_id): Path<String>,
+    Json(req): Json<SdkUpdateSubjectMemoryRequest>,
+) -> Result<Json<EngineSubjectMemory>, (StatusCode, String)> {
+    auth.require_tenant(req.tenant_id.as_str())?;
+    let direct = UpdateSubjectMemoryRequest {
+        tenant_id: req.tenant_id,
+        source_id: auth.source_id().to_string(),
+        text: req.text,
+        pinned: req.pinned,
+        expires_at: req.expires_at,
+        ttl_seconds: req.ttl_seconds,
+        clear_expiry: req.clear_expiry,
+        expected_version: req.expected_version,
+        edited_by: req.edited_by,
+        reason: req.reason,
+    };
+    apply_subject_memory_update(&state.pool, memory_id.as_str(), &direct)
+        .await
+        .map(Json)
+}
+
+pub async fn list_subject_memory_versions(
+    State(state): State<Arc<AppState>>,
+    auth: SdkAuthContext,
+    Path(memory_id): Path<String>,
+    Json(req): Json<SdkGetSubjectMemoryRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    auth.require_tenant(req.tenant_id.as_str())?;
+    let items = subject_memories::list_subject_memory_versions(
+        &state.pool,
+        req.tenant_id.as_str(),
+        auth.source_id(),
+        memory_id.as_str(),
+        100,
+    )
+    .await
+    .map_err(internal_error)?;
+    Ok(Json(json!({ "items": items })))
+}
+
+pub async fn get_subject_memory_provenance(
+    State(state): State<Arc<AppState>>,
+    auth: SdkAuthContext,
+    Path(memory_id): Path<String>,
+    Json(req): Json<SdkGetSubjectMemoryRequest>,
+) -> Result<Json<SubjectMemoryProvenanceResponse>, (StatusCode, String)> {
+    auth.require_tenant(req.tenant_id.as_str())?;
+    subject_memory_provenance(
+        &state.pool,
+        req.tenant_id.as_str(),
+        auth.source_id(),
+        memory_id.as_str(),
+    )
+    .await
+    .map(Json)
+}
+
 pub async fn list_summaries_by_thread_label(
     State(state): State<Arc<AppState>>,
     auth: SdkAuthContext,
@@ -113,3 +181,42 @@ pub async fn list_summaries_by_thread_label(
     .map_err(internal_error)?;
     Ok(Json(json!({ "items": items })))
 }
+
+pub async fn query_subject_memory_conflicts(
+    State(state): State<Arc<AppState>>,
+    auth: SdkAuthContext,
+    Json(req): Json<SdkQuerySubjectMemoryConflictsRequest>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    auth.require_tenant(req.tenant_id.as_str())?;
+    let items = subject_memories::query_subject_memory_conflicts(
+        &state.pool,
+        req.tenant_id.as_str(),
+        auth.source_id(),
+        req.subject_id.as_deref(),
+        req.status.as_deref(),
+        req.limit.unwrap_or(100),
+        req.offset.unwrap_or(0),
+    )
+    .await
+    .map_err(internal_error)?;
+    Ok(Json(json!({ "items": items })))
+}
+
+pub async fn resolve_subject_memory_conflict(
+    State(state): State<Arc<AppState>>,
+    auth: SdkAuthContext,
+    Path(conflict_id): Path<String>,
+    Json(req): Json<SdkResolveSubjectMemoryConflictRequest>,
+) -> Result<Json<EngineSubjectMemoryConflict>, (StatusCode, String)> {
+    auth.require_tenant(req.tenant_id.as_str())?;
+    let direct = ResolveSubjectMemoryConflictRequest {
+        tenant_id: req.tenant_id,
+        source_id: auth.source_id().to_string(),
+        action: req.action,
+        resolved_by: req.resolved_by,
+        note: req.note,
+    };
+    apply_subject_memory_conflict_resolution(&state.pool, conflict_id.as_str(), &direct)
+        .await
+        .map(Json)
+}
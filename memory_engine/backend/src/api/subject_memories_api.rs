@@ -11,11 +11,15 @@ use serde::Deserialize;
 use serde_json::json;
 
 use super::{memory_auth::MemoryAuthContext, source_guard};
+use crate::db::Db;
 use crate::models::{
-    EngineSubjectMemory, MarkSubjectMemoriesRolledUpRequest, MarkSubjectMemoriesRolledUpResponse,
-    QuerySubjectMemoriesRequest, UpsertSubjectMemoryRequest,
+    EngineSubjectMemory, EngineSubjectMemoryConflict, MarkSubjectMemoriesRolledUpRequest,
+    MarkSubjectMemoriesRolledUpResponse, QuerySubjectMemoriesRequest,
+    QuerySubjectMemoryConflictsRequest, ResolveSubjectMemoryConflictRequest,
+    SubjectMemoryProvenanceResponse, UpdateSubjectMemoryRequest, UpsertSubjectMemoryRequest,
 };
 use crate::repositories::subject_memories;
+use crate::services::subject_memory::{load_subject_memory_provenance, plan_subject_memory_edit};
 use crate::state::AppState;
 
 #[derive(Debug, Deserialize)]
@@ -28,6 +32,13 @@ pub struct ListSubjectMemoriesQuery {
     offset: Option<i64>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct SubjectMemoryLookupQuery {
+    tenant_id: String,
+    source_id: String,
+    limit: Option<i64>,
+}
+
 pub async fn upsert_subject_memory(
     State(state): State<Arc<AppState>>,
     auth: MemoryAuthContext,
@@ -115,6 +126,236 @@ pub async fn query_subject_memories(
     Ok(Json(json!({ "items": items })))
 }
 
+pub async fn update_subject_memory(
+    State(state): State<Arc<AppState>>,
+    auth: MemoryAuthContext,
+    Path(memory_id): Path<String>,
+    Json(req): Json<UpdateSubjectMemoryRequest>,
+) -> Result<Json<EngineSubjectMemory>, (axum::http::StatusCode, String)> {
+    auth.ensure_tenant_scope(req.tenant_id.as_str())?;
+    source_guard::ensure_write_source_allowed(&state.pool, req.source_id.as_str()).await?;
+    apply_subject_memory_update(&state.pool, memory_id.as_str(), &req)
+        .await
+        .map(Json)
+}
+
+pub async fn list_subject_memory_versions(
+    State(state): State<Arc<AppState>>,
+    auth: MemoryAuthContext,
+    Path(memory_id): Path<String>,
+    Query(query): Query<SubjectMemoryLookupQuery>,
+) -> Result<Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+    auth.ensure_tenant_scope(query.tenant_id.as_str())?;
+    let items = subject_memories::list_subject_memory_versions(
+        &state.pool,
+        query.tenant_id.as_str(),
+        query.source_id.as_str(),
+        memory_id.as_str(),
+        query.limit.unwrap_or(100),
+    )
+    .await
+    .map_err(internal_error)?;
+    Ok(Json(json!({ "items": items })))
+}
+
+pub async fn get_subject_memory_provenance(
+    State(state): State<Arc<AppState>>,
+    auth: MemoryAuthContext,
+    Path(memory_id): Path<String>,
+    Query(query): Query<SubjectMemoryLookupQuery>,
+) -> Result<Json<SubjectMemoryProvenanceResponse>, (axum::http::StatusCode, String)> {
+    auth.ensure_tenant_scope(query.tenant_id.as_str())?;
+    subject_memory_provenance(
+        &state.pool,
+        query.tenant_id.as_str(),
+        query.source_id.as_str(),
+        memory_id.as_str(),
+    )
+    .await
+    .map(Json)
+}
+
+pub async fn query_subject_memory_conflicts(
+    State(state): State<Arc<AppState>>,
+    auth: MemoryAuthContext,
+    Json(req): Json<QuerySubjectMemoryConflictsRequest>,
+) -> Result<Json<serde_json::Value>, (axum::http::StatusCode, String)> {
+    auth.ensure_tenant_scope(req.tenant_id.as_str())?;
+    let items = subject_memories::query_subject_memory_conflicts(
+        &state.pool,
+        req.tenant_id.as_str(),
+        req.source_id.as_str(),
+        req.subject_id.as_deref(),
+        req.status.as_deref(),
+        req.limit.unwrap_or(100),
+        req.offset.unwrap_or(0),
+    )
+    .await
+    .map_err(internal_error)?;
+    Ok(Json(json!({ "items": items })))
+}
+
+pub async fn resolve_subject_memory_conflict(
+    State(state): State<Arc<AppState>>,
+    auth: MemoryAuthContext,
+    Path(conflict_id): Path<String>,
+    Json(req): Json<ResolveSubjectMemoryConflictRequest>,
+) -> Result<Json<EngineSubjectMemoryConflict>, (axum::http::StatusCode, String)> {
+    auth.ensure_tenant_scope(req.tenant_id.as_str())?;
+    source_guard::ensure_write_source_allowed(&state.pool, req.source_id.as_str()).await?;
+    apply_subject_memory_conflict_resolution(&state.pool, conflict_id.as_str(), &req)
+        .await
+        .map(Json)
+}
+
+pub(crate) async fn apply_subject_memory_update(
+    db: &Db,
+    memory_id: &str,
+    req: &UpdateSubjectMemoryRequest,
+) -> Result<EngineSubjectMemory, (axum::http::StatusCode, String)> {
+    let existing = find_subject_memory(
+        db,
+        req.tenant_id.as_str(),
+        req.source_id.as_str(),
+        memory_id,
+    )
+    .await?;
+    if let Some(expected) = req.expected_version {
+        if expected != existing.version {
+            return Err(version_conflict(expected, existing.version));
+        }
+    }
+    let edit = plan_subject_memory_edit(&existing, req, chrono::Utc::now())
+        .map_err(|err| (axum::http::StatusCode::BAD_REQUEST, err))?;
+    subject_memories::apply_subject_memory_edit(db, &existing, &edit)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| version_conflict(existing.version, existing.version + 1))
+}
+
+pub(crate) async fn subject_memory_provenance(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    memory_id: &str,
+) -> Result<SubjectMemoryProvenanceResponse, (axum::http::StatusCode, String)> {
+    let memory = find_subject_memory(db, tenant_id, source_id, memory_id).await?;
+    load_subject_memory_provenance(db, memory)
+        .await
+        .map_err(internal_error)
+}
+
+/// `keep_left` / `keep_right` supersede the other memory of the pair with the
+/// kept one; `dismiss` closes the conflict and leaves both memories active.
+pub(crate) async fn apply_subject_memory_conflict_resolution(
+    db: &Db,
+    conflict_id: &str,
+    req: &ResolveSubjectMemoryConflictRequest,
+) -> Result<EngineSubjectMemoryConflict, (axum::http::StatusCode, String)> {
+    let conflict = subject_memories::get_subject_memory_conflict(
+        db,
+        req.tenant_id.as_str(),
+        req.source_id.as_str(),
+        conflict_id,
+    )
+    .await
+    .map_err(internal_error)?
+    .ok_or_else(|| {
+        (
+            axum::http::StatusCode::NOT_FOUND,
+            "subject memory conflict not found".to_string(),
+        )
+    })?;
+    if conflict.status != "open" {
+        return Err(conflict_closed(conflict.status.as_str()));
+    }
+
+    let action = req.action.trim();
+    let (status, superseded) = match action {
+        "keep_left" => (
+            "resolved",
+            Some((&conflict.right_memory_id, &conflict.left_memory_id)),
+        ),
+        "keep_right" => (
+            "resolved",
+            Some((&conflict.left_memory_id, &conflict.right_memory_id)),
+        ),
+        "dismiss" => ("dismissed", None),
+        other => {
+            return Err((
+                axum::http::StatusCode::BAD_REQUEST,
+                format!("unsupported conflict resolution action: {other}"),
+            ))
+        }
+    };
+    let note = req
+        .note
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty());
+    if let Some((memory_id, kept_id)) = superseded {
+        let marked = subject_memories::mark_subject_memory_superseded(
+            db,
+            conflict.tenant_id.as_str(),
+            conflict.source_id.as_str(),
+            memory_id,
+            kept_id,
+            Some(note.unwrap_or(conflict.reason.as_str())).filter(|value| !value.is_empty()),
+        )
+        .await
+        .map_err(internal_error)?;
+        if !marked {
+            return Err((
+                axum::http::StatusCode::CONFLICT,
+                "subject memory is pinned or no longer active".to_string(),
+            ));
+        }
+    }
+
+    subject_memories::close_subject_memory_conflict(
+        db,
+        &conflict,
+        status,
+        action,
+        req.resolved_by.as_deref(),
+        note,
+    )
+    .await
+    .map_err(internal_error)?
+    .ok_or_else(|| conflict_closed("closed"))
+}
+
+fn conflict_closed(status: &str) -> (axum::http::StatusCode, String) {
+    (
+        axum::http::StatusCode::CONFLICT,
+        format!("subject memory conflict is already {status}"),
+    )
+}
+
+async fn find_subject_memory(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    memory_id: &str,
+) -> Result<EngineSubjectMemory, (axum::http::StatusCode, String)> {
+    subject_memories::get_subject_memory_by_id(db, tenant_id, source_id, memory_id)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| {
+            (
+                axum::http::StatusCode::NOT_FOUND,
+                "subject memory not found".to_string(),
+            )
+        })
+}
+
+fn version_conflict(expected: i64, actual: i64) -> (axum::http::StatusCode, String) {
+    (
+        axum::http::StatusCode::CONFLICT,
+        format!("subject memory version conflict: expected {expected}, found {actual}"),
+    )
+}
+
 fn internal_error(message: String) -> (axum::http::StatusCode, String) {
     (axum::http::StatusCode::INTERNAL_SERVER_ERROR, message)
 }
@@ -20,6 +20,7 @@ use crate::models::{
     ThreadRecordsPageResponse, TurnProcessRecordsResponse,
 };
 use crate::repositories::records;
+use crate::services::context;
 use crate::state::AppState;
 
 pub async fn batch_sync_records(
@@ -34,6 +35,12 @@ pub async fn batch_sync_records(
         records::batch_sync_records(&state.config, &state.pool, thread_id.as_str(), &req)
             .await
             .map_err(internal_error)?;
+    context::spawn_thread_embedding_index(
+        &state,
+        req.tenant_id.as_str(),
+        req.source_id.as_str(),
+        thread_id.as_str(),
+    );
     Ok(Json(BatchSyncRecordsResponse {
         thread_id,
         received_count: req.records.len(),
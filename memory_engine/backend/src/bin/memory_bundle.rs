@@ -0,0 +1,124 @@
+#![allow(dead_code, unused_imports)]
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+#[path = "../config.rs"]
+mod config;
+#[path = "../db/mod.rs"]
+mod db;
+#[path = "../models/mod.rs"]
+mod models;
+#[path = "../repositories/mod.rs"]
+mod repositories;
+
+use std::collections::HashMap;
+
+use repositories::memory_bundles;
+
+use crate::models::{ExportMemoryBundleRequest, MemoryBundle, MemoryBundleImportOptions};
+
+const USAGE: &str = "usage:
+  memory_bundle export --tenant-id ID --source-id ID [--subject-id ID] [--scope-key KEY] --out FILE
+  memory_bundle import --in FILE --tenant-id ID [--source-id ID] [--mode merge|replace] [--remap-ids] [--dry-run]";
+
+const SWITCHES: [&str; 2] = ["--remap-ids", "--dry-run"];
+
+struct Args {
+    values: HashMap<String, String>,
+}
+
+impl Args {
+    fn parse(items: &[String]) -> Result<Self, String> {
+        let mut values = HashMap::new();
+        let mut iter = items.iter();
+        while let Some(flag) = iter.next() {
+            if !flag.starts_with("--") {
+                return Err(format!("unexpected argument: {flag}\n{USAGE}"));
+            }
+            if SWITCHES.contains(&flag.as_str()) {
+                values.insert(flag.clone(), "true".to_string());
+                continue;
+            }
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("{flag} requires a value\n{USAGE}"))?;
+            values.insert(flag.clone(), value.clone());
+        }
+        Ok(Self { values })
+    }
+
+    fn optional(&self, flag: &str) -> Option<String> {
+        self.values
+            .get(flag)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn required(&self, flag: &str) -> Result<String, String> {
+        self.optional(flag)
+            .ok_or_else(|| format!("{flag} is required\n{USAGE}"))
+    }
+
+    fn switch(&self, flag: &str) -> bool {
+        self.values.contains_key(flag)
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<(), String> {
+    let argv = std::env::args().skip(1).collect::<Vec<_>>();
+    let Some((command, rest)) = argv.split_first() else {
+        return Err(USAGE.to_string());
+    };
+    let args = Args::parse(rest)?;
+    if command != "export" && command != "import" {
+        return Err(format!("unknown command: {command}\n{USAGE}"));
+    }
+
+    chatos_service_runtime::load_service_dotenv(std::path::Path::new(env!("CARGO_MANIFEST_DIR")));
+    let config = config::AppConfig::from_env()?;
+    let pool = db::init_pool(&config).await?;
+    db::init_schema(&pool).await?;
+
+    if command == "export" {
+        let out = args.required("--out")?;
+        let req = ExportMemoryBundleRequest {
+            tenant_id: args.required("--tenant-id")?,
+            source_id: args.required("--source-id")?,
+            subject_id: args.optional("--subject-id"),
+            scope_key: args.optional("--scope-key"),
+        };
+        let selector = memory_bundles::resolve_memory_bundle_selector(&pool, &req).await?;
+        let bundle = memory_bundles::export_memory_bundle(&pool, selector).await?;
+        let bytes = serde_json::to_vec_pretty(&bundle).map_err(|err| err.to_string())?;
+        std::fs::write(out.as_str(), bytes).map_err(|err| format!("write {out} failed: {err}"))?;
+        let documents = bundle.collections.values().map(Vec::len).sum::<usize>();
+        println!(
+            "memory_bundle export complete out={} documents={} checksum={}",
+            out, documents, bundle.checksum
+        );
+        return Ok(());
+    }
+
+    let input = args.required("--in")?;
+    let bytes =
+        std::fs::read(input.as_str()).map_err(|err| format!("read {input} failed: {err}"))?;
+    let bundle: MemoryBundle =
+        serde_json::from_slice(&bytes).map_err(|err| format!("parse {input} failed: {err}"))?;
+    let options = MemoryBundleImportOptions {
+        tenant_id: args.required("--tenant-id")?,
+        source_id: args.optional("--source-id"),
+        mode: args.optional("--mode"),
+        remap_ids: args.switch("--remap-ids"),
+        dry_run: args.switch("--dry-run"),
+    };
+    let report = memory_bundles::import_memory_bundle(&pool, bundle, &options).await?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&report).map_err(|err| err.to_string())?
+    );
+    if !report.dry_run {
+        println!("run backfill_compact_turns for the imported source to rebuild compact turns");
+    }
+    Ok(())
+}
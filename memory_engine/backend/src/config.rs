@@ -17,6 +17,12 @@ pub struct AppConfig {
     pub openai_base_url: String,
     pub openai_model: String,
     pub openai_temperature: f64,
+    pub embedding_provider: String,
+    pub embedding_base_url: Option<String>,
+    pub embedding_api_key: Option<String>,
+    pub embedding_model: String,
+    pub embedding_dimensions: usize,
+    pub embedding_backfill_limit: usize,
     pub api_enabled: bool,
     pub worker_enabled: bool,
     pub worker_interval_secs: u64,
@@ -69,6 +75,16 @@ impl AppConfig {
         let openai_base_url = required_text("MEMORY_ENGINE_OPENAI_BASE_URL")?;
         let openai_model = required_text("MEMORY_ENGINE_OPENAI_MODEL")?;
         let openai_temperature = required_f64("MEMORY_ENGINE_OPENAI_TEMPERATURE")?.clamp(0.0, 2.0);
+        let embedding_provider = required_text("MEMORY_ENGINE_EMBEDDING_PROVIDER")?
+            .trim()
+            .to_ascii_lowercase();
+        let embedding_base_url = optional_text("MEMORY_ENGINE_EMBEDDING_BASE_URL");
+        let embedding_api_key = optional_text("MEMORY_ENGINE_EMBEDDING_API_KEY");
+        let embedding_model = required_text("MEMORY_ENGINE_EMBEDDING_MODEL")?;
+        let embedding_dimensions =
+            required_usize("MEMORY_ENGINE_EMBEDDING_DIMENSIONS")?.clamp(32, 4_096);
+        let embedding_backfill_limit =
+            required_usize("MEMORY_ENGINE_EMBEDDING_BACKFILL_LIMIT")?.clamp(1, 1_000);
         let api_enabled = required_runtime_bool("MEMORY_ENGINE_API_ENABLED")?;
         let worker_enabled = required_managed_bool("MEMORY_ENGINE_WORKER_ENABLED")?;
         let worker_interval_secs = required_u64("MEMORY_ENGINE_WORKER_INTERVAL_SECS")?.max(3);
@@ -144,6 +160,12 @@ impl AppConfig {
             openai_base_url,
             openai_model,
             openai_temperature,
+            embedding_provider,
+            embedding_base_url,
+            embedding_api_key,
+            embedding_model,
+            embedding_dimensions,
+            embedding_backfill_limit,
             api_enabled,
             worker_enabled,
             worker_interval_secs,
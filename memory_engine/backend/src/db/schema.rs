@@ -21,6 +21,7 @@ pub async fn init_schema(db: &Db) -> Result<(), String> {
     ensure_compact_turn_indexes(db).await?;
     ensure_summary_indexes(db).await?;
     ensure_thread_snapshot_indexes(db).await?;
+    ensure_memory_embedding_indexes(db).await?;
     Ok(())
 }
 
@@ -159,6 +160,30 @@ async fn ensure_subject_memory_indexes(db: &Db) -> Result<(), String> {
         db.collection("engine_subject_memories"),
         doc! {"tenant_id": 1, "source_id": 1, "subject_id": 1, "memory_type": 1, "level": 1, "source_digest": 1},
     )
+    .await?;
+    ensure_named_index(
+        db.collection("engine_subject_memories"),
+        "idx_engine_subject_memories_scope_subject_pinned_updated_at",
+        doc! {"tenant_id": 1, "source_id": 1, "subject_id": 1, "pinned": 1, "updated_at": -1},
+    )
+    .await?;
+    ensure_named_unique_index(
+        db.collection("engine_subject_memory_versions"),
+        "uq_engine_subject_memory_versions_scope_memory_version",
+        doc! {"tenant_id": 1, "source_id": 1, "memory_id": 1, "version": 1},
+    )
+    .await?;
+    ensure_named_unique_index(
+        db.collection("engine_subject_memory_conflicts"),
+        "uq_engine_subject_memory_conflicts_scope_pair",
+        doc! {"tenant_id": 1, "source_id": 1, "left_memory_id": 1, "right_memory_id": 1},
+    )
+    .await?;
+    ensure_named_index(
+        db.collection("engine_subject_memory_conflicts"),
+        "idx_engine_subject_memory_conflicts_scope_subject_status_created_at",
+        doc! {"tenant_id": 1, "source_id": 1, "subject_id": 1, "status": 1, "created_at": -1},
+    )
     .await
 }
 
@@ -239,6 +264,12 @@ async fn ensure_record_indexes(db: &Db) -> Result<(), String> {
         doc! {"summary_status": 1, "tenant_id": 1, "source_id": 1, "thread_id": 1},
     )
     .await?;
+    ensure_named_index(
+        collection.clone(),
+        "idx_engine_records_scope_summary_id",
+        doc! {"tenant_id": 1, "source_id": 1, "summary_id": 1},
+    )
+    .await?;
     drop_index_if_exists(collection.clone(), "thread_id_1_id_1").await?;
     drop_index_if_exists(collection.clone(), "thread_id_1_created_at_1").await?;
     drop_index_if_exists(collection, "thread_id_1_summary_status_1_created_at_1").await
@@ -340,3 +371,25 @@ async fn ensure_thread_snapshot_indexes(db: &Db) -> Result<(), String> {
     drop_index_if_exists(collection.clone(), "thread_id_1_snapshot_type_1_turn_id_1").await?;
     drop_index_if_exists(collection, "thread_id_1_snapshot_type_1_updated_at_-1").await
 }
+
+async fn ensure_memory_embedding_indexes(db: &Db) -> Result<(), String> {
+    let collection = db.collection("engine_memory_embeddings");
+    ensure_named_unique_index(
+        collection.clone(),
+        "uq_engine_memory_embeddings_scope_model_item",
+        doc! {"tenant_id": 1, "source_id": 1, "model_key": 1, "item_type": 1, "item_id": 1},
+    )
+    .await?;
+    ensure_named_index(
+        collection.clone(),
+        "idx_engine_memory_embeddings_scope_thread",
+        doc! {"tenant_id": 1, "source_id": 1, "thread_id": 1},
+    )
+    .await?;
+    ensure_named_index(
+        collection,
+        "idx_engine_memory_embeddings_scope_model_subject",
+        doc! {"tenant_id": 1, "source_id": 1, "model_key": 1, "subject_id": 1, "item_type": 1},
+    )
+    .await
+}
@@ -75,6 +75,8 @@ async fn main() -> Result<(), String> {
     )
     .await?;
 
+    let embedding_provider = ai::build_embedding_provider(&config)?;
+
     let state = Arc::new(AppState {
         pool,
         config: config.clone(),
@@ -83,6 +85,7 @@ async fn main() -> Result<(), String> {
         rabbitmq_queue_inspector,
         pressure: pressure::MemoryEnginePressureState::new(pressure_policy),
         cloud_agent_store,
+        embedding_provider,
     });
     pressure::start_config_watcher(state.clone(), pressure_config_client.clone());
     let service_id = std::env::var("CHATOS_SERVICE_ID")
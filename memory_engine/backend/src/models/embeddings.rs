@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use serde::{Deserialize, Serialize};
+
+pub const MEMORY_EMBEDDING_ITEM_RECORD: &str = "record";
+pub const MEMORY_EMBEDDING_ITEM_SUMMARY: &str = "summary";
+pub const MEMORY_EMBEDDING_ITEM_SUBJECT_MEMORY: &str = "subject_memory";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EngineMemoryEmbedding {
+    pub id: String,
+    pub tenant_id: String,
+    pub source_id: String,
+    pub item_type: String,
+    pub item_id: String,
+    pub thread_id: Option<String>,
+    pub subject_id: Option<String>,
+    pub model_key: String,
+    pub content_hash: String,
+    pub vector: Vec<f32>,
+    pub created_at: String,
+    pub updated_at: String,
+}
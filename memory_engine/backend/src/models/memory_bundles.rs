@@ -0,0 +1,89 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const MEMORY_BUNDLE_FORMAT: &str = "chatos.memory_engine.bundle";
+pub const MEMORY_BUNDLE_VERSION: i64 = 1;
+
+/// What a bundle covers. `subject_id` and `memory_type` are resolved from
+/// `scope_key` at export time, so an import never needs the scope to exist.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryBundleSelector {
+    pub tenant_id: String,
+    pub source_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subject_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope_key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory_type: Option<String>,
+}
+
+/// Portable snapshot of one source's memory. Documents are stored as relaxed
+/// extended JSON keyed by collection, and `checksum` covers `collections`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryBundle {
+    pub format: String,
+    pub version: i64,
+    pub exported_at: String,
+    pub selector: MemoryBundleSelector,
+    pub checksum: String,
+    pub collections: BTreeMap<String, Vec<Value>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportMemoryBundleRequest {
+    pub tenant_id: String,
+    pub source_id: String,
+    pub subject_id: Option<String>,
+    pub scope_key: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MemoryBundleImportOptions {
+    pub tenant_id: String,
+    /// Defaults to the source the bundle was exported from.
+    #[serde(default)]
+    pub source_id: Option<String>,
+    /// `merge` keeps existing documents; `replace` first removes everything
+    /// the bundle's selector covers in the target.
+    #[serde(default)]
+    pub mode: Option<String>,
+    /// Give every imported document a fresh id and rewrite references to it.
+    #[serde(default)]
+    pub remap_ids: bool,
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportMemoryBundleRequest {
+    #[serde(flatten)]
+    pub options: MemoryBundleImportOptions,
+    pub bundle: MemoryBundle,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryBundleCollectionReport {
+    pub collection: String,
+    pub total: usize,
+    pub inserted: usize,
+    pub skipped: usize,
+    pub replaced: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryBundleImportReport {
+    pub dry_run: bool,
+    pub mode: String,
+    pub version: i64,
+    pub checksum: String,
+    pub tenant_id: String,
+    pub source_id: String,
+    pub remapped_ids: usize,
+    pub collections: Vec<MemoryBundleCollectionReport>,
+}
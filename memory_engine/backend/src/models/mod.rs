@@ -4,6 +4,8 @@
 mod common;
 mod compose;
 mod control_plane;
+mod embeddings;
+mod memory_bundles;
 mod records;
 mod sources;
 mod subject_memories;
@@ -30,6 +32,15 @@ pub use self::control_plane::{
     DEFAULT_ENGINE_SUMMARY_PROMPT_TEMPLATE_EN, DEFAULT_ENGINE_THREAD_REPAIR_PROMPT_TEMPLATE,
     DEFAULT_ENGINE_THREAD_REPAIR_PROMPT_TEMPLATE_EN, PROMPT_LANGUAGE_EN, PROMPT_LANGUAGE_ZH,
 };
+pub use self::embeddings::{
+    EngineMemoryEmbedding, MEMORY_EMBEDDING_ITEM_RECORD, MEMORY_EMBEDDING_ITEM_SUBJECT_MEMORY,
+    MEMORY_EMBEDDING_ITEM_SUMMARY,
+};
+pub use self::memory_bundles::{
+    ExportMemoryBundleRequest, ImportMemoryBundleRequest, MemoryBundle,
+    MemoryBundleCollectionReport, MemoryBundleImportOptions, MemoryBundleImportReport,
+    MemoryBundleSelector, MEMORY_BUNDLE_FORMAT, MEMORY_BUNDLE_VERSION,
+};
 pub use self::records::{
     BatchSyncRecordsRequest, BatchSyncRecordsResponse, CompactTurnsResponse, EngineCompactTurn,
     EngineRecord, ThreadRecordsPageResponse, TurnProcessRecordsResponse, TurnRecordSlice,
@@ -40,9 +51,12 @@ pub use self::sources::{
     UpsertSourceRequest,
 };
 pub use self::subject_memories::{
-    EngineSubjectMemory, MarkSubjectMemoriesRolledUpRequest, MarkSubjectMemoriesRolledUpResponse,
-    QuerySubjectMemoriesRequest, RunSubjectMemoryJobRequest, RunSubjectMemoryJobResponse,
-    UpsertSubjectMemoryRequest,
+    EngineSubjectMemory, EngineSubjectMemoryConflict, EngineSubjectMemoryVersion,
+    MarkSubjectMemoriesRolledUpRequest, MarkSubjectMemoriesRolledUpResponse,
+    QuerySubjectMemoriesRequest, QuerySubjectMemoryConflictsRequest,
+    ResolveSubjectMemoryConflictRequest, RunSubjectMemoryJobRequest, RunSubjectMemoryJobResponse,
+    SubjectMemoryEdit, SubjectMemoryProvenance, SubjectMemoryProvenanceResponse,
+    UpdateSubjectMemoryRequest, UpsertSubjectMemoryRequest,
 };
 pub use self::subject_memory_scopes::{
     EngineSubjectMemoryScope, RunSubjectMemoryScopesRequest, RunSubjectMemoryScopesResponse,
@@ -4,7 +4,12 @@
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-pub use memory_engine_sdk::{EngineSubjectMemory, QuerySubjectMemoriesRequest};
+pub use memory_engine_sdk::{
+    EngineSubjectMemory, EngineSubjectMemoryConflict, EngineSubjectMemoryVersion,
+    QuerySubjectMemoriesRequest, QuerySubjectMemoryConflictsRequest,
+    ResolveSubjectMemoryConflictRequest, SubjectMemoryProvenance, SubjectMemoryProvenanceResponse,
+    UpdateSubjectMemoryRequest,
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpsertSubjectMemoryRequest {
@@ -24,6 +29,21 @@ pub struct UpsertSubjectMemoryRequest {
     pub status: Option<String>,
     pub created_at: Option<String>,
     pub updated_at: Option<String>,
+    #[serde(default)]
+    pub provenance: Option<SubjectMemoryProvenance>,
+}
+
+/// Resolved state of a user edit, written over the memory and appended to its
+/// version history as `version`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubjectMemoryEdit {
+    pub text: String,
+    pub pinned: bool,
+    pub expires_at: Option<String>,
+    pub version: i64,
+    pub edited_by: Option<String>,
+    pub reason: Option<String>,
+    pub edited_at: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -0,0 +1,404 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::{BTreeMap, HashMap};
+
+use mongodb::bson::{Bson, Document};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::models::{MemoryBundle, MEMORY_BUNDLE_FORMAT, MEMORY_BUNDLE_VERSION};
+
+pub(crate) struct BundleCollection {
+    pub(crate) name: &'static str,
+    pub(crate) collection: &'static str,
+    /// Fields that identify a document in the target, matching the unique
+    /// index of the collection.
+    pub(crate) key_fields: &'static [&'static str],
+    /// Whether the unique index is scoped by `tenant_id` and `source_id`.
+    pub(crate) scoped_key: bool,
+    /// Fields holding ids of other bundle documents, rewritten by `remap_ids`.
+    pub(crate) id_references: &'static [&'static str],
+}
+
+/// Import order: parents come before the documents that reference them.
+pub(crate) const BUNDLE_COLLECTIONS: &[BundleCollection] = &[
+    BundleCollection {
+        name: "subjects",
+        collection: "engine_subjects",
+        key_fields: &["subject_id"],
+        scoped_key: true,
+        id_references: &["id"],
+    },
+    BundleCollection {
+        name: "subject_memory_scopes",
+        collection: "engine_subject_memory_scopes",
+        key_fields: &["scope_key"],
+        scoped_key: true,
+        id_references: &["id"],
+    },
+    BundleCollection {
+        name: "threads",
+        collection: "engine_threads",
+        key_fields: &["id"],
+        scoped_key: true,
+        id_references: &["id"],
+    },
+    BundleCollection {
+        name: "records",
+        collection: "engine_records",
+        key_fields: &["thread_id", "id"],
+        scoped_key: true,
+        id_references: &["id", "thread_id", "summary_id"],
+    },
+    BundleCollection {
+        name: "summaries",
+        collection: "engine_summaries",
+        key_fields: &["id"],
+        scoped_key: false,
+        id_references: &[
+            "id",
+            "thread_id",
+            "source_record_start_id",
+            "source_record_end_id",
+            "rollup_summary_id",
+        ],
+    },
+    BundleCollection {
+        name: "thread_snapshots",
+        collection: "engine_thread_snapshots",
+        key_fields: &["thread_id", "snapshot_type", "turn_id"],
+        scoped_key: true,
+        id_references: &["id", "thread_id", "user_message_id"],
+    },
+    BundleCollection {
+        name: "subject_memories",
+        collection: "engine_subject_memories",
+        key_fields: &["subject_id", "memory_key"],
+        scoped_key: true,
+        id_references: &[
+            "id",
+            "superseded_by",
+            "provenance.source_summary_ids",
+            "provenance.source_memory_ids",
+        ],
+    },
+    BundleCollection {
+        name: "subject_memory_versions",
+        collection: "engine_subject_memory_versions",
+        key_fields: &["memory_id", "version"],
+        scoped_key: true,
+        id_references: &["id", "memory_id"],
+    },
+    BundleCollection {
+        name: "subject_memory_conflicts",
+        collection: "engine_subject_memory_conflicts",
+        key_fields: &["left_memory_id", "right_memory_id"],
+        scoped_key: true,
+        id_references: &["id", "left_memory_id", "right_memory_id"],
+    },
+];
+
+pub(crate) fn bundle_collection(name: &str) -> Option<&'static BundleCollection> {
+    BUNDLE_COLLECTIONS.iter().find(|item| item.name == name)
+}
+
+pub(crate) fn bundle_checksum(
+    collections: &BTreeMap<String, Vec<Value>>,
+) -> Result<String, String> {
+    let payload = serde_json::to_vec(collections).map_err(|err| err.to_string())?;
+    Ok(format!("sha256:{}", hex::encode(Sha256::digest(payload))))
+}
+
+pub fn verify_memory_bundle(bundle: &MemoryBundle) -> Result<(), String> {
+    if bundle.format != MEMORY_BUNDLE_FORMAT {
+        return Err(format!("unsupported bundle format: {}", bundle.format));
+    }
+    if bundle.version < 1 || bundle.version > MEMORY_BUNDLE_VERSION {
+        return Err(format!(
+            "unsupported bundle version {} (this build reads up to {})",
+            bundle.version, MEMORY_BUNDLE_VERSION
+        ));
+    }
+    if let Some(name) = bundle
+        .collections
+        .keys()
+        .find(|name| bundle_collection(name).is_none())
+    {
+        return Err(format!("unknown bundle collection: {name}"));
+    }
+    let checksum = bundle_checksum(&bundle.collections)?;
+    if checksum != bundle.checksum {
+        return Err(format!(
+            "bundle checksum mismatch: expected {}, computed {}",
+            bundle.checksum, checksum
+        ));
+    }
+    Ok(())
+}
+
+pub(crate) fn document_to_bundle_value(mut document: Document) -> Value {
+    document.remove("_id");
+    Bson::Document(document).into_relaxed_extjson()
+}
+
+pub(crate) fn bundle_value_to_document(value: Value) -> Result<Document, String> {
+    match Bson::try_from(value).map_err(|err| err.to_string())? {
+        Bson::Document(document) => Ok(document),
+        other => Err(format!(
+            "bundle entry is not a document: {:?}",
+            other.element_type()
+        )),
+    }
+}
+
+/// Assigns a fresh id to every document that has one, keeping the prefix
+/// before the first `_` so ids stay recognisable (`smem_…`, `emb_…`).
+pub(crate) fn plan_id_remap(
+    collections: &BTreeMap<String, Vec<Document>>,
+) -> HashMap<String, String> {
+    collections
+        .values()
+        .flatten()
+        .filter_map(|document| document.get_str("id").ok())
+        .map(|id| {
+            let fresh = match id.split_once('_') {
+                Some((prefix, _)) if !prefix.is_empty() => format!("{prefix}_{}", Uuid::new_v4()),
+                _ => Uuid::new_v4().to_string(),
+            };
+            (id.to_string(), fresh)
+        })
+        .collect()
+}
+
+/// Moves a document into the target tenant and source and rewrites id
+/// references that point at other documents of the bundle.
+pub(crate) fn retarget_document(
+    document: &mut Document,
+    collection: &BundleCollection,
+    tenant_id: &str,
+    source_id: &str,
+    id_map: &HashMap<String, String>,
+) {
+    document.remove("_id");
+    document.insert("tenant_id", tenant_id);
+    document.insert("source_id", source_id);
+    if id_map.is_empty() {
+        return;
+    }
+    for path in collection.id_references {
+        if let Some(value) = field_mut(document, path) {
+            remap_value(value, id_map);
+        }
+    }
+}
+
+fn field_mut<'a>(document: &'a mut Document, path: &str) -> Option<&'a mut Bson> {
+    match path.split_once('.') {
+        Some((head, rest)) => match document.get_mut(head)? {
+            Bson::Document(nested) => field_mut(nested, rest),
+            _ => None,
+        },
+        None => document.get_mut(path),
+    }
+}
+
+fn remap_value(value: &mut Bson, id_map: &HashMap<String, String>) {
+    match value {
+        Bson::String(id) => {
+            if let Some(fresh) = id_map.get(id.as_str()) {
+                *id = fresh.clone();
+            }
+        }
+        Bson::Array(items) => {
+            for item in items {
+                remap_value(item, id_map);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn key_filter(
+    document: &Document,
+    collection: &BundleCollection,
+) -> Result<Document, String> {
+    let mut filter = Document::new();
+    if collection.scoped_key {
+        for field in ["tenant_id", "source_id"] {
+            filter.insert(field, key_value(document, field, collection)?);
+        }
+    }
+    for field in collection.key_fields {
+        filter.insert(*field, key_value(document, field, collection)?);
+    }
+    Ok(filter)
+}
+
+pub(crate) fn document_key(
+    document: &Document,
+    collection: &BundleCollection,
+) -> Result<String, String> {
+    Ok(key_filter(document, collection)?
+        .values()
+        .map(|value| match value {
+            Bson::Int32(number) => number.to_string(),
+            Bson::Int64(number) => number.to_string(),
+            Bson::String(text) => text.clone(),
+            other => other.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\u{1f}"))
+}
+
+fn key_value(
+    document: &Document,
+    field: &str,
+    collection: &BundleCollection,
+) -> Result<Bson, String> {
+    document
+        .get(field)
+        .filter(|value| !matches!(value, Bson::Null))
+        .cloned()
+        .ok_or_else(|| format!("{} document is missing {}", collection.name, field))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, HashMap};
+
+    use mongodb::bson::doc;
+    use serde_json::json;
+
+    use super::{
+        bundle_checksum, bundle_collection, bundle_value_to_document, document_key,
+        document_to_bundle_value, plan_id_remap, retarget_document, verify_memory_bundle,
+    };
+    use crate::models::{
+        MemoryBundle, MemoryBundleSelector, MEMORY_BUNDLE_FORMAT, MEMORY_BUNDLE_VERSION,
+    };
+
+    fn bundle(collections: BTreeMap<String, Vec<serde_json::Value>>) -> MemoryBundle {
+        MemoryBundle {
+            format: MEMORY_BUNDLE_FORMAT.to_string(),
+            version: MEMORY_BUNDLE_VERSION,
+            exported_at: "2026-05-01T00:00:00+00:00".to_string(),
+            selector: MemoryBundleSelector {
+                tenant_id: "tenant_a".to_string(),
+                source_id: "source_a".to_string(),
+                ..Default::default()
+            },
+            checksum: bundle_checksum(&collections).expect("checksum"),
+            collections,
+        }
+    }
+
+    #[test]
+    fn verify_accepts_intact_bundle_and_rejects_tampering() {
+        let mut collections = BTreeMap::new();
+        collections.insert(
+            "threads".to_string(),
+            vec![json!({"id": "thread_1", "title": "Plan"})],
+        );
+        let mut bundle = bundle(collections);
+        assert_eq!(verify_memory_bundle(&bundle), Ok(()));
+
+        bundle.collections.get_mut("threads").expect("threads")[0]["title"] = json!("Edited");
+        assert!(verify_memory_bundle(&bundle)
+            .expect_err("tampered")
+            .contains("checksum mismatch"));
+    }
+
+    #[test]
+    fn verify_rejects_newer_versions_and_unknown_collections() {
+        let mut bundle = bundle(BTreeMap::new());
+        bundle.version = MEMORY_BUNDLE_VERSION + 1;
+        assert!(verify_memory_bundle(&bundle)
+            .expect_err("version")
+            .contains("unsupported bundle version"));
+
+        let mut collections = BTreeMap::new();
+        collections.insert("job_runs".to_string(), Vec::new());
+        assert_eq!(
+            verify_memory_bundle(&super::tests::bundle(collections)),
+            Err("unknown bundle collection: job_runs".to_string())
+        );
+    }
+
+    #[test]
+    fn bundle_values_round_trip_without_mongo_ids() {
+        let value = document_to_bundle_value(doc! {
+            "_id": mongodb::bson::oid::ObjectId::new(),
+            "id": "rec_1",
+            "version": 3i64,
+        });
+
+        assert_eq!(value, json!({"id": "rec_1", "version": 3}));
+        let document = bundle_value_to_document(value).expect("document");
+        assert_eq!(document.get_str("id"), Ok("rec_1"));
+    }
+
+    #[test]
+    fn retarget_rewrites_tenant_and_id_references() {
+        let mut collections = BTreeMap::new();
+        collections.insert(
+            "subject_memories".to_string(),
+            vec![doc! {"id": "smem_old", "tenant_id": "tenant_a"}],
+        );
+        collections.insert("summaries".to_string(), vec![doc! {"id": "sum_old"}]);
+        let id_map = plan_id_remap(&collections);
+        assert!(id_map["smem_old"].starts_with("smem_"));
+        assert_ne!(id_map["smem_old"], "smem_old");
+
+        let mut memory = doc! {
+            "_id": 7,
+            "id": "smem_old",
+            "tenant_id": "tenant_a",
+            "source_id": "source_a",
+            "subject_id": "agent:1",
+            "memory_key": "agent_recall:l1:abc",
+            "superseded_by": "smem_elsewhere",
+            "provenance": {"source_summary_ids": ["sum_old", "sum_missing"]},
+        };
+        retarget_document(
+            &mut memory,
+            bundle_collection("subject_memories").expect("collection"),
+            "tenant_b",
+            "source_b",
+            &id_map,
+        );
+
+        assert!(memory.get("_id").is_none());
+        assert_eq!(memory.get_str("tenant_id"), Ok("tenant_b"));
+        assert_eq!(memory.get_str("source_id"), Ok("source_b"));
+        assert_eq!(memory.get_str("id"), Ok(id_map["smem_old"].as_str()));
+        assert_eq!(memory.get_str("superseded_by"), Ok("smem_elsewhere"));
+        let provenance = memory.get_document("provenance").expect("provenance");
+        assert_eq!(
+            provenance.get_array("source_summary_ids").expect("ids"),
+            &vec![
+                id_map["sum_old"].clone().into(),
+                mongodb::bson::Bson::from("sum_missing")
+            ]
+        );
+    }
+
+    #[test]
+    fn retarget_without_remap_keeps_ids() {
+        let mut record = doc! {"id": "rec_1", "thread_id": "thread_1", "tenant_id": "tenant_a"};
+        retarget_document(
+            &mut record,
+            bundle_collection("records").expect("collection"),
+            "tenant_b",
+            "source_b",
+            &HashMap::new(),
+        );
+
+        assert_eq!(record.get_str("id"), Ok("rec_1"));
+        assert_eq!(
+            document_key(&record, bundle_collection("records").expect("collection")),
+            Ok("tenant_b\u{1f}source_b\u{1f}thread_1\u{1f}rec_1".to_string())
+        );
+    }
+}
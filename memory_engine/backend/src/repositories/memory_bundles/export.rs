@@ -0,0 +1,247 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::BTreeMap;
+
+use futures_util::TryStreamExt;
+use mongodb::bson::{doc, Document};
+use mongodb::ClientSession;
+
+use crate::db::Db;
+use crate::models::{
+    now_rfc3339, ExportMemoryBundleRequest, MemoryBundle, MemoryBundleSelector,
+    MEMORY_BUNDLE_FORMAT, MEMORY_BUNDLE_VERSION,
+};
+
+use super::collections::{bundle_checksum, document_to_bundle_value};
+
+pub async fn resolve_memory_bundle_selector(
+    db: &Db,
+    req: &ExportMemoryBundleRequest,
+) -> Result<MemoryBundleSelector, String> {
+    let tenant_id = required(req.tenant_id.as_str(), "tenant_id")?;
+    let source_id = required(req.source_id.as_str(), "source_id")?;
+    let mut selector = MemoryBundleSelector {
+        tenant_id: tenant_id.to_string(),
+        source_id: source_id.to_string(),
+        subject_id: optional(req.subject_id.as_deref()),
+        scope_key: optional(req.scope_key.as_deref()),
+        memory_type: None,
+    };
+    let Some(scope_key) = selector.scope_key.clone() else {
+        return Ok(selector);
+    };
+
+    let scope = db
+        .collection::<Document>("engine_subject_memory_scopes")
+        .find_one(doc! {
+            "tenant_id": tenant_id,
+            "source_id": source_id,
+            "scope_key": scope_key.as_str(),
+        })
+        .await
+        .map_err(|err| err.to_string())?
+        .ok_or_else(|| format!("subject memory scope not found: {scope_key}"))?;
+    let scope_subject_id = scope
+        .get_str("subject_id")
+        .map_err(|_| format!("subject memory scope {scope_key} has no subject_id"))?;
+    if let Some(subject_id) = selector.subject_id.as_deref() {
+        if subject_id != scope_subject_id {
+            return Err(format!(
+                "scope {scope_key} belongs to subject {scope_subject_id}, not {subject_id}"
+            ));
+        }
+    }
+    selector.subject_id = Some(scope_subject_id.to_string());
+    selector.memory_type = scope.get_str("memory_type").ok().map(ToOwned::to_owned);
+    Ok(selector)
+}
+
+pub async fn export_memory_bundle(
+    db: &Db,
+    selector: MemoryBundleSelector,
+) -> Result<MemoryBundle, String> {
+    let collections = collect_bundle_documents(db, &selector, None)
+        .await?
+        .into_iter()
+        .map(|(name, documents)| {
+            (
+                name,
+                documents
+                    .into_iter()
+                    .map(document_to_bundle_value)
+                    .collect::<Vec<_>>(),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    Ok(MemoryBundle {
+        format: MEMORY_BUNDLE_FORMAT.to_string(),
+        version: MEMORY_BUNDLE_VERSION,
+        exported_at: now_rfc3339(),
+        checksum: bundle_checksum(&collections)?,
+        selector,
+        collections,
+    })
+}
+
+/// Loads every document the selector covers, keyed by bundle collection name.
+/// Without a subject the whole source is included; with one, thread data is
+/// limited to that subject's threads and memory data to its memories. With a
+/// session the reads see that session's transaction snapshot.
+pub(crate) async fn collect_bundle_documents(
+    db: &Db,
+    selector: &MemoryBundleSelector,
+    mut session: Option<&mut ClientSession>,
+) -> Result<BTreeMap<String, Vec<Document>>, String> {
+    let base = doc! {
+        "tenant_id": selector.tenant_id.as_str(),
+        "source_id": selector.source_id.as_str(),
+    };
+    let with = |extra: Document| {
+        let mut filter = base.clone();
+        filter.extend(extra);
+        filter
+    };
+    let subject = selector
+        .subject_id
+        .as_deref()
+        .map(|subject_id| doc! {"subject_id": subject_id})
+        .unwrap_or_default();
+    let mut memory_scope = subject.clone();
+    if let Some(memory_type) = selector.memory_type.as_deref() {
+        memory_scope.insert("memory_type", memory_type);
+    }
+    let mut scope_filter = subject.clone();
+    if let Some(scope_key) = selector.scope_key.as_deref() {
+        scope_filter.insert("scope_key", scope_key);
+    }
+
+    let mut collections = BTreeMap::new();
+    let subjects = find_documents(
+        db,
+        session.as_deref_mut(),
+        "engine_subjects",
+        with(subject.clone()),
+    )
+    .await?;
+    collections.insert("subjects".to_string(), subjects);
+    let scopes = find_documents(
+        db,
+        session.as_deref_mut(),
+        "engine_subject_memory_scopes",
+        with(scope_filter),
+    )
+    .await?;
+    collections.insert("subject_memory_scopes".to_string(), scopes);
+
+    let threads = find_documents(
+        db,
+        session.as_deref_mut(),
+        "engine_threads",
+        with(subject.clone()),
+    )
+    .await?;
+    let thread_scope = if selector.subject_id.is_some() {
+        doc! {"thread_id": {"$in": document_ids(&threads)}}
+    } else {
+        Document::new()
+    };
+    collections.insert("threads".to_string(), threads);
+    for (name, collection) in [
+        ("records", "engine_records"),
+        ("summaries", "engine_summaries"),
+        ("thread_snapshots", "engine_thread_snapshots"),
+    ] {
+        let documents = find_documents(
+            db,
+            session.as_deref_mut(),
+            collection,
+            with(thread_scope.clone()),
+        )
+        .await?;
+        collections.insert(name.to_string(), documents);
+    }
+
+    let memories = find_documents(
+        db,
+        session.as_deref_mut(),
+        "engine_subject_memories",
+        with(memory_scope.clone()),
+    )
+    .await?;
+    let memory_ids = document_ids(&memories);
+    collections.insert("subject_memories".to_string(), memories);
+    let version_scope = if selector.subject_id.is_some() {
+        doc! {"memory_id": {"$in": memory_ids}}
+    } else {
+        Document::new()
+    };
+    let versions = find_documents(
+        db,
+        session.as_deref_mut(),
+        "engine_subject_memory_versions",
+        with(version_scope),
+    )
+    .await?;
+    collections.insert("subject_memory_versions".to_string(), versions);
+    let conflicts = find_documents(
+        db,
+        session,
+        "engine_subject_memory_conflicts",
+        with(memory_scope),
+    )
+    .await?;
+    collections.insert("subject_memory_conflicts".to_string(), conflicts);
+
+    Ok(collections)
+}
+
+async fn find_documents(
+    db: &Db,
+    session: Option<&mut ClientSession>,
+    collection: &str,
+    filter: Document,
+) -> Result<Vec<Document>, String> {
+    let target = db.collection::<Document>(collection);
+    let find = target.find(filter).sort(doc! {"_id": 1});
+    match session {
+        Some(session) => find
+            .session(&mut *session)
+            .await
+            .map_err(|err| err.to_string())?
+            .stream(session)
+            .try_collect()
+            .await
+            .map_err(|err| err.to_string()),
+        None => find
+            .await
+            .map_err(|err| err.to_string())?
+            .try_collect()
+            .await
+            .map_err(|err| err.to_string()),
+    }
+}
+
+fn document_ids(documents: &[Document]) -> Vec<String> {
+    documents
+        .iter()
+        .filter_map(|document| document.get_str("id").ok())
+        .map(ToOwned::to_owned)
+        .collect()
+}
+
+fn required<'a>(value: &'a str, field: &str) -> Result<&'a str, String> {
+    let value = value.trim();
+    if value.is_empty() {
+        return Err(format!("{field} is required"));
+    }
+    Ok(value)
+}
+
+fn optional(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+}
@@ -0,0 +1,503 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use mongodb::bson::{doc, Bson, Document};
+use mongodb::ClientSession;
+use serde_json::Value;
+
+use crate::db::Db;
+use crate::models::{
+    MemoryBundle, MemoryBundleCollectionReport, MemoryBundleImportOptions,
+    MemoryBundleImportReport, MemoryBundleSelector,
+};
+
+use super::collections::{
+    bundle_collection, bundle_value_to_document, document_key, key_filter, plan_id_remap,
+    retarget_document, verify_memory_bundle, BundleCollection, BUNDLE_COLLECTIONS,
+};
+use super::export::collect_bundle_documents;
+
+const IMPORT_MODE_MERGE: &str = "merge";
+const IMPORT_MODE_REPLACE: &str = "replace";
+const DELETE_BATCH_SIZE: usize = 500;
+
+/// Documents keyed by bundle collection name.
+type BundleDocuments = BTreeMap<String, Vec<Document>>;
+
+/// Writes a verified bundle into the target tenant and source. Documents that
+/// already exist under their unique key are skipped; in `replace` mode the
+/// target's copy of the selector is read and removed first, and the read, the
+/// removal and the inserts run in one transaction so a failed import leaves
+/// the target as it was. Transactions need a replica set or sharded cluster,
+/// so `replace` is refused, dry runs included, on a standalone server. A dry
+/// run performs the same lookups and reports the outcome without writing.
+pub async fn import_memory_bundle(
+    db: &Db,
+    bundle: MemoryBundle,
+    options: &MemoryBundleImportOptions,
+) -> Result<MemoryBundleImportReport, String> {
+    verify_memory_bundle(&bundle)?;
+    let mode = options
+        .mode
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(IMPORT_MODE_MERGE);
+    if mode != IMPORT_MODE_MERGE && mode != IMPORT_MODE_REPLACE {
+        return Err(format!("unsupported import mode: {mode}"));
+    }
+    let tenant_id = options.tenant_id.trim();
+    if tenant_id.is_empty() {
+        return Err("tenant_id is required".to_string());
+    }
+    let source_id = options
+        .source_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(bundle.selector.source_id.as_str())
+        .to_string();
+
+    let (documents, id_map) = stage_bundle_documents(
+        bundle.collections,
+        tenant_id,
+        source_id.as_str(),
+        options.remap_ids,
+    )?;
+
+    let target = MemoryBundleSelector {
+        tenant_id: tenant_id.to_string(),
+        source_id: source_id.clone(),
+        ..bundle.selector.clone()
+    };
+    if mode == IMPORT_MODE_REPLACE {
+        ensure_transactions_supported(db).await?;
+    }
+
+    let transactional = mode == IMPORT_MODE_REPLACE && !options.dry_run;
+    let mut session = None;
+    if !options.dry_run {
+        let mut started = db
+            .client()
+            .start_session()
+            .await
+            .map_err(|err| format!("start bundle import session failed: {err}"))?;
+        if transactional {
+            started
+                .start_transaction()
+                .await
+                .map_err(|err| format!("start bundle import transaction failed: {err}"))?;
+        }
+        session = Some(started);
+    }
+    // Reading the replaced set inside the transaction deletes exactly the
+    // documents the transaction sees; a concurrent write to them fails the
+    // import with a write conflict instead of surviving the replace.
+    let result = if mode == IMPORT_MODE_REPLACE {
+        collect_bundle_documents(db, &target, session.as_mut()).await
+    } else {
+        Ok(BTreeMap::new())
+    };
+    let result = match result {
+        Ok(replaced) => import_collections(db, session.as_mut(), documents, &replaced).await,
+        Err(err) => Err(err),
+    };
+    let reports = match session {
+        Some(mut session) if transactional => match result {
+            Ok(reports) => {
+                session
+                    .commit_transaction()
+                    .await
+                    .map_err(|err| format!("commit bundle import failed: {err}"))?;
+                reports
+            }
+            Err(err) => {
+                // Dropping an active transaction aborts it as well, so a failed
+                // abort only loses the explicit round trip.
+                let _ = session.abort_transaction().await;
+                return Err(err);
+            }
+        },
+        _ => result?,
+    };
+
+    Ok(MemoryBundleImportReport {
+        dry_run: options.dry_run,
+        mode: mode.to_string(),
+        version: bundle.version,
+        checksum: bundle.checksum,
+        tenant_id: tenant_id.to_string(),
+        source_id,
+        remapped_ids: id_map.len(),
+        collections: reports,
+    })
+}
+
+/// Converts bundle entries into documents of the target tenant and source,
+/// with fresh ids when `remap_ids` is set. Nothing is written.
+pub(crate) fn stage_bundle_documents(
+    collections: BTreeMap<String, Vec<Value>>,
+    tenant_id: &str,
+    source_id: &str,
+    remap_ids: bool,
+) -> Result<(BundleDocuments, HashMap<String, String>), String> {
+    let mut documents = BTreeMap::new();
+    for (name, values) in collections {
+        let converted = values
+            .into_iter()
+            .map(bundle_value_to_document)
+            .collect::<Result<Vec<_>, _>>()?;
+        documents.insert(name, converted);
+    }
+    let id_map = if remap_ids {
+        plan_id_remap(&documents)
+    } else {
+        HashMap::new()
+    };
+    for (name, items) in &mut documents {
+        let collection =
+            bundle_collection(name).ok_or_else(|| format!("unknown bundle collection: {name}"))?;
+        for document in items {
+            retarget_document(document, collection, tenant_id, source_id, &id_map);
+        }
+    }
+    Ok((documents, id_map))
+}
+
+/// Removes `replaced` and inserts the staged documents, collection by
+/// collection in import order. Without a session nothing is written and the
+/// reports describe what an import would do.
+async fn import_collections(
+    db: &Db,
+    mut session: Option<&mut ClientSession>,
+    mut documents: BundleDocuments,
+    replaced: &BundleDocuments,
+) -> Result<Vec<MemoryBundleCollectionReport>, String> {
+    let dry_run = session.is_none();
+    let mut reports = Vec::new();
+    for collection in BUNDLE_COLLECTIONS {
+        let existing = replaced
+            .get(collection.name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        let replaced_keys = existing
+            .iter()
+            .map(|document| document_key(document, collection))
+            .collect::<Result<HashSet<_>, _>>()?;
+        if let Some(session) = session.as_mut() {
+            delete_documents(db, session, collection.collection, existing).await?;
+        }
+
+        let items = documents.remove(collection.name).unwrap_or_default();
+        let mut report = MemoryBundleCollectionReport {
+            collection: collection.name.to_string(),
+            total: items.len(),
+            replaced: existing.len(),
+            ..Default::default()
+        };
+        let mut seen = HashSet::new();
+        for document in items {
+            let key = document_key(&document, collection)?;
+            let exists = !seen.insert(key.clone())
+                || if dry_run && replaced_keys.contains(&key) {
+                    false
+                } else {
+                    document_exists(db, session.as_deref_mut(), collection, &document).await?
+                };
+            if exists {
+                report.skipped += 1;
+                continue;
+            }
+            if let Some(session) = session.as_mut() {
+                db.collection::<Document>(collection.collection)
+                    .insert_one(document)
+                    .session(&mut **session)
+                    .await
+                    .map_err(|err| format!("insert into {} failed: {err}", collection.name))?;
+            }
+            report.inserted += 1;
+        }
+        reports.push(report);
+    }
+    Ok(reports)
+}
+
+/// Multi-document transactions need a replica set member or `mongos`; a
+/// standalone server would only fail on the first write inside the session.
+async fn ensure_transactions_supported(db: &Db) -> Result<(), String> {
+    let hello = db
+        .run_command(doc! {"hello": 1})
+        .await
+        .map_err(|err| format!("check MongoDB topology failed: {err}"))?;
+    if transactions_supported(&hello) {
+        return Ok(());
+    }
+    Err(
+        "replace import needs MongoDB transactions, which require a replica set or sharded \
+         cluster; this server is standalone. Use mode=merge or run against a replica set"
+            .to_string(),
+    )
+}
+
+fn transactions_supported(hello: &Document) -> bool {
+    hello.contains_key("setName") || hello.get_str("msg").ok() == Some("isdbgrid")
+}
+
+async fn document_exists(
+    db: &Db,
+    session: Option<&mut ClientSession>,
+    collection: &BundleCollection,
+    document: &Document,
+) -> Result<bool, String> {
+    let target = db.collection::<Document>(collection.collection);
+    let count = target
+        .count_documents(key_filter(document, collection)?)
+        .limit(1);
+    let count = match session {
+        Some(session) => count.session(session).await,
+        None => count.await,
+    }
+    .map_err(|err| err.to_string())?;
+    Ok(count > 0)
+}
+
+async fn delete_documents(
+    db: &Db,
+    session: &mut ClientSession,
+    collection: &str,
+    documents: &[Document],
+) -> Result<(), String> {
+    let object_ids = documents
+        .iter()
+        .filter_map(|document| document.get("_id").cloned())
+        .collect::<Vec<Bson>>();
+    for batch in object_ids.chunks(DELETE_BATCH_SIZE) {
+        db.collection::<Document>(collection)
+            .delete_many(doc! {"_id": {"$in": batch.to_vec()}})
+            .session(&mut *session)
+            .await
+            .map_err(|err| err.to_string())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use mongodb::bson::{doc, Document};
+    use serde_json::{json, Value};
+    use uuid::Uuid;
+
+    use super::super::collections::{bundle_checksum, document_to_bundle_value};
+    use super::super::export::{collect_bundle_documents, export_memory_bundle};
+    use super::{import_memory_bundle, stage_bundle_documents, transactions_supported};
+    use crate::db::Db;
+    use crate::models::{MemoryBundle, MemoryBundleImportOptions, MemoryBundleSelector};
+
+    const TEST_MONGODB_URI_ENV: &str = "MEMORY_ENGINE_TEST_MONGODB_URI";
+
+    fn selector(source_id: &str) -> MemoryBundleSelector {
+        MemoryBundleSelector {
+            tenant_id: "tenant_a".to_string(),
+            source_id: source_id.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn replace_into(source_id: &str) -> MemoryBundleImportOptions {
+        MemoryBundleImportOptions {
+            tenant_id: "tenant_a".to_string(),
+            source_id: Some(source_id.to_string()),
+            mode: Some("replace".to_string()),
+            ..Default::default()
+        }
+    }
+
+    /// A scratch database on the replica set named by
+    /// `MEMORY_ENGINE_TEST_MONGODB_URI`; transactions need a replica set.
+    async fn scratch_db() -> Db {
+        let uri = std::env::var(TEST_MONGODB_URI_ENV)
+            .unwrap_or_else(|_| panic!("{TEST_MONGODB_URI_ENV} is not set"));
+        mongodb::Client::with_uri_str(uri)
+            .await
+            .expect("connect test MongoDB")
+            .database(format!("memory_bundle_import_{}", Uuid::new_v4().simple()).as_str())
+    }
+
+    async fn seed_source(db: &Db, source_id: &str, thread_id: &str) {
+        let scoped = |document: Document| {
+            let mut scoped = doc! {"tenant_id": "tenant_a", "source_id": source_id};
+            scoped.extend(document);
+            scoped
+        };
+        db.collection::<Document>("engine_threads")
+            .insert_one(scoped(
+                doc! {"id": thread_id, "subject_id": "agent:1", "title": "Plan"},
+            ))
+            .await
+            .expect("seed thread");
+        db.collection::<Document>("engine_records")
+            .insert_many([
+                scoped(doc! {"id": format!("{thread_id}_rec_1"), "thread_id": thread_id, "content": "Uses PostgreSQL."}),
+                scoped(doc! {"id": format!("{thread_id}_rec_2"), "thread_id": thread_id, "content": "Deploys on Fridays."}),
+            ])
+            .await
+            .expect("seed records");
+        db.collection::<Document>("engine_subject_memories")
+            .insert_one(scoped(doc! {
+                "id": format!("{thread_id}_smem"),
+                "subject_id": "agent:1",
+                "memory_key": format!("agent_recall:l0:{thread_id}"),
+                "text": "Prefers small releases.",
+            }))
+            .await
+            .expect("seed memory");
+    }
+
+    /// Bundle collections with the source id blanked, so exports of two
+    /// sources compare equal when they hold the same data.
+    fn without_source(bundle: &MemoryBundle) -> BTreeMap<String, Vec<Value>> {
+        bundle
+            .collections
+            .iter()
+            .map(|(name, values)| {
+                let values = values
+                    .iter()
+                    .cloned()
+                    .map(|mut value| {
+                        value["source_id"] = Value::Null;
+                        value
+                    })
+                    .collect();
+                (name.clone(), values)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn staging_an_exported_bundle_restores_its_documents() {
+        let thread = doc! {
+            "id": "thread_1",
+            "tenant_id": "tenant_a",
+            "source_id": "source_a",
+            "subject_id": "agent:1",
+            "version": 3,
+        };
+        let record = doc! {
+            "id": "rec_1",
+            "tenant_id": "tenant_a",
+            "source_id": "source_a",
+            "thread_id": "thread_1",
+            "structured_payload": {"steps": ["plan", "ship"]},
+        };
+        let mut collections = BTreeMap::new();
+        collections.insert(
+            "threads".to_string(),
+            vec![document_to_bundle_value(thread.clone())],
+        );
+        collections.insert(
+            "records".to_string(),
+            vec![document_to_bundle_value(record.clone())],
+        );
+
+        let (staged, id_map) =
+            stage_bundle_documents(collections, "tenant_a", "source_a", false).expect("stage");
+
+        assert!(id_map.is_empty());
+        assert_eq!(staged["threads"], vec![thread]);
+        assert_eq!(staged["records"], vec![record]);
+    }
+
+    #[test]
+    fn staging_rejects_entries_that_are_not_documents() {
+        let mut collections = BTreeMap::new();
+        collections.insert("threads".to_string(), vec![json!("thread_1")]);
+
+        assert!(
+            stage_bundle_documents(collections, "tenant_a", "source_a", false)
+                .expect_err("not a document")
+                .contains("bundle entry is not a document")
+        );
+    }
+
+    #[test]
+    fn transactions_need_a_replica_set_or_mongos() {
+        assert!(transactions_supported(
+            &doc! {"isWritablePrimary": true, "setName": "rs0"}
+        ));
+        assert!(transactions_supported(
+            &doc! {"isWritablePrimary": true, "msg": "isdbgrid"}
+        ));
+        assert!(!transactions_supported(&doc! {"isWritablePrimary": true}));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a MongoDB replica set in MEMORY_ENGINE_TEST_MONGODB_URI"]
+    async fn replace_import_round_trips_an_exported_bundle() {
+        let db = scratch_db().await;
+        seed_source(&db, "source_a", "thread_a").await;
+        seed_source(&db, "source_b", "thread_stale").await;
+
+        let bundle = export_memory_bundle(&db, selector("source_a"))
+            .await
+            .expect("export source_a");
+        let report = import_memory_bundle(&db, bundle.clone(), &replace_into("source_b"))
+            .await
+            .expect("replace source_b");
+        let reimported = export_memory_bundle(&db, selector("source_b"))
+            .await
+            .expect("export source_b");
+        db.drop().await.expect("drop scratch database");
+
+        assert_eq!(without_source(&reimported), without_source(&bundle));
+        let threads = report
+            .collections
+            .iter()
+            .find(|item| item.collection == "threads")
+            .expect("threads report");
+        assert_eq!((threads.replaced, threads.inserted), (1, 1));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a MongoDB replica set in MEMORY_ENGINE_TEST_MONGODB_URI"]
+    async fn failed_replace_import_leaves_the_target_untouched() {
+        let db = scratch_db().await;
+        seed_source(&db, "source_a", "thread_a").await;
+        seed_source(&db, "source_b", "thread_kept").await;
+        let before = collect_bundle_documents(&db, &selector("source_b"), None)
+            .await
+            .expect("snapshot source_b");
+
+        // Threads are deleted and re-inserted before records, so the oversized
+        // record fails the import halfway through the swap.
+        let mut bundle = export_memory_bundle(&db, selector("source_a"))
+            .await
+            .expect("export source_a");
+        bundle
+            .collections
+            .get_mut("records")
+            .expect("records")
+            .push(json!({
+                "id": "rec_oversized",
+                "tenant_id": "tenant_a",
+                "source_id": "source_a",
+                "thread_id": "thread_a",
+                "content": "x".repeat(17 * 1024 * 1024),
+            }));
+        bundle.checksum = bundle_checksum(&bundle.collections).expect("checksum");
+
+        let error = import_memory_bundle(&db, bundle, &replace_into("source_b"))
+            .await
+            .expect_err("oversized record");
+        let after = collect_bundle_documents(&db, &selector("source_b"), None)
+            .await
+            .expect("snapshot source_b");
+        db.drop().await.expect("drop scratch database");
+
+        assert!(error.contains("insert into records failed"), "{error}");
+        assert_eq!(after, before);
+    }
+}
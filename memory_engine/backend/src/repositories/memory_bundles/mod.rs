@@ -0,0 +1,13 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+mod collections;
+mod export;
+mod import;
+
+#[allow(unused_imports)]
+pub use collections::verify_memory_bundle;
+#[allow(unused_imports)]
+pub use export::{export_memory_bundle, resolve_memory_bundle_selector};
+#[allow(unused_imports)]
+pub use import::import_memory_bundle;
@@ -0,0 +1,146 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use futures_util::TryStreamExt;
+use mongodb::bson::{doc, Bson};
+
+use crate::db::Db;
+use crate::models::{
+    EngineMemoryEmbedding, MEMORY_EMBEDDING_ITEM_RECORD, MEMORY_EMBEDDING_ITEM_SUBJECT_MEMORY,
+    MEMORY_EMBEDDING_ITEM_SUMMARY,
+};
+
+fn memory_embedding_collection(db: &Db) -> mongodb::Collection<EngineMemoryEmbedding> {
+    db.collection::<EngineMemoryEmbedding>("engine_memory_embeddings")
+}
+
+pub async fn list_memory_embeddings(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    model_key: &str,
+    item_type: &str,
+    item_ids: &[String],
+) -> Result<Vec<EngineMemoryEmbedding>, String> {
+    if item_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cursor = memory_embedding_collection(db)
+        .find(doc! {
+            "tenant_id": tenant_id,
+            "source_id": source_id,
+            "model_key": model_key,
+            "item_type": item_type,
+            "item_id": {"$in": item_ids},
+        })
+        .await
+        .map_err(|err| err.to_string())?;
+
+    cursor.try_collect().await.map_err(|err| err.to_string())
+}
+
+/// Where semantic recall may look: every embedded item of the thread, records and summaries
+/// of the thread's subject from any of its threads, and the memories of the allowed subjects.
+pub struct MemoryEmbeddingSearchScope<'a> {
+    pub tenant_id: &'a str,
+    pub source_id: &'a str,
+    pub model_key: &'a str,
+    pub thread_id: &'a str,
+    pub thread_subject_id: &'a str,
+    pub memory_subject_ids: &'a [String],
+    pub excluded_item_ids: &'a [String],
+}
+
+/// Streams the stored embeddings in `scope` and returns the `limit` best by `score`, best
+/// first. Only the running top list is held in memory.
+pub async fn search_memory_embeddings(
+    db: &Db,
+    scope: &MemoryEmbeddingSearchScope<'_>,
+    limit: usize,
+    score: impl Fn(&[f32]) -> f32,
+) -> Result<Vec<(EngineMemoryEmbedding, f32)>, String> {
+    if limit == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut branches = vec![doc! {"thread_id": scope.thread_id}];
+    if !scope.thread_subject_id.trim().is_empty() {
+        branches.push(doc! {
+            "item_type": {"$in": [MEMORY_EMBEDDING_ITEM_RECORD, MEMORY_EMBEDDING_ITEM_SUMMARY]},
+            "subject_id": scope.thread_subject_id,
+        });
+    }
+    if !scope.memory_subject_ids.is_empty() {
+        branches.push(doc! {
+            "item_type": MEMORY_EMBEDDING_ITEM_SUBJECT_MEMORY,
+            "subject_id": {"$in": scope.memory_subject_ids},
+        });
+    }
+    let mut cursor = memory_embedding_collection(db)
+        .find(doc! {
+            "tenant_id": scope.tenant_id,
+            "source_id": scope.source_id,
+            "model_key": scope.model_key,
+            "item_id": {"$nin": scope.excluded_item_ids},
+            "$or": branches,
+        })
+        .await
+        .map_err(|err| err.to_string())?;
+
+    let mut best = Vec::with_capacity(limit.saturating_mul(2));
+    while let Some(embedding) = cursor.try_next().await.map_err(|err| err.to_string())? {
+        let value = score(embedding.vector.as_slice());
+        best.push((embedding, value));
+        if best.len() >= limit.saturating_mul(2) {
+            keep_best(&mut best, limit);
+        }
+    }
+    keep_best(&mut best, limit);
+    Ok(best)
+}
+
+fn keep_best(scored: &mut Vec<(EngineMemoryEmbedding, f32)>, limit: usize) {
+    scored.sort_by(|left, right| right.1.total_cmp(&left.1));
+    scored.truncate(limit);
+}
+
+pub async fn upsert_memory_embedding(
+    db: &Db,
+    embedding: &EngineMemoryEmbedding,
+) -> Result<(), String> {
+    let vector = embedding
+        .vector
+        .iter()
+        .map(|value| Bson::Double(f64::from(*value)))
+        .collect::<Vec<_>>();
+
+    memory_embedding_collection(db)
+        .update_one(
+            doc! {
+                "tenant_id": &embedding.tenant_id,
+                "source_id": &embedding.source_id,
+                "model_key": &embedding.model_key,
+                "item_type": &embedding.item_type,
+                "item_id": &embedding.item_id,
+            },
+            doc! {
+                "$set": {
+                    "thread_id": mongodb::bson::to_bson(&embedding.thread_id).unwrap_or(Bson::Null),
+                    "subject_id": mongodb::bson::to_bson(&embedding.subject_id).unwrap_or(Bson::Null),
+                    "content_hash": &embedding.content_hash,
+                    "vector": vector,
+                    "updated_at": &embedding.updated_at,
+                },
+                "$setOnInsert": {
+                    "id": &embedding.id,
+                    "created_at": &embedding.created_at,
+                }
+            },
+        )
+        .upsert(true)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(())
+}
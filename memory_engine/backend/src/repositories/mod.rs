@@ -2,6 +2,8 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 pub mod control_plane;
+pub mod memory_bundles;
+pub mod memory_embeddings;
 pub mod observability;
 pub mod records;
 pub mod sources;
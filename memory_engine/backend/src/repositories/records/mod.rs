@@ -8,13 +8,13 @@ mod status;
 mod writes;
 
 pub(crate) use common::estimate_pending_record_tokens;
-pub(crate) use queries::list_records_by_ids;
 #[allow(unused_imports)]
 pub use queries::{
     count_records, get_record_by_id, list_compact_turn_slices, list_context_records,
-    list_pending_record_thread_scopes, list_pending_records, list_records_page,
-    list_turn_process_records, PendingRecordThreadScope,
+    list_pending_record_thread_scopes, list_pending_records, list_records_by_summary_ids,
+    list_records_page, list_turn_process_records, PendingRecordThreadScope,
 };
+pub(crate) use queries::{list_records_by_ids, list_source_records_by_ids};
 #[allow(unused_imports)]
 pub use status::{
     claim_records_for_summary, mark_claimed_records_summarized, mark_records_summarized,
@@ -284,6 +284,56 @@ pub(crate) async fn list_records_by_ids(
     Ok(ordered)
 }
 
+/// Records of any thread in the source, in no particular order; ids that no longer exist are
+/// skipped.
+pub(crate) async fn list_source_records_by_ids(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    record_ids: &[String],
+) -> Result<Vec<EngineRecord>, String> {
+    if record_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    record_collection(db)
+        .find(doc! {
+            "tenant_id": tenant_id,
+            "source_id": source_id,
+            "id": {"$in": record_ids.to_vec()},
+        })
+        .await
+        .map_err(|err| err.to_string())?
+        .try_collect::<Vec<EngineRecord>>()
+        .await
+        .map_err(|err| err.to_string())
+}
+
+pub async fn list_records_by_summary_ids(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    summary_ids: &[String],
+    limit: i64,
+) -> Result<Vec<EngineRecord>, String> {
+    if summary_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cursor = record_collection(db)
+        .find(doc! {
+            "tenant_id": tenant_id,
+            "source_id": source_id,
+            "summary_id": {"$in": summary_ids.to_vec()},
+        })
+        .sort(doc! {"created_at": 1})
+        .limit(limit.clamp(1, 1000))
+        .await
+        .map_err(|err| err.to_string())?;
+
+    collect_records(cursor).await
+}
+
 pub async fn list_pending_records(
     db: &Db,
     tenant_id: &str,
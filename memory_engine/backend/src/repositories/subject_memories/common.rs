@@ -33,6 +33,7 @@ pub(crate) fn build_subject_memory_filter(
         "source_id": source_id,
         "subject_id": subject_id,
         "status": "active",
+        "$or": unexpired_subject_memory_clause(now_rfc3339().as_str()),
     };
     if let Some(value) = memory_type.map(str::trim).filter(|value| !value.is_empty()) {
         filter.insert("memory_type", value);
@@ -43,6 +44,15 @@ pub(crate) fn build_subject_memory_filter(
     filter
 }
 
+/// Memories without `expires_at` never expire; the rest stay visible until the
+/// stored instant, which is normalized to the same RFC 3339 form as `now`.
+pub(crate) fn unexpired_subject_memory_clause(now: &str) -> Vec<Document> {
+    vec![
+        doc! {"expires_at": Bson::Null},
+        doc! {"expires_at": {"$gt": now}},
+    ]
+}
+
 pub(crate) fn normalized_subject_ids(subject_ids: &[String]) -> Vec<String> {
     subject_ids
         .iter()
@@ -83,7 +93,7 @@ pub(crate) fn upsert_subject_memory_document(
         "memory_key": memory_key,
     };
 
-    let update = doc! {
+    let mut update = doc! {
         "$set": {
             "tenant_id": &req.tenant_id,
             "source_id": &req.source_id,
@@ -107,6 +117,13 @@ pub(crate) fn upsert_subject_memory_document(
             "created_at": &created_at,
         }
     };
+    if let (Some(provenance), Ok(set)) = (req.provenance.as_ref(), update.get_document_mut("$set"))
+    {
+        set.insert(
+            "provenance",
+            mongodb::bson::to_bson(provenance).unwrap_or(Bson::Null),
+        );
+    }
 
     (filter, update)
 }
@@ -0,0 +1,135 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use futures_util::TryStreamExt;
+use mongodb::bson::{doc, Bson};
+
+use crate::db::Db;
+use crate::models::{now_rfc3339, EngineSubjectMemoryConflict};
+
+pub(crate) fn subject_memory_conflict_collection(
+    db: &Db,
+) -> mongodb::Collection<EngineSubjectMemoryConflict> {
+    db.collection::<EngineSubjectMemoryConflict>("engine_subject_memory_conflicts")
+}
+
+/// Queues a conflict for review. A pair that was already reported keeps its
+/// existing entry, so a dismissed conflict is not reopened by a rerun.
+pub async fn record_subject_memory_conflict(
+    db: &Db,
+    conflict: &EngineSubjectMemoryConflict,
+) -> Result<bool, String> {
+    let document = mongodb::bson::to_document(conflict).map_err(|err| err.to_string())?;
+    let result = subject_memory_conflict_collection(db)
+        .update_one(
+            doc! {
+                "tenant_id": &conflict.tenant_id,
+                "source_id": &conflict.source_id,
+                "left_memory_id": &conflict.left_memory_id,
+                "right_memory_id": &conflict.right_memory_id,
+            },
+            doc! {"$setOnInsert": document},
+        )
+        .upsert(true)
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(result.upserted_id.is_some())
+}
+
+pub async fn query_subject_memory_conflicts(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    subject_id: Option<&str>,
+    status: Option<&str>,
+    limit: i64,
+    offset: i64,
+) -> Result<Vec<EngineSubjectMemoryConflict>, String> {
+    let mut filter = doc! {
+        "tenant_id": tenant_id,
+        "source_id": source_id,
+    };
+    if let Some(value) = subject_id.map(str::trim).filter(|value| !value.is_empty()) {
+        filter.insert("subject_id", value);
+    }
+    if let Some(value) = status.map(str::trim).filter(|value| !value.is_empty()) {
+        filter.insert("status", value);
+    }
+
+    subject_memory_conflict_collection(db)
+        .find(filter)
+        .sort(doc! {"created_at": -1})
+        .skip(offset.max(0) as u64)
+        .limit(limit.clamp(1, 500))
+        .await
+        .map_err(|err| err.to_string())?
+        .try_collect()
+        .await
+        .map_err(|err| err.to_string())
+}
+
+pub async fn get_subject_memory_conflict(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    conflict_id: &str,
+) -> Result<Option<EngineSubjectMemoryConflict>, String> {
+    subject_memory_conflict_collection(db)
+        .find_one(doc! {
+            "tenant_id": tenant_id,
+            "source_id": source_id,
+            "id": conflict_id,
+        })
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Closes an open conflict. Returns `None` when it was already closed.
+pub async fn close_subject_memory_conflict(
+    db: &Db,
+    conflict: &EngineSubjectMemoryConflict,
+    status: &str,
+    resolution: &str,
+    resolved_by: Option<&str>,
+    note: Option<&str>,
+) -> Result<Option<EngineSubjectMemoryConflict>, String> {
+    let now = now_rfc3339();
+    let optional = |value: Option<&str>| {
+        value
+            .map(|value| Bson::String(value.to_string()))
+            .unwrap_or(Bson::Null)
+    };
+    let result = subject_memory_conflict_collection(db)
+        .update_one(
+            doc! {
+                "tenant_id": &conflict.tenant_id,
+                "source_id": &conflict.source_id,
+                "id": &conflict.id,
+                "status": "open",
+            },
+            doc! {
+                "$set": {
+                    "status": status,
+                    "resolution": resolution,
+                    "resolved_by": optional(resolved_by),
+                    "resolution_note": optional(note),
+                    "resolved_at": &now,
+                    "updated_at": &now,
+                }
+            },
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+    if result.matched_count == 0 {
+        return Ok(None);
+    }
+
+    get_subject_memory_conflict(
+        db,
+        conflict.tenant_id.as_str(),
+        conflict.source_id.as_str(),
+        conflict.id.as_str(),
+    )
+    .await
+}
@@ -2,16 +2,29 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 mod common;
+mod conflicts;
 mod queries;
 mod status;
+mod versions;
 mod writes;
 
+#[allow(unused_imports)]
+pub use conflicts::{
+    close_subject_memory_conflict, get_subject_memory_conflict, query_subject_memory_conflicts,
+    record_subject_memory_conflict,
+};
 #[allow(unused_imports)]
 pub use queries::{
-    find_subject_memory_by_source_digest, list_pending_subject_memories_by_level,
-    list_subject_memories, list_subject_memories_by_subject_ids, query_subject_memories,
+    find_subject_memory_by_source_digest, get_subject_memory_by_id,
+    list_contradiction_reference_memories, list_pending_subject_memories_by_level,
+    list_pinned_subject_memories_by_subject_ids, list_subject_memories,
+    list_subject_memories_by_ids, list_subject_memories_by_subject_ids, query_subject_memories,
 };
 #[allow(unused_imports)]
-pub use status::mark_subject_memories_rolled_up;
+pub use status::{mark_subject_memories_rolled_up, mark_subject_memory_superseded};
 #[allow(unused_imports)]
-pub use writes::{upsert_generated_subject_memory, upsert_subject_memory};
+pub use versions::list_subject_memory_versions;
+#[allow(unused_imports)]
+pub use writes::{
+    apply_subject_memory_edit, upsert_generated_subject_memory, upsert_subject_memory,
+};
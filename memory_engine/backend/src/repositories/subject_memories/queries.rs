@@ -4,11 +4,11 @@
 use mongodb::bson::doc;
 
 use crate::db::Db;
-use crate::models::EngineSubjectMemory;
+use crate::models::{now_rfc3339, EngineSubjectMemory};
 
 use super::common::{
     build_subject_memory_filter, collect_subject_memories, normalized_subject_ids,
-    subject_memory_collection,
+    subject_memory_collection, unexpired_subject_memory_clause,
 };
 
 pub async fn list_subject_memories_by_subject_ids(
@@ -33,6 +33,7 @@ pub async fn list_subject_memories_by_subject_ids(
         "source_id": source_id,
         "subject_id": {"$in": ids},
         "status": "active",
+        "$or": unexpired_subject_memory_clause(now_rfc3339().as_str()),
     };
     if let Some(value) = level {
         filter.insert("level", value.max(0));
@@ -180,6 +181,8 @@ pub async fn list_pending_subject_memories_by_level(
             "status": "active",
             "rollup_status": "pending",
             "metadata.relation_subject_id": relation_subject_id,
+            "pinned": {"$ne": true},
+            "$or": unexpired_subject_memory_clause(now_rfc3339().as_str()),
         })
         .sort(doc! {"updated_at": 1})
         .await
@@ -187,3 +190,104 @@ pub async fn list_pending_subject_memories_by_level(
 
     collect_subject_memories(cursor).await
 }
+
+/// Active memories of the same subject, relation and type at `min_level` or above that a rollup
+/// batch may contradict: pending memories the batch left out and the current higher-level
+/// rollups. Newest and highest levels come first.
+#[allow(clippy::too_many_arguments)]
+pub async fn list_contradiction_reference_memories(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    subject_id: &str,
+    relation_subject_id: &str,
+    memory_type: &str,
+    min_level: i64,
+    excluded_ids: &[String],
+    limit: i64,
+) -> Result<Vec<EngineSubjectMemory>, String> {
+    let mut filter =
+        build_subject_memory_filter(tenant_id, source_id, subject_id, Some(memory_type), None);
+    filter.insert("level", doc! {"$gte": min_level.max(0)});
+    filter.insert("rollup_status", "pending");
+    filter.insert("metadata.relation_subject_id", relation_subject_id);
+    filter.insert("id", doc! {"$nin": excluded_ids.to_vec()});
+
+    let cursor = subject_memory_collection(db)
+        .find(filter)
+        .sort(doc! {"level": -1, "updated_at": -1})
+        .limit(limit.clamp(1, 1000))
+        .await
+        .map_err(|err| err.to_string())?;
+
+    collect_subject_memories(cursor).await
+}
+
+pub async fn list_pinned_subject_memories_by_subject_ids(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    subject_ids: &[String],
+    limit: i64,
+) -> Result<Vec<EngineSubjectMemory>, String> {
+    let ids = normalized_subject_ids(subject_ids);
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cursor = subject_memory_collection(db)
+        .find(doc! {
+            "tenant_id": tenant_id,
+            "source_id": source_id,
+            "subject_id": {"$in": ids},
+            "status": "active",
+            "pinned": true,
+            "$or": unexpired_subject_memory_clause(now_rfc3339().as_str()),
+        })
+        .sort(doc! {"updated_at": -1})
+        .limit(limit.clamp(1, 1000))
+        .await
+        .map_err(|err| err.to_string())?;
+
+    collect_subject_memories(cursor).await
+}
+
+pub async fn get_subject_memory_by_id(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    memory_id: &str,
+) -> Result<Option<EngineSubjectMemory>, String> {
+    subject_memory_collection(db)
+        .find_one(doc! {
+            "tenant_id": tenant_id,
+            "source_id": source_id,
+            "id": memory_id,
+            "status": "active",
+        })
+        .await
+        .map_err(|err| err.to_string())
+}
+
+pub async fn list_subject_memories_by_ids(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    memory_ids: &[String],
+) -> Result<Vec<EngineSubjectMemory>, String> {
+    if memory_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cursor = subject_memory_collection(db)
+        .find(doc! {
+            "tenant_id": tenant_id,
+            "source_id": source_id,
+            "id": {"$in": memory_ids.to_vec()},
+        })
+        .sort(doc! {"level": -1, "updated_at": -1})
+        .await
+        .map_err(|err| err.to_string())?;
+
+    collect_subject_memories(cursor).await
+}
@@ -1,7 +1,7 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
-use mongodb::bson::doc;
+use mongodb::bson::{doc, Bson};
 
 use crate::db::Db;
 use crate::models::now_rfc3339;
@@ -30,6 +30,7 @@ pub async fn mark_subject_memories_rolled_up(
                 "id": {"$in": memory_ids.to_vec()},
                 "rollup_status": "pending",
                 "status": "active",
+                "pinned": {"$ne": true},
             },
             doc! {
                 "$set": {
@@ -45,3 +46,39 @@ pub async fn mark_subject_memories_rolled_up(
 
     Ok(result.modified_count as usize)
 }
+
+/// Retires an active, unpinned memory in favour of `superseded_by`. Returns
+/// `false` when the memory was pinned, already retired or does not exist.
+pub async fn mark_subject_memory_superseded(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    memory_id: &str,
+    superseded_by: &str,
+    reason: Option<&str>,
+) -> Result<bool, String> {
+    let now = now_rfc3339();
+    let result = subject_memory_collection(db)
+        .update_one(
+            doc! {
+                "tenant_id": tenant_id,
+                "source_id": source_id,
+                "id": memory_id,
+                "status": "active",
+                "pinned": {"$ne": true},
+            },
+            doc! {
+                "$set": {
+                    "status": "superseded",
+                    "superseded_by": superseded_by,
+                    "superseded_at": &now,
+                    "supersession_reason": reason.map(|value| Bson::String(value.to_string())).unwrap_or(Bson::Null),
+                    "updated_at": &now,
+                }
+            },
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+
+    Ok(result.modified_count > 0)
+}
@@ -0,0 +1,99 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use futures_util::TryStreamExt;
+use mongodb::bson::doc;
+use uuid::Uuid;
+
+use crate::db::Db;
+use crate::models::{EngineSubjectMemory, EngineSubjectMemoryVersion, SubjectMemoryEdit};
+
+pub(crate) fn subject_memory_version_collection(
+    db: &Db,
+) -> mongodb::Collection<EngineSubjectMemoryVersion> {
+    db.collection::<EngineSubjectMemoryVersion>("engine_subject_memory_versions")
+}
+
+pub async fn list_subject_memory_versions(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    memory_id: &str,
+    limit: i64,
+) -> Result<Vec<EngineSubjectMemoryVersion>, String> {
+    subject_memory_version_collection(db)
+        .find(doc! {
+            "tenant_id": tenant_id,
+            "source_id": source_id,
+            "memory_id": memory_id,
+        })
+        .sort(doc! {"version": -1})
+        .limit(limit.clamp(1, 1000))
+        .await
+        .map_err(|err| err.to_string())?
+        .try_collect()
+        .await
+        .map_err(|err| err.to_string())
+}
+
+/// Appends the edited state to the history. The first edit of a memory also
+/// records the state it replaced, so the original generated text is kept.
+pub(crate) async fn record_subject_memory_edit_versions(
+    db: &Db,
+    previous: &EngineSubjectMemory,
+    edit: &SubjectMemoryEdit,
+) -> Result<(), String> {
+    let collection = subject_memory_version_collection(db);
+    let has_history = collection
+        .count_documents(doc! {
+            "tenant_id": &previous.tenant_id,
+            "source_id": &previous.source_id,
+            "memory_id": &previous.id,
+        })
+        .await
+        .map_err(|err| err.to_string())?
+        > 0;
+
+    let mut versions = Vec::with_capacity(2);
+    if !has_history {
+        versions.push(EngineSubjectMemoryVersion {
+            id: format!("smemv_{}", Uuid::new_v4()),
+            tenant_id: previous.tenant_id.clone(),
+            source_id: previous.source_id.clone(),
+            memory_id: previous.id.clone(),
+            subject_id: previous.subject_id.clone(),
+            memory_key: previous.memory_key.clone(),
+            version: previous.version,
+            text: previous.text.clone(),
+            pinned: previous.pinned,
+            expires_at: previous.expires_at.clone(),
+            edited_by: previous.edited_by.clone(),
+            reason: None,
+            created_at: previous
+                .edited_at
+                .clone()
+                .unwrap_or_else(|| previous.updated_at.clone()),
+        });
+    }
+    versions.push(EngineSubjectMemoryVersion {
+        id: format!("smemv_{}", Uuid::new_v4()),
+        tenant_id: previous.tenant_id.clone(),
+        source_id: previous.source_id.clone(),
+        memory_id: previous.id.clone(),
+        subject_id: previous.subject_id.clone(),
+        memory_key: previous.memory_key.clone(),
+        version: edit.version,
+        text: edit.text.clone(),
+        pinned: edit.pinned,
+        expires_at: edit.expires_at.clone(),
+        edited_by: edit.edited_by.clone(),
+        reason: edit.reason.clone(),
+        created_at: edit.edited_at.clone(),
+    });
+
+    collection
+        .insert_many(versions)
+        .await
+        .map_err(|err| err.to_string())?;
+    Ok(())
+}
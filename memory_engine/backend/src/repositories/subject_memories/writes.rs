@@ -1,10 +1,13 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
+use mongodb::bson::{doc, Bson};
+
 use crate::db::Db;
-use crate::models::{EngineSubjectMemory, UpsertSubjectMemoryRequest};
+use crate::models::{EngineSubjectMemory, SubjectMemoryEdit, UpsertSubjectMemoryRequest};
 
 use super::common::{subject_memory_collection, upsert_subject_memory_document};
+use super::versions::record_subject_memory_edit_versions;
 
 pub async fn upsert_subject_memory(
     db: &Db,
@@ -27,6 +30,8 @@ pub async fn upsert_subject_memory(
         .ok_or_else(|| "upserted subject memory not found".to_string())
 }
 
+/// Writes a memory produced by the rollup job. A pinned memory under the same
+/// key is returned untouched so the job can neither rewrite nor drop it.
 pub async fn upsert_generated_subject_memory(
     db: &Db,
     subject_id: &str,
@@ -43,6 +48,15 @@ pub async fn upsert_generated_subject_memory(
         Some(rollup_status),
     );
 
+    if let Some(existing) = subject_memory_collection(db)
+        .find_one(filter.clone())
+        .await
+        .map_err(|err| err.to_string())?
+        .filter(|existing| existing.pinned || existing.superseded_by.is_some())
+    {
+        return Ok(existing);
+    }
+
     subject_memory_collection(db)
         .update_one(filter.clone(), update)
         .upsert(true)
@@ -55,3 +69,58 @@ pub async fn upsert_generated_subject_memory(
         .map_err(|err| err.to_string())?
         .ok_or_else(|| "upserted generated subject memory not found".to_string())
 }
+
+/// Applies `edit` only if the memory is still at the version it was read at.
+/// Returns `None` when a concurrent edit won the race.
+pub async fn apply_subject_memory_edit(
+    db: &Db,
+    existing: &EngineSubjectMemory,
+    edit: &SubjectMemoryEdit,
+) -> Result<Option<EngineSubjectMemory>, String> {
+    let filter = doc! {
+        "tenant_id": &existing.tenant_id,
+        "source_id": &existing.source_id,
+        "id": &existing.id,
+        "version": current_version_clause(existing.version),
+    };
+    let result = subject_memory_collection(db)
+        .update_one(
+            filter,
+            doc! {
+                "$set": {
+                    "text": &edit.text,
+                    "pinned": edit.pinned,
+                    "expires_at": mongodb::bson::to_bson(&edit.expires_at).unwrap_or(Bson::Null),
+                    "version": edit.version,
+                    "edited_by": mongodb::bson::to_bson(&edit.edited_by).unwrap_or(Bson::Null),
+                    "edited_at": &edit.edited_at,
+                    "updated_at": &edit.edited_at,
+                }
+            },
+        )
+        .await
+        .map_err(|err| err.to_string())?;
+    if result.matched_count == 0 {
+        return Ok(None);
+    }
+
+    record_subject_memory_edit_versions(db, existing, edit).await?;
+
+    subject_memory_collection(db)
+        .find_one(doc! {
+            "tenant_id": &existing.tenant_id,
+            "source_id": &existing.source_id,
+            "id": &existing.id,
+        })
+        .await
+        .map_err(|err| err.to_string())
+}
+
+fn current_version_clause(version: i64) -> Bson {
+    // Memories written before versioning have no `version` field and read as 1.
+    if version == 1 {
+        Bson::Document(doc! {"$in": [1i64, Bson::Null]})
+    } else {
+        Bson::Int64(version)
+    }
+}
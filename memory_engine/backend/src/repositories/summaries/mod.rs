@@ -26,7 +26,7 @@ pub use subject_dispatch::{
 pub use queries::{
     find_summary_by_source_digest, list_latest_thread_summaries,
     list_latest_thread_summaries_at_level, list_latest_thread_summaries_by_type,
-    list_pending_summaries_by_level, list_summaries_by_thread_label,
+    list_pending_summaries_by_level, list_summaries_by_ids, list_summaries_by_thread_label,
     list_summaries_by_thread_label_for_subject_memory_scope, list_thread_summaries,
     list_threads_with_pending_rollups,
 };
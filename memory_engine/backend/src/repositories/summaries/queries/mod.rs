@@ -14,5 +14,5 @@ pub use rollups::{
 };
 pub use thread::{
     list_latest_thread_summaries, list_latest_thread_summaries_at_level,
-    list_latest_thread_summaries_by_type, list_thread_summaries,
+    list_latest_thread_summaries_by_type, list_summaries_by_ids, list_thread_summaries,
 };
@@ -104,3 +104,26 @@ pub async fn list_thread_summaries(
 
     collect_summaries(cursor).await
 }
+
+pub async fn list_summaries_by_ids(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    summary_ids: &[String],
+) -> Result<Vec<EngineSummary>, String> {
+    if summary_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cursor = summary_collection(db)
+        .find(doc! {
+            "tenant_id": tenant_id,
+            "source_id": source_id,
+            "id": {"$in": summary_ids.to_vec()},
+        })
+        .sort(doc! {"created_at": 1})
+        .await
+        .map_err(|err| err.to_string())?;
+
+    collect_summaries(cursor).await
+}
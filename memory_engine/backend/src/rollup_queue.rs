@@ -21,7 +21,7 @@ use tracing::{info, warn};
 use crate::config::AppConfig;
 use crate::models::now_rfc3339;
 use crate::repositories::{control_plane, summaries};
-use crate::services::{control_plane as cp_service, summary};
+use crate::services::{context, control_plane as cp_service, summary};
 use crate::state::AppState;
 
 const ROLLUP_QUEUE_TRIGGER: &str = "queue";
@@ -323,6 +323,12 @@ async fn process_rollup_event(
         ROLLUP_QUEUE_TRIGGER,
     )
     .await?;
+    context::spawn_thread_embedding_index(
+        state,
+        envelope.tenant_id.as_str(),
+        envelope.source_id.as_str(),
+        envelope.thread_id.as_str(),
+    );
     summaries::mark_rollup_dispatch_consumed(&state.pool, &event).await?;
 
     if summary::prepare_thread_rollup(
@@ -291,6 +291,8 @@ fn continuation_outcome(reason: &str) -> AiSingleStepOutcome {
             response_id: None,
             response_output_items: Vec::new(),
             request_input_items: Vec::new(),
+            iteration_models: Vec::new(),
+            usage_totals: chatos_ai_runtime::UsageTotals::default(),
         },
         input_items: Vec::new(),
         reason: reason.to_string(),
@@ -307,6 +309,8 @@ fn final_outcome(content: String) -> AiSingleStepOutcome {
         response_id: None,
         response_output_items: Vec::new(),
         request_input_items: Vec::new(),
+        iteration_models: Vec::new(),
+        usage_totals: chatos_ai_runtime::UsageTotals::default(),
     })
 }
 
@@ -10,10 +10,12 @@ use crate::repositories::{subject_memories, summaries};
 use super::policy::ResolvedComposeContextPolicy;
 
 const FIXED_SUBJECT_MEMORY_LIMIT: i64 = 1;
+const PINNED_SUBJECT_MEMORY_LIMIT: i64 = 20;
 
 pub(crate) struct BuiltContextBlocks {
     pub(crate) blocks: Vec<ComposeContextBlock>,
     pub(crate) summary_count: usize,
+    pub(crate) included_item_ids: Vec<String>,
 }
 
 pub(crate) async fn build_context_blocks(
@@ -27,6 +29,7 @@ pub(crate) async fn build_context_blocks(
 ) -> Result<BuiltContextBlocks, String> {
     let mut blocks = Vec::new();
     let mut summary_count = 0usize;
+    let mut included_item_ids = Vec::new();
 
     if policy.include_thread_summary {
         let summary_blocks = load_thread_summary_blocks(
@@ -39,19 +42,22 @@ pub(crate) async fn build_context_blocks(
         .await?;
         summary_count = summary_blocks.1;
         blocks.extend(summary_blocks.0);
+        included_item_ids.extend(summary_blocks.2);
     }
 
     if policy.include_subject_memory {
         let _ = summary_subject_ids;
-        blocks.extend(
+        let memory_blocks =
             load_subject_memory_blocks(db, tenant_id, source_id, subject_memory_subject_ids)
-                .await?,
-        );
+                .await?;
+        blocks.extend(memory_blocks.0);
+        included_item_ids.extend(memory_blocks.1);
     }
 
     Ok(BuiltContextBlocks {
         blocks,
         summary_count,
+        included_item_ids,
     })
 }
 
@@ -198,7 +204,7 @@ async fn load_thread_summary_blocks(
     source_id: &str,
     thread_id: &str,
     summary_limit: i64,
-) -> Result<(Vec<ComposeContextBlock>, usize), String> {
+) -> Result<(Vec<ComposeContextBlock>, usize, Vec<String>), String> {
     let level0_rows = summaries::list_latest_thread_summaries_at_level(
         db,
         tenant_id,
@@ -214,11 +220,14 @@ async fn load_thread_summary_blocks(
             .await?
             .into_iter()
             .next();
+    let summary_ids = level0_rows
+        .iter()
+        .chain(top_summary.iter().filter(|item| item.level > 0))
+        .map(|item| item.id.clone())
+        .collect::<Vec<_>>();
 
-    Ok(build_thread_summary_blocks_from_rows(
-        level0_rows,
-        top_summary,
-    ))
+    let (blocks, summary_count) = build_thread_summary_blocks_from_rows(level0_rows, top_summary);
+    Ok((blocks, summary_count, summary_ids))
 }
 
 async fn load_subject_memory_blocks(
@@ -226,8 +235,25 @@ async fn load_subject_memory_blocks(
     tenant_id: &str,
     source_id: &str,
     subject_ids: &[String],
-) -> Result<Vec<ComposeContextBlock>, String> {
+) -> Result<(Vec<ComposeContextBlock>, Vec<String>), String> {
     let mut blocks = Vec::new();
+    let mut memory_ids = Vec::new();
+
+    for memory in subject_memories::list_pinned_subject_memories_by_subject_ids(
+        db,
+        tenant_id,
+        source_id,
+        subject_ids,
+        PINNED_SUBJECT_MEMORY_LIMIT,
+    )
+    .await?
+    {
+        memory_ids.push(memory.id.clone());
+        blocks.push(ComposeContextBlock {
+            block_type: "pinned_subject_memory".to_string(),
+            text: format_subject_memory(memory),
+        });
+    }
 
     if let Some(memory) = subject_memories::list_subject_memories_by_subject_ids(
         db,
@@ -235,17 +261,18 @@ async fn load_subject_memory_blocks(
         source_id,
         subject_ids,
         None,
-        FIXED_SUBJECT_MEMORY_LIMIT,
+        FIXED_SUBJECT_MEMORY_LIMIT + memory_ids.len() as i64,
     )
     .await?
     .into_iter()
-    .next()
+    .find(|memory| !memory.pinned)
     {
+        memory_ids.push(memory.id.clone());
         blocks.push(ComposeContextBlock {
             block_type: "subject_memory".to_string(),
             text: format_subject_memory(memory),
         });
     }
 
-    Ok(blocks)
+    Ok((blocks, memory_ids))
 }
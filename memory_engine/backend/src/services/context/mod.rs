@@ -3,20 +3,33 @@
 
 mod blocks;
 mod policy;
+mod semantic;
 #[cfg(test)]
 mod tests;
 
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use tracing::warn;
+
+use crate::ai::EmbeddingProvider;
 use crate::db::Db;
 use crate::models::{ComposeContextMeta, ComposeContextRequest, ComposeContextResponse};
 use crate::repositories::{records, threads};
+use crate::state::AppState;
 
 use self::blocks::{
     build_context_blocks, subject_ids_for_context, subject_memory_subject_ids_for_context,
 };
 use self::policy::ResolvedComposeContextPolicy;
+use self::semantic::{
+    build_semantic_block, fit_context_to_budget, index_embeddings, recall_semantic_hits,
+    EmbeddingIndexTarget, SemanticScope,
+};
 
 pub async fn compose_context(
     db: &Db,
+    embedding_provider: Option<&dyn EmbeddingProvider>,
     req: ComposeContextRequest,
 ) -> Result<ComposeContextResponse, String> {
     let thread = threads::get_thread_by_id(
@@ -40,7 +53,7 @@ pub async fn compose_context(
         req.subject_id.as_deref(),
         req.related_subject_ids.as_ref(),
     );
-    let context_blocks = build_context_blocks(
+    let mut context_blocks = build_context_blocks(
         db,
         req.tenant_id.as_str(),
         req.source_id.as_str(),
@@ -64,13 +77,152 @@ pub async fn compose_context(
         Vec::new()
     };
 
+    let semantic_hits = match (embedding_provider, policy.semantic_query()) {
+        (Some(provider), Some(query)) => {
+            let excluded_item_ids = context_blocks
+                .included_item_ids
+                .iter()
+                .cloned()
+                .chain(recent_records.iter().map(|record| record.id.clone()))
+                .collect::<HashSet<_>>();
+            let subject_ids: &[String] = if policy.include_subject_memory {
+                subject_memory_subject_ids.as_slice()
+            } else {
+                &[]
+            };
+            let scope = SemanticScope {
+                tenant_id: req.tenant_id.as_str(),
+                source_id: req.source_id.as_str(),
+                thread_id: thread.id.as_str(),
+                thread_subject_id: thread.subject_id.as_str(),
+                subject_ids,
+            };
+            recall_semantic_hits(
+                db,
+                provider,
+                &scope,
+                query,
+                policy.semantic_top_k,
+                &excluded_item_ids,
+            )
+            .await
+            .unwrap_or_else(|err| {
+                warn!(
+                    "[MEMORY-ENGINE] semantic recall failed, falling back to recency: thread_id={} error={}",
+                    thread.id, err
+                );
+                Vec::new()
+            })
+        }
+        _ => Vec::new(),
+    };
+    let (semantic_hits, recent_records) =
+        fit_context_to_budget(semantic_hits, recent_records, policy.token_budget);
+    context_blocks
+        .blocks
+        .extend(build_semantic_block(semantic_hits.as_slice()));
+
+    let recent_record_count = recent_records.len();
+
     Ok(ComposeContextResponse {
         thread_id: thread.id,
         blocks: context_blocks.blocks,
-        recent_records: recent_records.clone(),
+        recent_records,
         meta: ComposeContextMeta {
             summary_count: context_blocks.summary_count,
-            recent_record_count: recent_records.len(),
+            recent_record_count,
+            semantic_hit_count: semantic_hits.len(),
         },
     })
 }
+
+/// Embeds the thread's new or changed records and summaries in the background, so compose only
+/// has to embed the query. Runs after record sync and after the summary and rollup jobs.
+pub fn spawn_thread_embedding_index(
+    state: &Arc<AppState>,
+    tenant_id: &str,
+    source_id: &str,
+    thread_id: &str,
+) {
+    let Some(provider) = state.embedding_provider.clone() else {
+        return;
+    };
+    let db = state.pool.clone();
+    let limit = state.config.embedding_backfill_limit;
+    let (tenant_id, source_id, thread_id) = (
+        tenant_id.to_string(),
+        source_id.to_string(),
+        thread_id.to_string(),
+    );
+    tokio::spawn(async move {
+        let result = match threads::get_thread_by_id(
+            &db,
+            tenant_id.as_str(),
+            source_id.as_str(),
+            thread_id.as_str(),
+        )
+        .await
+        {
+            Ok(Some(thread)) => {
+                index_embeddings(
+                    &db,
+                    provider.as_ref(),
+                    tenant_id.as_str(),
+                    source_id.as_str(),
+                    EmbeddingIndexTarget::Thread {
+                        thread_id: thread.id.as_str(),
+                        subject_id: thread.subject_id.as_str(),
+                    },
+                    limit,
+                )
+                .await
+            }
+            Ok(None) => Ok(0),
+            Err(err) => Err(err),
+        };
+        if let Err(err) = result {
+            warn!(
+                "[MEMORY-ENGINE] thread embedding index failed: thread_id={} error={}",
+                thread_id, err
+            );
+        }
+    });
+}
+
+/// Embeds the subject's new or changed memories in the background after a subject memory run.
+pub fn spawn_subject_embedding_index(
+    state: &Arc<AppState>,
+    tenant_id: &str,
+    source_id: &str,
+    subject_id: &str,
+) {
+    let Some(provider) = state.embedding_provider.clone() else {
+        return;
+    };
+    let db = state.pool.clone();
+    let limit = state.config.embedding_backfill_limit;
+    let (tenant_id, source_id, subject_id) = (
+        tenant_id.to_string(),
+        source_id.to_string(),
+        subject_id.to_string(),
+    );
+    tokio::spawn(async move {
+        if let Err(err) = index_embeddings(
+            &db,
+            provider.as_ref(),
+            tenant_id.as_str(),
+            source_id.as_str(),
+            EmbeddingIndexTarget::Subject {
+                subject_id: subject_id.as_str(),
+            },
+            limit,
+        )
+        .await
+        {
+            warn!(
+                "[MEMORY-ENGINE] subject embedding index failed: subject_id={} error={}",
+                subject_id, err
+            );
+        }
+    });
+}
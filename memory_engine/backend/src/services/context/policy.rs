@@ -4,6 +4,8 @@
 use crate::models::ComposeContextPolicy;
 
 pub(crate) const STANDARD_LEVEL0_SUMMARY_LIMIT: i64 = 2;
+pub(crate) const DEFAULT_SEMANTIC_TOP_K: usize = 5;
+const MAX_SEMANTIC_TOP_K: usize = 50;
 
 pub(crate) struct ResolvedComposeContextPolicy {
     pub(crate) include_recent_records: bool,
@@ -11,6 +13,9 @@ pub(crate) struct ResolvedComposeContextPolicy {
     pub(crate) include_subject_memory: bool,
     pub(crate) recent_limit: Option<i64>,
     pub(crate) summary_limit: i64,
+    pub(crate) query: Option<String>,
+    pub(crate) semantic_top_k: usize,
+    pub(crate) token_budget: Option<i64>,
 }
 
 impl ResolvedComposeContextPolicy {
@@ -32,6 +37,22 @@ impl ResolvedComposeContextPolicy {
                 .and_then(|item| item.summary_limit)
                 .unwrap_or(STANDARD_LEVEL0_SUMMARY_LIMIT as usize)
                 .max(1) as i64,
+            query: policy
+                .and_then(|item| item.query.as_deref())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToOwned::to_owned),
+            semantic_top_k: policy
+                .and_then(|item| item.semantic_top_k)
+                .unwrap_or(DEFAULT_SEMANTIC_TOP_K)
+                .min(MAX_SEMANTIC_TOP_K),
+            token_budget: policy
+                .and_then(|item| item.token_budget)
+                .map(|value| value.max(1) as i64),
         }
     }
+
+    pub(crate) fn semantic_query(&self) -> Option<&str> {
+        self.query.as_deref().filter(|_| self.semantic_top_k > 0)
+    }
 }
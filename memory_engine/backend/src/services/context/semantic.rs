@@ -0,0 +1,499 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::{HashMap, HashSet};
+
+use sha2::{Digest, Sha256};
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::ai::{cosine_similarity, EmbeddingProvider};
+use crate::db::Db;
+use crate::models::{
+    now_rfc3339, ComposeContextBlock, EngineMemoryEmbedding, EngineRecord, EngineSubjectMemory,
+    EngineSummary, MEMORY_EMBEDDING_ITEM_RECORD, MEMORY_EMBEDDING_ITEM_SUBJECT_MEMORY,
+    MEMORY_EMBEDDING_ITEM_SUMMARY,
+};
+use crate::repositories::memory_embeddings::{self, MemoryEmbeddingSearchScope};
+use crate::repositories::{records, subject_memories, summaries};
+use crate::services::ai_pipeline::estimate_tokens_text;
+
+use super::blocks::format_subject_memory;
+
+const SEMANTIC_RECORD_CANDIDATE_LIMIT: i64 = 200;
+const SEMANTIC_SUMMARY_CANDIDATE_LIMIT: i64 = 100;
+const SEMANTIC_SUBJECT_MEMORY_CANDIDATE_LIMIT: i64 = 100;
+/// Stored embeddings fetched per requested hit, so hits dropped as stale or inactive still
+/// leave `top_k` to rank.
+const SEMANTIC_SEARCH_OVERFETCH: usize = 4;
+const MIN_SEMANTIC_SCORE: f32 = 0.05;
+const MAX_EMBEDDING_INPUT_CHARS: usize = 4_000;
+
+#[derive(Debug, Clone)]
+pub(crate) struct SemanticCandidate {
+    pub(crate) item_type: &'static str,
+    pub(crate) item_id: String,
+    pub(crate) thread_id: Option<String>,
+    pub(crate) subject_id: Option<String>,
+    pub(crate) content: String,
+    pub(crate) display_text: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SemanticHit {
+    pub(crate) candidate: SemanticCandidate,
+    pub(crate) score: f32,
+}
+
+pub(crate) struct SemanticScope<'a> {
+    pub(crate) tenant_id: &'a str,
+    pub(crate) source_id: &'a str,
+    pub(crate) thread_id: &'a str,
+    pub(crate) thread_subject_id: &'a str,
+    pub(crate) subject_ids: &'a [String],
+}
+
+/// Which items a background embedding pass covers.
+pub(crate) enum EmbeddingIndexTarget<'a> {
+    /// The thread's latest records and summaries, filed under the thread's subject so recall
+    /// from the subject's other threads finds them.
+    Thread {
+        thread_id: &'a str,
+        subject_id: &'a str,
+    },
+    /// The subject's active memories.
+    Subject { subject_id: &'a str },
+}
+
+/// Embeds only the query and ranks it against the stored embeddings of everything the scope
+/// allows. Items whose content changed since they were embedded, or that are no longer active,
+/// are skipped until the ingest pipeline re-embeds them.
+pub(crate) async fn recall_semantic_hits(
+    db: &Db,
+    provider: &dyn EmbeddingProvider,
+    scope: &SemanticScope<'_>,
+    query: &str,
+    top_k: usize,
+    excluded_item_ids: &HashSet<String>,
+) -> Result<Vec<SemanticHit>, String> {
+    if top_k == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut vectors = provider.embed(&[query.to_string()]).await?;
+    if vectors.len() != 1 {
+        return Err(format!(
+            "embedding provider returned {} vectors for 1 input",
+            vectors.len()
+        ));
+    }
+    let query_vector = vectors.remove(0);
+
+    let model_key = provider.model_key();
+    let excluded_item_ids = excluded_item_ids.iter().cloned().collect::<Vec<_>>();
+    let search_scope = MemoryEmbeddingSearchScope {
+        tenant_id: scope.tenant_id,
+        source_id: scope.source_id,
+        model_key: model_key.as_str(),
+        thread_id: scope.thread_id,
+        thread_subject_id: scope.thread_subject_id,
+        memory_subject_ids: scope.subject_ids,
+        excluded_item_ids: excluded_item_ids.as_slice(),
+    };
+    let embeddings = memory_embeddings::search_memory_embeddings(
+        db,
+        &search_scope,
+        top_k.saturating_mul(SEMANTIC_SEARCH_OVERFETCH),
+        |vector| cosine_similarity(query_vector.as_slice(), vector),
+    )
+    .await?
+    .into_iter()
+    .map(|(embedding, _)| embedding)
+    .collect::<Vec<_>>();
+    let candidates =
+        load_candidates_by_id(db, scope.tenant_id, scope.source_id, embeddings.as_slice()).await?;
+
+    let scored = embeddings
+        .into_iter()
+        .filter_map(|embedding| {
+            let candidate =
+                candidates.get(&(embedding.item_type.clone(), embedding.item_id.clone()))?;
+            (content_hash(candidate.content.as_str()) == embedding.content_hash)
+                .then(|| (candidate.clone(), embedding.vector))
+        })
+        .collect::<Vec<_>>();
+    Ok(rank_semantic_hits(query_vector.as_slice(), scored, top_k))
+}
+
+/// Embeds up to `limit` items of `target` that have no embedding for the provider's model yet,
+/// or whose content changed since, and returns how many were stored.
+pub(crate) async fn index_embeddings(
+    db: &Db,
+    provider: &dyn EmbeddingProvider,
+    tenant_id: &str,
+    source_id: &str,
+    target: EmbeddingIndexTarget<'_>,
+    limit: usize,
+) -> Result<usize, String> {
+    let candidates = load_index_candidates(db, tenant_id, source_id, target).await?;
+    if candidates.is_empty() {
+        return Ok(0);
+    }
+
+    let model_key = provider.model_key();
+    let mut stored = HashMap::new();
+    for item_type in [
+        MEMORY_EMBEDDING_ITEM_RECORD,
+        MEMORY_EMBEDDING_ITEM_SUMMARY,
+        MEMORY_EMBEDDING_ITEM_SUBJECT_MEMORY,
+    ] {
+        let item_ids = candidates
+            .iter()
+            .filter(|candidate| candidate.item_type == item_type)
+            .map(|candidate| candidate.item_id.clone())
+            .collect::<Vec<_>>();
+        for embedding in memory_embeddings::list_memory_embeddings(
+            db,
+            tenant_id,
+            source_id,
+            model_key.as_str(),
+            item_type,
+            item_ids.as_slice(),
+        )
+        .await?
+        {
+            stored.insert(
+                (embedding.item_type.clone(), embedding.item_id.clone()),
+                embedding,
+            );
+        }
+    }
+
+    let missing = candidates
+        .iter()
+        .map(|candidate| (candidate, content_hash(candidate.content.as_str())))
+        .filter(|(candidate, hash)| {
+            stored
+                .get(&(candidate.item_type.to_string(), candidate.item_id.clone()))
+                .is_none_or(|embedding| &embedding.content_hash != hash)
+        })
+        .take(limit)
+        .collect::<Vec<_>>();
+    if missing.is_empty() {
+        return Ok(0);
+    }
+
+    let inputs = missing
+        .iter()
+        .map(|(candidate, _)| candidate.content.clone())
+        .collect::<Vec<_>>();
+    let vectors = provider.embed(inputs.as_slice()).await?;
+    if vectors.len() != inputs.len() {
+        return Err(format!(
+            "embedding provider returned {} vectors for {} inputs",
+            vectors.len(),
+            inputs.len()
+        ));
+    }
+
+    let now = now_rfc3339();
+    let mut indexed = 0;
+    for ((candidate, hash), vector) in missing.into_iter().zip(vectors) {
+        let embedding = EngineMemoryEmbedding {
+            id: format!("emb_{}", Uuid::new_v4()),
+            tenant_id: tenant_id.to_string(),
+            source_id: source_id.to_string(),
+            item_type: candidate.item_type.to_string(),
+            item_id: candidate.item_id.clone(),
+            thread_id: candidate.thread_id.clone(),
+            subject_id: candidate.subject_id.clone(),
+            model_key: model_key.clone(),
+            content_hash: hash,
+            vector,
+            created_at: now.clone(),
+            updated_at: now.clone(),
+        };
+        match memory_embeddings::upsert_memory_embedding(db, &embedding).await {
+            Ok(()) => indexed += 1,
+            Err(err) => warn!(
+                "[MEMORY-ENGINE] store embedding failed: item_type={} item_id={} error={}",
+                embedding.item_type, embedding.item_id, err
+            ),
+        }
+    }
+    Ok(indexed)
+}
+
+pub(crate) fn rank_semantic_hits(
+    query_vector: &[f32],
+    candidates: Vec<(SemanticCandidate, Vec<f32>)>,
+    top_k: usize,
+) -> Vec<SemanticHit> {
+    let mut hits = candidates
+        .into_iter()
+        .map(|(candidate, vector)| SemanticHit {
+            score: cosine_similarity(query_vector, vector.as_slice()),
+            candidate,
+        })
+        .filter(|hit| hit.score >= MIN_SEMANTIC_SCORE)
+        .collect::<Vec<_>>();
+    hits.sort_by(|left, right| right.score.total_cmp(&left.score));
+    hits.truncate(top_k);
+    hits
+}
+
+/// Splits the token budget between semantic hits and recent records. Hits are
+/// admitted best-first into at most half of the budget, then the newest recent
+/// records fill whatever remains; any budget the records leave unused goes back
+/// to the remaining hits.
+pub(crate) fn fit_context_to_budget(
+    hits: Vec<SemanticHit>,
+    recent_records: Vec<EngineRecord>,
+    token_budget: Option<i64>,
+) -> (Vec<SemanticHit>, Vec<EngineRecord>) {
+    let Some(budget) = token_budget else {
+        return (hits, recent_records);
+    };
+
+    let hit_cost = |hit: &SemanticHit| estimate_tokens_text(hit.candidate.display_text.as_str());
+    let record_cost = |record: &EngineRecord| estimate_tokens_text(record.content.as_str());
+
+    let semantic_share = if recent_records.is_empty() {
+        budget
+    } else {
+        budget / 2
+    };
+    let mut used = 0i64;
+    let mut admitted = vec![false; hits.len()];
+    for (index, hit) in hits.iter().enumerate() {
+        let cost = hit_cost(hit);
+        if used + cost <= semantic_share {
+            used += cost;
+            admitted[index] = true;
+        }
+    }
+
+    let mut kept_records = Vec::new();
+    for record in recent_records.into_iter().rev() {
+        let cost = record_cost(&record);
+        if used + cost > budget {
+            break;
+        }
+        used += cost;
+        kept_records.push(record);
+    }
+    kept_records.reverse();
+
+    for (index, hit) in hits.iter().enumerate() {
+        if admitted[index] {
+            continue;
+        }
+        let cost = hit_cost(hit);
+        if used + cost <= budget {
+            used += cost;
+            admitted[index] = true;
+        }
+    }
+
+    let kept_hits = hits
+        .into_iter()
+        .zip(admitted)
+        .filter_map(|(hit, keep)| keep.then_some(hit))
+        .collect();
+    (kept_hits, kept_records)
+}
+
+pub(crate) fn build_semantic_block(hits: &[SemanticHit]) -> Option<ComposeContextBlock> {
+    if hits.is_empty() {
+        return None;
+    }
+    Some(ComposeContextBlock {
+        block_type: "semantic_recall".to_string(),
+        text: hits
+            .iter()
+            .map(|hit| hit.candidate.display_text.clone())
+            .collect::<Vec<_>>()
+            .join("\n\n---\n\n"),
+    })
+}
+
+pub(crate) fn record_candidate(record: EngineRecord) -> Option<SemanticCandidate> {
+    let content = record.content.trim();
+    if content.is_empty() {
+        return None;
+    }
+    Some(SemanticCandidate {
+        item_type: MEMORY_EMBEDDING_ITEM_RECORD,
+        display_text: format!(
+            "[record][role={}][created_at={}]\n{}",
+            record.role, record.created_at, content
+        ),
+        content: embedding_input(content),
+        item_id: record.id,
+        thread_id: Some(record.thread_id),
+        subject_id: None,
+    })
+}
+
+pub(crate) fn summary_candidate(summary: EngineSummary) -> Option<SemanticCandidate> {
+    let content = summary.summary_text.trim();
+    if content.is_empty() {
+        return None;
+    }
+    Some(SemanticCandidate {
+        item_type: MEMORY_EMBEDDING_ITEM_SUMMARY,
+        display_text: format!(
+            "[summary][level={}][created_at={}]\n{}",
+            summary.level, summary.created_at, content
+        ),
+        content: embedding_input(content),
+        item_id: summary.id,
+        thread_id: Some(summary.thread_id),
+        subject_id: Some(summary.subject_id),
+    })
+}
+
+pub(crate) fn subject_memory_candidate(memory: EngineSubjectMemory) -> Option<SemanticCandidate> {
+    let content = embedding_input(memory.text.trim());
+    if content.is_empty() {
+        return None;
+    }
+    Some(SemanticCandidate {
+        item_type: MEMORY_EMBEDDING_ITEM_SUBJECT_MEMORY,
+        item_id: memory.id.clone(),
+        thread_id: None,
+        subject_id: Some(memory.subject_id.clone()),
+        content,
+        display_text: format_subject_memory(memory),
+    })
+}
+
+async fn load_index_candidates(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    target: EmbeddingIndexTarget<'_>,
+) -> Result<Vec<SemanticCandidate>, String> {
+    let mut candidates = Vec::new();
+    match target {
+        EmbeddingIndexTarget::Thread {
+            thread_id,
+            subject_id,
+        } => {
+            let records = records::list_records_page(
+                db,
+                thread_id,
+                Some(tenant_id),
+                Some(source_id),
+                None,
+                None,
+                None,
+                SEMANTIC_RECORD_CANDIDATE_LIMIT,
+                0,
+                false,
+            )
+            .await?;
+            candidates.extend(records.items.into_iter().filter_map(record_candidate).map(
+                |candidate| SemanticCandidate {
+                    subject_id: Some(subject_id.to_string()),
+                    ..candidate
+                },
+            ));
+
+            let thread_summaries = summaries::list_latest_thread_summaries(
+                db,
+                tenant_id,
+                source_id,
+                thread_id,
+                SEMANTIC_SUMMARY_CANDIDATE_LIMIT,
+            )
+            .await?;
+            candidates.extend(thread_summaries.into_iter().filter_map(summary_candidate));
+        }
+        EmbeddingIndexTarget::Subject { subject_id } => {
+            let memories = subject_memories::list_subject_memories_by_subject_ids(
+                db,
+                tenant_id,
+                source_id,
+                &[subject_id.to_string()],
+                None,
+                SEMANTIC_SUBJECT_MEMORY_CANDIDATE_LIMIT,
+            )
+            .await?;
+            candidates.extend(memories.into_iter().filter_map(subject_memory_candidate));
+        }
+    }
+    Ok(candidates)
+}
+
+/// Loads the current state of the items behind `embeddings`, keyed by item type and id. Deleted
+/// items, unfinished summaries and inactive or expired memories are left out.
+async fn load_candidates_by_id(
+    db: &Db,
+    tenant_id: &str,
+    source_id: &str,
+    embeddings: &[EngineMemoryEmbedding],
+) -> Result<HashMap<(String, String), SemanticCandidate>, String> {
+    let ids_of = |item_type: &str| {
+        embeddings
+            .iter()
+            .filter(|embedding| embedding.item_type == item_type)
+            .map(|embedding| embedding.item_id.clone())
+            .collect::<Vec<_>>()
+    };
+    let now = now_rfc3339();
+    let mut candidates = Vec::new();
+    let record_ids = ids_of(MEMORY_EMBEDDING_ITEM_RECORD);
+    candidates.extend(
+        records::list_source_records_by_ids(db, tenant_id, source_id, record_ids.as_slice())
+            .await?
+            .into_iter()
+            .filter_map(record_candidate),
+    );
+    let summary_ids = ids_of(MEMORY_EMBEDDING_ITEM_SUMMARY);
+    candidates.extend(
+        summaries::list_summaries_by_ids(db, tenant_id, source_id, summary_ids.as_slice())
+            .await?
+            .into_iter()
+            .filter(|summary| summary.status == "done")
+            .filter_map(summary_candidate),
+    );
+    let memory_ids = ids_of(MEMORY_EMBEDDING_ITEM_SUBJECT_MEMORY);
+    candidates.extend(
+        subject_memories::list_subject_memories_by_ids(
+            db,
+            tenant_id,
+            source_id,
+            memory_ids.as_slice(),
+        )
+        .await?
+        .into_iter()
+        .filter(|memory| {
+            memory.status == "active"
+                && memory
+                    .expires_at
+                    .as_deref()
+                    .is_none_or(|expires_at| expires_at > now.as_str())
+        })
+        .filter_map(subject_memory_candidate),
+    );
+    Ok(candidates
+        .into_iter()
+        .map(|candidate| {
+            (
+                (candidate.item_type.to_string(), candidate.item_id.clone()),
+                candidate,
+            )
+        })
+        .collect())
+}
+
+fn embedding_input(content: &str) -> String {
+    content.chars().take(MAX_EMBEDDING_INPUT_CHARS).collect()
+}
+
+fn content_hash(content: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("sha256:{}", hex::encode(hasher.finalize()))
+}
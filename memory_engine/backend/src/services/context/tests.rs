@@ -6,7 +6,14 @@ use super::blocks::{
     subject_ids_for_context, subject_memory_subject_ids_for_context, thread_agent_subject_id,
 };
 use super::policy::ResolvedComposeContextPolicy;
-use crate::models::{EngineSubjectMemory, EngineSummary, EngineThread};
+use super::semantic::{
+    build_semantic_block, fit_context_to_budget, rank_semantic_hits, record_candidate,
+    summary_candidate, SemanticHit,
+};
+use crate::ai::HashingEmbeddingProvider;
+use crate::models::{
+    ComposeContextPolicy, EngineRecord, EngineSubjectMemory, EngineSummary, EngineThread,
+};
 
 fn summary(id: &str, level: i64, created_at: &str, text: &str) -> EngineSummary {
     EngineSummary {
@@ -58,11 +65,47 @@ fn subject_memory(
         rollup_status: "pending".to_string(),
         rollup_memory_key: None,
         rolled_up_at: None,
+        pinned: false,
+        expires_at: None,
+        version: 1,
+        edited_by: None,
+        edited_at: None,
+        provenance: None,
+        superseded_by: None,
+        superseded_at: None,
+        supersession_reason: None,
         created_at: "2026-05-12T00:00:00Z".to_string(),
         updated_at: "2026-05-12T00:00:00Z".to_string(),
     }
 }
 
+fn record(id: &str, created_at: &str, content: &str) -> EngineRecord {
+    EngineRecord {
+        id: id.to_string(),
+        thread_id: "thread_1".to_string(),
+        tenant_id: "tenant_1".to_string(),
+        source_id: "source_1".to_string(),
+        external_record_id: None,
+        role: "user".to_string(),
+        record_type: "message".to_string(),
+        content: content.to_string(),
+        structured_payload: None,
+        metadata: None,
+        summary_status: "pending".to_string(),
+        summary_id: None,
+        summarized_at: None,
+        created_at: created_at.to_string(),
+    }
+}
+
+fn semantic_hit(id: &str, content: &str, score: f32) -> SemanticHit {
+    SemanticHit {
+        candidate: record_candidate(record(id, "2026-05-12T00:00:00Z", content))
+            .expect("record candidate"),
+        score,
+    }
+}
+
 fn thread(
     subject_id: &str,
     labels: Option<Vec<&str>>,
@@ -229,3 +272,117 @@ fn compose_context_policy_uses_standard_summary_rule_without_recent_record_cap()
     assert_eq!(policy.summary_limit, 2);
     assert_eq!(policy.recent_limit, None);
 }
+
+#[test]
+fn compose_context_policy_enables_semantic_recall_only_with_query() {
+    let without_query = ResolvedComposeContextPolicy::from_request(Some(&ComposeContextPolicy {
+        query: Some("   ".to_string()),
+        ..Default::default()
+    }));
+    assert_eq!(without_query.semantic_query(), None);
+    assert_eq!(without_query.token_budget, None);
+
+    let with_query = ResolvedComposeContextPolicy::from_request(Some(&ComposeContextPolicy {
+        query: Some(" deploy worker ".to_string()),
+        token_budget: Some(800),
+        ..Default::default()
+    }));
+    assert_eq!(with_query.semantic_query(), Some("deploy worker"));
+    assert_eq!(with_query.semantic_top_k, 5);
+    assert_eq!(with_query.token_budget, Some(800));
+
+    let disabled = ResolvedComposeContextPolicy::from_request(Some(&ComposeContextPolicy {
+        query: Some("deploy worker".to_string()),
+        semantic_top_k: Some(0),
+        ..Default::default()
+    }));
+    assert_eq!(disabled.semantic_query(), None);
+}
+
+#[test]
+fn semantic_ranking_orders_hits_by_similarity_and_applies_top_k() {
+    let provider = HashingEmbeddingProvider::new(256);
+    let candidates = vec![
+        record_candidate(record(
+            "rec_theme",
+            "2026-05-12T01:00:00Z",
+            "The user prefers a dark theme in the editor",
+        ))
+        .expect("record candidate"),
+        summary_candidate(summary(
+            "sum_deploy",
+            0,
+            "2026-05-12T02:00:00Z",
+            "We agreed to deploy the summary worker to staging before production",
+        ))
+        .expect("summary candidate"),
+        record_candidate(record(
+            "rec_deploy",
+            "2026-05-12T03:00:00Z",
+            "Deploy the worker after the staging checks pass",
+        ))
+        .expect("record candidate"),
+    ]
+    .into_iter()
+    .map(|candidate| {
+        let vector = provider.embed_text(candidate.content.as_str());
+        (candidate, vector)
+    })
+    .collect::<Vec<_>>();
+
+    let hits = rank_semantic_hits(
+        provider
+            .embed_text("how do we deploy the worker to staging")
+            .as_slice(),
+        candidates,
+        2,
+    );
+
+    assert_eq!(hits.len(), 2);
+    assert!(hits[0].score >= hits[1].score);
+    assert!(hits.iter().all(|hit| hit.candidate.item_id != "rec_theme"));
+}
+
+#[test]
+fn token_budget_mixes_semantic_hits_with_newest_recent_records() {
+    let hits = vec![
+        semantic_hit("rec_old_a", &"a".repeat(120), 0.9),
+        semantic_hit("rec_old_b", &"b".repeat(120), 0.8),
+    ];
+    let recent = vec![
+        record("rec_1", "2026-05-12T01:00:00Z", &"x".repeat(120)),
+        record("rec_2", "2026-05-12T02:00:00Z", &"y".repeat(120)),
+        record("rec_3", "2026-05-12T03:00:00Z", &"z".repeat(120)),
+    ];
+
+    let (kept_hits, kept_records) = fit_context_to_budget(hits.clone(), recent.clone(), Some(100));
+
+    assert_eq!(kept_hits.len(), 1);
+    assert_eq!(kept_hits[0].candidate.item_id, "rec_old_a");
+    assert_eq!(
+        kept_records
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["rec_3"]
+    );
+
+    let (all_hits, all_records) = fit_context_to_budget(hits, recent, None);
+    assert_eq!(all_hits.len(), 2);
+    assert_eq!(all_records.len(), 3);
+}
+
+#[test]
+fn semantic_block_joins_hits_in_rank_order() {
+    assert!(build_semantic_block(&[]).is_none());
+
+    let block = build_semantic_block(&[
+        semantic_hit("rec_a", "first hit", 0.9),
+        semantic_hit("rec_b", "second hit", 0.5),
+    ])
+    .expect("semantic block");
+
+    assert_eq!(block.block_type, "semantic_recall");
+    assert!(block.text.find("first hit") < block.text.find("second hit"));
+    assert!(block.text.contains("[record][role=user]"));
+}
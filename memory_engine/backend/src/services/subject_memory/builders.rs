@@ -8,6 +8,8 @@ use crate::db::Db;
 use crate::services::ai_pipeline::cloud_agent::CloudSummaryPipelineSpec;
 use crate::services::ai_pipeline::SummaryBuildResult;
 
+use super::contradictions::{CONTRADICTION_MERGE_DIRECTIVE, CONTRADICTION_REPORT_DIRECTIVE};
+
 pub(crate) async fn build_subject_memory_from_summaries(
     config: &AppConfig,
     db: &Db,
@@ -69,8 +71,8 @@ pub(crate) async fn build_subject_memory_rollup(
         CloudSummaryPipelineSpec {
             prompt_title: prompt_title.to_string(),
             summary_prompt: None,
-            leaf_directive: format!("Roll up these prior subject memories from level {} to level {}. Preserve durable facts, active goals, constraints, and risks.", level, target_level),
-            merge_directive: format!("Merge these partial subject-memory rollups for level {} to level {} into one durable memory.", level, target_level),
+            leaf_directive: format!("Roll up these prior subject memories from level {} to level {}. Preserve durable facts, active goals, constraints, and risks. {}", level, target_level, CONTRADICTION_REPORT_DIRECTIVE),
+            merge_directive: format!("Merge these partial subject-memory rollups for level {} to level {} into one durable memory. {}", level, target_level, CONTRADICTION_MERGE_DIRECTIVE),
             token_limit,
             target_tokens: Some(target_summary_tokens),
             initial_token_limit_floor: 500,
@@ -0,0 +1,405 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::db::Db;
+use crate::models::{
+    now_rfc3339, EngineSubjectMemory, EngineSubjectMemoryConflict, RunSubjectMemoryJobRequest,
+};
+use crate::repositories::subject_memories;
+
+const CONTRADICTIONS_HEADER: &str = "[contradictions]";
+
+/// Existing memories shown to a rollup next to its batch so contradictions with them are reported.
+pub(crate) const CONTRADICTION_REFERENCE_LIMIT: i64 = 20;
+
+pub(crate) const CONTRADICTION_REPORT_DIRECTIVE: &str = "If two input memories state conflicting facts, write only the current fact in the memory text, then end the output with a line `[contradictions]` followed by one line per conflict: `superseded | <older memory_key> | <newer memory_key> | <reason>` when the newer memory clearly replaces the older fact, or `unresolved | <memory_key> | <memory_key> | <reason>` when it is unclear which one is correct. Items marked `[reference]` are existing memories outside this rollup: never copy their content into the memory text, only report where an input memory conflicts with them. Copy memory_key values exactly and omit the section when nothing conflicts.";
+
+pub(crate) const CONTRADICTION_MERGE_DIRECTIVE: &str = "Carry every line from the `[contradictions]` sections of the partial rollups into a single `[contradictions]` section at the end of the output.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ContradictionKind {
+    Superseded,
+    Unresolved,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MemoryContradiction {
+    pub(crate) kind: ContradictionKind,
+    pub(crate) older_key: String,
+    pub(crate) newer_key: String,
+    pub(crate) reason: String,
+}
+
+#[derive(Debug)]
+pub(crate) enum ContradictionAction<'a> {
+    Supersede {
+        older: &'a EngineSubjectMemory,
+        newer: &'a EngineSubjectMemory,
+        reason: String,
+    },
+    Flag {
+        older: &'a EngineSubjectMemory,
+        newer: &'a EngineSubjectMemory,
+        reason: String,
+    },
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct ContradictionOutcome {
+    pub(crate) superseded: usize,
+    pub(crate) flagged: usize,
+}
+
+/// Separates the trailing `[contradictions]` section from the rollup text.
+/// Malformed report lines are dropped; the memory text is returned unchanged
+/// when the model did not report anything.
+pub(crate) fn split_contradiction_report(text: &str) -> (String, Vec<MemoryContradiction>) {
+    let lines = text.lines().collect::<Vec<_>>();
+    let Some(header) = lines
+        .iter()
+        .rposition(|line| line.trim().eq_ignore_ascii_case(CONTRADICTIONS_HEADER))
+    else {
+        return (text.to_string(), Vec::new());
+    };
+
+    let body = lines[..header].join("\n").trim_end().to_string();
+    let mut seen = HashSet::new();
+    let contradictions = lines[header + 1..]
+        .iter()
+        .filter_map(|line| parse_contradiction_line(line))
+        .filter(|item| seen.insert((item.older_key.clone(), item.newer_key.clone())))
+        .collect();
+    (body, contradictions)
+}
+
+fn parse_contradiction_line(line: &str) -> Option<MemoryContradiction> {
+    let line = line.trim().trim_start_matches(['-', '*']).trim();
+    let mut parts = line.splitn(4, '|').map(str::trim);
+    let kind = match parts.next()?.to_ascii_lowercase().as_str() {
+        "superseded" => ContradictionKind::Superseded,
+        "unresolved" => ContradictionKind::Unresolved,
+        _ => return None,
+    };
+    let older_key = parts.next().filter(|value| !value.is_empty())?;
+    let newer_key = parts.next().filter(|value| !value.is_empty())?;
+    if older_key == newer_key {
+        return None;
+    }
+    Some(MemoryContradiction {
+        kind,
+        older_key: older_key.to_string(),
+        newer_key: newer_key.to_string(),
+        reason: parts.next().unwrap_or_default().to_string(),
+    })
+}
+
+/// Maps reported contradictions onto the rollup batch and the reference
+/// memories shown next to it. Every pair must involve a batch memory. A
+/// supersession is only applied when the replacement really is the newer
+/// memory; anything the model got backwards, or a memory that is already being
+/// replaced, goes to review.
+pub(crate) fn plan_contradiction_actions<'a>(
+    selected: &'a [EngineSubjectMemory],
+    references: &'a [EngineSubjectMemory],
+    contradictions: &[MemoryContradiction],
+) -> Vec<ContradictionAction<'a>> {
+    let batch_keys = selected
+        .iter()
+        .map(|memory| memory.memory_key.as_str())
+        .collect::<HashSet<_>>();
+    let by_key = references
+        .iter()
+        .chain(selected)
+        .map(|memory| (memory.memory_key.as_str(), memory))
+        .collect::<HashMap<_, _>>();
+    let mut retired = HashSet::new();
+    let mut actions = Vec::new();
+    for contradiction in contradictions {
+        let (Some(first), Some(second)) = (
+            by_key.get(contradiction.older_key.as_str()).copied(),
+            by_key.get(contradiction.newer_key.as_str()).copied(),
+        ) else {
+            continue;
+        };
+        if !batch_keys.contains(first.memory_key.as_str())
+            && !batch_keys.contains(second.memory_key.as_str())
+        {
+            continue;
+        }
+        let (older, newer) = if second.created_at < first.created_at {
+            (second, first)
+        } else {
+            (first, second)
+        };
+        let reason = contradiction.reason.clone();
+        let supersedable = contradiction.kind == ContradictionKind::Superseded
+            && older.id == first.id
+            && !older.pinned
+            && !retired.contains(newer.id.as_str());
+        if supersedable && retired.insert(older.id.as_str()) {
+            actions.push(ContradictionAction::Supersede {
+                older,
+                newer,
+                reason,
+            });
+        } else {
+            actions.push(ContradictionAction::Flag {
+                older,
+                newer,
+                reason,
+            });
+        }
+    }
+    actions
+}
+
+pub(crate) async fn apply_rollup_contradictions(
+    db: &Db,
+    req: &RunSubjectMemoryJobRequest,
+    selected: &[EngineSubjectMemory],
+    references: &[EngineSubjectMemory],
+    rollup_memory_key: &str,
+    contradictions: &[MemoryContradiction],
+) -> Result<ContradictionOutcome, String> {
+    let mut outcome = ContradictionOutcome::default();
+    for action in plan_contradiction_actions(selected, references, contradictions) {
+        match action {
+            ContradictionAction::Supersede {
+                older,
+                newer,
+                reason,
+            } => {
+                if subject_memories::mark_subject_memory_superseded(
+                    db,
+                    req.tenant_id.as_str(),
+                    req.source_id.as_str(),
+                    older.id.as_str(),
+                    newer.id.as_str(),
+                    Some(reason.as_str()).filter(|value| !value.is_empty()),
+                )
+                .await?
+                {
+                    outcome.superseded += 1;
+                }
+            }
+            ContradictionAction::Flag {
+                older,
+                newer,
+                reason,
+            } => {
+                let now = now_rfc3339();
+                let conflict = EngineSubjectMemoryConflict {
+                    id: format!("smconf_{}", Uuid::new_v4()),
+                    tenant_id: req.tenant_id.clone(),
+                    source_id: req.source_id.clone(),
+                    subject_id: req.subject_id.clone(),
+                    memory_type: req.memory_type.clone(),
+                    left_memory_id: older.id.clone(),
+                    left_memory_key: older.memory_key.clone(),
+                    right_memory_id: newer.id.clone(),
+                    right_memory_key: newer.memory_key.clone(),
+                    rollup_memory_key: Some(rollup_memory_key.to_string()),
+                    reason,
+                    status: "open".to_string(),
+                    resolution: None,
+                    resolved_by: None,
+                    resolution_note: None,
+                    resolved_at: None,
+                    created_at: now.clone(),
+                    updated_at: now,
+                };
+                if subject_memories::record_subject_memory_conflict(db, &conflict).await? {
+                    outcome.flagged += 1;
+                }
+            }
+        }
+    }
+    Ok(outcome)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        plan_contradiction_actions, split_contradiction_report, ContradictionAction,
+        ContradictionKind, MemoryContradiction,
+    };
+    use crate::models::EngineSubjectMemory;
+
+    fn memory(id: &str, key: &str, created_at: &str) -> EngineSubjectMemory {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "tenant_id": "tenant_1",
+            "source_id": "source_1",
+            "subject_id": "agent:1",
+            "memory_key": key,
+            "memory_type": "agent_recall",
+            "text": "text",
+            "level": 0,
+            "source_digest": null,
+            "confidence": null,
+            "last_seen_at": null,
+            "metadata": null,
+            "rollup_memory_key": null,
+            "rolled_up_at": null,
+            "created_at": created_at,
+            "updated_at": created_at
+        }))
+        .expect("memory")
+    }
+
+    fn contradiction(kind: ContradictionKind, older: &str, newer: &str) -> MemoryContradiction {
+        MemoryContradiction {
+            kind,
+            older_key: older.to_string(),
+            newer_key: newer.to_string(),
+            reason: "database changed".to_string(),
+        }
+    }
+
+    #[test]
+    fn split_report_strips_section_and_parses_lines() {
+        let (body, items) = split_contradiction_report(
+            "Project migrated to MySQL.\n\n[Contradictions]\n- superseded | k:a | k:b | migrated\nunresolved | k:c | k:d\nnonsense line\nsuperseded | k:a | k:a | self",
+        );
+
+        assert_eq!(body, "Project migrated to MySQL.");
+        assert_eq!(
+            items,
+            vec![
+                MemoryContradiction {
+                    kind: ContradictionKind::Superseded,
+                    older_key: "k:a".to_string(),
+                    newer_key: "k:b".to_string(),
+                    reason: "migrated".to_string(),
+                },
+                MemoryContradiction {
+                    kind: ContradictionKind::Unresolved,
+                    older_key: "k:c".to_string(),
+                    newer_key: "k:d".to_string(),
+                    reason: String::new(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn split_report_keeps_text_without_section() {
+        let (body, items) = split_contradiction_report("Uses PostgreSQL.\n");
+
+        assert_eq!(body, "Uses PostgreSQL.\n");
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn plan_supersedes_older_memory_in_chronological_order() {
+        let selected = vec![
+            memory("m1", "k:a", "2026-05-01T00:00:00+00:00"),
+            memory("m2", "k:b", "2026-05-02T00:00:00+00:00"),
+        ];
+
+        let actions = plan_contradiction_actions(
+            &selected,
+            &[],
+            &[contradiction(ContradictionKind::Superseded, "k:a", "k:b")],
+        );
+
+        assert!(matches!(
+            actions.as_slice(),
+            [ContradictionAction::Supersede { older, newer, .. }]
+                if older.id == "m1" && newer.id == "m2"
+        ));
+    }
+
+    #[test]
+    fn plan_flags_backwards_unknown_and_unresolved_reports() {
+        let mut pinned = memory("m3", "k:c", "2026-05-03T00:00:00+00:00");
+        pinned.pinned = true;
+        let selected = vec![
+            memory("m1", "k:a", "2026-05-01T00:00:00+00:00"),
+            memory("m2", "k:b", "2026-05-02T00:00:00+00:00"),
+            pinned,
+        ];
+
+        let actions = plan_contradiction_actions(
+            &selected,
+            &[],
+            &[
+                contradiction(ContradictionKind::Superseded, "k:b", "k:a"),
+                contradiction(ContradictionKind::Unresolved, "k:a", "k:c"),
+                contradiction(ContradictionKind::Superseded, "k:a", "k:missing"),
+            ],
+        );
+
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(
+            &actions[0],
+            ContradictionAction::Flag { older, newer, .. } if older.id == "m1" && newer.id == "m2"
+        ));
+        assert!(matches!(
+            &actions[1],
+            ContradictionAction::Flag { older, newer, .. } if older.id == "m1" && newer.id == "m3"
+        ));
+    }
+
+    #[test]
+    fn plan_does_not_supersede_a_memory_twice() {
+        let selected = vec![
+            memory("m1", "k:a", "2026-05-01T00:00:00+00:00"),
+            memory("m2", "k:b", "2026-05-02T00:00:00+00:00"),
+            memory("m3", "k:c", "2026-05-03T00:00:00+00:00"),
+        ];
+
+        let actions = plan_contradiction_actions(
+            &selected,
+            &[],
+            &[
+                contradiction(ContradictionKind::Superseded, "k:a", "k:b"),
+                contradiction(ContradictionKind::Superseded, "k:a", "k:c"),
+                contradiction(ContradictionKind::Superseded, "k:b", "k:c"),
+            ],
+        );
+
+        assert!(
+            matches!(&actions[0], ContradictionAction::Supersede { older, .. } if older.id == "m1")
+        );
+        assert!(matches!(&actions[1], ContradictionAction::Flag { older, .. } if older.id == "m1"));
+        assert!(
+            matches!(&actions[2], ContradictionAction::Supersede { older, .. } if older.id == "m2")
+        );
+    }
+
+    #[test]
+    fn plan_checks_the_batch_against_existing_memories() {
+        let mut rollup = memory("m1", "k:l1", "2026-05-01T00:00:00+00:00");
+        rollup.level = 1;
+        let references = vec![rollup, memory("m4", "k:kept", "2026-05-04T00:00:00+00:00")];
+        let selected = vec![
+            memory("m2", "k:a", "2026-05-02T00:00:00+00:00"),
+            memory("m3", "k:b", "2026-05-03T00:00:00+00:00"),
+        ];
+
+        let actions = plan_contradiction_actions(
+            &selected,
+            &references,
+            &[
+                contradiction(ContradictionKind::Superseded, "k:l1", "k:b"),
+                contradiction(ContradictionKind::Unresolved, "k:a", "k:kept"),
+                contradiction(ContradictionKind::Superseded, "k:l1", "k:kept"),
+            ],
+        );
+
+        assert_eq!(actions.len(), 2);
+        assert!(matches!(
+            &actions[0],
+            ContradictionAction::Supersede { older, newer, .. } if older.id == "m1" && newer.id == "m3"
+        ));
+        assert!(matches!(
+            &actions[1],
+            ContradictionAction::Flag { older, newer, .. } if older.id == "m2" && newer.id == "m4"
+        ));
+    }
+}
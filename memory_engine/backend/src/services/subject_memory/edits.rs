@@ -0,0 +1,314 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::HashSet;
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::db::Db;
+use crate::models::{
+    EngineSubjectMemory, SubjectMemoryEdit, SubjectMemoryProvenanceResponse,
+    UpdateSubjectMemoryRequest,
+};
+use crate::repositories::{records, subject_memories, summaries};
+
+const MAX_PROVENANCE_DEPTH: usize = 8;
+const PROVENANCE_RECORD_LIMIT: i64 = 200;
+
+/// Resolves a user update against the stored memory. Every accepted update
+/// produces the next version, even when only the pin or expiry changes.
+pub(crate) fn plan_subject_memory_edit(
+    existing: &EngineSubjectMemory,
+    req: &UpdateSubjectMemoryRequest,
+    now: DateTime<Utc>,
+) -> Result<SubjectMemoryEdit, String> {
+    let expiry_inputs = [
+        req.expires_at.is_some(),
+        req.ttl_seconds.is_some(),
+        req.clear_expiry,
+    ]
+    .into_iter()
+    .filter(|value| *value)
+    .count();
+    if expiry_inputs > 1 {
+        return Err("expires_at, ttl_seconds and clear_expiry are mutually exclusive".to_string());
+    }
+    if req.text.is_none() && req.pinned.is_none() && expiry_inputs == 0 {
+        return Err("update requires text, pinned or an expiry change".to_string());
+    }
+
+    let text = match req.text.as_deref().map(str::trim) {
+        Some("") => return Err("text must not be empty".to_string()),
+        Some(value) => value.to_string(),
+        None => existing.text.clone(),
+    };
+    let expires_at = if req.clear_expiry {
+        None
+    } else if let Some(ttl_seconds) = req.ttl_seconds {
+        if ttl_seconds <= 0 {
+            return Err("ttl_seconds must be positive".to_string());
+        }
+        Some((now + Duration::seconds(ttl_seconds)).to_rfc3339())
+    } else if let Some(raw) = req.expires_at.as_deref() {
+        let parsed = DateTime::parse_from_rfc3339(raw.trim())
+            .map_err(|err| format!("invalid expires_at: {err}"))?
+            .with_timezone(&Utc);
+        if parsed <= now {
+            return Err("expires_at must be in the future".to_string());
+        }
+        Some(parsed.to_rfc3339())
+    } else {
+        existing.expires_at.clone()
+    };
+
+    Ok(SubjectMemoryEdit {
+        text,
+        pinned: req.pinned.unwrap_or(existing.pinned),
+        expires_at,
+        version: existing.version + 1,
+        edited_by: normalize_optional(req.edited_by.as_deref()),
+        reason: normalize_optional(req.reason.as_deref()),
+        edited_at: now.to_rfc3339(),
+    })
+}
+
+/// Walks a memory's provenance down to the summaries and records it was
+/// generated from. Rollups reach their summaries through the lower-level
+/// memories they were built on.
+pub(crate) async fn load_subject_memory_provenance(
+    db: &Db,
+    memory: EngineSubjectMemory,
+) -> Result<SubjectMemoryProvenanceResponse, String> {
+    let tenant_id = memory.tenant_id.clone();
+    let source_id = memory.source_id.clone();
+    let provenance = memory.provenance.clone().unwrap_or_default();
+    let source_memories = subject_memories::list_subject_memories_by_ids(
+        db,
+        tenant_id.as_str(),
+        source_id.as_str(),
+        provenance.source_memory_ids.as_slice(),
+    )
+    .await?;
+
+    let mut summary_ids = provenance.source_summary_ids;
+    let mut seen = HashSet::from([memory.id.clone()]);
+    let mut frontier = source_memories.clone();
+    for _ in 0..MAX_PROVENANCE_DEPTH {
+        let mut next_ids = Vec::new();
+        for item in &frontier {
+            if !seen.insert(item.id.clone()) {
+                continue;
+            }
+            if let Some(item_provenance) = item.provenance.as_ref() {
+                summary_ids.extend(item_provenance.source_summary_ids.iter().cloned());
+                next_ids.extend(
+                    item_provenance
+                        .source_memory_ids
+                        .iter()
+                        .filter(|id| !seen.contains(*id))
+                        .cloned(),
+                );
+            }
+        }
+        if next_ids.is_empty() {
+            break;
+        }
+        frontier = subject_memories::list_subject_memories_by_ids(
+            db,
+            tenant_id.as_str(),
+            source_id.as_str(),
+            next_ids.as_slice(),
+        )
+        .await?;
+    }
+    let summary_ids = dedupe_preserving_order(summary_ids);
+
+    let source_summaries = summaries::list_summaries_by_ids(
+        db,
+        tenant_id.as_str(),
+        source_id.as_str(),
+        summary_ids.as_slice(),
+    )
+    .await?;
+    let source_records = records::list_records_by_summary_ids(
+        db,
+        tenant_id.as_str(),
+        source_id.as_str(),
+        summary_ids.as_slice(),
+        PROVENANCE_RECORD_LIMIT,
+    )
+    .await?;
+
+    Ok(SubjectMemoryProvenanceResponse {
+        memory,
+        source_summaries,
+        source_memories,
+        source_records,
+    })
+}
+
+fn dedupe_preserving_order(values: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    values
+        .into_iter()
+        .filter(|value| seen.insert(value.clone()))
+        .collect()
+}
+
+fn normalize_optional(value: Option<&str>) -> Option<String> {
+    value
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(ToOwned::to_owned)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+
+    use super::{dedupe_preserving_order, plan_subject_memory_edit};
+    use crate::models::{EngineSubjectMemory, UpdateSubjectMemoryRequest};
+
+    fn memory() -> EngineSubjectMemory {
+        serde_json::from_value(serde_json::json!({
+            "id": "smem_1",
+            "tenant_id": "tenant_1",
+            "source_id": "source_1",
+            "subject_id": "agent:1",
+            "memory_key": "agent_recall:l0:abc",
+            "memory_type": "agent_recall",
+            "text": "generated text",
+            "level": 0,
+            "source_digest": null,
+            "confidence": null,
+            "last_seen_at": null,
+            "metadata": null,
+            "rollup_memory_key": null,
+            "rolled_up_at": null,
+            "created_at": "2026-05-01T00:00:00+00:00",
+            "updated_at": "2026-05-01T00:00:00+00:00"
+        }))
+        .expect("memory")
+    }
+
+    fn request() -> UpdateSubjectMemoryRequest {
+        UpdateSubjectMemoryRequest {
+            tenant_id: "tenant_1".to_string(),
+            source_id: "source_1".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn edit_bumps_version_and_keeps_unchanged_fields() {
+        let now = Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap();
+        let edit = plan_subject_memory_edit(
+            &memory(),
+            &UpdateSubjectMemoryRequest {
+                text: Some("  corrected text ".to_string()),
+                edited_by: Some(" user_1 ".to_string()),
+                ..request()
+            },
+            now,
+        )
+        .expect("edit");
+
+        assert_eq!(edit.text, "corrected text");
+        assert_eq!(edit.version, 2);
+        assert!(!edit.pinned);
+        assert_eq!(edit.expires_at, None);
+        assert_eq!(edit.edited_by.as_deref(), Some("user_1"));
+        assert_eq!(edit.edited_at, now.to_rfc3339());
+    }
+
+    #[test]
+    fn edit_resolves_ttl_and_normalizes_expires_at() {
+        let now = Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap();
+        let ttl = plan_subject_memory_edit(
+            &memory(),
+            &UpdateSubjectMemoryRequest {
+                ttl_seconds: Some(3600),
+                ..request()
+            },
+            now,
+        )
+        .expect("ttl edit");
+        assert_eq!(ttl.expires_at.as_deref(), Some("2026-06-01T01:00:00+00:00"));
+
+        let absolute = plan_subject_memory_edit(
+            &memory(),
+            &UpdateSubjectMemoryRequest {
+                expires_at: Some("2026-06-02T08:00:00+08:00".to_string()),
+                pinned: Some(true),
+                ..request()
+            },
+            now,
+        )
+        .expect("absolute edit");
+        assert_eq!(
+            absolute.expires_at.as_deref(),
+            Some("2026-06-02T00:00:00+00:00")
+        );
+        assert!(absolute.pinned);
+    }
+
+    #[test]
+    fn edit_rejects_invalid_requests() {
+        let now = Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap();
+        let cases = [
+            request(),
+            UpdateSubjectMemoryRequest {
+                text: Some("   ".to_string()),
+                ..request()
+            },
+            UpdateSubjectMemoryRequest {
+                ttl_seconds: Some(0),
+                ..request()
+            },
+            UpdateSubjectMemoryRequest {
+                expires_at: Some("2026-05-01T00:00:00Z".to_string()),
+                ..request()
+            },
+            UpdateSubjectMemoryRequest {
+                ttl_seconds: Some(60),
+                clear_expiry: true,
+                ..request()
+            },
+        ];
+        for case in cases {
+            assert!(plan_subject_memory_edit(&memory(), &case, now).is_err());
+        }
+    }
+
+    #[test]
+    fn clear_expiry_removes_existing_expiry() {
+        let now = Utc.with_ymd_and_hms(2026, 6, 1, 0, 0, 0).unwrap();
+        let mut existing = memory();
+        existing.expires_at = Some("2026-07-01T00:00:00+00:00".to_string());
+        existing.version = 4;
+        let edit = plan_subject_memory_edit(
+            &existing,
+            &UpdateSubjectMemoryRequest {
+                clear_expiry: true,
+                ..request()
+            },
+            now,
+        )
+        .expect("edit");
+
+        assert_eq!(edit.expires_at, None);
+        assert_eq!(edit.version, 5);
+    }
+
+    #[test]
+    fn provenance_ids_are_deduplicated_in_order() {
+        assert_eq!(
+            dedupe_preserving_order(vec![
+                "sum_b".to_string(),
+                "sum_a".to_string(),
+                "sum_b".to_string(),
+            ]),
+            vec!["sum_b".to_string(), "sum_a".to_string()]
+        );
+    }
+}
@@ -105,6 +105,8 @@ pub(crate) fn build_success_job_run(
             "marked_count": response.marked_source_summaries + response.marked_source_memories,
             "generated_level0": response.generated_level0,
             "generated_rollups": response.generated_rollups,
+            "superseded_memories": progress.superseded_memories,
+            "flagged_conflicts": progress.flagged_conflicts,
         })),
         error_message: None,
     }
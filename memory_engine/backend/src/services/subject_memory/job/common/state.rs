@@ -7,6 +7,8 @@ pub(crate) struct SubjectMemoryJobProgress {
     pub(crate) generated_rollups: usize,
     pub(crate) marked_source_summaries: usize,
     pub(crate) marked_source_memories: usize,
+    pub(crate) superseded_memories: usize,
+    pub(crate) flagged_conflicts: usize,
 }
 
 impl SubjectMemoryJobProgress {
@@ -17,6 +19,8 @@ impl SubjectMemoryJobProgress {
             generated_rollups: 0,
             marked_source_summaries: 0,
             marked_source_memories: 0,
+            superseded_memories: 0,
+            flagged_conflicts: 0,
         }
     }
 
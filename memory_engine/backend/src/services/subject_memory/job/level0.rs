@@ -3,7 +3,9 @@
 
 use crate::config::AppConfig;
 use crate::db::Db;
-use crate::models::{now_rfc3339, RunSubjectMemoryJobRequest, UpsertSubjectMemoryRequest};
+use crate::models::{
+    now_rfc3339, RunSubjectMemoryJobRequest, SubjectMemoryProvenance, UpsertSubjectMemoryRequest,
+};
 use crate::repositories::subject_memories;
 
 use super::super::builders::build_subject_memory_from_summaries;
@@ -133,6 +135,10 @@ pub(crate) async fn process_level0_selection(
         status: Some("active".to_string()),
         created_at: None,
         updated_at: None,
+        provenance: Some(SubjectMemoryProvenance {
+            source_summary_ids: selected_ids.clone(),
+            ..Default::default()
+        }),
     };
     subject_memories::upsert_generated_subject_memory(
         db,
@@ -170,6 +176,7 @@ pub(crate) async fn process_level0_selection(
                 status: Some("deleted".to_string()),
                 created_at: None,
                 updated_at: None,
+                provenance: None,
             };
             let _ = subject_memories::upsert_generated_subject_memory(
                 db,
@@ -3,13 +3,19 @@
 
 use crate::config::AppConfig;
 use crate::db::Db;
-use crate::models::{now_rfc3339, RunSubjectMemoryJobRequest, UpsertSubjectMemoryRequest};
+use crate::models::{
+    now_rfc3339, RunSubjectMemoryJobRequest, SubjectMemoryProvenance, UpsertSubjectMemoryRequest,
+};
 use crate::repositories::subject_memories;
 use crate::services::ai_pipeline::{estimate_tokens_text, SummaryBuildResult};
 
 use super::super::builders::build_subject_memory_rollup;
+use super::super::contradictions::{
+    apply_rollup_contradictions, split_contradiction_report, CONTRADICTION_REFERENCE_LIMIT,
+};
 use super::super::render::{
-    build_memory_metadata, decorate_generated_text, digest_from_ids, subject_memory_to_rollup_block,
+    build_memory_metadata, decorate_generated_text, digest_from_ids,
+    subject_memory_to_reference_block, subject_memory_to_rollup_block,
 };
 use super::super::{RollupSelection, SubjectMemoryJobSettings};
 use super::common::{
@@ -81,7 +87,38 @@ pub(crate) async fn process_rollup_selection(
         }
     }
 
-    let build = if summarizable.is_empty() {
+    let references = if summarizable.is_empty() {
+        Vec::new()
+    } else {
+        subject_memories::list_contradiction_reference_memories(
+            db,
+            req.tenant_id.as_str(),
+            req.source_id.as_str(),
+            req.subject_id.as_str(),
+            settings.relation_subject_id.as_str(),
+            req.memory_type.as_str(),
+            level,
+            selected_ids.as_slice(),
+            CONTRADICTION_REFERENCE_LIMIT,
+        )
+        .await
+        .unwrap_or_else(|err| {
+            tracing::warn!(
+                "[MEMORY-ENGINE-SUBJECT] load contradiction references failed subject_id={} level={} error={}",
+                req.subject_id, level, err
+            );
+            Vec::new()
+        })
+        .into_iter()
+        .filter(|memory| {
+            estimate_tokens_text(subject_memory_to_reference_block(memory).as_str())
+                <= settings.token_limit.max(500)
+        })
+        .collect::<Vec<_>>()
+    };
+    summarizable.extend(references.iter().map(subject_memory_to_reference_block));
+
+    let mut build = if summarizable.is_empty() {
         SummaryBuildResult {
             text: format!(
                 "All {} selected {} memories at level {} exceeded token_limit={}, so this rollup only marks the batch as rolled up.",
@@ -137,6 +174,8 @@ pub(crate) async fn process_rollup_selection(
         }
     };
 
+    let (memory_body, contradictions) = split_contradiction_report(build.text.as_str());
+    build.text = memory_body;
     let memory_text = decorate_generated_text(
         build,
         Some(oversized),
@@ -165,6 +204,10 @@ pub(crate) async fn process_rollup_selection(
         status: Some("active".to_string()),
         created_at: None,
         updated_at: None,
+        provenance: Some(SubjectMemoryProvenance {
+            source_memory_ids: selected_ids.clone(),
+            ..Default::default()
+        }),
     };
     subject_memories::upsert_generated_subject_memory(
         db,
@@ -205,6 +248,7 @@ pub(crate) async fn process_rollup_selection(
                 status: Some("deleted".to_string()),
                 created_at: None,
                 updated_at: None,
+                provenance: None,
             };
             let _ = subject_memories::upsert_generated_subject_memory(
                 db,
@@ -233,5 +277,27 @@ pub(crate) async fn process_rollup_selection(
         }
     };
 
+    if !contradictions.is_empty() {
+        match apply_rollup_contradictions(
+            db,
+            req,
+            selection.selected.as_slice(),
+            references.as_slice(),
+            memory_key.as_str(),
+            contradictions.as_slice(),
+        )
+        .await
+        {
+            Ok(outcome) => {
+                progress.superseded_memories += outcome.superseded;
+                progress.flagged_conflicts += outcome.flagged;
+            }
+            Err(err) => tracing::warn!(
+                "[MEMORY-ENGINE-SUBJECT] apply rollup contradictions failed subject_id={} memory_key={} error={}",
+                req.subject_id, memory_key, err
+            ),
+        }
+    }
+
     Ok(())
 }
@@ -43,6 +43,8 @@ pub(crate) struct RollupSelection {
 }
 
 mod builders;
+mod contradictions;
+mod edits;
 mod job;
 mod render;
 #[cfg(test)]
@@ -51,6 +53,7 @@ mod scopes;
 mod selectors;
 mod settings;
 
+pub(crate) use edits::{load_subject_memory_provenance, plan_subject_memory_edit};
 pub(crate) use job::resume_subject_memory_job;
 pub use job::run_subject_memory_job;
 pub use scopes::run_registered_subject_memory_scopes;
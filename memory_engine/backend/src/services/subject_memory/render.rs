@@ -32,6 +32,11 @@ pub(crate) fn subject_memory_to_rollup_block(item: &EngineSubjectMemory) -> Stri
     )
 }
 
+/// An existing memory shown to a rollup only so contradictions with it can be reported.
+pub(crate) fn subject_memory_to_reference_block(item: &EngineSubjectMemory) -> String {
+    format!("[reference]{}", subject_memory_to_rollup_block(item))
+}
+
 pub(crate) fn build_memory_metadata(
     memory_metadata: Option<Value>,
     relation_subject_id: &str,
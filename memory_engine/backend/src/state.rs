@@ -5,6 +5,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::ai::EmbeddingProvider;
 use crate::config::AppConfig;
 use crate::db::Db;
 use crate::models::MemoryEngineWorkerRuntimeStats;
@@ -47,6 +48,7 @@ pub struct AppState {
     pub rabbitmq_queue_inspector: RabbitMqQueueInspector,
     pub pressure: MemoryEnginePressureState,
     pub cloud_agent_store: CloudAgentStateStore,
+    pub embedding_provider: Option<Arc<dyn EmbeddingProvider>>,
 }
 
 #[cfg(test)]
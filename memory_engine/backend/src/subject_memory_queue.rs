@@ -21,7 +21,7 @@ use tracing::{info, warn};
 use crate::config::AppConfig;
 use crate::models::now_rfc3339;
 use crate::repositories::{control_plane, subject_memory_scopes, summaries, threads};
-use crate::services::subject_memory;
+use crate::services::{context, subject_memory};
 use crate::state::AppState;
 
 const SOURCE_AVAILABLE_EVENT: &str = "source_available";
@@ -519,6 +519,12 @@ async fn process_scope_event(
     }
 
     subject_memory::run_scope_once(&state.config, &state.pool, &scope).await?;
+    context::spawn_subject_embedding_index(
+        state,
+        scope.tenant_id.as_str(),
+        scope.source_id.as_str(),
+        scope.subject_id.as_str(),
+    );
     subject_memory_scopes::mark_subject_memory_dispatch_consumed(&state.pool, &event).await?;
     if subject_memory::scope_has_pending_work(&state.pool, &scope).await? {
         if let Some(next) = subject_memory_scopes::rearm_subject_memory_dispatch(
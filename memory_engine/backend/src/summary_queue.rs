@@ -21,7 +21,7 @@ use tracing::{info, warn};
 use crate::config::AppConfig;
 use crate::models::now_rfc3339;
 use crate::repositories::{control_plane, threads};
-use crate::services::summary;
+use crate::services::{context, summary};
 use crate::state::AppState;
 
 const SUMMARY_QUEUE_TRIGGER: &str = "queue";
@@ -369,7 +369,12 @@ async fn process_summary_event(
     )
     .await;
     match run_result {
-        Ok(_) => {}
+        Ok(_) => context::spawn_thread_embedding_index(
+            state,
+            envelope.tenant_id.as_str(),
+            envelope.source_id.as_str(),
+            envelope.thread_id.as_str(),
+        ),
         Err(error) if error.contains("summary slot already occupied") => {
             return Err(
                 crate::services::memory_cloud_agent::MEMORY_CLOUD_AGENT_DEFERRED.to_string(),
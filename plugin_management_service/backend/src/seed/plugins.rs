@@ -522,6 +522,7 @@ fn bundled_release(
         dependencies: PluginDependencySpec::default(),
         permissions: permissions.clone(),
         bundled_content_variant: Some("chatos-internal-skill-bundles-v2".to_string()),
+        wasm_modules: Vec::new(),
     };
     validate_plugin_manifest(&manifest).map_err(|err| err.to_string())?;
     let mut components = plugin_component_descriptors(&manifest);
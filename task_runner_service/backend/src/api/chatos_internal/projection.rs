@@ -372,6 +372,19 @@ fn project_plugin_hook_dispatch(payload: &Value) -> Value {
         &[("event", 128), ("snapshot_sha256", 64)],
     );
     copy_bool(source, &mut projected, "blocking_failure");
+    // Only the kind of decision is kept; its reason and any rewritten input may quote tool payloads.
+    if let Some(decision) = source.get("decision").and_then(Value::as_object) {
+        let mut item = serde_json::Map::new();
+        copy_bounded_string_fields(decision, &mut item, &[("decision", 16)]);
+        copy_bool(decision, &mut item, "user_approved");
+        projected.insert("decision".to_string(), Value::Object(item));
+    }
+    if source
+        .get("updated_input")
+        .is_some_and(|input| !input.is_null())
+    {
+        projected.insert("input_rewritten".to_string(), Value::Bool(true));
+    }
     if let Some(executions) = source.get("executions").and_then(Value::as_array) {
         let executions = executions
             .iter()
@@ -28,6 +28,8 @@ fn chatos_model_catalog_item_exposes_runtime_metadata_without_api_key() {
         request_cwd: None,
         include_prompt_cache_retention: false,
         request_body_limit_bytes: None,
+        pricing: None,
+        fallback_model_config_ids: Vec::new(),
         enabled: true,
         created_at: "2026-08-11T00:00:00Z".to_string(),
         updated_at: "2026-08-11T00:00:00Z".to_string(),
@@ -223,7 +225,15 @@ mod plugin_projection_tests {
                             "stdout_sha256": "b".repeat(64),
                             "stderr_sha256": "c".repeat(64),
                             "error": secret
-                        }]
+                        }],
+                        "decision": {
+                            "decision": "deny",
+                            "hook_id": "private-hook-id",
+                            "reason": secret,
+                            "user_approved": false
+                        },
+                        "updated_input": {"path": secret},
+                        "additional_context": [secret]
                     }
                 }),
             ),
@@ -319,6 +329,26 @@ mod plugin_projection_tests {
                 .and_then(|payload| payload.pointer("/hook_dispatch/executions/0/matched")),
             Some(&Value::Bool(true))
         );
+        assert_eq!(
+            events[0]
+                .payload
+                .as_ref()
+                .and_then(|payload| payload.get("hook_dispatch")),
+            Some(&json!({
+                "event": "PreToolUse",
+                "snapshot_sha256": "a".repeat(64),
+                "blocking_failure": false,
+                "decision": {"decision": "deny", "user_approved": false},
+                "input_rewritten": true,
+                "executions": [{
+                    "matched": true,
+                    "succeeded": false,
+                    "timed_out": false,
+                    "workspace_write": true,
+                    "workspace_write_approved": false
+                }]
+            }))
+        );
         assert_eq!(
             events[2]
                 .payload
@@ -449,6 +449,8 @@ fn model_config(id: &str, owner_user_id: Option<&str>, enabled: bool) -> ModelCo
         request_cwd: None,
         include_prompt_cache_retention: false,
         request_body_limit_bytes: None,
+        pricing: None,
+        fallback_model_config_ids: Vec::new(),
         enabled,
         created_at: "2026-01-01T00:00:00Z".to_string(),
         updated_at: "2026-01-01T00:00:00Z".to_string(),
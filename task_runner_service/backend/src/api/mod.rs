@@ -27,10 +27,10 @@ use crate::models::{
     HealthResponse, LoginRequest, LoginResponse, McpCatalogEntry, McpPromptPreviewRequest,
     McpPromptPreviewResponse, McpServerInfo, ModelCatalogResponse, ModelConfigRecord,
     ModelConfigTestResponse, ModelConfigUsageRecord, PaginatedResponse, PreviewModelCatalogRequest,
-    PromptListFilters, RecordTaskProcessRequest, RunListFilters, RunSummaryRecord,
-    SetTaskPrerequisitesRequest, SseTicketResponse, StartTaskRunRequest,
-    SubmitAskUserPromptRequest, SystemConfigResponse, TaskDependencyGraph, TaskIndexResponse,
-    TaskListFilters, TaskMcpResolutionResponse, TaskMemoryContextOptions,
+    PromptListFilters, RecordTaskProcessRequest, RunListFilters, RunSpendGroup,
+    RunSpendSummaryRecord, RunSummaryRecord, SetTaskPrerequisitesRequest, SseTicketResponse,
+    StartTaskRunRequest, SubmitAskUserPromptRequest, SystemConfigResponse, TaskDependencyGraph,
+    TaskIndexResponse, TaskListFilters, TaskMcpResolutionResponse, TaskMemoryContextOptions,
     TaskMemoryContextResponse, TaskMemoryRecordsOptions, TaskMemoryRecordsResponse,
     TaskMemorySummaryResponse, TaskProjectRecord, TaskProjectStatus, TaskRecord,
     TaskRunEventRecord, TaskRunRecord, TaskRunStatus, TaskRunnerInternalPromptPreviewResponse,
@@ -32,16 +32,17 @@ use super::models::{
     update_model_config,
 };
 use super::projects::{
-    create_project, delete_project, get_project, import_chatos_project, list_project_tasks,
-    list_projects, sync_get_project, sync_list_projects, update_project,
+    create_project, delete_project, get_project, get_project_spend, import_chatos_project,
+    list_project_tasks, list_projects, sync_get_project, sync_list_projects, update_project,
 };
 use super::prompts::{
     cancel_prompt, get_prompt, list_prompt_task_counts, list_prompts, list_prompts_page,
     list_run_prompts, submit_prompt,
 };
 use super::runs::{
-    cancel_run, get_run, get_run_workspace_changes, get_run_workspace_integration, list_run_events,
-    list_run_index, list_run_summaries, list_runs, list_runs_page, list_task_runs, retry_run,
+    cancel_run, get_run, get_run_workspace_changes, get_run_workspace_integration,
+    hand_off_run_workspace_conflict, list_run_events, list_run_index, list_run_spend,
+    list_run_summaries, list_runs, list_runs_page, list_task_runs, retry_run,
     retry_run_workspace_integration, start_task_run, stream_run_events,
     waive_run_workspace_integration,
 };
@@ -79,6 +80,7 @@ pub fn build_public_router(state: AppState) -> Router {
                 .delete(delete_project),
         )
         .route("/api/projects/{id}/tasks", get(list_project_tasks))
+        .route("/api/projects/{id}/spend", get(get_project_spend))
         .route("/api/tasks", get(list_tasks).post(create_task))
         .route("/api/tasks/summaries", get(list_task_summaries))
         .route("/api/tasks/page", get(list_tasks_page))
@@ -144,6 +146,7 @@ pub fn build_public_router(state: AppState) -> Router {
         .route("/api/model-configs/usage", get(list_model_config_usage))
         .route("/api/runs", get(list_runs))
         .route("/api/runs/summaries", get(list_run_summaries))
+        .route("/api/runs/spend", get(list_run_spend))
         .route("/api/runs/page", get(list_runs_page))
         .route("/api/runs/index", get(list_run_index))
         .route("/api/runs/{id}", get(get_run))
@@ -165,6 +168,10 @@ pub fn build_public_router(state: AppState) -> Router {
             "/api/runs/{id}/integration/waive",
             post(waive_run_workspace_integration),
         )
+        .route(
+            "/api/runs/{id}/integration/handoff",
+            post(hand_off_run_workspace_conflict),
+        )
         .route(
             "/api/queue-operations/run-post-process/replay",
             post(replay_run_post_process),
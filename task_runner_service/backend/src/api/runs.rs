@@ -8,10 +8,11 @@ mod listing;
 mod streaming;
 
 pub(in crate::api) use self::lifecycle::{
-    cancel_run, get_run, get_run_workspace_changes, get_run_workspace_integration, list_run_events,
-    retry_run, retry_run_workspace_integration, start_task_run, waive_run_workspace_integration,
+    cancel_run, get_run, get_run_workspace_changes, get_run_workspace_integration,
+    hand_off_run_workspace_conflict, list_run_events, retry_run, retry_run_workspace_integration,
+    start_task_run, waive_run_workspace_integration,
 };
 pub(in crate::api) use self::listing::{
-    list_run_index, list_run_summaries, list_runs, list_runs_page, list_task_runs,
+    list_run_index, list_run_spend, list_run_summaries, list_runs, list_runs_page, list_task_runs,
 };
 pub(in crate::api) use self::streaming::stream_run_events;
@@ -2,13 +2,20 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 use super::*;
-use crate::models::TaskRunWorkspaceExecution;
+use crate::models::{TaskRunWorkspaceExecution, WorkspaceConflictResolver};
 
 #[derive(Debug, Deserialize)]
 pub(in crate::api) struct WaiveRunWorkspaceIntegrationRequest {
     reason: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub(in crate::api) struct HandOffRunWorkspaceConflictRequest {
+    resolver: WorkspaceConflictResolver,
+    #[serde(default)]
+    instructions: Option<String>,
+}
+
 pub(in crate::api) async fn start_task_run(
     Path(id): Path<String>,
     State(state): State<AppState>,
@@ -215,3 +222,25 @@ pub(in crate::api) async fn waive_run_workspace_integration(
         .ok_or_else(|| ApiError::conflict("当前运行没有可放弃的代码集成冲突"))?;
     Ok(Json(redact_workspace_paths(&state, run)?))
 }
+
+pub(in crate::api) async fn hand_off_run_workspace_conflict(
+    Path(id): Path<String>,
+    State(state): State<AppState>,
+    Extension(current_user): Extension<CurrentUser>,
+    Json(request): Json<HandOffRunWorkspaceConflictRequest>,
+) -> Result<Json<TaskRunRecord>, ApiError> {
+    let existing = state
+        .run_service
+        .get_run(&id)
+        .await
+        .map_err(ApiError::bad_request)?
+        .ok_or_else(|| ApiError::not_found(format!("运行记录不存在: {id}")))?;
+    ensure_run_access(&state, &existing, &current_user).await?;
+    let run = state
+        .run_service
+        .hand_off_run_workspace_conflict(&id, request.resolver, request.instructions.as_deref())
+        .await
+        .map_err(ApiError::bad_request)?
+        .ok_or_else(|| ApiError::conflict("当前运行没有可移交的代码集成冲突"))?;
+    Ok(Json(redact_workspace_paths(&state, run)?))
+}
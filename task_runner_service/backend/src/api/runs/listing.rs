@@ -139,6 +139,52 @@ pub(in crate::api) async fn list_run_summaries(
     )))
 }
 
+#[derive(Debug, Default, Deserialize)]
+pub(in crate::api) struct RunSpendQuery {
+    #[serde(default)]
+    group: RunSpendGroup,
+}
+
+pub(in crate::api) async fn list_run_spend(
+    State(state): State<AppState>,
+    Extension(current_user): Extension<CurrentUser>,
+    Query(query): Query<RunSpendQuery>,
+) -> Result<Json<Vec<RunSpendSummaryRecord>>, ApiError> {
+    let spend = state
+        .run_service
+        .list_run_spend(query.group)
+        .await
+        .map_err(ApiError::bad_request)?;
+    if current_user.is_admin() {
+        return Ok(Json(spend));
+    }
+    let visible_scope_ids = match query.group {
+        RunSpendGroup::Owner => HashSet::from([effective_owner_user_id(&current_user)?]),
+        RunSpendGroup::Project => state
+            .task_project_service
+            .list_projects_for_user(&current_user)
+            .await
+            .map_err(ApiError::bad_request)?
+            .into_iter()
+            .filter(|project| {
+                project.id == PUBLIC_PROJECT_ID
+                    || owned_resource_visible_to_user(
+                        project.owner_user_id.as_deref(),
+                        &current_user,
+                    )
+                    .unwrap_or(false)
+            })
+            .map(|project| project.id)
+            .collect(),
+    };
+    Ok(Json(
+        spend
+            .into_iter()
+            .filter(|summary| visible_scope_ids.contains(&summary.scope_id))
+            .collect(),
+    ))
+}
+
 impl RunListQuery {
     fn into_filters(self) -> RunListFilters {
         RunListFilters {
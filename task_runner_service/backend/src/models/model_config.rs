@@ -2,7 +2,7 @@
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
 use chatos_ai_runtime::model_config::{normalize_provider, normalize_thinking_level};
-use chatos_ai_runtime::ModelRuntimeConfig;
+use chatos_ai_runtime::{ModelPricing, ModelRuntimeConfig};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -44,6 +44,12 @@ pub struct ModelConfigRecord {
     pub request_cwd: Option<String>,
     pub include_prompt_cache_retention: bool,
     pub request_body_limit_bytes: Option<usize>,
+    #[serde(default)]
+    pub pricing: Option<ModelPricing>,
+    /// Model configs tried in order when this one is overloaded, rate limited or has no
+    /// upstream auth. Their own fallbacks are not followed.
+    #[serde(default)]
+    pub fallback_model_config_ids: Vec<String>,
     pub enabled: bool,
     pub created_at: String,
     pub updated_at: String,
@@ -71,6 +77,7 @@ impl ModelConfigRecord {
         .with_prompt_cache_retention(self.include_prompt_cache_retention)
         .with_request_body_limit_bytes(self.request_body_limit_bytes)
         .with_max_transient_retries(Some(self.model_request_max_retries))
+        .with_pricing(self.pricing.clone())
     }
 }
 
@@ -151,6 +158,8 @@ mod tests {
             request_cwd: None,
             include_prompt_cache_retention: false,
             request_body_limit_bytes: None,
+            pricing: None,
+            fallback_model_config_ids: Vec::new(),
             enabled: true,
             created_at: "2026-01-01T00:00:00Z".to_string(),
             updated_at: "2026-01-01T00:00:00Z".to_string(),
@@ -175,6 +184,10 @@ pub struct CreateModelConfigRequest {
     pub request_cwd: Option<String>,
     pub include_prompt_cache_retention: Option<bool>,
     pub request_body_limit_bytes: Option<usize>,
+    #[serde(default)]
+    pub pricing: Option<ModelPricing>,
+    #[serde(default)]
+    pub fallback_model_config_ids: Vec<String>,
     pub enabled: Option<bool>,
 }
 
@@ -199,6 +212,8 @@ pub struct ChatosSyncedModelConfigRequest {
     #[serde(default)]
     pub supports_reasoning: Option<bool>,
     pub supports_responses: Option<bool>,
+    #[serde(default)]
+    pub pricing: Option<ModelPricing>,
     pub enabled: Option<bool>,
 }
 
@@ -219,6 +234,10 @@ pub struct UpdateModelConfigRequest {
     pub request_cwd: Option<String>,
     pub include_prompt_cache_retention: Option<bool>,
     pub request_body_limit_bytes: Option<usize>,
+    #[serde(default)]
+    pub pricing: Option<ModelPricing>,
+    #[serde(default)]
+    pub fallback_model_config_ids: Option<Vec<String>>,
     pub enabled: Option<bool>,
 }
 
@@ -1,6 +1,7 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
+use chatos_ai_runtime::UsageTotals;
 use chatos_mcp::{AskUserPromptPayload, AskUserResponseSubmission};
 use chatos_mcp_management_sdk::RuntimeWorkspaceRouteTarget;
 use serde::{Deserialize, Serialize};
@@ -133,6 +134,36 @@ pub enum WorkspaceIntegrationStatus {
     Failed,
 }
 
+/// Who takes over when the run's branch cannot be rebased onto the execution
+/// branch.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WorkspaceConflictResolver {
+    /// A follow-up task rebases the run's changes and resolves the conflict.
+    Agent,
+    /// The user resolves the conflict on the run branch and retries.
+    User,
+}
+
+impl WorkspaceConflictResolver {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Agent => "agent",
+            Self::User => "user",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaskRunConflictHandoff {
+    pub resolver: WorkspaceConflictResolver,
+    pub requested_at: String,
+    #[serde(default)]
+    pub instructions: Option<String>,
+    #[serde(default)]
+    pub resolution_task_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum TaskRunBranchTarget {
@@ -201,6 +232,8 @@ pub struct TaskRunWorkspaceExecution {
     #[serde(default)]
     pub conflict_message: Option<String>,
     #[serde(default)]
+    pub conflict_handoff: Option<TaskRunConflictHandoff>,
+    #[serde(default)]
     pub integration_last_error: Option<String>,
     #[serde(default)]
     pub prepared_at: Option<String>,
@@ -276,6 +309,8 @@ pub struct TaskRunRecord {
     pub result_summary: Option<String>,
     pub error_message: Option<String>,
     pub usage: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spend: Option<TaskRunSpendRecord>,
     pub report: Option<Value>,
     pub cancel_requested: bool,
     #[serde(default)]
@@ -319,6 +354,33 @@ pub struct TaskRunRecord {
     pub updated_at: String,
 }
 
+/// Model usage accumulated by one run. The project and owner are copied from
+/// the task when the first step is recorded so spend can be grouped without
+/// joining task records.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TaskRunSpendRecord {
+    pub project_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_user_id: Option<String>,
+    #[serde(default)]
+    pub usage: UsageTotals,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RunSpendGroup {
+    #[default]
+    Project,
+    Owner,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RunSpendSummaryRecord {
+    pub scope_id: String,
+    pub run_count: usize,
+    pub usage: UsageTotals,
+}
+
 impl TaskRunRecord {
     pub fn is_waiting_for_workspace_integration(&self) -> bool {
         self.status == TaskRunStatus::Running
@@ -391,6 +453,7 @@ impl TaskRunRecord {
             result_summary: None,
             error_message: None,
             usage: None,
+            spend: None,
             report: None,
             cancel_requested: false,
             cancel_event_pending: false,
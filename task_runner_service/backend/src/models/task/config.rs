@@ -97,6 +97,9 @@ pub struct TaskMcpConfig {
     pub requires_execution: bool,
     #[serde(default = "task_workspace_changes_required_default")]
     pub workspace_changes_required: bool,
+    /// Hands integration conflicts over instead of leaving the run blocked.
+    #[serde(default)]
+    pub integration_conflict_resolver: Option<crate::models::WorkspaceConflictResolver>,
     #[serde(default)]
     pub execution_service_id: Option<String>,
     #[serde(default)]
@@ -120,6 +123,7 @@ impl Default for TaskMcpConfig {
             workspace_dir: None,
             requires_execution: task_requires_execution_default(),
             workspace_changes_required: task_workspace_changes_required_default(),
+            integration_conflict_resolver: None,
             execution_service_id: None,
             external_mcp_config_ids: Vec::new(),
             selected_skill_ids: Vec::new(),
@@ -175,9 +179,35 @@ pub enum TaskScheduleMode {
     Manual,
     Once,
     Interval,
+    Cron,
     ContactAsync,
 }
 
+/// What the scheduler does with slots that passed while it was not running.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskScheduleMissedSlotPolicy {
+    /// Drop missed slots and wait for the next future one.
+    Skip,
+    /// Run once for all missed slots, then continue from the next future one.
+    #[default]
+    RunOnce,
+    /// Run every missed slot in order, bounded to the most recent ones.
+    CatchUp,
+}
+
+/// A recurring local-time window in which scheduled runs must not start.
+/// `start` and `end` are `HH:MM` in the schedule time zone; `end` may be
+/// `24:00`, and a window whose end is before its start runs past midnight.
+/// `weekdays` lists the days the window starts on; empty means every day.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TaskScheduleBlackoutWindow {
+    #[serde(default)]
+    pub weekdays: Vec<String>,
+    pub start: String,
+    pub end: String,
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct TaskScheduleConfig {
     #[serde(default)]
@@ -190,6 +220,22 @@ pub struct TaskScheduleConfig {
     pub next_run_at: Option<String>,
     #[serde(default)]
     pub last_scheduled_at: Option<String>,
+    /// Five-field cron expression (or `@daily` style alias) for `cron` mode.
+    #[serde(default)]
+    pub cron_expression: Option<String>,
+    /// IANA time zone used for cron fields and blackout windows; UTC if unset.
+    #[serde(default)]
+    pub timezone: Option<String>,
+    #[serde(default)]
+    pub blackout_windows: Vec<TaskScheduleBlackoutWindow>,
+    /// Random delay of up to this many seconds added to each slot.
+    #[serde(default)]
+    pub jitter_seconds: Option<i64>,
+    #[serde(default)]
+    pub missed_slot_policy: TaskScheduleMissedSlotPolicy,
+    /// The slot behind `next_run_at` before jitter was applied.
+    #[serde(default)]
+    pub slot_at: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -11,6 +11,8 @@ pub struct TaskMcpRequestConfig {
     #[serde(default)]
     pub workspace_changes_required: Option<bool>,
     #[serde(default)]
+    pub integration_conflict_resolver: Option<crate::models::WorkspaceConflictResolver>,
+    #[serde(default)]
     pub enabled_builtin_kinds: Vec<String>,
     #[serde(default)]
     pub external_mcp_config_ids: Vec<String>,
@@ -44,6 +44,7 @@ mod chatos_async_dispatch;
 mod chatos_callbacks;
 mod chatos_message_tasks;
 mod filter_sanitize;
+mod integration_conflict;
 mod managed_config;
 #[path = "services/tool_runtime/mcp_catalog_service.rs"]
 mod mcp_catalog_service;
@@ -69,7 +70,9 @@ mod run_post_process;
 mod run_prerequisites;
 mod run_recovery;
 mod run_service;
+mod run_spend;
 mod schedule_helpers;
+mod schedule_rules;
 mod status_display;
 mod stream_events;
 mod task_dependencies;
@@ -102,21 +105,30 @@ pub use self::chatos_message_tasks::{
 pub(crate) use self::filter_sanitize::sanitize_prompt_list_filters;
 use self::filter_sanitize::{sanitize_run_list_filters, sanitize_task_list_filters};
 use self::managed_config::{
-    load_managed_config_snapshot, require_managed_string, require_managed_string_map,
-    require_managed_string_set, require_managed_u64, require_managed_usize,
-    TASK_RUNNER_AI_READ_TIMEOUT_CONFIG_KEY, TASK_RUNNER_EXECUTION_TIMEOUT_CONFIG_KEY,
+    load_managed_config_snapshot, optional_managed_string, optional_managed_string_set,
+    optional_managed_usd, optional_managed_usd_map, require_managed_string,
+    require_managed_string_map, require_managed_string_set, require_managed_u64,
+    require_managed_usize, TASK_RUNNER_AI_READ_TIMEOUT_CONFIG_KEY,
+    TASK_RUNNER_EXECUTION_TIMEOUT_CONFIG_KEY, TASK_RUNNER_SPEND_PROJECT_BUDGETS_USD_CONFIG_KEY,
+    TASK_RUNNER_SPEND_RUN_BUDGET_USD_CONFIG_KEY,
     TASK_RUNNER_SUPPLY_CHAIN_BASELINE_REVISION_CONFIG_KEY,
+    TASK_RUNNER_SUPPLY_CHAIN_BLOCKING_ECOSYSTEMS_CONFIG_KEY,
+    TASK_RUNNER_SUPPLY_CHAIN_CARGO_BUILD_SCRIPT_ALLOWLIST_CONFIG_KEY,
     TASK_RUNNER_SUPPLY_CHAIN_INSTALL_SCRIPT_ALLOWLIST_CONFIG_KEY,
     TASK_RUNNER_SUPPLY_CHAIN_NODE_AUDIT_LEVEL_CONFIG_KEY,
     TASK_RUNNER_SUPPLY_CHAIN_NODE_AUDIT_REGISTRY_CONFIG_KEY,
     TASK_RUNNER_SUPPLY_CHAIN_NODE_DEPENDENCY_REQUIREMENTS_CONFIG_KEY,
     TASK_RUNNER_SUPPLY_CHAIN_NODE_INSTALL_REGISTRY_CONFIG_KEY,
+    TASK_RUNNER_SUPPLY_CHAIN_PYTHON_INDEX_URL_CONFIG_KEY,
+    TASK_RUNNER_SUPPLY_CHAIN_PYTHON_SOURCE_BUILD_ALLOWLIST_CONFIG_KEY,
     TASK_RUNNER_TOOL_RESULTS_TOTAL_MAX_CHARS_CONFIG_KEY,
     TASK_RUNNER_TOOL_RESULT_MAX_CHARS_CONFIG_KEY,
 };
 pub(crate) use self::plugin_management_policy::TaskRunnerCapabilityPolicy;
 use self::process_log_text::apply_task_process_log_update;
-use self::schedule_helpers::{advance_task_schedule_after_dispatch, sanitize_task_schedule_config};
+use self::schedule_helpers::{
+    advance_task_schedule_after_dispatch, defer_due_task_schedule, sanitize_task_schedule_config,
+};
 use self::status_display::{TaskScheduleModeExt, TaskStatusExt};
 use self::task_tenant_scope::{
     align_task_tenant_to_owner, resolve_task_tenant_id, save_task_if_tenant_aligned,
@@ -177,6 +189,7 @@ pub struct RunService {
     callback_delivery_locks: Arc<KeyedAsyncLockRegistry>,
     runtime_abort_tokens:
         Arc<parking_lot::Mutex<HashMap<String, tokio_util::sync::CancellationToken>>>,
+    ai_cassette: Option<Arc<chatos_ai_runtime::AiCassette>>,
 }
 
 impl RunService {
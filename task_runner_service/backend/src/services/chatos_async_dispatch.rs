@@ -67,6 +67,7 @@ impl RunService {
                     // from bypassing that dependency gate.
                     next_run_at: None,
                     last_scheduled_at: task.schedule.last_scheduled_at.clone(),
+                    ..TaskScheduleConfig::default()
                 };
                 task.updated_at = now_rfc3339();
                 activated_tasks.push(self.store.save_task(task).await?);
@@ -338,6 +339,8 @@ mod tests {
             request_cwd: None,
             include_prompt_cache_retention: false,
             request_body_limit_bytes: None,
+            pricing: None,
+            fallback_model_config_ids: Vec::new(),
             enabled: true,
             created_at: now.clone(),
             updated_at: now,
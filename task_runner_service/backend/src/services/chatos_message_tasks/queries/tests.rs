@@ -118,6 +118,7 @@ fn failed_run_for_task(task: &TaskRecord, run_id: &str) -> TaskRunRecord {
         result_summary: Some("run failed".to_string()),
         error_message: Some("boom".to_string()),
         usage: None,
+        spend: None,
         report: None,
         cancel_requested: false,
         cancel_event_pending: false,
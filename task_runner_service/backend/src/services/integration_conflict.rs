@@ -0,0 +1,322 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use serde_json::{json, Map, Value};
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use crate::models::{
+    now_rfc3339, TaskMcpConfig, TaskRecord, TaskRunBranchTarget, TaskRunConflictHandoff,
+    TaskRunEventRecord, TaskRunRecord, TaskRunStatus, TaskStatus, WorkspaceConflictResolver,
+    WorkspaceIntegrationStatus,
+};
+
+use super::verification_repair::contact_async_schedule;
+use super::RunService;
+
+const CONFLICT_RESOLUTION_ORIGIN_RUN_ID_KEY: &str = "conflict_resolution_origin_run_id";
+const CONFLICT_HANDOFF_INSTRUCTIONS_MAX_CHARS: usize = 4_000;
+
+impl RunService {
+    /// Hands a blocked integration conflict to a follow-up agent task or to
+    /// the user. The Run stays blocked until the resolution lands; the agent
+    /// path waives it automatically once the resolution task integrates.
+    pub async fn hand_off_run_workspace_conflict(
+        &self,
+        run_id: &str,
+        resolver: WorkspaceConflictResolver,
+        instructions: Option<&str>,
+    ) -> Result<Option<TaskRunRecord>, String> {
+        let instructions = instructions
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToOwned::to_owned);
+        if instructions
+            .as_deref()
+            .is_some_and(|value| value.chars().count() > CONFLICT_HANDOFF_INSTRUCTIONS_MAX_CHARS)
+        {
+            return Err(format!(
+                "冲突处理说明不能超过 {CONFLICT_HANDOFF_INSTRUCTIONS_MAX_CHARS} 个字符"
+            ));
+        }
+        let Some(existing) = self.store.get_run(run_id).await? else {
+            return Ok(None);
+        };
+        let execution = existing
+            .workspace_execution
+            .as_ref()
+            .filter(|execution| {
+                existing.status == TaskRunStatus::Blocked
+                    && execution.integration_status == WorkspaceIntegrationStatus::Conflict
+            })
+            .ok_or_else(|| "当前运行没有可移交的代码集成冲突".to_string())?;
+        if execution.conflict_handoff.is_some() {
+            return Err("当前运行的代码集成冲突已经移交处理".to_string());
+        }
+        let task = self
+            .store
+            .get_task(existing.task_id.as_str())
+            .await?
+            .ok_or_else(|| format!("Task not found for conflict handoff: {}", existing.task_id))?;
+
+        let now = now_rfc3339();
+        let resolution = (resolver == WorkspaceConflictResolver::Agent).then(|| {
+            build_conflict_resolution_task(&task, &existing, instructions.as_deref(), &now)
+        });
+        let handoff = TaskRunConflictHandoff {
+            resolver,
+            requested_at: now,
+            instructions,
+            resolution_task_id: resolution.as_ref().map(|task| task.id.clone()),
+        };
+        // The resolution task is saved before the handoff points at it, so a
+        // failed save never leaves the Run handed off to a missing task.
+        let resolution = match resolution {
+            Some(resolution) => Some(self.store.save_task(resolution).await?),
+            None => None,
+        };
+        let Some(run) = self
+            .store
+            .record_run_conflict_handoff(run_id, &handoff)
+            .await?
+        else {
+            if let Some(resolution) = resolution.as_ref() {
+                if let Err(error) = self.store.delete_task(resolution.id.as_str()).await {
+                    warn!(
+                        run_id,
+                        resolution_task_id = resolution.id.as_str(),
+                        error = error.as_str(),
+                        "failed to delete the unused conflict resolution task"
+                    );
+                }
+            }
+            return Err("运行的代码集成状态已变化，请刷新后重试".to_string());
+        };
+
+        let mut dispatched = Vec::new();
+        if let Some(resolution) = resolution.as_ref() {
+            dispatched = self
+                .dispatch_ready_chatos_async_tasks_for_source_task(resolution)
+                .await?;
+        }
+        let execution = run.workspace_execution.as_ref();
+        let run_branch_ref = execution.and_then(run_branch_ref);
+        let execution_branch_ref =
+            execution.and_then(|execution| execution.execution_branch_ref.clone());
+        let message = match resolver {
+            WorkspaceConflictResolver::Agent => "代码集成冲突已移交给冲突解决任务".to_string(),
+            WorkspaceConflictResolver::User => format!(
+                "代码集成冲突已移交给用户：请在运行分支 {} 上基于 {} 解决冲突并推送，然后重新集成",
+                run_branch_ref.as_deref().unwrap_or("-"),
+                execution_branch_ref.as_deref().unwrap_or("-"),
+            ),
+        };
+        self.store
+            .append_run_event(TaskRunEventRecord::new(
+                run.id.clone(),
+                "integration_conflict_handed_off",
+                Some(message),
+                Some(json!({
+                    "resolver": resolver.as_str(),
+                    "resolution_task_id": handoff.resolution_task_id,
+                    "auto_started_run_ids": dispatched.iter().map(|run| run.id.as_str()).collect::<Vec<_>>(),
+                    "conflict_files": execution.map(|execution| execution.conflict_files.clone()),
+                    "run_branch_ref": run_branch_ref,
+                    "execution_branch_ref": execution_branch_ref,
+                    "result_commit": execution.and_then(|execution| execution.result_commit.clone()),
+                })),
+            ))
+            .await?;
+        info!(
+            run_id,
+            resolver = resolver.as_str(),
+            resolution_task_id = handoff.resolution_task_id.as_deref(),
+            "handed off Task Run integration conflict"
+        );
+        Ok(Some(run))
+    }
+
+    /// Applies the resolver frozen into the Run's MCP config right after the
+    /// integration step reports a conflict.
+    pub(in crate::services) async fn hand_off_conflict_if_configured(
+        &self,
+        run: &TaskRunRecord,
+    ) -> Result<(), String> {
+        let Some(resolver) = frozen_conflict_resolver(run) else {
+            return Ok(());
+        };
+        if run
+            .workspace_execution
+            .as_ref()
+            .is_some_and(|execution| execution.conflict_handoff.is_some())
+        {
+            return Ok(());
+        }
+        if let Err(error) = self
+            .hand_off_run_workspace_conflict(run.id.as_str(), resolver, None)
+            .await
+        {
+            warn!(
+                run_id = run.id.as_str(),
+                error = error.as_str(),
+                "automatic integration conflict handoff failed; the Run stays blocked"
+            );
+        }
+        Ok(())
+    }
+
+    /// Once a conflict resolution task has integrated, its origin Run no
+    /// longer needs its own integration and is completed as waived.
+    pub(in crate::services) async fn complete_handed_off_conflict(
+        &self,
+        task: &TaskRecord,
+        run: &TaskRunRecord,
+    ) -> Result<(), String> {
+        let Some(origin_run_id) = task
+            .input_payload
+            .as_ref()
+            .and_then(|payload| payload.get(CONFLICT_RESOLUTION_ORIGIN_RUN_ID_KEY))
+            .and_then(Value::as_str)
+        else {
+            return Ok(());
+        };
+        if run.status != TaskRunStatus::Succeeded {
+            return Ok(());
+        }
+        let Some(origin) = self.store.get_run(origin_run_id).await? else {
+            return Ok(());
+        };
+        let handed_off_to_task = origin
+            .workspace_execution
+            .as_ref()
+            .and_then(|execution| execution.conflict_handoff.as_ref())
+            .is_some_and(|handoff| handoff.resolution_task_id.as_deref() == Some(task.id.as_str()));
+        if !handed_off_to_task {
+            return Ok(());
+        }
+        let Some(mut origin_task) = self.store.get_task(origin.task_id.as_str()).await? else {
+            return Ok(());
+        };
+        let reason = format!("代码集成冲突已由任务 {} 解决", task.id);
+        let Some(origin) = self
+            .store
+            .waive_run_workspace_integration(origin_run_id, reason.as_str())
+            .await?
+        else {
+            return Ok(());
+        };
+        let event = TaskRunEventRecord::new(
+            origin.id.clone(),
+            "integration_conflict_resolved",
+            Some(reason),
+            Some(json!({
+                "resolution_task_id": task.id,
+                "resolution_run_id": run.id,
+                "integrated_commit": run.workspace_execution.as_ref()
+                    .and_then(|execution| execution.integrated_commit.clone()),
+            })),
+        );
+        self.finish_integration_waiver(&mut origin_task, origin, event)
+            .await?;
+        Ok(())
+    }
+}
+
+fn frozen_conflict_resolver(run: &TaskRunRecord) -> Option<WorkspaceConflictResolver> {
+    run.input_snapshot
+        .get("mcp_config")
+        .cloned()
+        .and_then(|value| serde_json::from_value::<TaskMcpConfig>(value).ok())
+        .and_then(|config| config.integration_conflict_resolver)
+}
+
+fn run_branch_ref(execution: &crate::models::TaskRunWorkspaceExecution) -> Option<String> {
+    match execution.branch_target.as_ref()? {
+        TaskRunBranchTarget::Run { branch_ref, .. } => Some(branch_ref.clone()),
+        TaskRunBranchTarget::Default { branch_ref } => Some(branch_ref.clone()),
+        TaskRunBranchTarget::Local => None,
+    }
+}
+
+fn build_conflict_resolution_task(
+    task: &TaskRecord,
+    run: &TaskRunRecord,
+    instructions: Option<&str>,
+    now: &str,
+) -> TaskRecord {
+    let execution = run.workspace_execution.as_ref();
+    let conflict_files = execution
+        .map(|execution| execution.conflict_files.clone())
+        .unwrap_or_default();
+    let mut resolution = task.clone();
+    let resolution_id = Uuid::new_v4().to_string();
+    resolution.id = resolution_id.clone();
+    resolution.title = format!("解决集成冲突：{}", task.title);
+    resolution.description = Some(format!(
+        "将运行 {} 的代码变更重新应用到执行批次分支，并解决集成冲突。",
+        run.id
+    ));
+    let mut objective = format!(
+        "运行 {} 的代码变更（分支 {}，提交 {}）无法集成到执行批次分支 {}。冲突文件：{}。请基于当前执行批次分支重新应用这些变更，解决冲突时同时保留双方的意图，保证原任务目标“{}”仍然达成，并运行相关构建和测试。",
+        run.id,
+        execution
+            .and_then(run_branch_ref)
+            .unwrap_or_else(|| "-".to_string()),
+        execution
+            .and_then(|execution| execution.result_commit.as_deref())
+            .unwrap_or("-"),
+        execution
+            .and_then(|execution| execution.execution_branch_ref.as_deref())
+            .unwrap_or("-"),
+        if conflict_files.is_empty() {
+            "未知".to_string()
+        } else {
+            conflict_files.join("、")
+        },
+        task.objective,
+    );
+    if let Some(instructions) = instructions {
+        objective.push_str("补充说明：");
+        objective.push_str(instructions);
+    }
+    resolution.objective = objective;
+    let mut payload = task
+        .input_payload
+        .as_ref()
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_else(Map::new);
+    payload.insert(
+        CONFLICT_RESOLUTION_ORIGIN_RUN_ID_KEY.to_string(),
+        Value::String(run.id.clone()),
+    );
+    payload.insert(
+        "conflict_files".to_string(),
+        Value::Array(conflict_files.into_iter().map(Value::String).collect()),
+    );
+    if let Some(execution_group_id) =
+        execution.and_then(|execution| execution.execution_group_id.clone())
+    {
+        payload.insert(
+            "execution_group_id".to_string(),
+            Value::String(execution_group_id),
+        );
+    }
+    resolution.input_payload = Some(Value::Object(payload));
+    resolution.status = TaskStatus::Ready;
+    resolution.memory_thread_id = format!("task-{resolution_id}");
+    resolution.result_summary = None;
+    resolution.process_log = None;
+    resolution.last_run_id = None;
+    resolution.schedule = contact_async_schedule(now);
+    resolution.prerequisite_task_ids = Vec::new();
+    resolution.task_tool_state = Default::default();
+    resolution.task_tool_state.idempotency_key = Some(format!("integration-conflict:{}", run.id));
+    resolution.mcp_config.workspace_changes_required = true;
+    // A resolution that conflicts again stays blocked for a person to look at.
+    resolution.mcp_config.integration_conflict_resolver = None;
+    resolution.created_at = now.to_string();
+    resolution.updated_at = now.to_string();
+    resolution.deleted_at = None;
+    resolution
+}
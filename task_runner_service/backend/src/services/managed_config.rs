@@ -25,6 +25,18 @@ pub(super) const TASK_RUNNER_SUPPLY_CHAIN_NODE_INSTALL_REGISTRY_CONFIG_KEY: &str
     "task_runner.supply_chain.node_install_registry";
 pub(super) const TASK_RUNNER_SUPPLY_CHAIN_NODE_AUDIT_REGISTRY_CONFIG_KEY: &str =
     "task_runner.supply_chain.node_audit_registry";
+pub(super) const TASK_RUNNER_SUPPLY_CHAIN_CARGO_BUILD_SCRIPT_ALLOWLIST_CONFIG_KEY: &str =
+    "task_runner.supply_chain.cargo_build_script_allowlist";
+pub(super) const TASK_RUNNER_SUPPLY_CHAIN_PYTHON_INDEX_URL_CONFIG_KEY: &str =
+    "task_runner.supply_chain.python_index_url";
+pub(super) const TASK_RUNNER_SUPPLY_CHAIN_PYTHON_SOURCE_BUILD_ALLOWLIST_CONFIG_KEY: &str =
+    "task_runner.supply_chain.python_source_build_allowlist";
+pub(super) const TASK_RUNNER_SUPPLY_CHAIN_BLOCKING_ECOSYSTEMS_CONFIG_KEY: &str =
+    "task_runner.supply_chain.blocking_ecosystems";
+pub(super) const TASK_RUNNER_SPEND_RUN_BUDGET_USD_CONFIG_KEY: &str =
+    "task_runner.spend.run_budget_usd";
+pub(super) const TASK_RUNNER_SPEND_PROJECT_BUDGETS_USD_CONFIG_KEY: &str =
+    "task_runner.spend.project_budgets_usd";
 
 #[cfg(not(test))]
 fn managed_config_client() -> Result<&'static chatos_config_sdk::ConfigClient, String> {
@@ -202,6 +214,31 @@ pub(super) fn require_managed_string_set(
         .collect()
 }
 
+/// Ecosystem supply-chain settings are opt-in, so a missing or null key means
+/// an empty value.
+pub(super) fn optional_managed_string(
+    snapshot: &chatos_config_sdk::ConfigSnapshot,
+    key: &str,
+) -> Result<String, String> {
+    match snapshot.values.get(key) {
+        None | Some(Value::Null) => Ok(String::new()),
+        Some(value) => value
+            .as_str()
+            .map(|value| value.trim().to_string())
+            .ok_or_else(|| format!("invalid managed configuration key {key}")),
+    }
+}
+
+pub(super) fn optional_managed_string_set(
+    snapshot: &chatos_config_sdk::ConfigSnapshot,
+    key: &str,
+) -> Result<std::collections::BTreeSet<String>, String> {
+    match snapshot.values.get(key) {
+        None | Some(Value::Null) => Ok(Default::default()),
+        Some(_) => require_managed_string_set(snapshot, key),
+    }
+}
+
 pub(super) fn require_managed_string_map(
     snapshot: &chatos_config_sdk::ConfigSnapshot,
     key: &str,
@@ -236,3 +273,47 @@ pub(super) fn require_managed_string_map(
         })
         .collect()
 }
+
+/// Spend budgets are opt-in, so a missing or null key means no budget.
+pub(super) fn optional_managed_usd(
+    snapshot: &chatos_config_sdk::ConfigSnapshot,
+    key: &str,
+) -> Result<Option<f64>, String> {
+    match snapshot.values.get(key) {
+        None | Some(Value::Null) => Ok(None),
+        Some(value) => managed_usd_value(key, value).map(Some),
+    }
+}
+
+pub(super) fn optional_managed_usd_map(
+    snapshot: &chatos_config_sdk::ConfigSnapshot,
+    key: &str,
+) -> Result<std::collections::BTreeMap<String, f64>, String> {
+    let values = match snapshot.values.get(key) {
+        None | Some(Value::Null) => return Ok(Default::default()),
+        Some(value) => value
+            .as_object()
+            .ok_or_else(|| format!("invalid managed configuration key {key}"))?,
+    };
+    values
+        .iter()
+        .map(|(name, value)| {
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(format!(
+                    "managed configuration key {key} contains an empty project id"
+                ));
+            }
+            Ok((name.to_string(), managed_usd_value(key, value)?))
+        })
+        .collect()
+}
+
+fn managed_usd_value(key: &str, value: &Value) -> Result<f64, String> {
+    value
+        .as_f64()
+        .filter(|amount| amount.is_finite() && *amount >= 0.0)
+        .ok_or_else(|| {
+            format!("managed configuration key {key} must contain non-negative USD amounts")
+        })
+}
@@ -16,6 +16,7 @@ pub(super) fn sanitize_task_memory_context_policy(
         include_subject_memory: Some(options.include_subject_memory.unwrap_or(false)),
         recent_record_limit: Some(options.recent_record_limit.unwrap_or(12).clamp(1, 100)),
         summary_limit: Some(options.summary_limit.unwrap_or(6).clamp(1, 50)),
+        ..Default::default()
     }
 }
 
@@ -26,6 +26,10 @@ mod catalog;
 mod mutation;
 mod testing;
 
+/// Longest fallback chain a model config may declare. Every extra hop adds a failed request's
+/// latency before the turn is served.
+const MAX_FALLBACK_MODEL_CONFIGS: usize = 3;
+
 impl ModelConfigService {
     pub(crate) fn new(store: AppStore) -> Self {
         Self { store }
@@ -55,6 +59,34 @@ impl ModelConfigService {
             .transpose()
     }
 
+    /// Trims the ids and checks that each names another existing model config exactly once.
+    async fn validated_fallback_model_config_ids(
+        &self,
+        model_config_id: Option<&str>,
+        fallback_ids: Vec<String>,
+    ) -> Result<Vec<String>, String> {
+        if fallback_ids.len() > MAX_FALLBACK_MODEL_CONFIGS {
+            return Err(format!(
+                "fallback_model_config_ids accepts at most {MAX_FALLBACK_MODEL_CONFIGS} model configs"
+            ));
+        }
+        let mut validated = Vec::with_capacity(fallback_ids.len());
+        for fallback_id in fallback_ids {
+            let fallback_id = fallback_id.trim().to_string();
+            validate_required("fallback_model_config_ids", &fallback_id)?;
+            if model_config_id == Some(fallback_id.as_str()) || validated.contains(&fallback_id) {
+                return Err(format!(
+                    "fallback_model_config_ids must list other model configs once: {fallback_id}"
+                ));
+            }
+            if self.store.get_model_config(&fallback_id).await?.is_none() {
+                return Err(format!("fallback model config not found: {fallback_id}"));
+            }
+            validated.push(fallback_id);
+        }
+        Ok(validated)
+    }
+
     pub async fn list_model_configs(&self) -> Result<Vec<ModelConfigRecord>, String> {
         let records = self.store.list_model_configs().await?;
         Ok(records
@@ -101,6 +133,9 @@ impl ModelConfigService {
             .await?
             .map(normalize_model_config_record)
             .transpose()?;
+        if let Some(pricing) = input.pricing.as_ref() {
+            pricing.validate()?;
+        }
         let now = now_rfc3339();
         let record = ModelConfigRecord {
             id: input.id.trim().to_string(),
@@ -157,6 +192,13 @@ impl ModelConfigService {
             request_body_limit_bytes: existing
                 .as_ref()
                 .and_then(|item| item.request_body_limit_bytes),
+            pricing: input
+                .pricing
+                .or_else(|| existing.as_ref().and_then(|item| item.pricing.clone())),
+            fallback_model_config_ids: existing
+                .as_ref()
+                .map(|item| item.fallback_model_config_ids.clone())
+                .unwrap_or_default(),
             enabled: input.enabled.unwrap_or(true),
             created_at: existing
                 .as_ref()
@@ -58,6 +58,8 @@ impl ModelConfigService {
             request_cwd: None,
             include_prompt_cache_retention: false,
             request_body_limit_bytes: None,
+            pricing: None,
+            fallback_model_config_ids: Vec::new(),
             enabled: true,
             created_at: now_rfc3339(),
             updated_at: now_rfc3339(),
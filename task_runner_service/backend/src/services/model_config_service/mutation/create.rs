@@ -15,6 +15,12 @@ impl ModelConfigService {
             normalize_model_thinking_level_input(provider.as_str(), input.thinking_level.clone())?;
         let prompt_vendor =
             normalize_model_prompt_vendor_input(input.prompt_vendor, provider.as_str())?;
+        if let Some(pricing) = input.pricing.as_ref() {
+            pricing.validate()?;
+        }
+        let fallback_model_config_ids = self
+            .validated_fallback_model_config_ids(None, input.fallback_model_config_ids)
+            .await?;
         let now = now_rfc3339();
         let record = ModelConfigRecord {
             id: Uuid::new_v4().to_string(),
@@ -41,6 +47,8 @@ impl ModelConfigService {
             request_cwd: normalized_optional(input.request_cwd),
             include_prompt_cache_retention: input.include_prompt_cache_retention.unwrap_or(false),
             request_body_limit_bytes: input.request_body_limit_bytes,
+            pricing: input.pricing,
+            fallback_model_config_ids,
             enabled: input.enabled.unwrap_or(true),
             created_at: now.clone(),
             updated_at: now,
@@ -75,6 +75,15 @@ impl ModelConfigService {
         if let Some(request_body_limit_bytes) = patch.request_body_limit_bytes {
             model.request_body_limit_bytes = Some(request_body_limit_bytes);
         }
+        if let Some(pricing) = patch.pricing {
+            pricing.validate()?;
+            model.pricing = Some(pricing);
+        }
+        if let Some(fallback_ids) = patch.fallback_model_config_ids {
+            model.fallback_model_config_ids = self
+                .validated_fallback_model_config_ids(Some(id), fallback_ids)
+                .await?;
+        }
         if let Some(enabled) = patch.enabled {
             if !enabled {
                 if let Some(task_id) = self.first_task_using_model_config(id).await? {
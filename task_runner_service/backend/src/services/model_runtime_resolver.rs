@@ -1,8 +1,13 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
+use chatos_ai_runtime::ModelRuntimeConfig;
+use tracing::warn;
+
 use crate::config::AppConfig;
 use crate::models::{ModelConfigRecord, TaskRecord};
+use crate::services::model_catalog::normalize_model_config_record;
+use crate::store::AppStore;
 
 pub(super) async fn resolve_model_runtime_for_task(
     config: &AppConfig,
@@ -20,3 +25,44 @@ pub(super) async fn resolve_model_runtime_for_task(
         model_config.id
     ))
 }
+
+/// Runtime configs for the model config's fallback chain, in order. Fallbacks that were
+/// deleted, disabled or cannot run in the cloud are skipped so the primary model still runs.
+pub(super) async fn resolve_fallback_model_runtimes(
+    config: &AppConfig,
+    store: &AppStore,
+    task: &TaskRecord,
+    model_config: &ModelConfigRecord,
+) -> Result<Vec<ModelRuntimeConfig>, String> {
+    let mut fallbacks = Vec::new();
+    for fallback_id in &model_config.fallback_model_config_ids {
+        let Some(record) = store.get_model_config(fallback_id).await? else {
+            warn!(
+                model_config_id = model_config.id.as_str(),
+                fallback_model_config_id = fallback_id.as_str(),
+                "skipping missing fallback model config"
+            );
+            continue;
+        };
+        let resolved = match normalize_model_config_record(record) {
+            Ok(record) if record.enabled => {
+                resolve_model_runtime_for_task(config, task, &record).await
+            }
+            Ok(_) => continue,
+            Err(err) => Err(err),
+        };
+        match resolved {
+            Ok(mut record) => {
+                record.request_cwd = None;
+                fallbacks.push(record.to_runtime_config(None));
+            }
+            Err(err) => warn!(
+                model_config_id = model_config.id.as_str(),
+                fallback_model_config_id = fallback_id.as_str(),
+                error = err.as_str(),
+                "skipping unusable fallback model config"
+            ),
+        }
+    }
+    Ok(fallbacks)
+}
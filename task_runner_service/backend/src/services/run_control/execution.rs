@@ -5,6 +5,7 @@ use std::sync::Arc;
 
 use super::*;
 use crate::models::TaskMcpConfig;
+use crate::services::run_spend::SPEND_BUDGET_EXCEEDED_FINISH_REASON;
 use crate::services::stream_events::flush_pending_stream_event;
 use chatos_ai_runtime::TaskRunReport;
 use chatos_cloud_agent_protocol::CloudAgentRunStatus;
@@ -115,9 +116,12 @@ impl RunService {
                             .and_then(Value::as_str)
                             .map(str::to_string)
                     }),
+                iteration_models: lifecycle.iteration_models,
+                usage_totals: chatos_ai_runtime::UsageTotals::default(),
                 completed_at: now_rfc3339(),
             },
-            CloudAgentRunStatus::Cancelled => chatos_ai_runtime::AiTurnReport::aborted(),
+            CloudAgentRunStatus::Cancelled => chatos_ai_runtime::AiTurnReport::aborted()
+                .with_iteration_models(lifecycle.iteration_models),
             CloudAgentRunStatus::Failed | CloudAgentRunStatus::Blocked => {
                 chatos_ai_runtime::AiTurnReport::failed(
                     outcome
@@ -125,6 +129,7 @@ impl RunService {
                         .and_then(Value::as_str)
                         .unwrap_or("Cloud Agent execution failed"),
                 )
+                .with_iteration_models(lifecycle.iteration_models)
             }
             _ => return Err("Cloud Agent terminal lifecycle has non-terminal status".to_string()),
         };
@@ -156,26 +161,32 @@ impl RunService {
             .map_err(|error| format!("decode terminal supply-chain state failed: {error}"))?
             .unwrap_or_default();
         if task.mcp_config.requires_execution {
-            let supply_chain_policy = self.effective_node_supply_chain_policy().await?;
-            let supply_chain_report = supply_chain_evidence.evaluate(&supply_chain_policy);
-            if supply_chain_report.applicable {
+            let supply_chain_policy = self.effective_supply_chain_policy().await?;
+            let supply_chain_audit =
+                supply_chain_evidence.evaluate_supply_chain(&supply_chain_policy);
+            for entry in supply_chain_audit.applicable_entries() {
                 crate::services::run_model_phase::callbacks::execution::attach_supply_chain_outcome_receipt(
                     &mut report,
-                    &supply_chain_report,
+                    entry.evidence,
                 );
                 self.store.append_run_event_sync(TaskRunEventRecord::new(
                     run.id.clone(),
                     "supply_chain_audit",
-                    Some(match supply_chain_report.status {
-                        "passed" => "Node.js 供应链审计通过".to_string(),
-                        _ => "Node.js 供应链审计未通过".to_string(),
+                    Some(if entry.passed {
+                        format!("{} 供应链审计通过", entry.label)
+                    } else {
+                        format!("{} 供应链审计未通过", entry.label)
                     }),
-                    Some(supply_chain_report.event_payload()),
+                    Some(entry.payload),
                 ));
             }
+            crate::services::run_model_phase::callbacks::execution::block_outcome_on_supply_chain_findings(
+                &mut report,
+                &supply_chain_audit.enforced_blocking_reasons(&supply_chain_policy),
+            );
             if let Some(input_snapshot) = run.input_snapshot.as_object_mut() {
                 match supply_chain_evidence
-                    .passed_receipt(&supply_chain_policy, &supply_chain_report)
+                    .passed_receipt(&supply_chain_policy, &supply_chain_audit)
                 {
                     Some(receipt) => {
                         input_snapshot.insert("supply_chain_receipt".to_string(), receipt);
@@ -279,10 +290,28 @@ impl TaskRunnerSingleStepExecutable {
                     response_id: None,
                     response_output_items,
                     request_input_items: self.prepared.continuation_input_items(),
+                    iteration_models: Vec::new(),
+                    usage_totals: chatos_ai_runtime::UsageTotals::default(),
                 },
                 tool_calls: Value::Array(self.automatic_recovery_calls),
             }
         };
+        let outcome = stop_at_spend_budget(
+            &self.service,
+            self.run_id.as_str(),
+            outcome,
+            lifecycle_state.as_ref(),
+        )
+        .await;
+        if let chatos_ai_runtime::AiSingleStepOutcome::Final(response)
+        | chatos_ai_runtime::AiSingleStepOutcome::ToolCommand { response, .. }
+        | chatos_ai_runtime::AiSingleStepOutcome::Continue { response, .. } = &outcome
+        {
+            lifecycle_state
+                .lock()
+                .iteration_models
+                .extend(response.iteration_models.iter().cloned());
+        }
         let lifecycle = lifecycle_state.lock().clone();
         let path_redactor = crate::services::path_redaction::WorkspacePathRedactor::for_workspace(
             self.service.config.default_workspace_dir.as_str(),
@@ -318,6 +347,50 @@ impl TaskRunnerSingleStepExecutable {
     }
 }
 
+/// Records the step's model spend. When another step would cross a configured
+/// budget, a step that wants to continue is turned into a blocked terminal
+/// outcome so the run stops with a clear reason instead of being cut off
+/// mid-loop.
+async fn stop_at_spend_budget(
+    service: &RunService,
+    run_id: &str,
+    outcome: chatos_ai_runtime::AiSingleStepOutcome,
+    lifecycle_state: &parking_lot::Mutex<
+        crate::services::run_model_phase::callbacks::runtime_state::TaskRunnerLifecycleState,
+    >,
+) -> chatos_ai_runtime::AiSingleStepOutcome {
+    use chatos_ai_runtime::AiSingleStepOutcome;
+
+    let usage = match &outcome {
+        AiSingleStepOutcome::Final(response)
+        | AiSingleStepOutcome::ToolCommand { response, .. }
+        | AiSingleStepOutcome::Continue { response, .. } => &response.usage_totals,
+        _ => return outcome,
+    };
+    let stop = match service.record_step_spend(run_id, usage).await {
+        Ok(Some(stop)) => stop,
+        Ok(None) => return outcome,
+        Err(error) => {
+            warn!("failed to record spend for task run {}: {}", run_id, error);
+            return outcome;
+        }
+    };
+    match outcome {
+        AiSingleStepOutcome::ToolCommand { response, .. }
+        | AiSingleStepOutcome::Continue { response, .. } => {
+            lifecycle_state.lock().execution_outcome = Some(stop.execution_outcome());
+            AiSingleStepOutcome::Final(chatos_ai_runtime::AiRuntimeResult {
+                content: stop.blocking_reason(),
+                tool_calls: None,
+                finish_reason: Some(SPEND_BUDGET_EXCEEDED_FINISH_REASON.to_string()),
+                response_output_items: Vec::new(),
+                ..response
+            })
+        }
+        outcome => outcome,
+    }
+}
+
 impl TaskRunnerSingleStepResolver {
     async fn prepare(
         &self,
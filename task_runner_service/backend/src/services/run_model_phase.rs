@@ -14,7 +14,6 @@ use chatos_ai_runtime::{
     DEFAULT_TASK_RUN_MAX_ITERATIONS,
 };
 use chatos_cloud_agent_runtime::cloud_agent_trigger_input_items;
-use chatos_mcp_management_sdk::McpManagementRuntimeSessionHandle;
 use chatos_mcp_runtime::McpExecutorBuilder;
 use memory_engine_sdk::ComposeContextPolicy;
 use serde_json::{json, Value};
@@ -46,7 +45,7 @@ pub(in crate::services) struct PreparedModelExecution {
     run_spec: TaskRunSpec,
     runtime_config: TaskRuntimeConfig,
     mcp_builder: McpExecutorBuilder,
-    mcp_management_runtime_session: McpManagementRuntimeSessionHandle,
+    mcp_runtime_session_ref: String,
     mcp_command_queue: String,
     tool_result_model_budget_limits: ToolResultModelBudgetLimits,
     effective_workspace_dir: String,
@@ -44,7 +44,7 @@ impl RunService {
                 .collect(),
         );
         let supply_chain_policy = if task.mcp_config.requires_execution {
-            Some(self.effective_node_supply_chain_policy().await?)
+            Some(self.effective_supply_chain_policy().await?)
         } else {
             None
         };
@@ -55,9 +55,12 @@ impl RunService {
                 *runtime_execution.supply_chain_evidence.lock() = inherited;
             }
         }
-        let runtime_config = prepared_execution.runtime_config.with_max_iterations(Some(
-            max_iterations.saturating_add(TASK_OUTCOME_PROTOCOL_RESERVED_ITERATIONS),
-        ));
+        let runtime_config = prepared_execution
+            .runtime_config
+            .with_max_iterations(Some(
+                max_iterations.saturating_add(TASK_OUTCOME_PROTOCOL_RESERVED_ITERATIONS),
+            ))
+            .with_ai_cassette(self.ai_cassette.clone());
         let runtime = runtime_config
             .build_runtime_with_mcp_builder_and_memory_http_client(
                 prepared_execution.mcp_builder,
@@ -83,10 +86,7 @@ impl RunService {
             run_spec,
             runtime,
             runtime_options: runtime_execution.runtime_options,
-            mcp_runtime_session_ref: prepared_execution
-                .mcp_management_runtime_session
-                .session_id()
-                .to_string(),
+            mcp_runtime_session_ref: prepared_execution.mcp_runtime_session_ref,
             mcp_command_queue: prepared_execution.mcp_command_queue,
             lifecycle_state: runtime_execution.lifecycle_state,
             progress: runtime_execution.progress,
@@ -172,9 +172,8 @@ impl RunService {
 
 pub(in crate::services) fn attach_supply_chain_outcome_receipt(
     report: &mut TaskRunReport,
-    supply_chain: &crate::services::run_model_phase::supply_chain::SupplyChainAuditReport,
+    evidence: String,
 ) {
-    let evidence = supply_chain.evidence_summary();
     let Some(outcome) = report.execution_outcome.as_mut() else {
         return;
     };
@@ -187,6 +186,31 @@ pub(in crate::services) fn attach_supply_chain_outcome_receipt(
     }
 }
 
+/// Blocks a succeeded outcome when an audit of an enforced ecosystem did not
+/// pass; other outcomes already carry their own blocking reason.
+pub(in crate::services) fn block_outcome_on_supply_chain_findings(
+    report: &mut TaskRunReport,
+    blocking_reasons: &[String],
+) {
+    if blocking_reasons.is_empty() {
+        return;
+    }
+    let Some(outcome) = report.execution_outcome.as_mut() else {
+        return;
+    };
+    if outcome.status != chatos_ai_runtime::TaskExecutionOutcomeStatus::Succeeded {
+        return;
+    }
+    outcome.status = chatos_ai_runtime::TaskExecutionOutcomeStatus::Blocked;
+    outcome.blocking_reason = Some(format!(
+        "依赖供应链审计未通过：{}",
+        blocking_reasons.join("; ")
+    ));
+    outcome
+        .unmet_acceptance_criteria
+        .push("引入的依赖必须通过供应链审计".to_string());
+}
+
 fn append_external_mcp_runtime_notice(
     run_spec: &mut TaskRunSpec,
     task: &TaskRecord,
@@ -257,6 +281,9 @@ fn is_user_configured_external_tool(info: &chatos_mcp_runtime::ToolInfo) -> bool
             .server_name
 }
 
+#[cfg(test)]
+mod replay_tests;
+
 #[cfg(test)]
 mod supply_chain_receipt_tests {
     use super::*;
@@ -303,6 +330,8 @@ mod supply_chain_receipt_tests {
                 response_id: None,
                 response_output_items: Vec::new(),
                 request_input_items: Vec::new(),
+                iteration_models: Vec::new(),
+                usage_totals: chatos_ai_runtime::UsageTotals::default(),
             }),
         );
         report.execution_outcome = Some(chatos_ai_runtime::TaskExecutionOutcome::succeeded(
@@ -317,13 +346,14 @@ mod supply_chain_receipt_tests {
         let mut task_report = task_report();
         attach_supply_chain_outcome_receipt(
             &mut task_report,
-            &report(
+            report(
                 "blocked",
                 vec![
                     "Node.js dependency audit found 0 high and 1 critical vulnerabilities"
                         .to_string(),
                 ],
-            ),
+            )
+            .evidence_summary(),
         );
 
         let outcome = task_report.execution_outcome.unwrap();
@@ -341,7 +371,10 @@ mod supply_chain_receipt_tests {
     #[test]
     fn passed_supply_chain_report_adds_receipt_evidence() {
         let mut task_report = task_report();
-        attach_supply_chain_outcome_receipt(&mut task_report, &report("passed", Vec::new()));
+        attach_supply_chain_outcome_receipt(
+            &mut task_report,
+            report("passed", Vec::new()).evidence_summary(),
+        );
 
         let outcome = task_report.execution_outcome.unwrap();
         assert_eq!(
@@ -353,4 +386,33 @@ mod supply_chain_receipt_tests {
             .iter()
             .any(|evidence| evidence.contains("high=0, critical=0")));
     }
+
+    #[test]
+    fn enforced_supply_chain_findings_block_a_succeeded_outcome() {
+        let mut task_report = task_report();
+        block_outcome_on_supply_chain_findings(&mut task_report, &[]);
+        assert_eq!(
+            task_report.execution_outcome.as_ref().unwrap().status,
+            chatos_ai_runtime::TaskExecutionOutcomeStatus::Succeeded
+        );
+
+        block_outcome_on_supply_chain_findings(
+            &mut task_report,
+            &["Cargo dependency audit was not executed with recorded evidence".to_string()],
+        );
+        let outcome = task_report.execution_outcome.unwrap();
+        assert_eq!(
+            outcome.status,
+            chatos_ai_runtime::TaskExecutionOutcomeStatus::Blocked
+        );
+        assert!(outcome
+            .blocking_reason
+            .as_deref()
+            .is_some_and(|reason| reason.contains("Cargo dependency audit")));
+        assert_eq!(outcome.unmet_acceptance_criteria.len(), 1);
+        assert_eq!(
+            outcome.verification_evidence,
+            vec!["tests passed".to_string()]
+        );
+    }
 }
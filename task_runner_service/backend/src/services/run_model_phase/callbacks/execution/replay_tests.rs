@@ -0,0 +1,378 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::VecDeque;
+use std::net::{IpAddr, Ipv4Addr};
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::post;
+use axum::{Json, Router};
+use chatos_ai_runtime::{AiCassette, ToolResultModelBudgetLimits};
+use tokio::sync::Mutex as AsyncMutex;
+
+use super::*;
+use crate::ask_user_prompt_service::AskUserPromptService;
+use crate::config::{AppConfig, StoreMode};
+use crate::models::{CreateTaskRequest, TaskMcpRequestConfig};
+use crate::services::TaskService;
+use crate::store::AppStore;
+
+type ProviderReplies = Arc<AsyncMutex<VecDeque<(StatusCode, Value)>>>;
+
+fn test_config() -> AppConfig {
+    AppConfig {
+        host: IpAddr::V4(Ipv4Addr::LOCALHOST),
+        port: 0,
+        otlp_endpoint: "http://127.0.0.1:4317".to_string(),
+        otlp_trace_sample_ratio: 0.0,
+        otlp_export_timeout: Duration::from_secs(1),
+        role: crate::config::TaskRunnerRole::All,
+        store_mode: StoreMode::Memory,
+        database_url: "memory://model-phase-replay-test".to_string(),
+        memory_engine_base_url: None,
+        memory_engine_source_id: "task".to_string(),
+        memory_engine_operator_token: None,
+        memory_engine_http_client: reqwest::Client::new(),
+        default_tenant_id: "tenant".to_string(),
+        default_subject_id: "subject".to_string(),
+        default_workspace_dir: ".".to_string(),
+        memory_timeout: Duration::from_millis(30_000),
+        execution_timeout: Duration::from_millis(30_000),
+        scheduler_poll_interval: Duration::from_millis(1_000),
+        worker_id: "test-worker".to_string(),
+        worker_claim_ttl: Duration::from_millis(120_000),
+        worker_concurrency: 4,
+        auto_memory_summary: false,
+        default_task_execution_max_iterations: 8,
+        default_tool_result_model_max_chars: 1_000,
+        default_tool_results_model_total_max_chars: 1_000,
+        chatos_callback_url: String::new(),
+        chatos_callback_http_client: reqwest::Client::new(),
+        internal_api_secret: None,
+        chatos_internal_api_secret: None,
+        mcp_management_internal_api_secret: None,
+        user_service_internal_api_secret: None,
+        callback_timeout: Duration::from_millis(1_000),
+        admin_username: "admin".to_string(),
+        admin_password: "admin".to_string(),
+        admin_display_name: "Admin".to_string(),
+        user_service_base_url: "http://127.0.0.1:39190".to_string(),
+        user_service_request_timeout: Duration::from_millis(5_000),
+        project_service_base_url: None,
+        project_service_internal_base_url: None,
+        project_service_internal_http_client: reqwest::Client::new(),
+        project_service_sync_secret: None,
+        project_service_request_timeout: Duration::from_millis(5_000),
+    }
+}
+
+async fn mock_provider(
+    State(replies): State<ProviderReplies>,
+    Json(_payload): Json<Value>,
+) -> Response {
+    let (status, body) = replies.lock().await.pop_front().unwrap_or((
+        StatusCode::INTERNAL_SERVER_ERROR,
+        json!({"error": "unexpected"}),
+    ));
+    (status, Json(body)).into_response()
+}
+
+/// Serves `replies` in order on `/responses` and returns the base URL.
+async fn start_provider(
+    replies: Vec<(StatusCode, Value)>,
+) -> (String, ProviderReplies, tokio::task::JoinHandle<()>) {
+    let replies = Arc::new(AsyncMutex::new(
+        replies.into_iter().collect::<VecDeque<_>>(),
+    ));
+    let app = Router::new()
+        .route("/responses", post(mock_provider))
+        .with_state(Arc::clone(&replies));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind mock provider");
+    let address = listener.local_addr().expect("mock provider address");
+    let server = tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+    (format!("http://{address}"), replies, server)
+}
+
+async fn test_task(config: &AppConfig, store: &AppStore) -> TaskRecord {
+    TaskService::new(config.clone(), store.clone())
+        .create_task(
+            CreateTaskRequest {
+                title: "replayed task".to_string(),
+                description: None,
+                objective: "inspect the README".to_string(),
+                input_payload: None,
+                status: Some(TaskStatus::Ready),
+                priority: None,
+                tags: None,
+                default_model_config_id: None,
+                project_id: None,
+                task_profile: None,
+                tenant_id: None,
+                subject_id: None,
+                schedule: None,
+                plugin_config: Default::default(),
+                mcp_config: Some(TaskMcpRequestConfig {
+                    requires_execution: Some(false),
+                    ..TaskMcpRequestConfig::default()
+                }),
+                prerequisite_task_ids: None,
+            },
+            None,
+            None,
+        )
+        .await
+        .expect("create task")
+}
+
+fn test_run(task: &TaskRecord, run_id: &str) -> TaskRunRecord {
+    let now = now_rfc3339();
+    TaskRunRecord {
+        id: run_id.to_string(),
+        task_id: task.id.clone(),
+        agent_run_id: None,
+        agent_ordering_lane_key: None,
+        agent_lane_seq: None,
+        execution_lane_key: None,
+        model_config_id: "model-1".to_string(),
+        memory_thread_id: task.memory_thread_id.clone(),
+        status: TaskRunStatus::Running,
+        model_phase_status: crate::models::ModelPhaseStatus::Running,
+        started_at: Some(now.clone()),
+        finished_at: None,
+        input_snapshot: json!({}),
+        effective_tools: Default::default(),
+        workspace_execution: None,
+        mcp_runtime_session_ref: None,
+        context_snapshot: None,
+        result_summary: None,
+        error_message: None,
+        usage: None,
+        spend: None,
+        report: None,
+        cancel_requested: false,
+        cancel_event_pending: false,
+        dispatch_paused: false,
+        dispatch_event_pending: false,
+        post_process_event_pending: false,
+        post_process_event_enqueued: false,
+        post_process_completed: false,
+        post_process_dead_lettered: false,
+        post_process_attempt_count: 0,
+        post_process_last_error: None,
+        memory_summary_processed: false,
+        chatos_followup_processed: false,
+        summary_job_run_id: None,
+        worker_id: None,
+        claim_token: None,
+        claim_until: None,
+        attempt: 0,
+        attempts: Vec::new(),
+        chatos_started_callback_delivery: None,
+        chatos_callback_delivery: None,
+        created_at: now.clone(),
+        updated_at: now,
+    }
+}
+
+fn test_model_config(base_url: &str) -> ModelConfigRecord {
+    let now = now_rfc3339();
+    serde_json::from_value(json!({
+        "id": "model-1",
+        "name": "replay model",
+        "provider": "openai",
+        "base_url": base_url,
+        "api_key": "test-key",
+        "model": "gpt-test",
+        "temperature": null,
+        "max_output_tokens": null,
+        "model_request_max_retries": 0,
+        "thinking_level": null,
+        "supports_responses": true,
+        "instructions": "You are the replay test agent.",
+        "request_cwd": null,
+        "include_prompt_cache_retention": false,
+        "request_body_limit_bytes": null,
+        "enabled": true,
+        "created_at": now,
+        "updated_at": now,
+    }))
+    .expect("model config")
+}
+
+/// Runs one model phase step for `run_id` exactly as the cloud agent worker
+/// does after preparation, with MCP disabled so the only external traffic is
+/// the model request.
+async fn execute_step(
+    service: &RunService,
+    task: &TaskRecord,
+    run_id: &str,
+    model_config: &ModelConfigRecord,
+) -> Result<chatos_ai_runtime::AiSingleStepOutcome, String> {
+    let run = test_run(task, run_id);
+    service.store.save_run(run.clone()).await?;
+    let agent = TaskRunnerAgent::new(service.resolve_task_runner_agent_key_for_task(task).await?);
+    let run_spec = agent.build_run_spec(TaskRunnerRunSpecInput::new(
+        task.id.clone(),
+        run.id.clone(),
+        model_config.to_runtime_config(None),
+        model_config.id.clone(),
+        "Read README.md and summarize it.",
+        json!({ "task_id": task.id, "run_id": run.id }),
+    ));
+    let prepared = PreparedModelExecution {
+        agent,
+        run_spec,
+        runtime_config: TaskRuntimeConfig::new()
+            .with_mcp_init_mode(chatos_ai_runtime::TaskMcpInitMode::Disabled),
+        mcp_builder: McpExecutorBuilder::new(),
+        mcp_runtime_session_ref: format!("session-{run_id}"),
+        mcp_command_queue: "mcp-commands".to_string(),
+        tool_result_model_budget_limits: ToolResultModelBudgetLimits::from_env(),
+        effective_workspace_dir: ".".to_string(),
+    };
+    let step = service
+        .prepare_single_model_step(task, &run, model_config, prepared)
+        .await?;
+    step.execute(1, "initial".to_string(), 1).await
+}
+
+fn tool_call_reply() -> (StatusCode, Value) {
+    (
+        StatusCode::OK,
+        json!({
+            "id": "resp-read",
+            "status": "completed",
+            "output": [{
+                "type": "function_call",
+                "id": "fc_read",
+                "call_id": "call_read_readme",
+                "name": "read_file",
+                "arguments": "{\"path\":\"README.md\"}"
+            }],
+            "usage": {"input_tokens": 40, "output_tokens": 9, "total_tokens": 49}
+        }),
+    )
+}
+
+#[tokio::test]
+async fn recorded_model_phase_step_replays_without_a_provider() {
+    let config = test_config();
+    let store = AppStore::new(&config).await.expect("store");
+    let service = RunService::new(
+        config.clone(),
+        store.clone(),
+        AskUserPromptService::new(store.clone()),
+    );
+    let task = test_task(&config, &store).await;
+    let (base_url, replies, server) = start_provider(vec![tool_call_reply()]).await;
+    let model_config = test_model_config(base_url.as_str());
+
+    let recorder = Arc::new(AiCassette::record_in_memory());
+    let recorded = execute_step(
+        &service.clone().with_ai_cassette(Arc::clone(&recorder)),
+        &task,
+        "run-recorded",
+        &model_config,
+    )
+    .await
+    .expect("recorded step");
+    server.abort();
+    assert!(replies.lock().await.is_empty());
+    assert_eq!(recorder.file().interactions.len(), 1);
+
+    let replay = Arc::new(AiCassette::replay(recorder.file()));
+    let replayed = execute_step(
+        &service.clone().with_ai_cassette(Arc::clone(&replay)),
+        &task,
+        "run-replayed",
+        &model_config,
+    )
+    .await
+    .expect("replayed step");
+
+    let (
+        chatos_ai_runtime::AiSingleStepOutcome::ToolCommand {
+            response: recorded_response,
+            tool_calls: recorded_calls,
+        },
+        chatos_ai_runtime::AiSingleStepOutcome::ToolCommand {
+            response: replayed_response,
+            tool_calls: replayed_calls,
+        },
+    ) = (recorded, replayed)
+    else {
+        panic!("both steps should stop for the model's tool call");
+    };
+    assert_eq!(replayed_calls, recorded_calls);
+    assert_eq!(
+        chatos_ai_runtime::tool_call::extract_tool_call_name(&replayed_calls[0]),
+        Some("read_file")
+    );
+    assert_eq!(
+        replayed_response.usage_totals,
+        recorded_response.usage_totals
+    );
+    assert_eq!(replayed_response.usage_totals.input_tokens, 40);
+    assert_eq!(replay.unused_interactions(), 0);
+}
+
+#[tokio::test]
+async fn replayed_provider_error_fails_the_step_like_the_recorded_one() {
+    let config = test_config();
+    let store = AppStore::new(&config).await.expect("store");
+    let service = RunService::new(
+        config.clone(),
+        store.clone(),
+        AskUserPromptService::new(store.clone()),
+    );
+    let task = test_task(&config, &store).await;
+    let (base_url, _replies, server) = start_provider(vec![(
+        StatusCode::SERVICE_UNAVAILABLE,
+        json!({"error": {"type": "server_error", "message": "upstream unavailable"}}),
+    )])
+    .await;
+    let model_config = test_model_config(base_url.as_str());
+
+    let recorder = Arc::new(AiCassette::record_in_memory());
+    let recorded = execute_step(
+        &service.clone().with_ai_cassette(Arc::clone(&recorder)),
+        &task,
+        "run-recorded",
+        &model_config,
+    )
+    .await
+    .expect("recorded step outcome");
+    server.abort();
+
+    let replay = Arc::new(AiCassette::replay(recorder.file()));
+    let replayed = execute_step(
+        &service.clone().with_ai_cassette(Arc::clone(&replay)),
+        &task,
+        "run-replayed",
+        &model_config,
+    )
+    .await
+    .expect("replayed step outcome");
+
+    let (
+        chatos_ai_runtime::AiSingleStepOutcome::Failed {
+            error: recorded_error,
+        },
+        chatos_ai_runtime::AiSingleStepOutcome::Failed {
+            error: replayed_error,
+        },
+    ) = (recorded, replayed)
+    else {
+        panic!("a 503 with no request retries left should fail the step");
+    };
+    assert!(recorded_error.contains("503"));
+    assert_eq!(replayed_error, recorded_error);
+    assert_eq!(replay.unused_interactions(), 0);
+}
@@ -5,10 +5,11 @@ use super::*;
 use crate::services::run_model_phase::supply_chain::SupplyChainEvidenceState;
 use async_trait::async_trait;
 use chatos_ai_runtime::{
-    AiResponse, RuntimeBeforeModelRequest, RuntimeFinalResponseAction, RuntimeFinalResponseContext,
-    RuntimeIterationContext, RuntimeLifecycleHook, TaskAcceptanceEvidence, TaskExecutionOutcome,
-    TaskExecutionOutcomeStatus, TaskExecutionProgressState, TaskExecutionReviewCheckpoint,
-    TaskExecutionReviewPolicy, TaskExecutionReviewTrigger,
+    AiIterationModel, AiResponse, RuntimeBeforeModelRequest, RuntimeFinalResponseAction,
+    RuntimeFinalResponseContext, RuntimeIterationContext, RuntimeLifecycleHook,
+    TaskAcceptanceEvidence, TaskExecutionOutcome, TaskExecutionOutcomeStatus,
+    TaskExecutionProgressState, TaskExecutionReviewCheckpoint, TaskExecutionReviewPolicy,
+    TaskExecutionReviewTrigger,
 };
 #[cfg(test)]
 #[path = "runtime_state/tests.rs"]
@@ -21,6 +22,10 @@ const TASK_OUTCOME_REPORT_REQUIRED_REASON: &str = "task_outcome_report_required"
 pub(in crate::services) struct TaskRunnerLifecycleState {
     pub(in crate::services) visible_response: Option<AiResponse>,
     pub(in crate::services) execution_outcome: Option<TaskExecutionOutcome>,
+    /// Models that served each completed step, so the terminal report shows
+    /// fallbacks even though every step runs in its own runtime call.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(in crate::services) iteration_models: Vec<AiIterationModel>,
 }
 
 struct TaskRunnerLifecycleHook {
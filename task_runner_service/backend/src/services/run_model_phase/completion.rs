@@ -423,7 +423,11 @@ fn structured_outcome_result_summary(
     let supply_chain_evidence = outcome
         .verification_evidence
         .iter()
-        .filter(|evidence| evidence.starts_with("Node.js supply-chain audit status:"))
+        .filter(|evidence| {
+            evidence
+                .split_once(" supply-chain audit status:")
+                .is_some_and(|(label, _)| matches!(label, "Node.js" | "Cargo" | "Python" | "Go"))
+        })
         .map(|evidence| path_redactor.redact_text(evidence))
         .collect::<Vec<_>>();
     if !supply_chain_evidence.is_empty() {
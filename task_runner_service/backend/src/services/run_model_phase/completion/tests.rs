@@ -291,6 +291,7 @@ fn run_record(task: &TaskRecord) -> TaskRunRecord {
         result_summary: None,
         error_message: None,
         usage: None,
+        spend: None,
         report: None,
         cancel_requested: false,
         cancel_event_pending: false,
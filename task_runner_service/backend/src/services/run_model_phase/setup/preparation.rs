@@ -6,9 +6,11 @@ use crate::services::TaskRunnerCapabilityPolicy;
 
 mod mcp_inputs;
 mod mcp_management_gateway;
+mod sampling_spend;
 
 use mcp_inputs::mcp_provider_skills_prefixed_input_items;
 use mcp_management_gateway::resolve_mcp_management_gateway;
+use sampling_spend::RunSamplingSpend;
 
 pub(super) async fn prepare_model_execution(
     service: &RunService,
@@ -51,6 +53,13 @@ pub(super) async fn prepare_model_execution(
             model_config,
         )
         .await?;
+    let fallback_models = crate::services::model_runtime_resolver::resolve_fallback_model_runtimes(
+        &service.config,
+        &service.store,
+        task,
+        model_config,
+    )
+    .await?;
     let agent = TaskRunnerAgent::new(task_agent_key);
     let agent_prompt =
         crate::services::plugin_management_prompts::resolve_task_runner_agent_prompt(
@@ -120,6 +129,7 @@ pub(super) async fn prepare_model_execution(
         prefixed_input_items,
         prompt_cache_policy,
     );
+    run_spec.model_config.fallback_models = fallback_models;
     let memory_scope = build_memory_scope(service, task, run);
     run_spec = run_spec.with_memory_scope(Some(memory_scope));
     persist_context_snapshot(service, run, run_spec.memory_scope.as_ref()).await;
@@ -127,6 +137,15 @@ pub(super) async fn prepare_model_execution(
         mcp_management_gateway.into_parts();
     let mcp_builder = McpExecutorBuilder::new()
         .with_http_server(mcp_management_server)
+        .with_resource_tools()
+        .with_sampling_handler(Arc::new(
+            chatos_ai_runtime::AiMcpSamplingHandler::default().with_usage_recorder(Arc::new(
+                RunSamplingSpend::new(service, run.id.as_str(), &run_spec.model_config),
+            )),
+        ))
+        .with_elicitation_handler(Arc::new(chatos_mcp::AskUserElicitationHandler::new(
+            chatos_mcp::AskUserStoreRef::new(Arc::new(service.ask_user_prompt_service.clone())),
+        )))
         .with_tool_result_max_chars(tool_result_model_budget_limits.per_result_max_chars);
 
     Ok(PreparedModelExecution {
@@ -134,7 +153,7 @@ pub(super) async fn prepare_model_execution(
         run_spec,
         runtime_config,
         mcp_builder,
-        mcp_management_runtime_session,
+        mcp_runtime_session_ref: mcp_management_runtime_session.session_id().to_string(),
         mcp_command_queue,
         tool_result_model_budget_limits,
         effective_workspace_dir,
@@ -292,6 +311,7 @@ fn build_memory_scope(service: &RunService, task: &TaskRecord, run: &TaskRunReco
         include_subject_memory: Some(true),
         recent_record_limit: None,
         summary_limit: Some(2),
+        ..Default::default()
     })
 }
 
@@ -486,6 +506,78 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn fallback_chain_resolves_usable_model_configs_in_order() {
+        let service = test_run_service(test_config());
+        let task = sample_task(crate::models::TASK_PROFILE_DEFAULT, "project-1");
+        let mut primary = model_config("primary", true);
+        primary.fallback_model_config_ids = vec![
+            "disabled".to_string(),
+            "second".to_string(),
+            "deleted".to_string(),
+            "local-only".to_string(),
+            "first".to_string(),
+        ];
+        let mut local_only = model_config("local-only", true);
+        local_only.api_key.clear();
+        for record in [
+            model_config("first", true),
+            model_config("second", true),
+            model_config("disabled", false),
+            local_only,
+        ] {
+            service
+                .store
+                .save_model_config(record)
+                .await
+                .expect("save model config");
+        }
+
+        let fallbacks = crate::services::model_runtime_resolver::resolve_fallback_model_runtimes(
+            &service.config,
+            &service.store,
+            &task,
+            &primary,
+        )
+        .await
+        .expect("fallback models");
+
+        assert_eq!(
+            fallbacks
+                .iter()
+                .map(|fallback| fallback.model.as_str())
+                .collect::<Vec<_>>(),
+            ["second-model", "first-model"]
+        );
+        assert!(fallbacks
+            .iter()
+            .all(|fallback| fallback.request_cwd.is_none()));
+    }
+
+    fn model_config(id: &str, enabled: bool) -> ModelConfigRecord {
+        let now = now_rfc3339();
+        serde_json::from_value(json!({
+            "id": id,
+            "name": id,
+            "provider": "openai",
+            "base_url": "https://api.example.test/v1",
+            "api_key": "test-key",
+            "model": format!("{id}-model"),
+            "temperature": null,
+            "max_output_tokens": null,
+            "thinking_level": null,
+            "supports_responses": true,
+            "instructions": null,
+            "request_cwd": "/workspace",
+            "include_prompt_cache_retention": false,
+            "request_body_limit_bytes": null,
+            "enabled": enabled,
+            "created_at": now,
+            "updated_at": now,
+        }))
+        .expect("model config")
+    }
+
     fn test_config() -> AppConfig {
         AppConfig {
             host: IpAddr::V4(Ipv4Addr::LOCALHOST),
@@ -612,6 +704,7 @@ mod tests {
             result_summary: None,
             error_message: None,
             usage: None,
+            spend: None,
             report: None,
             cancel_requested: false,
             cancel_event_pending: false,
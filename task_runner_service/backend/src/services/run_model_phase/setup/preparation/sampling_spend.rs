@@ -0,0 +1,77 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use async_trait::async_trait;
+use chatos_ai_runtime::{McpSamplingUsageRecorder, ModelPricing, ModelRuntimeConfig, UsageTotals};
+use chatos_mcp_runtime::ToolCallerModelRuntime;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::services::RunService;
+
+/// Bills MCP sampling requests made during a run to the run's spend, priced
+/// like the run's own model (or fallback) that answered them.
+#[derive(Clone)]
+pub(super) struct RunSamplingSpend {
+    service: RunService,
+    run_id: String,
+    priced_models: Vec<(String, String, Option<ModelPricing>)>,
+}
+
+impl RunSamplingSpend {
+    pub(super) fn new(service: &RunService, run_id: &str, model: &ModelRuntimeConfig) -> Self {
+        let priced_models = std::iter::once(model)
+            .chain(model.fallback_models.iter())
+            .map(|model| {
+                (
+                    model.base_url.clone(),
+                    model.model.clone(),
+                    model.pricing.clone(),
+                )
+            })
+            .collect();
+        Self {
+            service: service.clone(),
+            run_id: run_id.to_string(),
+            priced_models,
+        }
+    }
+
+    fn pricing_for(&self, runtime: &ToolCallerModelRuntime) -> Option<&ModelPricing> {
+        self.priced_models
+            .iter()
+            .find(|(base_url, model, _)| *base_url == runtime.base_url && *model == runtime.model)
+            .and_then(|(_, _, pricing)| pricing.as_ref())
+    }
+}
+
+impl std::fmt::Debug for RunSamplingSpend {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        formatter
+            .debug_struct("RunSamplingSpend")
+            .field("run_id", &self.run_id)
+            .finish()
+    }
+}
+
+#[async_trait]
+impl McpSamplingUsageRecorder for RunSamplingSpend {
+    async fn record_sampling_usage(
+        &self,
+        caller_model_runtime: &ToolCallerModelRuntime,
+        usage: &Value,
+    ) {
+        let mut totals = UsageTotals::default();
+        totals.record(usage, self.pricing_for(caller_model_runtime));
+        if let Err(error) = self
+            .service
+            .record_step_spend(self.run_id.as_str(), &totals)
+            .await
+        {
+            warn!(
+                "failed to record MCP sampling spend for task run {}: {}",
+                self.run_id, error
+            );
+        }
+    }
+}
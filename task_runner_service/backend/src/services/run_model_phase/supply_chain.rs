@@ -6,6 +6,21 @@ use std::collections::{BTreeMap, BTreeSet};
 use serde::Serialize;
 use serde_json::{json, Value};
 
+mod ecosystems;
+
+pub(crate) use ecosystems::{CargoSupplyChainPolicy, PythonSupplyChainPolicy};
+use ecosystems::{DependencyEcosystem, EcosystemAuditReport, EcosystemEvidence};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct SupplyChainPolicy {
+    pub(crate) node: NodeSupplyChainPolicy,
+    pub(crate) cargo: CargoSupplyChainPolicy,
+    pub(crate) python: PythonSupplyChainPolicy,
+    /// Ecosystems whose blocked audit turns a succeeded outcome into a blocked
+    /// one; audits of the remaining ecosystems are recorded as receipts only.
+    pub(crate) blocking_ecosystems: BTreeSet<String>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct NodeSupplyChainPolicy {
     pub(crate) baseline_revision: String,
@@ -32,6 +47,12 @@ pub(in crate::services) struct SupplyChainEvidenceState {
     pending_terminal_commands: BTreeMap<String, String>,
     #[serde(default)]
     inherited_from_run_id: Option<String>,
+    #[serde(default)]
+    cargo: EcosystemEvidence,
+    #[serde(default)]
+    python: EcosystemEvidence,
+    #[serde(default)]
+    go: EcosystemEvidence,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -126,10 +147,26 @@ pub(in crate::services) struct SupplyChainAuditReport {
     pub(super) blocking_reasons: Vec<String>,
 }
 
+#[derive(Debug, Clone)]
+pub(in crate::services) struct SupplyChainAudit {
+    pub(in crate::services) node: SupplyChainAuditReport,
+    pub(in crate::services) ecosystems: Vec<EcosystemAuditReport>,
+}
+
+#[derive(Debug, Clone)]
+pub(in crate::services) struct SupplyChainAuditEntry {
+    pub(in crate::services) label: &'static str,
+    pub(in crate::services) passed: bool,
+    pub(in crate::services) evidence: String,
+    pub(in crate::services) payload: Value,
+}
+
 impl SupplyChainEvidenceState {
+    /// Evidence of the latest passed prerequisite receipt at this run's
+    /// execution head, provided it still passes under the current policy.
     pub(in crate::services) fn inherit_for_run(
         run: &crate::models::TaskRunRecord,
-        policy: &NodeSupplyChainPolicy,
+        policy: &SupplyChainPolicy,
     ) -> Option<Self> {
         let workspace = run.workspace_execution.as_ref()?;
         let execution_group_id = workspace.execution_group_id.as_deref()?;
@@ -153,26 +190,32 @@ impl SupplyChainEvidenceState {
             let run_id = prerequisite.get("run_id").and_then(Value::as_str)?;
             let receipt = prerequisite.get("supply_chain_receipt")?.clone();
             let receipt = serde_json::from_value::<SupplyChainEvidenceReceipt>(receipt).ok()?;
-            if receipt.status != "passed" || receipt.baseline_revision != policy.baseline_revision {
+            if receipt.status != "passed"
+                || receipt.baseline_revision != policy.node.baseline_revision
+            {
                 return None;
             }
             let mut evidence = receipt.evidence;
             evidence.pending_package_manifest_events.clear();
             evidence.staged_package_manifest_updates.clear();
             evidence.pending_terminal_commands.clear();
+            if !evidence.evaluate_supply_chain(policy).passed() {
+                return None;
+            }
             evidence.inherited_from_run_id = Some(run_id.to_string());
+            evidence.mark_ecosystem_evidence_inherited();
             Some(evidence)
         })
     }
 
     pub(in crate::services) fn passed_receipt(
         &self,
-        policy: &NodeSupplyChainPolicy,
-        report: &SupplyChainAuditReport,
+        policy: &SupplyChainPolicy,
+        audit: &SupplyChainAudit,
     ) -> Option<Value> {
-        (report.status == "passed").then(|| {
+        audit.passed().then(|| {
             serde_json::to_value(SupplyChainEvidenceReceipt {
-                baseline_revision: policy.baseline_revision.clone(),
+                baseline_revision: policy.node.baseline_revision.clone(),
                 status: "passed".to_string(),
                 evidence: self.clone(),
             })
@@ -250,6 +293,7 @@ impl SupplyChainEvidenceState {
             && payload.get("is_error").and_then(Value::as_bool) != Some(true)
         {
             observe_project_paths(payload, self);
+            self.observe_ecosystem_paths(payload);
             if !applied_manifest_update && result_mutates_package_manifest(payload) {
                 self.node_project_observed = true;
                 self.dependency_activity_observed = true;
@@ -308,6 +352,7 @@ impl SupplyChainEvidenceState {
     }
 
     fn observe_terminal_command_result(&mut self, result: TerminalCommandResult) {
+        self.observe_ecosystem_command(&result);
         let command = result.command.as_str();
         let normalized = command.to_ascii_lowercase();
         let exit_code = result.exit_code;
@@ -363,6 +408,19 @@ impl SupplyChainEvidenceState {
         }
     }
 
+    pub(in crate::services) fn evaluate_supply_chain(
+        &self,
+        policy: &SupplyChainPolicy,
+    ) -> SupplyChainAudit {
+        SupplyChainAudit {
+            node: self.evaluate(&policy.node),
+            ecosystems: DependencyEcosystem::ALL
+                .into_iter()
+                .map(|ecosystem| self.evaluate_ecosystem(ecosystem, policy))
+                .collect(),
+        }
+    }
+
     pub(in crate::services) fn evaluate(
         &self,
         policy: &NodeSupplyChainPolicy,
@@ -595,7 +653,61 @@ impl SupplyChainAuditReport {
     }
 }
 
-pub(super) fn policy_guidance(policy: &NodeSupplyChainPolicy) -> Value {
+impl SupplyChainAudit {
+    pub(in crate::services) fn applicable_entries(&self) -> Vec<SupplyChainAuditEntry> {
+        let node = self.node.applicable.then(|| SupplyChainAuditEntry {
+            label: "Node.js",
+            passed: self.node.status == "passed",
+            evidence: self.node.evidence_summary(),
+            payload: self.node.event_payload(),
+        });
+        node.into_iter()
+            .chain(
+                self.ecosystems
+                    .iter()
+                    .filter(|report| report.applicable)
+                    .map(|report| SupplyChainAuditEntry {
+                        label: report.ecosystem.label(),
+                        passed: report.status == "passed",
+                        evidence: report.evidence_summary(),
+                        payload: report.event_payload(),
+                    }),
+            )
+            .collect()
+    }
+
+    /// Blocking reasons of the blocked audits whose ecosystem the policy
+    /// enforces.
+    pub(in crate::services) fn enforced_blocking_reasons(
+        &self,
+        policy: &SupplyChainPolicy,
+    ) -> Vec<String> {
+        let node = (self.node.status == "blocked" && policy.blocking_ecosystems.contains("node"))
+            .then_some(&self.node.blocking_reasons);
+        node.into_iter()
+            .chain(
+                self.ecosystems
+                    .iter()
+                    .filter(|report| {
+                        report.status == "blocked"
+                            && policy.blocking_ecosystems.contains(report.ecosystem.key())
+                    })
+                    .map(|report| &report.blocking_reasons),
+            )
+            .flatten()
+            .cloned()
+            .collect()
+    }
+
+    fn passed(&self) -> bool {
+        let entries = self.applicable_entries();
+        !entries.is_empty() && entries.iter().all(|entry| entry.passed)
+    }
+}
+
+pub(super) fn policy_guidance(policy: &SupplyChainPolicy) -> Value {
+    let ecosystem_guidance = ecosystems::policy_guidance(policy);
+    let policy = &policy.node;
     let allowlist = if policy.install_script_allowlist.is_empty() {
         "none".to_string()
     } else {
@@ -612,7 +724,7 @@ pub(super) fn policy_guidance(policy: &NodeSupplyChainPolicy) -> Value {
         "type": "message",
         "role": "system",
         "content": format!(
-            "[Node.js supply-chain requirements]\nFor any Node.js project, keep the dependency lockfile and use these exact centrally reviewed requirements whenever the package is present: {dependency_requirements}. After the final dependency change, read the complete package.json so the runtime can verify the baseline. Install dependencies with lifecycle scripts disabled using registry `{}` (for npm: `npm ci --ignore-scripts --registry={}`), run lifecycle scripts only for these approved packages: {allowlist}, and finish with a JSON dependency audit at `{}` severity using the independently configured audit registry `{}` (for npm: `npm audit --audit-level={} --json --registry={}`). The active dependency baseline revision is `{}`. A Node.js implementation is not complete until the final package.json, installation, and audit commands have successful, parseable tool evidence and high/critical vulnerabilities are zero.",
+            "[Node.js supply-chain requirements]\nFor any Node.js project, keep the dependency lockfile and use these exact centrally reviewed requirements whenever the package is present: {dependency_requirements}. After the final dependency change, read the complete package.json so the runtime can verify the baseline. Install dependencies with lifecycle scripts disabled using registry `{}` (for npm: `npm ci --ignore-scripts --registry={}`), run lifecycle scripts only for these approved packages: {allowlist}, and finish with a JSON dependency audit at `{}` severity using the independently configured audit registry `{}` (for npm: `npm audit --audit-level={} --json --registry={}`). The active dependency baseline revision is `{}`. A Node.js implementation is not complete until the final package.json, installation, and audit commands have successful, parseable tool evidence and high/critical vulnerabilities are zero.\n\n{ecosystem_guidance}",
             policy.install_registry,
             policy.install_registry,
             policy.audit_level,
@@ -1880,9 +1992,15 @@ mod tests {
             0,
             r#"{"metadata":{"vulnerabilities":{"total":0,"info":0,"low":0,"moderate":0,"high":0,"critical":0}}}"#,
         ));
-        let previous_report = previous.evaluate(&policy);
+        let supply_chain_policy = SupplyChainPolicy {
+            node: policy.clone(),
+            cargo: CargoSupplyChainPolicy::default(),
+            python: PythonSupplyChainPolicy::default(),
+            blocking_ecosystems: BTreeSet::new(),
+        };
+        let previous_audit = previous.evaluate_supply_chain(&supply_chain_policy);
         let receipt = previous
-            .passed_receipt(&policy, &previous_report)
+            .passed_receipt(&supply_chain_policy, &previous_audit)
             .expect("passed receipt");
         let mut run = crate::models::TaskRunRecord::queued(
             "run-current".to_string(),
@@ -1908,7 +2026,7 @@ mod tests {
             .expect("workspace execution"),
         );
 
-        let mut inherited = SupplyChainEvidenceState::inherit_for_run(&run, &policy)
+        let mut inherited = SupplyChainEvidenceState::inherit_for_run(&run, &supply_chain_policy)
             .expect("matching receipt should inherit");
         assert_eq!(inherited.evaluate(&policy).status, "passed");
 
@@ -1933,6 +2051,69 @@ mod tests {
             .as_mut()
             .expect("workspace")
             .execution_base_commit = Some("different-head".to_string());
-        assert!(SupplyChainEvidenceState::inherit_for_run(&run, &policy).is_none());
+        assert!(SupplyChainEvidenceState::inherit_for_run(&run, &supply_chain_policy).is_none());
+    }
+
+    #[test]
+    fn inherited_receipt_is_dropped_when_the_current_policy_no_longer_passes_it() {
+        let allowing = SupplyChainPolicy {
+            node: policy(),
+            cargo: CargoSupplyChainPolicy {
+                build_script_allowlist: BTreeSet::from(["openssl-sys".to_string()]),
+            },
+            python: PythonSupplyChainPolicy::default(),
+            blocking_ecosystems: BTreeSet::new(),
+        };
+        let mut previous = SupplyChainEvidenceState::default();
+        previous.observe_tool_result(&terminal_result("cargo add openssl-sys", 0, ""));
+        previous.observe_tool_result(&terminal_result(
+            "git diff -- Cargo.lock",
+            0,
+            "+[[package]]\n+name = \"openssl-sys\"\n+version = \"0.9.104\"\n",
+        ));
+        previous.observe_tool_result(&terminal_result(
+            "cargo metadata --format-version 1",
+            0,
+            r#"{"packages":[{"name":"openssl-sys","targets":[{"kind":["custom-build"]}]}]}"#,
+        ));
+        previous.observe_tool_result(&terminal_result(
+            "cargo audit --json",
+            0,
+            r#"{"vulnerabilities":{"found":false,"count":0,"list":[]}}"#,
+        ));
+        let audit = previous.evaluate_supply_chain(&allowing);
+        let receipt = previous
+            .passed_receipt(&allowing, &audit)
+            .expect("passed receipt");
+        let mut run = crate::models::TaskRunRecord::queued(
+            "run-current".to_string(),
+            "task-current".to_string(),
+            "model".to_string(),
+            "thread".to_string(),
+            json!({
+                "resolved_prerequisites": [{
+                    "run_id": "run-previous",
+                    "execution_group_id": "group-1",
+                    "integrated_commit": "head-1",
+                    "supply_chain_receipt": receipt,
+                }]
+            }),
+            "2026-08-15T10:00:00Z".to_string(),
+        );
+        run.workspace_execution = Some(
+            serde_json::from_value(json!({
+                "status": "ready",
+                "execution_group_id": "group-1",
+                "execution_base_commit": "head-1"
+            }))
+            .expect("workspace execution"),
+        );
+        assert!(SupplyChainEvidenceState::inherit_for_run(&run, &allowing).is_some());
+
+        let tightened = SupplyChainPolicy {
+            cargo: CargoSupplyChainPolicy::default(),
+            ..allowing
+        };
+        assert!(SupplyChainEvidenceState::inherit_for_run(&run, &tightened).is_none());
     }
 }
@@ -0,0 +1,1248 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use std::collections::BTreeSet;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use super::{
+    command_invocation_segment, command_invokes_executable, command_masks_failure, CommandEvidence,
+    SupplyChainEvidenceState, SupplyChainPolicy, TerminalCommandResult,
+};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct CargoSupplyChainPolicy {
+    pub(crate) build_script_allowlist: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct PythonSupplyChainPolicy {
+    pub(crate) index_url: String,
+    pub(crate) source_build_allowlist: BTreeSet<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(in crate::services) enum DependencyEcosystem {
+    Cargo,
+    Python,
+    Go,
+}
+
+#[derive(Debug, Clone, Default, Serialize, serde::Deserialize)]
+#[serde(default)]
+pub(super) struct EcosystemEvidence {
+    project_observed: bool,
+    dependency_activity_observed: bool,
+    lockfile_verified: bool,
+    inherited: bool,
+    install: Option<CommandEvidence>,
+    unsafe_commands: Vec<String>,
+    lockfile_additions: Option<Vec<String>>,
+    build_script_packages: Option<BTreeSet<String>>,
+    source_built_packages: BTreeSet<String>,
+    local_build_scripts: BTreeSet<String>,
+    audit: Option<AdvisoryAuditEvidence>,
+}
+
+#[derive(Debug, Clone, Serialize, serde::Deserialize)]
+struct AdvisoryAuditEvidence {
+    command: String,
+    exit_code: Option<i64>,
+    output_truncated: bool,
+    advisories: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub(in crate::services) struct EcosystemAuditReport {
+    pub(in crate::services) ecosystem: DependencyEcosystem,
+    pub(in crate::services) applicable: bool,
+    pub(in crate::services) status: &'static str,
+    pub(super) baseline_revision: String,
+    pub(super) lockfile_verified: bool,
+    pub(super) install_command: Option<String>,
+    pub(super) install_exit_code: Option<i64>,
+    pub(super) audit_command: Option<String>,
+    pub(super) audit_exit_code: Option<i64>,
+    pub(super) advisories: Option<Vec<String>>,
+    pub(super) lockfile_additions: Vec<String>,
+    pub(super) build_script_packages_added: Vec<String>,
+    pub(super) source_built_packages: Vec<String>,
+    pub(super) local_build_scripts: Vec<String>,
+    pub(super) blocking_reasons: Vec<String>,
+}
+
+const CARGO_LOCK_CHANGING_COMMANDS: &[&[&str]] = &[
+    &["cargo", "add"],
+    &["cargo", "remove"],
+    &["cargo", "update"],
+    &["cargo", "generate-lockfile"],
+];
+
+const PIP_INSTALL_COMMANDS: &[&[&str]] = &[
+    &["pip", "install"],
+    &["pip3", "install"],
+    &["python", "-m", "pip", "install"],
+    &["python3", "-m", "pip", "install"],
+    &["uv", "pip", "install"],
+];
+
+const PYTHON_LOCKED_INSTALL_COMMANDS: &[&[&str]] = &[
+    &["uv", "sync"],
+    &["uv", "pip", "sync"],
+    &["pip-sync"],
+    &["poetry", "install"],
+    &["poetry", "sync"],
+    &["pdm", "install"],
+    &["pdm", "sync"],
+];
+
+const PYTHON_LOCK_CHANGING_COMMANDS: &[&[&str]] = &[
+    &["uv", "add"],
+    &["uv", "remove"],
+    &["uv", "lock"],
+    &["uv", "pip", "compile"],
+    &["pip-compile"],
+    &["poetry", "add"],
+    &["poetry", "remove"],
+    &["poetry", "lock"],
+    &["poetry", "update"],
+    &["pdm", "add"],
+    &["pdm", "remove"],
+    &["pdm", "lock"],
+    &["pdm", "update"],
+];
+
+const PYTHON_AUDIT_COMMANDS: &[&[&str]] = &[
+    &["pip-audit"],
+    &["python", "-m", "pip_audit"],
+    &["python3", "-m", "pip_audit"],
+    &["uvx", "pip-audit"],
+    &["uv", "run", "pip-audit"],
+];
+
+const PYTHON_TOOLS: &[&str] = &[
+    "pip",
+    "pip3",
+    "uv",
+    "uvx",
+    "poetry",
+    "pdm",
+    "pip-audit",
+    "pip-compile",
+    "pip-sync",
+];
+
+const GO_LOCK_CHANGING_COMMANDS: &[&[&str]] = &[
+    &["go", "get"],
+    &["go", "mod", "tidy"],
+    &["go", "mod", "edit"],
+    &["go", "work", "sync"],
+];
+
+const GO_CHECKSUM_BYPASS_MARKERS: &[&str] = &[
+    "gosumdb=off",
+    "gonosumdb=",
+    "gonosumcheck=",
+    "goinsecure=",
+    "goflags=-insecure",
+    " -insecure",
+];
+
+impl DependencyEcosystem {
+    pub(in crate::services) const ALL: [Self; 3] = [Self::Cargo, Self::Python, Self::Go];
+
+    pub(in crate::services) fn key(self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo",
+            Self::Python => "python",
+            Self::Go => "go",
+        }
+    }
+
+    pub(in crate::services) fn label(self) -> &'static str {
+        match self {
+            Self::Cargo => "Cargo",
+            Self::Python => "Python",
+            Self::Go => "Go",
+        }
+    }
+
+    fn audit_command_hint(self) -> &'static str {
+        match self {
+            Self::Cargo => "cargo audit --json",
+            Self::Python => "pip-audit -f json",
+            Self::Go => "govulncheck -json",
+        }
+    }
+
+    fn is_manifest(self, path: &str) -> bool {
+        let file_name = file_name(path);
+        match self {
+            Self::Cargo => file_name == "cargo.toml",
+            Self::Python => {
+                matches!(
+                    file_name,
+                    "pyproject.toml" | "setup.py" | "setup.cfg" | "pipfile"
+                ) || (file_name.starts_with("requirements") && file_name.ends_with(".txt"))
+            }
+            Self::Go => file_name == "go.mod",
+        }
+    }
+
+    fn is_lockfile(self, path: &str) -> bool {
+        let file_name = file_name(path);
+        match self {
+            Self::Cargo => file_name == "cargo.lock",
+            Self::Python => matches!(
+                file_name,
+                "uv.lock" | "poetry.lock" | "pdm.lock" | "pipfile.lock"
+            ),
+            Self::Go => file_name == "go.sum",
+        }
+    }
+}
+
+impl EcosystemEvidence {
+    fn record_dependency_change(&mut self) {
+        if self.inherited {
+            // An inherited receipt only covers the dependency graph at the
+            // prerequisite's integrated commit; any later change must be
+            // re-verified in this run.
+            let local_build_scripts = std::mem::take(&mut self.local_build_scripts);
+            *self = Self {
+                project_observed: true,
+                local_build_scripts,
+                ..Self::default()
+            };
+        }
+        self.dependency_activity_observed = true;
+    }
+
+    fn record_install(&mut self, command: &str, exit_code: Option<i64>) {
+        let succeeded = exit_code == Some(0) && !command_masks_failure(command);
+        if succeeded
+            || self.install.as_ref().is_none_or(|install| {
+                install.exit_code != Some(0) || command_masks_failure(install.command.as_str())
+            })
+        {
+            self.install = Some(CommandEvidence {
+                command: command.to_string(),
+                exit_code,
+            });
+        }
+    }
+
+    fn record_audit(&mut self, result: &TerminalCommandResult, advisories: Option<Vec<String>>) {
+        self.project_observed = true;
+        self.audit = Some(AdvisoryAuditEvidence {
+            command: result.command.clone(),
+            exit_code: result.exit_code,
+            output_truncated: result.output_truncated,
+            advisories,
+        });
+    }
+
+    fn observe_cargo_command(&mut self, result: &TerminalCommandResult, normalized: &str) {
+        let succeeded =
+            result.exit_code == Some(0) && !command_masks_failure(result.command.as_str());
+        if command_invokes_executable(normalized, "cargo") {
+            self.project_observed = true;
+        }
+        if CARGO_LOCK_CHANGING_COMMANDS
+            .iter()
+            .any(|invocation| command_invocation_segment(normalized, invocation).is_some())
+        {
+            self.record_dependency_change();
+            self.lockfile_verified = false;
+            self.lockfile_additions = None;
+            self.build_script_packages = None;
+            self.audit = None;
+        }
+        if let Some(segment) = command_invocation_segment(normalized, &["git", "diff"]) {
+            let summary_only = [
+                "--stat",
+                "--name-only",
+                "--name-status",
+                "--numstat",
+                "--shortstat",
+            ]
+            .iter()
+            .any(|flag| segment.contains(flag));
+            if segment.contains("cargo.lock") && !summary_only {
+                self.project_observed = true;
+                if succeeded && !result.output_truncated {
+                    self.lockfile_verified = true;
+                    self.lockfile_additions = Some(cargo_lockfile_additions(&result.output));
+                }
+            }
+        }
+        if command_invocation_segment(normalized, &["cargo", "metadata"]).is_some()
+            && succeeded
+            && !result.output_truncated
+        {
+            if let Some(packages) = find_json(&result.output, build_script_packages) {
+                self.build_script_packages = Some(packages);
+            }
+        }
+        if let Some(segment) = command_invocation_segment(normalized, &["cargo", "audit"]) {
+            let advisories = segment
+                .contains("--json")
+                .then(|| find_json(&result.output, cargo_audit_advisories))
+                .flatten();
+            self.record_audit(result, advisories);
+        }
+    }
+
+    fn observe_python_command(&mut self, result: &TerminalCommandResult, normalized: &str) {
+        let command = result.command.as_str();
+        let succeeded = result.exit_code == Some(0) && !command_masks_failure(command);
+        if PYTHON_TOOLS
+            .iter()
+            .any(|tool| command_invokes_executable(normalized, tool))
+        {
+            self.project_observed = true;
+        }
+        if let Some(segment) = PYTHON_LOCK_CHANGING_COMMANDS
+            .iter()
+            .find_map(|invocation| command_invocation_segment(normalized, invocation))
+        {
+            self.record_dependency_change();
+            self.audit = None;
+            let compiles_requirements = command_invocation_segment(segment, &["pip-compile"])
+                .or_else(|| command_invocation_segment(segment, &["uv", "pip", "compile"]))
+                .is_some();
+            self.lockfile_verified =
+                succeeded && (!compiles_requirements || segment.contains("--generate-hashes"));
+        }
+        if let Some(segment) = PIP_INSTALL_COMMANDS
+            .iter()
+            .find_map(|invocation| command_invocation_segment(normalized, invocation))
+        {
+            self.record_dependency_change();
+            self.record_install(command, result.exit_code);
+            if segment.contains("--require-hashes") {
+                self.lockfile_verified |= succeeded;
+            } else {
+                self.unsafe_commands.push(command.to_string());
+            }
+            self.source_built_packages
+                .extend(python_source_builds(&result.output));
+        }
+        if let Some(segment) = PYTHON_LOCKED_INSTALL_COMMANDS
+            .iter()
+            .find_map(|invocation| command_invocation_segment(normalized, invocation))
+        {
+            self.record_dependency_change();
+            self.record_install(command, result.exit_code);
+            let enforces_lockfile =
+                if command_invocation_segment(segment, &["uv", "sync"]).is_some() {
+                    segment.contains("--locked") || segment.contains("--frozen")
+                } else if command_invocation_segment(segment, &["pdm", "install"]).is_some() {
+                    segment.contains("--frozen-lockfile") || segment.contains("--check")
+                } else {
+                    true
+                };
+            if enforces_lockfile {
+                self.lockfile_verified |= succeeded;
+            } else {
+                self.unsafe_commands.push(command.to_string());
+            }
+            self.source_built_packages
+                .extend(python_source_builds(&result.output));
+        }
+        if let Some(segment) = PYTHON_AUDIT_COMMANDS
+            .iter()
+            .find_map(|invocation| command_invocation_segment(normalized, invocation))
+        {
+            let json_output = ["-f json", "-fjson", "--format json", "--format=json"]
+                .iter()
+                .any(|flag| segment.contains(flag));
+            let advisories = json_output
+                .then(|| find_json(&result.output, pip_audit_advisories))
+                .flatten();
+            self.record_audit(result, advisories);
+        }
+    }
+
+    fn observe_go_command(&mut self, result: &TerminalCommandResult, normalized: &str) {
+        let command = result.command.as_str();
+        let succeeded = result.exit_code == Some(0) && !command_masks_failure(command);
+        let invokes_go = command_invokes_executable(normalized, "go");
+        if invokes_go || command_invokes_executable(normalized, "govulncheck") {
+            self.project_observed = true;
+        }
+        if GO_LOCK_CHANGING_COMMANDS
+            .iter()
+            .any(|invocation| command_invocation_segment(normalized, invocation).is_some())
+        {
+            self.record_dependency_change();
+            self.lockfile_verified = false;
+            self.audit = None;
+        }
+        if invokes_go
+            && GO_CHECKSUM_BYPASS_MARKERS
+                .iter()
+                .any(|marker| normalized.contains(marker))
+        {
+            self.record_dependency_change();
+            self.unsafe_commands.push(command.to_string());
+        }
+        if command_invocation_segment(normalized, &["go", "mod", "download"]).is_some() {
+            self.record_install(command, result.exit_code);
+        }
+        if command_invocation_segment(normalized, &["go", "mod", "verify"]).is_some()
+            && succeeded
+            && result.output.contains("all modules verified")
+        {
+            self.lockfile_verified = true;
+        }
+        if let Some(segment) = command_invocation_segment(normalized, &["govulncheck"]) {
+            let json_output = ["-json", "-format json", "-format=json"]
+                .iter()
+                .any(|flag| segment.contains(flag));
+            let advisories = json_output
+                .then(|| govulncheck_advisories(&result.output))
+                .flatten();
+            self.record_audit(result, advisories);
+        }
+    }
+}
+
+impl SupplyChainEvidenceState {
+    fn ecosystem(&self, ecosystem: DependencyEcosystem) -> &EcosystemEvidence {
+        match ecosystem {
+            DependencyEcosystem::Cargo => &self.cargo,
+            DependencyEcosystem::Python => &self.python,
+            DependencyEcosystem::Go => &self.go,
+        }
+    }
+
+    fn ecosystem_mut(&mut self, ecosystem: DependencyEcosystem) -> &mut EcosystemEvidence {
+        match ecosystem {
+            DependencyEcosystem::Cargo => &mut self.cargo,
+            DependencyEcosystem::Python => &mut self.python,
+            DependencyEcosystem::Go => &mut self.go,
+        }
+    }
+
+    pub(super) fn mark_ecosystem_evidence_inherited(&mut self) {
+        for ecosystem in DependencyEcosystem::ALL {
+            self.ecosystem_mut(ecosystem).inherited = true;
+        }
+    }
+
+    pub(super) fn observe_ecosystem_paths(&mut self, payload: &Value) {
+        let committed = payload
+            .get("name")
+            .and_then(Value::as_str)
+            .is_some_and(|name| name.ends_with("commit_edit_session"));
+        let mut paths = Vec::new();
+        collect_strings(payload, &mut paths);
+        for path in paths {
+            let path = path.replace('\\', "/");
+            let normalized = path.to_ascii_lowercase();
+            for ecosystem in DependencyEcosystem::ALL {
+                let is_lockfile = ecosystem.is_lockfile(&normalized);
+                if !is_lockfile && !ecosystem.is_manifest(&normalized) {
+                    continue;
+                }
+                let evidence = self.ecosystem_mut(ecosystem);
+                evidence.project_observed = true;
+                if committed {
+                    evidence.record_dependency_change();
+                    // A hand-edited Python lockfile counts only once a locked
+                    // install or hash check runs against it afterwards.
+                    if is_lockfile && ecosystem == DependencyEcosystem::Python {
+                        evidence.lockfile_verified = false;
+                    }
+                }
+            }
+            if committed && file_name(&normalized) == "build.rs" {
+                self.cargo.project_observed = true;
+                self.cargo.local_build_scripts.insert(path);
+            }
+        }
+    }
+
+    pub(super) fn observe_ecosystem_command(&mut self, result: &TerminalCommandResult) {
+        let normalized = result.command.to_ascii_lowercase();
+        self.cargo.observe_cargo_command(result, &normalized);
+        self.python.observe_python_command(result, &normalized);
+        self.go.observe_go_command(result, &normalized);
+    }
+
+    pub(super) fn evaluate_ecosystem(
+        &self,
+        ecosystem: DependencyEcosystem,
+        policy: &SupplyChainPolicy,
+    ) -> EcosystemAuditReport {
+        let evidence = self.ecosystem(ecosystem);
+        let baseline_revision = policy.node.baseline_revision.clone();
+        if !evidence.project_observed || !evidence.dependency_activity_observed {
+            return EcosystemAuditReport {
+                ecosystem,
+                applicable: false,
+                status: "not_applicable",
+                baseline_revision,
+                lockfile_verified: false,
+                install_command: None,
+                install_exit_code: None,
+                audit_command: None,
+                audit_exit_code: None,
+                advisories: None,
+                lockfile_additions: Vec::new(),
+                build_script_packages_added: Vec::new(),
+                source_built_packages: Vec::new(),
+                local_build_scripts: Vec::new(),
+                blocking_reasons: Vec::new(),
+            };
+        }
+
+        let label = ecosystem.label();
+        let mut blocking_reasons = Vec::new();
+        if !evidence.unsafe_commands.is_empty() {
+            blocking_reasons.push(format!(
+                "{label} dependency commands bypassed lockfile or checksum verification: {}",
+                evidence.unsafe_commands.join("; ")
+            ));
+        }
+        if evidence.install.as_ref().is_some_and(|install| {
+            install.exit_code != Some(0) || command_masks_failure(install.command.as_str())
+        }) {
+            blocking_reasons.push(format!("{label} dependency installation failed"));
+        }
+
+        let mut build_script_packages_added = Vec::new();
+        match ecosystem {
+            DependencyEcosystem::Cargo => match evidence.lockfile_additions.as_ref() {
+                None => blocking_reasons.push(
+                    "Cargo.lock changes were not reviewed with a recorded `git diff -- Cargo.lock`"
+                        .to_string(),
+                ),
+                Some(additions) if !additions.is_empty() => {
+                    match evidence.build_script_packages.as_ref() {
+                        Some(packages) => {
+                            build_script_packages_added = additions
+                                .iter()
+                                .map(|addition| crate_name(addition))
+                                .filter(|name| packages.contains(*name))
+                                .map(ToOwned::to_owned)
+                                .collect::<BTreeSet<_>>()
+                                .into_iter()
+                                .collect();
+                            let unapproved = build_script_packages_added
+                                .iter()
+                                .filter(|name| !policy.cargo.build_script_allowlist.contains(*name))
+                                .cloned()
+                                .collect::<Vec<_>>();
+                            if !unapproved.is_empty() {
+                                blocking_reasons.push(format!(
+                                    "Cargo crates with build scripts or proc-macros were added outside the allowlist: {}",
+                                    unapproved.join(", ")
+                                ));
+                            }
+                        }
+                        None => blocking_reasons.push(
+                            "Cargo build-script and proc-macro crates were not inventoried with `cargo metadata --format-version 1`"
+                                .to_string(),
+                        ),
+                    }
+                }
+                Some(_) => {}
+            },
+            DependencyEcosystem::Python => {
+                if !evidence.lockfile_verified {
+                    blocking_reasons.push(
+                        "Python dependencies were not resolved from a lockfile or hash-checked requirements"
+                            .to_string(),
+                    );
+                }
+                let index_url = policy.python.index_url.trim();
+                if let Some(install) = evidence.install.as_ref() {
+                    let install_command = install.command.to_ascii_lowercase();
+                    if !index_url.is_empty()
+                        && PIP_INSTALL_COMMANDS.iter().any(|invocation| {
+                            command_invocation_segment(&install_command, invocation).is_some()
+                        })
+                        && !command_uses_index(install.command.as_str(), index_url)
+                    {
+                        blocking_reasons.push(format!(
+                            "Python dependency installation did not use the configured index `{index_url}`"
+                        ));
+                    }
+                }
+                let unapproved = evidence
+                    .source_built_packages
+                    .difference(&policy.python.source_build_allowlist)
+                    .cloned()
+                    .collect::<Vec<_>>();
+                if !unapproved.is_empty() {
+                    blocking_reasons.push(format!(
+                        "Python packages were built from source outside the allowlist: {}",
+                        unapproved.join(", ")
+                    ));
+                }
+            }
+            DependencyEcosystem::Go => {
+                if !evidence.lockfile_verified {
+                    blocking_reasons.push(
+                        "Go module checksums were not verified with `go mod verify`".to_string(),
+                    );
+                }
+            }
+        }
+
+        match evidence.audit.as_ref() {
+            Some(audit) if command_masks_failure(audit.command.as_str()) => {
+                blocking_reasons.push(format!(
+                    "{label} dependency audit command masked its failure status"
+                ));
+            }
+            Some(audit) if audit.output_truncated => {
+                blocking_reasons.push(format!(
+                    "{label} dependency audit JSON output was truncated"
+                ));
+            }
+            Some(audit) => match audit.advisories.as_ref() {
+                None => blocking_reasons.push(format!(
+                    "{label} dependency audit output was not complete `{}` JSON",
+                    ecosystem.audit_command_hint()
+                )),
+                Some(advisories) if !advisories.is_empty() => {
+                    blocking_reasons.push(format!(
+                        "{label} dependency audit found {} advisories: {}",
+                        advisories.len(),
+                        advisories.join(", ")
+                    ));
+                }
+                Some(_) if audit.exit_code != Some(0) => {
+                    blocking_reasons.push(format!(
+                        "{label} dependency audit exited with code {}",
+                        audit
+                            .exit_code
+                            .map(|code| code.to_string())
+                            .unwrap_or_else(|| "unknown".to_string())
+                    ));
+                }
+                Some(_) => {}
+            },
+            None => blocking_reasons.push(format!(
+                "{label} dependency audit was not executed with recorded evidence"
+            )),
+        }
+
+        EcosystemAuditReport {
+            ecosystem,
+            applicable: true,
+            status: if blocking_reasons.is_empty() {
+                "passed"
+            } else {
+                "blocked"
+            },
+            baseline_revision,
+            lockfile_verified: evidence.lockfile_verified,
+            install_command: evidence.install.as_ref().map(|item| item.command.clone()),
+            install_exit_code: evidence.install.as_ref().and_then(|item| item.exit_code),
+            audit_command: evidence.audit.as_ref().map(|item| item.command.clone()),
+            audit_exit_code: evidence.audit.as_ref().and_then(|item| item.exit_code),
+            advisories: evidence
+                .audit
+                .as_ref()
+                .and_then(|item| item.advisories.clone()),
+            lockfile_additions: evidence.lockfile_additions.clone().unwrap_or_default(),
+            build_script_packages_added,
+            source_built_packages: evidence.source_built_packages.iter().cloned().collect(),
+            local_build_scripts: evidence.local_build_scripts.iter().cloned().collect(),
+            blocking_reasons,
+        }
+    }
+}
+
+impl EcosystemAuditReport {
+    pub(super) fn evidence_summary(&self) -> String {
+        let label = self.ecosystem.label();
+        let Some(advisories) = self.advisories.as_ref() else {
+            return format!(
+                "{label} supply-chain audit status: {}; baseline {}; lockfile verified={}; audit evidence incomplete",
+                self.status, self.baseline_revision, self.lockfile_verified
+            );
+        };
+        format!(
+            "{label} supply-chain audit status: {}; baseline {}; lockfile verified={}; command `{}` exited {}; advisories={}",
+            self.status,
+            self.baseline_revision,
+            self.lockfile_verified,
+            self.audit_command.as_deref().unwrap_or("not executed"),
+            self.audit_exit_code
+                .map(|code| code.to_string())
+                .unwrap_or_else(|| "unknown".to_string()),
+            advisories.len(),
+        )
+    }
+
+    pub(super) fn event_payload(&self) -> Value {
+        serde_json::to_value(self).unwrap_or_else(|_| json!({"status": "serialization_failed"}))
+    }
+}
+
+pub(super) fn policy_guidance(policy: &SupplyChainPolicy) -> String {
+    let cargo_allowlist = allowlist_summary(&policy.cargo.build_script_allowlist);
+    let python_allowlist = allowlist_summary(&policy.python.source_build_allowlist);
+    let index = policy.python.index_url.trim();
+    let index = if index.is_empty() {
+        String::new()
+    } else {
+        format!(" with `--index-url {index}`")
+    };
+    format!(
+        "[Cargo, Python and Go supply-chain requirements]\nWhen Rust, Python or Go dependencies change, the same baseline revision `{}` applies. Cargo: after the final Cargo.toml/Cargo.lock change, show the lockfile diff with `git diff -- Cargo.lock`, inventory build-script and proc-macro crates with `cargo metadata --format-version 1 | jq -c '[.packages[] | select(any(.targets[].kind[]; . == \"custom-build\" or . == \"proc-macro\")) | .name]'`, only add new crates with build scripts or proc-macros from this allowlist: {cargo_allowlist}, and finish with `cargo audit --json`. Python: install only from a lockfile or hash-checked requirements (`uv sync --locked`, `poetry install`, or `pip install --require-hashes -r requirements.txt`{index}), build packages from source only for: {python_allowlist}, and finish with `pip-audit -f json`. Go: never disable GOSUMDB or fetch modules insecurely, run `go mod verify` after the final go.mod/go.sum change, and finish with `govulncheck -json ./...`. These audits must run without masking their exit status, produce complete JSON output, and report zero advisories.",
+        policy.node.baseline_revision,
+    )
+}
+
+fn allowlist_summary(allowlist: &BTreeSet<String>) -> String {
+    if allowlist.is_empty() {
+        "none".to_string()
+    } else {
+        allowlist.iter().cloned().collect::<Vec<_>>().join(", ")
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+fn collect_strings<'a>(value: &'a Value, strings: &mut Vec<&'a str>) {
+    match value {
+        Value::String(value) => strings.push(value),
+        Value::Array(items) => items.iter().for_each(|item| collect_strings(item, strings)),
+        Value::Object(map) => map.values().for_each(|item| collect_strings(item, strings)),
+        _ => {}
+    }
+}
+
+fn crate_name(addition: &str) -> &str {
+    addition.split_once('@').map_or(addition, |(name, _)| name)
+}
+
+fn command_uses_index(command: &str, expected_index: &str) -> bool {
+    let expected_index = expected_index.trim_end_matches('/');
+    let tokens = command
+        .split_whitespace()
+        .map(|token| token.trim_matches(|character| matches!(character, '\'' | '"')))
+        .collect::<Vec<_>>();
+    tokens.windows(2).any(|pair| {
+        matches!(pair[0], "-i" | "--index-url" | "--default-index")
+            && pair[1].trim_end_matches('/') == expected_index
+    }) || tokens.iter().any(|token| {
+        token
+            .strip_prefix("--index-url=")
+            .or_else(|| token.strip_prefix("--default-index="))
+            .is_some_and(|index| index.trim_end_matches('/') == expected_index)
+    })
+}
+
+/// Returns `name@version` for every package version a Cargo.lock diff adds,
+/// including version bumps of packages that were already locked.
+fn cargo_lockfile_additions(diff: &str) -> Vec<String> {
+    let mut additions = BTreeSet::new();
+    let mut current_name = None;
+    for line in diff.lines() {
+        if line.starts_with("@@") || line.starts_with("diff --git") {
+            current_name = None;
+            continue;
+        }
+        if line.starts_with("+++") || line.starts_with("---") {
+            continue;
+        }
+        let Some(marker) = line.chars().next().filter(|c| matches!(c, '+' | '-' | ' ')) else {
+            continue;
+        };
+        let body = line[1..].trim();
+        if body == "[[package]]" {
+            current_name = None;
+            continue;
+        }
+        if let Some(name) = toml_string_value(body, "name") {
+            current_name = (marker != '-').then_some(name);
+            continue;
+        }
+        if marker != '+' {
+            continue;
+        }
+        if let (Some(version), Some(name)) = (toml_string_value(body, "version"), &current_name) {
+            additions.insert(format!("{name}@{version}"));
+        }
+    }
+    additions.into_iter().collect()
+}
+
+fn toml_string_value(line: &str, key: &str) -> Option<String> {
+    let value = line
+        .strip_prefix(key)?
+        .trim_start()
+        .strip_prefix('=')?
+        .trim();
+    value
+        .strip_prefix('"')?
+        .strip_suffix('"')
+        .map(ToOwned::to_owned)
+}
+
+/// Accepts either full `cargo metadata` output or a JSON array of crate names
+/// already filtered down to build-script and proc-macro packages.
+fn build_script_packages(value: &Value) -> Option<BTreeSet<String>> {
+    if let Some(names) = value.as_array() {
+        return names
+            .iter()
+            .map(|name| name.as_str().map(ToOwned::to_owned))
+            .collect();
+    }
+    let packages = value.get("packages")?.as_array()?;
+    Some(
+        packages
+            .iter()
+            .filter(|package| {
+                package
+                    .get("targets")
+                    .and_then(Value::as_array)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|target| target.get("kind").and_then(Value::as_array))
+                    .flatten()
+                    .filter_map(Value::as_str)
+                    .any(|kind| matches!(kind, "custom-build" | "proc-macro"))
+            })
+            .filter_map(|package| package.get("name").and_then(Value::as_str))
+            .map(ToOwned::to_owned)
+            .collect(),
+    )
+}
+
+fn cargo_audit_advisories(value: &Value) -> Option<Vec<String>> {
+    let vulnerabilities = value.get("vulnerabilities")?;
+    vulnerabilities.get("count")?.as_u64()?;
+    vulnerabilities
+        .get("list")?
+        .as_array()?
+        .iter()
+        .map(|item| {
+            let id = item.pointer("/advisory/id")?.as_str()?;
+            let name = item.pointer("/package/name")?.as_str()?;
+            let version = item
+                .pointer("/package/version")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown");
+            Some(format!("{id} ({name}@{version})"))
+        })
+        .collect()
+}
+
+fn pip_audit_advisories(value: &Value) -> Option<Vec<String>> {
+    let dependencies = value.get("dependencies").unwrap_or(value).as_array()?;
+    let mut advisories = Vec::new();
+    for dependency in dependencies {
+        let name = dependency.get("name")?.as_str()?;
+        let version = dependency
+            .get("version")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        for vulnerability in dependency
+            .get("vulns")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let id = vulnerability.get("id")?.as_str()?;
+            advisories.push(format!("{id} ({name}@{version})"));
+        }
+    }
+    Some(advisories)
+}
+
+/// govulncheck streams one JSON message per line; only findings whose trace
+/// reaches a function are reachable from the module's code.
+fn govulncheck_advisories(output: &str) -> Option<Vec<String>> {
+    let start = output.find('{')?;
+    let mut config_observed = false;
+    let mut advisories = BTreeSet::new();
+    for value in serde_json::Deserializer::from_str(&output[start..]).into_iter::<Value>() {
+        let Ok(value) = value else {
+            break;
+        };
+        if value.get("config").is_some() {
+            config_observed = true;
+        }
+        let Some(finding) = value.get("finding") else {
+            continue;
+        };
+        let called = finding
+            .get("trace")
+            .and_then(Value::as_array)
+            .and_then(|trace| trace.first())
+            .and_then(|frame| frame.get("function"))
+            .and_then(Value::as_str)
+            .is_some_and(|function| !function.is_empty());
+        if called {
+            advisories.insert(finding.get("osv")?.as_str()?.to_string());
+        }
+    }
+    config_observed.then(|| advisories.into_iter().collect())
+}
+
+fn python_source_builds(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let name = if let Some(rest) = line.strip_prefix("Building wheel for ") {
+                rest.split_whitespace().next()?
+            } else {
+                let rest = line.strip_prefix("Built ")?;
+                let name = rest.split_whitespace().next()?;
+                name.split_once("==").map_or(name, |(name, _)| name)
+            };
+            Some(name.to_ascii_lowercase().replace(['_', '.'], "-"))
+        })
+        .collect()
+}
+
+fn find_json<T>(output: &str, extract: impl Fn(&Value) -> Option<T>) -> Option<T> {
+    if let Ok(value) = serde_json::from_str::<Value>(output.trim()) {
+        if let Some(found) = extract(&value) {
+            return Some(found);
+        }
+    }
+    for (start, character) in output.char_indices() {
+        if !matches!(character, '{' | '[') {
+            continue;
+        }
+        let mut values = serde_json::Deserializer::from_str(&output[start..]).into_iter::<Value>();
+        let Some(Ok(value)) = values.next() else {
+            continue;
+        };
+        if let Some(found) = extract(&value) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::run_model_phase::supply_chain::NodeSupplyChainPolicy;
+
+    fn policy() -> SupplyChainPolicy {
+        SupplyChainPolicy {
+            node: NodeSupplyChainPolicy {
+                baseline_revision: "baseline-2026-08".to_string(),
+                dependency_requirements: Default::default(),
+                audit_level: "high".to_string(),
+                install_script_allowlist: BTreeSet::new(),
+                install_registry: String::new(),
+                audit_registry: String::new(),
+            },
+            cargo: CargoSupplyChainPolicy {
+                build_script_allowlist: BTreeSet::from(["serde_derive".to_string()]),
+            },
+            python: PythonSupplyChainPolicy {
+                index_url: "https://pypi.internal/simple".to_string(),
+                source_build_allowlist: BTreeSet::new(),
+            },
+            blocking_ecosystems: BTreeSet::new(),
+        }
+    }
+
+    fn terminal_result(command: &str, exit_code: i64, output: &str) -> Value {
+        json!({
+            "name": "sandbox_terminal_controller_execute_command",
+            "success": exit_code == 0,
+            "is_error": exit_code != 0,
+            "result": {
+                "common": command,
+                "exit_code": exit_code,
+                "output": output,
+            }
+        })
+    }
+
+    fn committed(path: &str) -> Value {
+        json!({
+            "name": "code_maintainer_write_commit_edit_session",
+            "success": true,
+            "is_error": false,
+            "result": { "committed_paths": [{ "path": path }] }
+        })
+    }
+
+    const CARGO_LOCK_DIFF: &str = r#"diff --git a/Cargo.lock b/Cargo.lock
+--- a/Cargo.lock
++++ b/Cargo.lock
+@@ -10,6 +10,18 @@
++[[package]]
++name = "openssl-sys"
++version = "0.9.104"
++source = "registry+https://github.com/rust-lang/crates.io-index"
++
++[[package]]
++name = "serde_derive"
++version = "1.0.217"
++
+ [[package]]
+ name = "tokio"
+-version = "1.41.0"
++version = "1.42.0"
+"#;
+
+    #[test]
+    fn cargo_lock_additions_require_metadata_and_allowlisted_build_scripts() {
+        let mut evidence = SupplyChainEvidenceState::default();
+        evidence.observe_tool_result(&committed("Cargo.toml"));
+        evidence.observe_tool_result(&terminal_result(
+            "git diff -- Cargo.lock",
+            0,
+            CARGO_LOCK_DIFF,
+        ));
+        evidence.observe_tool_result(&terminal_result(
+            "cargo audit --json",
+            0,
+            r#"{"database":{},"vulnerabilities":{"found":false,"count":0,"list":[]}}"#,
+        ));
+
+        let report = evidence.evaluate_ecosystem(DependencyEcosystem::Cargo, &policy());
+        assert_eq!(report.status, "blocked");
+        assert_eq!(
+            report.lockfile_additions,
+            vec![
+                "openssl-sys@0.9.104".to_string(),
+                "serde_derive@1.0.217".to_string(),
+                "tokio@1.42.0".to_string(),
+            ]
+        );
+        assert!(report
+            .blocking_reasons
+            .iter()
+            .any(|reason| reason.contains("were not inventoried")));
+
+        evidence.observe_tool_result(&terminal_result(
+            "cargo metadata --format-version 1 | jq -c '[.packages[] | .name]'",
+            0,
+            r#"["openssl-sys","serde_derive"]"#,
+        ));
+        let report = evidence.evaluate_ecosystem(DependencyEcosystem::Cargo, &policy());
+        assert_eq!(report.status, "blocked");
+        assert_eq!(
+            report.build_script_packages_added,
+            vec!["openssl-sys".to_string(), "serde_derive".to_string()]
+        );
+        assert_eq!(
+            report.blocking_reasons,
+            vec![
+                "Cargo crates with build scripts or proc-macros were added outside the allowlist: openssl-sys"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn clean_cargo_audit_passes_and_advisories_block() {
+        let mut evidence = SupplyChainEvidenceState::default();
+        evidence.observe_tool_result(&terminal_result("cargo add tokio", 0, ""));
+        evidence.observe_tool_result(&terminal_result(
+            "git diff HEAD -- Cargo.lock",
+            0,
+            " [[package]]\n name = \"tokio\"\n-version = \"1.41.0\"\n+version = \"1.42.0\"\n",
+        ));
+        evidence.observe_tool_result(&terminal_result(
+            "cargo metadata --format-version 1",
+            0,
+            r#"{"packages":[{"name":"tokio","targets":[{"kind":["lib"]}]},{"name":"app","targets":[{"kind":["custom-build"]}]}]}"#,
+        ));
+        evidence.observe_tool_result(&terminal_result(
+            "cargo audit --json",
+            0,
+            r#"{"vulnerabilities":{"found":false,"count":0,"list":[]}}"#,
+        ));
+        let report = evidence.evaluate_ecosystem(DependencyEcosystem::Cargo, &policy());
+        assert_eq!(report.status, "passed");
+        assert!(report.evidence_summary().starts_with(
+            "Cargo supply-chain audit status: passed; baseline baseline-2026-08; lockfile verified=true"
+        ));
+
+        evidence.observe_tool_result(&terminal_result(
+            "cargo audit --json",
+            1,
+            r#"{"vulnerabilities":{"found":true,"count":1,"list":[{"advisory":{"id":"RUSTSEC-2025-0001"},"package":{"name":"tokio","version":"1.42.0"}}]}}"#,
+        ));
+        let report = evidence.evaluate_ecosystem(DependencyEcosystem::Cargo, &policy());
+        assert_eq!(report.status, "blocked");
+        assert_eq!(
+            report.blocking_reasons,
+            vec![
+                "Cargo dependency audit found 1 advisories: RUSTSEC-2025-0001 (tokio@1.42.0)"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn python_installs_must_be_locked_and_audited() {
+        let mut evidence = SupplyChainEvidenceState::default();
+        evidence.observe_tool_result(&committed("requirements.txt"));
+        evidence.observe_tool_result(&terminal_result(
+            "pip install -r requirements.txt",
+            0,
+            "Building wheel for PyYAML (pyproject.toml) ... done",
+        ));
+        evidence.observe_tool_result(&terminal_result(
+            "pip-audit -r requirements.txt || true",
+            0,
+            "No known vulnerabilities found",
+        ));
+
+        let report = evidence.evaluate_ecosystem(DependencyEcosystem::Python, &policy());
+        assert_eq!(report.status, "blocked");
+        for expected in [
+            "bypassed lockfile or checksum verification",
+            "not resolved from a lockfile",
+            "did not use the configured index",
+            "built from source outside the allowlist: pyyaml",
+            "masked its failure status",
+        ] {
+            assert!(
+                report
+                    .blocking_reasons
+                    .iter()
+                    .any(|reason| reason.contains(expected)),
+                "missing {expected}: {:?}",
+                report.blocking_reasons
+            );
+        }
+
+        let mut evidence = SupplyChainEvidenceState::default();
+        evidence.observe_tool_result(&committed("uv.lock"));
+        evidence.observe_tool_result(&terminal_result(
+            "uv sync --locked",
+            0,
+            "Installed 3 packages",
+        ));
+        evidence.observe_tool_result(&terminal_result(
+            "uvx pip-audit -f json",
+            0,
+            r#"{"dependencies":[{"name":"requests","version":"2.32.3","vulns":[]},{"name":"app","skip_reason":"local"}],"fixes":[]}"#,
+        ));
+        let report = evidence.evaluate_ecosystem(DependencyEcosystem::Python, &policy());
+        assert_eq!(report.status, "passed", "{:?}", report.blocking_reasons);
+        assert_eq!(report.advisories, Some(Vec::new()));
+    }
+
+    #[test]
+    fn python_lockfile_counts_only_after_an_enforced_install() {
+        let clean_audit = terminal_result(
+            "pip-audit -f json",
+            0,
+            r#"{"dependencies":[{"name":"requests","version":"2.32.3","vulns":[]}],"fixes":[]}"#,
+        );
+        let not_locked = |evidence: &SupplyChainEvidenceState| {
+            let report = evidence.evaluate_ecosystem(DependencyEcosystem::Python, &policy());
+            !report.lockfile_verified
+                && report
+                    .blocking_reasons
+                    .iter()
+                    .any(|reason| reason.contains("not resolved from a lockfile"))
+        };
+
+        let mut evidence = SupplyChainEvidenceState::default();
+        evidence.observe_tool_result(&committed("uv.lock"));
+        evidence.observe_tool_result(&clean_audit);
+        assert!(not_locked(&evidence));
+
+        evidence.observe_tool_result(&terminal_result("uv sync", 0, "Installed 3 packages"));
+        evidence.observe_tool_result(&clean_audit);
+        assert!(not_locked(&evidence));
+
+        let mut evidence = SupplyChainEvidenceState::default();
+        evidence.observe_tool_result(&terminal_result(
+            "uv sync --locked",
+            0,
+            "Installed 3 packages",
+        ));
+        evidence.observe_tool_result(&committed("uv.lock"));
+        evidence.observe_tool_result(&clean_audit);
+        assert!(not_locked(&evidence));
+    }
+
+    #[test]
+    fn go_modules_require_checksum_verification_and_reachable_findings_block() {
+        let mut evidence = SupplyChainEvidenceState::default();
+        evidence.observe_tool_result(&terminal_result(
+            "GOSUMDB=off go get example.com/lib@v1.2.0",
+            0,
+            "",
+        ));
+        evidence.observe_tool_result(&terminal_result(
+            "govulncheck -json ./...",
+            0,
+            concat!(
+                "{\"config\":{\"protocol_version\":\"v1.0.0\"}}\n",
+                "{\"finding\":{\"osv\":\"GO-2025-0001\",\"trace\":[{\"module\":\"example.com/lib\"}]}}\n",
+                "{\"finding\":{\"osv\":\"GO-2025-0002\",\"trace\":[{\"module\":\"example.com/lib\",\"function\":\"Parse\"}]}}\n",
+            ),
+        ));
+
+        let report = evidence.evaluate_ecosystem(DependencyEcosystem::Go, &policy());
+        assert_eq!(report.status, "blocked");
+        assert_eq!(report.advisories, Some(vec!["GO-2025-0002".to_string()]));
+        assert!(report
+            .blocking_reasons
+            .iter()
+            .any(|reason| reason.contains("GOSUMDB=off")));
+        assert!(report
+            .blocking_reasons
+            .iter()
+            .any(|reason| reason.contains("go mod verify")));
+
+        let mut evidence = SupplyChainEvidenceState::default();
+        evidence.observe_tool_result(&committed("go.sum"));
+        evidence.observe_tool_result(&terminal_result("go mod verify", 0, "all modules verified"));
+        evidence.observe_tool_result(&terminal_result(
+            "govulncheck -json ./...",
+            0,
+            "{\"config\":{\"protocol_version\":\"v1.0.0\"}}\n",
+        ));
+        assert_eq!(
+            evidence
+                .evaluate_ecosystem(DependencyEcosystem::Go, &policy())
+                .status,
+            "passed"
+        );
+    }
+
+    #[test]
+    fn read_only_inspection_and_builds_do_not_make_ecosystems_applicable() {
+        let mut evidence = SupplyChainEvidenceState::default();
+        evidence.observe_tool_result(&json!({
+            "name": "code_maintainer_read_list_dir",
+            "success": true,
+            "is_error": false,
+            "result": {
+                "entries": [
+                    { "path": "Cargo.toml" },
+                    { "path": "pyproject.toml" },
+                    { "path": "go.sum" }
+                ]
+            }
+        }));
+        evidence.observe_tool_result(&terminal_result("cargo test --locked", 0, ""));
+        evidence.observe_tool_result(&terminal_result("go test ./...", 0, ""));
+
+        for ecosystem in DependencyEcosystem::ALL {
+            let report = evidence.evaluate_ecosystem(ecosystem, &policy());
+            assert!(!report.applicable, "{ecosystem:?}");
+            assert_eq!(report.status, "not_applicable");
+        }
+    }
+}
@@ -128,25 +128,37 @@ impl RunService {
         else {
             return Ok(None);
         };
+        let event = TaskRunEventRecord::new(
+            run.id.clone(),
+            "integration_waived",
+            Some(format!("已放弃该可选任务的代码变更：{reason}")),
+            Some(serde_json::json!({
+                "reason": reason,
+                "execution_group_id": run.workspace_execution.as_ref()
+                    .and_then(|execution| execution.execution_group_id.clone()),
+                "result_commit": run.workspace_execution.as_ref()
+                    .and_then(|execution| execution.result_commit.clone()),
+            })),
+        );
+        self.finish_integration_waiver(&mut task, run, event)
+            .await
+            .map(Some)
+    }
+
+    /// Completes the Task of a Run whose integration was waived in the store
+    /// and resumes its post-processing.
+    pub(in crate::services) async fn finish_integration_waiver(
+        &self,
+        task: &mut crate::models::TaskRecord,
+        run: TaskRunRecord,
+        event: TaskRunEventRecord,
+    ) -> Result<TaskRunRecord, String> {
         task.status = TaskStatus::Succeeded;
         task.result_summary = run.result_summary.clone();
         task.last_run_id = Some(run.id.clone());
         task.updated_at = now_rfc3339();
         self.store.save_task(task.clone()).await?;
-        self.store
-            .append_run_event(TaskRunEventRecord::new(
-                run.id.clone(),
-                "integration_waived",
-                Some(format!("已放弃该可选任务的代码变更：{reason}")),
-                Some(serde_json::json!({
-                    "reason": reason,
-                    "execution_group_id": run.workspace_execution.as_ref()
-                        .and_then(|execution| execution.execution_group_id.clone()),
-                    "result_commit": run.workspace_execution.as_ref()
-                        .and_then(|execution| execution.result_commit.clone()),
-                })),
-            ))
-            .await?;
+        self.store.append_run_event(event).await?;
         self.try_send_terminal_callback(task.id.as_str(), &run)
             .await;
         if let Err(error) = self.enqueue_run_post_process_if_needed(&run).await {
@@ -156,7 +168,7 @@ impl RunService {
                 "failed to publish waived Run post-process event; Outbox reconciliation will retry"
             );
         }
-        Ok(Some(self.store.get_run(run_id).await?.unwrap_or(run)))
+        Ok(self.store.get_run(run.id.as_str()).await?.unwrap_or(run))
     }
 
     pub async fn retry_run_workspace_integration(
@@ -352,6 +364,7 @@ impl RunService {
                 Some(WorkspaceIntegrationStatus::Conflict) => {
                     self.finish_run_after_integration(&task, &mut run, TaskRunStatus::Blocked)
                         .await?;
+                    self.hand_off_conflict_if_configured(&run).await?;
                     self.store.mark_run_post_process_completed(run_id).await?;
                     return Ok(());
                 }
@@ -378,6 +391,7 @@ impl RunService {
             return Ok(());
         }
 
+        self.complete_handed_off_conflict(&task, &run).await?;
         self.promote_execution_group_if_complete(&task, &mut run)
             .await?;
 
@@ -818,6 +832,7 @@ mod tests {
                 local_patch_truncated: false,
                 conflict_files: Vec::new(),
                 conflict_message: None,
+                conflict_handoff: None,
                 integration_last_error: None,
                 prepared_at: None,
                 finalized_at: None,
@@ -972,4 +987,156 @@ mod tests {
             .iter()
             .any(|event| event.event_type == "integration_waived"));
     }
+
+    #[tokio::test]
+    async fn user_conflict_handoff_keeps_run_blocked_and_is_recorded_once() {
+        let (task_service, run_service, store) = waiver_services().await;
+        let task = create_waiver_task(&task_service, &store, true).await;
+        save_conflicted_run(&store, task.id.as_str(), true).await;
+
+        let handed_off = run_service
+            .hand_off_run_workspace_conflict(
+                "waiver-run",
+                crate::models::WorkspaceConflictResolver::User,
+                Some("  keep both config keys  "),
+            )
+            .await
+            .expect("hand off conflict")
+            .expect("handed off run");
+        assert_eq!(handed_off.status, TaskRunStatus::Blocked);
+        let handoff = handed_off
+            .workspace_execution
+            .as_ref()
+            .and_then(|execution| execution.conflict_handoff.as_ref())
+            .expect("handoff");
+        assert_eq!(
+            handoff.resolver,
+            crate::models::WorkspaceConflictResolver::User
+        );
+        assert_eq!(
+            handoff.instructions.as_deref(),
+            Some("keep both config keys")
+        );
+        assert!(handoff.resolution_task_id.is_none());
+
+        let error = run_service
+            .hand_off_run_workspace_conflict(
+                "waiver-run",
+                crate::models::WorkspaceConflictResolver::Agent,
+                None,
+            )
+            .await
+            .expect_err("second handoff is rejected");
+        assert!(error.contains("已经移交"));
+        let events = store
+            .list_run_events("waiver-run")
+            .await
+            .expect("list events");
+        assert!(events
+            .iter()
+            .any(|event| event.event_type == "integration_conflict_handed_off"));
+
+        let retried = store
+            .rearm_run_workspace_integration("waiver-run")
+            .await
+            .expect("rearm integration")
+            .expect("retried run");
+        assert!(retried
+            .workspace_execution
+            .expect("workspace")
+            .conflict_handoff
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn agent_conflict_handoff_completes_origin_run_when_resolution_integrates() {
+        let (task_service, run_service, store) = waiver_services().await;
+        let task = create_waiver_task(&task_service, &store, true).await;
+        save_conflicted_run(&store, task.id.as_str(), true).await;
+
+        let handed_off = run_service
+            .hand_off_run_workspace_conflict(
+                "waiver-run",
+                crate::models::WorkspaceConflictResolver::Agent,
+                None,
+            )
+            .await
+            .expect("hand off conflict")
+            .expect("handed off run");
+        let resolution_task_id = handed_off
+            .workspace_execution
+            .as_ref()
+            .and_then(|execution| execution.conflict_handoff.as_ref())
+            .and_then(|handoff| handoff.resolution_task_id.clone())
+            .expect("resolution task id");
+        let resolution = store
+            .get_task(resolution_task_id.as_str())
+            .await
+            .expect("load resolution task")
+            .expect("resolution task");
+        assert_eq!(resolution.status, TaskStatus::Ready);
+        assert!(resolution.objective.contains("src/main.rs"));
+        assert_eq!(
+            resolution.task_tool_state.idempotency_key.as_deref(),
+            Some("integration-conflict:waiver-run")
+        );
+        assert_eq!(
+            resolution
+                .input_payload
+                .as_ref()
+                .and_then(|payload| payload.get("execution_group_id"))
+                .and_then(serde_json::Value::as_str),
+            Some("group-1")
+        );
+
+        let mut resolution_run = run(
+            "resolution-run",
+            "2026-08-15T11:00:00Z",
+            Some(WorkspaceIntegrationStatus::Integrated),
+        );
+        resolution_run.task_id = resolution.id.clone();
+        run_service
+            .complete_handed_off_conflict(&resolution, &resolution_run)
+            .await
+            .expect("complete handed off conflict");
+
+        let origin = store
+            .get_run("waiver-run")
+            .await
+            .expect("load origin run")
+            .expect("origin run");
+        assert_eq!(origin.status, TaskRunStatus::Succeeded);
+        let integration = origin.workspace_execution.as_ref().expect("workspace");
+        assert_eq!(
+            integration.integration_status,
+            WorkspaceIntegrationStatus::Waived
+        );
+        assert!(integration
+            .waiver_reason
+            .as_deref()
+            .is_some_and(|reason| reason.contains(resolution.id.as_str())));
+        let origin_task = task_service
+            .get_task(task.id.as_str())
+            .await
+            .expect("load task")
+            .expect("task");
+        assert_eq!(origin_task.status, TaskStatus::Succeeded);
+
+        resolution_run.status = TaskRunStatus::Failed;
+        run_service
+            .complete_handed_off_conflict(&resolution, &resolution_run)
+            .await
+            .expect("completion is idempotent");
+        let events = store
+            .list_run_events("waiver-run")
+            .await
+            .expect("list events");
+        assert_eq!(
+            events
+                .iter()
+                .filter(|event| event.event_type == "integration_conflict_resolved")
+                .count(),
+            1
+        );
+    }
 }
@@ -28,6 +28,7 @@ impl RunService {
             start_locks: Arc::new(KeyedAsyncLockRegistry::default()),
             callback_delivery_locks: Arc::new(KeyedAsyncLockRegistry::default()),
             runtime_abort_tokens: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            ai_cassette: None,
         }
     }
 
@@ -51,9 +52,19 @@ impl RunService {
             start_locks: Arc::new(KeyedAsyncLockRegistry::default()),
             callback_delivery_locks: Arc::new(KeyedAsyncLockRegistry::default()),
             runtime_abort_tokens: Arc::new(parking_lot::Mutex::new(HashMap::new())),
+            ai_cassette: None,
         }
     }
 
+    /// Routes every model request of this service through `cassette`, so model
+    /// phase steps can be recorded or replayed without a provider. Without
+    /// one, requests use the cassette configured through the environment.
+    #[cfg(test)]
+    pub(crate) fn with_ai_cassette(mut self, cassette: Arc<chatos_ai_runtime::AiCassette>) -> Self {
+        self.ai_cassette = Some(cassette);
+        self
+    }
+
     pub(super) async fn effective_task_execution_max_iterations(&self) -> Result<usize, String> {
         Ok(self
             .effective_task_runner_runtime_settings()
@@ -81,12 +92,56 @@ impl RunService {
         require_managed_u64(&snapshot, TASK_RUNNER_AI_READ_TIMEOUT_CONFIG_KEY, 1)
     }
 
-    pub(super) async fn effective_node_supply_chain_policy(
+    pub(super) async fn effective_supply_chain_policy(
         &self,
-    ) -> Result<super::run_model_phase::supply_chain::NodeSupplyChainPolicy, String> {
+    ) -> Result<super::run_model_phase::supply_chain::SupplyChainPolicy, String> {
         let snapshot = load_managed_config_snapshot().await?;
-        let audit_level = require_managed_string(
+        let blocking_ecosystems = optional_managed_string_set(
             &snapshot,
+            TASK_RUNNER_SUPPLY_CHAIN_BLOCKING_ECOSYSTEMS_CONFIG_KEY,
+        )?
+        .into_iter()
+        .map(|ecosystem| ecosystem.to_ascii_lowercase())
+        .collect::<std::collections::BTreeSet<_>>();
+        if let Some(unknown) = blocking_ecosystems
+            .iter()
+            .find(|ecosystem| !matches!(ecosystem.as_str(), "node" | "cargo" | "python" | "go"))
+        {
+            return Err(format!(
+                "managed configuration key {} contains unknown ecosystem {unknown}",
+                TASK_RUNNER_SUPPLY_CHAIN_BLOCKING_ECOSYSTEMS_CONFIG_KEY
+            ));
+        }
+        Ok(super::run_model_phase::supply_chain::SupplyChainPolicy {
+            node: Self::node_supply_chain_policy(&snapshot)?,
+            cargo: super::run_model_phase::supply_chain::CargoSupplyChainPolicy {
+                build_script_allowlist: optional_managed_string_set(
+                    &snapshot,
+                    TASK_RUNNER_SUPPLY_CHAIN_CARGO_BUILD_SCRIPT_ALLOWLIST_CONFIG_KEY,
+                )?,
+            },
+            python: super::run_model_phase::supply_chain::PythonSupplyChainPolicy {
+                index_url: optional_managed_string(
+                    &snapshot,
+                    TASK_RUNNER_SUPPLY_CHAIN_PYTHON_INDEX_URL_CONFIG_KEY,
+                )?,
+                source_build_allowlist: optional_managed_string_set(
+                    &snapshot,
+                    TASK_RUNNER_SUPPLY_CHAIN_PYTHON_SOURCE_BUILD_ALLOWLIST_CONFIG_KEY,
+                )?
+                .into_iter()
+                .map(|package| package.to_ascii_lowercase().replace(['_', '.'], "-"))
+                .collect(),
+            },
+            blocking_ecosystems,
+        })
+    }
+
+    fn node_supply_chain_policy(
+        snapshot: &chatos_config_sdk::ConfigSnapshot,
+    ) -> Result<super::run_model_phase::supply_chain::NodeSupplyChainPolicy, String> {
+        let audit_level = require_managed_string(
+            snapshot,
             TASK_RUNNER_SUPPLY_CHAIN_NODE_AUDIT_LEVEL_CONFIG_KEY,
         )?
         .to_ascii_lowercase();
@@ -99,24 +154,24 @@ impl RunService {
         Ok(
             super::run_model_phase::supply_chain::NodeSupplyChainPolicy {
                 baseline_revision: require_managed_string(
-                    &snapshot,
+                    snapshot,
                     TASK_RUNNER_SUPPLY_CHAIN_BASELINE_REVISION_CONFIG_KEY,
                 )?,
                 dependency_requirements: require_managed_string_map(
-                    &snapshot,
+                    snapshot,
                     TASK_RUNNER_SUPPLY_CHAIN_NODE_DEPENDENCY_REQUIREMENTS_CONFIG_KEY,
                 )?,
                 audit_level,
                 install_script_allowlist: require_managed_string_set(
-                    &snapshot,
+                    snapshot,
                     TASK_RUNNER_SUPPLY_CHAIN_INSTALL_SCRIPT_ALLOWLIST_CONFIG_KEY,
                 )?,
                 install_registry: require_managed_string(
-                    &snapshot,
+                    snapshot,
                     TASK_RUNNER_SUPPLY_CHAIN_NODE_INSTALL_REGISTRY_CONFIG_KEY,
                 )?,
                 audit_registry: require_managed_string(
-                    &snapshot,
+                    snapshot,
                     TASK_RUNNER_SUPPLY_CHAIN_NODE_AUDIT_REGISTRY_CONFIG_KEY,
                 )?,
             },
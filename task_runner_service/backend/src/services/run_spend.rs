@@ -0,0 +1,234 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use chatos_ai_runtime::{TaskExecutionOutcome, TaskExecutionOutcomeStatus, UsageTotals};
+use serde_json::{json, Value};
+
+use super::*;
+use crate::models::{RunSpendGroup, RunSpendSummaryRecord};
+
+pub(super) const SPEND_BUDGET_EXCEEDED_FINISH_REASON: &str = "spend_budget_exceeded";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(super) struct TaskRunSpendBudgets {
+    pub(super) run_usd: Option<f64>,
+    pub(super) project_usd: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum SpendBudgetScope {
+    Run,
+    Project,
+}
+
+impl SpendBudgetScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Run => "run",
+            Self::Project => "project",
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Run => "单次运行",
+            Self::Project => "项目",
+        }
+    }
+}
+
+/// A budget the run would cross if it spent as much on its next step as it
+/// did on the last one.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct SpendBudgetStop {
+    pub(super) scope: SpendBudgetScope,
+    pub(super) budget_usd: f64,
+    pub(super) spent_usd: f64,
+}
+
+impl SpendBudgetStop {
+    pub(super) fn blocking_reason(&self) -> String {
+        format!(
+            "已达到{}消费预算：已花费 ${:.4}，预算 ${:.4}",
+            self.scope.label(),
+            self.spent_usd,
+            self.budget_usd
+        )
+    }
+
+    pub(super) fn execution_outcome(&self) -> TaskExecutionOutcome {
+        let reason = self.blocking_reason();
+        TaskExecutionOutcome {
+            status: TaskExecutionOutcomeStatus::Blocked,
+            summary: format!("任务在完成前停止：{reason}"),
+            blocking_reason: Some(reason.clone()),
+            unmet_acceptance_criteria: vec!["任务未能在消费预算内完成".to_string()],
+            verification_evidence: vec![reason],
+            acceptance_evidence: Vec::new(),
+            referenced_paths: Vec::new(),
+            referenced_endpoints: Vec::new(),
+        }
+    }
+
+    fn event_payload(&self) -> Value {
+        json!({
+            "scope": self.scope.as_str(),
+            "budget_usd": self.budget_usd,
+            "spent_usd": self.spent_usd,
+        })
+    }
+}
+
+pub(super) fn exceeded_spend_budget(
+    budgets: &TaskRunSpendBudgets,
+    run_spent_usd: f64,
+    project_spent_usd: Option<f64>,
+    step_cost_usd: f64,
+) -> Option<SpendBudgetStop> {
+    let project_budget = budgets.project_usd.zip(project_spent_usd);
+    [
+        budgets
+            .run_usd
+            .map(|budget| (SpendBudgetScope::Run, budget, run_spent_usd)),
+        project_budget.map(|(budget, spent)| (SpendBudgetScope::Project, budget, spent)),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|(_, budget, spent)| spent + step_cost_usd > *budget)
+    .map(|(scope, budget_usd, spent_usd)| SpendBudgetStop {
+        scope,
+        budget_usd,
+        spent_usd,
+    })
+}
+
+impl RunService {
+    pub(super) async fn effective_spend_budgets(
+        &self,
+        project_id: &str,
+    ) -> Result<TaskRunSpendBudgets, String> {
+        let snapshot = load_managed_config_snapshot().await?;
+        let project_usd =
+            optional_managed_usd_map(&snapshot, TASK_RUNNER_SPEND_PROJECT_BUDGETS_USD_CONFIG_KEY)?
+                .remove(project_id);
+        Ok(TaskRunSpendBudgets {
+            run_usd: optional_managed_usd(&snapshot, TASK_RUNNER_SPEND_RUN_BUDGET_USD_CONFIG_KEY)?,
+            project_usd,
+        })
+    }
+
+    /// Adds one model step's usage to the run and checks the configured
+    /// budgets. Returns the budget that stops the run, if any.
+    pub(super) async fn record_step_spend(
+        &self,
+        run_id: &str,
+        usage: &UsageTotals,
+    ) -> Result<Option<SpendBudgetStop>, String> {
+        if usage.is_empty() {
+            return Ok(None);
+        }
+        let Some(run) = self.store.get_run(run_id).await? else {
+            return Ok(None);
+        };
+        let task = self
+            .store
+            .get_task(run.task_id.as_str())
+            .await?
+            .ok_or_else(|| format!("Task not found: {}", run.task_id))?;
+        let owner_user_id = task
+            .owner_user_id
+            .as_deref()
+            .or(task.creator_user_id.as_deref());
+        let Some(spend) = self
+            .store
+            .record_run_spend(run_id, task.project_id.as_str(), owner_user_id, usage)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let budgets = self
+            .effective_spend_budgets(spend.project_id.as_str())
+            .await?;
+        let project_spent_usd = match budgets.project_usd {
+            Some(_) => Some(
+                self.project_spend(spend.project_id.as_str())
+                    .await?
+                    .cost_usd,
+            ),
+            None => None,
+        };
+        let stop = exceeded_spend_budget(
+            &budgets,
+            spend.usage.cost_usd,
+            project_spent_usd,
+            usage.cost_usd,
+        );
+        if let Some(stop) = stop.as_ref() {
+            self.store.append_run_event_sync(TaskRunEventRecord::new(
+                run_id.to_string(),
+                "spend_budget_exceeded",
+                Some(stop.blocking_reason()),
+                Some(stop.event_payload()),
+            ));
+        }
+        Ok(stop)
+    }
+
+    pub async fn list_run_spend(
+        &self,
+        group: RunSpendGroup,
+    ) -> Result<Vec<RunSpendSummaryRecord>, String> {
+        self.store.list_run_spend(group, None).await
+    }
+
+    pub async fn project_spend(&self, project_id: &str) -> Result<UsageTotals, String> {
+        Ok(self
+            .store
+            .list_run_spend(RunSpendGroup::Project, Some(project_id))
+            .await?
+            .into_iter()
+            .next()
+            .map(|summary| summary.usage)
+            .unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn budgets(run_usd: Option<f64>, project_usd: Option<f64>) -> TaskRunSpendBudgets {
+        TaskRunSpendBudgets {
+            run_usd,
+            project_usd,
+        }
+    }
+
+    #[test]
+    fn stops_when_another_step_of_the_same_cost_would_exceed_the_run_budget() {
+        assert_eq!(
+            exceeded_spend_budget(&budgets(Some(1.0), None), 0.6, None, 0.3),
+            None
+        );
+
+        let stop = exceeded_spend_budget(&budgets(Some(1.0), None), 0.8, None, 0.3)
+            .expect("run budget stop");
+        assert_eq!(stop.scope, SpendBudgetScope::Run);
+        assert_eq!(stop.budget_usd, 1.0);
+        let outcome = stop.execution_outcome();
+        assert_eq!(outcome.status, TaskExecutionOutcomeStatus::Blocked);
+        assert!(outcome.validate().is_ok());
+    }
+
+    #[test]
+    fn project_budget_counts_spend_across_runs() {
+        let stop = exceeded_spend_budget(&budgets(Some(5.0), Some(10.0)), 0.5, Some(9.9), 0.2)
+            .expect("project budget stop");
+        assert_eq!(stop.scope, SpendBudgetScope::Project);
+        assert_eq!(stop.spent_usd, 9.9);
+        assert_eq!(
+            exceeded_spend_budget(&budgets(None, None), 100.0, None, 1.0),
+            None
+        );
+    }
+}
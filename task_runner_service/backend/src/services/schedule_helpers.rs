@@ -1,11 +1,20 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
-use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::VecDeque;
 
-use crate::models::{now_rfc3339, TaskScheduleConfig, TaskScheduleMode};
+use chrono::{DateTime, Utc};
+
+use crate::models::{
+    now_rfc3339, TaskScheduleConfig, TaskScheduleMissedSlotPolicy, TaskScheduleMode,
+};
 
 use super::normalized_optional;
+use super::schedule_rules::{parse_timezone, ScheduleRules};
+
+/// Catch-up after a long outage only replays this many of the latest slots.
+const MAX_CATCH_UP_SLOTS: usize = 24;
+const MAX_CATCH_UP_SCANNED_SLOTS: usize = 10_000;
 
 pub(super) fn sanitize_task_schedule_config(
     mut schedule: TaskScheduleConfig,
@@ -16,6 +25,14 @@ pub(super) fn sanitize_task_schedule_config(
     schedule.last_scheduled_at = existing
         .and_then(|item| item.last_scheduled_at.clone())
         .or(schedule.last_scheduled_at);
+    schedule.cron_expression = normalized_optional(schedule.cron_expression);
+    schedule.timezone = normalized_optional(schedule.timezone)
+        .map(|value| parse_timezone(Some(value.as_str())).map(|tz| tz.name().to_string()))
+        .transpose()?;
+    schedule.slot_at = None;
+    if !matches!(schedule.mode, TaskScheduleMode::Cron) {
+        schedule.cron_expression = None;
+    }
 
     match schedule.mode {
         TaskScheduleMode::Manual => {
@@ -53,6 +70,17 @@ pub(super) fn sanitize_task_schedule_config(
                     .and_then(|item| item.next_run_at.clone())
                     .or_else(|| Some(now_rfc3339()));
             }
+            ScheduleRules::from_config(&schedule)?;
+            schedule.slot_at = schedule.next_run_at.clone();
+        }
+        TaskScheduleMode::Cron => {
+            if schedule.cron_expression.is_none() {
+                return Err("cron 调度必须提供 cron 表达式".to_string());
+            }
+            schedule.run_at = None;
+            schedule.interval_seconds = None;
+            let rules = ScheduleRules::from_config(&schedule)?;
+            set_next_slot(&mut schedule, &rules, rules.next_slot_after(Utc::now())?);
         }
         TaskScheduleMode::ContactAsync => {
             let run_at = schedule
@@ -81,11 +109,15 @@ pub(super) fn advance_task_schedule_after_dispatch(
         TaskScheduleMode::Once => {
             next.next_run_at = None;
         }
-        TaskScheduleMode::Interval => {
-            let seconds = next
-                .interval_seconds
-                .ok_or_else(|| "循环调度缺少 interval_seconds".to_string())?;
-            next.next_run_at = Some((started_at + ChronoDuration::seconds(seconds)).to_rfc3339());
+        TaskScheduleMode::Interval | TaskScheduleMode::Cron => {
+            let rules = ScheduleRules::from_config(&next)?;
+            let slot = match (next.missed_slot_policy, current_slot(&next)) {
+                (TaskScheduleMissedSlotPolicy::CatchUp, Some(current)) => {
+                    catch_up_slot(&rules, current, started_at)?
+                }
+                _ => rules.next_slot_after(started_at)?,
+            };
+            set_next_slot(&mut next, &rules, slot);
         }
         TaskScheduleMode::ContactAsync => {
             next.next_run_at = None;
@@ -94,6 +126,73 @@ pub(super) fn advance_task_schedule_after_dispatch(
     Ok(next)
 }
 
+/// Returns the rescheduled config when the due slot must not start at `now`:
+/// the `skip` policy drops slots that a later slot has already overtaken,
+/// and no slot starts inside a blackout window. `None` means run it.
+pub(super) fn defer_due_task_schedule(
+    schedule: &TaskScheduleConfig,
+    now: DateTime<Utc>,
+) -> Result<Option<TaskScheduleConfig>, String> {
+    if !matches!(
+        schedule.mode,
+        TaskScheduleMode::Interval | TaskScheduleMode::Cron
+    ) {
+        return Ok(None);
+    }
+    let rules = ScheduleRules::from_config(schedule)?;
+    let missed = match (schedule.missed_slot_policy, current_slot(schedule)) {
+        (TaskScheduleMissedSlotPolicy::Skip, Some(current)) => {
+            rules.next_slot_after(current)? <= now
+        }
+        _ => false,
+    };
+    if !missed && !rules.in_blackout(now) {
+        return Ok(None);
+    }
+    let mut next = schedule.clone();
+    set_next_slot(&mut next, &rules, rules.next_slot_after(now)?);
+    Ok(Some(next))
+}
+
+/// The oldest slot after `current` among the latest `MAX_CATCH_UP_SLOTS`
+/// that have already passed, or the next future slot if none has.
+fn catch_up_slot(
+    rules: &ScheduleRules,
+    current: DateTime<Utc>,
+    started_at: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    let mut missed = VecDeque::with_capacity(MAX_CATCH_UP_SLOTS);
+    let mut cursor = current;
+    for _ in 0..MAX_CATCH_UP_SCANNED_SLOTS {
+        let slot = rules.next_slot_after(cursor)?;
+        if slot > started_at {
+            return Ok(missed.pop_front().unwrap_or(slot));
+        }
+        if missed.len() == MAX_CATCH_UP_SLOTS {
+            missed.pop_front();
+        }
+        missed.push_back(slot);
+        cursor = slot;
+    }
+    match missed.pop_front() {
+        Some(slot) => Ok(slot),
+        None => rules.next_slot_after(started_at),
+    }
+}
+
+fn current_slot(schedule: &TaskScheduleConfig) -> Option<DateTime<Utc>> {
+    schedule
+        .slot_at
+        .as_deref()
+        .or(schedule.next_run_at.as_deref())
+        .and_then(parse_rfc3339)
+}
+
+fn set_next_slot(schedule: &mut TaskScheduleConfig, rules: &ScheduleRules, slot: DateTime<Utc>) {
+    schedule.slot_at = Some(slot.to_rfc3339());
+    schedule.next_run_at = Some(rules.jittered(slot).to_rfc3339());
+}
+
 fn parse_rfc3339(value: &str) -> Option<DateTime<Utc>> {
     DateTime::parse_from_rfc3339(value)
         .ok()
@@ -107,3 +206,94 @@ fn ensure_rfc3339(label: &str, value: &str) -> Result<(), String> {
         Err(format!("{label} 必须是 RFC3339 时间"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        parse_rfc3339(value).expect("rfc3339")
+    }
+
+    fn nightly(policy: TaskScheduleMissedSlotPolicy, slot_at: &str) -> TaskScheduleConfig {
+        TaskScheduleConfig {
+            mode: TaskScheduleMode::Cron,
+            cron_expression: Some("0 3 * * *".to_string()),
+            timezone: Some("Asia/Shanghai".to_string()),
+            missed_slot_policy: policy,
+            next_run_at: Some(slot_at.to_string()),
+            slot_at: Some(slot_at.to_string()),
+            ..TaskScheduleConfig::default()
+        }
+    }
+
+    #[test]
+    fn sanitizes_cron_schedules_to_the_next_local_slot() {
+        let schedule = sanitize_task_schedule_config(
+            TaskScheduleConfig {
+                mode: TaskScheduleMode::Cron,
+                cron_expression: Some(" 0 3 * * * ".to_string()),
+                timezone: Some("Asia/Shanghai".to_string()),
+                interval_seconds: Some(60),
+                ..TaskScheduleConfig::default()
+            },
+            None,
+        )
+        .expect("cron schedule");
+        assert_eq!(schedule.cron_expression.as_deref(), Some("0 3 * * *"));
+        assert_eq!(schedule.interval_seconds, None);
+        let next = utc(schedule.next_run_at.as_deref().expect("next_run_at"));
+        assert!(next > Utc::now());
+        assert_eq!(next.format("%H:%M").to_string(), "19:00");
+
+        let error = sanitize_task_schedule_config(
+            TaskScheduleConfig {
+                mode: TaskScheduleMode::Cron,
+                ..TaskScheduleConfig::default()
+            },
+            None,
+        )
+        .expect_err("missing expression");
+        assert!(error.contains("cron"));
+    }
+
+    #[test]
+    fn missed_slot_policies_decide_what_runs_after_downtime() {
+        // The 03:00 Shanghai slot of 2026-05-01 is picked up three days late.
+        let slot = "2026-04-30T19:00:00+00:00";
+        let started_at = utc("2026-05-03T20:00:00Z");
+
+        let run_once = advance_task_schedule_after_dispatch(
+            &nightly(TaskScheduleMissedSlotPolicy::RunOnce, slot),
+            started_at,
+        )
+        .expect("run once");
+        assert_eq!(
+            run_once.next_run_at.as_deref(),
+            Some("2026-05-04T19:00:00+00:00")
+        );
+
+        let catch_up = advance_task_schedule_after_dispatch(
+            &nightly(TaskScheduleMissedSlotPolicy::CatchUp, slot),
+            started_at,
+        )
+        .expect("catch up");
+        assert_eq!(
+            catch_up.next_run_at.as_deref(),
+            Some("2026-05-01T19:00:00+00:00")
+        );
+
+        let skip = nightly(TaskScheduleMissedSlotPolicy::Skip, slot);
+        let deferred = defer_due_task_schedule(&skip, started_at)
+            .expect("skip")
+            .expect("stale slot is skipped");
+        assert_eq!(
+            deferred.next_run_at.as_deref(),
+            Some("2026-05-04T19:00:00+00:00")
+        );
+        assert_eq!(deferred.last_scheduled_at, None);
+        assert!(defer_due_task_schedule(&skip, utc("2026-04-30T19:00:30Z"))
+            .expect("on time")
+            .is_none());
+    }
+}
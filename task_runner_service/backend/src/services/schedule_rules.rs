@@ -0,0 +1,590 @@
+// SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
+// Required Notice: Copyright (c) 2025 AI Chat Team
+
+use chrono::{
+    DateTime, Datelike, Duration as ChronoDuration, LocalResult, NaiveDate, NaiveDateTime,
+    NaiveTime, TimeZone, Timelike, Utc, Weekday,
+};
+use chrono_tz::Tz;
+
+use crate::models::{TaskScheduleBlackoutWindow, TaskScheduleConfig, TaskScheduleMode};
+
+const MAX_SCHEDULE_JITTER_SECONDS: i64 = 3_600;
+/// Cron expressions that never match (e.g. `0 0 30 2 *`) stop searching here.
+const CRON_SEARCH_YEARS: i32 = 5;
+/// Slots inside blackout windows are skipped; give up after this many.
+const MAX_BLACKOUT_SKIPPED_SLOTS: usize = 100_000;
+const MAX_BLACKOUT_SHIFTS: usize = 64;
+
+/// Parsed recurrence rules of an `interval` or `cron` schedule.
+pub(super) struct ScheduleRules {
+    timezone: Tz,
+    recurrence: Recurrence,
+    blackouts: Vec<BlackoutWindow>,
+    jitter_seconds: i64,
+}
+
+enum Recurrence {
+    Interval(ChronoDuration),
+    Cron(CronExpression),
+}
+
+impl ScheduleRules {
+    pub(super) fn from_config(schedule: &TaskScheduleConfig) -> Result<Self, String> {
+        let recurrence = match schedule.mode {
+            TaskScheduleMode::Interval => Recurrence::Interval(ChronoDuration::seconds(
+                schedule
+                    .interval_seconds
+                    .ok_or_else(|| "循环调度缺少 interval_seconds".to_string())?,
+            )),
+            TaskScheduleMode::Cron => Recurrence::Cron(CronExpression::parse(
+                schedule
+                    .cron_expression
+                    .as_deref()
+                    .ok_or_else(|| "cron 调度缺少 cron_expression".to_string())?,
+            )?),
+            _ => return Err("只有循环调度和 cron 调度支持调度规则".to_string()),
+        };
+        let jitter_seconds = schedule.jitter_seconds.unwrap_or(0);
+        if !(0..=MAX_SCHEDULE_JITTER_SECONDS).contains(&jitter_seconds) {
+            return Err(format!(
+                "schedule.jitter_seconds 只能在 0 到 {MAX_SCHEDULE_JITTER_SECONDS} 秒之间"
+            ));
+        }
+        if matches!(schedule.mode, TaskScheduleMode::Interval)
+            && jitter_seconds > 0
+            && jitter_seconds >= schedule.interval_seconds.unwrap_or(0)
+        {
+            return Err("schedule.jitter_seconds 必须小于循环间隔".to_string());
+        }
+        Ok(Self {
+            timezone: parse_timezone(schedule.timezone.as_deref())?,
+            recurrence,
+            blackouts: schedule
+                .blackout_windows
+                .iter()
+                .map(BlackoutWindow::parse)
+                .collect::<Result<_, _>>()?,
+            jitter_seconds,
+        })
+    }
+
+    /// The first slot strictly after `after` that is outside every blackout
+    /// window. Cron slots inside a window are skipped; interval slots are
+    /// pushed to the end of the window.
+    pub(super) fn next_slot_after(&self, after: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+        match &self.recurrence {
+            Recurrence::Interval(interval) => self.shift_out_of_blackout(after + *interval),
+            Recurrence::Cron(cron) => {
+                let mut cursor = after;
+                for _ in 0..MAX_BLACKOUT_SKIPPED_SLOTS {
+                    let slot = cron.next_after(self.timezone, cursor)?;
+                    if !self.in_blackout(slot) {
+                        return Ok(slot);
+                    }
+                    cursor = slot;
+                }
+                Err("cron 调度在禁止运行时段之外没有可执行时间".to_string())
+            }
+        }
+    }
+
+    pub(super) fn in_blackout(&self, at: DateTime<Utc>) -> bool {
+        self.blackout_end(at).is_some()
+    }
+
+    /// Delays `slot` by a random amount up to the configured jitter, unless
+    /// that would land it inside a blackout window.
+    pub(super) fn jittered(&self, slot: DateTime<Utc>) -> DateTime<Utc> {
+        if self.jitter_seconds <= 0 {
+            return slot;
+        }
+        let jittered = slot + ChronoDuration::seconds(rand::random_range(0..=self.jitter_seconds));
+        if self.in_blackout(jittered) {
+            slot
+        } else {
+            jittered
+        }
+    }
+
+    fn shift_out_of_blackout(&self, mut slot: DateTime<Utc>) -> Result<DateTime<Utc>, String> {
+        for _ in 0..MAX_BLACKOUT_SHIFTS {
+            let Some(end) = self.blackout_end(slot) else {
+                return Ok(slot);
+            };
+            slot = resolve_local(self.timezone, end, slot)
+                .ok_or_else(|| "禁止运行时段的结束时间无法换算为有效时间".to_string())?;
+        }
+        Err("循环调度在禁止运行时段之外没有可执行时间".to_string())
+    }
+
+    fn blackout_end(&self, at: DateTime<Utc>) -> Option<NaiveDateTime> {
+        let local = at.with_timezone(&self.timezone).naive_local();
+        self.blackouts
+            .iter()
+            .filter_map(|window| window.end_if_contains(local))
+            .max()
+    }
+}
+
+pub(super) fn parse_timezone(value: Option<&str>) -> Result<Tz, String> {
+    match value.map(str::trim).filter(|value| !value.is_empty()) {
+        Some(value) => value
+            .parse::<Tz>()
+            .map_err(|_| format!("schedule.timezone 不是合法的 IANA 时区: {value}")),
+        None => Ok(Tz::UTC),
+    }
+}
+
+/// Maps a wall-clock time to its first instant strictly after `after`.
+/// Ambiguous times (DST fall-back) use the first occurrence, or the second
+/// when the first is not after `after`; times skipped by DST spring-forward
+/// run an hour later on the wall clock, i.e. right after the gap.
+fn resolve_local(
+    timezone: Tz,
+    local: NaiveDateTime,
+    after: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    let instant = match timezone.from_local_datetime(&local) {
+        LocalResult::Single(value) => value.with_timezone(&Utc),
+        LocalResult::Ambiguous(first, second) => {
+            let first = first.with_timezone(&Utc);
+            if first > after {
+                first
+            } else {
+                second.with_timezone(&Utc)
+            }
+        }
+        LocalResult::None => timezone
+            .from_local_datetime(&(local + ChronoDuration::hours(1)))
+            .earliest()?
+            .with_timezone(&Utc),
+    };
+    (instant > after).then_some(instant)
+}
+
+struct BlackoutWindow {
+    weekdays: Vec<Weekday>,
+    start_minute: u32,
+    end_minute: u32,
+}
+
+impl BlackoutWindow {
+    fn parse(window: &TaskScheduleBlackoutWindow) -> Result<Self, String> {
+        let start_minute = parse_clock(window.start.as_str(), false)?;
+        let end_minute = parse_clock(window.end.as_str(), true)?;
+        if start_minute == end_minute {
+            return Err("禁止运行时段的开始和结束时间不能相同".to_string());
+        }
+        let weekdays = window
+            .weekdays
+            .iter()
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<Weekday>()
+                    .map_err(|_| format!("禁止运行时段的星期不合法: {value}"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self {
+            weekdays,
+            start_minute,
+            end_minute,
+        })
+    }
+
+    /// Checks the occurrence starting on the same day and the overnight one
+    /// starting the day before.
+    fn end_if_contains(&self, local: NaiveDateTime) -> Option<NaiveDateTime> {
+        let date = local.date();
+        [date.pred_opt(), Some(date)]
+            .into_iter()
+            .flatten()
+            .filter(|day| self.weekdays.is_empty() || self.weekdays.contains(&day.weekday()))
+            .find_map(|day| {
+                let start = at_minute(day, self.start_minute);
+                let end = if self.end_minute > self.start_minute {
+                    at_minute(day, self.end_minute)
+                } else {
+                    at_minute(day, self.end_minute + 24 * 60)
+                };
+                (start <= local && local < end).then_some(end)
+            })
+    }
+}
+
+fn at_minute(day: NaiveDate, minute: u32) -> NaiveDateTime {
+    day.and_time(NaiveTime::MIN) + ChronoDuration::minutes(i64::from(minute))
+}
+
+fn parse_clock(value: &str, allow_end_of_day: bool) -> Result<u32, String> {
+    let value = value.trim();
+    if allow_end_of_day && value == "24:00" {
+        return Ok(24 * 60);
+    }
+    NaiveTime::parse_from_str(value, "%H:%M")
+        .map(|time| time.hour() * 60 + time.minute())
+        .map_err(|_| format!("禁止运行时段的时间必须是 HH:MM: {value}"))
+}
+
+/// Standard five-field cron: minute, hour, day of month, month, day of week.
+/// Fields accept `*`, lists, ranges, `/step` and month/weekday names. As in
+/// Vixie cron, a day matches either day field when both are restricted.
+pub(super) struct CronExpression {
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    day_of_month_any: bool,
+    day_of_week_any: bool,
+}
+
+struct CronField {
+    name: &'static str,
+    min: u32,
+    max: u32,
+    names: &'static [&'static str],
+}
+
+const MINUTE_FIELD: CronField = CronField {
+    name: "minute",
+    min: 0,
+    max: 59,
+    names: &[],
+};
+const HOUR_FIELD: CronField = CronField {
+    name: "hour",
+    min: 0,
+    max: 23,
+    names: &[],
+};
+const DAY_OF_MONTH_FIELD: CronField = CronField {
+    name: "day-of-month",
+    min: 1,
+    max: 31,
+    names: &[],
+};
+const MONTH_FIELD: CronField = CronField {
+    name: "month",
+    min: 1,
+    max: 12,
+    names: &[
+        "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+    ],
+};
+/// 7 is accepted as another spelling of Sunday and folded into 0.
+const DAY_OF_WEEK_FIELD: CronField = CronField {
+    name: "day-of-week",
+    min: 0,
+    max: 7,
+    names: &["sun", "mon", "tue", "wed", "thu", "fri", "sat"],
+};
+
+impl CronExpression {
+    pub(super) fn parse(expression: &str) -> Result<Self, String> {
+        let expression = expression.trim();
+        let expanded = match expression.to_ascii_lowercase().as_str() {
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            "@monthly" => "0 0 1 * *",
+            "@weekly" => "0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@hourly" => "0 * * * *",
+            _ => expression,
+        };
+        let fields = expanded.split_whitespace().collect::<Vec<_>>();
+        let [minute, hour, day_of_month, month, day_of_week] = fields.as_slice() else {
+            return Err(format!(
+                "cron 表达式必须包含 5 个字段（分 时 日 月 周）: {expression}"
+            ));
+        };
+        let mut days_of_week = DAY_OF_WEEK_FIELD.parse(day_of_week)?;
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week & !(1 << 7)) | 1;
+        }
+        Ok(Self {
+            minutes: MINUTE_FIELD.parse(minute)?,
+            hours: HOUR_FIELD.parse(hour)?,
+            days_of_month: DAY_OF_MONTH_FIELD.parse(day_of_month)?,
+            months: MONTH_FIELD.parse(month)?,
+            days_of_week,
+            day_of_month_any: day_of_month.starts_with('*'),
+            day_of_week_any: day_of_week.starts_with('*'),
+        })
+    }
+
+    /// The first matching instant strictly after `after`, evaluated on the
+    /// wall clock of `timezone`.
+    pub(super) fn next_after(
+        &self,
+        timezone: Tz,
+        after: DateTime<Utc>,
+    ) -> Result<DateTime<Utc>, String> {
+        let local_after = after.with_timezone(&timezone).naive_local();
+        let mut candidate = local_after
+            .with_second(0)
+            .and_then(|value| value.with_nanosecond(0))
+            .unwrap_or(local_after)
+            + ChronoDuration::minutes(1);
+        let last_year = candidate.year() + CRON_SEARCH_YEARS;
+        while candidate.year() <= last_year {
+            let date = candidate.date();
+            if !has_bit(self.months, date.month()) {
+                candidate = first_of_next_month(date).and_time(NaiveTime::MIN);
+                continue;
+            }
+            if !self.matches_day(date) {
+                candidate = (date + ChronoDuration::days(1)).and_time(NaiveTime::MIN);
+                continue;
+            }
+            if !has_bit(self.hours, candidate.hour()) {
+                candidate = at_minute(date, (candidate.hour() + 1) * 60);
+                continue;
+            }
+            if !has_bit(self.minutes, candidate.minute()) {
+                candidate += ChronoDuration::minutes(1);
+                continue;
+            }
+            if let Some(slot) = resolve_local(timezone, candidate, after) {
+                return Ok(slot);
+            }
+            candidate += ChronoDuration::minutes(1);
+        }
+        Err(format!(
+            "cron 表达式在 {CRON_SEARCH_YEARS} 年内没有可执行时间"
+        ))
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let day_of_month = has_bit(self.days_of_month, date.day());
+        let day_of_week = has_bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        match (self.day_of_month_any, self.day_of_week_any) {
+            (false, false) => day_of_month || day_of_week,
+            _ => day_of_month && day_of_week,
+        }
+    }
+}
+
+impl CronField {
+    fn parse(&self, field: &str) -> Result<u64, String> {
+        let mut bits = 0u64;
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (
+                    range,
+                    step.parse::<u32>()
+                        .ok()
+                        .filter(|step| *step > 0)
+                        .ok_or_else(|| self.invalid(part))?,
+                ),
+                None => (part, 1),
+            };
+            let (start, end) = if range == "*" {
+                (self.min, self.max)
+            } else if let Some((start, end)) = range.split_once('-') {
+                (self.value(start)?, self.value(end)?)
+            } else {
+                let start = self.value(range)?;
+                // `5/15` means "from 5 to the end of the range every 15".
+                (start, if step > 1 { self.max } else { start })
+            };
+            if start > end {
+                return Err(self.invalid(part));
+            }
+            for value in (start..=end).step_by(step as usize) {
+                bits |= 1 << value;
+            }
+        }
+        Ok(bits)
+    }
+
+    fn value(&self, raw: &str) -> Result<u32, String> {
+        let lower = raw.to_ascii_lowercase();
+        let value = match self.names.iter().position(|name| *name == lower) {
+            Some(index) => index as u32 + self.min,
+            None => raw.parse::<u32>().map_err(|_| self.invalid(raw))?,
+        };
+        if (self.min..=self.max).contains(&value) {
+            Ok(value)
+        } else {
+            Err(self.invalid(raw))
+        }
+    }
+
+    fn invalid(&self, part: &str) -> String {
+        format!("cron 表达式的 {} 字段不合法: {part}", self.name)
+    }
+}
+
+fn has_bit(bits: u64, value: u32) -> bool {
+    bits & (1 << value) != 0
+}
+
+fn first_of_next_month(date: NaiveDate) -> NaiveDate {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap_or(NaiveDate::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .expect("rfc3339")
+            .with_timezone(&Utc)
+    }
+
+    fn cron_schedule(expression: &str, timezone: &str) -> TaskScheduleConfig {
+        TaskScheduleConfig {
+            mode: TaskScheduleMode::Cron,
+            cron_expression: Some(expression.to_string()),
+            timezone: Some(timezone.to_string()),
+            ..TaskScheduleConfig::default()
+        }
+    }
+
+    #[test]
+    fn cron_slots_follow_local_time_across_dst() {
+        let rules = ScheduleRules::from_config(&cron_schedule("30 2 * * *", "America/New_York"))
+            .expect("rules");
+        // 02:30 does not exist on 2026-03-08 and runs right after the gap.
+        assert_eq!(
+            rules
+                .next_slot_after(utc("2026-03-07T12:00:00Z"))
+                .expect("slot"),
+            utc("2026-03-08T07:30:00Z")
+        );
+        assert_eq!(
+            rules
+                .next_slot_after(utc("2026-03-08T07:30:00Z"))
+                .expect("slot"),
+            utc("2026-03-09T06:30:00Z")
+        );
+
+        let hourly = ScheduleRules::from_config(&cron_schedule("0 * * * *", "America/New_York"))
+            .expect("rules");
+        // 01:00 happens twice on 2026-11-01; only the first one fires.
+        let first = hourly
+            .next_slot_after(utc("2026-11-01T04:30:00Z"))
+            .expect("slot");
+        assert_eq!(first, utc("2026-11-01T05:00:00Z"));
+        assert_eq!(
+            hourly.next_slot_after(first).expect("slot"),
+            utc("2026-11-01T07:00:00Z")
+        );
+    }
+
+    #[test]
+    fn dst_fall_back_slots_are_always_after_the_previous_one() {
+        let quarterly =
+            ScheduleRules::from_config(&cron_schedule("*/15 * * * *", "America/New_York"))
+                .expect("rules");
+        // 01:45 EDT is followed by 02:00 EST; the repeated 01:xx hour does not fire again.
+        assert_eq!(
+            quarterly
+                .next_slot_after(utc("2026-11-01T05:45:00Z"))
+                .expect("slot"),
+            utc("2026-11-01T07:00:00Z")
+        );
+        // From 01:10 EST the next 01:15 is the second occurrence, not the earlier EDT one.
+        assert_eq!(
+            quarterly
+                .next_slot_after(utc("2026-11-01T06:10:00Z"))
+                .expect("slot"),
+            utc("2026-11-01T06:15:00Z")
+        );
+
+        let mut schedule = cron_schedule("0 * * * *", "America/New_York");
+        schedule.mode = TaskScheduleMode::Interval;
+        schedule.interval_seconds = Some(3_600);
+        schedule.blackout_windows = vec![TaskScheduleBlackoutWindow {
+            weekdays: Vec::new(),
+            start: "00:00".to_string(),
+            end: "01:30".to_string(),
+        }];
+        let rules = ScheduleRules::from_config(&schedule).expect("rules");
+        // 01:10 EST falls in the window again; its end is the 01:30 EST that follows,
+        // not the 01:30 EDT an hour earlier.
+        assert_eq!(
+            rules
+                .next_slot_after(utc("2026-11-01T05:10:00Z"))
+                .expect("slot"),
+            utc("2026-11-01T06:30:00Z")
+        );
+    }
+
+    #[test]
+    fn parses_cron_fields_aliases_and_day_semantics() {
+        let weekdays = CronExpression::parse("*/15 9-17 * jan-mar mon-fri").expect("cron");
+        assert_eq!(
+            weekdays
+                .next_after(Tz::UTC, utc("2026-01-02T17:50:00Z"))
+                .expect("slot"),
+            utc("2026-01-05T09:00:00Z")
+        );
+        let either_day = CronExpression::parse("0 0 1 * 7").expect("cron");
+        assert_eq!(
+            either_day
+                .next_after(Tz::UTC, utc("2026-02-02T00:00:00Z"))
+                .expect("slot"),
+            utc("2026-02-08T00:00:00Z")
+        );
+        assert!(CronExpression::parse("@daily").is_ok());
+        assert!(CronExpression::parse("0 0 * *").is_err());
+        assert!(CronExpression::parse("60 * * * *").is_err());
+        assert!(CronExpression::parse("0 0 30 2 *")
+            .expect("cron")
+            .next_after(Tz::UTC, utc("2026-01-01T00:00:00Z"))
+            .is_err());
+        assert!(parse_timezone(Some("Mars/Olympus")).is_err());
+    }
+
+    #[test]
+    fn blackout_windows_skip_cron_slots_and_delay_interval_slots() {
+        let mut schedule = cron_schedule("0 * * * *", "Europe/Berlin");
+        schedule.blackout_windows = vec![
+            TaskScheduleBlackoutWindow {
+                weekdays: Vec::new(),
+                start: "22:00".to_string(),
+                end: "06:00".to_string(),
+            },
+            TaskScheduleBlackoutWindow {
+                weekdays: vec!["sat".to_string(), "sun".to_string()],
+                start: "00:00".to_string(),
+                end: "24:00".to_string(),
+            },
+        ];
+        let rules = ScheduleRules::from_config(&schedule).expect("rules");
+        // Friday 21:00 Berlin is the last slot before the weekend.
+        assert_eq!(
+            rules
+                .next_slot_after(utc("2026-07-03T18:30:00Z"))
+                .expect("slot"),
+            utc("2026-07-03T19:00:00Z")
+        );
+        assert_eq!(
+            rules
+                .next_slot_after(utc("2026-07-03T19:00:00Z"))
+                .expect("slot"),
+            utc("2026-07-06T04:00:00Z")
+        );
+
+        schedule.mode = TaskScheduleMode::Interval;
+        schedule.interval_seconds = Some(3_600);
+        let rules = ScheduleRules::from_config(&schedule).expect("rules");
+        assert_eq!(
+            rules
+                .next_slot_after(utc("2026-07-03T19:30:00Z"))
+                .expect("slot"),
+            utc("2026-07-06T04:00:00Z")
+        );
+
+        schedule.blackout_windows[0].end = "22:00".to_string();
+        assert!(ScheduleRules::from_config(&schedule).is_err());
+    }
+}
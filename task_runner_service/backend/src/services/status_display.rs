@@ -46,6 +46,7 @@ impl TaskScheduleModeExt for TaskScheduleMode {
             TaskScheduleMode::Manual => "manual",
             TaskScheduleMode::Once => "once",
             TaskScheduleMode::Interval => "interval",
+            TaskScheduleMode::Cron => "cron",
             TaskScheduleMode::ContactAsync => "contact_async",
         }
     }
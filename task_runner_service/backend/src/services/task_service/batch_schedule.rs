@@ -1,6 +1,8 @@
 // SPDX-License-Identifier: PolyForm-Noncommercial-1.0.0
 // Required Notice: Copyright (c) 2025 AI Chat Team
 
+use tracing::{info, warn};
+
 use super::*;
 
 impl TaskService {
@@ -83,11 +85,49 @@ impl TaskService {
         Ok(summarize_batch_results(results))
     }
 
+    /// Due tasks whose slot may start now. Slots dropped by the `skip`
+    /// missed-slot policy or falling inside a blackout window are moved to
+    /// their next slot here instead of being returned.
     pub async fn list_due_scheduled_tasks(
         &self,
         now: DateTime<Utc>,
     ) -> Result<Vec<TaskRecord>, String> {
-        self.store.list_due_scheduled_tasks(now).await
+        let tasks = self.store.list_due_scheduled_tasks(now).await?;
+        let mut due = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let deferred = match defer_due_task_schedule(&task.schedule, now) {
+                Ok(deferred) => deferred,
+                Err(err) => {
+                    warn!(
+                        "scheduler could not evaluate schedule rules for task {}: {}",
+                        task.id, err
+                    );
+                    None
+                }
+            };
+            let (Some(schedule), Some(expected_next_run_at)) =
+                (deferred, task.schedule.next_run_at.as_deref())
+            else {
+                due.push(task);
+                continue;
+            };
+            info!(
+                "scheduler deferred task {} from {} to {}",
+                task.id,
+                expected_next_run_at,
+                schedule.next_run_at.as_deref().unwrap_or("-")
+            );
+            let updated_at = now_rfc3339();
+            self.store
+                .update_task_schedule_if_next_run_at(
+                    task.id.as_str(),
+                    expected_next_run_at,
+                    schedule,
+                    updated_at.as_str(),
+                )
+                .await?;
+        }
+        Ok(due)
     }
 
     pub async fn mark_scheduled_run_started(
@@ -65,6 +65,8 @@ impl TaskService {
         if let Some(workspace_changes_required) = requested_mcp_config.workspace_changes_required {
             mcp_config.workspace_changes_required = workspace_changes_required;
         }
+        mcp_config.integration_conflict_resolver =
+            requested_mcp_config.integration_conflict_resolver;
         mcp_config.enabled_builtin_kinds = requested_mcp_config.enabled_builtin_kinds;
         mcp_config.external_mcp_config_ids = requested_mcp_config.external_mcp_config_ids;
         if let Some(builtin_prompt_locale) =
@@ -141,6 +141,9 @@ impl TaskService {
             if let Some(workspace_changes_required) = mcp_config.workspace_changes_required {
                 task.mcp_config.workspace_changes_required = workspace_changes_required;
             }
+            if let Some(resolver) = mcp_config.integration_conflict_resolver {
+                task.mcp_config.integration_conflict_resolver = Some(resolver);
+            }
         }
         if let Some(plugin_config) = patch.plugin_config {
             task.plugin_config = plugin_config;
@@ -62,6 +62,7 @@ impl McpCatalogService {
             workspace_dir: normalized_optional(request.workspace_dir),
             requires_execution: true,
             workspace_changes_required: true,
+            integration_conflict_resolver: None,
             execution_service_id: None,
             external_mcp_config_ids: Vec::new(),
             selected_skill_ids: Vec::new(),
@@ -434,6 +434,8 @@ mod model_selection_tests {
             request_cwd: None,
             include_prompt_cache_retention: false,
             request_body_limit_bytes: None,
+            pricing: None,
+            fallback_model_config_ids: Vec::new(),
             enabled: true,
             created_at: "2026-01-01T00:00:00Z".to_string(),
             updated_at: "2026-01-01T00:00:00Z".to_string(),
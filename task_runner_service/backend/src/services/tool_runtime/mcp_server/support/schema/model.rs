@@ -91,6 +91,30 @@ fn thinking_level_schema() -> Value {
     })
 }
 
+fn pricing_schema() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "input_per_million_usd": { "type": "number", "minimum": 0 },
+            "cached_input_per_million_usd": { "type": "number", "minimum": 0 },
+            "cache_write_input_per_million_usd": { "type": "number", "minimum": 0 },
+            "output_per_million_usd": { "type": "number", "minimum": 0 },
+            "reasoning_per_million_usd": { "type": "number", "minimum": 0 }
+        },
+        "additionalProperties": false,
+        "description": "每百万 token 的美元单价。缓存输入、缓存写入和推理 token 未填写时分别按输入、输入和输出单价计费。"
+    })
+}
+
+fn fallback_model_config_ids_schema() -> Value {
+    json!({
+        "type": "array",
+        "items": { "type": "string", "minLength": 1 },
+        "maxItems": 3,
+        "description": "模型过载、限流或上游鉴权不可用时按顺序改用的其他模型配置 ID。"
+    })
+}
+
 pub(crate) fn create_model_config_schema() -> Value {
     json!({
         "type": "object",
@@ -112,6 +136,8 @@ pub(crate) fn create_model_config_schema() -> Value {
             "request_cwd": { "type": "string" },
             "include_prompt_cache_retention": { "type": "boolean" },
             "request_body_limit_bytes": { "type": "integer", "minimum": 1 },
+            "pricing": pricing_schema(),
+            "fallback_model_config_ids": fallback_model_config_ids_schema(),
             "enabled": { "type": "boolean" }
         },
         "required": ["name", "provider", "base_url", "model"],
@@ -140,6 +166,8 @@ pub(crate) fn update_model_config_schema() -> Value {
             "request_cwd": { "type": "string" },
             "include_prompt_cache_retention": { "type": "boolean" },
             "request_body_limit_bytes": { "type": "integer", "minimum": 1 },
+            "pricing": pricing_schema(),
+            "fallback_model_config_ids": fallback_model_config_ids_schema(),
             "enabled": { "type": "boolean" }
         },
         "additionalProperties": false
@@ -56,6 +56,7 @@ fn valid_planner_create_request() -> CreateTaskRequest {
         mcp_config: Some(TaskMcpRequestConfig {
             requires_execution: Some(false),
             workspace_changes_required: None,
+            integration_conflict_resolver: None,
             enabled_builtin_kinds: Vec::new(),
             external_mcp_config_ids: Vec::new(),
         }),
@@ -323,6 +324,8 @@ fn model_config(id: &str, owner_user_id: &str, enabled: bool) -> ModelConfigReco
         request_cwd: None,
         include_prompt_cache_retention: false,
         request_body_limit_bytes: None,
+        pricing: None,
+        fallback_model_config_ids: Vec::new(),
         enabled,
         created_at: "2026-01-01T00:00:00Z".to_string(),
         updated_at: "2026-01-01T00:00:00Z".to_string(),
@@ -268,6 +268,7 @@ async fn create_task_in_chatos_plan_profile_persists_plan_task_profile() {
             supports_reasoning: None,
             enabled: Some(true),
             supports_responses: Some(true),
+            pricing: None,
         })
         .await
         .expect("create model config");
@@ -357,6 +358,7 @@ async fn create_tasks_with_prerequisites_in_chatos_plan_profile_persist_plan_tas
             supports_reasoning: None,
             enabled: Some(true),
             supports_responses: Some(true),
+            pricing: None,
         })
         .await
         .expect("create model config");
@@ -29,6 +29,7 @@ async fn chatos_async_reuse_is_scoped_by_task_profile() {
             supports_reasoning: None,
             enabled: Some(true),
             supports_responses: Some(true),
+            pricing: None,
         })
         .await
         .expect("create model config");
@@ -150,6 +151,7 @@ async fn chatos_async_create_task_inherits_selected_conversation_model() {
                 supports_reasoning: None,
                 enabled: Some(true),
                 supports_responses: Some(true),
+                pricing: None,
             })
             .await
             .expect("create model config");
@@ -334,6 +336,7 @@ async fn chatos_async_create_task_does_not_reuse_succeeded_task() {
             supports_reasoning: None,
             enabled: Some(true),
             supports_responses: Some(true),
+            pricing: None,
         })
         .await
         .expect("create model config");
@@ -429,6 +432,7 @@ async fn chatos_async_batch_create_does_not_reuse_succeeded_task() {
             supports_reasoning: None,
             enabled: Some(true),
             supports_responses: Some(true),
+            pricing: None,
         })
         .await
         .expect("create model config");
@@ -358,6 +358,7 @@ async fn project_execution_planner_creates_multiple_runner_tasks_and_syncs_links
             supports_reasoning: None,
             enabled: Some(true),
             supports_responses: Some(true),
+            pricing: None,
         })
         .await
         .expect("create model config");
@@ -82,6 +82,7 @@ impl CreateTaskArgs {
         .then_some(TaskMcpRequestConfig {
             requires_execution,
             workspace_changes_required: None,
+            integration_conflict_resolver: None,
             enabled_builtin_kinds,
             external_mcp_config_ids,
         });
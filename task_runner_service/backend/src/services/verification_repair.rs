@@ -579,13 +579,14 @@ fn merge_implementation_capabilities(repair: &mut TaskRecord, plan: &Verificatio
     repair.mcp_config.external_mcp_config_ids = external_ids.into_iter().collect();
 }
 
-fn contact_async_schedule(now: &str) -> TaskScheduleConfig {
+pub(super) fn contact_async_schedule(now: &str) -> TaskScheduleConfig {
     TaskScheduleConfig {
         mode: TaskScheduleMode::ContactAsync,
         run_at: Some(now.to_string()),
         interval_seconds: None,
         next_run_at: None,
         last_scheduled_at: None,
+        ..TaskScheduleConfig::default()
     }
 }
 
@@ -57,6 +57,7 @@ fn workspace_execution(status: WorkspacePreparationStatus) -> TaskRunWorkspaceEx
         local_patch_truncated: false,
         conflict_files: Vec::new(),
         conflict_message: None,
+        conflict_handoff: None,
         integration_last_error: None,
         prepared_at: None,
         finalized_at: None,
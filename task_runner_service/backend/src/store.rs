@@ -4,11 +4,12 @@
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::sync::Arc;
 
+use chatos_ai_runtime::UsageTotals;
 use chrono::{DateTime, Utc};
 use futures_util::TryStreamExt;
 use mongodb::{
     bson::{self, doc, Bson, Document},
-    options::{FindOneOptions, FindOptions, IndexOptions, ReplaceOptions},
+    options::{FindOneOptions, FindOptions, IndexOptions, ReplaceOptions, UpdateOptions},
     Client, Collection, IndexModel,
 };
 use parking_lot::RwLock;
@@ -22,9 +23,10 @@ use crate::models::{
     now_rfc3339, AskUserPromptPruneResult, AskUserPromptRecord, AskUserPromptStatus,
     AskUserPromptTaskCountRecord, ChatosCallbackDeliveryState, ChatosCallbackDeliveryStatus,
     ModelConfigRecord, ModelConfigUsageRecord, PaginatedResponse, PromptListFilters,
-    RunEventPruneResult, RunExecutionStats, RunListFilters, RunSummaryRecord,
-    RuntimeSettingsRecord, TaskListFilters, TaskPrerequisiteRecord, TaskProjectRecord, TaskRecord,
-    TaskRunAttemptRecord, TaskRunAttemptStatus, TaskRunEventRecord, TaskRunRecord, TaskRunStatus,
+    RunEventPruneResult, RunExecutionStats, RunListFilters, RunSpendGroup, RunSpendSummaryRecord,
+    RunSummaryRecord, RuntimeSettingsRecord, TaskListFilters, TaskPrerequisiteRecord,
+    TaskProjectRecord, TaskRecord, TaskRunAttemptRecord, TaskRunAttemptStatus,
+    TaskRunConflictHandoff, TaskRunEventRecord, TaskRunRecord, TaskRunSpendRecord, TaskRunStatus,
     TaskScheduleConfig, TaskScheduleMode, TaskStatsResponse, TaskStatus, TaskSummaryRecord,
     UserRecord,
 };
@@ -42,8 +44,8 @@ mod task_support;
 
 use self::codec::ask_user_prompt_status_to_str;
 use self::mongo_support::{
-    bson_string_field, bson_usize_field, build_limit_stage, build_mongo_prompt_filter,
-    build_mongo_run_filter, build_mongo_task_filter, build_skip_stage,
+    bson_f64_field, bson_string_field, bson_usize_field, build_limit_stage,
+    build_mongo_prompt_filter, build_mongo_run_filter, build_mongo_task_filter, build_skip_stage,
     is_mongo_active_run_conflict, is_mongo_active_run_index_conflict,
     is_mongo_execution_lane_conflict, mongo_find_options,
 };
@@ -104,6 +106,17 @@ fn ensure_run_post_process_pending(run: &mut TaskRunRecord) {
 
 fn merge_run_async_progress(run: &mut TaskRunRecord, current: &TaskRunRecord) {
     merge_run_attempts(&mut run.attempts, &current.attempts);
+    // Spend is only ever added to, so the record with more requests is the
+    // newer one even when a stale copy of the run is being persisted.
+    if let Some(current_spend) = current.spend.as_ref() {
+        if run
+            .spend
+            .as_ref()
+            .is_none_or(|spend| spend.usage.requests < current_spend.usage.requests)
+        {
+            run.spend = Some(current_spend.clone());
+        }
+    }
     merge_callback_delivery(
         &mut run.chatos_started_callback_delivery,
         current.chatos_started_callback_delivery.as_ref(),
@@ -130,6 +130,17 @@ impl AppStore {
         }
     }
 
+    pub(crate) async fn record_run_conflict_handoff(
+        &self,
+        run_id: &str,
+        handoff: &TaskRunConflictHandoff,
+    ) -> Result<Option<TaskRunRecord>, String> {
+        match self {
+            Self::InMemory(store) => Ok(store.record_run_conflict_handoff(run_id, handoff)),
+            Self::Mongo(store) => store.record_run_conflict_handoff(run_id, handoff).await,
+        }
+    }
+
     pub(crate) async fn subscribe_run_terminal(
         &self,
         subscription: RunTerminalSubscriptionRecord,
@@ -238,6 +249,38 @@ impl AppStore {
         }
     }
 
+    /// Adds one step's model usage to the run and returns the run's new
+    /// totals, or `None` when the run no longer exists.
+    pub(crate) async fn record_run_spend(
+        &self,
+        run_id: &str,
+        project_id: &str,
+        owner_user_id: Option<&str>,
+        usage: &UsageTotals,
+    ) -> Result<Option<TaskRunSpendRecord>, String> {
+        match self {
+            Self::InMemory(store) => {
+                Ok(store.record_run_spend(run_id, project_id, owner_user_id, usage))
+            }
+            Self::Mongo(store) => {
+                store
+                    .record_run_spend(run_id, project_id, owner_user_id, usage)
+                    .await
+            }
+        }
+    }
+
+    pub async fn list_run_spend(
+        &self,
+        group: RunSpendGroup,
+        scope_id: Option<&str>,
+    ) -> Result<Vec<RunSpendSummaryRecord>, String> {
+        match self {
+            Self::InMemory(store) => Ok(store.list_run_spend(group, scope_id)),
+            Self::Mongo(store) => store.list_run_spend(group, scope_id).await,
+        }
+    }
+
     pub(crate) async fn mark_run_chatos_followup_processed(
         &self,
         run_id: &str,
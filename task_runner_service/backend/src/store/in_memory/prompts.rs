@@ -285,6 +285,7 @@ mod tests {
             result_summary: None,
             error_message: None,
             usage: None,
+            spend: None,
             report: None,
             cancel_requested: false,
             cancel_event_pending: false,
@@ -414,6 +414,55 @@ impl InMemoryStore {
         true
     }
 
+    pub(in crate::store) fn record_run_spend(
+        &self,
+        run_id: &str,
+        project_id: &str,
+        owner_user_id: Option<&str>,
+        usage: &UsageTotals,
+    ) -> Option<TaskRunSpendRecord> {
+        let mut data = self.inner.write();
+        let run = data.runs.get_mut(run_id)?;
+        let spend = run.spend.get_or_insert_with(TaskRunSpendRecord::default);
+        spend.project_id = project_id.to_string();
+        if let Some(owner_user_id) = owner_user_id {
+            spend.owner_user_id = Some(owner_user_id.to_string());
+        }
+        spend.usage.merge(usage);
+        let spend = spend.clone();
+        run.updated_at = now_rfc3339();
+        Some(spend)
+    }
+
+    pub(in crate::store) fn list_run_spend(
+        &self,
+        group: RunSpendGroup,
+        scope_id: Option<&str>,
+    ) -> Vec<RunSpendSummaryRecord> {
+        let data = self.inner.read();
+        let mut summaries = BTreeMap::<String, RunSpendSummaryRecord>::new();
+        for spend in data.runs.values().filter_map(|run| run.spend.as_ref()) {
+            let Some(key) = (match group {
+                RunSpendGroup::Project => Some(spend.project_id.as_str()),
+                RunSpendGroup::Owner => spend.owner_user_id.as_deref(),
+            }) else {
+                continue;
+            };
+            if scope_id.is_some_and(|scope_id| scope_id != key) {
+                continue;
+            }
+            let entry = summaries
+                .entry(key.to_string())
+                .or_insert_with(|| RunSpendSummaryRecord {
+                    scope_id: key.to_string(),
+                    ..RunSpendSummaryRecord::default()
+                });
+            entry.run_count += 1;
+            entry.usage.merge(&spend.usage);
+        }
+        summaries.into_values().collect()
+    }
+
     pub(in crate::store) fn mark_run_chatos_followup_processed(&self, run_id: &str) -> bool {
         let mut data = self.inner.write();
         let Some(run) = data.runs.get_mut(run_id) else {
@@ -512,11 +561,31 @@ impl InMemoryStore {
         execution.integrated_at = None;
         execution.conflict_files.clear();
         execution.conflict_message = None;
+        execution.conflict_handoff = None;
         execution.integration_last_error = None;
         run.updated_at = now_rfc3339();
         Some(run.clone())
     }
 
+    pub(in crate::store) fn record_run_conflict_handoff(
+        &self,
+        run_id: &str,
+        handoff: &TaskRunConflictHandoff,
+    ) -> Option<TaskRunRecord> {
+        let mut data = self.inner.write();
+        let run = data.runs.get_mut(run_id)?;
+        let execution = run.workspace_execution.as_mut()?;
+        if run.status != TaskRunStatus::Blocked
+            || execution.integration_status != WorkspaceIntegrationStatus::Conflict
+            || execution.conflict_handoff.is_some()
+        {
+            return None;
+        }
+        execution.conflict_handoff = Some(handoff.clone());
+        run.updated_at = now_rfc3339();
+        Some(run.clone())
+    }
+
     pub(in crate::store) fn waive_run_workspace_integration(
         &self,
         run_id: &str,
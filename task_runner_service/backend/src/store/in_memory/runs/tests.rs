@@ -31,6 +31,7 @@ fn queued_run() -> TaskRunRecord {
         result_summary: None,
         error_message: None,
         usage: None,
+        spend: None,
         report: None,
         cancel_requested: false,
         cancel_event_pending: false,
@@ -92,6 +93,42 @@ fn execution_stats_count_runs_and_pending_outboxes_without_cloning_records() {
     assert_eq!(stats.post_process_outbox_pending, 1);
 }
 
+#[test]
+fn run_spend_accumulates_and_survives_a_stale_run_save() {
+    let store = test_store();
+    let stale = store.save_run(queued_run()).expect("save queued run");
+    let mut other = queued_run();
+    other.id = "run-2".to_string();
+    other.task_id = "task-2".to_string();
+    store.save_run(other).expect("save second run");
+
+    let step = UsageTotals {
+        requests: 1,
+        input_tokens: 100,
+        output_tokens: 20,
+        cost_usd: 0.25,
+        ..UsageTotals::default()
+    };
+    store.record_run_spend("run-1", "project-a", Some("user-1"), &step);
+    let spend = store
+        .record_run_spend("run-1", "project-a", None, &step)
+        .expect("run spend");
+    store.record_run_spend("run-2", "project-a", Some("user-2"), &step);
+    store.save_run(stale).expect("save stale run copy");
+
+    assert_eq!(spend.usage.requests, 2);
+    assert_eq!(spend.owner_user_id.as_deref(), Some("user-1"));
+    assert_eq!(store.get_run("run-1").unwrap().spend, Some(spend));
+    let projects = store.list_run_spend(RunSpendGroup::Project, None);
+    assert_eq!(projects.len(), 1);
+    assert_eq!(projects[0].run_count, 2);
+    assert_eq!(projects[0].usage.input_tokens, 300);
+    assert!((projects[0].usage.cost_usd - 0.75).abs() < 1e-9);
+    let owner = store.list_run_spend(RunSpendGroup::Owner, Some("user-2"));
+    assert_eq!(owner.len(), 1);
+    assert_eq!(owner[0].usage.requests, 1);
+}
+
 #[test]
 fn running_run_initializes_a_durable_started_callback_without_terminal_delivery() {
     let store = test_store();
@@ -177,6 +177,7 @@ mod tests {
                 interval_seconds: Some(60),
                 next_run_at: Some(next_run_at.to_string()),
                 last_scheduled_at: None,
+                ..TaskScheduleConfig::default()
             },
             parent_task_id: None,
             source_run_id: None,
@@ -203,6 +204,7 @@ mod tests {
             interval_seconds: Some(60),
             next_run_at: Some("2026-01-01T00:01:00Z".to_string()),
             last_scheduled_at: Some("2026-01-01T00:00:00Z".to_string()),
+            ..TaskScheduleConfig::default()
         };
         let second_schedule = TaskScheduleConfig {
             mode: TaskScheduleMode::Interval,
@@ -210,6 +212,7 @@ mod tests {
             interval_seconds: Some(60),
             next_run_at: Some("2026-01-01T00:02:00Z".to_string()),
             last_scheduled_at: Some("2026-01-01T00:00:00Z".to_string()),
+            ..TaskScheduleConfig::default()
         };
 
         let first = store.update_task_schedule_if_next_run_at(
@@ -4,6 +4,64 @@
 use super::*;
 
 impl MongoStore {
+    pub(in crate::store) async fn list_run_spend(
+        &self,
+        group: RunSpendGroup,
+        scope_id: Option<&str>,
+    ) -> Result<Vec<RunSpendSummaryRecord>, String> {
+        let key_field = match group {
+            RunSpendGroup::Project => "spend.project_id",
+            RunSpendGroup::Owner => "spend.owner_user_id",
+        };
+        let mut match_doc = doc! { key_field: { "$type": "string" } };
+        if let Some(scope_id) = scope_id {
+            match_doc.insert(key_field, scope_id);
+        }
+        let rows = self
+            .aggregate_documents(
+                &self.runs,
+                vec![
+                    doc! { "$match": match_doc },
+                    doc! {
+                        "$group": {
+                            "_id": format!("${key_field}"),
+                            "run_count": { "$sum": 1_i32 },
+                            "requests": { "$sum": "$spend.usage.requests" },
+                            "unpriced_requests": { "$sum": "$spend.usage.unpriced_requests" },
+                            "input_tokens": { "$sum": "$spend.usage.input_tokens" },
+                            "cached_input_tokens": { "$sum": "$spend.usage.cached_input_tokens" },
+                            "cache_write_input_tokens": { "$sum": "$spend.usage.cache_write_input_tokens" },
+                            "output_tokens": { "$sum": "$spend.usage.output_tokens" },
+                            "reasoning_tokens": { "$sum": "$spend.usage.reasoning_tokens" },
+                            "cost_usd": { "$sum": "$spend.usage.cost_usd" },
+                        }
+                    },
+                    doc! { "$sort": { "_id": 1 } },
+                ],
+            )
+            .await?;
+        let count = |row: &Document, field: &str| bson_usize_field(row, field).unwrap_or(0) as u64;
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(RunSpendSummaryRecord {
+                    scope_id: bson_string_field(&row, "_id")?,
+                    run_count: bson_usize_field(&row, "run_count").unwrap_or(0),
+                    usage: UsageTotals {
+                        requests: count(&row, "requests"),
+                        unpriced_requests: count(&row, "unpriced_requests"),
+                        input_tokens: count(&row, "input_tokens"),
+                        cached_input_tokens: count(&row, "cached_input_tokens"),
+                        cache_write_input_tokens: count(&row, "cache_write_input_tokens"),
+                        output_tokens: count(&row, "output_tokens"),
+                        reasoning_tokens: count(&row, "reasoning_tokens"),
+                        cost_usd: bson_f64_field(&row, "cost_usd").unwrap_or(0.0),
+                    },
+                })
+            })
+            .collect())
+    }
+
     pub(in crate::store) async fn run_execution_stats(&self) -> Result<RunExecutionStats, String> {
         let count_when = |condition: Document| {
             doc! {
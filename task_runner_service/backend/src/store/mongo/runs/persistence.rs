@@ -3,6 +3,21 @@
 
 use super::*;
 
+/// Replaces the stored run with `run` but keeps the stored `spend`. Spend is only ever added
+/// by `record_run_spend` with `$inc`, so persisting a stale copy must not roll it back.
+fn run_replacement_keeping_spend(run: &TaskRunRecord) -> Result<Vec<Document>, String> {
+    let mut document = bson::to_document(run).map_err(|err| err.to_string())?;
+    document.remove("spend");
+    Ok(vec![doc! {
+        "$replaceWith": {
+            "$mergeObjects": [
+                { "$literal": document },
+                { "_id": "$_id", "spend": "$spend" },
+            ]
+        }
+    }])
+}
+
 impl MongoStore {
     pub(in crate::store) async fn save_run(
         &self,
@@ -26,10 +41,11 @@ impl MongoStore {
                 run.cancel_requested = true;
                 run.cancel_event_pending |= current.cancel_event_pending;
             }
+            run.spend = current.spend;
             let persisted = prepare_run_for_claim_guarded_persist(run.clone());
             let result = self
                 .runs
-                .replace_one(filter, &persisted, None)
+                .update_one(filter, run_replacement_keeping_spend(&persisted)?, None)
                 .await
                 .map_err(|err| err.to_string())?;
             if result.matched_count == 0 {
@@ -49,10 +65,10 @@ impl MongoStore {
         }
         let run = prepare_run_for_claim_guarded_persist(run);
         self.runs
-            .replace_one(
+            .update_one(
                 doc! { "id": &run.id },
-                &run,
-                ReplaceOptions::builder().upsert(true).build(),
+                run_replacement_keeping_spend(&run)?,
+                UpdateOptions::builder().upsert(true).build(),
             )
             .await
             .map_err(|err| {
@@ -213,6 +229,44 @@ impl MongoStore {
             .map_err(|err| err.to_string())
     }
 
+    pub(in crate::store) async fn record_run_spend(
+        &self,
+        run_id: &str,
+        project_id: &str,
+        owner_user_id: Option<&str>,
+        usage: &UsageTotals,
+    ) -> Result<Option<TaskRunSpendRecord>, String> {
+        let count = |value: u64| i64::try_from(value).unwrap_or(i64::MAX);
+        let mut set_doc = doc! {
+            "spend.project_id": project_id,
+            "updated_at": now_rfc3339(),
+        };
+        if let Some(owner_user_id) = owner_user_id {
+            set_doc.insert("spend.owner_user_id", owner_user_id);
+        }
+        self.runs
+            .update_one(
+                doc! { "id": run_id },
+                doc! {
+                    "$set": set_doc,
+                    "$inc": {
+                        "spend.usage.requests": count(usage.requests),
+                        "spend.usage.unpriced_requests": count(usage.unpriced_requests),
+                        "spend.usage.input_tokens": count(usage.input_tokens),
+                        "spend.usage.cached_input_tokens": count(usage.cached_input_tokens),
+                        "spend.usage.cache_write_input_tokens": count(usage.cache_write_input_tokens),
+                        "spend.usage.output_tokens": count(usage.output_tokens),
+                        "spend.usage.reasoning_tokens": count(usage.reasoning_tokens),
+                        "spend.usage.cost_usd": usage.cost_usd,
+                    },
+                },
+                None,
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        Ok(self.get_run(run_id).await?.and_then(|run| run.spend))
+    }
+
     pub(in crate::store) async fn mark_run_chatos_followup_processed(
         &self,
         run_id: &str,
@@ -355,6 +409,7 @@ impl MongoStore {
                         "workspace_execution.integrated_at": "",
                         "workspace_execution.conflict_files": "",
                         "workspace_execution.conflict_message": "",
+                        "workspace_execution.conflict_handoff": "",
                         "workspace_execution.integration_last_error": "",
                     },
                 },
@@ -368,6 +423,37 @@ impl MongoStore {
         self.get_run(run_id).await
     }
 
+    pub(in crate::store) async fn record_run_conflict_handoff(
+        &self,
+        run_id: &str,
+        handoff: &TaskRunConflictHandoff,
+    ) -> Result<Option<TaskRunRecord>, String> {
+        let handoff = mongodb::bson::to_bson(handoff).map_err(|err| err.to_string())?;
+        let result = self
+            .runs
+            .update_one(
+                doc! {
+                    "id": run_id,
+                    "status": "blocked",
+                    "workspace_execution.integration_status": "conflict",
+                    "workspace_execution.conflict_handoff": null,
+                },
+                doc! {
+                    "$set": {
+                        "workspace_execution.conflict_handoff": handoff,
+                        "updated_at": now_rfc3339(),
+                    },
+                },
+                None,
+            )
+            .await
+            .map_err(|err| err.to_string())?;
+        if result.modified_count == 0 {
+            return Ok(None);
+        }
+        self.get_run(run_id).await
+    }
+
     pub(in crate::store) async fn waive_run_workspace_integration(
         &self,
         run_id: &str,
@@ -415,3 +501,119 @@ impl MongoStore {
         self.get_run(run_id).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claimed_run(id: &str) -> TaskRunRecord {
+        let now = now_rfc3339();
+        TaskRunRecord {
+            id: id.to_string(),
+            task_id: format!("{id}-task"),
+            agent_run_id: None,
+            agent_ordering_lane_key: None,
+            agent_lane_seq: None,
+            execution_lane_key: None,
+            model_config_id: "model-1".to_string(),
+            memory_thread_id: "thread-1".to_string(),
+            status: TaskRunStatus::Running,
+            model_phase_status: crate::models::ModelPhaseStatus::Running,
+            started_at: Some(now.clone()),
+            finished_at: None,
+            input_snapshot: serde_json::json!({}),
+            effective_tools: Default::default(),
+            workspace_execution: None,
+            mcp_runtime_session_ref: None,
+            context_snapshot: None,
+            result_summary: None,
+            error_message: None,
+            usage: None,
+            spend: None,
+            report: None,
+            cancel_requested: false,
+            cancel_event_pending: false,
+            dispatch_paused: false,
+            dispatch_event_pending: false,
+            post_process_event_pending: false,
+            post_process_event_enqueued: false,
+            post_process_completed: false,
+            post_process_dead_lettered: false,
+            post_process_attempt_count: 0,
+            post_process_last_error: None,
+            memory_summary_processed: false,
+            chatos_followup_processed: false,
+            summary_job_run_id: None,
+            worker_id: Some("worker-1".to_string()),
+            claim_token: Some("claim-1".to_string()),
+            claim_until: None,
+            attempt: 1,
+            attempts: Vec::new(),
+            chatos_started_callback_delivery: None,
+            chatos_callback_delivery: None,
+            created_at: now.clone(),
+            updated_at: now,
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a MongoDB instance in TASK_RUNNER_TEST_MONGODB_URI"]
+    async fn stale_run_saves_keep_recorded_spend() {
+        let uri = std::env::var("TASK_RUNNER_TEST_MONGODB_URI")
+            .expect("TASK_RUNNER_TEST_MONGODB_URI must name a test database");
+        let (sender, _) = broadcast::channel(16);
+        let store = MongoStore::connect(uri.as_str(), sender)
+            .await
+            .expect("connect to MongoDB");
+        let run_id = format!("spend-test-{}", uuid::Uuid::new_v4());
+        let stale = claimed_run(run_id.as_str());
+        store
+            .runs
+            .insert_one(&stale, None)
+            .await
+            .expect("insert claimed run");
+
+        let step = UsageTotals {
+            requests: 1,
+            input_tokens: 100,
+            output_tokens: 20,
+            cost_usd: 0.25,
+            ..UsageTotals::default()
+        };
+        store
+            .record_run_spend(run_id.as_str(), "project-a", Some("user-1"), &step)
+            .await
+            .expect("record spend");
+        let spend = store
+            .record_run_spend(run_id.as_str(), "project-a", None, &step)
+            .await
+            .expect("record spend")
+            .expect("run spend");
+
+        let mut completed = stale.clone();
+        completed.status = TaskRunStatus::Succeeded;
+        let saved = store.save_run(completed).await.expect("save claimed run");
+        assert_eq!(saved.spend.as_ref(), Some(&spend));
+        let mut released = stale;
+        released.claim_token = None;
+        released.worker_id = None;
+        store.save_run(released).await.expect("save released run");
+
+        let stored = store
+            .get_run(run_id.as_str())
+            .await
+            .expect("load run")
+            .expect("stored run");
+        store
+            .runs
+            .delete_one(doc! { "id": run_id.as_str() }, None)
+            .await
+            .expect("clean up run");
+        assert_eq!(stored.spend, Some(spend));
+        assert_eq!(
+            stored.spend.as_ref().map(|spend| spend.usage.requests),
+            Some(2)
+        );
+        assert!(stored.claim_token.is_none());
+    }
+}
@@ -253,3 +253,12 @@ pub(super) fn bson_usize_field(doc: &Document, field: &str) -> Option<usize> {
         _ => None,
     }
 }
+
+pub(super) fn bson_f64_field(doc: &Document, field: &str) -> Option<f64> {
+    match doc.get(field) {
+        Some(Bson::Int32(value)) => Some(f64::from(*value)),
+        Some(Bson::Int64(value)) => Some(*value as f64),
+        Some(Bson::Double(value)) => Some(*value),
+        _ => None,
+    }
+}
@@ -131,10 +131,15 @@ export const enUS: MessageDictionary = {
   'tasks.schedule.manual': 'Manual',
   'tasks.schedule.once': 'Run once',
   'tasks.schedule.interval': 'Recurring',
+  'tasks.schedule.cron': 'Cron',
   'tasks.schedule.contactAsync': 'Contact async',
   'tasks.schedule.manualDescription': 'The scheduler will not trigger it automatically. It only runs when manually started or called by API.',
   'tasks.schedule.onceDescription': 'Run once at the specified time. It will not continue scheduling afterwards.',
   'tasks.schedule.intervalDescription': 'Start at the first run time, then continue on the interval. Minimum interval is 60 seconds.',
+  'tasks.schedule.cronDescription': 'Run on a cron expression in the local time of the chosen time zone, with optional blackout windows, jitter and a missed-slot policy.',
+  'tasks.schedule.missedSkip': 'Skip missed slots',
+  'tasks.schedule.missedRunOnce': 'Run once',
+  'tasks.schedule.missedCatchUp': 'Catch up (at most 24)',
   'tasks.schedule.contactAsyncDescription': 'Reserved for async contact tasks. It can only be picked up by the background scheduler and cannot be started manually.',
   'tasks.schedule.next': 'next',
   'tasks.schedule.nextAt': 'next {time}',
@@ -230,6 +235,15 @@ export const enUS: MessageDictionary = {
   'tasks.form.intervalSeconds': 'Interval (seconds)',
   'tasks.form.intervalRequired': 'Enter an interval',
   'tasks.form.intervalMin': 'Interval must be at least 60 seconds',
+  'tasks.form.cronExpression': 'Cron expression',
+  'tasks.form.cronExpressionHelp': 'minute hour day month weekday, e.g. 0 3 * * mon-fri; aliases such as @daily and @hourly also work',
+  'tasks.form.cronExpressionRequired': 'Enter a cron expression',
+  'tasks.form.timezone': 'Time zone',
+  'tasks.form.blackoutWindows': 'Blackout windows',
+  'tasks.form.blackoutWindowsHelp': 'Separate with semicolons, e.g. sat,sun 00:00-24:00; 22:00-06:00, in the schedule time zone',
+  'tasks.form.blackoutWindowsInvalid': 'Invalid blackout window format',
+  'tasks.form.jitterSeconds': 'Jitter (seconds)',
+  'tasks.form.missedSlotPolicy': 'Missed slots',
   'tasks.form.builtinMcp': 'MCP tools',
   'tasks.form.mcpProgramManaged': 'MCPs are selected by the Agent within strict Binding limits',
   'tasks.form.mcpProgramManagedHelp': 'Plugin Management Agent Binding is the only capability configuration source. The creating Agent selects the minimum MCP subset needed by this task, Task Runner validates and persists it, and required capabilities are added automatically. Provider, local or cloud execution, device, and workspace remain project-owned and are frozen before the run.',
@@ -131,10 +131,15 @@ export const zhCN: MessageDictionary = {
   'tasks.schedule.manual': '手动执行',
   'tasks.schedule.once': '定时一次',
   'tasks.schedule.interval': '周期执行',
+  'tasks.schedule.cron': 'Cron 调度',
   'tasks.schedule.contactAsync': '联系人异步',
   'tasks.schedule.manualDescription': '不会被后台调度器自动触发，只在手动点击运行或接口主动调用时执行。',
   'tasks.schedule.onceDescription': '在指定执行时间自动运行一次，运行后不会继续调度。',
   'tasks.schedule.intervalDescription': '从首次执行时间开始自动运行，并按循环间隔持续调度，最小间隔 60 秒。',
+  'tasks.schedule.cronDescription': '按 cron 表达式在指定时区的本地时间运行，可设置禁止运行时段、随机延迟和错过时段的处理方式。',
+  'tasks.schedule.missedSkip': '跳过错过的时段',
+  'tasks.schedule.missedRunOnce': '补跑一次',
+  'tasks.schedule.missedCatchUp': '逐个补跑（最多 24 次）',
   'tasks.schedule.contactAsyncDescription': '仅用于联系人异步任务。创建后只会由后台调度器扫描并触发，不能手动运行。',
   'tasks.schedule.next': '下次',
   'tasks.schedule.nextAt': '下次 {time}',
@@ -230,6 +235,15 @@ export const zhCN: MessageDictionary = {
   'tasks.form.intervalSeconds': '循环间隔（秒）',
   'tasks.form.intervalRequired': '请输入循环间隔',
   'tasks.form.intervalMin': '循环间隔至少 60 秒',
+  'tasks.form.cronExpression': 'Cron 表达式',
+  'tasks.form.cronExpressionHelp': '分 时 日 月 周，例如 0 3 * * mon-fri；也支持 @daily、@hourly 等别名',
+  'tasks.form.cronExpressionRequired': '请输入 cron 表达式',
+  'tasks.form.timezone': '时区',
+  'tasks.form.blackoutWindows': '禁止运行时段',
+  'tasks.form.blackoutWindowsHelp': '用分号分隔，例如 sat,sun 00:00-24:00; 22:00-06:00；按调度时区计算',
+  'tasks.form.blackoutWindowsInvalid': '禁止运行时段格式不正确',
+  'tasks.form.jitterSeconds': '随机延迟（秒）',
+  'tasks.form.missedSlotPolicy': '错过时段的处理',
   'tasks.form.builtinMcp': 'MCP 工具',
   'tasks.form.mcpProgramManaged': 'MCP 由 Agent 动态选择并受 Binding 严格约束',
   'tasks.form.mcpProgramManagedHelp': 'Plugin Management 的 Agent Binding 是唯一能力配置来源。创建任务的 Agent 只能从 Binding 允许的 MCP 中选择本任务所需的最小集合，Task Runner 会在创建时校验并固化；必选能力由系统补齐。Provider、本地或云端执行位置、设备和工作区仍只由项目上下文决定，并在运行前冻结。',
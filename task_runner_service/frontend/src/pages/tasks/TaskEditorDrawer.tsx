@@ -18,8 +18,10 @@ import {
 } from 'antd';
 
 import type { TranslateFn } from '../../i18n/I18nProvider';
-import type { TaskRecord, TaskScheduleMode } from '../../types';
+import type { TaskRecord, TaskScheduleMissedSlotPolicy, TaskScheduleMode } from '../../types';
 import {
+  missedSlotPolicyLabelKeys,
+  parseBlackoutWindows,
   scheduleModeDescriptionKeys,
   scheduleModeLabelKeys,
   taskProfileColorMap,
@@ -63,7 +65,7 @@ export function TaskEditorDrawer({
   const effectiveScheduleMode = scheduleMode ?? 'manual';
   const scheduleModeLabels = useMemo(
     () => Object.fromEntries(
-      (['manual', 'once', 'interval', 'contact_async'] as TaskScheduleMode[]).map(
+      (['manual', 'once', 'interval', 'cron', 'contact_async'] as TaskScheduleMode[]).map(
         (value) => [value, t(scheduleModeLabelKeys[value])],
       ),
     ) as Record<TaskScheduleMode, string>,
@@ -71,14 +73,14 @@ export function TaskEditorDrawer({
   );
   const scheduleModeDescriptions = useMemo(
     () => Object.fromEntries(
-      (['manual', 'once', 'interval', 'contact_async'] as TaskScheduleMode[]).map(
+      (['manual', 'once', 'interval', 'cron', 'contact_async'] as TaskScheduleMode[]).map(
         (value) => [value, t(scheduleModeDescriptionKeys[value])],
       ),
     ) as Record<TaskScheduleMode, string>,
     [t],
   );
   const scheduleModeOptions = useMemo(
-    () => (['manual', 'once', 'interval', 'contact_async'] as TaskScheduleMode[]).map(
+    () => (['manual', 'once', 'interval', 'cron', 'contact_async'] as TaskScheduleMode[]).map(
       (value) => ({
         label: scheduleModeLabels[value],
         value,
@@ -87,6 +89,12 @@ export function TaskEditorDrawer({
     ),
     [scheduleModeLabels],
   );
+  const missedSlotPolicyOptions = useMemo(
+    () => (['run_once', 'skip', 'catch_up'] as TaskScheduleMissedSlotPolicy[]).map(
+      (value) => ({ label: t(missedSlotPolicyLabelKeys[value]), value }),
+    ),
+    [t],
+  );
   const taskStatusOptions = useMemo(
     () => taskStatusValues.map((value) => ({
       label: t(`tasks.status.${value}`),
@@ -250,7 +258,7 @@ export function TaskEditorDrawer({
             <Select options={scheduleModeOptions} />
           </Form.Item>
 
-          {effectiveScheduleMode !== 'manual' ? (
+          {effectiveScheduleMode !== 'manual' && effectiveScheduleMode !== 'cron' ? (
             <Form.Item
               name="scheduleRunAt"
               label={
@@ -290,6 +298,48 @@ export function TaskEditorDrawer({
               <InputNumber style={{ width: '100%' }} min={60} step={60} />
             </Form.Item>
           ) : null}
+
+          {effectiveScheduleMode === 'cron' ? (
+            <>
+              <Form.Item
+                name="scheduleCronExpression"
+                label={t('tasks.form.cronExpression')}
+                extra={t('tasks.form.cronExpressionHelp')}
+                rules={[{ required: true, message: t('tasks.form.cronExpressionRequired') }]}
+              >
+                <Input placeholder="0 3 * * mon-fri" />
+              </Form.Item>
+              <Form.Item name="scheduleTimezone" label={t('tasks.form.timezone')}>
+                <Input placeholder="Asia/Shanghai" />
+              </Form.Item>
+            </>
+          ) : null}
+
+          {effectiveScheduleMode === 'interval' || effectiveScheduleMode === 'cron' ? (
+            <>
+              <Form.Item
+                name="scheduleBlackoutWindowsText"
+                label={t('tasks.form.blackoutWindows')}
+                extra={t('tasks.form.blackoutWindowsHelp')}
+                rules={[{
+                  validator: async (_, value) => {
+                    if (parseBlackoutWindows(value)) {
+                      return;
+                    }
+                    throw new Error(t('tasks.form.blackoutWindowsInvalid'));
+                  },
+                }]}
+              >
+                <Input placeholder="sat,sun 00:00-24:00; 22:00-06:00" />
+              </Form.Item>
+              <Form.Item name="scheduleJitterSeconds" label={t('tasks.form.jitterSeconds')}>
+                <InputNumber style={{ width: '100%' }} min={0} max={3600} step={30} />
+              </Form.Item>
+              <Form.Item name="scheduleMissedSlotPolicy" label={t('tasks.form.missedSlotPolicy')}>
+                <Select options={missedSlotPolicyOptions} />
+              </Form.Item>
+            </>
+          ) : null}
         </Form>
       ) : null}
     </Drawer>
@@ -9,7 +9,9 @@ import type {
   TaskRecord,
   TaskRunRecord,
   TaskProfile,
+  TaskScheduleBlackoutWindow,
   TaskScheduleConfig,
+  TaskScheduleMissedSlotPolicy,
   TaskScheduleMode,
   TaskStatus,
   AskUserPromptStatus,
@@ -28,6 +30,11 @@ export type TaskFormValues = {
   scheduleMode: TaskScheduleMode;
   scheduleRunAt?: string;
   scheduleIntervalSeconds?: number;
+  scheduleCronExpression?: string;
+  scheduleTimezone?: string;
+  scheduleBlackoutWindowsText?: string;
+  scheduleJitterSeconds?: number;
+  scheduleMissedSlotPolicy?: TaskScheduleMissedSlotPolicy;
 };
 
 export type RunTaskFormValues = {
@@ -48,6 +55,11 @@ export function buildEditTaskFormValues(task: TaskRecord): TaskFormValues {
     scheduleMode: task.schedule.mode,
     scheduleRunAt: formatScheduleInput(task.schedule.run_at ?? task.schedule.next_run_at),
     scheduleIntervalSeconds: task.schedule.interval_seconds || undefined,
+    scheduleCronExpression: task.schedule.cron_expression || undefined,
+    scheduleTimezone: task.schedule.timezone || undefined,
+    scheduleBlackoutWindowsText: formatBlackoutWindows(task.schedule.blackout_windows),
+    scheduleJitterSeconds: task.schedule.jitter_seconds || undefined,
+    scheduleMissedSlotPolicy: task.schedule.missed_slot_policy || 'run_once',
   };
 }
 
@@ -137,6 +149,7 @@ export const scheduleModeLabelKeys: Record<TaskScheduleMode, string> = {
   manual: 'tasks.schedule.manual',
   once: 'tasks.schedule.once',
   interval: 'tasks.schedule.interval',
+  cron: 'tasks.schedule.cron',
   contact_async: 'tasks.schedule.contactAsync',
 };
 
@@ -144,9 +157,16 @@ export const scheduleModeDescriptionKeys: Record<TaskScheduleMode, string> = {
   manual: 'tasks.schedule.manualDescription',
   once: 'tasks.schedule.onceDescription',
   interval: 'tasks.schedule.intervalDescription',
+  cron: 'tasks.schedule.cronDescription',
   contact_async: 'tasks.schedule.contactAsyncDescription',
 };
 
+export const missedSlotPolicyLabelKeys: Record<TaskScheduleMissedSlotPolicy, string> = {
+  skip: 'tasks.schedule.missedSkip',
+  run_once: 'tasks.schedule.missedRunOnce',
+  catch_up: 'tasks.schedule.missedCatchUp',
+};
+
 export const promptStatusColorMap: Record<AskUserPromptStatus, string> = {
   pending: 'processing',
   submitted: 'success',
@@ -247,6 +267,29 @@ export function buildSchedulePayload(values: TaskFormValues): TaskScheduleConfig
     };
   }
 
+  const blackoutWindows = parseBlackoutWindows(values.scheduleBlackoutWindowsText);
+  if (!blackoutWindows) {
+    return null;
+  }
+  const recurrenceRules = {
+    timezone: values.scheduleTimezone?.trim() || null,
+    blackout_windows: blackoutWindows,
+    jitter_seconds: values.scheduleJitterSeconds || null,
+    missed_slot_policy: values.scheduleMissedSlotPolicy || 'run_once',
+  };
+
+  if (values.scheduleMode === 'cron') {
+    const cronExpression = values.scheduleCronExpression?.trim();
+    if (!cronExpression) {
+      return null;
+    }
+    return {
+      mode: 'cron',
+      cron_expression: cronExpression,
+      ...recurrenceRules,
+    };
+  }
+
   const runAtInput = values.scheduleRunAt?.trim();
   if (!runAtInput) {
     return null;
@@ -278,9 +321,41 @@ export function buildSchedulePayload(values: TaskFormValues): TaskScheduleConfig
     mode: 'interval',
     run_at: runAt.toISOString(),
     interval_seconds: values.scheduleIntervalSeconds,
+    ...recurrenceRules,
   };
 }
 
+// Blackout windows are edited as `sat,sun 00:00-24:00; 22:00-06:00`.
+const blackoutWindowPattern = /^(?:([a-z,]+)\s+)?(\d{2}:\d{2})-(\d{2}:\d{2})$/i;
+
+export function parseBlackoutWindows(text?: string): TaskScheduleBlackoutWindow[] | null {
+  const windows: TaskScheduleBlackoutWindow[] = [];
+  for (const item of (text || '').split(';').map((value) => value.trim()).filter(Boolean)) {
+    const match = blackoutWindowPattern.exec(item);
+    if (!match) {
+      return null;
+    }
+    windows.push({
+      weekdays: match[1] ? match[1].split(',').filter(Boolean) : [],
+      start: match[2],
+      end: match[3],
+    });
+  }
+  return windows;
+}
+
+export function formatBlackoutWindows(windows?: TaskScheduleBlackoutWindow[]): string | undefined {
+  if (!windows?.length) {
+    return undefined;
+  }
+  return windows
+    .map((window) => {
+      const days = window.weekdays?.length ? `${window.weekdays.join(',')} ` : '';
+      return `${days}${window.start}-${window.end}`;
+    })
+    .join('; ');
+}
+
 export function formatScheduleInput(value?: string | null): string | undefined {
   if (!value) {
     return undefined;
@@ -308,6 +383,9 @@ export function describeTaskSchedule(schedule: TaskScheduleConfig, t: TranslateF
   if (schedule.interval_seconds) {
     parts.push(t('tasks.schedule.everySeconds', { seconds: schedule.interval_seconds }));
   }
+  if (schedule.cron_expression) {
+    parts.push(`${schedule.cron_expression} (${schedule.timezone || 'UTC'})`);
+  }
   return parts.join(' / ');
 }
 
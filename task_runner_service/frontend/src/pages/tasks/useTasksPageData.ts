@@ -93,7 +93,7 @@ export function useTasksPageData({
   const scheduleModeLabels = useMemo(
     () =>
       Object.fromEntries(
-        (['manual', 'once', 'interval', 'contact_async'] as TaskScheduleMode[]).map((value) => [
+        (['manual', 'once', 'interval', 'cron', 'contact_async'] as TaskScheduleMode[]).map((value) => [
           value,
           t(scheduleModeLabelKeys[value]),
         ]),
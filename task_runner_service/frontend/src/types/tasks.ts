@@ -14,7 +14,9 @@ export type TaskStatus =
 
 export type TaskMcpInitMode = 'full' | 'disabled';
 export type TaskBuiltinPromptMode = 'configured' | 'effective';
-export type TaskScheduleMode = 'manual' | 'once' | 'interval' | 'contact_async';
+export type TaskScheduleMode = 'manual' | 'once' | 'interval' | 'cron' | 'contact_async';
+
+export type TaskScheduleMissedSlotPolicy = 'skip' | 'run_once' | 'catch_up';
 export type TaskProcessLogOperation = 'append' | 'replace' | 'clear';
 export type TaskProfile = 'default' | 'chatos_plan';
 export type TaskProjectStatus = 'active' | 'archived';
@@ -134,6 +136,18 @@ export interface TaskScheduleConfig {
   interval_seconds?: number | null;
   next_run_at?: string | null;
   last_scheduled_at?: string | null;
+  cron_expression?: string | null;
+  timezone?: string | null;
+  blackout_windows?: TaskScheduleBlackoutWindow[];
+  jitter_seconds?: number | null;
+  missed_slot_policy?: TaskScheduleMissedSlotPolicy;
+  slot_at?: string | null;
+}
+
+export interface TaskScheduleBlackoutWindow {
+  weekdays?: string[];
+  start: string;
+  end: string;
 }
 
 export interface TaskToolOutcomeItem {